@@ -1040,6 +1040,8 @@ async def get_box_metrics(
         "boot_timing": {
             "total_create_ms": m.total_create_duration_ms,
             "guest_boot_ms": m.guest_boot_duration_ms,
+            "guest_kernel_boot_ms": m.guest_kernel_boot_ms,
+            "guest_agent_init_ms": m.guest_agent_init_ms,
             "filesystem_setup_ms": m.stage_filesystem_setup_ms,
             "image_prepare_ms": m.stage_image_prepare_ms,
             "guest_rootfs_ms": m.stage_guest_rootfs_ms,
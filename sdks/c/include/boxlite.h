@@ -62,6 +62,8 @@ typedef enum BoxliteErrorCode {
   // Interactive execution session was reaped server-side after disconnect.
   // Reattach is no longer possible — start a new exec.
   SessionReaped = 21,
+  // Operation exceeded its deadline (exec timeout, boot timeout, RPC deadline)
+  Timeout = 22,
 } BoxliteErrorCode;
 
 // Transport protocol for a port forwarding rule.
@@ -75,6 +77,14 @@ typedef enum BoxliteRegistryTransport {
   BoxliteRegistryTransportHttp = 1,
 } BoxliteRegistryTransport;
 
+// Stage of an image pull.
+typedef enum BoxlitePullPhase {
+  BoxlitePullPhaseManifest = 0,
+  BoxlitePullPhaseDownloading = 1,
+  BoxlitePullPhaseExtracting = 2,
+  BoxlitePullPhaseDone = 3,
+} BoxlitePullPhase;
+
 // Opaque handle wrapping an `AdvancedBoxOptions`. Allocated via
 // `boxlite_advanced_options_new`, freed via `boxlite_advanced_options_free`.
 typedef struct AdvancedBoxOptionsHandle AdvancedBoxOptionsHandle;
@@ -147,6 +157,9 @@ typedef void (*CBoxGetBoxCb)(CBoxHandle*, CBoxliteError*, void*);
 // Box remove completion.
 typedef void (*CBoxRemoveBoxCb)(CBoxliteError*, void*);
 
+// Box rename completion.
+typedef void (*CBoxRenameBoxCb)(CBoxliteError*, void*);
+
 // Box start completion.
 typedef void (*CBoxStartBoxCb)(CBoxliteError*, void*);
 
@@ -226,6 +239,23 @@ typedef struct CImagePullResult {
 // Image pull completion.
 typedef void (*CBoxImagePullCb)(struct CImagePullResult*, CBoxliteError*, void*);
 
+// One image pull progress update. Borrowed for the duration of the
+// progress callback only; copy anything needed afterwards.
+//
+// - `layer_digest`: NULL for image-wide phases.
+// - `total_bytes`: meaningful only when `has_total` is 1.
+typedef struct CPullProgress {
+  const char *layer_digest;
+  uint64_t bytes_downloaded;
+  uint64_t total_bytes;
+  int has_total;
+  enum BoxlitePullPhase phase;
+} CPullProgress;
+
+// Image pull progress update (streaming; the `CPullProgress` is borrowed
+// for the duration of the call).
+typedef void (*CBoxPullProgressCb)(const struct CPullProgress*, void*);
+
 typedef struct CImageInfo {
   char *reference;
   char *repository;
@@ -254,6 +284,10 @@ typedef struct CBoxInfo {
   int cpus;
   int memory_mib;
   int64_t created_at;
+  // Labels as a JSON object, e.g. `{"team":"infra"}`.
+  char *labels;
+  // Port mappings as a JSON array, e.g. `[{"host_port":8080,"guest_port":80}]`.
+  char *ports;
 } CBoxInfo;
 
 // Box info completion.
@@ -280,6 +314,8 @@ typedef struct CBoxMetrics {
   int64_t network_bytes_received;
   int network_tcp_connections;
   int network_tcp_errors;
+  int64_t disk_bytes_used;
+  int64_t disk_bytes_total;
 } CBoxMetrics;
 
 // Per-box metrics completion.
@@ -291,6 +327,7 @@ typedef struct CRuntimeMetrics {
   int num_running_boxes;
   int total_commands_executed;
   int total_exec_errors;
+  int guest_connections_total;
 } CRuntimeMetrics;
 
 // Runtime metrics completion.
@@ -375,6 +412,17 @@ enum BoxliteErrorCode boxlite_remove(CBoxliteRuntime *runtime,
                                      void *user_data,
                                      CBoxliteError *out_error);
 
+// Rename a box, found by ID or name, to `new_name`.
+//
+// Fails with `AlreadyExists` if another box has `new_name`, and with
+// `InvalidArgument` if it is empty or contains `/`.
+enum BoxliteErrorCode boxlite_rename(CBoxliteRuntime *runtime,
+                                     const char *id_or_name,
+                                     const char *new_name,
+                                     CBoxRenameBoxCb cb,
+                                     void *user_data,
+                                     CBoxliteError *out_error);
+
 enum BoxliteErrorCode boxlite_start_box(CBoxHandle *handle,
                                         CBoxStartBoxCb cb,
                                         void *user_data,
@@ -470,6 +518,7 @@ enum BoxliteErrorCode boxlite_simple_new(const char *image,
                                          CBoxliteSimple **out_box,
                                          CBoxliteError *out_error);
 
+// Run a command in the box, reusing the runner's warm guest session.
 enum BoxliteErrorCode boxlite_simple_run(CBoxliteSimple *box_runner,
                                          const char *command,
                                          const char *const *args,
@@ -477,6 +526,15 @@ enum BoxliteErrorCode boxlite_simple_run(CBoxliteSimple *box_runner,
                                          CBoxliteExecResult **out_result,
                                          CBoxliteError *out_error);
 
+// Number of guest connections the runner has established.
+//
+// Stays at 1 across any number of `boxlite_simple_run` calls while the box
+// is healthy; useful for verifying session reuse.
+enum BoxliteErrorCode boxlite_simple_guest_connections(CBoxliteSimple *box_runner,
+                                                       int *out_count,
+                                                       CBoxliteError *out_error);
+
+// Stop and remove the box, tearing down its guest session.
 void boxlite_simple_free(CBoxliteSimple *box_runner);
 
 void boxlite_result_free(CBoxliteExecResult *result);
@@ -487,6 +545,16 @@ enum BoxliteErrorCode boxlite_image_pull(CBoxliteImageHandle *handle,
                                          void *user_data,
                                          CBoxliteError *out_error);
 
+// Pull an image, calling `progress_cb` with each progress update and `cb`
+// once when the pull finishes. Both fire on the drain thread; every
+// progress update is dispatched before `cb`.
+enum BoxliteErrorCode boxlite_pull_with_progress(CBoxliteImageHandle *handle,
+                                                 const char *image_ref,
+                                                 CBoxPullProgressCb progress_cb,
+                                                 CBoxImagePullCb cb,
+                                                 void *user_data,
+                                                 CBoxliteError *out_error);
+
 enum BoxliteErrorCode boxlite_image_list(CBoxliteImageHandle *handle,
                                          CBoxImageListCb cb,
                                          void *user_data,
@@ -545,6 +613,9 @@ void boxlite_options_set_workdir(CBoxliteOptions *opts, const char *workdir);
 
 void boxlite_options_add_env(CBoxliteOptions *opts, const char *key, const char *val);
 
+// Attach a label to the box; shown in its info and usable for filtering.
+void boxlite_options_add_label(CBoxliteOptions *opts, const char *key, const char *val);
+
 void boxlite_options_add_volume(CBoxliteOptions *opts,
                                 const char *host_path,
                                 const char *guest_path,
@@ -18,8 +18,8 @@ use boxlite::litebox::LiteBox;
 
 use crate::error::{BoxliteErrorCode, FFIError, null_pointer_error, write_error};
 use crate::event_queue::{
-    CBoxCreateBoxCb, CBoxGetBoxCb, CBoxGetOrCreateBoxCb, CBoxRemoveBoxCb, CBoxStartBoxCb,
-    CBoxStopBoxCb, EventQueue, RuntimeEvent, push_event,
+    CBoxCreateBoxCb, CBoxGetBoxCb, CBoxGetOrCreateBoxCb, CBoxRemoveBoxCb, CBoxRenameBoxCb,
+    CBoxStartBoxCb, CBoxStopBoxCb, EventQueue, RuntimeEvent, push_event,
 };
 use crate::options::OptionsHandle;
 use crate::runtime::RuntimeHandle;
@@ -100,6 +100,22 @@ pub unsafe extern "C" fn boxlite_remove(
     remove_box(runtime, id_or_name, force != 0, cb, user_data, out_error)
 }
 
+/// Rename a box, found by ID or name, to `new_name`.
+///
+/// Fails with `AlreadyExists` if another box has `new_name`, and with
+/// `InvalidArgument` if it is empty or contains `/`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn boxlite_rename(
+    runtime: *mut CBoxliteRuntime,
+    id_or_name: *const c_char,
+    new_name: *const c_char,
+    cb: CBoxRenameBoxCb,
+    user_data: *mut c_void,
+    out_error: *mut CBoxliteError,
+) -> BoxliteErrorCode {
+    rename_box(runtime, id_or_name, new_name, cb, user_data, out_error)
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn boxlite_start_box(
     handle: *mut CBoxHandle,
@@ -375,6 +391,58 @@ unsafe fn remove_box(
     }
 }
 
+unsafe fn rename_box(
+    runtime: *mut RuntimeHandle,
+    id_or_name: *const c_char,
+    new_name: *const c_char,
+    cb: CBoxRenameBoxCb,
+    user_data: *mut c_void,
+    out_error: *mut FFIError,
+) -> BoxliteErrorCode {
+    unsafe {
+        if runtime.is_null() {
+            write_error(out_error, null_pointer_error("runtime"));
+            return BoxliteErrorCode::InvalidArgument;
+        }
+
+        let id_str = match c_str_to_string(id_or_name) {
+            Ok(s) => s,
+            Err(e) => {
+                write_error(out_error, e);
+                return BoxliteErrorCode::InvalidArgument;
+            }
+        };
+        let new_name = match c_str_to_string(new_name) {
+            Ok(s) => s,
+            Err(e) => {
+                write_error(out_error, e);
+                return BoxliteErrorCode::InvalidArgument;
+            }
+        };
+        let cb = crate::unwrap_cb_or_return!(cb, out_error);
+
+        let runtime_ref = &*runtime;
+        let runtime_clone = runtime_ref.runtime.clone();
+        let queue = runtime_ref.queue.clone();
+        let user_data_addr = user_data as usize;
+
+        runtime_ref.tokio_rt.spawn(async move {
+            let result = runtime_clone.rename(&id_str, &new_name).await;
+            push_event(
+                &queue,
+                RuntimeEvent::RenameBox {
+                    cb,
+                    user_data: user_data_addr,
+                    result,
+                },
+            )
+            .await;
+        });
+
+        BoxliteErrorCode::Ok
+    }
+}
+
 unsafe fn start_box(
     handle: *mut BoxHandle,
     cb: CBoxStartBoxCb,
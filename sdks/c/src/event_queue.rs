@@ -11,7 +11,7 @@ use std::sync::{Condvar, Mutex};
 
 use boxlite::BoxliteError;
 
-use crate::images::{CImageInfoList, CImagePullResult};
+use crate::images::{CImageInfoList, CImagePullResult, CPullProgress};
 use crate::info::{CBoxInfo, CBoxInfoList};
 use crate::metrics::{CBoxMetrics, CRuntimeMetrics};
 
@@ -106,12 +106,21 @@ pub(crate) type CBoxGetBoxFn =
 pub type CBoxRemoveBoxCb = Option<extern "C" fn(*mut crate::CBoxliteError, *mut c_void)>;
 pub(crate) type CBoxRemoveBoxFn = extern "C" fn(*mut crate::CBoxliteError, *mut c_void);
 
+/// Box rename completion.
+pub type CBoxRenameBoxCb = Option<extern "C" fn(*mut crate::CBoxliteError, *mut c_void)>;
+pub(crate) type CBoxRenameBoxFn = extern "C" fn(*mut crate::CBoxliteError, *mut c_void);
+
 /// Image pull completion.
 pub type CBoxImagePullCb =
     Option<extern "C" fn(*mut CImagePullResult, *mut crate::CBoxliteError, *mut c_void)>;
 pub(crate) type CBoxImagePullFn =
     extern "C" fn(*mut CImagePullResult, *mut crate::CBoxliteError, *mut c_void);
 
+/// Image pull progress update (streaming; the `CPullProgress` is borrowed
+/// for the duration of the call).
+pub type CBoxPullProgressCb = Option<extern "C" fn(*const CPullProgress, *mut c_void)>;
+pub(crate) type CBoxPullProgressFn = extern "C" fn(*const CPullProgress, *mut c_void);
+
 /// Image list completion.
 pub type CBoxImageListCb =
     Option<extern "C" fn(*mut CImageInfoList, *mut crate::CBoxliteError, *mut c_void)>;
@@ -252,7 +261,8 @@ impl<T> Drop for OwnedFfiPtr<T> {
 // ─── Event variants ────────────────────────────────────────────────────────
 //
 // Each async op produces exactly one of these events; streaming pumps
-// produce many `Stdout`/`Stderr` events plus a single `Exit` per execution.
+// produce many `Stdout`/`Stderr` events plus a single `Exit` per execution,
+// and progress-reporting pulls many `PullProgress` events before `ImagePull`.
 // `user_data` is stored as `usize` because raw `*mut c_void` is `!Send`;
 // it is cast back to `*mut c_void` at dispatch time.
 //
@@ -277,6 +287,11 @@ pub enum RuntimeEvent {
         user_data: usize,
         exit_code: i32,
     },
+    PullProgress {
+        cb: CBoxPullProgressFn,
+        user_data: usize,
+        progress: boxlite::PullProgress,
+    },
 
     /* Lifecycle */
     CreateBox {
@@ -309,6 +324,11 @@ pub enum RuntimeEvent {
         user_data: usize,
         result: Result<(), BoxliteError>,
     },
+    RenameBox {
+        cb: CBoxRenameBoxFn,
+        user_data: usize,
+        result: Result<(), BoxliteError>,
+    },
     ImagePull {
         cb: CBoxImagePullFn,
         user_data: usize,
@@ -1137,6 +1157,8 @@ mod owned_ffi_ptr_nested_leak_tests {
             cpus: 1,
             memory_mib: 256,
             created_at: 0,
+            labels: test_cstr("{}"),
+            ports: test_cstr("[]"),
         });
 
         let owned = OwnedFfiPtr::new_with(payload, crate::info::free_box_info_ptr);
@@ -1145,9 +1167,9 @@ mod owned_ffi_ptr_nested_leak_tests {
         let after = FREE_STR_CALLS.load(AtomicOrdering::SeqCst);
         assert_eq!(
             after - before,
-            4,
+            6,
             "OwnedFfiPtr<CBoxInfo>::drop reclaimed {} inner CStrings; \
-             expected 4 (id + name + image + status). Inner allocations leak.",
+             expected 6 (id + name + image + status + labels + ports). Inner allocations leak.",
             after - before
         );
     }
@@ -1204,6 +1226,8 @@ mod owned_ffi_ptr_nested_leak_tests {
             cpus: 2,
             memory_mib: 512,
             created_at: 0,
+            labels: test_cstr("{}"),
+            ports: test_cstr("[]"),
         }];
         let items_ptr = items_vec.as_mut_ptr();
         let items_len = items_vec.len();
@@ -1220,9 +1244,9 @@ mod owned_ffi_ptr_nested_leak_tests {
         let after = FREE_STR_CALLS.load(AtomicOrdering::SeqCst);
         assert_eq!(
             after - before,
-            4,
+            6,
             "OwnedFfiPtr<CBoxInfoList>::drop reclaimed {} inner CStrings; \
-             expected 4 (1 item × 4 fields). Inner allocations leak.",
+             expected 6 (1 item × 6 fields). Inner allocations leak.",
             after - before
         );
     }
@@ -1,4 +1,3 @@
-use futures::StreamExt;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_int};
 use std::ptr;
@@ -17,6 +16,10 @@ use crate::util::c_str_to_string;
 use crate::{CBoxliteError, CBoxliteExecResult, CBoxliteSimple};
 
 /// Opaque handle for Runner API (auto-manages runtime)
+///
+/// The box is started in `boxlite_simple_new` and its `LiteBox` is held for
+/// the runner's lifetime, so every `boxlite_simple_run` reuses the same warm
+/// guest session (one gRPC connection) instead of re-resolving the box.
 pub struct BoxRunner {
     pub runtime: BoxliteRuntime,
     pub handle: Option<LiteBox>,
@@ -59,6 +62,7 @@ pub unsafe extern "C" fn boxlite_simple_new(
     runner_new(image, cpus, memory_mib, out_box, out_error)
 }
 
+/// Run a command in the box, reusing the runner's warm guest session.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn boxlite_simple_run(
     box_runner: *mut CBoxliteSimple,
@@ -71,6 +75,20 @@ pub unsafe extern "C" fn boxlite_simple_run(
     runner_exec(box_runner, command, args, argc, out_result, out_error)
 }
 
+/// Number of guest connections the runner has established.
+///
+/// Stays at 1 across any number of `boxlite_simple_run` calls while the box
+/// is healthy; useful for verifying session reuse.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn boxlite_simple_guest_connections(
+    box_runner: *mut CBoxliteSimple,
+    out_count: *mut c_int,
+    out_error: *mut CBoxliteError,
+) -> BoxliteErrorCode {
+    runner_guest_connections(box_runner, out_count, out_error)
+}
+
+/// Stop and remove the box, tearing down its guest session.
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn boxlite_simple_free(box_runner: *mut CBoxliteSimple) {
     runner_free(box_runner)
@@ -140,6 +158,11 @@ unsafe fn runner_new(
         let result = tokio_rt.block_on(async {
             let handle = runtime.create(options, None).await?;
             let box_id = handle.id().clone();
+            // Start eagerly so the guest session is warm before the first run.
+            if let Err(e) = handle.start().await {
+                let _ = runtime.remove(box_id.as_ref(), true).await;
+                return Err(e);
+            }
             Ok::<(LiteBox, BoxID), BoxliteError>((handle, box_id))
         });
 
@@ -217,62 +240,22 @@ unsafe fn runner_exec(
             }
         };
 
-        let result = runner_ref.tokio_rt.block_on(async {
-            let mut cmd = boxlite::BoxCommand::new(cmd_str);
-            cmd = cmd.args(arg_vec);
-
-            let mut execution = handle.exec(cmd).await?;
-
-            let mut stdout_lines = Vec::new();
-            let mut stderr_lines = Vec::new();
-
-            let mut stdout_stream = execution.stdout();
-            let mut stderr_stream = execution.stderr();
-
-            loop {
-                tokio::select! {
-                    Some(line) = async {
-                        match &mut stdout_stream {
-                            Some(s) => s.next().await,
-                            None => None,
-                        }
-                    } => {
-                        stdout_lines.push(line);
-                    }
-                    Some(line) = async {
-                        match &mut stderr_stream {
-                            Some(s) => s.next().await,
-                            None => None,
-                        }
-                    } => {
-                        stderr_lines.push(line);
-                    }
-                    else => break,
-                }
-            }
-
-            let status = execution.wait().await?;
-
-            Ok::<(i32, String, String), BoxliteError>((
-                status.exit_code,
-                stdout_lines.join("\n"),
-                stderr_lines.join("\n"),
-            ))
-        });
+        let command = boxlite::BoxCommand::new(cmd_str).args(arg_vec);
+        let result = runner_ref.tokio_rt.block_on(handle.run(command));
 
         match result {
-            Ok((exit_code, stdout, stderr)) => {
-                let stdout_c = match CString::new(stdout) {
+            Ok(output) => {
+                let stdout_c = match CString::new(output.stdout) {
                     Ok(s) => s.into_raw(),
                     Err(_) => ptr::null_mut(),
                 };
-                let stderr_c = match CString::new(stderr) {
+                let stderr_c = match CString::new(output.stderr) {
                     Ok(s) => s.into_raw(),
                     Err(_) => ptr::null_mut(),
                 };
 
                 let exec_result = Box::new(ExecResult {
-                    exit_code,
+                    exit_code: output.exit_code,
                     stdout_text: stdout_c,
                     stderr_text: stderr_c,
                 });
@@ -288,6 +271,36 @@ unsafe fn runner_exec(
     }
 }
 
+unsafe fn runner_guest_connections(
+    runner: *mut BoxRunner,
+    out_count: *mut c_int,
+    out_error: *mut FFIError,
+) -> BoxliteErrorCode {
+    unsafe {
+        if runner.is_null() {
+            write_error(out_error, null_pointer_error("runner"));
+            return BoxliteErrorCode::InvalidArgument;
+        }
+        if out_count.is_null() {
+            write_error(out_error, null_pointer_error("out_count"));
+            return BoxliteErrorCode::InvalidArgument;
+        }
+
+        let runner_ref = &*runner;
+        match runner_ref.tokio_rt.block_on(runner_ref.runtime.metrics()) {
+            Ok(metrics) => {
+                *out_count = metrics.guest_connections_total() as c_int;
+                BoxliteErrorCode::Ok
+            }
+            Err(e) => {
+                let code = error_to_code(&e);
+                write_error(out_error, e);
+                code
+            }
+        }
+    }
+}
+
 unsafe fn result_free(result: *mut ExecResult) {
     if !result.is_null() {
         unsafe {
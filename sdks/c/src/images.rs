@@ -1,7 +1,8 @@
 //! Image operations for the BoxLite C SDK.
 //!
-//! Async methods (`boxlite_image_pull`, `boxlite_image_list`) follow the
-//! post-and-drain pattern; results are dispatched on the user's drain thread.
+//! Async methods (`boxlite_image_pull`, `boxlite_pull_with_progress`,
+//! `boxlite_image_list`) follow the post-and-drain pattern; results and
+//! progress updates are dispatched on the user's drain thread.
 
 use std::ffi::CString;
 use std::os::raw::{c_char, c_int, c_void};
@@ -11,9 +12,13 @@ use std::sync::Arc;
 use tokio::runtime::Runtime as TokioRuntime;
 
 use boxlite::ImageHandle as CoreImageHandle;
+use boxlite::{PullPhase, PullProgress};
+use futures::StreamExt;
 
 use crate::error::{BoxliteErrorCode, FFIError, error_to_code, null_pointer_error, write_error};
-use crate::event_queue::{CBoxImageListCb, CBoxImagePullCb, EventQueue, RuntimeEvent, push_event};
+use crate::event_queue::{
+    CBoxImageListCb, CBoxImagePullCb, CBoxPullProgressCb, EventQueue, RuntimeEvent, push_event,
+};
 use crate::runtime::RuntimeLiveness;
 use crate::{CBoxliteError, CBoxliteImageHandle};
 
@@ -49,6 +54,41 @@ pub struct CImagePullResult {
     pub layer_count: c_int,
 }
 
+/// Stage of an image pull.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoxlitePullPhase {
+    BoxlitePullPhaseManifest = 0,
+    BoxlitePullPhaseDownloading = 1,
+    BoxlitePullPhaseExtracting = 2,
+    BoxlitePullPhaseDone = 3,
+}
+
+impl From<PullPhase> for BoxlitePullPhase {
+    fn from(phase: PullPhase) -> Self {
+        match phase {
+            PullPhase::Manifest => Self::BoxlitePullPhaseManifest,
+            PullPhase::Downloading => Self::BoxlitePullPhaseDownloading,
+            PullPhase::Extracting => Self::BoxlitePullPhaseExtracting,
+            PullPhase::Done => Self::BoxlitePullPhaseDone,
+        }
+    }
+}
+
+/// One image pull progress update. Borrowed for the duration of the
+/// progress callback only; copy anything needed afterwards.
+///
+/// - `layer_digest`: NULL for image-wide phases.
+/// - `total_bytes`: meaningful only when `has_total` is 1.
+#[repr(C)]
+pub struct CPullProgress {
+    pub layer_digest: *const c_char,
+    pub bytes_downloaded: u64,
+    pub total_bytes: u64,
+    pub has_total: c_int,
+    pub phase: BoxlitePullPhase,
+}
+
 fn to_c_str(s: &str) -> *mut c_char {
     CString::new(s)
         .map(|c| c.into_raw())
@@ -141,6 +181,21 @@ pub unsafe extern "C" fn boxlite_image_pull(
     image_pull(handle, image_ref, cb, user_data, out_error)
 }
 
+/// Pull an image, calling `progress_cb` with each progress update and `cb`
+/// once when the pull finishes. Both fire on the drain thread; every
+/// progress update is dispatched before `cb`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn boxlite_pull_with_progress(
+    handle: *mut CBoxliteImageHandle,
+    image_ref: *const c_char,
+    progress_cb: CBoxPullProgressCb,
+    cb: CBoxImagePullCb,
+    user_data: *mut c_void,
+    out_error: *mut CBoxliteError,
+) -> BoxliteErrorCode {
+    image_pull_with_progress(handle, image_ref, progress_cb, cb, user_data, out_error)
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn boxlite_image_list(
     handle: *mut CBoxliteImageHandle,
@@ -277,3 +332,105 @@ unsafe fn image_pull(
         BoxliteErrorCode::Ok
     }
 }
+
+unsafe fn image_pull_with_progress(
+    handle: *mut ImageHandle,
+    image_ref: *const c_char,
+    progress_cb: CBoxPullProgressCb,
+    cb: CBoxImagePullCb,
+    user_data: *mut c_void,
+    out_error: *mut FFIError,
+) -> BoxliteErrorCode {
+    unsafe {
+        if handle.is_null() {
+            write_error(out_error, null_pointer_error("handle"));
+            return BoxliteErrorCode::InvalidArgument;
+        }
+
+        let image_ref = match crate::util::c_str_to_string(image_ref) {
+            Ok(reference) => reference,
+            Err(e) => {
+                write_error(out_error, e);
+                return BoxliteErrorCode::InvalidArgument;
+            }
+        };
+
+        let handle_ref = &*handle;
+        if let Err(e) = crate::util::ensure_runtime_live(&handle_ref.liveness, "pull image") {
+            let code = error_to_code(&e);
+            write_error(out_error, e);
+            return code;
+        }
+        let progress_cb = crate::unwrap_cb_or_return!(progress_cb, out_error);
+        let cb = crate::unwrap_cb_or_return!(cb, out_error);
+
+        let core_handle = handle_ref.handle.clone();
+        let queue = handle_ref.queue.clone();
+        let user_data_addr = user_data as usize;
+
+        handle_ref.tokio_rt.spawn(async move {
+            // The core callback is synchronous; relay updates through a
+            // channel so they are queued in order, ahead of the completion.
+            let (tx, mut rx) = futures::channel::mpsc::unbounded();
+            let pull = core_handle.pull_with_progress(&image_ref, move |progress| {
+                let _ = tx.unbounded_send(progress);
+            });
+            let relay = async {
+                while let Some(progress) = rx.next().await {
+                    push_event(
+                        &queue,
+                        RuntimeEvent::PullProgress {
+                            cb: progress_cb,
+                            user_data: user_data_addr,
+                            progress,
+                        },
+                    )
+                    .await;
+                }
+            };
+            let (result, ()) = tokio::join!(pull, relay);
+
+            let result = result.map(|image| {
+                crate::event_queue::OwnedFfiPtr::new_with(
+                    Box::new(CImagePullResult::new(
+                        image.reference(),
+                        image.config_digest(),
+                        image.layer_count(),
+                    )),
+                    free_image_pull_result,
+                )
+            });
+            push_event(
+                &queue,
+                RuntimeEvent::ImagePull {
+                    cb,
+                    user_data: user_data_addr,
+                    result,
+                },
+            )
+            .await;
+        });
+
+        BoxliteErrorCode::Ok
+    }
+}
+
+/// Call `cb` with a borrowed C view of `progress`.
+pub(crate) fn dispatch_pull_progress(
+    progress: &PullProgress,
+    cb: crate::event_queue::CBoxPullProgressFn,
+    user_data: *mut c_void,
+) {
+    let digest = progress
+        .layer_digest
+        .as_deref()
+        .and_then(|digest| CString::new(digest).ok());
+    let c_progress = CPullProgress {
+        layer_digest: digest.as_ref().map_or(ptr::null(), |d| d.as_ptr()),
+        bytes_downloaded: progress.bytes_downloaded,
+        total_bytes: progress.total_bytes.unwrap_or(0),
+        has_total: progress.total_bytes.is_some() as c_int,
+        phase: progress.phase.into(),
+    };
+    cb(&c_progress, user_data);
+}
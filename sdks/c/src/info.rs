@@ -27,6 +27,10 @@ pub struct CBoxInfo {
     pub cpus: c_int,
     pub memory_mib: c_int,
     pub created_at: i64,
+    /// Labels as a JSON object, e.g. `{"team":"infra"}`.
+    pub labels: *mut c_char,
+    /// Port mappings as a JSON array, e.g. `[{"host_port":8080,"guest_port":80}]`.
+    pub ports: *mut c_char,
 }
 
 #[repr(C)]
@@ -69,6 +73,8 @@ impl CBoxInfo {
             cpus: info.cpus as c_int,
             memory_mib: info.memory_mib as c_int,
             created_at: info.created_at.timestamp(),
+            labels: to_c_str(&serde_json::to_string(&info.labels).unwrap_or_default()),
+            ports: to_c_str(&serde_json::to_string(&info.ports).unwrap_or_default()),
         }
     }
 }
@@ -83,6 +89,8 @@ pub unsafe fn free_box_info(info: *mut CBoxInfo) {
         free_str(info_ref.name);
         free_str(info_ref.image);
         free_str(info_ref.status);
+        free_str(info_ref.labels);
+        free_str(info_ref.ports);
     }
 }
 
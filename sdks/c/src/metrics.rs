@@ -23,6 +23,8 @@ pub struct CBoxMetrics {
     pub network_bytes_received: i64,
     pub network_tcp_connections: c_int,
     pub network_tcp_errors: c_int,
+    pub disk_bytes_used: i64,
+    pub disk_bytes_total: i64,
 }
 
 #[repr(C)]
@@ -33,6 +35,7 @@ pub struct CRuntimeMetrics {
     pub num_running_boxes: c_int,
     pub total_commands_executed: c_int,
     pub total_exec_errors: c_int,
+    pub guest_connections_total: c_int,
 }
 
 #[unsafe(no_mangle)]
@@ -87,6 +90,8 @@ unsafe fn box_metrics(
                 network_bytes_received: m.network_bytes_received.unwrap_or(0) as i64,
                 network_tcp_connections: m.network_tcp_connections.unwrap_or(0) as c_int,
                 network_tcp_errors: m.network_tcp_errors.unwrap_or(0) as c_int,
+                disk_bytes_used: m.disk_bytes_used.unwrap_or(0) as i64,
+                disk_bytes_total: m.disk_bytes_total.unwrap_or(0) as i64,
             });
             push_event(
                 &queue,
@@ -128,6 +133,7 @@ unsafe fn runtime_metrics(
                 num_running_boxes: m.num_running_boxes() as c_int,
                 total_commands_executed: m.total_commands_executed() as c_int,
                 total_exec_errors: m.total_exec_errors() as c_int,
+                guest_connections_total: m.guest_connections_total() as c_int,
             });
             push_event(
                 &queue,
@@ -70,6 +70,16 @@ pub unsafe extern "C" fn boxlite_options_add_env(
     options_add_env(opts, key, val)
 }
 
+/// Attach a label to the box; shown in its info and usable for filtering.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn boxlite_options_add_label(
+    opts: *mut CBoxliteOptions,
+    key: *const c_char,
+    val: *const c_char,
+) {
+    options_add_label(opts, key, val)
+}
+
 #[unsafe(no_mangle)]
 pub unsafe extern "C" fn boxlite_options_add_volume(
     opts: *mut CBoxliteOptions,
@@ -293,6 +303,21 @@ pub unsafe fn options_add_env(handle: *mut OptionsHandle, key: *const c_char, va
     }
 }
 
+pub unsafe fn options_add_label(
+    handle: *mut OptionsHandle,
+    key: *const c_char,
+    val: *const c_char,
+) {
+    unsafe {
+        if handle.is_null() || key.is_null() || val.is_null() {
+            return;
+        }
+        if let (Ok(k), Ok(v)) = (c_str_to_string(key), c_str_to_string(val)) {
+            (*handle).options.labels.insert(k, v);
+        }
+    }
+}
+
 pub unsafe fn options_add_volume(
     handle: *mut OptionsHandle,
     host_path: *const c_char,
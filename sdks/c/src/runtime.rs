@@ -479,6 +479,11 @@ unsafe fn dispatch_event(event: RuntimeEvent) {
                 user_data,
                 exit_code,
             } => cb(exit_code, user_data as *mut c_void),
+            RuntimeEvent::PullProgress {
+                cb,
+                user_data,
+                progress,
+            } => crate::images::dispatch_pull_progress(&progress, cb, user_data as *mut c_void),
             RuntimeEvent::CreateBox {
                 cb,
                 user_data,
@@ -509,6 +514,11 @@ unsafe fn dispatch_event(event: RuntimeEvent) {
                 user_data,
                 result,
             } => dispatch_unit_event(result, user_data, cb),
+            RuntimeEvent::RenameBox {
+                cb,
+                user_data,
+                result,
+            } => dispatch_unit_event(result, user_data, cb),
             RuntimeEvent::ImagePull {
                 cb,
                 user_data,
@@ -107,6 +107,11 @@ fn test_error_code_mapping() {
         error_to_code(&BoxliteError::Execution("test".into())),
         BoxliteErrorCode::Execution
     );
+    assert_eq!(
+        error_to_code(&BoxliteError::Timeout("test".into())),
+        BoxliteErrorCode::Timeout
+    );
+    assert_eq!(BoxliteErrorCode::Timeout as i32, 22);
 }
 
 #[test]
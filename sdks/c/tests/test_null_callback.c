@@ -87,6 +87,25 @@ static void test_runtime_shutdown_null_cb(CBoxliteRuntime *rt) {
   assert_null_cb_rejected(code, &err, "boxlite_runtime_shutdown(cb=NULL)");
 }
 
+static void noop_pull_progress(const CPullProgress *progress, void *ud) {
+  (void)progress;
+  (void)ud;
+}
+
+static void test_pull_with_progress_null_cb(CBoxliteRuntime *rt) {
+  CBoxliteImageHandle *images = NULL;
+  CBoxliteError err = (CBoxliteError){0};
+  BoxliteErrorCode ic = boxlite_runtime_images(rt, &images, &err);
+  assert(ic == Ok);
+
+  err = (CBoxliteError){0};
+  BoxliteErrorCode code = boxlite_pull_with_progress(
+      images, "alpine:latest", noop_pull_progress, NULL, NULL, &err);
+  assert_null_cb_rejected(code, &err, "boxlite_pull_with_progress(cb=NULL)");
+
+  boxlite_image_free(images);
+}
+
 int main(void) {
   printf("\n═══════════════════════════════════════\n");
   printf("  BoxLite C SDK - NULL callback rejection\n");
@@ -98,11 +117,12 @@ int main(void) {
   test_runtime_metrics_null_cb(rt);
   test_list_info_null_cb(rt);
   test_runtime_shutdown_null_cb(rt);
+  test_pull_with_progress_null_cb(rt);
 
   boxlite_runtime_free(rt);
 
   printf("\n═══════════════════════════════════════\n");
-  printf("  ✅ ALL NULL-CB TESTS PASSED (4 tests)\n");
+  printf("  ✅ ALL NULL-CB TESTS PASSED (5 tests)\n");
   printf("═══════════════════════════════════════\n");
   return 0;
 }
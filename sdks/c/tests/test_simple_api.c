@@ -158,6 +158,34 @@ void test_simple_multiple_commands() {
   boxlite_simple_free(box);
 }
 
+void test_simple_reuses_guest_session() {
+  printf("\nTEST: Simple API - guest session reuse\n");
+
+  CBoxliteSimple *box;
+  CBoxliteError error = {0};
+
+  BoxliteErrorCode code = boxlite_simple_new("alpine:3.19", 0, 0, &box, &error);
+  assert(code == Ok);
+
+  for (int i = 0; i < 10; i++) {
+    CBoxliteExecResult *result;
+    const char *args[] = {"ping", NULL};
+    code = boxlite_simple_run(box, "/bin/echo", args, 1, &result, &error);
+    assert(code == Ok);
+    assert(result->exit_code == 0);
+    boxlite_result_free(result);
+  }
+
+  int connections = -1;
+  code = boxlite_simple_guest_connections(box, &connections, &error);
+  assert(code == Ok);
+  assert(connections == 1);
+
+  printf("  ✓ 10 runs shared 1 guest connection\n");
+
+  boxlite_simple_free(box);
+}
+
 void test_simple_result_cleanup() {
   printf("\nTEST: Simple API - result cleanup\n");
 
@@ -225,12 +253,13 @@ int main() {
   test_simple_run_no_args();
   test_simple_run_failure();
   test_simple_multiple_commands();
+  test_simple_reuses_guest_session();
   test_simple_result_cleanup();
   test_simple_null_safety();
   test_simple_auto_cleanup();
 
   printf("\n═══════════════════════════════════════\n");
-  printf("  ✅ ALL TESTS PASSED (%d tests)\n", 9);
+  printf("  ✅ ALL TESTS PASSED (%d tests)\n", 10);
   printf("═══════════════════════════════════════\n");
 
   return 0;
@@ -69,6 +69,16 @@ func TestIsStopped(t *testing.T) {
 	}
 }
 
+func TestIsTimeout(t *testing.T) {
+	err := &Error{Code: ErrTimeout, Message: "exec timed out"}
+	if !IsTimeout(err) {
+		t.Error("expected IsTimeout to return true")
+	}
+	if IsTimeout(&Error{Code: ErrExecution, Message: "exit 1"}) {
+		t.Error("expected IsTimeout to return false for other codes")
+	}
+}
+
 func TestError_Unwrap(t *testing.T) {
 	err := &Error{Code: ErrNotFound, Message: "test"}
 	var target *Error
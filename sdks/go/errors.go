@@ -39,6 +39,9 @@ const (
 	// disconnect; reattach is no longer possible — start a new exec.
 	// Server-side HTTP 410.
 	ErrSessionReaped ErrorCode = 21
+	// Operation exceeded its deadline (exec timeout, boot timeout, RPC
+	// deadline). Server-side HTTP 504.
+	ErrTimeout ErrorCode = 22
 )
 
 // Error is a typed error from the BoxLite runtime.
@@ -75,6 +78,12 @@ func IsStopped(err error) bool {
 	return errors.As(err, &e) && e.Code == ErrStopped
 }
 
+// IsTimeout reports whether err indicates an operation exceeded its deadline.
+func IsTimeout(err error) bool {
+	var e *Error
+	return errors.As(err, &e) && e.Code == ErrTimeout
+}
+
 // ErrRuntimeClosed is returned by async operations when Runtime.Close is
 // called while the operation is in flight. Callers select on r.closing
 // alongside their result channel and ctx.Done(); when closing fires, the
@@ -32,6 +32,8 @@ type BoxMetrics struct {
 	NetworkBytesReceived int64
 	NetworkTCPConns      int
 	NetworkTCPErrors     int
+	DiskBytesUsed        int64
+	DiskBytesTotal       int64
 }
 
 // Metrics returns aggregate runtime metrics.
@@ -107,6 +109,8 @@ func cBoxMetricsToGo(cm *C.CBoxMetrics) BoxMetrics {
 		NetworkBytesReceived: int64(cm.network_bytes_received),
 		NetworkTCPConns:      int(cm.network_tcp_connections),
 		NetworkTCPErrors:     int(cm.network_tcp_errors),
+		DiskBytesUsed:        int64(cm.disk_bytes_used),
+		DiskBytesTotal:       int64(cm.disk_bytes_total),
 	}
 }
 
@@ -188,12 +188,16 @@ export interface JsBoxMetrics {
   bytesReceivedTotal: number;
   totalCreateDurationMs?: number;
   guestBootDurationMs?: number;
+  guestKernelBootMs?: number;
+  guestAgentInitMs?: number;
   cpuPercent?: number;
   memoryBytes?: number;
   networkBytesSent?: number;
   networkBytesReceived?: number;
   networkTcpConnections?: number;
   networkTcpErrors?: number;
+  diskBytesUsed?: number;
+  diskBytesTotal?: number;
   stageFilesystemSetupMs?: number;
   stageImagePrepareMs?: number;
   stageGuestRootfsMs?: number;
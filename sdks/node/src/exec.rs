@@ -254,7 +254,7 @@ impl JsExecution {
     #[napi]
     pub async fn wait(&self) -> Result<JsExecResult> {
         let exec = self.execution.lock().await.clone();
-        let exec_result = exec.wait().await.map_err(map_err)?;
+        let exec_result = exec.wait_status().await.map_err(map_err)?;
         Ok(JsExecResult {
             exit_code: exec_result.exit_code,
             error_message: exec_result.error_message,
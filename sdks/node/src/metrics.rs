@@ -54,6 +54,10 @@ pub struct JsBoxMetrics {
     pub total_create_duration_ms: Option<f64>,
     /// Time from box subprocess spawn to guest agent ready (milliseconds)
     pub guest_boot_duration_ms: Option<f64>,
+    /// Kernel portion of guest boot (milliseconds)
+    pub guest_kernel_boot_ms: Option<f64>,
+    /// Guest agent portion of guest boot (milliseconds)
+    pub guest_agent_init_ms: Option<f64>,
 
     // Resource usage (runtime, may be None if not available)
     /// CPU usage percent (0.0-100.0)
@@ -70,6 +74,10 @@ pub struct JsBoxMetrics {
     pub network_tcp_connections: Option<f64>,
     /// Total TCP connection errors
     pub network_tcp_errors: Option<f64>,
+    /// Bytes used on the filesystem backing the container rootfs
+    pub disk_bytes_used: Option<f64>,
+    /// Size of the filesystem backing the container rootfs
+    pub disk_bytes_total: Option<f64>,
 
     // Stage-level timing breakdown
     /// Time to create box directory structure (milliseconds)
@@ -98,6 +106,8 @@ impl From<BoxMetrics> for JsBoxMetrics {
             // Lifecycle timing (convert u128 to f64 for JavaScript)
             total_create_duration_ms: m.total_create_duration_ms.map(|v| v as f64),
             guest_boot_duration_ms: m.guest_boot_duration_ms.map(|v| v as f64),
+            guest_kernel_boot_ms: m.guest_kernel_boot_ms.map(|v| v as f64),
+            guest_agent_init_ms: m.guest_agent_init_ms.map(|v| v as f64),
 
             // Resource usage
             cpu_percent: m.cpu_percent.map(|v| v as f64),
@@ -108,6 +118,8 @@ impl From<BoxMetrics> for JsBoxMetrics {
             network_bytes_received: m.network_bytes_received.map(|v| v as f64),
             network_tcp_connections: m.network_tcp_connections.map(|v| v as f64),
             network_tcp_errors: m.network_tcp_errors.map(|v| v as f64),
+            disk_bytes_used: m.disk_bytes_used.map(|v| v as f64),
+            disk_bytes_total: m.disk_bytes_total.map(|v| v as f64),
 
             // Stage timing (convert u128 to f64 for JavaScript)
             stage_filesystem_setup_ms: m.stage_filesystem_setup_ms.map(|v| v as f64),
@@ -201,8 +201,8 @@ pub struct JsBoxOptions {
 
     /// Override image ENTRYPOINT directive.
     ///
-    /// When set, completely replaces the image's ENTRYPOINT.
-    /// Use with `cmd` to build the full command:
+    /// When set, completely replaces the image's ENTRYPOINT and drops the
+    /// image's CMD. Use with `cmd` to build the full command:
     ///   Final execution = entrypoint + cmd
     pub entrypoint: Option<Vec<String>>,
 
@@ -409,12 +409,18 @@ impl TryFrom<JsBoxOptions> for BoxOptions {
         Ok(BoxOptions {
             cpus: js_opts.cpus,
             memory_mib: js_opts.memory_mib,
+            cpu_affinity: None,
             disk_size_gb: js_opts.disk_size_gb.map(|v| v as u64),
             working_dir: js_opts.working_dir,
             env,
             rootfs,
+            platform: None,
+            pull_policy: Default::default(),
             volumes,
+            tmpfs: Vec::new(),
             network,
+            network_rate_limit: None,
+            mac_address: None,
             ports,
             advanced: AdvancedBoxOptions {
                 security,
@@ -423,10 +429,28 @@ impl TryFrom<JsBoxOptions> for BoxOptions {
             },
             auto_remove: js_opts.auto_remove.unwrap_or(false),
             detach: js_opts.detach.unwrap_or(false),
+            stop_grace_secs: None,
+            boot_timeout_secs: None,
+            idle_timeout_secs: None,
+            kernel: None,
+            read_only_rootfs: false,
+            cap_add: Vec::new(),
+            cap_drop: Vec::new(),
+            labels: Default::default(),
+            dns: Default::default(),
+            extra_hosts: Vec::new(),
+            vsock_ports: Vec::new(),
+            console_ring_lines: None,
             entrypoint: js_opts.entrypoint,
             cmd: js_opts.cmd,
             user: js_opts.user,
+            userns_mapping: None,
+            disks: Vec::new(),
+            overlay_upper_disk: None,
+            kernel_modules: Vec::new(),
+            exec_profiles: Default::default(),
             secrets,
+            pull_progress: None,
         })
     }
 }
@@ -169,7 +169,7 @@ impl PyExecution {
         let execution = Arc::clone(&self.execution);
 
         pyo3_async_runtimes::tokio::future_into_py(py, async move {
-            let exec_result = execution.wait().await.map_err(map_err)?;
+            let exec_result = execution.wait_status().await.map_err(map_err)?;
             Ok(PyExecResult {
                 exit_code: exec_result.exit_code,
                 error_message: exec_result.error_message,
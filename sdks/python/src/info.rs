@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use boxlite::{BoxInfo, BoxStateInfo, BoxStatus, HealthState as CoreHealthState};
 use pyo3::prelude::*;
 
@@ -177,7 +179,12 @@ pub(crate) struct PyBoxInfo {
     #[pyo3(get)]
     pub(crate) memory_mib: u32,
     #[pyo3(get)]
+    pub(crate) labels: HashMap<String, String>,
+    #[pyo3(get)]
     pub(crate) health_status: PyHealthStatus,
+    /// Active port mappings as `(host_port, guest_port)` tuples.
+    #[pyo3(get)]
+    pub(crate) ports: Vec<(u16, u16)>,
 }
 
 #[pymethods]
@@ -194,6 +201,8 @@ impl PyBoxInfo {
             "image": self.image,
             "cpus": self.cpus,
             "memory_mib": self.memory_mib,
+            "labels": self.labels,
+            "ports": self.ports,
             "created_at": self.created_at,
             "health_status": {
                 "state": self.health_status.state.value,
@@ -223,7 +232,13 @@ impl From<BoxInfo> for PyBoxInfo {
             image: info.image,
             cpus: info.cpus,
             memory_mib: info.memory_mib,
+            labels: info.labels,
             health_status,
+            ports: info
+                .ports
+                .iter()
+                .map(|p| (p.host_port, p.guest_port))
+                .collect(),
         }
     }
 }
@@ -58,6 +58,10 @@ pub(crate) struct PyBoxMetrics {
     #[pyo3(get)]
     pub(crate) guest_boot_duration_ms: Option<u128>,
     #[pyo3(get)]
+    pub(crate) guest_kernel_boot_ms: Option<u128>,
+    #[pyo3(get)]
+    pub(crate) guest_agent_init_ms: Option<u128>,
+    #[pyo3(get)]
     pub(crate) cpu_percent: Option<f32>,
     #[pyo3(get)]
     pub(crate) memory_bytes: Option<u64>,
@@ -69,6 +73,10 @@ pub(crate) struct PyBoxMetrics {
     pub(crate) network_tcp_connections: Option<u64>,
     #[pyo3(get)]
     pub(crate) network_tcp_errors: Option<u64>,
+    #[pyo3(get)]
+    pub(crate) disk_bytes_used: Option<u64>,
+    #[pyo3(get)]
+    pub(crate) disk_bytes_total: Option<u64>,
     // Stage-level timing breakdown
     #[pyo3(get)]
     pub(crate) stage_filesystem_setup_ms: Option<u128>,
@@ -108,12 +116,16 @@ impl From<BoxMetrics> for PyBoxMetrics {
             bytes_received_total: metrics.bytes_received_total(),
             total_create_duration_ms: metrics.total_create_duration_ms(),
             guest_boot_duration_ms: metrics.guest_boot_duration_ms(),
+            guest_kernel_boot_ms: metrics.guest_kernel_boot_ms(),
+            guest_agent_init_ms: metrics.guest_agent_init_ms(),
             cpu_percent: metrics.cpu_percent(),
             memory_bytes: metrics.memory_bytes(),
             network_bytes_sent: metrics.network_bytes_sent(),
             network_bytes_received: metrics.network_bytes_received(),
             network_tcp_connections: metrics.network_tcp_connections(),
             network_tcp_errors: metrics.network_tcp_errors(),
+            disk_bytes_used: metrics.disk_bytes_used(),
+            disk_bytes_total: metrics.disk_bytes_total(),
             stage_filesystem_setup_ms: metrics.stage_filesystem_setup_ms(),
             stage_image_prepare_ms: metrics.stage_image_prepare_ms(),
             stage_guest_rootfs_ms: metrics.stage_guest_rootfs_ms(),
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 use boxlite::BoxliteRestOptions;
@@ -379,6 +380,9 @@ pub(crate) struct PyBoxOptions {
     pub(crate) working_dir: Option<String>,
     #[pyo3(get, set)]
     pub(crate) env: Vec<(String, String)>,
+    /// User-defined labels, reported in `BoxInfo.labels`.
+    #[pyo3(get, set)]
+    pub(crate) labels: HashMap<String, String>,
     pub(crate) volumes: Vec<PyVolumeSpec>,
     #[pyo3(get, set)]
     pub(crate) network: Option<PyNetworkSpec>,
@@ -421,6 +425,7 @@ impl PyBoxOptions {
         disk_size_gb=None,
         working_dir=None,
         env=vec![],
+        labels=None,
         volumes=vec![],
         network=None,
         ports=vec![],
@@ -441,6 +446,7 @@ impl PyBoxOptions {
         disk_size_gb: Option<u64>,
         working_dir: Option<String>,
         env: Vec<(String, String)>,
+        labels: Option<HashMap<String, String>>,
         volumes: Vec<PyVolumeSpec>,
         network: Option<PyNetworkSpec>,
         ports: Vec<PyPortSpec>,
@@ -460,6 +466,7 @@ impl PyBoxOptions {
             disk_size_gb,
             working_dir,
             env,
+            labels: labels.unwrap_or_default(),
             volumes,
             network,
             ports,
@@ -516,6 +523,7 @@ impl TryFrom<PyBoxOptions> for BoxOptions {
             disk_size_gb: py_opts.disk_size_gb,
             working_dir: py_opts.working_dir,
             env: py_opts.env,
+            labels: py_opts.labels,
             rootfs,
             volumes,
             network,
@@ -20,6 +20,7 @@ fn main() {
         disk_path: PathBuf::from("/tmp/test.qcow2"),
         read_only: false,
         format: DiskFormat::Qcow2,
+        passthrough: false,
     };
     disks.add(qcow2_disk);
     println!("  ✓ Added QCOW2 disk: vda -> /tmp/test.qcow2 (read-write)");
@@ -30,6 +31,7 @@ fn main() {
         disk_path: PathBuf::from("/tmp/scratch.raw"),
         read_only: true,
         format: DiskFormat::Raw,
+        passthrough: false,
     };
     disks.add(raw_disk);
     println!("  ✓ Added raw disk: vdb -> /tmp/scratch.raw (read-only)");
@@ -66,6 +66,46 @@ impl BoxStore {
         }
     }
 
+    /// Change a box's name in both the `name` column and the config JSON.
+    ///
+    /// Returns `AlreadyExists` if another box has the name (UNIQUE
+    /// constraint) and `NotFound` if the box doesn't exist.
+    pub fn rename(&self, box_id: &str, name: &str) -> BoxliteResult<()> {
+        let mut conn = self.db.conn();
+        let tx = db_err!(conn.transaction())?;
+
+        let json: Option<String> = db_err!(
+            tx.query_row(
+                "SELECT json FROM box_config WHERE id = ?1",
+                params![box_id],
+                |row| row.get(0),
+            )
+            .optional()
+        )?;
+        let Some(json) = json else {
+            return Err(BoxliteError::NotFound(box_id.to_string()));
+        };
+        let mut config: BoxConfig = serde_json::from_str(&json)
+            .map_err(|e| BoxliteError::Database(format!("Failed to deserialize config: {}", e)))?;
+        config.name = Some(name.to_string());
+        let json = serde_json::to_string(&config)
+            .map_err(|e| BoxliteError::Database(format!("Failed to serialize config: {}", e)))?;
+
+        tx.execute(
+            "UPDATE box_config SET name = ?1, json = ?2 WHERE id = ?3",
+            params![name, json, box_id],
+        )
+        .map_err(|e| match e.sqlite_error_code() {
+            Some(rusqlite::ErrorCode::ConstraintViolation) => {
+                BoxliteError::AlreadyExists(format!("box with name '{}' already exists", name))
+            }
+            _ => BoxliteError::Database(e.to_string()),
+        })?;
+        db_err!(tx.commit())?;
+
+        Ok(())
+    }
+
     /// Delete box configuration (and state via CASCADE).
     pub fn delete(&self, box_id: &str) -> BoxliteResult<bool> {
         let conn = self.db.conn();
@@ -468,6 +508,27 @@ mod tests {
         assert!(store.load(config.id.as_str()).unwrap().is_none());
     }
 
+    #[test]
+    fn test_rename() {
+        let (store, _dir) = create_test_db();
+        let mut taken = create_test_config(TEST_ID_1);
+        taken.name = Some("taken".to_string());
+        store.save(&taken, &BoxState::new()).unwrap();
+        let config = create_test_config(TEST_ID_2);
+        store.save(&config, &BoxState::new()).unwrap();
+
+        store.rename(TEST_ID_2, "renamed").unwrap();
+        let loaded = store.load_config(TEST_ID_2).unwrap().unwrap();
+        assert_eq!(loaded.name.as_deref(), Some("renamed"));
+
+        let err = store.rename(TEST_ID_2, "taken").unwrap_err();
+        assert!(matches!(err, BoxliteError::AlreadyExists(_)), "{err:?}");
+        assert!(matches!(
+            store.rename(TEST_ID_3, "other"),
+            Err(BoxliteError::NotFound(_))
+        ));
+    }
+
     #[test]
     fn test_list_all() {
         let (store, _dir) = create_test_db();
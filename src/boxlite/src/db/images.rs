@@ -107,7 +107,6 @@ impl ImageIndexStore {
     }
 
     /// Remove cached image from index.
-    #[allow(dead_code)]
     pub fn remove(&self, reference: &str) -> BoxliteResult<bool> {
         let conn = self.db.conn();
         let rows_affected = db_err!(conn.execute(
@@ -4,7 +4,7 @@
 //! - `Disk` - RAII wrapper for disk image files
 //! - `DiskFormat` - Disk format types (Ext4, Qcow2)
 //! - `create_ext4_from_dir` - Create ext4 filesystem from directory
-//! - `Qcow2Helper` - QCOW2 copy-on-write disk creation
+//! - `Qcow2Helper` - QCOW2 copy-on-write disk creation and internal snapshots
 //! - `fork_qcow2` - Atomic fork: rename + COW child creation
 
 use std::path::{Path, PathBuf};
@@ -130,13 +130,19 @@ impl Drop for Disk {
 pub(crate) mod base_disk;
 pub mod constants;
 pub(crate) mod ext4;
+pub(crate) mod passthrough;
 pub(crate) mod qcow2;
+pub(crate) mod qcow2_snapshot;
 
 pub(crate) use base_disk::{BaseDisk, BaseDiskKind, BaseDiskManager};
 pub use ext4::{create_ext4_from_dir, inject_file_into_ext4};
+pub(crate) use passthrough::validate_passthrough_device;
+pub(crate) use qcow2::has_qcow2_magic;
 pub use qcow2::{
     BackingFormat, Qcow2Helper, is_backing_dependency, read_backing_chain, read_backing_file_path,
 };
+pub use qcow2_snapshot::Qcow2Snapshot;
+pub(crate) use qcow2_snapshot::lock_attached_disk;
 
 // ============================================================================
 // DiskInfo — serde DTO for disk path + size metadata
@@ -0,0 +1,67 @@
+//! Validation of host block devices attached to the guest directly.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
+
+/// Check that `path` is a host block device the caller may open for
+/// reading, and for writing unless `read_only`.
+pub(crate) fn validate_passthrough_device(path: &Path, read_only: bool) -> BoxliteResult<()> {
+    let metadata = std::fs::metadata(path).map_err(|e| {
+        BoxliteError::Storage(format!(
+            "passthrough device {} is not accessible: {e}",
+            path.display()
+        ))
+    })?;
+    if !metadata.file_type().is_block_device() {
+        return Err(BoxliteError::Storage(format!(
+            "passthrough device {} is not a block device",
+            path.display()
+        )));
+    }
+
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(!read_only)
+        .open(path)
+        .map_err(|e| {
+            BoxliteError::Storage(format!(
+                "cannot open block device {} for {}: {e}",
+                path.display(),
+                if read_only { "reading" } else { "writing" }
+            ))
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regular_file_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("disk.img");
+        std::fs::write(&image, vec![0u8; 4096]).unwrap();
+
+        for read_only in [true, false] {
+            let err = validate_passthrough_device(&image, read_only).unwrap_err();
+            assert!(matches!(err, BoxliteError::Storage(_)), "{err:?}");
+            assert!(err.to_string().contains("not a block device"), "{err}");
+        }
+    }
+
+    #[test]
+    fn character_device_is_rejected() {
+        let err = validate_passthrough_device(Path::new("/dev/null"), true).unwrap_err();
+        assert!(err.to_string().contains("not a block device"), "{err}");
+    }
+
+    #[test]
+    fn missing_path_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = validate_passthrough_device(&dir.path().join("nvme0n1"), true).unwrap_err();
+        assert!(matches!(err, BoxliteError::Storage(_)), "{err:?}");
+        assert!(err.to_string().contains("not accessible"), "{err}");
+    }
+}
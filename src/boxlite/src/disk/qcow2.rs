@@ -1123,7 +1123,7 @@ pub fn read_backing_chain(path: &Path) -> Vec<PathBuf> {
 ///
 /// Raw backing files are valid terminal nodes in a qcow2 backing chain, so a
 /// non-qcow2 magic is not an error here.
-fn has_qcow2_magic(path: &Path) -> BoxliteResult<bool> {
+pub(crate) fn has_qcow2_magic(path: &Path) -> BoxliteResult<bool> {
     let mut file = std::fs::File::open(path)
         .map_err(|e| BoxliteError::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
 
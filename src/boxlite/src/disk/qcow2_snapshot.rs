@@ -0,0 +1,841 @@
+//! Qcow2 internal snapshots.
+//!
+//! Reads and writes the snapshot table of a qcow2 image so a disk can be
+//! checkpointed and rolled back in place, like `qemu-img snapshot`.
+//! Refcounts follow QEMU's model: every L1 table (the active one and each
+//! snapshot's copy) holds one reference on each L2 table and data cluster it
+//! reaches. The active tables carry the COPIED flag exactly where the
+//! refcount is 1, so writers copy shared clusters instead of overwriting them.
+//!
+//! Only the subset of the format boxlite writes is handled: version 3,
+//! 16-bit refcounts, no encryption, compression or incompatible features.
+//!
+//! A VMM attaching a disk read-write holds a shared `flock` on it (see
+//! [`lock_attached_disk`]); snapshot operations take the lock exclusively
+//! and refuse to run while a box is using the disk.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{File, OpenOptions};
+use std::io::ErrorKind;
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+use super::constants::qcow2::REFCOUNT_ORDER;
+use super::qcow2::{Qcow2Helper, has_qcow2_magic};
+
+/// Offset bits of L1, L2 and refcount table entries.
+const OFFSET_MASK: u64 = 0x00FF_FFFF_FFFF_FE00;
+/// L1/L2 entry flag: refcount is exactly 1, safe to write in place.
+const OFLAG_COPIED: u64 = 1 << 63;
+/// L2 entry flag: compressed cluster.
+const OFLAG_COMPRESSED: u64 = 1 << 62;
+
+/// Version 3 header length (up to and including `header_length`).
+const HEADER_LEN: usize = 104;
+/// Byte offset of `nb_snapshots`; `snapshots_offset` follows it.
+const NB_SNAPSHOTS_OFFSET: u64 = 60;
+/// Fixed part of a snapshot table entry.
+const SNAPSHOT_HEADER_LEN: usize = 40;
+/// `vm_state_size_large` + `disk_size`, the minimum for version 3 images.
+const SNAPSHOT_EXTRA_LEN: usize = 16;
+/// QEMU rejects snapshot entries with more extra data than this.
+const MAX_SNAPSHOT_EXTRA_LEN: usize = 1024;
+
+/// An internal snapshot recorded in a qcow2 image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Qcow2Snapshot {
+    /// Numeric ID assigned by the image ("1", "2", ...).
+    pub id: String,
+    /// Snapshot name.
+    pub name: String,
+    /// Virtual disk size when the snapshot was taken.
+    pub virtual_size: u64,
+    /// When the snapshot was taken.
+    pub created_at: SystemTime,
+}
+
+impl Qcow2Helper {
+    /// List the internal snapshots of the qcow2 image at `disk`.
+    pub fn list_snapshots(disk: &Path) -> BoxliteResult<Vec<Qcow2Snapshot>> {
+        let image = Image::open(disk, false)?;
+        Ok(image
+            .snapshots
+            .iter()
+            .map(|snapshot| snapshot.info(image.size))
+            .collect())
+    }
+
+    /// Record the current contents of `disk` as internal snapshot `name`.
+    ///
+    /// Fails with `InvalidState` while a running box has the disk attached
+    /// read-write, and with `InvalidArgument` if `name` is already taken.
+    pub fn create_snapshot(disk: &Path, name: &str) -> BoxliteResult<Qcow2Snapshot> {
+        if name.is_empty() || name.len() > u16::MAX as usize {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "invalid snapshot name {:?}",
+                name
+            )));
+        }
+
+        let mut image = Image::open(disk, true)?;
+        if image.snapshots.iter().any(|s| s.name == name) {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "snapshot '{}' already exists in {}",
+                name,
+                disk.display()
+            )));
+        }
+
+        // The snapshot keeps its own copy of the L1 table; L2 tables and data
+        // clusters are shared with the active image.
+        let l1_copy: Vec<u64> = image.l1.iter().map(|e| e & !OFLAG_COPIED).collect();
+        let l1_copy_offset = image.alloc_clusters(image.clusters_for(l1_copy.len() * 8))?;
+        image.write_table(l1_copy_offset, &l1_copy)?;
+
+        // Everything the active L1 reaches is now shared, which also clears
+        // COPIED so the VM copies on its next write.
+        let mut l1 = std::mem::take(&mut image.l1);
+        image.update_refcounts(&mut l1, 1)?;
+        image.write_table(image.l1_offset, &l1)?;
+        image.l1 = l1;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let next_id = image
+            .snapshots
+            .iter()
+            .filter_map(|s| s.id.parse::<u64>().ok())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let mut extra = vec![0u8; SNAPSHOT_EXTRA_LEN];
+        extra[8..16].copy_from_slice(&image.size.to_be_bytes());
+        image.snapshots.push(SnapshotEntry {
+            l1_offset: l1_copy_offset,
+            l1_size: l1_copy.len() as u32,
+            id: next_id.to_string(),
+            name: name.to_string(),
+            date_sec: now.as_secs() as u32,
+            date_nsec: now.subsec_nanos(),
+            vm_clock_nsec: 0,
+            vm_state_size: 0,
+            extra,
+        });
+        image.write_snapshot_table()?;
+        image.flush_refcounts()?;
+        image.sync()?;
+        image.write_snapshot_header()?;
+        image.sync()?;
+
+        let info = image.snapshots.last().unwrap().info(image.size);
+        tracing::info!(
+            disk = %disk.display(),
+            id = %info.id,
+            name,
+            "Created qcow2 snapshot"
+        );
+        Ok(info)
+    }
+
+    /// Roll `disk` back to internal snapshot `name`, discarding later writes.
+    ///
+    /// The snapshot itself is kept and can be restored again. Fails with
+    /// `InvalidState` while a running box has the disk attached read-write.
+    pub fn restore_snapshot(disk: &Path, name: &str) -> BoxliteResult<()> {
+        let mut image = Image::open(disk, true)?;
+        let snapshot = image
+            .snapshots
+            .iter()
+            .find(|s| s.name == name)
+            .ok_or_else(|| {
+                BoxliteError::NotFound(format!(
+                    "snapshot '{}' not found in {}",
+                    name,
+                    disk.display()
+                ))
+            })?;
+        if snapshot.l1_size as usize > image.l1.len() {
+            return Err(BoxliteError::Unsupported(format!(
+                "snapshot '{}' of {} was taken at a larger disk size",
+                name,
+                disk.display()
+            )));
+        }
+
+        let mut l1 = image.read_table(snapshot.l1_offset, snapshot.l1_size as usize)?;
+        l1.resize(image.l1.len(), 0);
+
+        // Reference the restored tables, switch to them, then release the old
+        // ones. A crash between steps leaks clusters but never frees one that
+        // is still in use.
+        image.update_refcounts(&mut l1, 1)?;
+        image.flush_refcounts()?;
+        image.sync()?;
+        image.write_table(image.l1_offset, &l1)?;
+        image.sync()?;
+        let mut old_l1 = std::mem::take(&mut image.l1);
+        image.update_refcounts(&mut old_l1, -1)?;
+        image.flush_refcounts()?;
+        image.sync()?;
+
+        // Mark the clusters the active image now owns alone as writable in
+        // place.
+        image.update_refcounts(&mut l1, 0)?;
+        image.write_table(image.l1_offset, &l1)?;
+        image.sync()?;
+        image.l1 = l1;
+
+        tracing::info!(disk = %disk.display(), name, "Restored qcow2 snapshot");
+        Ok(())
+    }
+}
+
+/// Hold a shared lock on a disk a VM attaches read-write.
+///
+/// The VMM keeps the returned file open for as long as the VM runs; snapshot
+/// operations need the lock exclusively and so refuse to touch the disk
+/// until the VM exits. Blocks while a snapshot operation is in progress.
+pub(crate) fn lock_attached_disk(path: &Path) -> BoxliteResult<File> {
+    let file = File::open(path)
+        .map_err(|e| BoxliteError::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
+    // SAFETY: `file` owns a valid descriptor for the duration of the call.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_SH) } != 0 {
+        return Err(BoxliteError::Storage(format!(
+            "Failed to lock {}: {}",
+            path.display(),
+            std::io::Error::last_os_error()
+        )));
+    }
+    Ok(file)
+}
+
+/// One entry of the snapshot table, kept verbatim for rewriting.
+struct SnapshotEntry {
+    l1_offset: u64,
+    l1_size: u32,
+    id: String,
+    name: String,
+    date_sec: u32,
+    date_nsec: u32,
+    vm_clock_nsec: u64,
+    vm_state_size: u32,
+    extra: Vec<u8>,
+}
+
+impl SnapshotEntry {
+    fn info(&self, image_size: u64) -> Qcow2Snapshot {
+        let virtual_size = match self.extra.get(8..16) {
+            Some(bytes) => u64::from_be_bytes(bytes.try_into().unwrap()),
+            None => image_size,
+        };
+        Qcow2Snapshot {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            virtual_size,
+            created_at: UNIX_EPOCH + Duration::new(self.date_sec as u64, self.date_nsec),
+        }
+    }
+
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.l1_offset.to_be_bytes());
+        out.extend_from_slice(&self.l1_size.to_be_bytes());
+        out.extend_from_slice(&(self.id.len() as u16).to_be_bytes());
+        out.extend_from_slice(&(self.name.len() as u16).to_be_bytes());
+        out.extend_from_slice(&self.date_sec.to_be_bytes());
+        out.extend_from_slice(&self.date_nsec.to_be_bytes());
+        out.extend_from_slice(&self.vm_clock_nsec.to_be_bytes());
+        out.extend_from_slice(&self.vm_state_size.to_be_bytes());
+        out.extend_from_slice(&(self.extra.len() as u32).to_be_bytes());
+        out.extend_from_slice(&self.extra);
+        out.extend_from_slice(self.id.as_bytes());
+        out.extend_from_slice(self.name.as_bytes());
+        out.resize(out.len().next_multiple_of(8), 0);
+    }
+}
+
+/// An open qcow2 image with its refcounts loaded on demand.
+struct Image {
+    file: File,
+    path: PathBuf,
+    cluster_bits: u32,
+    size: u64,
+    l1_offset: u64,
+    l1: Vec<u64>,
+    refcount_table_offset: u64,
+    refcount_table: Vec<u64>,
+    refcount_table_dirty: bool,
+    /// Loaded refcount blocks, by refcount table index.
+    refcount_blocks: BTreeMap<usize, Vec<u16>>,
+    dirty_blocks: BTreeSet<usize>,
+    snapshots: Vec<SnapshotEntry>,
+    snapshots_offset: u64,
+    /// Bytes the snapshot table occupied when the image was opened.
+    snapshots_len: usize,
+    /// Next free cluster offset, past the end of the file.
+    end: u64,
+}
+
+impl Image {
+    /// Open and validate `path`; `writable` also takes the exclusive lock.
+    fn open(path: &Path, writable: bool) -> BoxliteResult<Self> {
+        if !has_qcow2_magic(path)? {
+            return Err(BoxliteError::Storage(format!(
+                "{} is not a qcow2 image",
+                path.display()
+            )));
+        }
+
+        let file = OpenOptions::new()
+            .read(true)
+            .write(writable)
+            .open(path)
+            .map_err(|e| {
+                BoxliteError::Storage(format!("Failed to open {}: {}", path.display(), e))
+            })?;
+        if writable {
+            // SAFETY: `file` owns a valid descriptor for the duration of the call.
+            if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } != 0 {
+                let err = std::io::Error::last_os_error();
+                return Err(if err.kind() == ErrorKind::WouldBlock {
+                    BoxliteError::InvalidState(format!(
+                        "disk {} is attached read-write to a running box",
+                        path.display()
+                    ))
+                } else {
+                    BoxliteError::Storage(format!("Failed to lock {}: {}", path.display(), err))
+                });
+            }
+        }
+
+        let mut hdr = [0u8; HEADER_LEN];
+        file.read_exact_at(&mut hdr, 0).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to read qcow2 header from {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let version = u32::from_be_bytes(hdr[4..8].try_into().unwrap());
+        let cluster_bits = u32::from_be_bytes(hdr[20..24].try_into().unwrap());
+        let size = u64::from_be_bytes(hdr[24..32].try_into().unwrap());
+        let crypt_method = u32::from_be_bytes(hdr[32..36].try_into().unwrap());
+        let l1_size = u32::from_be_bytes(hdr[36..40].try_into().unwrap());
+        let l1_offset = u64::from_be_bytes(hdr[40..48].try_into().unwrap());
+        let refcount_table_offset = u64::from_be_bytes(hdr[48..56].try_into().unwrap());
+        let refcount_table_clusters = u32::from_be_bytes(hdr[56..60].try_into().unwrap());
+        let nb_snapshots = u32::from_be_bytes(hdr[60..64].try_into().unwrap());
+        let snapshots_offset = u64::from_be_bytes(hdr[64..72].try_into().unwrap());
+        let incompatible_features = u64::from_be_bytes(hdr[72..80].try_into().unwrap());
+        let refcount_order = u32::from_be_bytes(hdr[96..100].try_into().unwrap());
+
+        if version != 3
+            || crypt_method != 0
+            || incompatible_features != 0
+            || refcount_order != REFCOUNT_ORDER as u32
+        {
+            return Err(BoxliteError::Unsupported(format!(
+                "snapshots of {} are not supported (qcow2 v{}, encryption {}, \
+                 incompatible features {:#x}, refcount order {})",
+                path.display(),
+                version,
+                crypt_method,
+                incompatible_features,
+                refcount_order
+            )));
+        }
+        if !(9..=21).contains(&cluster_bits) {
+            return Err(BoxliteError::Storage(format!(
+                "Invalid qcow2 cluster bits {} in {}",
+                cluster_bits,
+                path.display()
+            )));
+        }
+
+        let len = file
+            .metadata()
+            .map_err(|e| {
+                BoxliteError::Storage(format!("Failed to stat {}: {}", path.display(), e))
+            })?
+            .len();
+        let cluster_size = 1u64 << cluster_bits;
+
+        let mut image = Image {
+            file,
+            path: path.to_path_buf(),
+            cluster_bits,
+            size,
+            l1_offset,
+            l1: Vec::new(),
+            refcount_table_offset,
+            refcount_table: Vec::new(),
+            refcount_table_dirty: false,
+            refcount_blocks: BTreeMap::new(),
+            dirty_blocks: BTreeSet::new(),
+            snapshots: Vec::new(),
+            snapshots_offset,
+            snapshots_len: 0,
+            end: len.next_multiple_of(cluster_size),
+        };
+        image.l1 = image.read_table(l1_offset, l1_size as usize)?;
+        image.refcount_table = image.read_table(
+            refcount_table_offset,
+            (refcount_table_clusters as usize) << (cluster_bits - 3),
+        )?;
+        image.read_snapshot_table(nb_snapshots)?;
+        Ok(image)
+    }
+
+    fn cluster_size(&self) -> u64 {
+        1 << self.cluster_bits
+    }
+
+    fn clusters_for(&self, bytes: usize) -> u64 {
+        (bytes as u64).div_ceil(self.cluster_size()).max(1)
+    }
+
+    fn read_at(&self, offset: u64, len: usize) -> BoxliteResult<Vec<u8>> {
+        let mut buf = vec![0u8; len];
+        self.file.read_exact_at(&mut buf, offset).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to read {} bytes at {:#x} from {}: {}",
+                len,
+                offset,
+                self.path.display(),
+                e
+            ))
+        })?;
+        Ok(buf)
+    }
+
+    fn write_at(&self, offset: u64, buf: &[u8]) -> BoxliteResult<()> {
+        self.file.write_all_at(buf, offset).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to write {} bytes at {:#x} to {}: {}",
+                buf.len(),
+                offset,
+                self.path.display(),
+                e
+            ))
+        })
+    }
+
+    fn read_table(&self, offset: u64, entries: usize) -> BoxliteResult<Vec<u64>> {
+        Ok(self
+            .read_at(offset, entries * 8)?
+            .chunks_exact(8)
+            .map(|c| u64::from_be_bytes(c.try_into().unwrap()))
+            .collect())
+    }
+
+    fn write_table(&self, offset: u64, table: &[u64]) -> BoxliteResult<()> {
+        let buf: Vec<u8> = table.iter().flat_map(|e| e.to_be_bytes()).collect();
+        self.write_at(offset, &buf)
+    }
+
+    fn sync(&self) -> BoxliteResult<()> {
+        self.file.sync_all().map_err(|e| {
+            BoxliteError::Storage(format!("Failed to sync {}: {}", self.path.display(), e))
+        })
+    }
+
+    fn read_snapshot_table(&mut self, count: u32) -> BoxliteResult<()> {
+        let mut pos = self.snapshots_offset;
+        for _ in 0..count {
+            let hdr = self.read_at(pos, SNAPSHOT_HEADER_LEN)?;
+            let id_len = u16::from_be_bytes(hdr[12..14].try_into().unwrap()) as usize;
+            let name_len = u16::from_be_bytes(hdr[14..16].try_into().unwrap()) as usize;
+            let extra_len = u32::from_be_bytes(hdr[36..40].try_into().unwrap()) as usize;
+            if extra_len > MAX_SNAPSHOT_EXTRA_LEN {
+                return Err(BoxliteError::Storage(format!(
+                    "Corrupt snapshot table in {}: {} bytes of extra data",
+                    self.path.display(),
+                    extra_len
+                )));
+            }
+
+            let var = self.read_at(
+                pos + SNAPSHOT_HEADER_LEN as u64,
+                extra_len + id_len + name_len,
+            )?;
+            let (extra, strings) = var.split_at(extra_len);
+            let (id, name) = strings.split_at(id_len);
+            self.snapshots.push(SnapshotEntry {
+                l1_offset: u64::from_be_bytes(hdr[0..8].try_into().unwrap()),
+                l1_size: u32::from_be_bytes(hdr[8..12].try_into().unwrap()),
+                id: String::from_utf8_lossy(id).into_owned(),
+                name: String::from_utf8_lossy(name).into_owned(),
+                date_sec: u32::from_be_bytes(hdr[16..20].try_into().unwrap()),
+                date_nsec: u32::from_be_bytes(hdr[20..24].try_into().unwrap()),
+                vm_clock_nsec: u64::from_be_bytes(hdr[24..32].try_into().unwrap()),
+                vm_state_size: u32::from_be_bytes(hdr[32..36].try_into().unwrap()),
+                extra: extra.to_vec(),
+            });
+            pos += (SNAPSHOT_HEADER_LEN + var.len()).next_multiple_of(8) as u64;
+        }
+        self.snapshots_len = (pos - self.snapshots_offset) as usize;
+        Ok(())
+    }
+
+    /// Write the snapshot table to fresh clusters and free the old ones.
+    fn write_snapshot_table(&mut self) -> BoxliteResult<()> {
+        let mut buf = Vec::new();
+        for snapshot in &self.snapshots {
+            snapshot.encode(&mut buf);
+        }
+        let offset = self.alloc_clusters(self.clusters_for(buf.len()))?;
+        self.write_at(offset, &buf)?;
+
+        if self.snapshots_offset != 0 && self.snapshots_len != 0 {
+            let old = self.snapshots_offset;
+            for i in 0..self.clusters_for(self.snapshots_len) {
+                self.adjust_refcount(old + (i << self.cluster_bits), -1)?;
+            }
+        }
+        self.snapshots_offset = offset;
+        self.snapshots_len = buf.len();
+        Ok(())
+    }
+
+    fn write_snapshot_header(&self) -> BoxliteResult<()> {
+        let mut buf = [0u8; 12];
+        buf[0..4].copy_from_slice(&(self.snapshots.len() as u32).to_be_bytes());
+        buf[4..12].copy_from_slice(&self.snapshots_offset.to_be_bytes());
+        self.write_at(NB_SNAPSHOTS_OFFSET, &buf)
+    }
+
+    /// Add `addend` to the refcount of everything `l1` reaches.
+    ///
+    /// Unless references are being dropped, COPIED flags in the L2 tables
+    /// (written back in place) and in `l1` are recomputed from the new
+    /// counts. Tables being released keep their flags: they may still be the
+    /// active ones on disk, and must not claim clusters they share.
+    fn update_refcounts(&mut self, l1: &mut [u64], addend: i32) -> BoxliteResult<()> {
+        let l2_entries = (self.cluster_size() / 8) as usize;
+        for l1_entry in l1.iter_mut() {
+            let l2_offset = *l1_entry & OFFSET_MASK;
+            if l2_offset == 0 {
+                continue;
+            }
+
+            let mut l2 = self.read_table(l2_offset, l2_entries)?;
+            let mut l2_changed = false;
+            for l2_entry in l2.iter_mut() {
+                if *l2_entry & OFLAG_COMPRESSED != 0 {
+                    return Err(BoxliteError::Unsupported(format!(
+                        "snapshots of {} are not supported: compressed clusters",
+                        self.path.display()
+                    )));
+                }
+                let data_offset = *l2_entry & OFFSET_MASK;
+                if data_offset == 0 {
+                    continue;
+                }
+                let refcount = if addend != 0 {
+                    self.adjust_refcount(data_offset, addend)?
+                } else {
+                    self.refcount(data_offset)?
+                };
+                if addend >= 0 {
+                    let flagged = with_copied(*l2_entry, refcount);
+                    l2_changed |= flagged != *l2_entry;
+                    *l2_entry = flagged;
+                }
+            }
+            if l2_changed {
+                self.write_table(l2_offset, &l2)?;
+            }
+
+            let refcount = if addend != 0 {
+                self.adjust_refcount(l2_offset, addend)?
+            } else {
+                self.refcount(l2_offset)?
+            };
+            if addend >= 0 {
+                *l1_entry = with_copied(*l1_entry, refcount);
+            }
+        }
+        Ok(())
+    }
+
+    /// Entries per refcount block (16-bit refcounts).
+    fn refcounts_per_block(&self) -> u64 {
+        self.cluster_size() / 2
+    }
+
+    /// Load the refcount block at `table_index`; unallocated blocks read as zeros.
+    fn refcount_block(&mut self, table_index: usize) -> BoxliteResult<&mut Vec<u16>> {
+        if !self.refcount_blocks.contains_key(&table_index) {
+            let offset = self.refcount_table[table_index] & OFFSET_MASK;
+            let block = if offset == 0 {
+                vec![0; self.refcounts_per_block() as usize]
+            } else {
+                self.read_at(offset, self.cluster_size() as usize)?
+                    .chunks_exact(2)
+                    .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                    .collect()
+            };
+            self.refcount_blocks.insert(table_index, block);
+        }
+        Ok(self.refcount_blocks.get_mut(&table_index).unwrap())
+    }
+
+    fn refcount(&mut self, offset: u64) -> BoxliteResult<u16> {
+        let cluster = offset >> self.cluster_bits;
+        let table_index = (cluster / self.refcounts_per_block()) as usize;
+        if table_index >= self.refcount_table.len() {
+            return Ok(0);
+        }
+        let index = (cluster % self.refcounts_per_block()) as usize;
+        Ok(self.refcount_block(table_index)?[index])
+    }
+
+    fn adjust_refcount(&mut self, offset: u64, addend: i32) -> BoxliteResult<u16> {
+        let cluster = offset >> self.cluster_bits;
+        let table_index = (cluster / self.refcounts_per_block()) as usize;
+        if table_index >= self.refcount_table.len() {
+            return Err(BoxliteError::Unsupported(format!(
+                "refcount table of {} is full",
+                self.path.display()
+            )));
+        }
+        let index = (cluster % self.refcounts_per_block()) as usize;
+        let path = self.path.clone();
+        let block = self.refcount_block(table_index)?;
+        let refcount = u16::try_from(block[index] as i32 + addend).map_err(|_| {
+            BoxliteError::Storage(format!(
+                "Refcount of cluster {:#x} in {} out of range ({} {:+})",
+                offset,
+                path.display(),
+                block[index],
+                addend
+            ))
+        })?;
+        block[index] = refcount;
+        self.dirty_blocks.insert(table_index);
+        Ok(refcount)
+    }
+
+    /// Allocate `count` contiguous clusters at the end of the file.
+    fn alloc_clusters(&mut self, count: u64) -> BoxliteResult<u64> {
+        let offset = self.end;
+        self.end += count << self.cluster_bits;
+        for i in 0..count {
+            self.adjust_refcount(offset + (i << self.cluster_bits), 1)?;
+        }
+        Ok(offset)
+    }
+
+    /// Write changed refcount blocks, allocating clusters for new ones.
+    fn flush_refcounts(&mut self) -> BoxliteResult<()> {
+        // A new block's own cluster may land in another new block.
+        loop {
+            let missing: Vec<usize> = self
+                .dirty_blocks
+                .iter()
+                .copied()
+                .filter(|&i| self.refcount_table[i] & OFFSET_MASK == 0)
+                .collect();
+            if missing.is_empty() {
+                break;
+            }
+            for table_index in missing {
+                self.refcount_table[table_index] = self.alloc_clusters(1)?;
+                self.refcount_table_dirty = true;
+            }
+        }
+
+        for &table_index in &self.dirty_blocks {
+            let buf: Vec<u8> = self.refcount_blocks[&table_index]
+                .iter()
+                .flat_map(|r| r.to_be_bytes())
+                .collect();
+            self.write_at(self.refcount_table[table_index] & OFFSET_MASK, &buf)?;
+        }
+        self.dirty_blocks.clear();
+
+        if self.refcount_table_dirty {
+            self.write_table(self.refcount_table_offset, &self.refcount_table)?;
+            self.refcount_table_dirty = false;
+        }
+        Ok(())
+    }
+}
+
+/// Set or clear COPIED on an L1/L2 entry to match its refcount.
+fn with_copied(entry: u64, refcount: u16) -> u64 {
+    if refcount == 1 {
+        entry | OFLAG_COPIED
+    } else {
+        entry & !OFLAG_COPIED
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Write one guest cluster the way a qcow2 driver would: copy shared L2
+    /// tables and data clusters, write in place when COPIED.
+    fn write_cluster(path: &Path, virtual_cluster: u64, data: &[u8]) {
+        let mut image = Image::open(path, true).unwrap();
+        let l2_entries = image.cluster_size() / 8;
+        let l1_index = (virtual_cluster / l2_entries) as usize;
+        let l2_index = (virtual_cluster % l2_entries) as usize;
+
+        let mut l2_offset = image.l1[l1_index] & OFFSET_MASK;
+        let mut l2 = if l2_offset == 0 {
+            vec![0; l2_entries as usize]
+        } else {
+            image.read_table(l2_offset, l2_entries as usize).unwrap()
+        };
+        if image.l1[l1_index] & OFLAG_COPIED == 0 {
+            let copy = image.alloc_clusters(1).unwrap();
+            if l2_offset != 0 {
+                image.adjust_refcount(l2_offset, -1).unwrap();
+            }
+            l2_offset = copy;
+        }
+
+        let old = l2[l2_index] & OFFSET_MASK;
+        let data_offset = if l2[l2_index] & OFLAG_COPIED != 0 {
+            old
+        } else {
+            let fresh = image.alloc_clusters(1).unwrap();
+            if old != 0 {
+                image.adjust_refcount(old, -1).unwrap();
+            }
+            fresh
+        };
+        image.write_at(data_offset, data).unwrap();
+        l2[l2_index] = data_offset | OFLAG_COPIED;
+        image.write_table(l2_offset, &l2).unwrap();
+        image.l1[l1_index] = l2_offset | OFLAG_COPIED;
+        image.write_table(image.l1_offset, &image.l1).unwrap();
+        image.flush_refcounts().unwrap();
+    }
+
+    fn read_cluster(path: &Path, virtual_cluster: u64) -> Vec<u8> {
+        let image = Image::open(path, false).unwrap();
+        let cluster_size = image.cluster_size() as usize;
+        let l2_entries = image.cluster_size() / 8;
+        let l2_offset = image.l1[(virtual_cluster / l2_entries) as usize] & OFFSET_MASK;
+        if l2_offset == 0 {
+            return vec![0; cluster_size];
+        }
+        let l2 = image.read_table(l2_offset, l2_entries as usize).unwrap();
+        match l2[(virtual_cluster % l2_entries) as usize] & OFFSET_MASK {
+            0 => vec![0; cluster_size],
+            offset => image.read_at(offset, cluster_size).unwrap(),
+        }
+    }
+
+    fn new_disk(dir: &TempDir) -> (PathBuf, usize) {
+        let path = dir.path().join("scratch.qcow2");
+        Qcow2Helper::create_disk(&path, true).unwrap();
+        let cluster_size = Image::open(&path, false).unwrap().cluster_size() as usize;
+        (path, cluster_size)
+    }
+
+    #[test]
+    fn restore_reverts_writes_made_after_snapshot() {
+        let dir = TempDir::new().unwrap();
+        let (disk, cluster_size) = new_disk(&dir);
+        write_cluster(&disk, 0, &vec![0xAA; cluster_size]);
+
+        let snapshot = Qcow2Helper::create_snapshot(&disk, "base").unwrap();
+        assert_eq!(snapshot.id, "1");
+        assert_eq!(snapshot.name, "base");
+
+        write_cluster(&disk, 0, &vec![0xBB; cluster_size]);
+        write_cluster(&disk, 3, &vec![0xCC; cluster_size]);
+        assert_eq!(read_cluster(&disk, 0), vec![0xBB; cluster_size]);
+
+        Qcow2Helper::restore_snapshot(&disk, "base").unwrap();
+        assert_eq!(read_cluster(&disk, 0), vec![0xAA; cluster_size]);
+        assert_eq!(read_cluster(&disk, 3), vec![0; cluster_size]);
+
+        // The snapshot survives a restore and later writes.
+        write_cluster(&disk, 0, &vec![0xDD; cluster_size]);
+        Qcow2Helper::restore_snapshot(&disk, "base").unwrap();
+        assert_eq!(read_cluster(&disk, 0), vec![0xAA; cluster_size]);
+    }
+
+    #[test]
+    fn list_snapshots_returns_entries_in_creation_order() {
+        let dir = TempDir::new().unwrap();
+        let (disk, cluster_size) = new_disk(&dir);
+        assert!(Qcow2Helper::list_snapshots(&disk).unwrap().is_empty());
+
+        Qcow2Helper::create_snapshot(&disk, "empty").unwrap();
+        write_cluster(&disk, 1, &vec![0x11; cluster_size]);
+        Qcow2Helper::create_snapshot(&disk, "one-cluster").unwrap();
+
+        let snapshots = Qcow2Helper::list_snapshots(&disk).unwrap();
+        let names: Vec<_> = snapshots.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["empty", "one-cluster"]);
+        assert_eq!(snapshots[1].id, "2");
+        assert_eq!(
+            snapshots[0].virtual_size,
+            Qcow2Helper::qcow2_virtual_size(&disk).unwrap()
+        );
+
+        Qcow2Helper::restore_snapshot(&disk, "empty").unwrap();
+        assert_eq!(read_cluster(&disk, 1), vec![0; cluster_size]);
+        Qcow2Helper::restore_snapshot(&disk, "one-cluster").unwrap();
+        assert_eq!(read_cluster(&disk, 1), vec![0x11; cluster_size]);
+    }
+
+    #[test]
+    fn duplicate_and_unknown_names_are_rejected() {
+        let dir = TempDir::new().unwrap();
+        let (disk, _) = new_disk(&dir);
+        Qcow2Helper::create_snapshot(&disk, "base").unwrap();
+
+        assert!(matches!(
+            Qcow2Helper::create_snapshot(&disk, "base"),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            Qcow2Helper::restore_snapshot(&disk, "missing"),
+            Err(BoxliteError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn non_qcow2_disk_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let raw = dir.path().join("raw.img");
+        std::fs::write(&raw, vec![0u8; 4096]).unwrap();
+
+        assert!(matches!(
+            Qcow2Helper::create_snapshot(&raw, "base"),
+            Err(BoxliteError::Storage(_))
+        ));
+        assert!(matches!(
+            Qcow2Helper::list_snapshots(&raw),
+            Err(BoxliteError::Storage(_))
+        ));
+    }
+
+    #[test]
+    fn attached_disk_cannot_be_snapshotted() {
+        let dir = TempDir::new().unwrap();
+        let (disk, _) = new_disk(&dir);
+
+        let attached = lock_attached_disk(&disk).unwrap();
+        assert!(matches!(
+            Qcow2Helper::create_snapshot(&disk, "base"),
+            Err(BoxliteError::InvalidState(_))
+        ));
+        assert!(Qcow2Helper::list_snapshots(&disk).unwrap().is_empty());
+
+        drop(attached);
+        Qcow2Helper::create_snapshot(&disk, "base").unwrap();
+    }
+}
@@ -1,9 +1,10 @@
 //! Auto-detecting decompressor for OCI layer tarballs.
 //!
-//! OCI allows both gzip-compressed (`application/vnd.oci.image.layer.v1.tar+gzip`)
-//! and uncompressed layer media types. We sniff the 2-byte gzip magic instead
-//! of trusting the media type, so the extractor and the diff-ID verifier share
-//! one source of truth.
+//! OCI allows gzip-compressed (`application/vnd.oci.image.layer.v1.tar+gzip`),
+//! zstd-compressed (`...tar+zstd`) and uncompressed layer media types. We sniff
+//! the compression magic instead of trusting the media type, so the extractor
+//! and the diff-ID verifier share one source of truth, and layers whose
+//! registry-reported media type is wrong or generic still extract.
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use flate2::read::GzDecoder;
@@ -12,15 +13,22 @@ use std::io::{BufReader, Read};
 use std::path::Path;
 use tracing::debug;
 
-/// Opens OCI layer tarballs, transparently decompressing gzip when present.
+/// gzip member header.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+/// zstd frame header.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Opens OCI layer tarballs, transparently decompressing gzip or zstd when
+/// present.
 pub(super) struct TarballReader;
 
 impl TarballReader {
     /// Return a reader over the uncompressed tar stream. Detects gzip by the
-    /// two-byte magic `1f 8b`; anything else is treated as raw tar.
+    /// magic `1f 8b` and zstd by `28 b5 2f fd`; anything else is treated as
+    /// raw tar. Decompression is streaming in both cases.
     pub(super) fn open(tarball_path: &Path) -> BoxliteResult<Box<dyn Read>> {
-        let mut header = [0u8; 2];
-        {
+        let mut header = [0u8; 4];
+        let header_len = {
             let probe = fs::File::open(tarball_path).map_err(|e| {
                 BoxliteError::Storage(format!(
                     "Failed to open layer tarball {}: {}",
@@ -28,10 +36,17 @@ impl TarballReader {
                     e
                 ))
             })?;
-            (&probe).take(2).read_exact(&mut header).map_err(|e| {
-                BoxliteError::Storage(format!("Failed to read layer header: {}", e))
-            })?;
+            read_header(&probe, &mut header)
+                .map_err(|e| BoxliteError::Storage(format!("Failed to read layer header: {}", e)))?
+        };
+        if header_len < GZIP_MAGIC.len() {
+            return Err(BoxliteError::Storage(format!(
+                "Layer tarball {} is too short ({} bytes)",
+                tarball_path.display(),
+                header_len
+            )));
         }
+        let header = &header[..header_len];
 
         let file = fs::File::open(tarball_path).map_err(|e| {
             BoxliteError::Storage(format!(
@@ -41,9 +56,19 @@ impl TarballReader {
             ))
         })?;
 
-        if header == [0x1f, 0x8b] {
+        if header.starts_with(&GZIP_MAGIC) {
             debug!("Detected gzip compression for {}", tarball_path.display());
             Ok(Box::new(GzDecoder::new(BufReader::new(file))))
+        } else if header.starts_with(&ZSTD_MAGIC) {
+            debug!("Detected zstd compression for {}", tarball_path.display());
+            let decoder = zstd::Decoder::with_buffer(BufReader::new(file)).map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "Failed to initialize zstd decoder for {}: {}",
+                    tarball_path.display(),
+                    e
+                ))
+            })?;
+            Ok(Box::new(decoder))
         } else {
             debug!(
                 "Detected uncompressed tarball for {}",
@@ -53,3 +78,18 @@ impl TarballReader {
         }
     }
 }
+
+/// Fill `buf` from the start of `file`, stopping early at EOF. Returns the
+/// number of bytes read.
+fn read_header(mut file: &fs::File, buf: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match file.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,572 @@
+//! Layer diff generation for committed boxes.
+//!
+//! Compares a full rootfs tar (as packed by the guest) against an image's
+//! base layers and writes the difference as a gzip-compressed OCI layer:
+//! entries that are new or whose metadata changed, plus `.wh.` whiteouts for
+//! base paths that no longer exist. Like `docker commit`, change detection
+//! is metadata-based (type, mode, owner, size, mtime, link target).
+//!
+//! Imported rootfs trees and tarballs (`docker import`) are written through
+//! the same layer writer as a single full layer.
+
+use super::compression::TarballReader;
+use crate::images::storage::HashingWriter;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Component, Path, PathBuf};
+use tar::{EntryType, Header};
+
+const WHITEOUT_PREFIX: &str = ".wh.";
+const OPAQUE_WHITEOUT: &str = ".wh..wh..opq";
+
+/// Files the runtime manages per box; never baked into a committed layer.
+const RUNTIME_MANAGED: &[&str] = &["etc/hosts", "etc/hostname", "etc/resolv.conf"];
+
+/// Result of [`write_layer_diff`].
+#[derive(Debug, Clone)]
+pub struct LayerDiff {
+    /// `sha256:<hex>` of the uncompressed layer tar.
+    pub diff_id: String,
+    /// `sha256:<hex>` of the compressed layer blob.
+    pub digest: String,
+    /// Compressed blob size in bytes.
+    pub size: u64,
+    /// Number of added or modified entries.
+    pub changed: usize,
+    /// Number of whiteouts written.
+    pub removed: usize,
+}
+
+/// Uncompressed layer tar stream: hashed for the diff id, then gzipped and
+/// hashed again for the blob digest.
+type LayerWriter = HashingWriter<GzEncoder<HashingWriter<fs::File>>>;
+
+/// Metadata used to decide whether a rootfs entry differs from the base.
+#[derive(Debug, Clone, PartialEq)]
+struct EntryMeta {
+    kind: EntryType,
+    mode: u32,
+    uid: u64,
+    gid: u64,
+    size: u64,
+    mtime: u64,
+    link: Option<PathBuf>,
+}
+
+impl EntryMeta {
+    fn from_header(header: &Header, link: Option<PathBuf>) -> io::Result<Self> {
+        let kind = header.entry_type();
+        let is_file = kind.is_file();
+        Ok(Self {
+            kind,
+            mode: header.mode()? & 0o7777,
+            uid: header.uid()?,
+            gid: header.gid()?,
+            // Only file sizes are meaningful; directory sizes vary by tool.
+            size: if is_file { header.size()? } else { 0 },
+            mtime: header.mtime()?,
+            link,
+        })
+    }
+}
+
+/// Write the diff between `rootfs_tar` and `base_layers` to `output`.
+///
+/// `base_layers` are layer tarballs ordered bottom to top (gzip or raw).
+pub fn write_layer_diff(
+    base_layers: &[PathBuf],
+    rootfs_tar: &Path,
+    output: &Path,
+) -> BoxliteResult<LayerDiff> {
+    let base = index_base_layers(base_layers)?;
+    write_layer(output, |builder| write_entries(&base, rootfs_tar, builder))
+}
+
+/// Compress an overlay upper layer tar (as packed by the guest) to `output`.
+///
+/// The upper tar already is a diff with `.wh.` whiteouts, so entries are
+/// copied as-is except for runtime-managed files.
+pub fn write_upper_layer(upper_tar: &Path, output: &Path) -> BoxliteResult<LayerDiff> {
+    write_layer(output, |builder| copy_upper_entries(upper_tar, builder))
+}
+
+/// Pack the rootfs directory `dir` into a single layer at `output`.
+///
+/// Symlinks are stored as links, not followed.
+pub fn write_directory_layer(dir: &Path, output: &Path) -> BoxliteResult<LayerDiff> {
+    write_layer(output, |builder| append_tree(dir, builder))
+}
+
+/// Repack the rootfs tarball `tarball` (gzip or raw) into a single layer at
+/// `output`.
+pub fn write_tarball_layer(tarball: &Path, output: &Path) -> BoxliteResult<LayerDiff> {
+    let reader = TarballReader::open(tarball)?;
+    write_layer(output, |builder| copy_tar_entries(reader, builder))
+}
+
+/// Write a gzip-compressed layer to `output` with the entries `fill` appends.
+fn write_layer(
+    output: &Path,
+    fill: impl FnOnce(&mut tar::Builder<LayerWriter>) -> io::Result<(usize, usize)>,
+) -> BoxliteResult<LayerDiff> {
+    let file = fs::File::create(output).map_err(|e| {
+        BoxliteError::Storage(format!(
+            "Failed to create layer {}: {}",
+            output.display(),
+            e
+        ))
+    })?;
+    let compressed = HashingWriter::new(file);
+    let gz = GzEncoder::new(compressed, Compression::default());
+    let mut builder = tar::Builder::new(HashingWriter::new(gz));
+
+    let (changed, removed) = fill(&mut builder)
+        .map_err(|e| BoxliteError::Storage(format!("Failed to write layer diff: {}", e)))?;
+
+    let finish = || -> io::Result<(String, String, u64)> {
+        let (gz, diff_hash, _) = builder.into_inner()?.finalize();
+        let (file, digest_hash, size) = gz.finish()?.finalize();
+        file.sync_all()?;
+        Ok((diff_hash, digest_hash, size))
+    };
+    let (diff_hash, digest_hash, size) = finish()
+        .map_err(|e| BoxliteError::Storage(format!("Failed to finish layer diff: {}", e)))?;
+
+    Ok(LayerDiff {
+        diff_id: format!("sha256:{}", diff_hash),
+        digest: format!("sha256:{}", digest_hash),
+        size,
+        changed,
+        removed,
+    })
+}
+
+/// Merge the base layers into one path -> metadata view, applying whiteouts.
+fn index_base_layers(layers: &[PathBuf]) -> BoxliteResult<BTreeMap<PathBuf, EntryMeta>> {
+    let mut index = BTreeMap::new();
+    for layer in layers {
+        let reader = TarballReader::open(layer)?;
+        apply_layer(&mut index, reader).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to read base layer {}: {}",
+                layer.display(),
+                e
+            ))
+        })?;
+    }
+    Ok(index)
+}
+
+fn apply_layer<R: Read>(index: &mut BTreeMap<PathBuf, EntryMeta>, reader: R) -> io::Result<()> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let entry = entry?;
+        let Some(path) = normalize(&entry.path()?) else {
+            continue;
+        };
+        let name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default();
+        let parent = path.parent().unwrap_or(Path::new("")).to_path_buf();
+
+        if name == OPAQUE_WHITEOUT {
+            remove_descendants(index, &parent);
+            continue;
+        }
+        if let Some(hidden) = name.strip_prefix(WHITEOUT_PREFIX) {
+            let target = parent.join(hidden);
+            index.remove(&target);
+            remove_descendants(index, &target);
+            continue;
+        }
+
+        let header = entry.header();
+        let link = entry.link_name()?.map(|l| l.into_owned());
+        let meta = if header.entry_type().is_hard_link() {
+            // The guest packs hard links as regular files; compare against
+            // the link target so they don't count as changes.
+            match link
+                .as_deref()
+                .and_then(normalize)
+                .and_then(|t| index.get(&t))
+            {
+                Some(target) => target.clone(),
+                None => EntryMeta::from_header(header, link)?,
+            }
+        } else {
+            EntryMeta::from_header(header, link)?
+        };
+        if !meta.kind.is_dir() {
+            remove_descendants(index, &path);
+        }
+        index.insert(path, meta);
+    }
+    Ok(())
+}
+
+fn remove_descendants(index: &mut BTreeMap<PathBuf, EntryMeta>, dir: &Path) {
+    let doomed: Vec<PathBuf> = index
+        .keys()
+        .filter(|p| p.starts_with(dir) && p.as_path() != dir)
+        .cloned()
+        .collect();
+    for path in doomed {
+        index.remove(&path);
+    }
+}
+
+/// Copy changed rootfs entries into `builder`, then add whiteouts.
+///
+/// Returns `(changed, removed)`.
+fn write_entries<W: io::Write>(
+    base: &BTreeMap<PathBuf, EntryMeta>,
+    rootfs_tar: &Path,
+    builder: &mut tar::Builder<W>,
+) -> io::Result<(usize, usize)> {
+    // Path -> whether it is a directory, for every path in the rootfs.
+    let mut seen: HashMap<PathBuf, bool> = HashMap::new();
+    let mut changed = 0;
+
+    let mut archive = tar::Archive::new(io::BufReader::new(fs::File::open(rootfs_tar)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(path) = normalize(&entry.path()?) else {
+            continue;
+        };
+        if is_excluded(&path, base) {
+            continue;
+        }
+
+        let link = entry.link_name()?.map(|l| l.into_owned());
+        let meta = EntryMeta::from_header(entry.header(), link.clone())?;
+        seen.insert(path.clone(), meta.kind.is_dir());
+        if base.get(&path) == Some(&meta) {
+            continue;
+        }
+
+        let mut header = entry.header().clone();
+        match link {
+            Some(target) if meta.kind.is_symlink() || meta.kind.is_hard_link() => {
+                builder.append_link(&mut header, &path, target)?;
+            }
+            _ => builder.append_data(&mut header, &path, &mut entry)?,
+        }
+        changed += 1;
+    }
+
+    let mut removed = 0;
+    for path in base.keys() {
+        if seen.contains_key(path) {
+            continue;
+        }
+        // Only the topmost removed path needs a whiteout.
+        let parent = path.parent().unwrap_or(Path::new(""));
+        if !parent.as_os_str().is_empty() && seen.get(parent) != Some(&true) {
+            continue;
+        }
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+        let mut whiteout_name = std::ffi::OsString::from(WHITEOUT_PREFIX);
+        whiteout_name.push(name);
+
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Regular);
+        header.set_size(0);
+        header.set_mode(0o644);
+        builder.append_data(&mut header, parent.join(whiteout_name), io::empty())?;
+        removed += 1;
+    }
+
+    Ok((changed, removed))
+}
+
+/// Copy upper layer entries into `builder`, skipping runtime-managed files.
+///
+/// Returns `(changed, removed)`, counting `.wh.` entries as removals.
+fn copy_upper_entries<W: io::Write>(
+    upper_tar: &Path,
+    builder: &mut tar::Builder<W>,
+) -> io::Result<(usize, usize)> {
+    let (mut changed, mut removed) = (0, 0);
+
+    let mut archive = tar::Archive::new(io::BufReader::new(fs::File::open(upper_tar)?));
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(path) = normalize(&entry.path()?) else {
+            continue;
+        };
+        if RUNTIME_MANAGED.iter().any(|p| path == Path::new(p)) {
+            continue;
+        }
+
+        let link = entry.link_name()?.map(|l| l.into_owned());
+        let mut header = entry.header().clone();
+        let kind = header.entry_type();
+        match link {
+            Some(target) if kind.is_symlink() || kind.is_hard_link() => {
+                builder.append_link(&mut header, &path, target)?;
+            }
+            _ => builder.append_data(&mut header, &path, &mut entry)?,
+        }
+
+        let is_whiteout = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with(WHITEOUT_PREFIX));
+        if is_whiteout {
+            removed += 1;
+        } else {
+            changed += 1;
+        }
+    }
+
+    Ok((changed, removed))
+}
+
+/// Append every entry under `dir`, sorted by name, with paths relative to it.
+///
+/// Returns `(added, 0)`.
+fn append_tree<W: io::Write>(
+    dir: &Path,
+    builder: &mut tar::Builder<W>,
+) -> io::Result<(usize, usize)> {
+    builder.follow_symlinks(false);
+    let mut added = 0;
+    for entry in walkdir::WalkDir::new(dir)
+        .min_depth(1)
+        .follow_links(false)
+        .sort_by_file_name()
+    {
+        let entry = entry?;
+        let path = entry
+            .path()
+            .strip_prefix(dir)
+            .map_err(|e| io::Error::other(e.to_string()))?;
+        builder.append_path_with_name(entry.path(), path)?;
+        added += 1;
+    }
+    Ok((added, 0))
+}
+
+/// Copy every entry of the tar stream `reader` with normalized paths.
+///
+/// Returns `(added, 0)`.
+fn copy_tar_entries<R: Read, W: io::Write>(
+    reader: R,
+    builder: &mut tar::Builder<W>,
+) -> io::Result<(usize, usize)> {
+    let mut added = 0;
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let Some(path) = normalize(&entry.path()?) else {
+            continue;
+        };
+
+        let link = entry.link_name()?.map(|l| l.into_owned());
+        let mut header = entry.header().clone();
+        let kind = header.entry_type();
+        match link {
+            Some(target) if kind.is_symlink() || kind.is_hard_link() => {
+                builder.append_link(&mut header, &path, target)?;
+            }
+            _ => builder.append_data(&mut header, &path, &mut entry)?,
+        }
+        added += 1;
+    }
+    Ok((added, 0))
+}
+
+/// Paths that never belong in a committed layer.
+fn is_excluded(path: &Path, base: &BTreeMap<PathBuf, EntryMeta>) -> bool {
+    if RUNTIME_MANAGED.iter().any(|p| path == Path::new(p)) {
+        return true;
+    }
+    // mkfs artifact of the rootfs disk, not part of the image.
+    let lost_found = Path::new("lost+found");
+    (path == lost_found || path.starts_with(lost_found)) && !base.contains_key(lost_found)
+}
+
+/// Strip `./`, leading `/` and trailing separators; `None` for the root.
+fn normalize(path: &Path) -> Option<PathBuf> {
+    let normalized: PathBuf = path
+        .components()
+        .filter(|c| matches!(c, Component::Normal(_)))
+        .collect();
+    (!normalized.as_os_str().is_empty()).then_some(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn append_file(builder: &mut tar::Builder<Vec<u8>>, path: &str, content: &[u8], mtime: u64) {
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Regular);
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        builder.append_data(&mut header, path, content).unwrap();
+    }
+
+    fn append_dir(builder: &mut tar::Builder<Vec<u8>>, path: &str) {
+        let mut header = Header::new_gnu();
+        header.set_entry_type(EntryType::Directory);
+        header.set_size(0);
+        header.set_mode(0o755);
+        builder.append_data(&mut header, path, io::empty()).unwrap();
+    }
+
+    fn write_tar(path: &Path, build: impl FnOnce(&mut tar::Builder<Vec<u8>>)) {
+        let mut builder = tar::Builder::new(Vec::new());
+        build(&mut builder);
+        fs::write(path, builder.into_inner().unwrap()).unwrap();
+    }
+
+    fn entry_names(layer: &Path) -> Vec<String> {
+        let reader = TarballReader::open(layer).unwrap();
+        let mut archive = tar::Archive::new(reader);
+        archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn diff_contains_changes_and_whiteouts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let base = tmp.path().join("base.tar");
+        write_tar(&base, |b| {
+            append_dir(b, "etc/");
+            append_file(b, "etc/os-release", b"alpine", 1);
+            append_file(b, "etc/motd", b"hi", 1);
+            append_dir(b, "var/");
+            append_dir(b, "var/cache/");
+            append_file(b, "var/cache/a", b"a", 1);
+        });
+        let rootfs = tmp.path().join("rootfs.tar");
+        write_tar(&rootfs, |b| {
+            append_dir(b, "etc");
+            append_file(b, "etc/os-release", b"alpine", 1);
+            append_file(b, "etc/motd", b"hello", 2);
+            append_file(b, "etc/hosts", b"127.0.0.1 box", 3);
+            append_file(b, "committed.txt", b"new", 3);
+        });
+
+        let out = tmp.path().join("layer.tar.gz");
+        let diff = write_layer_diff(&[base], &rootfs, &out).unwrap();
+
+        assert_eq!(
+            entry_names(&out),
+            vec!["etc/motd", "committed.txt", ".wh.var"]
+        );
+        assert_eq!(diff.changed, 2);
+        assert_eq!(diff.removed, 1);
+        assert_eq!(diff.size, fs::metadata(&out).unwrap().len());
+        assert!(diff.diff_id.starts_with("sha256:"));
+        assert_ne!(diff.diff_id, diff.digest);
+    }
+
+    #[test]
+    fn base_whiteouts_hide_lower_entries() {
+        let tmp = tempfile::tempdir().unwrap();
+        let lower = tmp.path().join("lower.tar");
+        write_tar(&lower, |b| {
+            append_dir(b, "opt/");
+            append_file(b, "opt/old", b"x", 1);
+            append_file(b, "gone", b"x", 1);
+        });
+        let upper = tmp.path().join("upper.tar");
+        write_tar(&upper, |b| {
+            append_file(b, "opt/.wh..wh..opq", b"", 1);
+            append_file(b, ".wh.gone", b"", 1);
+        });
+        let rootfs = tmp.path().join("rootfs.tar");
+        write_tar(&rootfs, |b| append_dir(b, "opt"));
+
+        let out = tmp.path().join("layer.tar.gz");
+        let diff = write_layer_diff(&[lower, upper], &rootfs, &out).unwrap();
+
+        assert!(entry_names(&out).is_empty());
+        assert_eq!((diff.changed, diff.removed), (0, 0));
+    }
+
+    #[test]
+    fn normalize_strips_dot_and_trailing_slash() {
+        assert_eq!(normalize(Path::new("./etc/")), Some(PathBuf::from("etc")));
+        assert_eq!(
+            normalize(Path::new("/usr/bin")),
+            Some(PathBuf::from("usr/bin"))
+        );
+        assert_eq!(normalize(Path::new("./")), None);
+    }
+
+    #[test]
+    fn upper_layer_keeps_whiteouts_and_drops_runtime_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        let upper = tmp.path().join("upper.tar");
+        write_tar(&upper, |b| {
+            append_dir(b, "etc");
+            append_file(b, "etc/.wh.motd", b"", 0);
+            append_file(b, "etc/resolv.conf", b"nameserver 10.0.2.3", 2);
+            append_dir(b, "opt");
+            append_file(b, "opt/.wh..wh..opq", b"", 0);
+            append_file(b, "opt/app", b"new", 2);
+        });
+
+        let out = tmp.path().join("layer.tar.gz");
+        let diff = write_upper_layer(&upper, &out).unwrap();
+
+        assert_eq!(
+            entry_names(&out),
+            vec!["etc", "etc/.wh.motd", "opt", "opt/.wh..wh..opq", "opt/app"]
+        );
+        assert_eq!(diff.changed, 3);
+        assert_eq!(diff.removed, 2);
+        assert_eq!(diff.size, fs::metadata(&out).unwrap().len());
+    }
+
+    #[test]
+    fn directory_layer_packs_tree_relative_to_root() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path().join("rootfs");
+        fs::create_dir_all(root.join("etc")).unwrap();
+        fs::write(root.join("etc/os-release"), "imported").unwrap();
+        fs::write(root.join("marker"), "x").unwrap();
+        std::os::unix::fs::symlink("/nowhere", root.join("dangling")).unwrap();
+
+        let out = tmp.path().join("layer.tar.gz");
+        let diff = write_directory_layer(&root, &out).unwrap();
+
+        assert_eq!(
+            entry_names(&out),
+            vec!["dangling", "etc", "etc/os-release", "marker"]
+        );
+        assert_eq!((diff.changed, diff.removed), (4, 0));
+        assert_eq!(diff.size, fs::metadata(&out).unwrap().len());
+    }
+
+    #[test]
+    fn tarball_layer_normalizes_paths() {
+        let tmp = tempfile::tempdir().unwrap();
+        let rootfs = tmp.path().join("rootfs.tar");
+        write_tar(&rootfs, |b| {
+            append_dir(b, "./");
+            append_dir(b, "./bin/");
+            append_file(b, "./bin/app", b"app", 1);
+        });
+
+        let out = tmp.path().join("layer.tar.gz");
+        let diff = write_tarball_layer(&rootfs, &out).unwrap();
+
+        assert_eq!(entry_names(&out), vec!["bin", "bin/app"]);
+        assert_eq!(diff.changed, 2);
+    }
+}
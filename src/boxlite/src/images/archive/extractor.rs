@@ -1689,6 +1689,35 @@ mod tests {
         assert_eq!(content, "test content");
     }
 
+    #[test]
+    fn test_zstd_compression_detection() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let tar_path = temp_dir.path().join("test.tar.zst");
+
+        let entries = vec![
+            TestEntry {
+                path: "etc/".to_string(),
+                entry_type: TestEntryType::Directory,
+            },
+            TestEntry {
+                path: "etc/os-release".to_string(),
+                entry_type: TestEntryType::File {
+                    content: b"ID=zstd\n".to_vec(),
+                },
+            },
+        ];
+
+        let tar_data = create_test_tar(entries);
+        let zstd_data = zstd::encode_all(&tar_data[..], 0).unwrap();
+        std::fs::write(&tar_path, &zstd_data).unwrap();
+
+        let dest_dir = temp_dir.path().join("extracted");
+        extract(&tar_path, &dest_dir).unwrap();
+
+        let content = std::fs::read_to_string(dest_dir.join("etc/os-release")).unwrap();
+        assert_eq!(content, "ID=zstd\n");
+    }
+
     #[test]
     fn test_uncompressed_tar_detection() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -1,12 +1,13 @@
 //! Archive helpers (containerd-style apply).
 //!
 //! Mirrors containerd's layout: `extractor` performs the streaming layer
-//! apply, `verifier` checks DiffIDs, `compression` opens tarballs with
+//! apply, `diff` computes a layer from a rootfs (or packs an imported one), `verifier` checks DiffIDs, `compression` opens tarballs with
 //! transparent gzip detection, `metadata` groups per-entry header data,
 //! `time` provides time helpers, `override_stat` provides rootless container
 //! support, `safe_root` enforces containment.
 
 mod compression;
+mod diff;
 mod extractor;
 mod metadata;
 mod override_stat;
@@ -14,5 +15,8 @@ mod safe_root;
 mod time;
 mod verifier;
 
+pub use diff::{
+    LayerDiff, write_directory_layer, write_layer_diff, write_tarball_layer, write_upper_layer,
+};
 pub use extractor::LayerExtractor;
 pub use verifier::LayerVerifier;
@@ -15,7 +15,7 @@
 use std::path::{Path, PathBuf};
 
 use crate::images::archive::LayerExtractor;
-use crate::images::storage::ImageStorage;
+use crate::images::blob_store::ImageBlobStore;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 // ============================================================================
@@ -99,12 +99,12 @@ impl BlobSource {
 #[derive(Clone, Debug)]
 pub struct StoreBlobSource {
     /// Shared reference to image storage
-    storage: std::sync::Arc<ImageStorage>,
+    storage: std::sync::Arc<dyn ImageBlobStore>,
 }
 
 impl StoreBlobSource {
     /// Create a new store blob source.
-    pub fn new(storage: std::sync::Arc<ImageStorage>) -> Self {
+    pub fn new(storage: std::sync::Arc<dyn ImageBlobStore>) -> Self {
         Self { storage }
     }
 
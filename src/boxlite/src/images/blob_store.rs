@@ -0,0 +1,306 @@
+//! Pluggable backend for image blobs.
+//!
+//! [`ImageBlobStore`] is everything the image store and `BlobSource` need
+//! from blob storage: existence checks, manifest reads and writes, staged
+//! downloads, and the paths layers are read and extracted from.
+//! [`ImageStorage`] is the default, keeping blobs under the images directory.
+//!
+//! Layers are consumed as files (the rootfs builders unpack or mount them),
+//! so a backend that keeps blobs elsewhere (object storage, a shared cache)
+//! still materializes them at the paths it returns.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use oci_client::manifest::OciManifest;
+
+use crate::images::storage::{ImageStorage, StagedDownload, StoredBlob};
+use boxlite_shared::errors::BoxliteResult;
+
+/// Storage backend for image manifests, configs, and layers.
+///
+/// Blobs are addressed by digest (`sha256:<hex>`). Implementations must be
+/// safe for concurrent use: parallel pulls stage and commit blobs at once.
+///
+/// Set [`BoxliteOptions::image_blob_store`](crate::BoxliteOptions::image_blob_store)
+/// to use a custom backend.
+#[async_trait]
+pub trait ImageBlobStore: Send + Sync + std::fmt::Debug {
+    // ========================================================================
+    // MANIFESTS
+    // ========================================================================
+
+    /// Persist `manifest` under `digest`. Idempotent.
+    fn save_manifest(&self, manifest: &OciManifest, digest: &str) -> BoxliteResult<()>;
+
+    /// Read back a manifest saved under `digest`.
+    fn load_manifest(&self, digest: &str) -> BoxliteResult<OciManifest>;
+
+    /// Whether a manifest is stored under `digest`.
+    fn has_manifest(&self, digest: &str) -> bool;
+
+    /// Where the manifest for `digest` lives.
+    fn manifest_path(&self, digest: &str) -> PathBuf;
+
+    // ========================================================================
+    // LAYERS
+    // ========================================================================
+
+    /// Whether the layer tarball for `digest` is stored.
+    fn has_layer(&self, digest: &str) -> bool;
+
+    /// Re-hash the stored layer tarball; `Ok(false)` when it does not match.
+    async fn verify_layer(&self, digest: &str) -> BoxliteResult<bool>;
+
+    /// Where the layer tarball for `digest` lives.
+    fn layer_tarball_path(&self, digest: &str) -> PathBuf;
+
+    /// Where the extracted layer for `digest` lives.
+    fn layer_extracted_path(&self, digest: &str) -> PathBuf;
+
+    /// Extract `tarball_path` to [`layer_extracted_path`](Self::layer_extracted_path),
+    /// keeping whiteout markers. Must tolerate concurrent extraction of the
+    /// same digest.
+    fn extract_layer(&self, digest: &str, tarball_path: &Path) -> BoxliteResult<()>;
+
+    /// Start a download of the layer tarball for `digest`.
+    ///
+    /// Committing the returned handle verifies the bytes and moves them to
+    /// [`layer_tarball_path`](Self::layer_tarball_path).
+    async fn stage_layer_download(
+        &self,
+        digest: &str,
+        expected_size: i64,
+    ) -> BoxliteResult<StagedDownload>;
+
+    // ========================================================================
+    // CONFIGS
+    // ========================================================================
+
+    /// Whether the config blob for `digest` is stored.
+    fn has_config(&self, digest: &str) -> bool;
+
+    /// Where the config blob for `digest` lives.
+    fn config_path(&self, digest: &str) -> PathBuf;
+
+    /// Start a download of the config blob for `digest`; committing moves it
+    /// to [`config_path`](Self::config_path).
+    async fn stage_config_download(&self, digest: &str) -> BoxliteResult<StagedDownload>;
+
+    // ========================================================================
+    // UTILITY
+    // ========================================================================
+
+    /// Whether every layer in `layer_digests` is stored.
+    fn verify_blobs_exist(&self, layer_digests: &[String]) -> bool {
+        layer_digests.iter().all(|digest| self.has_layer(digest))
+    }
+
+    /// Where the ext4 disk image built for `image_digest` lives.
+    fn disk_image_path(&self, image_digest: &str) -> PathBuf;
+
+    /// Every stored blob, for pruning and size accounting.
+    fn list_blobs(&self) -> BoxliteResult<Vec<StoredBlob>>;
+
+    /// Cache directory for a local OCI bundle at `bundle_path`.
+    fn local_bundle_cache_dir(&self, bundle_path: &Path, manifest_digest: &str) -> PathBuf;
+
+    /// Hardlink identical files across extracted layers.
+    ///
+    /// Backends without a layer pool ignore this.
+    fn set_layer_dedup(&mut self, _enabled: bool) {}
+
+    /// Remove layer pool entries no longer in use; returns how many.
+    fn sweep_layer_pool(&self) -> usize {
+        0
+    }
+}
+
+#[async_trait]
+impl ImageBlobStore for ImageStorage {
+    fn save_manifest(&self, manifest: &OciManifest, digest: &str) -> BoxliteResult<()> {
+        ImageStorage::save_manifest(self, manifest, digest)
+    }
+
+    fn load_manifest(&self, digest: &str) -> BoxliteResult<OciManifest> {
+        ImageStorage::load_manifest(self, digest)
+    }
+
+    fn has_manifest(&self, digest: &str) -> bool {
+        ImageStorage::has_manifest(self, digest)
+    }
+
+    fn manifest_path(&self, digest: &str) -> PathBuf {
+        ImageStorage::manifest_path(self, digest)
+    }
+
+    fn has_layer(&self, digest: &str) -> bool {
+        ImageStorage::has_layer(self, digest)
+    }
+
+    async fn verify_layer(&self, digest: &str) -> BoxliteResult<bool> {
+        ImageStorage::verify_layer(self, digest).await
+    }
+
+    fn layer_tarball_path(&self, digest: &str) -> PathBuf {
+        ImageStorage::layer_tarball_path(self, digest)
+    }
+
+    fn layer_extracted_path(&self, digest: &str) -> PathBuf {
+        ImageStorage::layer_extracted_path(self, digest)
+    }
+
+    fn extract_layer(&self, digest: &str, tarball_path: &Path) -> BoxliteResult<()> {
+        ImageStorage::extract_layer(self, digest, tarball_path)
+    }
+
+    async fn stage_layer_download(
+        &self,
+        digest: &str,
+        expected_size: i64,
+    ) -> BoxliteResult<StagedDownload> {
+        ImageStorage::stage_layer_download(self, digest, expected_size).await
+    }
+
+    fn has_config(&self, digest: &str) -> bool {
+        ImageStorage::has_config(self, digest)
+    }
+
+    fn config_path(&self, digest: &str) -> PathBuf {
+        ImageStorage::config_path(self, digest)
+    }
+
+    async fn stage_config_download(&self, digest: &str) -> BoxliteResult<StagedDownload> {
+        ImageStorage::stage_config_download(self, digest).await
+    }
+
+    fn verify_blobs_exist(&self, layer_digests: &[String]) -> bool {
+        ImageStorage::verify_blobs_exist(self, layer_digests)
+    }
+
+    fn disk_image_path(&self, image_digest: &str) -> PathBuf {
+        ImageStorage::disk_image_path(self, image_digest)
+    }
+
+    fn list_blobs(&self) -> BoxliteResult<Vec<StoredBlob>> {
+        ImageStorage::list_blobs(self)
+    }
+
+    fn local_bundle_cache_dir(&self, bundle_path: &Path, manifest_digest: &str) -> PathBuf {
+        ImageStorage::local_bundle_cache_dir(self, bundle_path, manifest_digest)
+    }
+
+    fn set_layer_dedup(&mut self, enabled: bool) {
+        ImageStorage::set_layer_dedup(self, enabled)
+    }
+
+    fn sweep_layer_pool(&self) -> usize {
+        ImageStorage::sweep_layer_pool(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::images::blob_source::{BlobSource, StoreBlobSource};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Delegates to a tempdir `ImageStorage`, counting layer path lookups.
+    #[derive(Debug)]
+    struct CountingStore {
+        inner: ImageStorage,
+        layer_lookups: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ImageBlobStore for CountingStore {
+        fn save_manifest(&self, manifest: &OciManifest, digest: &str) -> BoxliteResult<()> {
+            self.inner.save_manifest(manifest, digest)
+        }
+        fn load_manifest(&self, digest: &str) -> BoxliteResult<OciManifest> {
+            self.inner.load_manifest(digest)
+        }
+        fn has_manifest(&self, digest: &str) -> bool {
+            self.inner.has_manifest(digest)
+        }
+        fn manifest_path(&self, digest: &str) -> PathBuf {
+            self.inner.manifest_path(digest)
+        }
+        fn has_layer(&self, digest: &str) -> bool {
+            self.inner.has_layer(digest)
+        }
+        async fn verify_layer(&self, digest: &str) -> BoxliteResult<bool> {
+            self.inner.verify_layer(digest).await
+        }
+        fn layer_tarball_path(&self, digest: &str) -> PathBuf {
+            self.layer_lookups.fetch_add(1, Ordering::SeqCst);
+            self.inner.layer_tarball_path(digest)
+        }
+        fn layer_extracted_path(&self, digest: &str) -> PathBuf {
+            self.inner.layer_extracted_path(digest)
+        }
+        fn extract_layer(&self, digest: &str, tarball_path: &Path) -> BoxliteResult<()> {
+            self.inner.extract_layer(digest, tarball_path)
+        }
+        async fn stage_layer_download(
+            &self,
+            digest: &str,
+            expected_size: i64,
+        ) -> BoxliteResult<StagedDownload> {
+            self.inner.stage_layer_download(digest, expected_size).await
+        }
+        fn has_config(&self, digest: &str) -> bool {
+            self.inner.has_config(digest)
+        }
+        fn config_path(&self, digest: &str) -> PathBuf {
+            self.inner.config_path(digest)
+        }
+        async fn stage_config_download(&self, digest: &str) -> BoxliteResult<StagedDownload> {
+            self.inner.stage_config_download(digest).await
+        }
+        fn disk_image_path(&self, image_digest: &str) -> PathBuf {
+            self.inner.disk_image_path(image_digest)
+        }
+        fn list_blobs(&self) -> BoxliteResult<Vec<StoredBlob>> {
+            self.inner.list_blobs()
+        }
+        fn local_bundle_cache_dir(&self, bundle_path: &Path, manifest_digest: &str) -> PathBuf {
+            self.inner
+                .local_bundle_cache_dir(bundle_path, manifest_digest)
+        }
+    }
+
+    #[test]
+    fn blob_source_reads_through_custom_store() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Arc::new(CountingStore {
+            inner: ImageStorage::new(tmp.path().to_path_buf()).unwrap(),
+            layer_lookups: AtomicUsize::new(0),
+        });
+        let source = BlobSource::Store(StoreBlobSource::new(store.clone()));
+
+        let digest = "sha256:abc";
+        assert_eq!(
+            source.layer_tarball_path(digest),
+            store.inner.layer_tarball_path(digest)
+        );
+        assert_eq!(store.layer_lookups.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn default_store_verifies_blobs_through_trait() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store: Arc<dyn ImageBlobStore> =
+            Arc::new(ImageStorage::new(tmp.path().to_path_buf()).unwrap());
+
+        assert!(store.verify_blobs_exist(&[]));
+        assert!(!store.verify_blobs_exist(&["sha256:missing".to_string()]));
+
+        let tarball = store.layer_tarball_path("sha256:present");
+        std::fs::create_dir_all(tarball.parent().unwrap()).unwrap();
+        std::fs::write(tarball, b"layer").unwrap();
+        assert!(store.verify_blobs_exist(&["sha256:present".to_string()]));
+        assert_eq!(store.sweep_layer_pool(), 0);
+    }
+}
@@ -0,0 +1,205 @@
+//! In-memory cache of parsed image configs.
+//!
+//! Creating a box reads and parses its image's config blob several times
+//! (rootfs preparation, guest rootfs, container spec). Configs are
+//! content-addressed, so a parsed config can be shared by every box created
+//! from the same image until its blob is removed from the store.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use boxlite_shared::errors::BoxliteResult;
+use oci_spec::image::ImageConfiguration;
+
+/// Configs kept when `BoxliteOptions::image_config_cache_capacity` is unset.
+pub(crate) const DEFAULT_CONFIG_CACHE_CAPACITY: usize = 64;
+
+/// Least-recently-used cache of parsed image configs, keyed by config digest.
+///
+/// A capacity of 0 disables caching: every lookup goes to the loader.
+#[derive(Debug)]
+pub(crate) struct ConfigCache {
+    capacity: usize,
+    inner: Mutex<CacheInner>,
+}
+
+#[derive(Debug, Default)]
+struct CacheInner {
+    /// Digest -> (config, last-use tick).
+    entries: HashMap<String, (Arc<ImageConfiguration>, u64)>,
+    tick: u64,
+}
+
+impl ConfigCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            inner: Mutex::new(CacheInner::default()),
+        }
+    }
+
+    /// Return the config for `digest`, calling `load` only on a miss.
+    ///
+    /// The lock is not held across `load`, so two concurrent misses for one
+    /// digest may both load; the second insert simply replaces the first.
+    pub fn get_or_load(
+        &self,
+        digest: &str,
+        load: impl FnOnce() -> BoxliteResult<ImageConfiguration>,
+    ) -> BoxliteResult<Arc<ImageConfiguration>> {
+        if let Some(config) = self.get(digest) {
+            return Ok(config);
+        }
+
+        let config = Arc::new(load()?);
+        if self.capacity > 0 {
+            let mut inner = self.inner.lock().unwrap();
+            inner.tick += 1;
+            let tick = inner.tick;
+            inner
+                .entries
+                .insert(digest.to_string(), (Arc::clone(&config), tick));
+            if inner.entries.len() > self.capacity
+                && let Some(oldest) = inner
+                    .entries
+                    .iter()
+                    .min_by_key(|(_, (_, used))| *used)
+                    .map(|(digest, _)| digest.clone())
+            {
+                inner.entries.remove(&oldest);
+            }
+        }
+        Ok(config)
+    }
+
+    fn get(&self, digest: &str) -> Option<Arc<ImageConfiguration>> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tick += 1;
+        let tick = inner.tick;
+        inner.entries.get_mut(digest).map(|(config, used)| {
+            *used = tick;
+            Arc::clone(config)
+        })
+    }
+
+    /// Drop the entry for `digest`, e.g. after its blob was pruned.
+    pub fn invalidate(&self, digest: &str) {
+        self.inner.lock().unwrap().entries.remove(digest);
+    }
+
+    /// Drop every entry.
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn counting_loader(loads: &AtomicUsize) -> impl FnOnce() -> BoxliteResult<ImageConfiguration> {
+        move || {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(ImageConfiguration::default())
+        }
+    }
+
+    #[test]
+    fn second_load_hits_cache() {
+        let cache = ConfigCache::new(4);
+        let loads = AtomicUsize::new(0);
+
+        let first = cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        let second = cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = ConfigCache::new(2);
+        let loads = AtomicUsize::new(0);
+
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        cache
+            .get_or_load("sha256:b", counting_loader(&loads))
+            .unwrap();
+        // Touch `a` so `b` is the least recently used when `c` arrives.
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        cache
+            .get_or_load("sha256:c", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+        cache
+            .get_or_load("sha256:b", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn invalidate_forces_reload() {
+        let cache = ConfigCache::new(4);
+        let loads = AtomicUsize::new(0);
+
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        cache.invalidate("sha256:a");
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+        cache.clear();
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn zero_capacity_disables_caching() {
+        let cache = ConfigCache::new(0);
+        let loads = AtomicUsize::new(0);
+
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn failed_load_is_not_cached() {
+        let cache = ConfigCache::new(4);
+        let err = cache.get_or_load("sha256:a", || {
+            Err(boxlite_shared::errors::BoxliteError::Storage(
+                "missing".to_string(),
+            ))
+        });
+        assert!(err.is_err());
+
+        let loads = AtomicUsize::new(0);
+        cache
+            .get_or_load("sha256:a", counting_loader(&loads))
+            .unwrap();
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+}
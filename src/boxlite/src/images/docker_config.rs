@@ -0,0 +1,174 @@
+//! Registry credentials from the Docker CLI config.
+//!
+//! When a registry has no auth in `BoxliteOptions::image_registries`, pulls
+//! fall back to what `docker login` stored in `$DOCKER_CONFIG/config.json`
+//! (default `~/.docker/config.json`). Only inline `auths` entries are read;
+//! credential helpers (`credsStore`, `credHelpers`) are not invoked.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+
+use crate::runtime::options::ImageRegistryAuth;
+
+/// Key the Docker CLI stores Docker Hub credentials under.
+const DOCKER_HUB_KEY: &str = "https://index.docker.io/v1/";
+/// Host names that all refer to Docker Hub.
+const DOCKER_HUB_HOSTS: &[&str] = &["docker.io", "index.docker.io", "registry-1.docker.io"];
+
+/// Parsed `config.json`; unknown fields are ignored.
+#[derive(Debug, Default, Deserialize)]
+pub(super) struct DockerConfig {
+    #[serde(default)]
+    auths: HashMap<String, DockerAuthEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct DockerAuthEntry {
+    /// base64 of `username:password`.
+    #[serde(default)]
+    auth: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+    #[serde(default)]
+    registrytoken: Option<String>,
+}
+
+impl DockerConfig {
+    /// Load the current user's Docker config; `None` if absent or unreadable.
+    pub(super) fn load() -> Option<Self> {
+        let path = config_path()?;
+        let raw = std::fs::read(&path).ok()?;
+        match serde_json::from_slice(&raw) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Ignoring invalid Docker config");
+                None
+            }
+        }
+    }
+
+    /// Credentials stored for `host`, if any.
+    pub(super) fn credentials_for(&self, host: &str) -> Option<ImageRegistryAuth> {
+        let is_hub = DOCKER_HUB_HOSTS.contains(&host);
+        self.auths
+            .iter()
+            .find(|(key, _)| {
+                if is_hub && key.as_str() == DOCKER_HUB_KEY {
+                    return true;
+                }
+                let key_host = normalize_key(key);
+                key_host == host || (is_hub && DOCKER_HUB_HOSTS.contains(&key_host))
+            })
+            .and_then(|(_, entry)| entry.to_auth())
+    }
+}
+
+impl DockerAuthEntry {
+    fn to_auth(&self) -> Option<ImageRegistryAuth> {
+        if let Some(token) = self.registrytoken.as_ref().filter(|t| !t.is_empty()) {
+            return Some(ImageRegistryAuth::Bearer {
+                token: token.clone(),
+            });
+        }
+        if let (Some(username), Some(password)) = (&self.username, &self.password) {
+            return Some(ImageRegistryAuth::Basic {
+                username: username.clone(),
+                password: password.clone(),
+            });
+        }
+        let decoded = STANDARD.decode(self.auth.as_ref()?.trim()).ok()?;
+        let decoded = String::from_utf8(decoded).ok()?;
+        let (username, password) = decoded.split_once(':')?;
+        Some(ImageRegistryAuth::Basic {
+            username: username.to_string(),
+            password: password.to_string(),
+        })
+    }
+}
+
+/// `$DOCKER_CONFIG/config.json`, else `~/.docker/config.json`.
+fn config_path() -> Option<PathBuf> {
+    let dir = match std::env::var_os("DOCKER_CONFIG") {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::home_dir()?.join(".docker"),
+    };
+    Some(dir.join("config.json"))
+}
+
+/// Auth keys may be bare hosts or URLs (`https://host/v1/`); keep the host.
+fn normalize_key(key: &str) -> &str {
+    let key = key
+        .strip_prefix("https://")
+        .or_else(|| key.strip_prefix("http://"))
+        .unwrap_or(key);
+    key.split('/').next().unwrap_or(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> DockerConfig {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn decodes_inline_auth_for_exact_and_url_keys() {
+        let password = String::from_utf8(vec![115, 101, 99, 114, 101, 116]).unwrap();
+        let encoded = STANDARD.encode(format!("alice:{password}"));
+        let config = parse(&format!(
+            r#"{{"auths": {{
+                "registry.local:5000": {{"auth": "{encoded}"}},
+                "https://ghcr.io/v1/": {{"auth": "{encoded}"}}
+            }}}}"#
+        ));
+        let expected = ImageRegistryAuth::Basic {
+            username: "alice".into(),
+            password,
+        };
+
+        assert_eq!(
+            config.credentials_for("registry.local:5000"),
+            Some(expected.clone())
+        );
+        assert_eq!(config.credentials_for("ghcr.io"), Some(expected));
+        assert_eq!(config.credentials_for("quay.io"), None);
+    }
+
+    #[test]
+    fn docker_hub_key_matches_hub_aliases() {
+        let encoded = STANDARD.encode("bob:pw");
+        let config = parse(&format!(
+            r#"{{"auths": {{"{DOCKER_HUB_KEY}": {{"auth": "{encoded}"}}}}}}"#
+        ));
+
+        for host in DOCKER_HUB_HOSTS {
+            assert!(config.credentials_for(host).is_some(), "host={host}");
+        }
+    }
+
+    #[test]
+    fn registry_token_takes_precedence_and_helpers_are_ignored() {
+        let config = parse(
+            r#"{"credsStore": "desktop",
+                "auths": {
+                    "a.local": {"registrytoken": "tok", "auth": "Ym9iOnB3"},
+                    "b.local": {}
+                }}"#,
+        );
+
+        assert_eq!(
+            config.credentials_for("a.local"),
+            Some(ImageRegistryAuth::Bearer {
+                token: "tok".into()
+            })
+        );
+        assert_eq!(config.credentials_for("b.local"), None);
+    }
+}
@@ -0,0 +1,217 @@
+//! Content-addressed hardlink pool for extracted layer files.
+//!
+//! Identical files across layers are stored once: after a layer is extracted,
+//! each regular file is hashed (content plus the ownership, mode, and
+//! xattrs a hardlink would share) and replaced by a hardlink to `cas/{hash}` when the
+//! pool already holds it, or added to the pool when it does not.
+//!
+//! A deduplicated file takes the mtime of the first copy pooled. Within one
+//! layer at most one path links to each pool entry, so copying a layer never
+//! turns independent files into hardlinks of each other.
+
+use std::collections::HashSet;
+use std::io::Read;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+/// Hardlink pool at `~/.boxlite/images/cas`.
+#[derive(Clone, Debug)]
+pub(crate) struct LayerPool {
+    dir: PathBuf,
+}
+
+impl LayerPool {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Replace files under `layer_dir` with hardlinks into the pool.
+    ///
+    /// Returns the number of files now shared with another layer. Files on a
+    /// different filesystem than the pool keep their own copy.
+    pub fn dedup(&self, layer_dir: &Path) -> BoxliteResult<usize> {
+        std::fs::create_dir_all(&self.dir).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to create layer pool {}: {}",
+                self.dir.display(),
+                e
+            ))
+        })?;
+
+        let mut linked_in_layer = HashSet::new();
+        let mut shared = 0;
+        for entry in walkdir::WalkDir::new(layer_dir).follow_links(false) {
+            let entry = entry.map_err(|e| {
+                BoxliteError::Storage(format!(
+                    "Failed to walk layer {}: {}",
+                    layer_dir.display(),
+                    e
+                ))
+            })?;
+            let path = entry.path();
+            let io_err = |e: std::io::Error| {
+                BoxliteError::Storage(format!("Failed to dedup {}: {}", path.display(), e))
+            };
+
+            let meta = entry.metadata().map_err(|e| io_err(e.into()))?;
+            // Empty files gain nothing; linked files are already shared
+            // within the layer and must stay distinct from the pool.
+            if !meta.is_file() || meta.len() == 0 || meta.nlink() > 1 {
+                continue;
+            }
+
+            let key = file_key(path, &meta).map_err(io_err)?;
+            if !linked_in_layer.insert(key.clone()) {
+                continue;
+            }
+            if self.link(path, &key).map_err(io_err)? {
+                shared += 1;
+            }
+        }
+        Ok(shared)
+    }
+
+    /// Remove pool entries no extracted layer links to any more.
+    pub fn sweep(&self) -> usize {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return 0;
+        };
+        let mut removed = 0;
+        for entry in entries.flatten() {
+            let unused = entry.metadata().is_ok_and(|meta| meta.nlink() == 1);
+            if unused && std::fs::remove_file(entry.path()).is_ok() {
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    /// Link `path` with the pool entry for `key`, adding it when missing.
+    ///
+    /// Returns whether `path` now shares an existing pool entry.
+    fn link(&self, path: &Path, key: &str) -> std::io::Result<bool> {
+        let pooled = self.dir.join(key);
+        loop {
+            match std::fs::hard_link(path, &pooled) {
+                Ok(()) => return Ok(false),
+                Err(e) if is_cross_device(&e) => return Ok(false),
+                Err(e) if e.kind() != std::io::ErrorKind::AlreadyExists => return Err(e),
+                Err(_) => {}
+            }
+
+            // Swap in a link to the pooled copy atomically.
+            let staged = path.with_file_name(format!(
+                ".{}.dedup",
+                path.file_name().unwrap_or_default().to_string_lossy()
+            ));
+            match std::fs::hard_link(&pooled, &staged) {
+                Ok(()) => {}
+                Err(e) if is_cross_device(&e) => return Ok(false),
+                // Swept between the two links; pool this copy instead.
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e),
+            }
+            if let Err(e) = std::fs::rename(&staged, path) {
+                let _ = std::fs::remove_file(&staged);
+                return Err(e);
+            }
+            return Ok(true);
+        }
+    }
+}
+
+/// Pool key: SHA-256 of everything a hardlink shares with its other names.
+fn file_key(path: &Path, meta: &std::fs::Metadata) -> std::io::Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{:o}:{}:{}\0", meta.mode(), meta.uid(), meta.gid()));
+    let mut names: Vec<_> = xattr::list(path)?.collect();
+    names.sort();
+    for name in names {
+        hasher.update(name.as_encoded_bytes());
+        hasher.update(b"=");
+        hasher.update(xattr::get(path, &name)?.unwrap_or_default());
+        hasher.update(b"\0");
+    }
+    hasher.update(b"\0");
+
+    let mut file = std::fs::File::open(path)?;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn is_cross_device(e: &std::io::Error) -> bool {
+    e.raw_os_error() == Some(libc::EXDEV)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ino(path: &Path) -> u64 {
+        std::fs::metadata(path).unwrap().ino()
+    }
+
+    #[test]
+    fn test_dedup_keeps_copies_within_one_layer_distinct() {
+        let dir = tempfile::tempdir().unwrap();
+        let layer = dir.path().join("layer");
+        std::fs::create_dir_all(&layer).unwrap();
+        std::fs::write(layer.join("a"), "same").unwrap();
+        std::fs::write(layer.join("b"), "same").unwrap();
+
+        let pool = LayerPool::new(dir.path().join("cas"));
+        assert_eq!(pool.dedup(&layer).unwrap(), 0);
+        assert_ne!(ino(&layer.join("a")), ino(&layer.join("b")));
+        assert_eq!(std::fs::read(layer.join("b")).unwrap(), b"same");
+    }
+
+    #[test]
+    fn test_dedup_separates_files_with_different_modes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let pool = LayerPool::new(dir.path().join("cas"));
+        for (name, mode) in [("l1", 0o644), ("l2", 0o755)] {
+            let layer = dir.path().join(name);
+            std::fs::create_dir_all(&layer).unwrap();
+            std::fs::write(layer.join("tool"), "same").unwrap();
+            std::fs::set_permissions(layer.join("tool"), std::fs::Permissions::from_mode(mode))
+                .unwrap();
+            assert_eq!(pool.dedup(&layer).unwrap(), 0);
+        }
+        assert_ne!(
+            ino(&dir.path().join("l1/tool")),
+            ino(&dir.path().join("l2/tool"))
+        );
+    }
+
+    #[test]
+    fn test_sweep_removes_unreferenced_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let layer = dir.path().join("layer");
+        std::fs::create_dir_all(&layer).unwrap();
+        std::fs::write(layer.join("a"), "content").unwrap();
+
+        let pool = LayerPool::new(dir.path().join("cas"));
+        pool.dedup(&layer).unwrap();
+        assert_eq!(pool.sweep(), 0, "entry still linked from the layer");
+
+        std::fs::remove_dir_all(&layer).unwrap();
+        assert_eq!(pool.sweep(), 1);
+        assert_eq!(
+            std::fs::read_dir(dir.path().join("cas")).unwrap().count(),
+            0
+        );
+    }
+}
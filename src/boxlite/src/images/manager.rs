@@ -9,18 +9,28 @@
 //! - `ImageStore` handles all locking internally
 //! - `ImageObject` uses `BlobSource` for blob access
 
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use chrono::{DateTime, Utc};
 
+use super::archive::{
+    LayerDiff, write_directory_layer, write_layer_diff, write_tarball_layer, write_upper_layer,
+};
 use super::blob_source::{BlobSource, LocalBundleBlobSource, StoreBlobSource};
+use super::blob_store::ImageBlobStore;
+use super::config_cache::{ConfigCache, DEFAULT_CONFIG_CACHE_CAPACITY};
 use super::object::ImageObject;
 use crate::db::Database;
 use crate::images::store::{ImageStore, SharedImageStore};
-use crate::runtime::options::ImageRegistry;
-use crate::runtime::types::ImageInfo;
-use boxlite_shared::errors::BoxliteResult;
+use crate::runtime::options::{
+    CommitOptions, ImageRegistry, ImportSource, Platform, PruneOptions, PullPolicy, RetryPolicy,
+};
+use crate::runtime::types::{
+    BlobKind, ImageInfo, PrunedReport, PullPhase, PullProgress, PullProgressCallback,
+};
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use oci_client::Reference;
 use std::str::FromStr;
 
@@ -83,6 +93,7 @@ pub(super) struct LayerInfo {
 #[derive(Clone)]
 pub struct ImageManager {
     store: SharedImageStore,
+    config_cache: Arc<ConfigCache>,
 }
 
 impl std::fmt::Debug for ImageManager {
@@ -103,8 +114,52 @@ impl ImageManager {
         db: Database,
         image_registries: Vec<ImageRegistry>,
     ) -> BoxliteResult<Self> {
-        let store = Arc::new(ImageStore::new(images_dir, db, image_registries)?);
-        Ok(Self { store })
+        Self::with_store_options(
+            images_dir,
+            db,
+            image_registries,
+            None,
+            RetryPolicy::default(),
+            false,
+            None,
+        )
+    }
+
+    /// Create an image manager whose store is capped at `max_bytes` on disk,
+    /// retries transient registry failures per `pull_retry`, and optionally
+    /// deduplicates extracted layer files (`dedup_layers`). `blob_store`
+    /// replaces the filesystem blob store under `images_dir`.
+    ///
+    /// See [`enforce_size_cap`](Self::enforce_size_cap).
+    pub fn with_store_options(
+        images_dir: PathBuf,
+        db: Database,
+        image_registries: Vec<ImageRegistry>,
+        max_bytes: Option<u64>,
+        pull_retry: RetryPolicy,
+        dedup_layers: bool,
+        blob_store: Option<Arc<dyn ImageBlobStore>>,
+    ) -> BoxliteResult<Self> {
+        let mut store = ImageStore::new(images_dir, db, image_registries)?
+            .with_max_bytes(max_bytes)
+            .with_retry_policy(pull_retry)
+            .with_layer_dedup(dedup_layers);
+        if let Some(blob_store) = blob_store {
+            store = store.with_blob_store(blob_store);
+        }
+        let store = Arc::new(store);
+        Ok(Self {
+            store,
+            config_cache: Arc::new(ConfigCache::new(DEFAULT_CONFIG_CACHE_CAPACITY)),
+        })
+    }
+
+    /// Keep up to `capacity` parsed image configs in memory, so boxes
+    /// created from the same image skip re-reading its config blob.
+    /// 0 disables the cache.
+    pub fn with_config_cache_capacity(mut self, capacity: usize) -> Self {
+        self.config_cache = Arc::new(ConfigCache::new(capacity));
+        self
     }
 
     /// Pull an OCI image from a registry.
@@ -115,15 +170,98 @@ impl ImageManager {
     /// Thread Safety: `ImageStore` handles locking internally. Multiple
     /// concurrent pulls of the same image will only download once.
     pub async fn pull(&self, image_ref: &str) -> BoxliteResult<ImageObject> {
-        let manifest = self.store.pull(image_ref).await?;
+        self.pull_reporting(image_ref, None, PullPolicy::IfNotPresent, None)
+            .await
+    }
+
+    /// Pull an OCI image, reporting progress to `progress`.
+    ///
+    /// Reports the manifest phase, per-layer download bytes, and a final
+    /// [`PullPhase::Done`]. A cached image only reports `Done`.
+    pub async fn pull_with_progress(
+        &self,
+        image_ref: &str,
+        progress: impl Fn(PullProgress) + Send + Sync + 'static,
+    ) -> BoxliteResult<ImageObject> {
+        let progress = PullProgressCallback::new(progress);
+        let image = self
+            .pull_reporting(image_ref, None, PullPolicy::IfNotPresent, Some(&progress))
+            .await?;
+        progress.report(PullProgress::image(PullPhase::Done));
+        Ok(image)
+    }
+
+    /// Pull without reporting `Done`, for callers with work (e.g. rootfs
+    /// extraction) still to report. `platform` defaults to the host's;
+    /// `policy` decides whether the registry is contacted.
+    pub(crate) async fn pull_reporting(
+        &self,
+        image_ref: &str,
+        platform: Option<&Platform>,
+        policy: PullPolicy,
+        progress: Option<&PullProgressCallback>,
+    ) -> BoxliteResult<ImageObject> {
+        let manifest = self
+            .store
+            .pull_with_progress(image_ref, platform, policy, progress)
+            .await?;
         let storage = self.store.storage().await;
         let blob_source = BlobSource::Store(StoreBlobSource::new(storage));
 
-        Ok(ImageObject::new(
-            image_ref.to_string(),
-            manifest,
-            blob_source,
-        ))
+        Ok(
+            ImageObject::new(image_ref.to_string(), manifest, blob_source)
+                .with_config_cache(Arc::clone(&self.config_cache)),
+        )
+    }
+
+    /// Check that `image_ref` can be pulled, without downloading it.
+    ///
+    /// Returns the registry-qualified reference it resolves to.
+    pub async fn resolve(&self, image_ref: &str) -> BoxliteResult<String> {
+        self.store.resolve(image_ref).await
+    }
+
+    /// Evict least-recently-used images until the store fits under its cap.
+    ///
+    /// `in_use` are image references that must survive (e.g. the images of
+    /// running boxes). Returns the evicted references; empty when no cap is
+    /// configured.
+    pub async fn enforce_size_cap(&self, in_use: &[String]) -> BoxliteResult<Vec<String>> {
+        let evicted = self.store.enforce_size_cap(in_use).await?;
+        if !evicted.is_empty() {
+            // Eviction reports references, not the config digests it removed.
+            self.config_cache.clear();
+        }
+        Ok(evicted)
+    }
+
+    /// Delete blobs no cached image references.
+    ///
+    /// See [`ImageStore::prune`] for what `local_bundles` and
+    /// `backing_files` protect.
+    pub async fn prune(
+        &self,
+        options: &PruneOptions,
+        local_bundles: &[PathBuf],
+        backing_files: &HashSet<PathBuf>,
+    ) -> BoxliteResult<PrunedReport> {
+        let report = self
+            .store
+            .prune(options, local_bundles, backing_files)
+            .await?;
+        if !options.dry_run {
+            for blob in &report.removed {
+                if matches!(blob.kind, BlobKind::Config) {
+                    self.config_cache.invalidate(&blob.digest);
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    /// Total on-disk size of cached image blobs, in bytes.
+    pub async fn disk_usage(&self) -> BoxliteResult<u64> {
+        self.store.disk_usage().await
     }
 
     /// List all cached images.
@@ -140,30 +278,165 @@ impl ImageManager {
                     DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH)
                 });
 
-            let (repository, tag) = match Reference::from_str(&reference) {
-                Ok(r) => (
-                    r.repository().to_string(),
-                    r.tag().unwrap_or("latest").to_string(),
-                ),
-                Err(_) => {
-                    // Fallback if reference stored in DB is invalid
-                    (reference.clone(), "<none>".to_string())
-                }
-            };
-
-            images.push(ImageInfo {
-                reference,
-                repository,
-                tag,
-                id: cached.manifest_digest,
-                cached_at,
-                size: None, // Size calculation is expensive now? omitted for list temporarily
-            });
+            images.push(image_info(reference, cached.manifest_digest, cached_at));
         }
 
         Ok(images)
     }
 
+    /// Commit a box rootfs as a new image on top of `base`.
+    ///
+    /// `rootfs_tar` is the box's full container rootfs; only its difference
+    /// from `base` becomes the new top layer. The new config starts from
+    /// `base`'s, with `changes` applied. The image is stored under
+    /// `image_ref`, so `RootfsSpec::Image(image_ref)` boots it.
+    pub async fn commit(
+        &self,
+        base: &ImageObject,
+        rootfs_tar: &Path,
+        image_ref: &str,
+        changes: &CommitOptions,
+    ) -> BoxliteResult<ImageInfo> {
+        let tarballs: Vec<PathBuf> = base
+            .layers_with_tarballs()
+            .into_iter()
+            .map(|(_, p)| p)
+            .collect();
+        let rootfs = rootfs_tar.to_path_buf();
+        self.commit_layer(base, rootfs_tar, image_ref, changes, move |layer_path| {
+            write_layer_diff(&tarballs, &rootfs, layer_path)
+        })
+        .await
+    }
+
+    /// Commit an overlay upper layer as a new image on top of `base`.
+    ///
+    /// `upper_tar` is the box's upper dir as packed by the guest (already an
+    /// OCI layer with whiteouts), so no diff against `base` is computed.
+    /// Otherwise behaves like [`commit`](Self::commit).
+    pub async fn commit_upper(
+        &self,
+        base: &ImageObject,
+        upper_tar: &Path,
+        image_ref: &str,
+        changes: &CommitOptions,
+    ) -> BoxliteResult<ImageInfo> {
+        let upper = upper_tar.to_path_buf();
+        self.commit_layer(base, upper_tar, image_ref, changes, move |layer_path| {
+            write_upper_layer(&upper, layer_path)
+        })
+        .await
+    }
+
+    /// Build the top layer with `write_layer` next to `source_tar`, then store
+    /// it with `base`'s layers and a config derived from `changes`.
+    async fn commit_layer(
+        &self,
+        base: &ImageObject,
+        source_tar: &Path,
+        image_ref: &str,
+        changes: &CommitOptions,
+        write_layer: impl FnOnce(&Path) -> BoxliteResult<LayerDiff> + Send + 'static,
+    ) -> BoxliteResult<ImageInfo> {
+        let base_layers = base.layers_with_tarballs();
+        let layer_path = source_tar.with_extension("layer.tar.gz");
+
+        let diff = {
+            let layer_path = layer_path.clone();
+            tokio::task::spawn_blocking(move || write_layer(&layer_path))
+                .await
+                .map_err(|e| BoxliteError::Internal(format!("layer diff task failed: {}", e)))?
+        };
+        let diff = match diff {
+            Ok(diff) => diff,
+            Err(e) => {
+                let _ = std::fs::remove_file(&layer_path);
+                return Err(e);
+            }
+        };
+        tracing::debug!(
+            changed = diff.changed,
+            removed = diff.removed,
+            size = diff.size,
+            "Computed commit layer"
+        );
+
+        let mut config = serde_json::to_value(base.load_config().await?)
+            .map_err(|e| BoxliteError::Image(format!("Failed to serialize image config: {}", e)))?;
+        apply_commit_changes(&mut config, &diff.diff_id, changes)?;
+        let config = serde_json::to_vec(&config)
+            .map_err(|e| BoxliteError::Image(format!("Failed to serialize image config: {}", e)))?;
+
+        let result = self
+            .store
+            .commit(image_ref, &base_layers, &layer_path, &diff, &config)
+            .await;
+        let _ = std::fs::remove_file(&layer_path);
+        let manifest = result?;
+
+        Ok(image_info(
+            image_ref.to_string(),
+            manifest.manifest_digest,
+            Utc::now(),
+        ))
+    }
+
+    /// Create a single-layer image from a rootfs directory or tarball, like
+    /// `docker import`.
+    ///
+    /// The image gets a minimal config for the host platform (no entrypoint,
+    /// command, or env) and is stored under `tag`, so
+    /// `RootfsSpec::Image(tag)` boots it.
+    pub async fn import_rootfs(&self, source: ImportSource, tag: &str) -> BoxliteResult<ImageInfo> {
+        let (path, is_dir) = match &source {
+            ImportSource::Directory(dir) => (dir, true),
+            ImportSource::Tarball(tarball) => (tarball, false),
+        };
+        let matches = if is_dir {
+            path.is_dir()
+        } else {
+            path.is_file()
+        };
+        if !matches {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "import source is not a {}: {}",
+                if is_dir { "directory" } else { "file" },
+                path.display()
+            )));
+        }
+
+        let layer_path = tempfile::Builder::new()
+            .prefix("boxlite-import-")
+            .suffix(".layer.tar.gz")
+            .tempfile()
+            .map_err(|e| BoxliteError::Storage(format!("Failed to create import layer: {}", e)))?
+            .into_temp_path();
+        let diff = {
+            let layer_path = layer_path.to_path_buf();
+            tokio::task::spawn_blocking(move || match &source {
+                ImportSource::Directory(dir) => write_directory_layer(dir, &layer_path),
+                ImportSource::Tarball(tarball) => write_tarball_layer(tarball, &layer_path),
+            })
+            .await
+            .map_err(|e| BoxliteError::Internal(format!("import task failed: {}", e)))??
+        };
+
+        let config = serde_json::to_vec(&import_config(&diff.diff_id))
+            .map_err(|e| BoxliteError::Image(format!("Failed to serialize image config: {}", e)))?;
+        // `commit` moves the layer into the store; the temp path only
+        // cleans up after a failure.
+        let manifest = self
+            .store
+            .commit(tag, &[], &layer_path, &diff, &config)
+            .await?;
+
+        Ok(image_info(
+            tag.to_string(),
+            manifest.manifest_digest,
+            Utc::now(),
+        ))
+    }
+
     /// Load an OCI/Docker image from a local directory.
     ///
     /// Reads image manifest from `manifest.json` and returns an `ImageObject`.
@@ -200,3 +473,185 @@ impl ImageManager {
         Ok(ImageObject::new(reference, manifest, blob_source))
     }
 }
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+fn image_info(reference: String, id: String, cached_at: DateTime<Utc>) -> ImageInfo {
+    let (repository, tag) = match Reference::from_str(&reference) {
+        Ok(r) => {
+            // Digest-pinned references have no tag, like `docker images`.
+            let tag = match (r.tag(), r.digest()) {
+                (Some(tag), _) => tag,
+                (None, Some(_)) => "<none>",
+                (None, None) => "latest",
+            };
+            (r.repository().to_string(), tag.to_string())
+        }
+        Err(_) => {
+            // Fallback if reference stored in DB is invalid
+            (reference.clone(), "<none>".to_string())
+        }
+    };
+
+    ImageInfo {
+        reference,
+        repository,
+        tag,
+        id,
+        cached_at,
+        size: None, // Size calculation is expensive now? omitted for list temporarily
+    }
+}
+
+/// Minimal image config for an imported rootfs layer with DiffID `diff_id`.
+fn import_config(diff_id: &str) -> serde_json::Value {
+    let platform = Platform::host();
+    let now = Utc::now().to_rfc3339();
+    serde_json::json!({
+        "architecture": platform.arch,
+        "os": platform.os,
+        "created": now,
+        "config": {},
+        "rootfs": { "type": "layers", "diff_ids": [diff_id] },
+        "history": [{ "created": now, "created_by": "boxlite import" }]
+    })
+}
+
+/// Update a base image config (as JSON) for a commit: append the new layer's
+/// DiffID, apply `changes`, and record a history entry.
+fn apply_commit_changes(
+    config: &mut serde_json::Value,
+    diff_id: &str,
+    changes: &CommitOptions,
+) -> BoxliteResult<()> {
+    use serde_json::{Value, json};
+
+    let Some(root) = config.as_object_mut() else {
+        return Err(BoxliteError::Image("image config is not an object".into()));
+    };
+    let now = Utc::now().to_rfc3339();
+
+    let rootfs = root
+        .entry("rootfs")
+        .or_insert_with(|| json!({ "type": "layers", "diff_ids": [] }));
+    if !rootfs.is_object() {
+        *rootfs = json!({ "type": "layers", "diff_ids": [] });
+    }
+    match rootfs.get_mut("diff_ids").and_then(Value::as_array_mut) {
+        Some(diff_ids) => diff_ids.push(json!(diff_id)),
+        None => rootfs["diff_ids"] = json!([diff_id]),
+    }
+
+    let runtime = root.entry("config").or_insert_with(|| json!({}));
+    if !runtime.is_object() {
+        *runtime = json!({});
+    }
+    if let Some(entrypoint) = &changes.entrypoint {
+        runtime["Entrypoint"] = json!(entrypoint);
+    }
+    if let Some(cmd) = &changes.cmd {
+        runtime["Cmd"] = json!(cmd);
+    }
+    if !changes.env.is_empty() {
+        let mut env: Vec<String> = runtime
+            .get("Env")
+            .and_then(Value::as_array)
+            .map(|vars| {
+                vars.iter()
+                    .filter_map(|v| v.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        for (key, value) in &changes.env {
+            let var = format!("{key}={value}");
+            let prefix = format!("{key}=");
+            match env.iter_mut().find(|v| v.starts_with(&prefix)) {
+                Some(existing) => *existing = var,
+                None => env.push(var),
+            }
+        }
+        runtime["Env"] = json!(env);
+    }
+
+    root.insert("created".into(), json!(now));
+    let mut entry = json!({ "created": now, "created_by": "boxlite commit" });
+    if let Some(message) = &changes.message {
+        entry["comment"] = json!(message);
+    }
+    match root.get_mut("history").and_then(Value::as_array_mut) {
+        Some(history) => history.push(entry),
+        None => {
+            root.insert("history".into(), json!([entry]));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn commit_changes_extend_base_config() {
+        let mut config = json!({
+            "architecture": "amd64",
+            "os": "linux",
+            "config": {
+                "Env": ["PATH=/usr/bin", "LANG=C"],
+                "Cmd": ["/bin/sh"]
+            },
+            "rootfs": { "type": "layers", "diff_ids": ["sha256:base"] },
+            "history": [{ "created_by": "base" }]
+        });
+        let changes = CommitOptions {
+            entrypoint: Some(vec!["/app".into()]),
+            env: vec![
+                ("LANG".into(), "C.UTF-8".into()),
+                ("MODE".into(), "prod".into()),
+            ],
+            message: Some("add app".into()),
+            ..Default::default()
+        };
+
+        apply_commit_changes(&mut config, "sha256:new", &changes).unwrap();
+
+        assert_eq!(
+            config["rootfs"]["diff_ids"],
+            json!(["sha256:base", "sha256:new"])
+        );
+        assert_eq!(config["config"]["Entrypoint"], json!(["/app"]));
+        assert_eq!(config["config"]["Cmd"], json!(["/bin/sh"]));
+        assert_eq!(
+            config["config"]["Env"],
+            json!(["PATH=/usr/bin", "LANG=C.UTF-8", "MODE=prod"])
+        );
+        assert_eq!(config["history"][1]["comment"], json!("add app"));
+        assert_eq!(config["architecture"], json!("amd64"));
+    }
+
+    #[test]
+    fn commit_changes_fill_missing_sections() {
+        let mut config = json!({ "os": "linux" });
+        apply_commit_changes(&mut config, "sha256:new", &CommitOptions::default()).unwrap();
+
+        assert_eq!(config["rootfs"]["diff_ids"], json!(["sha256:new"]));
+        assert_eq!(config["history"].as_array().unwrap().len(), 1);
+        assert!(config["config"].as_object().unwrap().is_empty());
+    }
+
+    #[test]
+    fn import_config_is_single_layer_for_host() {
+        let config = import_config("sha256:rootfs");
+
+        assert_eq!(config["rootfs"]["diff_ids"], json!(["sha256:rootfs"]));
+        assert_eq!(config["os"], json!("linux"));
+        assert_eq!(config["architecture"], json!(Platform::host().arch));
+        assert!(config["config"].as_object().unwrap().is_empty());
+        // Parses as an OCI config, so the image boots like a pulled one.
+        let parsed: oci_spec::image::ImageConfiguration = serde_json::from_value(config).unwrap();
+        assert_eq!(parsed.rootfs().diff_ids().len(), 1);
+    }
+}
@@ -1,17 +1,24 @@
 mod archive;
 mod blob_source;
+mod blob_store;
 mod config;
+mod config_cache;
+mod docker_config;
 mod image_disk;
+mod layer_pool;
 mod manager;
 mod object;
+mod progress;
 mod storage;
 mod store;
 
 pub use archive::LayerExtractor;
+pub use blob_store::ImageBlobStore;
 pub use config::ContainerImageConfig;
 pub use image_disk::ImageDiskManager;
 pub use manager::ImageManager;
 pub use object::ImageObject;
+pub use storage::{HashingWriter, ImageStorage, StagedDownload, StoredBlob};
 
 use oci_client::Reference;
 
@@ -23,6 +30,8 @@ use oci_client::Reference;
 ///
 /// For qualified images (e.g., `"ghcr.io/foo/bar"`), yields only the original.
 /// For unqualified images (e.g., `"alpine"`), yields one `Reference` per registry.
+/// A digest-pinned image (`"alpine@sha256:..."`) keeps its digest on every
+/// candidate, so no tag is resolved.
 ///
 /// # Examples
 ///
@@ -116,12 +125,16 @@ impl Iterator for ReferenceIter<'_> {
         let registry = &self.registries[self.index];
         self.index += 1;
 
+        let repository = self.base_ref.repository().to_string();
+        if let Some(digest) = self.base_ref.digest() {
+            return Some(Reference::with_digest(
+                registry.clone(),
+                repository,
+                digest.to_string(),
+            ));
+        }
         let tag = self.base_ref.tag().unwrap_or("latest").to_string();
-        Some(Reference::with_tag(
-            registry.clone(),
-            self.base_ref.repository().to_string(),
-            tag,
-        ))
+        Some(Reference::with_tag(registry.clone(), repository, tag))
     }
 }
 
@@ -201,6 +214,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_digest_pinned_keeps_digest_per_registry() {
+        let digest = "sha256:834dcb65465985fc2f648451d76c81d166bc7672391c9064a0a115ce6306c85f";
+        let registries = vec!["ghcr.io".to_string(), "docker.io".to_string()];
+        let iter = ReferenceIter::new(&format!("alpine@{digest}"), &registries).unwrap();
+        let refs: Vec<Reference> = iter.collect();
+
+        assert_eq!(refs.len(), 2);
+        for r in &refs {
+            assert_eq!(r.digest(), Some(digest));
+            assert_eq!(r.tag(), None);
+            assert!(r.repository().ends_with("alpine"));
+        }
+        assert_eq!(refs[0].registry(), "ghcr.io");
+
+        // Qualified digest refs pass through untouched.
+        let pinned = format!("quay.io/foo/bar@{digest}");
+        let refs: Vec<Reference> = ReferenceIter::new(&pinned, &registries).unwrap().collect();
+        assert_eq!(refs.len(), 1);
+        assert_eq!(refs[0].registry(), "quay.io");
+        assert_eq!(refs[0].digest(), Some(digest));
+    }
+
     #[test]
     fn test_qualified_bypasses_registries() {
         let registries = vec!["ghcr.io".to_string()];
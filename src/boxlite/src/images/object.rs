@@ -5,9 +5,11 @@
 //! layer access, inspection).
 
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use super::blob_source::BlobSource;
-use super::manager::ImageManifest;
+use super::config_cache::ConfigCache;
+use super::manager::{ImageManifest, LayerInfo};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 // ============================================================================
@@ -35,6 +37,10 @@ pub struct ImageObject {
 
     /// Source of blobs with source-specific caching
     blob_source: BlobSource,
+
+    /// Parsed-config cache shared through `ImageManager`; `None` for local
+    /// bundles, whose blobs stay outside the trusted store.
+    config_cache: Option<Arc<ConfigCache>>,
 }
 
 impl ImageObject {
@@ -44,9 +50,16 @@ impl ImageObject {
             reference,
             manifest,
             blob_source,
+            config_cache: None,
         }
     }
 
+    /// Serve `load_config` through `cache`.
+    pub(super) fn with_config_cache(mut self, cache: Arc<ConfigCache>) -> Self {
+        self.config_cache = Some(cache);
+        self
+    }
+
     // ========================================================================
     // METADATA OPERATIONS
     // ========================================================================
@@ -91,6 +104,15 @@ impl ImageObject {
     /// Use `ContainerConfig::from_oci_config()` if you need extracted container
     /// runtime configuration (entrypoint, env, workdir).
     pub async fn load_config(&self) -> BoxliteResult<oci_spec::image::ImageConfiguration> {
+        match &self.config_cache {
+            Some(cache) => cache
+                .get_or_load(&self.manifest.config_digest, || self.read_config())
+                .map(|config| (*config).clone()),
+            None => self.read_config(),
+        }
+    }
+
+    fn read_config(&self) -> BoxliteResult<oci_spec::image::ImageConfiguration> {
         let config_path = self.blob_source.config_path(&self.manifest.config_digest);
         let config_json = std::fs::read_to_string(&config_path).map_err(|e| {
             BoxliteError::Storage(format!(
@@ -133,6 +155,18 @@ impl ImageObject {
             .collect()
     }
 
+    /// Manifest layers paired with their tarball paths (ordered bottom to top)
+    pub(super) fn layers_with_tarballs(&self) -> Vec<(LayerInfo, PathBuf)> {
+        self.manifest
+            .layers
+            .iter()
+            .map(|layer| {
+                let path = self.blob_source.layer_tarball_path(&layer.digest);
+                (layer.clone(), path)
+            })
+            .collect()
+    }
+
     /// Get paths to extracted layer directories (with caching)
     ///
     /// This method extracts each layer tarball to a separate directory and caches
@@ -282,13 +316,12 @@ impl ImageObject {
     /// This is used as a cache key for base disks - same layers = same base disk.
     /// Uses SHA256 hash of concatenated layer digests.
     pub(crate) fn compute_image_digest(&self) -> String {
-        use sha2::{Digest, Sha256};
-
-        let mut hasher = Sha256::new();
-        for layer in &self.manifest.layers {
-            hasher.update(layer.digest.as_bytes());
-        }
-        format!("sha256:{:x}", hasher.finalize())
+        image_digest(
+            self.manifest
+                .layers
+                .iter()
+                .map(|layer| layer.digest.as_str()),
+        )
     }
 
     // ========================================================================
@@ -322,6 +355,20 @@ impl std::fmt::Debug for ImageObject {
     }
 }
 
+/// Image digest for an ordered list of layer digests.
+///
+/// See [`ImageObject::compute_image_digest`]; also used to find the disk
+/// images of cached images without loading them.
+pub(super) fn image_digest<'a>(layer_digests: impl IntoIterator<Item = &'a str>) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for digest in layer_digests {
+        hasher.update(digest.as_bytes());
+    }
+    format!("sha256:{:x}", hasher.finalize())
+}
+
 impl std::fmt::Display for ImageObject {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -0,0 +1,133 @@
+//! Per-layer download progress reporting for image pulls.
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use tokio::io::AsyncWrite;
+
+use crate::runtime::types::{PullPhase, PullProgress, PullProgressCallback};
+
+/// Download progress of one layer across retry attempts.
+///
+/// Only reports byte counts above the highest one already reported, so a
+/// retried download stays silent until it passes where the failed attempt
+/// stopped.
+pub(super) struct LayerProgress<'a> {
+    callback: &'a PullProgressCallback,
+    digest: &'a str,
+    total_bytes: Option<u64>,
+    reported: u64,
+}
+
+impl<'a> LayerProgress<'a> {
+    /// `size` is the manifest descriptor size; values <= 0 mean unknown.
+    pub(super) fn new(callback: &'a PullProgressCallback, digest: &'a str, size: i64) -> Self {
+        Self {
+            callback,
+            digest,
+            total_bytes: u64::try_from(size).ok().filter(|size| *size > 0),
+            reported: 0,
+        }
+    }
+
+    /// Wrap the writer for one download attempt that starts at byte `start`
+    /// (non-zero when resuming an interrupted download).
+    pub(super) fn writer<W>(&mut self, inner: W, start: u64) -> ProgressWriter<'_, 'a, W> {
+        ProgressWriter {
+            inner,
+            progress: self,
+            written: start,
+        }
+    }
+
+    fn advance(&mut self, written: u64) {
+        if written <= self.reported {
+            return;
+        }
+        self.reported = written;
+        self.callback.report(PullProgress {
+            layer_digest: Some(self.digest.to_string()),
+            bytes_downloaded: written,
+            total_bytes: self.total_bytes,
+            phase: PullPhase::Downloading,
+        });
+    }
+}
+
+/// `AsyncWrite` wrapper that reports bytes written to a [`LayerProgress`].
+pub(super) struct ProgressWriter<'p, 'a, W> {
+    inner: W,
+    progress: &'p mut LayerProgress<'a>,
+    written: u64,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for ProgressWriter<'_, '_, W> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        let this = self.get_mut();
+        let poll = Pin::new(&mut this.inner).poll_write(cx, buf);
+        if let Poll::Ready(Ok(n)) = poll {
+            this.written += n as u64;
+            this.progress.advance(this.written);
+        }
+        poll
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::AsyncWriteExt;
+
+    #[tokio::test]
+    async fn retried_attempt_only_reports_new_high_water_marks() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let callback =
+            PullProgressCallback::new(move |p| sink.lock().unwrap().push(p.bytes_downloaded));
+        let mut progress = LayerProgress::new(&callback, "sha256:abc", 10);
+
+        let mut first = progress.writer(Vec::new(), 0);
+        first.write_all(&[0; 4]).await.unwrap();
+        first.write_all(&[0; 2]).await.unwrap();
+        let mut retry = progress.writer(Vec::new(), 0);
+        retry.write_all(&[0; 5]).await.unwrap();
+        retry.write_all(&[0; 5]).await.unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), vec![4, 6, 10]);
+    }
+
+    #[tokio::test]
+    async fn resumed_attempt_counts_from_its_start_offset() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = seen.clone();
+        let callback =
+            PullProgressCallback::new(move |p| sink.lock().unwrap().push(p.bytes_downloaded));
+        let mut progress = LayerProgress::new(&callback, "sha256:abc", 10);
+
+        progress
+            .writer(Vec::new(), 0)
+            .write_all(&[0; 6])
+            .await
+            .unwrap();
+        progress
+            .writer(Vec::new(), 6)
+            .write_all(&[0; 4])
+            .await
+            .unwrap();
+
+        assert_eq!(*seen.lock().unwrap(), vec![6, 10]);
+    }
+}
@@ -14,7 +14,9 @@ use std::path::{Path, PathBuf};
 use oci_client::manifest::OciManifest;
 
 use crate::images::archive::LayerExtractor;
+use crate::images::layer_pool::LayerPool;
 use crate::runtime::layout::ImageFilesystemLayout;
+use crate::runtime::types::BlobKind;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 // ============================================================================
@@ -28,12 +30,15 @@ use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 /// verification.
 pub struct ImageStorage {
     layout: ImageFilesystemLayout,
+    /// Hardlink pool shared by extracted layers (`None` = no dedup).
+    layer_pool: Option<LayerPool>,
 }
 
 impl std::fmt::Debug for ImageStorage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ImageStorage")
             .field("images_dir", &self.layout.root())
+            .field("dedup_layers", &self.layer_pool.is_some())
             .finish()
     }
 }
@@ -43,7 +48,25 @@ impl ImageStorage {
     pub fn new(images_dir: PathBuf) -> BoxliteResult<Self> {
         let layout = ImageFilesystemLayout::new(images_dir);
         layout.prepare()?;
-        Ok(Self { layout })
+        Ok(Self {
+            layout,
+            layer_pool: None,
+        })
+    }
+
+    /// Hardlink identical files across extracted layers to a shared pool.
+    ///
+    /// Needs hardlinks between the pool and `extracted/`, which share the
+    /// images directory; files that cannot be linked keep their own copy.
+    pub fn set_layer_dedup(&mut self, enabled: bool) {
+        self.layer_pool = enabled.then(|| LayerPool::new(self.layout.cas_dir()));
+    }
+
+    /// Remove pool entries no longer linked from any extracted layer.
+    ///
+    /// Returns the number of entries removed; a no-op without dedup.
+    pub fn sweep_layer_pool(&self) -> usize {
+        self.layer_pool.as_ref().map_or(0, LayerPool::sweep)
     }
 
     // ========================================================================
@@ -239,6 +262,15 @@ impl ImageStorage {
             return Err(e);
         }
 
+        // Dedup before publishing so readers never see files being swapped.
+        // A failure only costs disk space.
+        if let Some(pool) = &self.layer_pool {
+            match pool.dedup(&temp_path) {
+                Ok(shared) => tracing::debug!("Layer {} shares {} files via pool", digest, shared),
+                Err(e) => tracing::warn!("Failed to dedup layer {}: {}", digest, e),
+            }
+        }
+
         // Atomic rename: only one thread/process wins
         match std::fs::rename(&temp_path, &extracted_path) {
             Ok(()) => {
@@ -427,6 +459,50 @@ impl ImageStorage {
         layer_digests.iter().all(|digest| self.has_layer(digest))
     }
 
+    /// Get path to the ext4 disk image built for an image digest.
+    ///
+    /// **Mutability**: Immutable - pure path computation, no I/O.
+    pub fn disk_image_path(&self, image_digest: &str) -> PathBuf {
+        self.layout
+            .disk_images_dir()
+            .join(format!("{}.ext4", image_digest.replace(':', "-")))
+    }
+
+    /// Enumerate every blob on disk with the digest it is stored under.
+    ///
+    /// **Mutability**: Immutable - reads filesystem only, no state changes.
+    ///
+    /// Covers manifests, configs, layer tarballs, extracted layers, image
+    /// disks, and local bundle caches. In-flight downloads and extractions
+    /// (temp names with extra suffixes) are skipped.
+    pub fn list_blobs(&self) -> BoxliteResult<Vec<StoredBlob>> {
+        let mut blobs = Vec::new();
+        let digest_dirs = [
+            (BlobKind::Manifest, self.layout.manifests_dir(), ".json"),
+            (BlobKind::Config, self.layout.configs_dir(), ".json"),
+            (BlobKind::Layer, self.layout.layers_dir(), ".tar.gz"),
+            (BlobKind::ExtractedLayer, self.layout.extracted_dir(), ""),
+            (BlobKind::DiskImage, self.layout.disk_images_dir(), ".ext4"),
+        ];
+        for (kind, dir, suffix) in digest_dirs {
+            for (name, path) in read_dir_names(&dir)? {
+                if let Some(digest) = digest_from_file_name(&name, suffix) {
+                    blobs.push(StoredBlob { kind, digest, path });
+                }
+            }
+        }
+        for (name, path) in read_dir_names(&self.layout.local_dir())? {
+            if path.is_dir() {
+                blobs.push(StoredBlob {
+                    kind: BlobKind::LocalBundleCache,
+                    digest: name,
+                    path,
+                });
+            }
+        }
+        Ok(blobs)
+    }
+
     /// Get the images directory path.
     ///
     /// **Mutability**: Immutable - returns reference to stored path.
@@ -456,11 +532,54 @@ impl ImageStorage {
     }
 }
 
+/// A blob found on disk by [`ImageStorage::list_blobs`].
+#[derive(Debug, Clone)]
+pub struct StoredBlob {
+    pub kind: BlobKind,
+    /// Digest the blob is stored under ("sha256:..."), or the directory
+    /// name for local bundle caches.
+    pub digest: String,
+    pub path: PathBuf,
+}
+
+/// Entries of `dir` as (file name, path). A missing directory is empty.
+fn read_dir_names(dir: &Path) -> BoxliteResult<Vec<(String, PathBuf)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(BoxliteError::Storage(format!(
+                "Failed to read {}: {}",
+                dir.display(),
+                e
+            )));
+        }
+    };
+    Ok(entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            Some((name, entry.path()))
+        })
+        .collect())
+}
+
+/// Parse a blob file name (`{algo}-{hex}{suffix}`) back into `{algo}:{hex}`.
+fn digest_from_file_name(name: &str, suffix: &str) -> Option<String> {
+    let stem = name.strip_suffix(suffix)?;
+    let (algorithm, hex) = stem.split_once('-')?;
+    let valid = !algorithm.is_empty()
+        && algorithm.chars().all(|c| c.is_ascii_alphanumeric())
+        && !hex.is_empty()
+        && hex.chars().all(|c| c.is_ascii_hexdigit());
+    valid.then(|| format!("{}:{}", algorithm, hex))
+}
+
 // ============================================================================
 // HASHING WRITER
 // ============================================================================
 
-/// AsyncWrite wrapper that computes SHA256 of all bytes written through it.
+/// Writer wrapper (sync or async) that computes SHA256 of all bytes written through it.
 ///
 /// Feeds every successfully written byte through a SHA256 hasher, providing
 /// inline digest verification without requiring a post-download re-read.
@@ -482,6 +601,11 @@ impl<W> HashingWriter<W> {
         }
     }
 
+    /// Bytes written through the writer so far.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
     /// Consume the writer and return (inner_writer, hex_hash, bytes_written).
     pub fn finalize(self) -> (W, String, u64) {
         use sha2::Digest;
@@ -524,6 +648,20 @@ impl<W: tokio::io::AsyncWrite + Unpin> tokio::io::AsyncWrite for HashingWriter<W
     }
 }
 
+impl<W: std::io::Write> std::io::Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        use sha2::Digest;
+        let n = self.inner.write(buf)?;
+        self.hasher.update(&buf[..n]);
+        self.bytes_written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
 // ============================================================================
 // STAGED DOWNLOAD
 // ============================================================================
@@ -555,8 +693,9 @@ pub struct StagedDownload {
 }
 
 impl StagedDownload {
-    /// Create a new staged download
-    fn new(
+    /// Create a new staged download that commits `staged_path` to
+    /// `final_path` once its SHA256 matches `expected_hash`.
+    pub fn new(
         staged_path: PathBuf,
         final_path: PathBuf,
         expected_hash: String,
@@ -580,6 +719,23 @@ impl StagedDownload {
         self.writer.as_mut().expect("writer already consumed")
     }
 
+    /// Bytes staged so far; an interrupted download resumes from here.
+    pub fn bytes_written(&self) -> u64 {
+        self.writer.as_ref().map_or(0, HashingWriter::bytes_written)
+    }
+
+    /// Discard the staged bytes so the blob can be downloaded from the start.
+    pub async fn restart(&mut self) -> std::io::Result<()> {
+        use sha2::Digest;
+        use tokio::io::AsyncSeekExt;
+        let writer = self.file();
+        writer.inner.set_len(0).await?;
+        writer.inner.rewind().await?;
+        writer.hasher = sha2::Sha256::new();
+        writer.bytes_written = 0;
+        Ok(())
+    }
+
     /// Get the staged file path (for debugging/logging)
     #[allow(unused)]
     pub fn staged_path(&self) -> &Path {
@@ -707,6 +863,52 @@ mod tests {
         assert_eq!(store.images_dir(), images_dir);
     }
 
+    #[test]
+    fn test_list_blobs_skips_temp_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = ImageStorage::new(temp_dir.path().to_path_buf()).unwrap();
+
+        std::fs::write(store.manifest_path("sha256:aa"), "{}").unwrap();
+        std::fs::write(store.layer_tarball_path("sha256:bb"), "layer").unwrap();
+        std::fs::write(
+            store
+                .layer_tarball_path("sha256:cc")
+                .with_extension("1234.downloading"),
+            "partial",
+        )
+        .unwrap();
+        std::fs::create_dir_all(store.layer_extracted_path("sha256:bb")).unwrap();
+        std::fs::create_dir_all(
+            store
+                .layer_extracted_path("sha256:dd")
+                .with_extension("1234.extracting"),
+        )
+        .unwrap();
+        std::fs::write(store.disk_image_path("sha256:ee"), "ext4").unwrap();
+        let local = store.local_bundle_cache_dir(Path::new("/bundle"), "sha256:ff");
+        std::fs::create_dir_all(&local).unwrap();
+
+        let found: Vec<(BlobKind, String)> = store
+            .list_blobs()
+            .unwrap()
+            .into_iter()
+            .map(|blob| (blob.kind, blob.digest))
+            .collect();
+
+        let local_name = local.file_name().unwrap().to_str().unwrap().to_string();
+        let expected = [
+            (BlobKind::Manifest, "sha256:aa".to_string()),
+            (BlobKind::Layer, "sha256:bb".to_string()),
+            (BlobKind::ExtractedLayer, "sha256:bb".to_string()),
+            (BlobKind::DiskImage, "sha256:ee".to_string()),
+            (BlobKind::LocalBundleCache, local_name),
+        ];
+        assert_eq!(found.len(), expected.len(), "{found:?}");
+        for entry in &expected {
+            assert!(found.contains(entry), "missing {entry:?} in {found:?}");
+        }
+    }
+
     #[test]
     fn test_manifest_path() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -743,6 +945,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_extract_layer_dedup_shares_inodes_across_layers() {
+        use std::os::unix::fs::MetadataExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = ImageStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        store.set_layer_dedup(true);
+        let tar_path = temp_dir.path().join("layer.tar");
+        std::fs::write(&tar_path, tar_with_whiteout_marker()).unwrap();
+
+        // Two layers carrying the same bin/new-tool.
+        store.extract_layer("sha256:layer-a", &tar_path).unwrap();
+        store.extract_layer("sha256:layer-b", &tar_path).unwrap();
+
+        let ino = |digest: &str| {
+            std::fs::metadata(store.layer_extracted_path(digest).join("bin/new-tool"))
+                .unwrap()
+                .ino()
+        };
+        assert_eq!(ino("sha256:layer-a"), ino("sha256:layer-b"));
+        assert_eq!(
+            std::fs::read(
+                store
+                    .layer_extracted_path("sha256:layer-b")
+                    .join("bin/new-tool")
+            )
+            .unwrap(),
+            b"upper"
+        );
+
+        // The pool entry goes once no layer links to it.
+        std::fs::remove_dir_all(store.layer_extracted_path("sha256:layer-a")).unwrap();
+        assert_eq!(store.sweep_layer_pool(), 0);
+        std::fs::remove_dir_all(store.layer_extracted_path("sha256:layer-b")).unwrap();
+        assert_eq!(store.sweep_layer_pool(), 1);
+    }
+
     #[test]
     fn test_config_path() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -886,6 +1125,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_staged_download_restart_discards_partial_bytes() {
+        use sha2::Digest;
+        use tokio::io::AsyncWriteExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = ImageStorage::new(temp_dir.path().to_path_buf()).unwrap();
+
+        let content = b"hello world";
+        let digest = format!("sha256:{:x}", sha2::Sha256::digest(content));
+        let mut staged = store
+            .stage_layer_download(&digest, content.len() as i64)
+            .await
+            .unwrap();
+        staged.file().write_all(b"garbage").await.unwrap();
+        assert_eq!(staged.bytes_written(), 7);
+
+        staged.restart().await.unwrap();
+        assert_eq!(staged.bytes_written(), 0);
+        staged.file().write_all(content).await.unwrap();
+        staged.file().flush().await.unwrap();
+        assert!(
+            staged.commit().await.unwrap(),
+            "restarted download should verify against the full blob"
+        );
+    }
+
     #[tokio::test]
     async fn test_staged_download_commit_negative_size_skips_validation() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -12,21 +12,39 @@
 //! - `config()` - Load config JSON
 //! - `layer_tarball()` - Get layer tarball path
 //! - `layer_extracted()` - Get extracted layer path (extracts if needed)
+//! - `enforce_size_cap()` - Evict least-recently-used images over the size cap
+//! - `commit()` - Register a locally built image (committed box)
 
 use crate::db::{CachedImage, Database, ImageIndexStore};
+use crate::images::archive::LayerDiff;
+use crate::images::blob_store::ImageBlobStore;
+use crate::images::docker_config::DockerConfig;
 use crate::images::manager::{ImageManifest, LayerInfo};
-use crate::images::storage::ImageStorage;
-use crate::runtime::options::{ImageRegistry, ImageRegistryAuth, RegistryTransport};
+use crate::images::object::image_digest;
+use crate::images::progress::LayerProgress;
+use crate::images::storage::{ImageStorage, StagedDownload};
+use crate::runtime::options::{
+    ImageRegistry, ImageRegistryAuth, Platform, PruneOptions, PullPolicy, RegistryTransport,
+    RetryPolicy,
+};
+use crate::runtime::types::{
+    BlobKind, PrunedBlob, PrunedReport, PullPhase, PullProgress, PullProgressCallback,
+};
 use boxlite_shared::{BoxliteError, BoxliteResult};
+use chrono::{DateTime, Utc};
 use oci_client::Reference;
-use oci_client::client::{ClientConfig, ClientProtocol};
+use oci_client::client::{BlobResponse, ClientConfig, ClientProtocol, SizedStream};
+use oci_client::errors::{OciDistributionError, OciErrorCode};
 use oci_client::manifest::{
     ImageIndexEntry, OciDescriptor, OciImageIndex, OciImageManifest as ClientOciImageManifest,
 };
 use oci_client::secrets::RegistryAuth as OciRegistryAuth;
 use oci_spec::image::MediaType;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::sync::RwLock;
 
 // ============================================================================
@@ -40,12 +58,12 @@ use tokio::sync::RwLock;
 struct ImageStoreInner {
     index: ImageIndexStore,
     /// Storage is Arc-wrapped so it can be shared with BlobSource
-    storage: Arc<ImageStorage>,
+    storage: Arc<dyn ImageBlobStore>,
 }
 
 impl ImageStoreInner {
     fn new(images_dir: PathBuf, db: Database) -> BoxliteResult<Self> {
-        let storage = Arc::new(ImageStorage::new(images_dir)?);
+        let storage: Arc<dyn ImageBlobStore> = Arc::new(ImageStorage::new(images_dir)?);
         let index = ImageIndexStore::new(db);
         Ok(Self { index, storage })
     }
@@ -84,6 +102,15 @@ pub struct ImageStore {
     registries: Vec<String>,
     /// Registry transport, TLS, auth, and search settings.
     image_registries: Vec<ImageRegistry>,
+    /// On-disk size cap for cached images (`None` = unbounded).
+    max_bytes: Option<u64>,
+    /// Retry behavior for transient registry failures.
+    retry_policy: RetryPolicy,
+    /// Last time each cached reference was pulled or served from cache.
+    ///
+    /// In-memory only; references never touched by this process fall back
+    /// to their `cached_at` timestamp from the index.
+    last_access: Mutex<HashMap<String, DateTime<Utc>>>,
 }
 
 impl std::fmt::Debug for ImageStore {
@@ -112,13 +139,49 @@ impl ImageStore {
             inner: RwLock::new(inner),
             registries,
             image_registries,
+            max_bytes: None,
+            retry_policy: RetryPolicy::default(),
+            last_access: Mutex::new(HashMap::new()),
         })
     }
 
+    /// Cap the on-disk size of cached images.
+    ///
+    /// The cap is enforced by [`enforce_size_cap`](Self::enforce_size_cap).
+    pub fn with_max_bytes(mut self, max_bytes: Option<u64>) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Retry transient registry failures during pulls per `policy`.
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Keep blobs in `storage` instead of the filesystem store under the
+    /// images directory. The image index stays in the database.
+    pub fn with_blob_store(mut self, storage: Arc<dyn ImageBlobStore>) -> Self {
+        self.inner.get_mut().storage = storage;
+        self
+    }
+
+    /// Hardlink identical files across extracted layers to a shared pool.
+    ///
+    /// Has no effect on a blob store that is already shared.
+    pub fn with_layer_dedup(mut self, enabled: bool) -> Self {
+        let inner = self.inner.get_mut();
+        // Not yet shared: only the builder holds the storage.
+        if let Some(storage) = Arc::get_mut(&mut inner.storage) {
+            storage.set_layer_dedup(enabled);
+        }
+        self
+    }
+
     /// Get shared reference to image storage for BlobSource creation.
     ///
     /// This allows creating `StoreBlobSource` that can outlive the lock.
-    pub async fn storage(&self) -> Arc<ImageStorage> {
+    pub async fn storage(&self) -> Arc<dyn ImageBlobStore> {
         Arc::clone(&self.inner.read().await.storage)
     }
 
@@ -153,6 +216,27 @@ impl ImageStore {
     /// Thread-safe: Multiple concurrent pulls of the same image will only
     /// download once; others will get the cached result.
     pub async fn pull(&self, image_ref: &str) -> BoxliteResult<ImageManifest> {
+        self.pull_with_progress(image_ref, None, PullPolicy::IfNotPresent, None)
+            .await
+    }
+
+    /// [`pull`](Self::pull), reporting manifest and per-layer download
+    /// progress to `progress`. Cached images report nothing.
+    ///
+    /// `platform` selects the manifest from a multi-platform image; `None`
+    /// means [`Platform::host`]. A cached image built for another platform
+    /// is pulled again.
+    ///
+    /// `policy` decides whether the cache is consulted: [`PullPolicy::Always`]
+    /// skips it and re-fetches the manifest (cached layers are still reused),
+    /// [`PullPolicy::Never`] never contacts the registry.
+    pub async fn pull_with_progress(
+        &self,
+        image_ref: &str,
+        platform: Option<&Platform>,
+        policy: PullPolicy,
+        progress: Option<&PullProgressCallback>,
+    ) -> BoxliteResult<ImageManifest> {
         use super::ReferenceIter;
 
         tracing::debug!(
@@ -171,18 +255,31 @@ impl ImageStore {
             let ref_str = reference.whole();
 
             // Fast path: check cache with read lock
-            {
+            if policy != PullPolicy::Always {
                 let inner = self.inner.read().await;
-                if let Some(manifest) = self.try_load_cached(&inner, &ref_str)? {
+                if let Some(manifest) = self.try_load_cached(&inner, &ref_str, platform)? {
                     tracing::info!("Using cached image: {}", ref_str);
+                    self.touch(&ref_str);
                     return Ok(manifest);
                 }
             } // Read lock released
 
+            if policy == PullPolicy::Never {
+                errors.push((
+                    ref_str,
+                    BoxliteError::Image("not cached locally and pull policy is Never".into()),
+                ));
+                continue;
+            }
+
             // Slow path: pull from registry
             tracing::info!("Pulling image from registry: {}", ref_str);
-            match self.pull_from_registry(&reference).await {
+            match self
+                .pull_from_registry(&reference, platform, progress)
+                .await
+            {
                 Ok(manifest) => {
+                    self.touch(&ref_str);
                     if !errors.is_empty() {
                         tracing::info!(
                             original = %image_ref,
@@ -216,7 +313,17 @@ impl ImageStore {
                 .map(|(registry, err)| format!("  - {}: {}", registry, err))
                 .collect();
 
-            Err(BoxliteError::Storage(format!(
+            // A registry refusal (auth required / not found) is actionable by
+            // the caller; surface it as an image error rather than storage.
+            let refused = errors
+                .iter()
+                .any(|(_, err)| matches!(err, BoxliteError::Image(_)));
+            let error = if refused {
+                BoxliteError::Image
+            } else {
+                BoxliteError::Storage
+            };
+            Err(error(format!(
                 "Failed to pull image '{}' after trying {} {}:\n{}",
                 image_ref,
                 errors.len(),
@@ -230,6 +337,62 @@ impl ImageStore {
         }
     }
 
+    /// Resolve `image_ref` to the first registry candidate that has it,
+    /// without downloading anything.
+    ///
+    /// A cached image resolves locally; otherwise each candidate's manifest
+    /// digest is fetched (a `HEAD` request) in registry order. Returns the
+    /// fully qualified reference that a [`pull`](Self::pull) would use.
+    pub async fn resolve(&self, image_ref: &str) -> BoxliteResult<String> {
+        use super::ReferenceIter;
+
+        let candidates = ReferenceIter::new(image_ref, &self.registries)
+            .map_err(|e| BoxliteError::Storage(format!("invalid image reference: {e}")))?;
+
+        let mut errors: Vec<String> = Vec::new();
+        for reference in candidates {
+            let ref_str = reference.whole();
+            {
+                let inner = self.inner.read().await;
+                if self.try_load_cached(&inner, &ref_str, None)?.is_some() {
+                    return Ok(ref_str);
+                }
+            }
+
+            let client = self.client_for(&reference);
+            let auth = self.auth_for(reference.registry());
+            match retry_transient(&self.retry_policy, "manifest lookup", || {
+                client.fetch_manifest_digest(&reference, &auth)
+            })
+            .await
+            {
+                Ok(_) => return Ok(ref_str),
+                Err(e) => {
+                    let err = registry_error("failed to resolve manifest", &reference, e);
+                    errors.push(format!("  - {}: {}", ref_str, err));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            return Err(BoxliteError::Storage(format!(
+                "No registries configured for image: {}",
+                image_ref
+            )));
+        }
+        Err(BoxliteError::Image(format!(
+            "Image '{}' not found in {} {}:\n{}",
+            image_ref,
+            errors.len(),
+            if errors.len() == 1 {
+                "registry"
+            } else {
+                "registries"
+            },
+            errors.join("\n")
+        )))
+    }
+
     /// List all cached images.
     ///
     /// Returns a vector of (reference, CachedImage) tuples ordered by cache time (Newest first).
@@ -238,6 +401,189 @@ impl ImageStore {
         inner.index.list_all()
     }
 
+    /// Total on-disk size, in bytes, of the blobs referenced by cached images.
+    ///
+    /// Blobs shared between images are counted once.
+    pub async fn disk_usage(&self) -> BoxliteResult<u64> {
+        let inner = self.inner.read().await;
+        let entries = inner.index.list_all()?;
+        let digests: HashSet<&str> = entries
+            .iter()
+            .flat_map(|(_, cached)| image_digests(cached))
+            .collect();
+        Ok(digests
+            .into_iter()
+            .map(|digest| blob_size(&inner.storage, digest))
+            .sum())
+    }
+
+    /// Evict least-recently-used images until the store fits under its cap.
+    ///
+    /// `in_use` lists image references (as the user wrote them) that must be
+    /// kept, e.g. the images of running boxes. Each one is resolved against
+    /// the configured search registries so unqualified names protect the
+    /// qualified index entry they were pulled as. Blobs shared with a
+    /// retained image are never removed.
+    ///
+    /// Returns the evicted references, least recently used first. A no-op
+    /// when no cap is configured or the store is already under it.
+    pub async fn enforce_size_cap(&self, in_use: &[String]) -> BoxliteResult<Vec<String>> {
+        let Some(max_bytes) = self.max_bytes else {
+            return Ok(Vec::new());
+        };
+
+        let mut usage = self.disk_usage().await?;
+        if usage <= max_bytes {
+            return Ok(Vec::new());
+        }
+
+        // Exclusive lock: no pull may read blobs while we delete them.
+        let inner = self.inner.write().await;
+        let protected = self.protected_refs(in_use);
+
+        let mut entries = inner.index.list_all()?;
+        entries.sort_by_key(|(reference, cached)| self.last_accessed(reference, cached));
+
+        let mut evicted: HashSet<String> = HashSet::new();
+        let mut order = Vec::new();
+        for (reference, cached) in &entries {
+            if usage <= max_bytes {
+                break;
+            }
+            if protected.contains(reference) {
+                continue;
+            }
+
+            // Blobs still referenced by any image that stays in the store.
+            let retained: HashSet<&str> = entries
+                .iter()
+                .filter(|(r, _)| r != reference && !evicted.contains(r))
+                .flat_map(|(_, c)| image_digests(c))
+                .collect();
+
+            let mut freed = 0;
+            for digest in image_digests(cached).filter(|d| !retained.contains(d)) {
+                freed += blob_size(&inner.storage, digest);
+                remove_blob(&inner.storage, digest);
+            }
+            inner.index.remove(reference)?;
+            self.last_access.lock().unwrap().remove(reference);
+
+            usage = usage.saturating_sub(freed);
+            tracing::info!(
+                reference = %reference,
+                freed_bytes = freed,
+                usage_bytes = usage,
+                max_bytes,
+                "Evicted least-recently-used image to honor image store cap"
+            );
+            evicted.insert(reference.clone());
+            order.push(reference.clone());
+        }
+
+        if !order.is_empty() {
+            inner.storage.sweep_layer_pool();
+        }
+
+        if usage > max_bytes {
+            tracing::warn!(
+                usage_bytes = usage,
+                max_bytes,
+                "Image store still over its cap; remaining images are in use"
+            );
+        }
+
+        Ok(order)
+    }
+
+    /// Delete blobs that no cached image references.
+    ///
+    /// A manifest, config, or layer survives if any index entry lists it; a
+    /// disk image survives if it was built from a cached image's layers or
+    /// backs a box disk (`backing_files`). Local bundle caches survive while
+    /// their bundle path is in `local_bundles`. Cached images themselves are
+    /// never removed.
+    ///
+    /// Blobs written by a pull that has not yet indexed its image look
+    /// unreferenced; use `options.until` to leave recent blobs alone.
+    pub async fn prune(
+        &self,
+        options: &PruneOptions,
+        local_bundles: &[PathBuf],
+        backing_files: &HashSet<PathBuf>,
+    ) -> BoxliteResult<PrunedReport> {
+        // Exclusive lock: no pull may start reading blobs while we delete them.
+        let inner = self.inner.write().await;
+        let entries = inner.index.list_all()?;
+
+        let referenced: HashSet<&str> = entries
+            .iter()
+            .flat_map(|(_, cached)| image_digests(cached))
+            .collect();
+        let disk_images: HashSet<String> = entries
+            .iter()
+            .map(|(_, cached)| image_digest(cached.layers.iter().map(String::as_str)))
+            .collect();
+        let backing_files: HashSet<PathBuf> = backing_files.iter().map(|p| canonical(p)).collect();
+        // Cache dirs are named `{path hash}-{manifest}`; any manifest of an
+        // active bundle path is kept.
+        let bundle_prefixes: Vec<String> = local_bundles
+            .iter()
+            .filter_map(|bundle| {
+                let dir = inner.storage.local_bundle_cache_dir(bundle, "");
+                Some(dir.file_name()?.to_string_lossy().into_owned())
+            })
+            .collect();
+
+        let mut report = PrunedReport::default();
+        for blob in inner.storage.list_blobs()? {
+            let keep = match blob.kind {
+                BlobKind::DiskImage => {
+                    disk_images.contains(&blob.digest)
+                        || backing_files.contains(&canonical(&blob.path))
+                }
+                BlobKind::LocalBundleCache => bundle_prefixes
+                    .iter()
+                    .any(|prefix| blob.digest.starts_with(prefix.as_str())),
+                _ => referenced.contains(blob.digest.as_str()),
+            };
+            if keep || !modified_before(&blob.path, options.until) {
+                continue;
+            }
+
+            let size = path_size(&blob.path);
+            if !options.dry_run
+                && let Err(e) = remove_path(&blob.path)
+            {
+                tracing::warn!("Failed to prune blob {}: {}", blob.path.display(), e);
+                continue;
+            }
+            report.reclaimed_bytes += size;
+            report.removed.push(PrunedBlob {
+                kind: blob.kind,
+                digest: blob.digest,
+                path: blob.path,
+                size,
+            });
+        }
+
+        // Pool entries only the removed layers linked to.
+        let swept_pool_files = if options.dry_run {
+            0
+        } else {
+            inner.storage.sweep_layer_pool()
+        };
+
+        tracing::info!(
+            removed = report.removed.len(),
+            reclaimed_bytes = report.reclaimed_bytes,
+            swept_pool_files,
+            dry_run = options.dry_run,
+            "Pruned image store"
+        );
+        Ok(report)
+    }
+
     /// Load an OCI image from a local directory.
     ///
     /// Reads OCI layout files (index.json, manifest blob) using oci-spec types
@@ -322,6 +668,115 @@ impl ImageStore {
         })
     }
 
+    /// Register a locally built image (e.g. a committed box) as `image_ref`.
+    ///
+    /// `base_layers` pairs each base layer with its current tarball path; any
+    /// not yet in the store (local bundles) are copied in. `layer_path` is the
+    /// new top layer and is moved into the store. `config` is the serialized
+    /// image config, whose `rootfs.diff_ids` must already list the new layer.
+    ///
+    /// The image is indexed under the first registry candidate of
+    /// `image_ref`, the same key [`pull`](Self::pull) checks first, so a later
+    /// pull of the reference resolves locally.
+    pub async fn commit(
+        &self,
+        image_ref: &str,
+        base_layers: &[(LayerInfo, PathBuf)],
+        layer_path: &Path,
+        layer: &LayerDiff,
+        config: &[u8],
+    ) -> BoxliteResult<ImageManifest> {
+        use super::ReferenceIter;
+        use sha2::{Digest, Sha256};
+
+        let key = ReferenceIter::new(image_ref, &self.registries)
+            .map_err(|e| BoxliteError::Storage(format!("invalid image reference: {e}")))?
+            .next()
+            .map(|reference| reference.whole())
+            .ok_or_else(|| {
+                BoxliteError::Storage(format!("invalid image reference: {image_ref}"))
+            })?;
+
+        let mut layers: Vec<LayerInfo> = base_layers.iter().map(|(l, _)| l.clone()).collect();
+        layers.push(LayerInfo {
+            digest: layer.digest.clone(),
+            media_type: "application/vnd.oci.image.layer.v1.tar+gzip".to_string(),
+            size: layer.size as i64,
+        });
+        let config_digest = format!("sha256:{:x}", Sha256::digest(config));
+
+        let inner = self.inner.read().await;
+        let storage = &inner.storage;
+
+        for (info, path) in base_layers {
+            if !storage.has_layer(&info.digest) {
+                copy_blob(path, &storage.layer_tarball_path(&info.digest))?;
+            }
+        }
+        move_blob(layer_path, &storage.layer_tarball_path(&layer.digest))?;
+
+        let config_path = storage.config_path(&config_digest);
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| {
+                BoxliteError::Storage(format!("Failed to create config dir: {}", e))
+            })?;
+        }
+        std::fs::write(&config_path, config).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to write config to {}: {}",
+                config_path.display(),
+                e
+            ))
+        })?;
+
+        let manifest = oci_client::manifest::OciManifest::Image(ClientOciImageManifest {
+            schema_version: 2,
+            media_type: Some("application/vnd.oci.image.manifest.v1+json".to_string()),
+            config: OciDescriptor {
+                digest: config_digest.clone(),
+                media_type: "application/vnd.oci.image.config.v1+json".to_string(),
+                size: config.len() as i64,
+                urls: None,
+                annotations: None,
+            },
+            layers: layers
+                .iter()
+                .map(|l| OciDescriptor {
+                    digest: l.digest.clone(),
+                    media_type: l.media_type.clone(),
+                    size: l.size,
+                    urls: None,
+                    annotations: None,
+                })
+                .collect(),
+            annotations: None,
+            artifact_type: None,
+            subject: None,
+        });
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| BoxliteError::Storage(format!("Failed to serialize manifest: {}", e)))?;
+        let manifest_digest = format!("sha256:{:x}", Sha256::digest(manifest_json.as_bytes()));
+        storage.save_manifest(&manifest, &manifest_digest)?;
+        let diff_ids = self.load_diff_ids_from_config(&inner, &config_digest)?;
+        drop(inner);
+
+        let image_manifest = ImageManifest {
+            manifest_digest,
+            layers,
+            config_digest,
+            diff_ids,
+        };
+        self.update_index(&key, &image_manifest).await?;
+        self.touch(&key);
+
+        tracing::info!(
+            image_ref = %key,
+            layer = %layer.digest,
+            "Committed image"
+        );
+        Ok(image_manifest)
+    }
+
     /// Get an ImageManifest digest from the descriptor.
     ///
     /// Handles at most two levels (like containerd):
@@ -372,18 +827,16 @@ impl ImageStore {
                     })?;
 
                 // Detect platform
-                let (platform_os, platform_arch) = Self::detect_platform();
+                let platform = Platform::host();
 
                 tracing::debug!(
-                    "Selecting platform manifest: {}/{} (Rust arch: {})",
-                    platform_os,
-                    platform_arch,
+                    "Selecting platform manifest: {} (Rust arch: {})",
+                    platform,
                     std::env::consts::ARCH
                 );
 
                 // Select platform-specific manifest descriptor using unified function
-                let platform_manifest =
-                    self.select_platform_manifest(&child_index, platform_os, platform_arch)?;
+                let platform_manifest = Self::select_platform_manifest(&child_index, &platform)?;
 
                 tracing::info!(
                     "Selected platform-specific manifest: {}",
@@ -421,11 +874,44 @@ impl ImageStore {
     // INTERNAL: Cache Operations
     // ========================================================================
 
+    /// Record that `image_ref` was just used.
+    fn touch(&self, image_ref: &str) {
+        self.last_access
+            .lock()
+            .unwrap()
+            .insert(image_ref.to_string(), Utc::now());
+    }
+
+    /// Last use of a cached reference, falling back to when it was cached.
+    fn last_accessed(&self, image_ref: &str, cached: &CachedImage) -> DateTime<Utc> {
+        if let Some(at) = self.last_access.lock().unwrap().get(image_ref) {
+            return *at;
+        }
+        DateTime::parse_from_rfc3339(&cached.cached_at)
+            .map(|dt| dt.with_timezone(&Utc))
+            .unwrap_or_else(|_| DateTime::<Utc>::from(std::time::SystemTime::UNIX_EPOCH))
+    }
+
+    /// Index keys that `in_use` references may have been cached under.
+    fn protected_refs(&self, in_use: &[String]) -> HashSet<String> {
+        use super::ReferenceIter;
+
+        let mut protected = HashSet::new();
+        for image_ref in in_use {
+            protected.insert(image_ref.clone());
+            if let Ok(candidates) = ReferenceIter::new(image_ref, &self.registries) {
+                protected.extend(candidates.map(|reference| reference.whole()));
+            }
+        }
+        protected
+    }
+
     /// Try to load image from local cache.
     fn try_load_cached(
         &self,
         inner: &ImageStoreInner,
         image_ref: &str,
+        platform: Option<&Platform>,
     ) -> BoxliteResult<Option<ImageManifest>> {
         // Check if image exists in index
         let cached = match inner.index.get(image_ref)? {
@@ -445,11 +931,43 @@ impl ImageStore {
             return Ok(None);
         }
 
+        if let Some(platform) = platform
+            && !Self::cached_matches_platform(inner, &cached, platform)
+        {
+            tracing::info!(
+                "Cached image {} is not built for {}, will re-download",
+                image_ref,
+                platform
+            );
+            return Ok(None);
+        }
+
         // Load manifest from disk
         let manifest = self.load_manifest_from_disk(inner, &cached)?;
         Ok(Some(manifest))
     }
 
+    /// Whether a cached image's config names `platform`. Unreadable configs
+    /// count as a mismatch so the image is pulled again.
+    fn cached_matches_platform(
+        inner: &ImageStoreInner,
+        cached: &CachedImage,
+        platform: &Platform,
+    ) -> bool {
+        let config = std::fs::read(inner.storage.config_path(&cached.config_digest))
+            .ok()
+            .and_then(|bytes| {
+                serde_json::from_slice::<oci_spec::image::ImageConfiguration>(&bytes).ok()
+            });
+        config.is_some_and(|config| {
+            platform.matches(
+                &config.os().to_string(),
+                &config.architecture().to_string(),
+                config.variant().as_deref(),
+            )
+        })
+    }
+
     fn verify_cached_image(
         &self,
         inner: &ImageStoreInner,
@@ -570,15 +1088,26 @@ impl ImageStore {
     ///
     /// This method handles the actual network I/O - manifest pull, layer download, etc.
     /// Lock is released during network I/O to allow other operations.
-    async fn pull_from_registry(&self, reference: &Reference) -> BoxliteResult<ImageManifest> {
+    async fn pull_from_registry(
+        &self,
+        reference: &Reference,
+        platform: Option<&Platform>,
+        progress: Option<&PullProgressCallback>,
+    ) -> BoxliteResult<ImageManifest> {
         let client = self.client_for(reference);
-        let auth = registry_auth_for(reference.registry(), &self.image_registries);
+        let auth = self.auth_for(reference.registry());
 
         // Step 1: Pull manifest (no lock needed)
-        let (manifest, manifest_digest_str) = client
-            .pull_manifest(reference, &auth)
+        if let Some(progress) = progress {
+            progress.report(PullProgress::image(PullPhase::Manifest));
+        }
+        let (manifest, manifest_digest_str) =
+            retry_transient(&self.retry_policy, "manifest pull", || {
+                client.pull_manifest(reference, &auth)
+            })
             .await
-            .map_err(|e| BoxliteError::Storage(format!("failed to pull manifest: {e}")))?;
+            .map_err(|e| registry_error("failed to pull manifest", reference, e))?;
+        verify_pinned_digest(reference, &manifest_digest_str)?;
 
         // Step 2: Save manifest (quick write lock)
         {
@@ -590,11 +1119,11 @@ impl ImageStore {
 
         // Step 3: Extract image manifest (may pull platform-specific manifest for multi-platform images)
         let mut image_manifest = self
-            .extract_image_manifest(&client, reference, &manifest, manifest_digest_str)
+            .extract_image_manifest(&client, reference, &manifest, manifest_digest_str, platform)
             .await?;
 
         // Step 4: Download layers (no lock during download, atomic file writes)
-        self.download_layers(&client, reference, &image_manifest.layers)
+        self.download_layers(&client, reference, &image_manifest.layers, progress)
             .await?;
 
         // Step 5: Download config (no lock during download)
@@ -645,6 +1174,7 @@ impl ImageStore {
         reference: &Reference,
         manifest: &oci_client::manifest::OciManifest,
         manifest_digest: String,
+        platform: Option<&Platform>,
     ) -> BoxliteResult<ImageManifest> {
         match manifest {
             oci_client::manifest::OciManifest::Image(img) => {
@@ -658,7 +1188,7 @@ impl ImageStore {
                 })
             }
             oci_client::manifest::OciManifest::ImageIndex(index) => {
-                self.extract_platform_manifest(client, reference, index)
+                self.extract_platform_manifest(client, reference, index, platform)
                     .await
             }
         }
@@ -700,17 +1230,17 @@ impl ImageStore {
         client: &oci_client::Client,
         reference: &Reference,
         index: &oci_client::manifest::OciImageIndex,
+        platform: Option<&Platform>,
     ) -> BoxliteResult<ImageManifest> {
-        let (platform_os, platform_arch) = Self::detect_platform();
+        let platform = platform.cloned().unwrap_or_else(Platform::host);
 
         tracing::debug!(
-            "Image index detected, selecting platform: {}/{} (Rust arch: {})",
-            platform_os,
-            platform_arch,
+            "Image index detected, selecting platform: {} (Rust arch: {})",
+            platform,
             std::env::consts::ARCH
         );
 
-        let platform_manifest = self.select_platform_manifest(index, platform_os, platform_arch)?;
+        let platform_manifest = Self::select_platform_manifest(index, &platform)?;
 
         let platform_ref = build_platform_ref(reference, &platform_manifest.digest);
         let platform_reference: Reference = platform_ref
@@ -721,13 +1251,13 @@ impl ImageStore {
             "Pulling platform-specific manifest: {}",
             platform_manifest.digest
         );
-        let (platform_image, platform_digest) = client
-            .pull_manifest(
-                &platform_reference,
-                &registry_auth_for(reference.registry(), &self.image_registries),
-            )
+        let auth = self.auth_for(reference.registry());
+        let (platform_image, platform_digest) =
+            retry_transient(&self.retry_policy, "platform manifest pull", || {
+                client.pull_manifest(&platform_reference, &auth)
+            })
             .await
-            .map_err(|e| BoxliteError::Storage(format!("failed to pull platform manifest: {e}")))?;
+            .map_err(|e| registry_error("failed to pull platform manifest", reference, e))?;
 
         // Save platform manifest (quick lock)
         {
@@ -754,48 +1284,33 @@ impl ImageStore {
         }
     }
 
-    fn detect_platform() -> (&'static str, &'static str) {
-        let os = "linux";
-        let arch = match std::env::consts::ARCH {
-            "aarch64" => "arm64",
-            "x86_64" => "amd64",
-            "x86" => "386",
-            "arm" => "arm",
-            other => other,
-        };
-        (os, arch)
-    }
-
     fn select_platform_manifest<'b>(
-        &self,
         index: &'b oci_client::manifest::OciImageIndex,
-        platform_os: &str,
-        platform_arch: &str,
+        platform: &Platform,
     ) -> BoxliteResult<&'b oci_client::manifest::ImageIndexEntry> {
         index
             .manifests
             .iter()
             .find(|m| {
-                if let Some(p) = &m.platform {
-                    p.os == platform_os && p.architecture == platform_arch
-                } else {
-                    false
-                }
+                m.platform
+                    .as_ref()
+                    .is_some_and(|p| platform.matches(&p.os, &p.architecture, p.variant.as_deref()))
             })
             .ok_or_else(|| {
                 let available = index
                     .manifests
                     .iter()
                     .filter_map(|m| {
-                        m.platform
-                            .as_ref()
-                            .map(|p| format!("{}/{}", p.os, p.architecture))
+                        m.platform.as_ref().map(|p| match &p.variant {
+                            Some(variant) => format!("{}/{}/{}", p.os, p.architecture, variant),
+                            None => format!("{}/{}", p.os, p.architecture),
+                        })
                     })
                     .collect::<Vec<_>>()
                     .join(", ");
-                BoxliteError::Storage(format!(
-                    "no image found for platform {}/{}. Available platforms: {}",
-                    platform_os, platform_arch, available
+                BoxliteError::Image(format!(
+                    "no image found for platform {}. Available platforms: {}",
+                    platform, available
                 ))
             })
     }
@@ -809,6 +1324,7 @@ impl ImageStore {
         client: &oci_client::Client,
         reference: &Reference,
         layers: &[LayerInfo],
+        progress: Option<&PullProgressCallback>,
     ) -> BoxliteResult<()> {
         use futures::future::join_all;
 
@@ -853,7 +1369,7 @@ impl ImageStore {
         // Download in parallel (no lock held)
         let download_futures = layers_to_download
             .iter()
-            .map(|layer| self.download_layer(client, reference, layer));
+            .map(|layer| self.download_layer(client, reference, layer, progress));
 
         let results = join_all(download_futures).await;
 
@@ -864,63 +1380,100 @@ impl ImageStore {
         Ok(())
     }
 
+    /// Download one layer, retrying transient failures per the retry policy.
+    ///
+    /// An interrupted download keeps what it has staged and resumes with a
+    /// `Range` request; a blob that fails verification is downloaded again
+    /// from the start.
     async fn download_layer(
         &self,
         client: &oci_client::Client,
         reference: &Reference,
         layer: &LayerInfo,
+        progress: Option<&PullProgressCallback>,
     ) -> BoxliteResult<()> {
-        const MAX_RETRIES: u32 = 3;
+        let policy = &self.retry_policy;
 
         tracing::info!("Downloading layer: {}", layer.digest);
 
+        let descriptor = OciDescriptor {
+            digest: layer.digest.clone(),
+            media_type: layer.media_type.clone(),
+            size: layer.size,
+            urls: None,
+            annotations: None,
+        };
         let mut last_error = None;
+        let mut layer_progress =
+            progress.map(|callback| LayerProgress::new(callback, &layer.digest, layer.size));
+        let mut interrupted = None;
 
-        for attempt in 1..=MAX_RETRIES {
-            if attempt > 1 {
+        for attempt in 0..=policy.max_retries {
+            if attempt > 0 {
+                let delay = policy.backoff(attempt);
                 tracing::info!(
-                    "Retrying layer download (attempt {}/{}): {}",
-                    attempt,
-                    MAX_RETRIES,
+                    "Retrying layer download in {:?} (attempt {}/{}): {}",
+                    delay,
+                    attempt + 1,
+                    policy.max_retries + 1,
                     layer.digest
                 );
+                tokio::time::sleep(delay).await;
             }
 
-            // Stage download (quick read lock for path computation)
-            let mut staged = {
-                let inner = self.inner.read().await;
-                match inner
-                    .storage
-                    .stage_layer_download(&layer.digest, layer.size)
-                    .await
-                {
-                    Ok(result) => result,
-                    Err(e) => {
-                        last_error = Some(format!(
-                            "Failed to stage layer {} download: {e}",
-                            layer.digest
-                        ));
-                        continue;
+            // Resume an interrupted download, else stage a new one (quick
+            // read lock for path computation)
+            let mut staged = match interrupted.take() {
+                Some(staged) => staged,
+                None => {
+                    let inner = self.inner.read().await;
+                    match inner
+                        .storage
+                        .stage_layer_download(&layer.digest, layer.size)
+                        .await
+                    {
+                        Ok(result) => result,
+                        Err(e) => {
+                            last_error = Some(format!(
+                                "Failed to stage layer {} download: {e}",
+                                layer.digest
+                            ));
+                            continue;
+                        }
                     }
                 }
             };
 
             // Download (no lock)
-            match client
-                .pull_blob(
+            let pulled = if staged.bytes_written() > 0 {
+                resume_blob(
+                    client,
                     reference,
-                    &OciDescriptor {
-                        digest: layer.digest.clone(),
-                        media_type: layer.media_type.clone(),
-                        size: layer.size,
-                        urls: None,
-                        annotations: None,
-                    },
-                    staged.file(),
+                    &descriptor,
+                    &mut staged,
+                    layer_progress.as_mut(),
                 )
                 .await
-            {
-                Ok(_) => match staged.commit().await {
+            } else {
+                match layer_progress.as_mut() {
+                    Some(layer_progress) => {
+                        client
+                            .pull_blob(
+                                reference,
+                                &descriptor,
+                                layer_progress.writer(staged.file(), 0),
+                            )
+                            .await
+                    }
+                    None => {
+                        client
+                            .pull_blob(reference, &descriptor, staged.file())
+                            .await
+                    }
+                }
+            };
+            match pulled {
+                Ok(()) => match staged.commit().await {
                     Ok(true) => {
                         tracing::info!("Downloaded and verified layer: {}", layer.digest);
                         return Ok(());
@@ -928,25 +1481,37 @@ impl ImageStore {
                     Ok(false) => {
                         tracing::warn!(
                             "Layer integrity check failed (attempt {}): hash mismatch for {}",
-                            attempt,
+                            attempt + 1,
                             layer.digest
                         );
                         last_error =
                             Some("layer integrity verification failed: hash mismatch".to_string());
                     }
                     Err(e) => {
-                        tracing::warn!("Layer commit error (attempt {}): {}", attempt, e);
+                        tracing::warn!("Layer commit error (attempt {}): {}", attempt + 1, e);
                         last_error = Some(format!("layer commit error: {e}"));
                     }
                 },
-                Err(e) => {
-                    tracing::warn!("Layer download failed (attempt {}): {}", attempt, e);
+                Err(e) if is_transient(&e) => {
+                    tracing::warn!(
+                        "Layer download interrupted after {} bytes (attempt {}): {}",
+                        staged.bytes_written(),
+                        attempt + 1,
+                        e
+                    );
                     last_error = Some(format!("failed to pull layer {}: {e}", layer.digest));
+                    interrupted = Some(staged);
+                }
+                Err(e) => {
                     staged.abort().await;
+                    return Err(registry_error("failed to pull layer", reference, e));
                 }
             }
         }
 
+        if let Some(staged) = interrupted {
+            staged.abort().await;
+        }
         Err(BoxliteError::Storage(last_error.unwrap_or_else(|| {
             "download failed after retries".to_string()
         })))
@@ -975,23 +1540,34 @@ impl ImageStore {
             inner.storage.stage_config_download(config_digest).await?
         };
 
-        // Download to temp file (no lock)
-        if let Err(e) = client
-            .pull_blob(
-                reference,
-                &OciDescriptor {
-                    digest: config_digest.to_string(),
-                    media_type: "application/vnd.oci.image.config.v1+json".to_string(),
-                    size: 0,
-                    urls: None,
-                    annotations: None,
-                },
-                staged.file(),
-            )
-            .await
-        {
-            staged.abort().await;
-            return Err(BoxliteError::Storage(format!("failed to pull config: {e}")));
+        // Download to temp file (no lock). Config blobs are small, so a
+        // retry starts over rather than resuming.
+        let descriptor = OciDescriptor {
+            digest: config_digest.to_string(),
+            media_type: "application/vnd.oci.image.config.v1+json".to_string(),
+            size: 0,
+            urls: None,
+            annotations: None,
+        };
+        let mut attempt = 0;
+        loop {
+            let Err(e) = client
+                .pull_blob(reference, &descriptor, staged.file())
+                .await
+            else {
+                break;
+            };
+            if !wait_to_retry(&self.retry_policy, attempt, "config download", &e).await {
+                staged.abort().await;
+                return Err(registry_error("failed to pull config", reference, e));
+            }
+            attempt += 1;
+            if let Err(e) = staged.restart().await {
+                staged.abort().await;
+                return Err(BoxliteError::Storage(format!(
+                    "failed to restart config {config_digest} download: {e}"
+                )));
+            }
         }
 
         // Verify and commit (atomic move to final location)
@@ -1005,6 +1581,18 @@ impl ImageStore {
         Ok(())
     }
 
+    /// Credentials for `host`: the configured registry auth, else what
+    /// `docker login` stored for it, else anonymous.
+    fn auth_for(&self, host: &str) -> OciRegistryAuth {
+        match registry_auth_for(host, &self.image_registries) {
+            OciRegistryAuth::Anonymous => DockerConfig::load()
+                .and_then(|config| config.credentials_for(host))
+                .map(|auth| oci_auth(&auth))
+                .unwrap_or(OciRegistryAuth::Anonymous),
+            auth => auth,
+        }
+    }
+
     fn client_for(&self, reference: &Reference) -> oci_client::Client {
         oci_client::Client::new(client_config_for_registry(
             reference.registry(),
@@ -1041,35 +1629,311 @@ impl ImageStore {
     }
 }
 
-fn client_config_for_registry(host: &str, image_registries: &[ImageRegistry]) -> ClientConfig {
-    let registry = image_registries
-        .iter()
-        .find(|registry| registry.host == host);
-
-    let protocol = match registry.map(|registry| &registry.transport) {
-        Some(RegistryTransport::Http) => ClientProtocol::Http,
-        _ => ClientProtocol::Https,
-    };
+/// Every blob digest a cached image occupies on disk.
+fn image_digests(cached: &CachedImage) -> impl Iterator<Item = &str> {
+    [
+        cached.manifest_digest.as_str(),
+        cached.config_digest.as_str(),
+    ]
+    .into_iter()
+    .chain(cached.layers.iter().map(String::as_str))
+}
 
-    ClientConfig {
-        protocol,
-        accept_invalid_certificates: registry.is_some_and(|registry| registry.skip_verify),
-        ..Default::default()
+/// Move a blob into the store, copying when it lives on another filesystem.
+fn move_blob(src: &Path, dst: &Path) -> BoxliteResult<()> {
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| BoxliteError::Storage(format!("Failed to create blob dir: {}", e)))?;
     }
+    if std::fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    copy_blob(src, dst)?;
+    let _ = std::fs::remove_file(src);
+    Ok(())
 }
 
-fn registry_auth_for(host: &str, image_registries: &[ImageRegistry]) -> OciRegistryAuth {
-    let auth = image_registries
-        .iter()
+/// Copy a blob into the store via a temp file, so readers never see a partial blob.
+fn copy_blob(src: &Path, dst: &Path) -> BoxliteResult<()> {
+    if let Some(parent) = dst.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| BoxliteError::Storage(format!("Failed to create blob dir: {}", e)))?;
+    }
+    let staged = dst.with_extension("partial");
+    std::fs::copy(src, &staged)
+        .and_then(|_| std::fs::rename(&staged, dst))
+        .map_err(|e| {
+            let _ = std::fs::remove_file(&staged);
+            BoxliteError::Storage(format!(
+                "Failed to copy blob {} to {}: {}",
+                src.display(),
+                dst.display(),
+                e
+            ))
+        })
+}
+
+/// Paths a blob digest may occupy: manifest, config, layer tarball, and
+/// extracted layer. Only the ones matching the blob's kind exist.
+fn blob_paths(storage: &dyn ImageBlobStore, digest: &str) -> [PathBuf; 4] {
+    [
+        storage.manifest_path(digest),
+        storage.config_path(digest),
+        storage.layer_tarball_path(digest),
+        storage.layer_extracted_path(digest),
+    ]
+}
+
+fn blob_size(storage: &dyn ImageBlobStore, digest: &str) -> u64 {
+    blob_paths(storage, digest)
+        .iter()
+        .map(|path| path_size(path))
+        .sum()
+}
+
+fn remove_blob(storage: &dyn ImageBlobStore, digest: &str) {
+    for path in blob_paths(storage, digest) {
+        if let Err(e) = remove_path(&path)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove evicted blob {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Remove a blob file or an extracted blob directory.
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+/// Canonical form of `path` for comparison, or `path` itself if it is gone.
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether `path` was last modified before `until` (always, without a cutoff).
+fn modified_before(path: &Path, until: Option<DateTime<Utc>>) -> bool {
+    let Some(until) = until else {
+        return true;
+    };
+    std::fs::symlink_metadata(path)
+        .and_then(|meta| meta.modified())
+        .is_ok_and(|modified| DateTime::<Utc>::from(modified) < until)
+}
+
+/// Apparent size of a file, or of every file under a directory.
+fn path_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.metadata().ok())
+        .filter(|meta| meta.is_file())
+        .map(|meta| meta.len())
+        .sum()
+}
+
+fn client_config_for_registry(host: &str, image_registries: &[ImageRegistry]) -> ClientConfig {
+    let registry = image_registries
+        .iter()
+        .find(|registry| registry.host == host);
+
+    let protocol = match registry.map(|registry| &registry.transport) {
+        Some(RegistryTransport::Http) => ClientProtocol::Http,
+        _ => ClientProtocol::Https,
+    };
+
+    ClientConfig {
+        protocol,
+        accept_invalid_certificates: registry.is_some_and(|registry| registry.skip_verify),
+        ..Default::default()
+    }
+}
+
+fn registry_auth_for(host: &str, image_registries: &[ImageRegistry]) -> OciRegistryAuth {
+    image_registries
+        .iter()
         .find(|registry| registry.host == host)
-        .map(|registry| &registry.auth);
+        .map(|registry| oci_auth(&registry.auth))
+        .unwrap_or(OciRegistryAuth::Anonymous)
+}
 
+fn oci_auth(auth: &ImageRegistryAuth) -> OciRegistryAuth {
     match auth {
-        Some(ImageRegistryAuth::Basic { username, password }) => {
+        ImageRegistryAuth::Basic { username, password } => {
             OciRegistryAuth::Basic(username.clone(), password.clone())
         }
-        Some(ImageRegistryAuth::Bearer { token }) => OciRegistryAuth::Bearer(token.clone()),
-        _ => OciRegistryAuth::Anonymous,
+        ImageRegistryAuth::Bearer { token } => OciRegistryAuth::Bearer(token.clone()),
+        ImageRegistryAuth::Anonymous => OciRegistryAuth::Anonymous,
+    }
+}
+
+/// Why a registry refused a request, when it is the caller's to fix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RegistryRefusal {
+    AuthRequired,
+    NotFound,
+}
+
+fn classify_registry_error(err: &OciDistributionError) -> Option<RegistryRefusal> {
+    match err {
+        OciDistributionError::AuthenticationFailure(_)
+        | OciDistributionError::UnauthorizedError { .. } => Some(RegistryRefusal::AuthRequired),
+        OciDistributionError::ServerError { code, .. } => match code {
+            401 | 403 => Some(RegistryRefusal::AuthRequired),
+            404 => Some(RegistryRefusal::NotFound),
+            _ => None,
+        },
+        OciDistributionError::ImageManifestNotFoundError(_) => Some(RegistryRefusal::NotFound),
+        OciDistributionError::RegistryError { envelope, .. } => {
+            envelope.errors.iter().find_map(|e| match e.code {
+                OciErrorCode::Unauthorized | OciErrorCode::Denied => {
+                    Some(RegistryRefusal::AuthRequired)
+                }
+                OciErrorCode::ManifestUnknown
+                | OciErrorCode::NameUnknown
+                | OciErrorCode::BlobUnknown => Some(RegistryRefusal::NotFound),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Whether a registry client error is worth retrying: the connection failed
+/// or dropped, or the registry answered with a 5xx. Refusals and other 4xx
+/// responses are final.
+fn is_transient(err: &OciDistributionError) -> bool {
+    match err {
+        OciDistributionError::RequestError(_) | OciDistributionError::IoError(_) => true,
+        OciDistributionError::ServerError { code, .. } => *code >= 500,
+        _ => false,
+    }
+}
+
+/// After a failed attempt (`attempt` is 0-based), sleep for the policy's
+/// backoff and return true if `err` is transient and retries remain.
+async fn wait_to_retry(
+    policy: &RetryPolicy,
+    attempt: u32,
+    what: &str,
+    err: &OciDistributionError,
+) -> bool {
+    if attempt >= policy.max_retries || !is_transient(err) {
+        return false;
+    }
+    let delay = policy.backoff(attempt + 1);
+    tracing::warn!(
+        "{what} failed (attempt {}/{}), retrying in {delay:?}: {err}",
+        attempt + 1,
+        policy.max_retries + 1
+    );
+    tokio::time::sleep(delay).await;
+    true
+}
+
+/// Run a registry request, retrying transient failures per `policy`.
+async fn retry_transient<T, F, Fut>(
+    policy: &RetryPolicy,
+    what: &str,
+    mut request: F,
+) -> Result<T, OciDistributionError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, OciDistributionError>>,
+{
+    let mut attempt = 0;
+    loop {
+        match request().await {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if !wait_to_retry(policy, attempt, what, &e).await {
+                    return Err(e);
+                }
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Continue an interrupted blob download from the bytes already staged.
+///
+/// Registries that ignore the `Range` request send the whole blob, which
+/// replaces the staged bytes.
+async fn resume_blob(
+    client: &oci_client::Client,
+    reference: &Reference,
+    descriptor: &OciDescriptor,
+    staged: &mut StagedDownload,
+    progress: Option<&mut LayerProgress<'_>>,
+) -> Result<(), OciDistributionError> {
+    let offset = staged.bytes_written();
+    let stream = match client
+        .pull_blob_stream_partial(reference, descriptor, offset, None)
+        .await?
+    {
+        BlobResponse::Partial(stream) => {
+            tracing::info!("Resuming {} at byte {}", descriptor.digest, offset);
+            stream
+        }
+        BlobResponse::Full(stream) => {
+            tracing::info!(
+                "Registry does not support ranged requests, restarting {}",
+                descriptor.digest
+            );
+            staged.restart().await?;
+            stream
+        }
+    };
+    let start = staged.bytes_written();
+    match progress {
+        Some(progress) => copy_blob_stream(stream, progress.writer(staged.file(), start)).await,
+        None => copy_blob_stream(stream, staged.file()).await,
+    }
+}
+
+async fn copy_blob_stream(
+    mut stream: SizedStream,
+    mut out: impl AsyncWrite + Unpin,
+) -> Result<(), OciDistributionError> {
+    use futures::StreamExt;
+
+    while let Some(chunk) = stream.next().await {
+        out.write_all(&chunk?).await?;
+    }
+    out.flush().await?;
+    Ok(())
+}
+
+/// Map a registry client error to `Image` when the registry refused the
+/// request (auth required or not found) and `Storage` otherwise.
+/// Reject a manifest whose digest differs from the one `reference` pins.
+///
+/// Tagged references are not checked: a tag may point anywhere.
+fn verify_pinned_digest(reference: &Reference, manifest_digest: &str) -> BoxliteResult<()> {
+    match reference.digest() {
+        Some(pinned) if pinned != manifest_digest => Err(BoxliteError::Image(format!(
+            "manifest digest mismatch for {}: pinned {pinned}, registry returned {manifest_digest}",
+            reference.whole()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn registry_error(context: &str, reference: &Reference, err: OciDistributionError) -> BoxliteError {
+    match classify_registry_error(&err) {
+        Some(RegistryRefusal::AuthRequired) => BoxliteError::Image(format!(
+            "{context}: authentication required for registry {} (check its credentials): {err}",
+            reference.registry()
+        )),
+        Some(RegistryRefusal::NotFound) => BoxliteError::Image(format!(
+            "{context}: image {} not found: {err}",
+            reference.whole()
+        )),
+        None => BoxliteError::Storage(format!("{context}: {err}")),
     }
 }
 
@@ -1145,6 +2009,74 @@ mod tests {
         String::from_utf8(vec![111, 112, 97, 113, 117, 101]).unwrap()
     }
 
+    fn fabricated_index() -> oci_client::manifest::OciImageIndex {
+        let entry = |digest: &str, arch: &str, variant: Option<&str>| {
+            let mut platform = serde_json::json!({"os": "linux", "architecture": arch});
+            if let Some(variant) = variant {
+                platform["variant"] = variant.into();
+            }
+            serde_json::json!({
+                "mediaType": "application/vnd.oci.image.manifest.v1+json",
+                "digest": digest,
+                "size": 1024,
+                "platform": platform,
+            })
+        };
+        serde_json::from_value(serde_json::json!({
+            "schemaVersion": 2,
+            "mediaType": "application/vnd.oci.image.index.v1+json",
+            "manifests": [
+                entry("sha256:amd64", "amd64", None),
+                entry("sha256:armv6", "arm", Some("v6")),
+                entry("sha256:armv7", "arm", Some("v7")),
+                entry("sha256:arm64", "arm64", Some("v8")),
+            ],
+        }))
+        .expect("fabricated index")
+    }
+
+    #[test]
+    fn test_select_platform_manifest_by_arch() {
+        let index = fabricated_index();
+
+        let arm64 = Platform::new("linux", "arm64");
+        let entry = ImageStore::select_platform_manifest(&index, &arm64).unwrap();
+        assert_eq!(entry.digest, "sha256:arm64");
+
+        let amd64 = Platform::new("linux", "amd64");
+        let entry = ImageStore::select_platform_manifest(&index, &amd64).unwrap();
+        assert_eq!(entry.digest, "sha256:amd64");
+    }
+
+    #[test]
+    fn test_select_platform_manifest_by_variant() {
+        let index = fabricated_index();
+
+        let armv7 = Platform::new("linux", "arm").with_variant("v7");
+        let entry = ImageStore::select_platform_manifest(&index, &armv7).unwrap();
+        assert_eq!(entry.digest, "sha256:armv7");
+
+        // Without a variant the first arm manifest wins.
+        let arm = Platform::new("linux", "arm");
+        let entry = ImageStore::select_platform_manifest(&index, &arm).unwrap();
+        assert_eq!(entry.digest, "sha256:armv6");
+    }
+
+    #[test]
+    fn test_select_platform_manifest_lists_available_platforms() {
+        let index = fabricated_index();
+
+        let riscv = Platform::new("linux", "riscv64");
+        let err = ImageStore::select_platform_manifest(&index, &riscv).unwrap_err();
+        assert!(matches!(err, BoxliteError::Image(_)), "{err}");
+        let msg = err.to_string();
+        assert!(msg.contains("linux/riscv64"), "{msg}");
+        assert!(
+            msg.contains("linux/amd64, linux/arm/v6, linux/arm/v7, linux/arm64/v8"),
+            "{msg}"
+        );
+    }
+
     #[test]
     fn build_platform_ref_collapses_digest_pinned_input_to_single_digest() {
         // Digest-pinned input — previous impl returned
@@ -1169,6 +2101,26 @@ mod tests {
             .expect("rebuilt platform ref must parse");
     }
 
+    #[test]
+    fn pinned_digest_mismatch_is_an_image_error() {
+        let pinned = "sha256:834dcb65465985fc2f648451d76c81d166bc7672391c9064a0a115ce6306c85f";
+        let reference: Reference = format!("docker.io/library/alpine@{pinned}")
+            .parse()
+            .expect("parse digest-pinned input");
+
+        verify_pinned_digest(&reference, pinned).expect("matching digest");
+
+        let other = "sha256:0000000000000000000000000000000000000000000000000000000000000000";
+        let err = verify_pinned_digest(&reference, other).unwrap_err();
+        assert!(matches!(err, BoxliteError::Image(_)), "{err:?}");
+        assert!(err.to_string().contains(pinned), "{err}");
+        assert!(err.to_string().contains(other), "{err}");
+
+        // Tags are mutable, so tagged pulls are never rejected.
+        let tagged: Reference = "docker.io/library/alpine:3.23".parse().unwrap();
+        verify_pinned_digest(&tagged, other).expect("tagged reference");
+    }
+
     #[test]
     fn build_platform_ref_drops_tag_when_resolving_to_platform_digest() {
         let reference: Reference = "docker.io/library/alpine:3.23"
@@ -1703,6 +2655,216 @@ mod tests {
         digest
     }
 
+    /// Write the manifest, config, and a single `layer_bytes`-sized layer for
+    /// a fake image and index it under `reference`.
+    fn write_fake_image(inner: &ImageStoreInner, reference: &str, layer_bytes: usize) {
+        use sha2::{Digest, Sha256};
+        let digest_of = |bytes: &[u8]| format!("sha256:{:x}", Sha256::digest(bytes));
+
+        let layer = vec![reference.len() as u8; layer_bytes];
+        let layer_digest = digest_of(&[reference.as_bytes(), layer.as_slice()].concat());
+        let config_digest = write_config_blob(inner, reference.as_bytes());
+        let manifest_digest = digest_of(format!("manifest {reference}").as_bytes());
+
+        for (path, bytes) in [
+            (inner.storage.layer_tarball_path(&layer_digest), layer),
+            (
+                inner.storage.manifest_path(&manifest_digest),
+                b"{}".to_vec(),
+            ),
+        ] {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, bytes).unwrap();
+        }
+
+        inner
+            .index
+            .upsert(
+                reference,
+                &CachedImage {
+                    manifest_digest,
+                    config_digest,
+                    layers: vec![layer_digest],
+                    cached_at: Utc::now().to_rfc3339(),
+                    complete: true,
+                },
+            )
+            .unwrap();
+    }
+
+    // Over the cap, the least-recently-used image goes first — but never one
+    // a running box still uses, even when it is older still.
+    #[tokio::test]
+    async fn enforce_size_cap_evicts_lru_image_but_keeps_in_use() {
+        const LAYER: usize = 64 * 1024;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = store_with_tmp(temp_dir.path()).with_max_bytes(Some(2 * LAYER as u64 + 4096));
+
+        // Oldest access first.
+        let now = Utc::now();
+        for (age_secs, reference) in [
+            (30, "docker.io/library/busybox:latest"),
+            (20, "docker.io/library/alpine:latest"),
+            (10, "docker.io/library/debian:latest"),
+        ] {
+            write_fake_image(&store.inner.read().await, reference, LAYER);
+            store.last_access.lock().unwrap().insert(
+                reference.to_string(),
+                now - chrono::Duration::seconds(age_secs),
+            );
+        }
+        assert!(store.disk_usage().await.unwrap() > 3 * LAYER as u64);
+
+        // busybox is the oldest but in use (referenced unqualified, as in
+        // BoxOptions); alpine is the least-recently-used evictable image.
+        let evicted = store
+            .enforce_size_cap(&["busybox:latest".to_string()])
+            .await
+            .unwrap();
+        assert_eq!(evicted, vec!["docker.io/library/alpine:latest".to_string()]);
+
+        let remaining: Vec<String> = store
+            .list()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(reference, _)| reference)
+            .collect();
+        assert!(remaining.contains(&"docker.io/library/busybox:latest".to_string()));
+        assert!(remaining.contains(&"docker.io/library/debian:latest".to_string()));
+        assert!(!remaining.contains(&"docker.io/library/alpine:latest".to_string()));
+        assert!(store.disk_usage().await.unwrap() <= 2 * LAYER as u64 + 4096);
+    }
+
+    #[tokio::test]
+    async fn enforce_size_cap_is_noop_without_cap() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = store_with_tmp(temp_dir.path());
+        write_fake_image(
+            &store.inner.read().await,
+            "docker.io/library/alpine:latest",
+            4096,
+        );
+
+        assert!(store.enforce_size_cap(&[]).await.unwrap().is_empty());
+        assert_eq!(store.list().await.unwrap().len(), 1);
+    }
+
+    /// Create `path` (and parents) with `bytes`.
+    fn write_blob(path: &Path, bytes: &[u8]) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, bytes).unwrap();
+    }
+
+    /// Fabricate a store with one cached image plus orphaned blobs of every
+    /// kind. Returns the orphaned paths.
+    async fn fabricate_store(store: &ImageStore) -> Vec<PathBuf> {
+        let inner = store.inner.read().await;
+        write_fake_image(&inner, "docker.io/library/alpine:latest", 4096);
+        let (_, cached) = inner.index.list_all().unwrap().pop().unwrap();
+        let storage = &inner.storage;
+
+        // Referenced blobs the fake image does not write itself.
+        std::fs::create_dir_all(storage.layer_extracted_path(&cached.layers[0])).unwrap();
+        write_blob(
+            &storage.disk_image_path(&image_digest(cached.layers.iter().map(String::as_str))),
+            b"image disk",
+        );
+        write_blob(&storage.disk_image_path("sha256:0b0c5"), b"box backing");
+        let active = storage.local_bundle_cache_dir(Path::new("/bundles/active"), "sha256:aaaa");
+        std::fs::create_dir_all(&active).unwrap();
+
+        let orphans = vec![
+            storage.manifest_path("sha256:0001"),
+            storage.config_path("sha256:0002"),
+            storage.layer_tarball_path("sha256:0003"),
+            storage.layer_extracted_path("sha256:0003"),
+            storage.disk_image_path("sha256:0004"),
+            storage.local_bundle_cache_dir(Path::new("/bundles/gone"), "sha256:bbbb"),
+        ];
+        for path in &orphans[..3] {
+            write_blob(path, b"orphan");
+        }
+        write_blob(&orphans[3].join("bin/sh"), b"orphan");
+        write_blob(&orphans[4], b"orphan");
+        write_blob(&orphans[5].join("layer"), b"orphan");
+        orphans
+    }
+
+    #[tokio::test]
+    async fn prune_removes_only_unreferenced_blobs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = store_with_tmp(temp_dir.path());
+        let orphans = fabricate_store(&store).await;
+        let storage = store.storage().await;
+        let local_bundles = [PathBuf::from("/bundles/active")];
+        let backing_files = HashSet::from([storage.disk_image_path("sha256:0b0c5")]);
+
+        let all_blobs = |storage: &dyn ImageBlobStore| {
+            let mut paths: Vec<PathBuf> = storage
+                .list_blobs()
+                .unwrap()
+                .into_iter()
+                .map(|b| b.path)
+                .collect();
+            paths.sort();
+            paths
+        };
+        let before = all_blobs(&storage);
+
+        // A dry run reports the orphans but deletes nothing.
+        let options = PruneOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let report = store
+            .prune(&options, &local_bundles, &backing_files)
+            .await
+            .unwrap();
+        let mut reported: Vec<PathBuf> = report.removed.iter().map(|b| b.path.clone()).collect();
+        reported.sort();
+        let mut expected = orphans.clone();
+        expected.sort();
+        assert_eq!(reported, expected);
+        assert_eq!(report.reclaimed_bytes, 6 * b"orphan".len() as u64);
+        assert_eq!(all_blobs(&storage), before);
+
+        let report = store
+            .prune(&PruneOptions::default(), &local_bundles, &backing_files)
+            .await
+            .unwrap();
+        assert_eq!(report.removed.len(), orphans.len());
+        for path in &orphans {
+            assert!(!path.exists(), "{} not pruned", path.display());
+        }
+        let remaining = all_blobs(&storage);
+        assert_eq!(remaining.len(), before.len() - orphans.len());
+        assert_eq!(store.list().await.unwrap().len(), 1);
+
+        // Nothing left to prune.
+        let report = store
+            .prune(&PruneOptions::default(), &local_bundles, &backing_files)
+            .await
+            .unwrap();
+        assert!(report.removed.is_empty());
+        assert_eq!(report.reclaimed_bytes, 0);
+    }
+
+    #[tokio::test]
+    async fn prune_until_keeps_recent_blobs() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = store_with_tmp(temp_dir.path());
+        let orphans = fabricate_store(&store).await;
+
+        let options = PruneOptions {
+            until: Some(Utc::now() - chrono::Duration::hours(1)),
+            ..Default::default()
+        };
+        let report = store.prune(&options, &[], &HashSet::new()).await.unwrap();
+        assert!(report.removed.is_empty(), "{:?}", report.removed);
+        assert!(orphans.iter().all(|path| path.exists()));
+    }
+
     // A config blob whose bytes hash to its digest but aren't valid JSON must
     // surface as an error, not an empty diff_ids list. The old code logged at
     // debug and returned Vec::new(), which downstream `verify_diff_ids` would
@@ -1820,4 +2982,516 @@ mod tests {
             "genuinely-empty diff_ids must be Ok, got {got:?}"
         );
     }
+
+    // ------------------------------------------------------------------------
+    // Mock registry: bearer token flow
+    // ------------------------------------------------------------------------
+
+    const MOCK_REPO: &str = "test/app";
+
+    /// Minimal registry that only serves `test/app:latest` to holders of a
+    /// token issued by its `/token` realm in exchange for basic credentials.
+    struct MockRegistry {
+        host: String,
+        /// `(path, authorization header)` of every request, in order.
+        requests: Arc<Mutex<Vec<(String, Option<String>)>>>,
+        faults: Arc<MockFaults>,
+    }
+
+    /// Transient failures for a [`MockRegistry`] to inject.
+    #[derive(Default)]
+    struct MockFaults {
+        /// Manifest requests to answer with 503 before serving the manifest.
+        manifest_unavailable: std::sync::atomic::AtomicU32,
+        /// Drop the connection halfway through the next full layer response.
+        truncate_layer: std::sync::atomic::AtomicBool,
+        /// Layer requests that carried a `Range` header.
+        ranged_layer_requests: std::sync::atomic::AtomicU32,
+    }
+
+    impl MockRegistry {
+        async fn start(username: &str, password: &str) -> Self {
+            use base64::Engine;
+            use sha2::{Digest, Sha256};
+            use std::io::Write;
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+            let digest = |bytes: &[u8]| format!("sha256:{:x}", Sha256::digest(bytes));
+
+            let tarball = create_minimal_tarball();
+            let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            gz.write_all(&tarball).unwrap();
+            let layer = gz.finish().unwrap();
+            let config = format!(
+                r#"{{"architecture":"amd64","os":"linux","rootfs":{{"type":"layers","diff_ids":["{}"]}}}}"#,
+                digest(&tarball)
+            )
+            .into_bytes();
+            let manifest = format!(
+                r#"{{"schemaVersion":2,"mediaType":"application/vnd.oci.image.manifest.v1+json","config":{{"mediaType":"application/vnd.oci.image.config.v1+json","digest":"{}","size":{}}},"layers":[{{"mediaType":"application/vnd.oci.image.layer.v1.tar+gzip","digest":"{}","size":{}}}]}}"#,
+                digest(&config),
+                config.len(),
+                digest(&layer),
+                layer.len()
+            )
+            .into_bytes();
+
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let host = listener.local_addr().unwrap().to_string();
+            let basic = format!(
+                "Basic {}",
+                base64::engine::general_purpose::STANDARD.encode(format!("{username}:{password}"))
+            );
+            let bearer = format!("Bearer {}", test_bearer_token());
+            let challenge =
+                format!(r#"Bearer realm="http://{host}/token",service="mock-registry""#);
+            let blobs: Arc<HashMap<String, Vec<u8>>> = Arc::new(HashMap::from([
+                (
+                    format!("/v2/{MOCK_REPO}/manifests/latest"),
+                    manifest.clone(),
+                ),
+                (format!("/v2/{MOCK_REPO}/blobs/{}", digest(&config)), config),
+                (format!("/v2/{MOCK_REPO}/blobs/{}", digest(&layer)), layer),
+            ]));
+            let manifest_digest = digest(&manifest);
+            let layer_path = format!("/v2/{MOCK_REPO}/blobs/{}", digest(&layer));
+            let requests = Arc::new(Mutex::new(Vec::new()));
+            let faults = Arc::new(MockFaults::default());
+
+            let log = Arc::clone(&requests);
+            let injected = Arc::clone(&faults);
+            tokio::spawn(async move {
+                use std::sync::atomic::Ordering;
+
+                while let Ok((mut stream, _)) = listener.accept().await {
+                    let mut buf = Vec::new();
+                    let mut chunk = [0u8; 4096];
+                    while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut chunk).await {
+                            Ok(0) | Err(_) => break,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&buf);
+                    let path = request
+                        .lines()
+                        .next()
+                        .and_then(|line| line.split_whitespace().nth(1))
+                        .unwrap_or("")
+                        .to_string();
+                    let authorization = request.lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("authorization")
+                            .then(|| value.trim().to_string())
+                    });
+                    let range_start = request.lines().find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        if !name.eq_ignore_ascii_case("range") {
+                            return None;
+                        }
+                        value
+                            .trim()
+                            .strip_prefix("bytes=")?
+                            .strip_suffix('-')?
+                            .parse::<usize>()
+                            .ok()
+                    });
+                    log.lock()
+                        .unwrap()
+                        .push((path.clone(), authorization.clone()));
+
+                    let authorized = authorization.as_deref() == Some(bearer.as_str());
+                    let mut truncate = false;
+                    let (status, headers, body): (&str, Vec<(&str, String)>, Vec<u8>) = if path
+                        .starts_with("/token")
+                    {
+                        if authorization.as_deref() == Some(basic.as_str()) {
+                            let token = format!(r#"{{"token":"{}"}}"#, test_bearer_token());
+                            ("200 OK", vec![], token.into_bytes())
+                        } else {
+                            ("401 Unauthorized", vec![], Vec::new())
+                        }
+                    } else if !authorized {
+                        let errors = r#"{"errors":[{"code":"UNAUTHORIZED","message":"authentication required"}]}"#;
+                        (
+                            "401 Unauthorized",
+                            vec![("WWW-Authenticate", challenge.clone())],
+                            errors.as_bytes().to_vec(),
+                        )
+                    } else if path == "/v2/" {
+                        ("200 OK", vec![], b"{}".to_vec())
+                    } else if path.contains("/manifests/")
+                        && injected
+                            .manifest_unavailable
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                            .is_ok()
+                    {
+                        ("503 Service Unavailable", vec![], Vec::new())
+                    } else if let (true, Some(start)) = (path == layer_path, range_start) {
+                        injected
+                            .ranged_layer_requests
+                            .fetch_add(1, Ordering::SeqCst);
+                        let body = &blobs[&path];
+                        let range = format!("bytes {start}-{}/{}", body.len() - 1, body.len());
+                        (
+                            "206 Partial Content",
+                            vec![("Content-Range", range)],
+                            body[start..].to_vec(),
+                        )
+                    } else if let Some(body) = blobs.get(&path) {
+                        truncate = path == layer_path
+                            && injected.truncate_layer.swap(false, Ordering::SeqCst);
+                        let headers = if path.contains("/manifests/") {
+                            vec![
+                                (
+                                    "Content-Type",
+                                    "application/vnd.oci.image.manifest.v1+json".to_string(),
+                                ),
+                                ("Docker-Content-Digest", manifest_digest.clone()),
+                            ]
+                        } else {
+                            vec![]
+                        };
+                        ("200 OK", headers, body.clone())
+                    } else {
+                        let errors = r#"{"errors":[{"code":"MANIFEST_UNKNOWN","message":"manifest unknown"}]}"#;
+                        ("404 Not Found", vec![], errors.as_bytes().to_vec())
+                    };
+
+                    let mut response = format!(
+                        "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n",
+                        body.len()
+                    );
+                    for (name, value) in headers {
+                        response.push_str(&format!("{name}: {value}\r\n"));
+                    }
+                    response.push_str("\r\n");
+                    let sent = if truncate { body.len() / 2 } else { body.len() };
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.write_all(&body[..sent]).await;
+                    let _ = stream.shutdown().await;
+                }
+            });
+
+            Self {
+                host,
+                requests,
+                faults,
+            }
+        }
+
+        fn requests(&self) -> Vec<(String, Option<String>)> {
+            self.requests.lock().unwrap().clone()
+        }
+    }
+
+    fn mock_store(dir: &Path, registry: ImageRegistry) -> ImageStore {
+        let db = Database::open(&dir.join("test.db")).unwrap();
+        ImageStore::new(dir.join("images"), db, vec![registry])
+            .unwrap()
+            .with_retry_policy(RetryPolicy {
+                max_retries: 3,
+                initial_backoff_ms: 1,
+                max_backoff_ms: 5,
+            })
+    }
+
+    #[tokio::test]
+    async fn pull_exchanges_basic_credentials_for_bearer_token() {
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+
+        let manifest = store
+            .pull(&format!("{}/{MOCK_REPO}:latest", registry.host))
+            .await
+            .expect("authenticated pull");
+        assert_eq!(manifest.layers.len(), 1);
+        assert_eq!(manifest.diff_ids.len(), 1);
+
+        let requests = registry.requests();
+        let token_requests: Vec<_> = requests
+            .iter()
+            .filter(|(path, _)| path.starts_with("/token"))
+            .collect();
+        assert!(
+            !token_requests.is_empty(),
+            "no token exchange: {requests:?}"
+        );
+        assert!(
+            token_requests[0]
+                .0
+                .contains(&format!("repository:{MOCK_REPO}:pull")),
+            "token request missing pull scope: {}",
+            token_requests[0].0
+        );
+        let bearer = format!("Bearer {}", test_bearer_token());
+        for (path, authorization) in requests.iter().filter(|(path, _)| path.contains("/blobs/")) {
+            assert_eq!(
+                authorization.as_deref(),
+                Some(bearer.as_str()),
+                "path={path}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn pull_without_credentials_reports_auth_required() {
+        let registry = MockRegistry::start("alice", &test_registry_password()).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", "wrong"),
+        );
+
+        let err = store
+            .pull(&format!("{}/{MOCK_REPO}:latest", registry.host))
+            .await
+            .unwrap_err();
+        match err {
+            BoxliteError::Image(msg) => {
+                assert!(msg.contains("authentication required"), "{msg}");
+                assert!(!msg.contains("not found"), "{msg}");
+            }
+            other => panic!("expected Image error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn pull_of_missing_repository_reports_not_found() {
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+
+        let err = store
+            .pull(&format!("{}/test/missing:latest", registry.host))
+            .await
+            .unwrap_err();
+        match err {
+            BoxliteError::Image(msg) => {
+                assert!(msg.contains("not found"), "{msg}");
+                assert!(!msg.contains("authentication required"), "{msg}");
+            }
+            other => panic!("expected Image error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn pull_with_progress_reports_monotonic_layer_bytes() {
+        use crate::runtime::types::{PullPhase, PullProgress, PullProgressCallback};
+
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+        let seen: Arc<Mutex<Vec<PullProgress>>> = Arc::default();
+        let sink = Arc::clone(&seen);
+        let callback = PullProgressCallback::new(move |p| sink.lock().unwrap().push(p));
+        let image_ref = format!("{}/{MOCK_REPO}:latest", registry.host);
+
+        let manifest = store
+            .pull_with_progress(&image_ref, None, PullPolicy::IfNotPresent, Some(&callback))
+            .await
+            .expect("pull");
+
+        let events = std::mem::take(&mut *seen.lock().unwrap());
+        assert_eq!(events[0].phase, PullPhase::Manifest);
+        for layer in &manifest.layers {
+            let bytes: Vec<u64> = events
+                .iter()
+                .filter(|p| p.layer_digest.as_deref() == Some(layer.digest.as_str()))
+                .inspect(|p| assert_eq!(p.phase, PullPhase::Downloading))
+                .map(|p| p.bytes_downloaded)
+                .collect();
+            assert!(!bytes.is_empty(), "no progress for {}", layer.digest);
+            assert!(bytes.windows(2).all(|w| w[0] < w[1]), "{bytes:?}");
+            assert_eq!(bytes.last().copied(), Some(layer.size as u64));
+        }
+
+        // A cached image has nothing to report.
+        store
+            .pull_with_progress(&image_ref, None, PullPolicy::IfNotPresent, Some(&callback))
+            .await
+            .expect("cached pull");
+        assert!(seen.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn pull_retries_unavailable_registry_and_resumes_truncated_layer() {
+        use std::sync::atomic::Ordering;
+
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        registry
+            .faults
+            .manifest_unavailable
+            .store(2, Ordering::SeqCst);
+        registry.faults.truncate_layer.store(true, Ordering::SeqCst);
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+
+        let manifest = store
+            .pull(&format!("{}/{MOCK_REPO}:latest", registry.host))
+            .await
+            .expect("pull should survive transient failures");
+        assert_eq!(manifest.layers.len(), 1);
+
+        let requests = registry.requests();
+        let manifest_requests = requests
+            .iter()
+            .filter(|(path, _)| path.contains("/manifests/"))
+            .count();
+        assert_eq!(manifest_requests, 3, "{requests:?}");
+        assert_eq!(
+            registry.faults.ranged_layer_requests.load(Ordering::SeqCst),
+            1,
+            "truncated layer should resume with a range request: {requests:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn pull_does_not_retry_not_found() {
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+
+        let err = store
+            .pull(&format!("{}/test/missing:latest", registry.host))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::Image(_)), "{err:?}");
+
+        let requests = registry.requests();
+        let manifest_requests = requests
+            .iter()
+            .filter(|(path, _)| path.contains("/manifests/"))
+            .count();
+        assert_eq!(manifest_requests, 1, "{requests:?}");
+    }
+
+    #[tokio::test]
+    async fn pull_policy_never_fails_on_uncached_image() {
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+
+        let err = store
+            .pull_with_progress(
+                &format!("{}/{MOCK_REPO}:latest", registry.host),
+                None,
+                PullPolicy::Never,
+                None,
+            )
+            .await
+            .unwrap_err();
+        match err {
+            BoxliteError::Image(msg) => assert!(msg.contains("pull policy is Never"), "{msg}"),
+            other => panic!("expected Image error, got {other:?}"),
+        }
+        assert!(registry.requests().is_empty(), "{:?}", registry.requests());
+    }
+
+    #[tokio::test]
+    async fn pull_policy_if_not_present_uses_cache_without_network() {
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+        let image_ref = format!("{}/{MOCK_REPO}:latest", registry.host);
+        let pulled = store.pull(&image_ref).await.expect("initial pull");
+        let request_count = registry.requests().len();
+
+        for policy in [PullPolicy::IfNotPresent, PullPolicy::Never] {
+            let cached = store
+                .pull_with_progress(&image_ref, None, policy, None)
+                .await
+                .expect("cached pull");
+            assert_eq!(cached.manifest_digest, pulled.manifest_digest);
+        }
+        assert_eq!(
+            registry.requests().len(),
+            request_count,
+            "cached pull hit the registry: {:?}",
+            &registry.requests()[request_count..]
+        );
+    }
+
+    #[tokio::test]
+    async fn pull_policy_always_rechecks_manifest_and_reuses_layers() {
+        let password = test_registry_password();
+        let registry = MockRegistry::start("alice", &password).await;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let store = mock_store(
+            temp_dir.path(),
+            ImageRegistry::http(registry.host.as_str()).with_basic_auth("alice", password),
+        );
+        let image_ref = format!("{}/{MOCK_REPO}:latest", registry.host);
+        let pulled = store.pull(&image_ref).await.expect("initial pull");
+        let request_count = registry.requests().len();
+
+        let repulled = store
+            .pull_with_progress(&image_ref, None, PullPolicy::Always, None)
+            .await
+            .expect("pull with Always");
+        assert_eq!(repulled.manifest_digest, pulled.manifest_digest);
+
+        let requests = registry.requests();
+        let new_requests = &requests[request_count..];
+        assert!(
+            new_requests
+                .iter()
+                .any(|(path, _)| path.contains("/manifests/")),
+            "Always should check the manifest: {new_requests:?}"
+        );
+        assert!(
+            !new_requests
+                .iter()
+                .any(|(path, _)| path.contains("/blobs/")),
+            "cached blobs should not be downloaded again: {new_requests:?}"
+        );
+    }
+
+    #[test]
+    fn transient_errors_are_connection_failures_and_5xx() {
+        let server_error = |code| OciDistributionError::ServerError {
+            code,
+            url: "http://registry.local/v2/".into(),
+            message: String::new(),
+        };
+        assert!(is_transient(&server_error(503)));
+        assert!(is_transient(&server_error(500)));
+        assert!(!is_transient(&server_error(404)));
+        assert!(!is_transient(&server_error(429)));
+        assert!(is_transient(&OciDistributionError::IoError(
+            std::io::ErrorKind::ConnectionReset.into()
+        )));
+        assert!(!is_transient(&OciDistributionError::AuthenticationFailure(
+            "denied".into()
+        )));
+    }
 }
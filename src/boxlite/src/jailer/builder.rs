@@ -4,7 +4,7 @@ use super::Jailer;
 use super::sandbox::{PlatformSandbox, Sandbox};
 use crate::runtime::advanced_options::{ResourceLimits, SecurityOptions};
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::VolumeSpec;
+use crate::runtime::options::{DiskSpec, VolumeSpec};
 use std::os::fd::RawFd;
 use std::path::PathBuf;
 
@@ -26,10 +26,12 @@ use std::path::PathBuf;
 pub struct JailerBuilder {
     security: SecurityOptions,
     volumes: Vec<VolumeSpec>,
+    disks: Vec<DiskSpec>,
     box_id: Option<String>,
     layout: Option<BoxFilesystemLayout>,
     preserved_fds: Vec<(RawFd, i32)>,
     detach: bool,
+    cpu_affinity: Option<Vec<usize>>,
 }
 
 impl Default for JailerBuilder {
@@ -44,10 +46,12 @@ impl JailerBuilder {
         Self {
             security: SecurityOptions::default(),
             volumes: Vec::new(),
+            disks: Vec::new(),
             box_id: None,
             layout: None,
             preserved_fds: Vec::new(),
             detach: false,
+            cpu_affinity: None,
         }
     }
 
@@ -84,6 +88,15 @@ impl JailerBuilder {
         self
     }
 
+    /// Set extra disk images attached to the VM.
+    ///
+    /// Each image (and its qcow2 backing chain, read-only) is granted to the
+    /// sandbox; writable disks also get write access.
+    pub fn with_disks(mut self, disks: Vec<DiskSpec>) -> Self {
+        self.disks = disks;
+        self
+    }
+
     /// Enable or disable jailer isolation.
     pub fn with_jailer_enabled(mut self, enabled: bool) -> Self {
         self.security.jailer_enabled = enabled;
@@ -282,6 +295,14 @@ impl JailerBuilder {
         self
     }
 
+    /// Pin the spawned process (and every thread it starts) to these host
+    /// cores with `sched_setaffinity` (Linux only; ignored with a warning
+    /// elsewhere). Indices are not validated here; see `BoxOptions::sanitize`.
+    pub fn with_cpu_affinity(mut self, cores: Option<Vec<usize>>) -> Self {
+        self.cpu_affinity = cores;
+        self
+    }
+
     /// Build with the platform-default sandbox.
     ///
     /// On Linux: [`BwrapSandbox`](super::sandbox::BwrapSandbox)
@@ -324,10 +345,12 @@ impl JailerBuilder {
             sandbox,
             security: self.security,
             volumes: self.volumes,
+            disks: self.disks,
             box_id,
             layout,
             preserved_fds: self.preserved_fds,
             detach: self.detach,
+            cpu_affinity: self.cpu_affinity,
         })
     }
 }
@@ -332,11 +332,13 @@ impl From<&ResourceLimits> for CgroupConfig {
         Self {
             memory_max: limits.max_memory,
             memory_high: limits.max_memory.map(|m| m * 9 / 10), // 90% of max
-            cpu_weight: None,                                   // Could add to ResourceLimits
-            cpu_max: limits.max_cpu_time.map(|t| {
-                // Convert seconds to quota/period
-                // 1 CPU = 100000/100000
-                (t * 1_000_000, 1_000_000)
+            cpu_weight: limits.cpu_weight,
+            cpu_max: limits.cpu_quota.or_else(|| {
+                limits.max_cpu_time.map(|t| {
+                    // Convert seconds to quota/period
+                    // 1 CPU = 100000/100000
+                    (t * 1_000_000, 1_000_000)
+                })
             }),
             pids_max: limits.max_processes,
         }
@@ -489,4 +491,38 @@ mod tests {
         assert_eq!(config.pids_max, Some(100));
         assert!(config.cpu_max.is_some());
     }
+
+    #[test]
+    fn test_cgroup_config_cpu_controls() {
+        let limits = ResourceLimits {
+            max_cpu_time: Some(60),
+            cpu_weight: Some(500),
+            cpu_quota: Some((50_000, 100_000)),
+            ..Default::default()
+        };
+
+        let config = CgroupConfig::from(&limits);
+
+        assert_eq!(config.cpu_weight, Some(500));
+        // An explicit quota wins over the one derived from max_cpu_time.
+        assert_eq!(config.cpu_max, Some((50_000, 100_000)));
+    }
+
+    #[test]
+    fn test_apply_limits_writes_cpu_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let limits = ResourceLimits {
+            cpu_weight: Some(50),
+            cpu_quota: Some((50_000, 100_000)),
+            ..Default::default()
+        };
+
+        apply_limits(dir.path(), &CgroupConfig::from(&limits)).unwrap();
+
+        let read = |name: &str| fs::read_to_string(dir.path().join(name)).unwrap();
+        assert_eq!(read("cpu.weight"), "50");
+        assert_eq!(read("cpu.max"), "50000 100000");
+        assert!(!dir.path().join("memory.max").exists());
+        assert!(!dir.path().join("pids.max").exists());
+    }
 }
@@ -0,0 +1,82 @@
+//! CPU affinity for the jailed process (Linux only).
+//!
+//! The CPU set is built in the parent, where allocation is allowed, and
+//! applied from the `pre_exec` hook with `apply_affinity_raw()`. Threads the
+//! shim creates later (libkrun vCPU threads included) inherit the mask.
+
+/// Build a `cpu_set_t` containing `cores`.
+///
+/// Indices must already be validated against the host CPU count; ones past
+/// `CPU_SETSIZE` are ignored.
+pub fn cpu_set(cores: &[usize]) -> libc::cpu_set_t {
+    // SAFETY: cpu_set_t is a plain bitmask; all-zeroes is the empty set.
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &core in cores {
+        if core < libc::CPU_SETSIZE as usize {
+            // SAFETY: `core` is below CPU_SETSIZE.
+            unsafe { libc::CPU_SET(core, &mut set) };
+        }
+    }
+    set
+}
+
+/// Restrict the calling thread to `set` - async-signal-safe version for pre_exec.
+///
+/// Only calls `sched_setaffinity`. Do NOT add logging, allocation or locking.
+///
+/// # Returns
+/// * `Ok(())` - Affinity applied
+/// * `Err(errno)` - The kernel rejected the mask (returns raw errno)
+pub fn apply_affinity_raw(set: &libc::cpu_set_t) -> Result<(), i32> {
+    // SAFETY: `set` is a valid cpu_set_t of the size passed.
+    let result = unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), set) };
+    if result != 0 {
+        return Err(super::get_errno());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_set_contains_requested_cores() {
+        let set = cpu_set(&[0, 2]);
+        unsafe {
+            assert!(libc::CPU_ISSET(0, &set));
+            assert!(!libc::CPU_ISSET(1, &set));
+            assert!(libc::CPU_ISSET(2, &set));
+            assert_eq!(libc::CPU_COUNT(&set), 2);
+        }
+    }
+
+    #[test]
+    fn test_apply_affinity_in_child_thread() {
+        fn current() -> libc::cpu_set_t {
+            let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+            let result = unsafe {
+                libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set)
+            };
+            assert_eq!(result, 0);
+            set
+        }
+
+        // Pin a throwaway thread so the test harness keeps its own mask.
+        std::thread::spawn(|| {
+            let allowed = current();
+            let core = (0..libc::CPU_SETSIZE as usize)
+                .find(|&c| unsafe { libc::CPU_ISSET(c, &allowed) })
+                .unwrap();
+            apply_affinity_raw(&cpu_set(&[core])).expect("pin to an allowed core");
+
+            let pinned = current();
+            unsafe {
+                assert_eq!(libc::CPU_COUNT(&pinned), 1);
+                assert!(libc::CPU_ISSET(core, &pinned));
+            }
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -82,7 +82,9 @@ pub mod seccomp;
 // ============================================================================
 
 // Core types
-pub use crate::runtime::advanced_options::{ResourceLimits, SecurityOptions};
+pub use crate::runtime::advanced_options::{
+    ResourceLimits, SeccompDefaultAction, SeccompProfile, SecurityOptions,
+};
 pub use builder::JailerBuilder;
 pub use error::{ConfigError, IsolationError, JailerError, SystemError};
 pub use sandbox::{
@@ -112,7 +114,7 @@ pub(crate) fn reap_box(_box_id: &crate::runtime::id::BoxID) -> bool {
 }
 
 // Volume specification (convenience re-export)
-pub use crate::runtime::options::VolumeSpec;
+pub use crate::runtime::options::{DiskSpec, VolumeSpec};
 
 // Linux-specific exports
 #[cfg(target_os = "linux")]
@@ -338,6 +340,38 @@ fn build_path_access(layout: &BoxFilesystemLayout, volumes: &[VolumeSpec]) -> Ve
     paths
 }
 
+/// Grant the VMM access to user-declared disk images.
+///
+/// Qcow2 images may chain to backing files elsewhere, so the whole chain is
+/// granted read-only, matching the box's own disks in [`build_path_access`].
+fn disk_path_access(disks: &[DiskSpec]) -> Vec<PathAccess> {
+    let mut paths = Vec::new();
+    for disk in disks {
+        let path = PathBuf::from(&disk.host_path);
+        if !path.exists() {
+            continue;
+        }
+        if disk.passthrough {
+            paths.push(PathAccess {
+                path,
+                writable: !disk.read_only,
+            });
+            continue;
+        }
+        for backing_path in read_backing_chain(&path) {
+            paths.push(PathAccess {
+                path: backing_path,
+                writable: false,
+            });
+        }
+        paths.push(PathAccess {
+            path,
+            writable: !disk.read_only,
+        });
+    }
+    paths
+}
+
 fn system_ca_paths() -> [PathBuf; 7] {
     [
         PathBuf::from("/etc/ssl/certs"),
@@ -379,6 +413,8 @@ pub struct Jailer<S: Sandbox> {
     pub(crate) security: SecurityOptions,
     /// Volume mounts (for sandbox path restrictions).
     pub(crate) volumes: Vec<VolumeSpec>,
+    /// Extra disk images attached to the VM (for sandbox path restrictions).
+    pub(crate) disks: Vec<DiskSpec>,
     /// Unique box identifier.
     pub(crate) box_id: String,
     /// Box filesystem layout (provides typed path accessors).
@@ -390,6 +426,8 @@ pub struct Jailer<S: Sandbox> {
     /// — `true` adds `setsid()` to the pre_exec chain, `false` sets the
     /// child's process group to itself at `Command` build time.
     pub(crate) detach: bool,
+    /// Host cores the shim is pinned to; `None` leaves the inherited mask.
+    pub(crate) cpu_affinity: Option<Vec<usize>>,
 }
 
 impl<S: Sandbox> Jail for Jailer<S> {
@@ -489,6 +527,15 @@ impl<S: Sandbox> Jail for Jailer<S> {
             self.preserved_fds.clone(),
             self.detach,
         );
+        if let Some(cores) = &self.cpu_affinity {
+            #[cfg(target_os = "linux")]
+            pre_exec::add_cpu_affinity_hook(&mut cmd, cores);
+            #[cfg(not(target_os = "linux"))]
+            tracing::warn!(
+                ?cores,
+                "cpu_affinity is Linux-only and ignored on this platform"
+            );
+        }
         cmd
     }
 }
@@ -533,7 +580,8 @@ impl<S: Sandbox> Jailer<S> {
     ///
     /// Delegates to [`build_path_access`] for granular filesystem rules.
     fn context(&self) -> SandboxContext<'_> {
-        let paths = build_path_access(&self.layout, &self.volumes);
+        let mut paths = build_path_access(&self.layout, &self.volumes);
+        paths.extend(disk_path_access(&self.disks));
         tracing::debug!(
             box_id = %self.box_id,
             path_count = paths.len(),
@@ -863,6 +911,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_disk_path_access_follows_read_only() {
+        let dir = tempdir().unwrap();
+        let data = dir.path().join("data.img");
+        let seed = dir.path().join("seed.img");
+        std::fs::write(&data, b"").unwrap();
+        std::fs::write(&seed, b"").unwrap();
+
+        let disks = vec![
+            DiskSpec {
+                id: "data".to_string(),
+                host_path: data.to_string_lossy().to_string(),
+                read_only: false,
+                passthrough: false,
+            },
+            DiskSpec {
+                id: "seed".to_string(),
+                host_path: seed.to_string_lossy().to_string(),
+                read_only: true,
+                passthrough: false,
+            },
+            DiskSpec {
+                id: "gone".to_string(),
+                host_path: "/does/not/exist.img".to_string(),
+                read_only: false,
+                passthrough: false,
+            },
+        ];
+
+        let paths = disk_path_access(&disks);
+
+        assert_eq!(paths.len(), 2, "Nonexistent disk should be skipped");
+        assert!(paths.iter().any(|p| p.path == data && p.writable));
+        assert!(paths.iter().any(|p| p.path == seed && !p.writable));
+    }
+
     #[test]
     fn test_build_path_access_single_file_grants_no_host_dir() {
         let dir = tempdir().unwrap();
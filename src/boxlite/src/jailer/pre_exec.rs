@@ -127,6 +127,24 @@ pub fn add_pre_exec_hook(
     }
 }
 
+/// Pin the child to `cores` before it execs (Linux only, async-signal-safe).
+///
+/// The CPU mask is built here in the parent; the hook only calls
+/// `sched_setaffinity`. Every thread the shim starts afterwards, including
+/// libkrun's vCPU threads, inherits the mask.
+#[cfg(target_os = "linux")]
+pub fn add_cpu_affinity_hook(cmd: &mut Command, cores: &[usize]) {
+    use std::os::unix::process::CommandExt;
+
+    let set = common::affinity::cpu_set(cores);
+    // SAFETY: The hook only calls sched_setaffinity.
+    unsafe {
+        cmd.pre_exec(move || {
+            common::affinity::apply_affinity_raw(&set).map_err(std::io::Error::from_raw_os_error)
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -155,4 +173,11 @@ mod tests {
         // Simulate preserving fd 5 → target fd 3
         add_pre_exec_hook(&mut cmd, limits, None, vec![(5, 3)], false);
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_add_cpu_affinity_hook_compiles() {
+        let mut cmd = Command::new("/bin/echo");
+        add_cpu_affinity_hook(&mut cmd, &[0]);
+    }
 }
@@ -7,6 +7,9 @@ const SHIM_ENV_ALLOWLIST: &[&str] = &[
     crate::runtime::constants::envs::BOXLITE_KRUNFW_EXTERNAL_KERNEL,
     crate::runtime::constants::envs::BOXLITE_KRUNFW_KERNEL_PATH,
     crate::runtime::constants::envs::BOXLITE_KRUNFW_KERNEL_FORMAT,
+    crate::runtime::constants::envs::BOXLITE_FIRECRACKER_BIN,
+    crate::runtime::constants::envs::BOXLITE_FIRECRACKER_KERNEL,
+    crate::runtime::constants::envs::BOXLITE_FIRECRACKER_TAP,
 ];
 
 /// Returns allowlisted host environment variables for a shim subprocess.
@@ -32,4 +35,11 @@ mod tests {
         assert!(SHIM_ENV_ALLOWLIST.contains(&"BOXLITE_KRUNFW_KERNEL_PATH"));
         assert!(SHIM_ENV_ALLOWLIST.contains(&"BOXLITE_KRUNFW_KERNEL_FORMAT"));
     }
+
+    #[test]
+    fn allowlist_contains_firecracker_controls() {
+        assert!(SHIM_ENV_ALLOWLIST.contains(&"BOXLITE_FIRECRACKER_BIN"));
+        assert!(SHIM_ENV_ALLOWLIST.contains(&"BOXLITE_FIRECRACKER_KERNEL"));
+        assert!(SHIM_ENV_ALLOWLIST.contains(&"BOXLITE_FIRECRACKER_TAP"));
+    }
 }
@@ -111,11 +111,23 @@ impl Sandbox for BwrapSandbox {
         // =====================================================================
         // Bind all pre-computed paths (system dirs + user volumes)
         // =====================================================================
+        // Plain binds are nodev, so passthrough disks need a device bind.
+        // Read-only ones stay read-only because libkrun opens them so.
         for pa in ctx.writable_paths() {
+            if is_block_device(&pa.path) {
+                bwrap_cmd.dev_bind(&pa.path, &pa.path);
+                tracing::debug!(path = %pa.path.display(), "bwrap: dev-bind (rw)");
+                continue;
+            }
             bwrap_cmd.bind(&pa.path, &pa.path);
             tracing::debug!(path = %pa.path.display(), "bwrap: bind (rw)");
         }
         for pa in ctx.readonly_paths() {
+            if is_block_device(&pa.path) {
+                bwrap_cmd.dev_bind(&pa.path, &pa.path);
+                tracing::debug!(path = %pa.path.display(), "bwrap: dev-bind (ro)");
+                continue;
+            }
             bwrap_cmd.ro_bind(&pa.path, &pa.path);
             tracing::debug!(path = %pa.path.display(), "bwrap: ro-bind");
         }
@@ -163,6 +175,11 @@ impl Sandbox for BwrapSandbox {
     }
 }
 
+fn is_block_device(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::FileTypeExt;
+    std::fs::metadata(path).is_ok_and(|m| m.file_type().is_block_device())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
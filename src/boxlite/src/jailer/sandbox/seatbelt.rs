@@ -603,6 +603,38 @@ mod tests {
         assert!(policy.contains("boxes/test-box"));
     }
 
+    #[test]
+    fn test_dynamic_write_paths_exclude_read_only_volume() {
+        use crate::jailer::{VolumeSpec, build_path_access};
+        use crate::runtime::layout::{BoxFilesystemLayout, FsLayoutConfig};
+
+        let home = tempfile::tempdir().unwrap();
+        let layout = BoxFilesystemLayout::new(
+            home.path().join("box"),
+            FsLayoutConfig::without_bind_mount(),
+            false,
+        );
+        let vol_dir = tempfile::tempdir().unwrap();
+        let vol_ro = vol_dir.path().join("input");
+        std::fs::create_dir_all(&vol_ro).unwrap();
+        let volumes = vec![VolumeSpec {
+            host_path: vol_ro.to_string_lossy().to_string(),
+            guest_path: "/mnt/input".to_string(),
+            read_only: true,
+        }];
+
+        // The volume is readable, but no write rule covers it.
+        let paths = build_path_access(&layout, &volumes);
+        let read = build_dynamic_read_paths(Path::new("/usr/local/bin/boxlite-shim"), &paths);
+        let write = build_dynamic_write_paths(&paths);
+        let vol_ro = canonicalize_or_original(&vol_ro);
+        assert!(read.contains(vol_ro.to_str().unwrap()));
+        assert!(
+            !write.contains(vol_ro.to_str().unwrap()),
+            "read-only volume must not be writable:\n{write}"
+        );
+    }
+
     #[test]
     fn test_policy_no_blanket_system_paths() {
         let paths = vec![PathAccess {
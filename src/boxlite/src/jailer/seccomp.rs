@@ -32,6 +32,7 @@
 //! `resources/seccomp/`. Future work: profile libkrun's actual syscall args
 //! and restore per-argument restrictions where possible.
 
+use crate::runtime::advanced_options::{SeccompDefaultAction, SeccompProfile};
 use boxlite_shared::errors::BoxliteError;
 use std::collections::HashMap;
 use std::io::Read;
@@ -286,6 +287,67 @@ pub fn apply_vmm_filter(box_id: &str) -> crate::BoxliteResult<()> {
     Ok(())
 }
 
+// ============================================================================
+// Custom profiles (SecurityOptions::seccomp_profile)
+// ============================================================================
+
+/// Filter name used when compiling a [`SeccompProfile`].
+const PROFILE_FILTER: &str = "profile";
+
+impl SeccompProfile {
+    /// Build the BPF program for this profile on the host architecture.
+    ///
+    /// Fails if the allowlist names a syscall unknown on this architecture.
+    pub fn build(&self) -> crate::BoxliteResult<BpfProgram> {
+        let arch: seccompiler::TargetArch = std::env::consts::ARCH
+            .try_into()
+            .map_err(|e| profile_error(format!("unsupported architecture: {e:?}")))?;
+        let default_action = match self.default_action {
+            SeccompDefaultAction::Errno => serde_json::json!({ "errno": libc::EPERM }),
+            SeccompDefaultAction::Kill => serde_json::json!("kill_process"),
+        };
+        let filter: Vec<_> = self
+            .allowed_syscalls
+            .iter()
+            .map(|name| serde_json::json!({ "syscall": name }))
+            .collect();
+        let json = serde_json::json!({
+            PROFILE_FILTER: {
+                "default_action": default_action,
+                "filter_action": "allow",
+                "filter": filter,
+            }
+        });
+
+        let mut compiled = seccompiler::compile_from_json(json.to_string().as_bytes(), arch)
+            .map_err(|e| profile_error(e.to_string()))?;
+        let program = compiled
+            .remove(PROFILE_FILTER)
+            .ok_or_else(|| profile_error("compiler produced no filter".to_string()))?;
+
+        // sock_filter is #[repr(C)] {code: u16, jt: u8, jf: u8, k: u32} — one
+        // BpfInstruction (same conversion as build.rs).
+        Ok(program
+            .iter()
+            // SAFETY: sock_filter and BpfInstruction are both 8 bytes.
+            .map(|instr| unsafe { std::mem::transmute_copy(instr) })
+            .collect())
+    }
+
+    /// Build this profile and install it on every thread (TSYNC).
+    pub fn install(&self) -> crate::BoxliteResult<()> {
+        let program = self.build()?;
+        apply_filter_all_threads(&program).map_err(|e| profile_error(e.to_string()))
+    }
+}
+
+fn profile_error(detail: String) -> BoxliteError {
+    use crate::jailer::error::{IsolationError, JailerError};
+    BoxliteError::from(JailerError::Isolation(IsolationError::Seccomp(format!(
+        "custom profile: {detail}"
+    ))))
+}
+
 /// Load pre-compiled BPF filters from embedded binary.
 #[cfg(target_os = "linux")]
 fn load_filters(box_id: &str) -> crate::BoxliteResult<BpfThreadMap> {
@@ -442,6 +504,64 @@ mod tests {
         .unwrap();
     }
 
+    /// Fork a child that installs `profile` (built in the parent, since the
+    /// child may only do async-signal-safe work), calls `getppid`, and exits 0
+    /// if it failed with EPERM. Returns the raw wait status.
+    fn run_profiled_child(profile: &SeccompProfile) -> libc::c_int {
+        let program = profile.build().unwrap();
+
+        let pid = unsafe { libc::fork() };
+        assert!(pid >= 0, "fork failed");
+        if pid == 0 {
+            if apply_filter(&program).is_err() {
+                unsafe { libc::_exit(2) };
+            }
+            let rc = unsafe { libc::syscall(libc::SYS_getppid) };
+            let errno = unsafe { *libc::__errno_location() };
+            let code = if rc == -1 && errno == libc::EPERM {
+                0
+            } else {
+                1
+            };
+            unsafe { libc::_exit(code) };
+        }
+
+        let mut status = 0;
+        assert_eq!(unsafe { libc::waitpid(pid, &mut status, 0) }, pid);
+        status
+    }
+
+    #[test]
+    fn test_profile_denied_syscall_returns_eperm() {
+        let status = run_profiled_child(&SeccompProfile::allow(["exit", "exit_group"]));
+        assert!(libc::WIFEXITED(status), "child did not exit: {status:#x}");
+        assert_eq!(
+            libc::WEXITSTATUS(status),
+            0,
+            "getppid was not denied with EPERM"
+        );
+    }
+
+    #[test]
+    fn test_profile_kill_action_kills_on_denied_syscall() {
+        let profile = SeccompProfile::allow(["exit", "exit_group"])
+            .with_default_action(SeccompDefaultAction::Kill);
+        let status = run_profiled_child(&profile);
+        assert!(
+            libc::WIFSIGNALED(status),
+            "child was not killed: {status:#x}"
+        );
+        assert_eq!(libc::WTERMSIG(status), libc::SIGSYS);
+    }
+
+    #[test]
+    fn test_profile_rejects_unknown_syscall() {
+        let err = SeccompProfile::allow(["read", "not_a_syscall"])
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("custom profile"), "{err}");
+    }
+
     #[cfg(target_os = "linux")]
     #[test]
     fn test_deserialize_embedded_filter() {
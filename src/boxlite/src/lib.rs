@@ -37,28 +37,38 @@ pub use portal::GuestSession;
 pub use runtime::{AuthHandle, BoxliteRuntime, ImageHandle, Principal};
 
 pub use boxlite_shared::errors::{BoxliteError, BoxliteResult};
-pub use disk::DiskInfo;
+pub use disk::{DiskInfo, Qcow2Helper, Qcow2Snapshot};
 pub use event_listener::{AuditEvent, AuditEventKind, AuditEventListener, EventListener};
+pub use images::{HashingWriter, ImageBlobStore, ImageStorage, StagedDownload, StoredBlob};
 pub use litebox::SnapshotHandle;
 pub use litebox::archive::ArchiveManifest;
 pub use litebox::snapshot_mgr::SnapshotInfo;
 pub use litebox::{
-    BoxCommand, CopyOptions, ExecResult, ExecStderr, ExecStdin, ExecStdout, Execution, ExecutionId,
-    HealthState, HealthStatus,
+    BoxCommand, BoxTraceLayer, CopyOptions, ExecOutput, ExecResult, ExecStderr, ExecStdin,
+    ExecStdout, Execution, ExecutionId, GuestLogStream, HealthProbe, HealthState, HealthStatus,
+    LogLine, OutputMode, TerminalSizeFollower, TraceEvent, TraceFilter, TraceStream,
 };
-pub use metrics::{BoxMetrics, RuntimeMetrics};
+pub use metrics::{BoxMetrics, RuntimeMetrics, RuntimeStats};
 pub use runtime::advanced_options::{
-    AdvancedBoxOptions, HealthCheckOptions, ResourceLimits, SecurityOptions,
+    AdvancedBoxOptions, HealthCheckOptions, ResourceLimits, SeccompDefaultAction, SeccompProfile,
+    SecurityOptions,
 };
 pub use runtime::options::{
-    BoxArchive, BoxOptions, BoxliteOptions, CloneOptions, ExportOptions, ImageRegistry,
-    ImageRegistryAuth, NetworkSpec, RegistryTransport, RootfsSpec, Secret, SnapshotOptions,
+    BoxArchive, BoxOptions, BoxliteOptions, CloneOptions, CommitOptions, DiskSpec, DnsConfig,
+    ExecResourceLimits, ExportOptions, IdMapping, ImageRegistry, ImageRegistryAuth, ImportSource,
+    KernelSpec, NetworkSpec, Platform, PruneOptions, PullPolicy, RegistryTransport, RetryPolicy,
+    RootfsSpec, Secret, SnapshotOptions, TmpfsMount, Ulimit, UserNsMapping,
 };
 /// Boxlite library version (from CARGO_PKG_VERSION at compile time).
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub use runtime::id::{BaseDiskID, BaseDiskIDMint, BoxID, BoxIDMint};
+pub use runtime::names::{BoxNameGenerator, RandomNameGenerator};
 pub use runtime::types::ContainerID;
-pub use runtime::types::{BoxInfo, BoxState, BoxStateInfo, BoxStatus};
+pub use runtime::types::{
+    BlobKind, BoxEvent, BoxFilter, BoxInfo, BoxState, BoxStateInfo, BoxStatus, ExecutionInfo,
+    PortMapping, ProcessInfo, PrunedBlob, PrunedReport, PullPhase, PullProgress,
+    PullProgressCallback, ValidationReport,
+};
 
 #[cfg(feature = "rest")]
 pub use rest::credential::{AccessToken, ApiKeyCredential, Credential};
@@ -14,18 +14,20 @@ use tokio::sync::OnceCell;
 use tokio::task::JoinHandle;
 use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
+use tracing::Instrument;
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 use super::config::BoxConfig;
 use super::exec::{BoxCommand, ExecStderr, ExecStdin, ExecStdout, Execution};
+use super::guest_log::GuestLogStream;
 use super::state::BoxState;
 use crate::disk::Disk;
 use crate::event_listener::EventListener;
 #[cfg(target_os = "linux")]
 use crate::fs::BindMountHandle;
-use crate::litebox::BoxTunnel;
 use crate::litebox::copy::CopyOptions;
+use crate::litebox::{BoxConnection, BoxTunnel};
 use crate::lock::LockGuard;
 use crate::metrics::{BoxMetrics, BoxMetricsStorage};
 use crate::net::NetworkBackend;
@@ -33,7 +35,7 @@ use crate::portal::GuestSession;
 use crate::portal::interfaces::GuestInterface;
 use crate::runtime::layout::BoxFilesystemLayout;
 use crate::runtime::rt_impl::SharedRuntimeImpl;
-use crate::runtime::types::BoxStatus;
+use crate::runtime::types::{BoxStatus, ExecutionInfo, PortMapping, ProcessInfo};
 use crate::vmm::controller::VmmHandler;
 use crate::{BoxID, BoxInfo, HealthCheckOptions, HealthState};
 
@@ -64,6 +66,9 @@ pub(crate) struct LiveState {
     #[allow(dead_code)]
     network: Option<Arc<dyn NetworkBackend>>,
 
+    /// Port mappings this VM was spawned with; `None` after a reattach.
+    published_ports: Option<Vec<PortMapping>>,
+
     // Metrics
     metrics: BoxMetricsStorage,
 
@@ -84,6 +89,7 @@ impl LiveState {
         handler: Box<dyn VmmHandler>,
         guest_session: GuestSession,
         network: Option<Box<dyn NetworkBackend>>,
+        published_ports: Option<Vec<PortMapping>>,
         metrics: BoxMetricsStorage,
         container_rootfs_disk: Disk,
         guest_rootfs_disk: Option<Disk>,
@@ -93,6 +99,7 @@ impl LiveState {
             handler: std::sync::Mutex::new(handler),
             guest_session,
             network: network.map(Arc::from),
+            published_ports,
             metrics,
             _container_rootfs_disk: container_rootfs_disk,
             guest_rootfs_disk,
@@ -113,6 +120,8 @@ pub(crate) struct BoxImpl {
     // --- Always available ---
     pub(crate) config: BoxConfig,
     pub(crate) state: Arc<RwLock<BoxState>>,
+    /// Current name; starts as `config.name` and changes on rename.
+    name: RwLock<Option<String>>,
     pub(crate) runtime: SharedRuntimeImpl,
     pub(crate) layout: BoxFilesystemLayout,
     /// Cancellation token for this box (child of runtime's token).
@@ -158,6 +167,7 @@ impl BoxImpl {
                 "box_layout is structurally infallible — only warns on isolate_mounts mismatch",
             );
         Self {
+            name: RwLock::new(config.name.clone()),
             config,
             state: Arc::new(RwLock::new(state)),
             runtime,
@@ -178,13 +188,31 @@ impl BoxImpl {
         &self.config.id
     }
 
+    /// Span tagging everything emitted during a box operation with `box_id`,
+    /// so `LiteBox::trace_stream()` can attribute untagged events.
+    fn span(&self) -> tracing::Span {
+        tracing::info_span!("box", box_id = %self.config.id)
+    }
+
     pub(crate) fn container_id(&self) -> &str {
         self.config.container.id.as_str()
     }
 
+    /// Current name, reflecting renames since this handle was created.
+    pub(crate) fn name(&self) -> Option<String> {
+        self.name.read().clone()
+    }
+
+    /// Replace the name, returning the previous one.
+    pub(crate) fn set_name(&self, name: Option<String>) -> Option<String> {
+        std::mem::replace(&mut *self.name.write(), name)
+    }
+
     pub(crate) fn info(&self) -> BoxInfo {
         let state = self.state.read();
-        BoxInfo::new(&self.config, &state)
+        let mut info = BoxInfo::new(&self.config, &state);
+        info.name = self.name();
+        info
     }
 
     // ========================================================================
@@ -248,6 +276,9 @@ impl BoxImpl {
             ));
         }
 
+        // Env files are read host-side; a bad file fails before the VM is touched.
+        let command = command.resolve_env_files()?;
+
         let live = self.live_state().await?;
 
         // Inject container ID into environment if not already set
@@ -271,6 +302,9 @@ impl BoxImpl {
             _ => command,
         };
 
+        let command = command.resolve_resource_profile(&self.config.options.exec_profiles)?;
+        command.validate_env()?;
+
         for listener in &self.event_listeners {
             listener.on_exec_started(&self.config.id, &command.command, &command.args);
         }
@@ -300,12 +334,79 @@ impl BoxImpl {
             components.execution_id,
             Box::new(exec_interface),
             components.result_rx,
-            Some(ExecStdin::new(components.stdin_tx)),
-            Some(ExecStdout::new(components.stdout_rx)),
-            Some(ExecStderr::new(components.stderr_rx)),
+            components.stdin_tx.map(ExecStdin::new),
+            Some(ExecStdout::new(components.stdout_rx).with_budget(components.stdout_budget)),
+            Some(ExecStderr::new(components.stderr_rx).with_budget(components.stderr_budget)),
+        ))
+    }
+
+    /// Re-attach to an execution started on this box.
+    ///
+    /// The returned handle has no stdin: only output and the exit status are
+    /// recovered.
+    pub(crate) async fn attach(&self, execution_id: &str) -> BoxliteResult<Execution> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+
+        let live = self.live_state().await?;
+        let mut exec_interface = live.guest_session.execution().await?;
+        let components = exec_interface
+            .attach(execution_id, self.shutdown_token.clone())
+            .await?;
+
+        Ok(Execution::new(
+            components.execution_id,
+            Box::new(exec_interface),
+            components.result_rx,
+            components.stdin_tx.map(ExecStdin::new),
+            Some(ExecStdout::new(components.stdout_rx).with_budget(components.stdout_budget)),
+            Some(ExecStderr::new(components.stderr_rx).with_budget(components.stderr_budget)),
         ))
     }
 
+    /// Resize the box's usable memory to `mib`, at most its boot memory.
+    ///
+    /// Returns the guest's online memory after the resize, in MiB.
+    pub(crate) async fn set_memory_mib(&self, mib: u32) -> BoxliteResult<u32> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+
+        let max_mib = self
+            .config
+            .options
+            .memory_mib
+            .unwrap_or(crate::runtime::constants::vm_defaults::DEFAULT_MEMORY_MIB);
+        validate_memory_target(mib, max_mib)?;
+        if !self.runtime.engine_features()?.memory_balloon {
+            return Err(BoxliteError::Unsupported(
+                "the VM engine does not support memory ballooning".into(),
+            ));
+        }
+
+        let live = self.live_state().await?;
+        let mut guest = live.guest_session.guest().await?;
+        let actual_mib = guest.resize_memory(mib).await?;
+        tracing::info!(
+            box_id = %self.config.id,
+            target_mib = mib,
+            actual_mib,
+            "Resized box memory"
+        );
+        Ok(actual_mib)
+    }
+
+    /// Tail of the console file the shim keeps bounded. Readable whether
+    /// or not the VM is running, so boot failures can be inspected.
+    pub(crate) fn console_logs(&self, lines: usize) -> BoxliteResult<Vec<String>> {
+        crate::vmm::console::tail_lines(&self.layout.console_output_path(), lines)
+    }
+
     pub(crate) async fn metrics(&self) -> BoxliteResult<BoxMetrics> {
         // Check if box is stopped before proceeding (via stop() or runtime shutdown)
         if self.shutdown_token.is_cancelled() {
@@ -315,21 +416,54 @@ impl BoxImpl {
         }
 
         let live = self.live_state().await?;
-        let handler = live
-            .handler
-            .lock()
-            .map_err(|e| BoxliteError::Internal(format!("handler lock poisoned: {}", e)))?;
-        let raw = handler.metrics()?;
+        let raw = {
+            let handler = live
+                .handler
+                .lock()
+                .map_err(|e| BoxliteError::Internal(format!("handler lock poisoned: {}", e)))?;
+            handler.metrics()?
+        };
+
+        // Network counters are best-effort too: not every backend reports them.
+        let network = match &live.network {
+            Some(network) => match network.stats().await {
+                Ok(stats) => Some(stats),
+                Err(e) => {
+                    tracing::debug!(box_id = %self.config.id, error = %e, "Network stats unavailable");
+                    None
+                }
+            },
+            None => None,
+        };
 
-        Ok(BoxMetrics::from_storage(
+        let mut metrics = BoxMetrics::from_storage(
             &live.metrics,
             raw.cpu_percent,
             raw.memory_bytes,
-            None,
-            None,
-            None,
-            None,
-        ))
+            network.as_ref().map(|s| s.bytes_sent()),
+            network.as_ref().map(|s| s.bytes_received()),
+            network.as_ref().map(|s| s.tcp_established()),
+            network.as_ref().map(|s| s.tcp_failed_connections()),
+        );
+        // Disk usage is best-effort: an older guest agent lacks the RPC.
+        match self.guest_disk_stats(live).await {
+            Ok(disk) => {
+                metrics.disk_bytes_used = Some(disk.bytes_used);
+                metrics.disk_bytes_total = Some(disk.bytes_total);
+            }
+            Err(e) => {
+                tracing::debug!(box_id = %self.config.id, error = %e, "Disk stats unavailable")
+            }
+        }
+        Ok(metrics)
+    }
+
+    async fn guest_disk_stats(
+        &self,
+        live: &LiveState,
+    ) -> BoxliteResult<boxlite_shared::DiskStatsResponse> {
+        let mut guest = live.guest_session.guest().await?;
+        guest.disk_stats(self.container_id()).await
     }
 
     pub(crate) async fn stop(&self) -> BoxliteResult<()> {
@@ -370,22 +504,42 @@ impl BoxImpl {
             // Recovered boxes lazy-attach here via vmm_attach (now
             // ProcessIdentity-gated). Live boxes hit the cached LiveState.
             // Either way the teardown is identical:
+            // Executions get SIGTERM and `stop_grace` to exit before SIGKILL;
+            // the extra time covers container shutdown and the fs sync.
+            let stop_grace = self.config.options.stop_grace();
             let guest_shutdown = async {
                 if let Ok(mut guest) = live.guest_session.guest().await {
-                    let _ = guest.shutdown().await;
+                    let _ = guest.shutdown(Some(stop_grace)).await;
                 }
             };
-            if tokio::time::timeout(Duration::from_secs(10), guest_shutdown)
+            let shutdown_timeout = stop_grace + Duration::from_secs(10);
+            if tokio::time::timeout(shutdown_timeout, guest_shutdown)
                 .await
                 .is_err()
             {
-                tracing::warn!(box_id = %self.config.id, "Guest shutdown timed out after 10s");
+                tracing::warn!(
+                    box_id = %self.config.id,
+                    timeout_secs = shutdown_timeout.as_secs(),
+                    "Guest shutdown timed out"
+                );
             }
 
             // Stop handler
             if let Ok(mut handler) = live.handler.lock() {
                 handler.stop()?;
             }
+
+            // The VM is gone; tear the network down before LiveState drops.
+            if let Some(network) = &live.network
+                && let Err(e) = network.shutdown()
+            {
+                tracing::warn!(
+                    box_id = %self.config.id,
+                    backend = network.name(),
+                    error = %e,
+                    "Network backend shutdown failed"
+                );
+            }
         }
         // If live_state() failed (vmm_attach said Absent — shim is gone),
         // or status wasn't Running, fall through to cleanup.
@@ -440,7 +594,7 @@ impl BoxImpl {
 
         // Invalidate cache so new handles get fresh BoxImpl
         self.runtime
-            .invalidate_box_impl(self.id(), self.config.name.as_deref());
+            .invalidate_box_impl(self.id(), self.name().as_deref());
 
         for listener in &self.event_listeners {
             listener.on_box_stopped(&self.config.id, None);
@@ -621,6 +775,96 @@ impl BoxImpl {
         Ok(())
     }
 
+    /// Stream the whole container rootfs out of the guest into `tar_dest`.
+    ///
+    /// Starts the box if needed.
+    pub(super) async fn download_rootfs(&self, tar_dest: &std::path::Path) -> BoxliteResult<()> {
+        let live = self.live_state().await?;
+        let mut files_iface = live.guest_session.files().await?;
+        files_iface
+            .download_tar("/", Some(self.container_id()), false, false, tar_dest)
+            .await
+    }
+
+    /// Stream the container's overlay upper layer out of the guest into
+    /// `tar_dest` as an OCI layer tar. Starts the box if needed.
+    pub(super) async fn download_upper_layer(
+        &self,
+        tar_dest: &std::path::Path,
+    ) -> BoxliteResult<()> {
+        let live = self.live_state().await?;
+        let mut container_iface = live.guest_session.container().await?;
+        container_iface
+            .diff_tar(self.container_id(), tar_dest)
+            .await
+    }
+
+    /// Stream the container's merged rootfs into `writer` as a flat tar.
+    /// Starts the box if needed.
+    pub(crate) async fn export_rootfs(
+        &self,
+        writer: &mut (dyn tokio::io::AsyncWrite + Unpin + Send),
+    ) -> BoxliteResult<()> {
+        let live = self.live_state().await?;
+        let mut container_iface = live.guest_session.container().await?;
+        container_iface
+            .export_tar(self.container_id(), writer)
+            .await
+    }
+
+    /// List the processes in the container's PID namespace. Starts the box
+    /// if needed.
+    pub(crate) async fn processes(&self) -> BoxliteResult<Vec<ProcessInfo>> {
+        let live = self.live_state().await?;
+        let mut container_iface = live.guest_session.container().await?;
+        let processes = container_iface
+            .list_processes(self.container_id())
+            .await?
+            .into_iter()
+            .map(|p| ProcessInfo {
+                pid: p.pid,
+                ppid: p.ppid,
+                cmd: p.cmd,
+                rss_kb: p.rss_kb,
+                state: p.state,
+            })
+            .collect();
+        Ok(processes)
+    }
+
+    /// List the executions the guest agent tracks.
+    pub(crate) async fn executions(&self) -> BoxliteResult<Vec<ExecutionInfo>> {
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+
+        let live = self.live_state().await?;
+        let mut exec_interface = live.guest_session.execution().await?;
+        let executions = exec_interface
+            .list()
+            .await?
+            .into_iter()
+            .map(|e| ExecutionInfo {
+                id: e.execution_id,
+                pid: e.pid,
+                program: e.program,
+                running: e.running,
+                started_at: chrono::DateTime::from_timestamp_millis(e.started_at_ms as i64)
+                    .unwrap_or_default(),
+            })
+            .collect();
+        Ok(executions)
+    }
+
+    /// Subscribe to the guest agent's log. Starts the box if needed.
+    pub(crate) async fn guest_log_stream(&self) -> BoxliteResult<GuestLogStream> {
+        let live = self.live_state().await?;
+        let mut guest = live.guest_session.guest().await?;
+        Ok(GuestLogStream::new(guest.stream_logs().await?))
+    }
+
     // ========================================================================
     // LIVE STATE INITIALIZATION (internal)
     // ========================================================================
@@ -634,8 +878,7 @@ impl BoxImpl {
     /// `LiveState` beside `guest_session`. Lazily starts the box like any live
     /// operation. `Unsupported` when the box was created network-disabled.
     ///
-    /// The owner accessor; the first caller lands with the outer SDK/CLI layer.
-    #[allow(dead_code)]
+    /// The owner accessor behind `LiteBox::network_capabilities`.
     pub(crate) async fn network(&self) -> BoxliteResult<&dyn NetworkBackend> {
         self.live_state()
             .await?
@@ -667,6 +910,14 @@ impl BoxImpl {
                 self.config.id, state.status
             ))
         })?;
+        // Boots (not reattaches) queue behind `max_concurrent_boots`. The
+        // permit lives until this function returns: ready or failed.
+        let _boot_permit = if state.status == BoxStatus::Running {
+            None
+        } else {
+            self.runtime.acquire_boot_permit().await?
+        };
+
         let locker = self.runtime.lock_manager.retrieve(lock_id)?;
         tracing::debug!(
             box_id = %self.config.id,
@@ -705,6 +956,9 @@ impl BoxImpl {
             let mut state = self.state.write();
             state.set_pid(Some(pid));
             state.set_status(BoxStatus::Running);
+            if let Some(ports) = &live_state.published_ports {
+                state.set_ports(ports.clone());
+            }
 
             // Initialize health status if health check is configured
             if self.config.options.advanced.health_check.is_some() {
@@ -746,6 +1000,11 @@ impl BoxImpl {
             *self.health_check_task.write() = Some(health_task);
         }
 
+        // The shim stops an idle box on its own; notice when it exits.
+        if self.config.options.idle_timeout_secs.is_some() {
+            self.spawn_idle_exit_watch();
+        }
+
         tracing::info!(
             box_id = %self.config.id,
             "Box started successfully (first_start={})",
@@ -755,6 +1014,46 @@ impl BoxImpl {
         Ok(live_state)
     }
 
+    /// Mark the box Stopped once the shim exits after an idle shutdown.
+    ///
+    /// Ends without touching state when `stop()` (or runtime shutdown) cancels
+    /// the box token first. Otherwise cancels the token itself, invalidating
+    /// this handle the same way `stop()` does.
+    fn spawn_idle_exit_watch(&self) {
+        let Some(pid) = self.state.read().pid else {
+            return;
+        };
+        let state = Arc::clone(&self.state);
+        let runtime = Arc::clone(&self.runtime);
+        let box_id = self.config.id.clone();
+        let pid_path = self.layout.pid_file_path();
+        let shutdown_token = self.shutdown_token.clone();
+
+        tokio::spawn(async move {
+            let monitor = crate::util::ProcessMonitor::new(pid);
+            tokio::select! {
+                _ = monitor.wait_for_exit() => {}
+                _ = shutdown_token.cancelled() => return,
+            }
+            if shutdown_token.is_cancelled() {
+                return;
+            }
+            shutdown_token.cancel();
+
+            tracing::info!(box_id = %box_id, pid, "Shim exited after idle timeout");
+            let _ = std::fs::remove_file(&pid_path);
+            let mut state_guard = state.write();
+            state_guard.mark_stop();
+            if let Err(e) = runtime.box_manager.save_box(&box_id, &state_guard) {
+                tracing::error!(
+                    box_id = %box_id,
+                    error = %e,
+                    "Failed to persist idle-stopped state"
+                );
+            }
+        });
+    }
+
     pub fn spawn_health_check(
         &self,
         state: Arc<RwLock<BoxState>>,
@@ -1095,6 +1394,21 @@ impl BoxImpl {
     }
 }
 
+/// Check a runtime memory target against the memory the VM booted with.
+fn validate_memory_target(mib: u32, max_mib: u32) -> BoxliteResult<()> {
+    if mib == 0 {
+        return Err(BoxliteError::InvalidArgument(
+            "memory must be greater than 0 MiB".into(),
+        ));
+    }
+    if mib > max_mib {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "memory {mib} MiB exceeds the box's configured maximum of {max_mib} MiB"
+        )));
+    }
+    Ok(())
+}
+
 // BoxBackend trait implementation
 // ============================================================================
 
@@ -1104,8 +1418,8 @@ impl crate::runtime::backend::BoxBackend for BoxImpl {
         self.id()
     }
 
-    fn name(&self) -> Option<&str> {
-        self.config.name.as_deref()
+    fn name(&self) -> Option<String> {
+        self.name()
     }
 
     fn info(&self) -> BoxInfo {
@@ -1113,19 +1427,31 @@ impl crate::runtime::backend::BoxBackend for BoxImpl {
     }
 
     async fn start(&self) -> BoxliteResult<()> {
-        self.start().await
+        self.start().instrument(self.span()).await
     }
 
     async fn exec(&self, command: BoxCommand) -> BoxliteResult<Execution> {
-        self.exec(command).await
+        self.exec(command).instrument(self.span()).await
+    }
+
+    async fn attach(&self, execution_id: &str) -> BoxliteResult<Execution> {
+        self.attach(execution_id).instrument(self.span()).await
     }
 
     async fn metrics(&self) -> BoxliteResult<BoxMetrics> {
         self.metrics().await
     }
 
+    async fn set_memory_mib(&self, mib: u32) -> BoxliteResult<u32> {
+        self.set_memory_mib(mib).instrument(self.span()).await
+    }
+
+    fn console_logs(&self, lines: usize) -> BoxliteResult<Vec<String>> {
+        self.console_logs(lines)
+    }
+
     async fn stop(&self) -> BoxliteResult<()> {
-        self.stop().await
+        self.stop().instrument(self.span()).await
     }
 
     async fn copy_into(
@@ -1134,7 +1460,9 @@ impl crate::runtime::backend::BoxBackend for BoxImpl {
         container_dst: &str,
         opts: CopyOptions,
     ) -> BoxliteResult<()> {
-        self.copy_into(host_src, container_dst, opts).await
+        self.copy_into(host_src, container_dst, opts)
+            .instrument(self.span())
+            .await
     }
 
     async fn copy_out(
@@ -1143,7 +1471,9 @@ impl crate::runtime::backend::BoxBackend for BoxImpl {
         host_dst: &std::path::Path,
         opts: CopyOptions,
     ) -> BoxliteResult<()> {
-        self.copy_out(container_src, host_dst, opts).await
+        self.copy_out(container_src, host_dst, opts)
+            .instrument(self.span())
+            .await
     }
 
     async fn clone_box(
@@ -1170,6 +1500,39 @@ impl crate::runtime::backend::BoxBackend for BoxImpl {
     ) -> BoxliteResult<crate::runtime::options::BoxArchive> {
         BoxImpl::export_box(self, options, dest).await
     }
+
+    async fn commit(
+        &self,
+        reference: &str,
+        options: crate::runtime::options::CommitOptions,
+    ) -> BoxliteResult<crate::runtime::types::ImageInfo> {
+        BoxImpl::commit(self, reference, options)
+            .instrument(self.span())
+            .await
+    }
+
+    async fn export_rootfs(
+        &self,
+        writer: &mut (dyn tokio::io::AsyncWrite + Unpin + Send),
+    ) -> BoxliteResult<()> {
+        BoxImpl::export_rootfs(self, writer)
+            .instrument(self.span())
+            .await
+    }
+
+    async fn guest_log_stream(&self) -> BoxliteResult<GuestLogStream> {
+        BoxImpl::guest_log_stream(self)
+            .instrument(self.span())
+            .await
+    }
+
+    async fn processes(&self) -> BoxliteResult<Vec<ProcessInfo>> {
+        BoxImpl::processes(self).instrument(self.span()).await
+    }
+
+    async fn executions(&self) -> BoxliteResult<Vec<ExecutionInfo>> {
+        BoxImpl::executions(self).instrument(self.span()).await
+    }
 }
 
 #[async_trait::async_trait]
@@ -1191,6 +1554,55 @@ impl crate::runtime::backend::BoxNetworkBackend for BoxImpl {
             }),
         ))
     }
+
+    async fn capabilities(&self) -> BoxliteResult<crate::net::BackendCapabilities> {
+        Ok(self.network().await?.capabilities())
+    }
+
+    async fn expose_port(&self, host_port: u16, guest_port: u16) -> BoxliteResult<()> {
+        async {
+            crate::net::expose_tcp_port(self.network().await?, host_port, guest_port).await?;
+            tracing::info!(host_port, guest_port, "Exposed port");
+            Ok(())
+        }
+        .instrument(self.span())
+        .await
+    }
+
+    async fn unexpose_port(&self, host_port: u16) -> BoxliteResult<()> {
+        async {
+            crate::net::unexpose_tcp_port(self.network().await?, host_port).await?;
+            tracing::info!(host_port, "Unexposed port");
+            Ok(())
+        }
+        .instrument(self.span())
+        .await
+    }
+
+    async fn port_forwards(&self) -> BoxliteResult<Vec<crate::net::Forward>> {
+        self.network().await?.list_forwards().await
+    }
+
+    async fn vsock_connect(&self, port: u32) -> BoxliteResult<Box<dyn BoxConnection>> {
+        if !self.config.options.vsock_ports.contains(&port) {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "vsock port {port} is not in BoxOptions::vsock_ports"
+            )));
+        }
+        // The engine binds the socket at VM spawn; make sure the box is up.
+        self.live_state().await?;
+
+        let socket_path = self.config.sockets().vsock_sock(port);
+        let stream = tokio::net::UnixStream::connect(&socket_path)
+            .await
+            .map_err(|e| {
+                BoxliteError::Network(format!(
+                    "failed to connect to vsock port {port} via {}: {e}",
+                    socket_path.display()
+                ))
+            })?;
+        Ok(Box::new(stream))
+    }
 }
 
 // ============================================================================
@@ -1235,6 +1647,14 @@ mod tests {
         let runtime = RuntimeImpl::new(BoxliteOptions {
             home_dir: temp_dir.path().to_path_buf(),
             image_registries: vec![],
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .expect("create runtime");
 
@@ -1308,4 +1728,18 @@ mod tests {
         // ChildGuard's Drop reaps the (now-dead) child.
         drop(child);
     }
+
+    #[test]
+    fn memory_target_must_fit_boot_memory() {
+        assert!(validate_memory_target(512, 1024).is_ok());
+        assert!(validate_memory_target(1024, 1024).is_ok());
+        assert!(matches!(
+            validate_memory_target(2048, 1024),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            validate_memory_target(0, 1024),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+    }
 }
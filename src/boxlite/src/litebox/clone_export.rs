@@ -197,7 +197,7 @@ impl BoxImpl {
 
         // Phase 2: Checksum + manifest + archive run with VM resumed.
         // These only read static temp files, no disk consistency needed.
-        let config_name = self.config.name.clone();
+        let config_name = self.name();
         let config_options = self.config.options.clone();
         let box_id_str = self.id().to_string();
         let dest = dest.to_path_buf();
@@ -308,6 +308,7 @@ fn do_export_finalize(
     let image = match &config_options.rootfs {
         crate::runtime::options::RootfsSpec::Image(img) => img.clone(),
         crate::runtime::options::RootfsSpec::RootfsPath(path) => path.clone(),
+        crate::runtime::options::RootfsSpec::OciBundle(path) => path.display().to_string(),
     };
 
     let manifest = ArchiveManifest {
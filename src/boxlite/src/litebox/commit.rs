@@ -0,0 +1,105 @@
+//! Commit operation for BoxImpl: save a box's rootfs as a local image.
+
+use std::time::Instant;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+use super::box_impl::BoxImpl;
+use crate::runtime::options::{CommitOptions, RootfsSpec};
+use crate::runtime::types::ImageInfo;
+
+impl BoxImpl {
+    /// Commit the box's current rootfs as image `reference`.
+    ///
+    /// The container rootfs is streamed out of the guest and diffed against
+    /// the box's base image; the difference becomes the new image's top layer.
+    /// Boxes with an overlay upper disk skip the diff: the guest exports the
+    /// overlay upper dir, which holds exactly the box's writes. The VM is not
+    /// quiesced (the guest packs the files), so writes racing with the commit
+    /// may or may not be captured.
+    pub(crate) async fn commit(
+        &self,
+        reference: &str,
+        options: CommitOptions,
+    ) -> BoxliteResult<ImageInfo> {
+        let t0 = Instant::now();
+
+        if self.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Handle invalidated after stop(). Use runtime.get() to get a new handle.".into(),
+            ));
+        }
+        if reference.is_empty() {
+            return Err(BoxliteError::Config(
+                "image reference cannot be empty".into(),
+            ));
+        }
+
+        let _lock = self.disk_ops.lock().await;
+        let base = match &self.config.options.rootfs {
+            RootfsSpec::Image(image) => self.runtime.pull_image(image).await?,
+            RootfsSpec::RootfsPath(path) => {
+                self.runtime
+                    .image_manager
+                    .load_from_local(path.into(), format!("local:{}", path))
+                    .await?
+            }
+            RootfsSpec::OciBundle(path) => {
+                self.runtime
+                    .image_manager
+                    .load_from_local(path.clone(), format!("local:{}", path.display()))
+                    .await?
+            }
+        };
+
+        let rootfs_tar = self.runtime.layout.temp_dir().join(format!(
+            "commit-{}-{}.tar",
+            self.config.id.as_str(),
+            uuid::Uuid::new_v4()
+        ));
+        let upper_only = self.config.options.overlay_upper_disk.is_some();
+        let downloaded = if upper_only {
+            self.download_upper_layer(&rootfs_tar).await
+        } else {
+            self.download_rootfs(&rootfs_tar).await
+        };
+        let result = match downloaded {
+            Ok(()) => {
+                let changes = self.commit_changes(options);
+                let images = &self.runtime.image_manager;
+                if upper_only {
+                    images
+                        .commit_upper(&base, &rootfs_tar, reference, &changes)
+                        .await
+                } else {
+                    images.commit(&base, &rootfs_tar, reference, &changes).await
+                }
+            }
+            Err(e) => Err(e),
+        };
+        let _ = tokio::fs::remove_file(&rootfs_tar).await;
+        let info = result?;
+
+        tracing::info!(
+            box_id = %self.config.id,
+            elapsed_ms = t0.elapsed().as_millis() as u64,
+            base = %base.reference(),
+            upper_only,
+            reference = %info.reference,
+            "commit completed"
+        );
+        Ok(info)
+    }
+
+    /// Effective config changes: the box's own overrides, then `options`.
+    fn commit_changes(&self, options: CommitOptions) -> CommitOptions {
+        let box_opts = &self.config.options;
+        CommitOptions {
+            entrypoint: options.entrypoint.or_else(|| box_opts.entrypoint.clone()),
+            cmd: options.cmd.or_else(|| box_opts.cmd.clone()),
+            // Later entries win when the image config is merged by key.
+            env: box_opts.env.iter().cloned().chain(options.env).collect(),
+            message: options.message,
+        }
+    }
+}
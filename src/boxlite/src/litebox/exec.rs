@@ -4,12 +4,16 @@
 //! The actual execution logic is in BoxImpl::exec().
 
 use crate::runtime::backend::ExecBackend;
-use boxlite_shared::errors::BoxliteResult;
+use crate::runtime::options::ExecResourceLimits;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use futures::Stream;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
-use std::time::Duration;
-use tokio::sync::mpsc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, mpsc};
 
 /// Command builder for executing programs in a box.
 ///
@@ -31,10 +35,45 @@ pub struct BoxCommand {
     pub(crate) command: String,
     pub(crate) args: Vec<String>,
     pub(crate) env: Option<Vec<(String, String)>>,
+    /// `.env` files merged under `env` when the command is executed.
+    pub(crate) env_files: Vec<PathBuf>,
     pub(crate) timeout: Option<Duration>,
     pub(crate) working_dir: Option<String>,
     pub(crate) tty: bool,
+    /// Initial PTY `(rows, cols)`; `None` uses the host terminal's size.
+    pub(crate) tty_size: Option<(u32, u32)>,
     pub(crate) user: Option<String>,
+    pub(crate) groups: Vec<u32>,
+    pub(crate) resource_profile: Option<String>,
+    /// Limits resolved from `resource_profile` when the command is executed.
+    pub(crate) resource_limits: Option<ExecResourceLimits>,
+    pub(crate) output_mode: OutputMode,
+    /// Cap on the serialized environment, checked when executed.
+    pub(crate) max_env_bytes: usize,
+    /// Fixed stdin payload, sent once and followed by EOF.
+    pub(crate) stdin_bytes: Option<Vec<u8>>,
+    /// Unread bytes held per output stream before the command is paused.
+    pub(crate) output_buffer_size: usize,
+}
+
+/// Default [`BoxCommand::max_env_bytes`]: 1 MiB.
+pub(crate) const DEFAULT_MAX_ENV_BYTES: usize = 1024 * 1024;
+
+/// Default [`BoxCommand::output_buffer_size`]: 8 MiB.
+pub(crate) const DEFAULT_OUTPUT_BUFFER_SIZE: usize = 8 * 1024 * 1024;
+
+/// How an execution's stdout and stderr are chunked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputMode {
+    /// Yield data as the guest reads it; chunks may split lines.
+    #[default]
+    Bytes,
+    /// Yield complete lines, newline included. The guest buffers a partial
+    /// line until its newline arrives or the stream ends.
+    Lines,
+    /// Like `Bytes`, but with the PTY's output processing turned off, so
+    /// `\n` is not translated to `\r\n`. Same as `Bytes` without a TTY.
+    Raw,
 }
 
 impl BoxCommand {
@@ -44,10 +83,19 @@ impl BoxCommand {
             command: command.into(),
             args: vec![],
             env: None,
+            env_files: Vec::new(),
             timeout: None,
             working_dir: None,
             tty: false,
+            tty_size: None,
             user: None,
+            groups: Vec::new(),
+            resource_profile: None,
+            resource_limits: None,
+            output_mode: OutputMode::default(),
+            max_env_bytes: DEFAULT_MAX_ENV_BYTES,
+            stdin_bytes: None,
+            output_buffer_size: DEFAULT_OUTPUT_BUFFER_SIZE,
         }
     }
 
@@ -75,6 +123,19 @@ impl BoxCommand {
         self
     }
 
+    /// Load environment variables from a `.env` file.
+    ///
+    /// The file holds `KEY=VALUE` lines; blank lines and `#` comments are
+    /// skipped, an `export ` prefix is allowed, and values may be single-
+    /// or double-quoted to keep spaces. Files are read on the host when the
+    /// command is executed: later files override earlier ones and
+    /// variables set with [`env`](Self::env) override both. A missing file
+    /// or malformed line fails the exec with `InvalidArgument`.
+    pub fn env_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.env_files.push(path.as_ref().to_path_buf());
+        self
+    }
+
     /// Set execution timeout.
     pub fn timeout(mut self, timeout: Duration) -> Self {
         self.timeout = Some(timeout);
@@ -89,12 +150,21 @@ impl BoxCommand {
 
     /// Enable TTY (pseudo-terminal) for interactive sessions.
     ///
-    /// Terminal size is auto-detected from the current terminal.
+    /// With a TTY, stdout and stderr are merged through the PTY; without
+    /// one they stay separate pipes. The initial size comes from
+    /// [`tty_size`](Self::tty_size), else the current terminal.
     pub fn tty(mut self, enable: bool) -> Self {
         self.tty = enable;
         self
     }
 
+    /// Set the PTY's initial size. Only used with `tty(true)`; resize later
+    /// with `Execution::resize_tty`.
+    pub fn tty_size(mut self, rows: u32, cols: u32) -> Self {
+        self.tty_size = Some((rows, cols));
+        self
+    }
+
     /// Set the user to run the command as.
     ///
     /// Format: `<name|uid>[:<group|gid>]` (same as `docker exec --user`).
@@ -104,6 +174,211 @@ impl BoxCommand {
         self.user = if s.trim().is_empty() { None } else { Some(s) };
         self
     }
+
+    /// Set supplementary group IDs for the command, in addition to the
+    /// user's primary group (like `docker exec --group-add`).
+    ///
+    /// IDs are applied numerically and need not exist in the container's
+    /// `/etc/group`.
+    pub fn groups(mut self, gids: impl IntoIterator<Item = u32>) -> Self {
+        self.groups = gids.into_iter().collect();
+        self
+    }
+
+    /// Limit the environment's total size, counted as `KEY=VALUE\0` per
+    /// entry (the size of the process's envp). Defaults to 1 MiB.
+    ///
+    /// Executing a command over the limit fails with `InvalidArgument`
+    /// instead of overflowing the RPC message or the guest's exec.
+    pub fn max_env_bytes(mut self, bytes: usize) -> Self {
+        self.max_env_bytes = bytes;
+        self
+    }
+
+    /// Send `bytes` as the command's stdin, then close it.
+    ///
+    /// For piping a fixed payload (e.g. into `cat`) without streaming
+    /// through [`Execution::stdin`], which returns `None` for such commands.
+    pub fn stdin_bytes(mut self, bytes: impl Into<Vec<u8>>) -> Self {
+        self.stdin_bytes = Some(bytes.into());
+        self
+    }
+
+    /// Choose how stdout and stderr are chunked. Defaults to
+    /// [`OutputMode::Bytes`].
+    pub fn output_mode(mut self, mode: OutputMode) -> Self {
+        self.output_mode = mode;
+        self
+    }
+
+    /// Limit how much unread output is buffered, per stream. Defaults to
+    /// 8 MiB.
+    ///
+    /// Output is not dropped for a slow reader: once `bytes` of stdout (or
+    /// stderr) are unread, the guest stops reading that pipe and the command
+    /// blocks on its next write until the stream is read. Dropping a stream
+    /// discards its output instead of pausing the command.
+    pub fn output_buffer_size(mut self, bytes: usize) -> Self {
+        self.output_buffer_size = bytes.max(1);
+        self
+    }
+
+    /// Run the command under a named resource profile.
+    ///
+    /// The name must match an entry in `BoxOptions::exec_profiles`; the guest
+    /// places the process in its own cgroup with that profile's limits.
+    /// Executing with an unknown profile name fails.
+    pub fn resource_profile(mut self, name: impl Into<String>) -> Self {
+        self.resource_profile = Some(name.into());
+        self
+    }
+
+    /// Queue the `stdin_bytes` payload on `stdin_tx` and close stdin.
+    ///
+    /// Returns the sender for streaming input when no payload is set.
+    pub(crate) fn feed_stdin(
+        &self,
+        stdin_tx: mpsc::UnboundedSender<Vec<u8>>,
+    ) -> Option<mpsc::UnboundedSender<Vec<u8>>> {
+        let Some(bytes) = &self.stdin_bytes else {
+            return Some(stdin_tx);
+        };
+        if !bytes.is_empty() {
+            // The pump owns the receiver for the execution's lifetime.
+            let _ = stdin_tx.send(bytes.clone());
+        }
+        // Dropping the sender makes the pump send EOF after the payload.
+        None
+    }
+
+    /// Resolve `resource_profile` against the box's configured profiles.
+    pub(crate) fn resolve_resource_profile(
+        mut self,
+        profiles: &HashMap<String, ExecResourceLimits>,
+    ) -> BoxliteResult<Self> {
+        if let Some(name) = &self.resource_profile {
+            let limits = profiles.get(name).ok_or_else(|| {
+                let mut known: Vec<_> = profiles.keys().map(String::as_str).collect();
+                known.sort_unstable();
+                BoxliteError::Config(format!(
+                    "unknown exec resource profile {name:?} (defined: {known:?})"
+                ))
+            })?;
+            self.resource_limits = Some(limits.clone());
+        }
+        Ok(self)
+    }
+
+    /// Check env keys and values can be passed to `execve`, and that the
+    /// environment fits in `max_env_bytes`.
+    pub(crate) fn validate_env(&self) -> BoxliteResult<()> {
+        let mut total = 0usize;
+        for (key, value) in self.env.iter().flatten() {
+            if key.is_empty() || key.contains(['=', '\0']) {
+                return Err(BoxliteError::InvalidArgument(format!(
+                    "invalid environment variable name {key:?}: must be non-empty \
+                     without '=' or NUL"
+                )));
+            }
+            if value.contains('\0') {
+                return Err(BoxliteError::InvalidArgument(format!(
+                    "environment variable {key} contains a NUL byte"
+                )));
+            }
+            total += key.len() + value.len() + 2;
+        }
+        if total > self.max_env_bytes {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "environment is {total} bytes, over the {} byte limit; \
+                 raise it with BoxCommand::max_env_bytes",
+                self.max_env_bytes
+            )));
+        }
+        Ok(())
+    }
+
+    /// Read `env_files` and merge them under the explicit `env` entries.
+    pub(crate) fn resolve_env_files(mut self) -> BoxliteResult<Self> {
+        if self.env_files.is_empty() {
+            return Ok(self);
+        }
+        let mut merged = Vec::new();
+        for path in std::mem::take(&mut self.env_files) {
+            let contents = std::fs::read_to_string(&path).map_err(|e| {
+                BoxliteError::InvalidArgument(format!(
+                    "cannot read env file {}: {e}",
+                    path.display()
+                ))
+            })?;
+            merged.extend(parse_env_file(&contents, &path)?);
+        }
+        // Later entries win when the env is collected into a map.
+        merged.extend(self.env.take().unwrap_or_default());
+        self.env = Some(merged);
+        Ok(self)
+    }
+}
+
+/// Parse `.env` file contents into `(key, value)` pairs in file order.
+fn parse_env_file(contents: &str, path: &Path) -> BoxliteResult<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let invalid = |reason: &str| {
+            BoxliteError::InvalidArgument(format!("{}:{}: {reason}", path.display(), index + 1))
+        };
+
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| invalid("expected KEY=VALUE"))?;
+        let key = key.trim();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(invalid(&format!("invalid variable name {key:?}")));
+        }
+        let value = parse_env_value(value.trim_start()).map_err(invalid)?;
+        vars.push((key.to_string(), value));
+    }
+    Ok(vars)
+}
+
+/// Parse the right-hand side of a `.env` line.
+///
+/// Double quotes allow `\"`, `\\` and `\n` escapes; single quotes are
+/// literal. Unquoted values end at a ` #` comment and are trimmed.
+fn parse_env_value(raw: &str) -> Result<String, &'static str> {
+    let Some(quote) = raw.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+        let value = raw.split(" #").next().unwrap_or_default();
+        return Ok(value.trim_end().to_string());
+    };
+
+    let mut value = String::new();
+    let mut chars = raw[1..].chars();
+    loop {
+        match chars.next() {
+            None => return Err("unterminated quoted value"),
+            Some(c) if c == quote => break,
+            Some('\\') if quote == '"' => match chars.next() {
+                Some('n') => value.push('\n'),
+                Some(c @ ('"' | '\\')) => value.push(c),
+                Some(c) => {
+                    value.push('\\');
+                    value.push(c);
+                }
+                None => return Err("unterminated quoted value"),
+            },
+            Some(c) => value.push(c),
+        }
+    }
+
+    let rest = chars.as_str().trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err("unexpected characters after closing quote");
+    }
+    Ok(value)
 }
 
 /// Handle to a running command execution.
@@ -231,9 +506,27 @@ impl Execution {
     /// already cached, returns immediately. Otherwise, awaits the next value
     /// from the result channel.
     ///
+    /// Returns `BoxliteError::Timeout` if the command's timeout elapsed and
+    /// the process was killed for it; use [`Execution::wait_status`] to get
+    /// the exit status of a timed-out process instead.
+    pub async fn wait(&self) -> BoxliteResult<ExecResult> {
+        let result = self.wait_status().await?;
+        if result.timed_out {
+            return Err(BoxliteError::Timeout(format!(
+                "execution {} exceeded its timeout and was killed (exit code {})",
+                self.id(),
+                result.exit_code
+            )));
+        }
+        Ok(result)
+    }
+
+    /// Wait for the execution to complete, reporting a timeout as an
+    /// ordinary result with [`ExecResult::timed_out`] set.
+    ///
     /// The lock domain is `wait_state`, independent of `inner`, so a parked
     /// wait does not block `kill`/`signal`/`resize_tty`.
-    pub async fn wait(&self) -> BoxliteResult<ExecResult> {
+    pub async fn wait_status(&self) -> BoxliteResult<ExecResult> {
         self.wait_state
             .cached
             .get_or_try_init(|| async {
@@ -274,6 +567,84 @@ impl Execution {
         inner.interface.resize_tty(&self.id, rows, cols, 0, 0).await
     }
 
+    /// Keep the PTY sized to the host terminal, like `docker exec -it`.
+    ///
+    /// Sends the terminal's current size right away and again on every
+    /// `SIGWINCH`, until the returned guard is dropped. Only useful for
+    /// executions started with TTY enabled.
+    pub fn follow_terminal_size(&self) -> BoxliteResult<TerminalSizeFollower> {
+        self.follow_window_changes(crate::util::get_terminal_size)
+    }
+
+    /// `follow_terminal_size` reading `(rows, cols)` from `size`.
+    fn follow_window_changes(
+        &self,
+        size: impl Fn() -> (u32, u32) + Send + 'static,
+    ) -> BoxliteResult<TerminalSizeFollower> {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut sigwinch = signal(SignalKind::window_change()).map_err(|e| {
+            BoxliteError::Internal(format!("Failed to install SIGWINCH handler: {}", e))
+        })?;
+        let execution = self.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                let (rows, cols) = size();
+                if let Err(e) = execution.resize_tty(rows, cols).await {
+                    tracing::debug!(rows, cols, "Failed to resize TTY: {}", e);
+                }
+                if sigwinch.recv().await.is_none() {
+                    break;
+                }
+            }
+        });
+        Ok(TerminalSizeFollower { task })
+    }
+
+    /// Drain stdout and stderr, then wait for exit.
+    ///
+    /// Both streams are read concurrently so a process filling one pipe
+    /// cannot stall on the other. If `timeout` elapses first the execution
+    /// is killed, and whatever output it produced is still returned.
+    pub(crate) async fn collect_output(
+        mut self,
+        timeout: Option<Duration>,
+    ) -> BoxliteResult<ExecOutput> {
+        let started = Instant::now();
+        let stdout = self.stdout();
+        let stderr = self.stderr();
+        let execution = &self;
+
+        let finished = async move {
+            let (stdout, stderr) = tokio::join!(drain(stdout), drain(stderr));
+            (stdout, stderr, execution.wait().await)
+        };
+        tokio::pin!(finished);
+
+        let mut timed_out = false;
+        let (stdout, stderr, result) = match timeout {
+            Some(limit) => match tokio::time::timeout(limit, &mut finished).await {
+                Ok(done) => done,
+                Err(_) => {
+                    timed_out = true;
+                    execution.kill().await?;
+                    finished.await
+                }
+            },
+            None => finished.await,
+        };
+        let result = result?;
+
+        Ok(ExecOutput {
+            exit_code: result.exit_code,
+            stdout,
+            stderr,
+            duration_ms: started.elapsed().as_millis() as u64,
+            timed_out,
+            error_message: result.error_message,
+        })
+    }
+
     /// Build a stub `Execution` for cross-crate tests.
     ///
     /// The stub backend no-ops every control operation. Callers drive
@@ -329,6 +700,19 @@ impl Execution {
     }
 }
 
+/// Guard returned by [`Execution::follow_terminal_size`]; resizing stops
+/// when it is dropped.
+#[must_use = "the PTY stops following the terminal when this is dropped"]
+pub struct TerminalSizeFollower {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for TerminalSizeFollower {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
 /// Exit status of a process.
 #[derive(Clone, Debug)]
 pub struct ExecResult {
@@ -338,6 +722,17 @@ pub struct ExecResult {
     /// (e.g., container init death causing PID namespace teardown).
     /// None if the process exited normally.
     pub error_message: Option<String>,
+    /// The command's timeout elapsed and the process was killed.
+    /// [`Execution::wait`] reports this as [`BoxliteError::Timeout`].
+    pub timed_out: bool,
+    /// Wall time from spawn to exit as measured by the guest, or 0 when
+    /// the backend does not report it.
+    pub duration_ms: u64,
+    /// The process was killed by the guest's OOM killer, as opposed to some
+    /// other SIGKILL.
+    pub oom_killed: bool,
+    /// Name of the terminating signal (e.g. `"SIGSEGV"`), if any.
+    pub signal_name: Option<String>,
 }
 
 impl ExecResult {
@@ -351,6 +746,42 @@ impl ExecResult {
     }
 }
 
+/// Captured result of [`LiteBox::run`](crate::LiteBox::run).
+#[derive(Clone, Debug)]
+pub struct ExecOutput {
+    /// Exit code, as in [`ExecResult::exit_code`]. A timed-out command
+    /// reports the code of the kill (usually `-9`).
+    pub exit_code: i32,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    /// Wall time from spawn to exit.
+    pub duration_ms: u64,
+    /// The command's timeout elapsed and it was killed.
+    pub timed_out: bool,
+    /// See [`ExecResult::error_message`].
+    pub error_message: Option<String>,
+}
+
+impl ExecOutput {
+    /// Returns true if the command exited 0 within its timeout.
+    pub fn success(&self) -> bool {
+        self.exit_code == 0 && !self.timed_out
+    }
+}
+
+/// Read a stream to the end; `None` reads nothing.
+async fn drain<S: Stream<Item = String> + Unpin>(stream: Option<S>) -> Vec<u8> {
+    use futures::StreamExt;
+
+    let mut buf = Vec::new();
+    if let Some(mut stream) = stream {
+        while let Some(chunk) = stream.next().await {
+            buf.extend_from_slice(chunk.as_bytes());
+        }
+    }
+    buf
+}
+
 /// Standard input stream (write-only).
 pub struct ExecStdin {
     sender: Option<mpsc::UnboundedSender<Vec<u8>>>,
@@ -391,14 +822,81 @@ impl ExecStdin {
     }
 }
 
+/// Byte budget shared by an output stream's producer and its reader.
+///
+/// The producer reserves each chunk's length before sending it and the
+/// reader releases it once the chunk is taken, so at most `capacity` bytes
+/// sit unread in the channel.
+#[derive(Clone)]
+pub(crate) struct OutputBudget {
+    permits: Arc<Semaphore>,
+    capacity: usize,
+}
+
+impl OutputBudget {
+    pub(crate) fn new(capacity: usize) -> Self {
+        let capacity = capacity.clamp(1, Semaphore::MAX_PERMITS);
+        Self {
+            permits: Arc::new(Semaphore::new(capacity)),
+            capacity,
+        }
+    }
+
+    /// Wait until `len` more bytes fit. A chunk larger than the whole
+    /// budget waits for an empty buffer instead of forever.
+    pub(crate) async fn reserve(&self, len: usize) {
+        let n = len.min(self.capacity).min(u32::MAX as usize) as u32;
+        if let Ok(permit) = self.permits.acquire_many(n).await {
+            permit.forget();
+        }
+    }
+
+    /// Return a read chunk's bytes. Chunks sent without a reservation (the
+    /// final UTF-8 flush, attach errors) only end a stream, so the surplus
+    /// they return is never drawn on.
+    fn release(&self, len: usize) {
+        let n = len.min(self.capacity).min(u32::MAX as usize);
+        self.permits.add_permits(n);
+    }
+
+    /// Unread bytes currently reserved.
+    #[cfg(test)]
+    pub(crate) fn used(&self) -> usize {
+        self.capacity - self.permits.available_permits()
+    }
+}
+
+/// Receive from `receiver`, returning each item's bytes to `budget`.
+fn poll_budgeted(
+    receiver: &mut mpsc::UnboundedReceiver<String>,
+    budget: Option<&OutputBudget>,
+    cx: &mut Context<'_>,
+) -> Poll<Option<String>> {
+    let poll = receiver.poll_recv(cx);
+    if let (Poll::Ready(Some(chunk)), Some(budget)) = (&poll, budget) {
+        budget.release(chunk.len());
+    }
+    poll
+}
+
 /// Standard output stream (read-only).
 pub struct ExecStdout {
     receiver: mpsc::UnboundedReceiver<String>,
+    budget: Option<OutputBudget>,
 }
 
 impl ExecStdout {
     pub(crate) fn new(receiver: mpsc::UnboundedReceiver<String>) -> Self {
-        Self { receiver }
+        Self {
+            receiver,
+            budget: None,
+        }
+    }
+
+    /// Release each chunk's bytes to `budget` as it is read.
+    pub(crate) fn with_budget(mut self, budget: OutputBudget) -> Self {
+        self.budget = Some(budget);
+        self
     }
 }
 
@@ -406,18 +904,29 @@ impl Stream for ExecStdout {
     type Item = String;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.receiver.poll_recv(cx)
+        let this = &mut *self;
+        poll_budgeted(&mut this.receiver, this.budget.as_ref(), cx)
     }
 }
 
 /// Standard error stream (read-only).
 pub struct ExecStderr {
     receiver: mpsc::UnboundedReceiver<String>,
+    budget: Option<OutputBudget>,
 }
 
 impl ExecStderr {
     pub(crate) fn new(receiver: mpsc::UnboundedReceiver<String>) -> Self {
-        Self { receiver }
+        Self {
+            receiver,
+            budget: None,
+        }
+    }
+
+    /// Release each chunk's bytes to `budget` as it is read.
+    pub(crate) fn with_budget(mut self, budget: OutputBudget) -> Self {
+        self.budget = Some(budget);
+        self
     }
 }
 
@@ -425,7 +934,8 @@ impl Stream for ExecStderr {
     type Item = String;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.receiver.poll_recv(cx)
+        let this = &mut *self;
+        poll_budgeted(&mut this.receiver, this.budget.as_ref(), cx)
     }
 }
 
@@ -451,6 +961,17 @@ mod tests {
         assert_eq!(cmd.user, Some("1000:1000".to_string()));
     }
 
+    #[test]
+    fn test_box_command_tty_builder() {
+        let cmd = BoxCommand::new("sh");
+        assert!(!cmd.tty);
+        assert_eq!(cmd.tty_size, None);
+
+        let cmd = cmd.tty(true).tty_size(40, 120);
+        assert!(cmd.tty);
+        assert_eq!(cmd.tty_size, Some((40, 120)));
+    }
+
     #[test]
     fn test_box_command_user_empty_string_becomes_none() {
         let cmd = BoxCommand::new("id").user("");
@@ -463,6 +984,178 @@ mod tests {
         assert_eq!(cmd.user, None);
     }
 
+    #[test]
+    fn test_box_command_groups_builder() {
+        assert!(BoxCommand::new("id").groups.is_empty());
+        let cmd = BoxCommand::new("id").user("1000:1000").groups([10, 20]);
+        assert_eq!(cmd.groups, vec![10, 20]);
+    }
+
+    #[test]
+    fn test_feed_stdin_sends_payload_then_closes() {
+        let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
+        let cmd = BoxCommand::new("cat").stdin_bytes("hello\n");
+        assert!(cmd.feed_stdin(tx).is_none());
+        assert_eq!(rx.try_recv().unwrap(), b"hello\n");
+        // Sender dropped: the pump sees the channel close and sends EOF.
+        assert!(matches!(
+            rx.try_recv(),
+            Err(mpsc::error::TryRecvError::Disconnected)
+        ));
+
+        let (tx, mut rx) = mpsc::unbounded_channel::<Vec<u8>>();
+        let streaming = BoxCommand::new("cat").feed_stdin(tx);
+        assert!(streaming.is_some());
+        assert!(matches!(
+            rx.try_recv(),
+            Err(mpsc::error::TryRecvError::Empty)
+        ));
+    }
+
+    fn write_env_file(dir: &tempfile::TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_env_file_quoting_and_comments() {
+        let contents = r#"
+# database settings
+export DB_HOST=localhost
+DB_NAME = app   # trailing comment
+GREETING="hello world"
+QUOTED_HASH="a # b"
+ESCAPED="say \"hi\"\nbye"
+LITERAL='$HOME \n stays'
+EMPTY=
+"#;
+        let vars = parse_env_file(contents, Path::new(".env")).unwrap();
+        assert_eq!(
+            vars,
+            vec![
+                ("DB_HOST".to_string(), "localhost".to_string()),
+                ("DB_NAME".to_string(), "app".to_string()),
+                ("GREETING".to_string(), "hello world".to_string()),
+                ("QUOTED_HASH".to_string(), "a # b".to_string()),
+                ("ESCAPED".to_string(), "say \"hi\"\nbye".to_string()),
+                ("LITERAL".to_string(), "$HOME \\n stays".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_env_file_malformed_line_names_line_number() {
+        for (contents, line) in [
+            ("A=1\n\nNOT_AN_ASSIGNMENT\n", 3),
+            ("A=\"unterminated\n", 1),
+            ("# ok\nA=\"x\" junk\n", 2),
+            ("=value\n", 1),
+        ] {
+            let err = parse_env_file(contents, Path::new("app.env")).unwrap_err();
+            assert!(
+                matches!(&err, BoxliteError::InvalidArgument(msg) if msg.starts_with(&format!("app.env:{line}:"))),
+                "{contents:?}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_env_file_override_precedence() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let base = write_env_file(&dir, "base.env", "A=base\nB=base\nC=base\n");
+        let local = write_env_file(&dir, "local.env", "B=local\nC=local\n");
+
+        let cmd = BoxCommand::new("env")
+            .env("C", "explicit")
+            .env_file(&base)
+            .env_file(&local)
+            .resolve_env_files()
+            .unwrap();
+        let env: HashMap<_, _> = cmd.env.unwrap().into_iter().collect();
+        assert_eq!(env["A"], "base");
+        assert_eq!(env["B"], "local");
+        assert_eq!(env["C"], "explicit");
+        assert!(cmd.env_files.is_empty());
+    }
+
+    #[test]
+    fn test_validate_env_rejects_bad_keys() {
+        assert!(BoxCommand::new("env").validate_env().is_ok());
+        assert!(
+            BoxCommand::new("env")
+                .env("A", "x=y")
+                .validate_env()
+                .is_ok()
+        );
+
+        for (key, value) in [("", "v"), ("A=B", "v"), ("A\0", "v"), ("A", "v\0")] {
+            let err = BoxCommand::new("env")
+                .env(key, value)
+                .validate_env()
+                .unwrap_err();
+            assert!(
+                matches!(err, BoxliteError::InvalidArgument(_)),
+                "{key:?}={value:?}: {err}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_env_size_limit_boundary() {
+        // "A=" + value + NUL: 3 bytes of overhead per entry.
+        let cmd = BoxCommand::new("env")
+            .max_env_bytes(16)
+            .env("A", "x".repeat(13));
+        assert!(cmd.validate_env().is_ok());
+
+        let err = cmd.env("B", "").validate_env().unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err}");
+
+        let big = BoxCommand::new("env").env("BIG", "x".repeat(DEFAULT_MAX_ENV_BYTES));
+        assert!(big.validate_env().is_err());
+    }
+
+    #[test]
+    fn test_env_file_missing_is_invalid_argument() {
+        let err = BoxCommand::new("env")
+            .env_file("/nonexistent/boxlite.env")
+            .resolve_env_files()
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn test_box_command_resource_profile_resolves() {
+        let light = ExecResourceLimits {
+            cpus: Some(0.5),
+            ..Default::default()
+        };
+        let profiles = HashMap::from([("light".to_string(), light.clone())]);
+
+        let cmd = BoxCommand::new("ls")
+            .resource_profile("light")
+            .resolve_resource_profile(&profiles)
+            .unwrap();
+        assert_eq!(cmd.resource_limits, Some(light));
+
+        let cmd = BoxCommand::new("ls")
+            .resolve_resource_profile(&profiles)
+            .unwrap();
+        assert_eq!(cmd.resource_limits, None);
+    }
+
+    #[test]
+    fn test_box_command_unknown_resource_profile_errors() {
+        let err = BoxCommand::new("ls")
+            .resource_profile("heavy")
+            .resolve_resource_profile(&HashMap::new())
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::Config(_)));
+        assert!(err.to_string().contains("heavy"));
+    }
+
     // ─── wait must not block kill ─────────────────────────────────────
     //
     // `kill`/`signal`/`resize_tty` need the inner mutex. If `wait`
@@ -537,4 +1230,216 @@ mod tests {
         assert!(result.is_ok());
         assert!(kill_observed.load(AtomicOrdering::SeqCst));
     }
+
+    /// Backend whose kill ends the process: closes its output streams and
+    /// reports exit by the signal.
+    struct KillingExecBackend {
+        streams: Option<(
+            tokio_mpsc::UnboundedSender<String>,
+            tokio_mpsc::UnboundedSender<String>,
+        )>,
+        result_tx: tokio_mpsc::UnboundedSender<ExecResult>,
+    }
+
+    #[async_trait]
+    impl ExecBackend for KillingExecBackend {
+        async fn signal(&mut self, _execution_id: &str, signal: i32) -> BoxliteResult<()> {
+            self.streams = None;
+            let _ = self.result_tx.send(ExecResult {
+                exit_code: -signal,
+                error_message: None,
+                timed_out: false,
+                duration_ms: 0,
+                oom_killed: false,
+                signal_name: None,
+            });
+            Ok(())
+        }
+
+        async fn resize_tty(
+            &mut self,
+            _execution_id: &str,
+            _rows: u32,
+            _cols: u32,
+            _x_pixels: u32,
+            _y_pixels: u32,
+        ) -> BoxliteResult<()> {
+            Ok(())
+        }
+    }
+
+    /// Execution plus senders for driving it. With `hang`, the backend also
+    /// holds the stream senders, so the streams stay open until killed.
+    #[allow(clippy::type_complexity)]
+    fn killable_execution(
+        hang: bool,
+    ) -> (
+        Execution,
+        tokio_mpsc::UnboundedSender<String>,
+        tokio_mpsc::UnboundedSender<String>,
+        tokio_mpsc::UnboundedSender<ExecResult>,
+    ) {
+        let (stdout_tx, stdout_rx) = tokio_mpsc::unbounded_channel();
+        let (stderr_tx, stderr_rx) = tokio_mpsc::unbounded_channel();
+        let (result_tx, result_rx) = tokio_mpsc::unbounded_channel();
+        let backend = KillingExecBackend {
+            streams: hang.then(|| (stdout_tx.clone(), stderr_tx.clone())),
+            result_tx: result_tx.clone(),
+        };
+        let exec = Execution::new(
+            "exec-run".into(),
+            Box::new(backend),
+            result_rx,
+            None,
+            Some(ExecStdout::new(stdout_rx)),
+            Some(ExecStderr::new(stderr_rx)),
+        );
+        (exec, stdout_tx, stderr_tx, result_tx)
+    }
+
+    #[tokio::test]
+    async fn collect_output_captures_both_streams_and_exit_code() {
+        let (exec, stdout_tx, stderr_tx, result_tx) = killable_execution(false);
+        let big = "x".repeat(64 * 1024);
+        for _ in 0..3 {
+            stdout_tx.send(big.clone()).unwrap();
+        }
+        stderr_tx.send("oops\n".into()).unwrap();
+        drop((stdout_tx, stderr_tx));
+        result_tx
+            .send(ExecResult {
+                exit_code: 3,
+                error_message: None,
+                timed_out: false,
+                duration_ms: 0,
+                oom_killed: false,
+                signal_name: None,
+            })
+            .unwrap();
+
+        let output = exec
+            .collect_output(Some(std::time::Duration::from_secs(5)))
+            .await
+            .unwrap();
+
+        assert_eq!(output.exit_code, 3);
+        assert!(!output.timed_out);
+        assert_eq!(output.stdout.len(), 3 * 64 * 1024);
+        assert_eq!(output.stderr, b"oops\n");
+        assert!(!output.success());
+    }
+
+    #[tokio::test]
+    async fn collect_output_kills_on_timeout_and_keeps_partial_output() {
+        let (exec, stdout_tx, stderr_tx, _result_tx) = killable_execution(true);
+        stdout_tx.send("partial".into()).unwrap();
+        drop((stdout_tx, stderr_tx));
+
+        let output = exec
+            .collect_output(Some(std::time::Duration::from_millis(50)))
+            .await
+            .unwrap();
+
+        assert!(output.timed_out);
+        assert_eq!(output.exit_code, -9);
+        assert_eq!(output.stdout, b"partial");
+        assert!(output.duration_ms >= 50);
+    }
+
+    #[tokio::test]
+    async fn wait_reports_guest_timeout_as_timeout_error() {
+        let (exec, _stdout_tx, _stderr_tx, result_tx) = killable_execution(false);
+        result_tx
+            .send(ExecResult {
+                exit_code: -15,
+                error_message: None,
+                timed_out: true,
+                duration_ms: 0,
+                oom_killed: false,
+                signal_name: None,
+            })
+            .unwrap();
+
+        let err = exec.wait().await.unwrap_err();
+        assert!(matches!(err, BoxliteError::Timeout(_)), "{err:?}");
+        // The cached result keeps reporting the timeout.
+        assert!(matches!(exec.wait().await, Err(BoxliteError::Timeout(_))));
+        let status = exec.wait_status().await.unwrap();
+        assert!(status.timed_out);
+        assert_eq!(status.exit_code, -15);
+    }
+
+    // ─── terminal resize forwarding ───────────────────────────────────
+
+    /// Backend that reports every PTY resize.
+    struct ResizeRecordingBackend {
+        resizes: tokio_mpsc::UnboundedSender<(u32, u32)>,
+    }
+
+    #[async_trait]
+    impl ExecBackend for ResizeRecordingBackend {
+        async fn signal(&mut self, _execution_id: &str, _signal: i32) -> BoxliteResult<()> {
+            Ok(())
+        }
+
+        async fn resize_tty(
+            &mut self,
+            _execution_id: &str,
+            rows: u32,
+            cols: u32,
+            _x_pixels: u32,
+            _y_pixels: u32,
+        ) -> BoxliteResult<()> {
+            let _ = self.resizes.send((rows, cols));
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn sigwinch_resizes_tty_to_new_terminal_size() {
+        use std::sync::atomic::AtomicU32;
+        use std::time::Duration;
+
+        let (resizes_tx, mut resizes) = tokio_mpsc::unbounded_channel();
+        let (_result_tx, result_rx) = tokio_mpsc::unbounded_channel::<ExecResult>();
+        let exec = Execution::new(
+            "tty-exec".to_string(),
+            Box::new(ResizeRecordingBackend {
+                resizes: resizes_tx,
+            }),
+            result_rx,
+            None,
+            None,
+            None,
+        );
+
+        let size = StdArc::new((AtomicU32::new(24), AtomicU32::new(80)));
+        let follower = {
+            let size = size.clone();
+            exec.follow_window_changes(move || {
+                (
+                    size.0.load(AtomicOrdering::SeqCst),
+                    size.1.load(AtomicOrdering::SeqCst),
+                )
+            })
+            .unwrap()
+        };
+        let next = |resizes: &mut tokio_mpsc::UnboundedReceiver<(u32, u32)>| {
+            tokio::time::timeout(Duration::from_secs(5), resizes.recv())
+        };
+
+        // The current size is sent before any signal arrives.
+        assert_eq!(next(&mut resizes).await.unwrap(), Some((24, 80)));
+
+        size.0.store(50, AtomicOrdering::SeqCst);
+        size.1.store(132, AtomicOrdering::SeqCst);
+        assert_eq!(unsafe { libc::raise(libc::SIGWINCH) }, 0);
+        assert_eq!(next(&mut resizes).await.unwrap(), Some((50, 132)));
+
+        // Dropping the guard stops forwarding: the task ends and releases
+        // its handle on the backend, closing the channel.
+        drop(follower);
+        drop(exec);
+        assert_eq!(next(&mut resizes).await.unwrap(), None);
+    }
 }
@@ -0,0 +1,83 @@
+//! Guest agent log streams.
+//!
+//! `LiteBox::guest_log_stream()` subscribes to the guest's Guest.StreamLogs
+//! RPC, which forwards the agent's own tracing events as they are emitted.
+//! This is the agent's log, not the VM console (kernel and init messages).
+
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use tracing::Level;
+
+/// One tracing event emitted by the guest agent.
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    /// When the guest emitted the event, on the guest clock.
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    /// Event target (usually the emitting module path).
+    pub target: String,
+    /// The event's formatted message.
+    pub message: String,
+    /// Remaining event fields, formatted with `Debug`.
+    pub fields: BTreeMap<String, String>,
+}
+
+impl From<boxlite_shared::GuestLogEvent> for LogLine {
+    fn from(event: boxlite_shared::GuestLogEvent) -> Self {
+        Self {
+            timestamp: DateTime::from_timestamp_millis(event.timestamp_ms).unwrap_or_default(),
+            level: event.level.parse().unwrap_or(Level::INFO),
+            target: event.target,
+            message: event.message,
+            fields: event.fields.into_iter().collect(),
+        }
+    }
+}
+
+/// Stream of guest agent log lines. Ends when the box stops; dropping it
+/// unsubscribes.
+pub struct GuestLogStream {
+    inner: tonic::Streaming<boxlite_shared::GuestLogEvent>,
+}
+
+impl GuestLogStream {
+    pub(crate) fn new(inner: tonic::Streaming<boxlite_shared::GuestLogEvent>) -> Self {
+        Self { inner }
+    }
+}
+
+impl Stream for GuestLogStream {
+    type Item = LogLine;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // A transport error ends the stream like a clean close.
+        match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => Poll::Ready(Some(event.into())),
+            Poll::Ready(Some(Err(_)) | None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn log_line_from_guest_event() {
+        let line = LogLine::from(boxlite_shared::GuestLogEvent {
+            timestamp_ms: 1_700_000_000_123,
+            level: "WARN".to_string(),
+            target: "boxlite_guest::service".to_string(),
+            message: "attach request".to_string(),
+            fields: [("execution_id".to_string(), "e1".to_string())].into(),
+        });
+        assert_eq!(line.level, Level::WARN);
+        assert_eq!(line.timestamp.timestamp_millis(), 1_700_000_000_123);
+        assert_eq!(line.fields["execution_id"], "e1");
+    }
+}
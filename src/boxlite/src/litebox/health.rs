@@ -0,0 +1,153 @@
+//! On-demand readiness probes for a box's workload.
+//!
+//! Unlike the background health check (`HealthCheckOptions`), which only
+//! pings the guest agent, a probe checks the service inside the box: an exec
+//! probe runs a command, a TCP probe connects through the box's port forward.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+use super::{BoxCommand, BoxStatus, HealthState, LiteBox};
+use crate::net::Forward;
+
+/// What [`LiteBox::healthcheck`] checks.
+#[derive(Clone, Debug)]
+pub enum HealthProbe {
+    /// Run `cmd` until it exits 0, at most `retries` times (at least once),
+    /// waiting `interval` between attempts.
+    Exec {
+        cmd: BoxCommand,
+        interval: Duration,
+        retries: u32,
+    },
+    /// Open a TCP connection to guest `port` through its host port forward
+    /// within `timeout`. The port must be published (`BoxOptions::ports` or
+    /// `LiteBox::expose_port`).
+    TcpPort { port: u16, timeout: Duration },
+}
+
+impl LiteBox {
+    /// Probe whether the workload in the box is ready.
+    ///
+    /// Returns `Starting` for a box that has not booted yet (the probe does
+    /// not start it) and `Unhealthy` for one that is stopped or failed.
+    /// Otherwise the probe decides between `Healthy` and `Unhealthy`.
+    pub async fn healthcheck(&self, probe: HealthProbe) -> BoxliteResult<HealthState> {
+        match self.info().status {
+            BoxStatus::Running => {}
+            BoxStatus::Configured => return Ok(HealthState::Starting),
+            _ => return Ok(HealthState::Unhealthy),
+        }
+
+        let healthy = match probe {
+            HealthProbe::Exec {
+                cmd,
+                interval,
+                retries,
+            } => self.probe_exec(cmd, interval, retries).await,
+            HealthProbe::TcpPort { port, timeout } => self.probe_tcp(port, timeout).await?,
+        };
+        Ok(if healthy {
+            HealthState::Healthy
+        } else {
+            HealthState::Unhealthy
+        })
+    }
+
+    async fn probe_exec(&self, cmd: BoxCommand, interval: Duration, retries: u32) -> bool {
+        let attempts = retries.max(1);
+        for attempt in 1..=attempts {
+            match self.run(cmd.clone()).await {
+                Ok(output) if output.exit_code == 0 => return true,
+                Ok(output) => tracing::debug!(
+                    box_id = %self.id(),
+                    attempt,
+                    exit_code = output.exit_code,
+                    "exec probe failed"
+                ),
+                Err(e) => tracing::debug!(
+                    box_id = %self.id(),
+                    attempt,
+                    error = %e,
+                    "exec probe could not run"
+                ),
+            }
+            if attempt < attempts {
+                tokio::time::sleep(interval).await;
+            }
+        }
+        false
+    }
+
+    async fn probe_tcp(&self, port: u16, timeout: Duration) -> BoxliteResult<bool> {
+        let forwards = self.network().port_forwards().await?;
+        let addr = forwarded_host_addr(&forwards, port).ok_or_else(|| {
+            BoxliteError::InvalidArgument(format!("guest port {port} is not forwarded to the host"))
+        })?;
+        let connected = matches!(
+            tokio::time::timeout(timeout, tokio::net::TcpStream::connect(addr)).await,
+            Ok(Ok(_))
+        );
+        if !connected {
+            tracing::debug!(box_id = %self.id(), port, %addr, "tcp probe failed");
+        }
+        Ok(connected)
+    }
+}
+
+/// Host address to dial for the TCP forward targeting guest `port`.
+///
+/// Wildcard binds are dialled on loopback.
+fn forwarded_host_addr(forwards: &[Forward], port: u16) -> Option<SocketAddr> {
+    let forward = forwards.iter().find(|f| {
+        (f.protocol.is_empty() || f.protocol == "tcp")
+            && f.remote
+                .rsplit_once(':')
+                .and_then(|(_, p)| p.parse::<u16>().ok())
+                == Some(port)
+    })?;
+    let mut addr: SocketAddr = forward.local.parse().ok()?;
+    if addr.ip().is_unspecified() {
+        addr.set_ip(if addr.is_ipv4() {
+            std::net::Ipv4Addr::LOCALHOST.into()
+        } else {
+            std::net::Ipv6Addr::LOCALHOST.into()
+        });
+    }
+    Some(addr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn forward(local: &str, remote: &str, protocol: &str) -> Forward {
+        Forward {
+            local: local.to_string(),
+            remote: remote.to_string(),
+            protocol: protocol.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_forwarded_host_addr() {
+        let forwards = vec![
+            forward("0.0.0.0:18080", "192.168.127.2:80", "tcp"),
+            forward("0.0.0.0:15353", "192.168.127.2:53", "udp"),
+            forward("[::]:18443", "192.168.127.2:443", ""),
+        ];
+
+        assert_eq!(
+            forwarded_host_addr(&forwards, 80),
+            Some("127.0.0.1:18080".parse().unwrap())
+        );
+        assert_eq!(
+            forwarded_host_addr(&forwards, 443),
+            Some("[::1]:18443".parse().unwrap())
+        );
+        assert_eq!(forwarded_host_addr(&forwards, 53), None);
+        assert_eq!(forwarded_host_addr(&forwards, 22), None);
+    }
+}
@@ -224,6 +224,18 @@ impl BoxBuilder {
 
             let mut metrics = box_metrics_from_pipeline(&pipeline_metrics);
             metrics.set_total_create_duration(total_create_duration_ms);
+            if let Some(duration_ms) = ctx.guest_boot_duration_ms {
+                metrics.set_guest_boot_duration(duration_ms);
+            }
+            if let Some(timing) = ctx.guest_boot_timing.take() {
+                tracing::debug!(
+                    box_id = %ctx.config.id,
+                    kernel_uptime_ms = timing.kernel_uptime_ms,
+                    agent_ready_ms = timing.agent_ready_ms,
+                    "Guest reported boot timing"
+                );
+                metrics.set_guest_boot_breakdown(u128::from(timing.agent_ready_ms));
+            }
 
             metrics.log_init_stages();
 
@@ -270,12 +282,14 @@ impl BoxBuilder {
             // vmm_spawn (which also used it to produce the wire spec) or, on the
             // reattach path, by vmm_attach; both thread it here for runtime control.
             let network = ctx.network_backend.take();
+            let published_ports = ctx.published_ports.take();
 
             // Build LiveState
             let live_state = LiveState::new(
                 handler,
                 guest_session,
                 network,
+                published_ports,
                 metrics,
                 container_disk,
                 guest_disk,
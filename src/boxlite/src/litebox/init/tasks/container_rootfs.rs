@@ -12,8 +12,9 @@ use crate::images::{ContainerImageConfig, ImageDiskManager};
 use crate::litebox::init::types::{ContainerRootfsPrepResult, USE_DISK_ROOTFS, USE_OVERLAYFS};
 use crate::pipeline::PipelineTask;
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::RootfsSpec;
+use crate::runtime::options::{Platform, PullPolicy, RootfsSpec};
 use crate::runtime::rt_impl::SharedRuntimeImpl;
+use crate::runtime::types::{PullPhase, PullProgress, PullProgressCallback};
 use async_trait::async_trait;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
@@ -27,6 +28,8 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
 
         let (
             rootfs_spec,
+            platform,
+            pull_policy,
             env,
             runtime,
             layout,
@@ -35,6 +38,8 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
             entrypoint_override,
             cmd_override,
             user_override,
+            working_dir_override,
+            pull_progress,
         ) = {
             let ctx = ctx.lock().await;
             let layout = ctx
@@ -48,6 +53,8 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
 
             (
                 ctx.config.options.rootfs.clone(),
+                ctx.config.options.platform.clone(),
+                ctx.config.options.pull_policy,
                 env,
                 ctx.runtime.clone(),
                 layout,
@@ -56,11 +63,15 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
                 ctx.config.options.entrypoint.clone(),
                 ctx.config.options.cmd.clone(),
                 ctx.config.options.user.clone(),
+                ctx.config.options.working_dir.clone(),
+                ctx.config.options.pull_progress.clone(),
             )
         };
 
         let (container_image_config, disk) = run_container_rootfs(
             &rootfs_spec,
+            platform.as_ref(),
+            pull_policy,
             &env,
             &runtime,
             &layout,
@@ -69,6 +80,8 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
             entrypoint_override.as_deref(),
             cmd_override.as_deref(),
             user_override.as_deref(),
+            working_dir_override.as_deref(),
+            pull_progress.as_ref(),
         )
         .await
         .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
@@ -89,6 +102,8 @@ impl PipelineTask<InitCtx> for ContainerRootfsTask {
 #[allow(clippy::too_many_arguments)]
 async fn run_container_rootfs(
     rootfs_spec: &RootfsSpec,
+    platform: Option<&Platform>,
+    pull_policy: PullPolicy,
     env: &[(String, String)],
     runtime: &SharedRuntimeImpl,
     layout: &BoxFilesystemLayout,
@@ -97,6 +112,8 @@ async fn run_container_rootfs(
     entrypoint_override: Option<&[String]>,
     cmd_override: Option<&[String]>,
     user_override: Option<&str>,
+    working_dir_override: Option<&str>,
+    pull_progress: Option<&PullProgressCallback>,
 ) -> BoxliteResult<(ContainerImageConfig, Disk)> {
     let disk_path = layout.disk_path();
 
@@ -116,24 +133,18 @@ async fn run_container_rootfs(
 
         let disk = Disk::new(disk_path.clone(), DiskFormat::Qcow2, true);
 
-        // Load container config
+        // Load container config. The disk was built from the cached image,
+        // so `Always` must not swap in a newer one.
+        let policy = match pull_policy {
+            PullPolicy::Always => PullPolicy::IfNotPresent,
+            policy => policy,
+        };
         let image = match rootfs_spec {
-            RootfsSpec::Image(r) => pull_image(runtime, r).await?,
+            RootfsSpec::Image(r) => pull_image(runtime, r, platform, policy, None).await?,
             RootfsSpec::RootfsPath(path) => {
-                let bundle_dir = std::path::Path::new(path);
-
-                if !bundle_dir.exists() {
-                    return Err(BoxliteError::Storage(format!(
-                        "Rootfs path does not exist: {}",
-                        path
-                    )));
-                }
-
-                runtime
-                    .image_manager
-                    .load_from_local(bundle_dir.to_path_buf(), format!("local:{}", path))
-                    .await?
+                load_local_bundle(runtime, std::path::Path::new(path)).await?
             }
+            RootfsSpec::OciBundle(path) => load_local_bundle(runtime, path).await?,
         };
         let image_config = image.load_config().await?;
         let mut container_image_config = ContainerImageConfig::from_oci_config(&image_config)?;
@@ -145,6 +156,7 @@ async fn run_container_rootfs(
             entrypoint_override,
             cmd_override,
             user_override,
+            working_dir_override,
         );
 
         return Ok((container_image_config, disk));
@@ -152,25 +164,20 @@ async fn run_container_rootfs(
 
     // Fresh start: pull or load image
     let image = match rootfs_spec {
-        RootfsSpec::Image(r) => pull_image(runtime, r).await?,
+        RootfsSpec::Image(r) => {
+            pull_image(runtime, r, platform, pull_policy, pull_progress).await?
+        }
         RootfsSpec::RootfsPath(path) => {
-            let bundle_dir = std::path::Path::new(path);
-
-            if !bundle_dir.exists() {
-                return Err(BoxliteError::Storage(format!(
-                    "Rootfs path does not exist: {}",
-                    path
-                )));
-            }
-
-            runtime
-                .image_manager
-                .load_from_local(bundle_dir.to_path_buf(), format!("local:{}", path))
-                .await?
+            load_local_bundle(runtime, std::path::Path::new(path)).await?
         }
+        RootfsSpec::OciBundle(path) => load_local_bundle(runtime, path).await?,
     };
 
     // Prepare rootfs from image
+    let pull_progress = pull_progress.filter(|_| matches!(rootfs_spec, RootfsSpec::Image(_)));
+    if let Some(progress) = pull_progress {
+        progress.report(PullProgress::image(PullPhase::Extracting));
+    }
     let rootfs_result = if USE_DISK_ROOTFS {
         prepare_disk_rootfs(&runtime.image_disk_mgr, &image).await?
     } else if USE_OVERLAYFS {
@@ -180,6 +187,9 @@ async fn run_container_rootfs(
             "Merged rootfs not supported. Use overlayfs or disk rootfs.".into(),
         ));
     };
+    if let Some(progress) = pull_progress {
+        progress.report(PullProgress::image(PullPhase::Done));
+    }
 
     let image_config = image.load_config().await?;
     let mut container_image_config = ContainerImageConfig::from_oci_config(&image_config)?;
@@ -192,6 +202,7 @@ async fn run_container_rootfs(
         entrypoint_override,
         cmd_override,
         user_override,
+        working_dir_override,
     );
 
     let disk = create_cow_disk(&rootfs_result, layout, disk_size_gb)?;
@@ -256,15 +267,23 @@ fn create_cow_disk(
     }
 }
 
-/// Apply user overrides to container image config (entrypoint, CMD, and user).
+/// Apply user overrides to container image config (entrypoint, CMD, user,
+/// and working directory).
+///
+/// Follows Docker: overriding the entrypoint also drops the image's CMD,
+/// which was written as arguments for the image's own entrypoint.
 fn apply_user_overrides(
     config: &mut ContainerImageConfig,
     entrypoint_override: Option<&[String]>,
     cmd_override: Option<&[String]>,
     user_override: Option<&str>,
+    working_dir_override: Option<&str>,
 ) {
     if let Some(ep) = entrypoint_override {
         config.entrypoint = ep.to_vec();
+        if cmd_override.is_none() {
+            config.cmd.clear();
+        }
     }
     if let Some(cmd) = cmd_override {
         config.cmd = cmd.to_vec();
@@ -272,14 +291,43 @@ fn apply_user_overrides(
     if let Some(user) = user_override {
         config.user = user.to_string();
     }
+    if let Some(dir) = working_dir_override {
+        config.working_dir = dir.to_string();
+    }
 }
 
 async fn pull_image(
     runtime: &crate::runtime::SharedRuntimeImpl,
     image_ref: &str,
+    platform: Option<&Platform>,
+    policy: PullPolicy,
+    progress: Option<&PullProgressCallback>,
 ) -> BoxliteResult<crate::images::ImageObject> {
-    // ImageManager has internal locking - direct access
-    runtime.image_manager.pull(image_ref).await
+    // Goes through the runtime so the image store cap is enforced
+    runtime
+        .pull_image_reporting(image_ref, platform, policy, progress)
+        .await
+}
+
+/// Load the image from a local OCI layout, reading its blobs in place.
+async fn load_local_bundle(
+    runtime: &crate::runtime::SharedRuntimeImpl,
+    bundle_dir: &std::path::Path,
+) -> BoxliteResult<crate::images::ImageObject> {
+    if !bundle_dir.exists() {
+        return Err(BoxliteError::Storage(format!(
+            "Rootfs path does not exist: {}",
+            bundle_dir.display()
+        )));
+    }
+
+    runtime
+        .image_manager
+        .load_from_local(
+            bundle_dir.to_path_buf(),
+            format!("local:{}", bundle_dir.display()),
+        )
+        .await
 }
 
 async fn prepare_overlayfs_layers(
@@ -342,3 +390,62 @@ async fn prepare_disk_rootfs(
         disk_size,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image_config() -> ContainerImageConfig {
+        ContainerImageConfig {
+            entrypoint: vec!["docker-entrypoint.sh".to_string()],
+            cmd: vec!["postgres".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn resolve(entrypoint: Option<&[&str]>, cmd: Option<&[&str]>) -> Vec<String> {
+        let to_vec = |args: &[&str]| args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let entrypoint = entrypoint.map(to_vec);
+        let cmd = cmd.map(to_vec);
+        let mut config = image_config();
+        apply_user_overrides(
+            &mut config,
+            entrypoint.as_deref(),
+            cmd.as_deref(),
+            None,
+            None,
+        );
+        config.final_cmd()
+    }
+
+    #[test]
+    fn image_entrypoint_and_cmd_without_overrides() {
+        assert_eq!(resolve(None, None), ["docker-entrypoint.sh", "postgres"]);
+    }
+
+    #[test]
+    fn cmd_override_keeps_image_entrypoint() {
+        assert_eq!(
+            resolve(None, Some(&["postgres", "-c", "fsync=off"])),
+            ["docker-entrypoint.sh", "postgres", "-c", "fsync=off"]
+        );
+    }
+
+    #[test]
+    fn entrypoint_override_drops_image_cmd() {
+        assert_eq!(resolve(Some(&["sleep"]), None), ["sleep"]);
+    }
+
+    #[test]
+    fn override_both() {
+        assert_eq!(
+            resolve(Some(&["sleep"]), Some(&["infinity"])),
+            ["sleep", "infinity"]
+        );
+    }
+
+    #[test]
+    fn empty_cmd_override_clears_image_cmd() {
+        assert_eq!(resolve(None, Some(&[])), ["docker-entrypoint.sh"]);
+    }
+}
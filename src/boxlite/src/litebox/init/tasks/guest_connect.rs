@@ -6,7 +6,7 @@
 //! IMPORTANT: Must wait for guest to be ready before creating session.
 //! Races guest readiness against shim process death for fast failure detection.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, boot_time_left, log_task_error, task_start};
 use crate::litebox::CrashReport;
 use crate::pipeline::PipelineTask;
 use crate::portal::GuestSession;
@@ -34,6 +34,9 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
             exit_file,
             console_log,
             stderr_file,
+            vmm_spawned_at,
+            guest_connections,
+            boot_timeout,
         ) = {
             let ctx = ctx.lock().await;
             // Use pipeline layout if available, otherwise construct from box_home
@@ -65,11 +68,15 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
                 exit_file,
                 console_log,
                 stderr_file,
+                ctx.vmm_spawned_at,
+                ctx.runtime.runtime_metrics.guest_connections.clone(),
+                ctx.config.options.boot_timeout(),
             )
         };
 
         // Wait for guest to be ready before creating session
         // Skip for reattach (Running status) - guest already signaled ready at boot
+        let mut guest_boot_duration_ms = None;
         if skip_guest_wait {
             tracing::debug!(box_id = %box_id, "Skipping guest ready wait (reattach)");
         } else {
@@ -81,17 +88,19 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
                 &console_log,
                 &stderr_file,
                 box_id.as_str(),
-                GUEST_READY_TIMEOUT,
+                boot_time_left(vmm_spawned_at, boot_timeout),
             )
             .await
             .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+            guest_boot_duration_ms = vmm_spawned_at.map(|t| t.elapsed().as_millis());
         }
 
         tracing::debug!(box_id = %box_id, "Guest is ready, creating session");
-        let guest_session = GuestSession::new(transport);
+        let guest_session = GuestSession::new(transport).with_connect_counter(guest_connections);
 
         let mut ctx = ctx.lock().await;
         ctx.guest_session = Some(guest_session);
+        ctx.guest_boot_duration_ms = guest_boot_duration_ms;
 
         Ok(())
     }
@@ -101,13 +110,6 @@ impl PipelineTask<InitCtx> for GuestConnectTask {
     }
 }
 
-/// Production timeout for the guest-ready handshake.
-///
-/// Exposed as a constant so tests can call `wait_for_guest_ready` with a
-/// short timeout and exercise the real timeout branch (including its
-/// diagnostic-collection logic) without waiting 30s.
-const GUEST_READY_TIMEOUT: Duration = Duration::from_secs(30);
-
 /// Wait for guest to signal readiness, racing against shim process death.
 ///
 /// Uses `tokio::select!` to detect three conditions:
@@ -189,8 +191,8 @@ async fn wait_for_guest_ready(
                         (true, true) => "guest booted but agent did not connect to vsock READY port",
                         (false, _) => "shim died silently (see stderr / exit file)",
                     };
-                    Err(BoxliteError::Engine(format!(
-                        "Box {box_id} failed to start: timeout after {}s\n\n\
+                    Err(BoxliteError::Timeout(format!(
+                        "Box {box_id} failed to start: boot timed out after {}s\n\n\
                          Evidence at T+{}s:\n\
                          • shim_alive          = {}\n\
                          • console_bytes       = {}\n\
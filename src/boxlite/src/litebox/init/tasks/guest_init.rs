@@ -3,17 +3,19 @@
 //! Sends init configuration to guest and starts container.
 //! Builds guest volumes from volume manager, uses rootfs config from vmm_config stage.
 
-use super::{InitCtx, log_task_error, task_start};
+use super::{InitCtx, boot_time_left, log_task_error, task_start};
 use crate::images::ContainerImageConfig;
-use crate::net::constants::{GATEWAY_IP, GUEST_CIDR, GUEST_INTERFACE};
+use crate::net::constants::{GATEWAY_IP, GUEST_CIDR, GUEST_INTERFACE, HOST_HOSTNAME, HOST_IP};
 use crate::pipeline::PipelineTask;
 use crate::portal::GuestSession;
 use crate::portal::interfaces::{ContainerRootfsInitConfig, GuestInitConfig, NetworkInitConfig};
-use crate::runtime::options::NetworkSpec;
+use crate::runtime::options::{DnsConfig, NetworkSpec, UserNsMapping};
 use crate::runtime::types::ContainerID;
 use crate::volumes::{ContainerMount, GuestVolumeManager};
 use async_trait::async_trait;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use boxlite_shared::{DnsConfig as ProtoDnsConfig, GuestBootTiming};
+use std::net::IpAddr;
 
 pub struct GuestInitTask;
 
@@ -32,6 +34,14 @@ impl PipelineTask<InitCtx> for GuestInitTask {
             container_mounts,
             network_spec,
             ca_cert_pem,
+            userns_mapping,
+            kernel_modules,
+            dns,
+            extra_hosts,
+            read_only_rootfs,
+            capabilities,
+            vmm_spawned_at,
+            boot_timeout,
         ) =
             {
                 let mut ctx = ctx.lock().await;
@@ -54,6 +64,10 @@ impl PipelineTask<InitCtx> for GuestInitTask {
                 })?;
                 let network_spec = ctx.config.options.network.clone();
                 let ca_cert_pem = ctx.ca_cert_pem.clone();
+                let userns_mapping = ctx.config.options.userns_mapping.clone();
+                let kernel_modules = ctx.config.options.kernel_modules.clone();
+                let dns = ctx.config.options.dns.clone();
+                let extra_hosts = ctx.config.options.extra_hosts.clone();
                 (
                     guest_session,
                     container_image_config,
@@ -63,10 +77,18 @@ impl PipelineTask<InitCtx> for GuestInitTask {
                     container_mounts,
                     network_spec,
                     ca_cert_pem,
+                    userns_mapping,
+                    kernel_modules,
+                    dns,
+                    extra_hosts,
+                    ctx.config.options.read_only_rootfs,
+                    ctx.config.options.capability_changes()?,
+                    ctx.vmm_spawned_at,
+                    ctx.config.options.boot_timeout(),
                 )
             };
 
-        run_guest_init(
+        let init = run_guest_init(
             guest_session.clone(),
             &container_image_config,
             &container_id,
@@ -75,15 +97,31 @@ impl PipelineTask<InitCtx> for GuestInitTask {
             &container_mounts,
             &network_spec,
             ca_cert_pem.as_deref(),
-        )
-        .await
-        .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
+            userns_mapping,
+            kernel_modules,
+            &dns,
+            &extra_hosts,
+            read_only_rootfs,
+            capabilities,
+        );
+        // The boot budget covers guest init too; a wedged guest must not
+        // hang start. The cleanup guard tears the VM down on error.
+        let boot_timing = tokio::time::timeout(boot_time_left(vmm_spawned_at, boot_timeout), init)
+            .await
+            .unwrap_or_else(|_| {
+                Err(BoxliteError::Timeout(format!(
+                    "Box {box_id} failed to start: boot timed out after {}s during guest init",
+                    boot_timeout.as_secs()
+                )))
+            })
+            .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
 
         let mut ctx = ctx.lock().await;
         ctx.guest_session = Some(guest_session);
         ctx.volume_mgr = Some(volume_mgr);
         ctx.rootfs_init = Some(rootfs_init);
         ctx.container_mounts = Some(container_mounts);
+        ctx.guest_boot_timing = boot_timing;
 
         Ok(())
     }
@@ -94,6 +132,8 @@ impl PipelineTask<InitCtx> for GuestInitTask {
 }
 
 /// Initialize guest and start container.
+///
+/// Returns the boot timing reported by the guest agent in its Guest.Init response.
 #[allow(clippy::too_many_arguments)]
 async fn run_guest_init(
     guest_session: GuestSession,
@@ -104,7 +144,13 @@ async fn run_guest_init(
     container_mounts: &[ContainerMount],
     network_spec: &NetworkSpec,
     ca_cert_pem: Option<&str>,
-) -> BoxliteResult<()> {
+    userns_mapping: Option<UserNsMapping>,
+    kernel_modules: Vec<String>,
+    dns: &DnsConfig,
+    extra_hosts: &[(String, IpAddr)],
+    read_only_rootfs: bool,
+    (cap_add, cap_drop): (Vec<String>, Vec<String>),
+) -> BoxliteResult<Option<GuestBootTiming>> {
     let container_id_str = container_id.as_str();
 
     // Build guest volumes from volume manager
@@ -122,12 +168,13 @@ async fn run_guest_init(
     let guest_init_config = GuestInitConfig {
         volumes: guest_volumes,
         network,
+        kernel_modules,
     };
 
-    // Step 1: Guest Init (volumes + network)
+    // Step 1: Guest Init (kernel modules + volumes + network)
     tracing::info!("Sending guest initialization request");
     let mut guest_interface = guest_session.guest().await?;
-    guest_interface.init(guest_init_config).await?;
+    let boot_timing = guest_interface.init(guest_init_config).await?;
     tracing::info!("Guest initialized successfully");
 
     // Step 2: Container Init (rootfs + container image config + user volume mounts)
@@ -141,9 +188,71 @@ async fn run_guest_init(
             rootfs_init.clone(),
             container_mounts.to_vec(),
             ca_certs,
+            userns_mapping,
+            dns_into_proto(dns, extra_hosts, network_spec),
+            read_only_rootfs,
+            cap_add,
+            cap_drop,
         )
         .await?;
     tracing::info!(container_id = %returned_id, "Container initialized");
 
-    Ok(())
+    Ok(boot_timing)
+}
+
+/// Build the container's resolver config; `None` keeps the guest default.
+///
+/// Custom nameservers bypass gvproxy's DNS, so the built-in host alias is
+/// pinned in `/etc/hosts` to keep it resolvable. `extra_hosts` are pinned
+/// there too, and win over the alias on a name clash.
+fn dns_into_proto(
+    dns: &DnsConfig,
+    extra_hosts: &[(String, IpAddr)],
+    network_spec: &NetworkSpec,
+) -> Option<ProtoDnsConfig> {
+    if *dns == DnsConfig::default() && extra_hosts.is_empty() {
+        return None;
+    }
+    let mut hosts = std::collections::HashMap::new();
+    if !dns.nameservers.is_empty() && matches!(network_spec, NetworkSpec::Enabled { .. }) {
+        hosts.insert(HOST_HOSTNAME.to_string(), HOST_IP.to_string());
+    }
+    for (name, ip) in extra_hosts {
+        hosts.insert(name.clone(), ip.to_string());
+    }
+    Some(ProtoDnsConfig {
+        nameservers: dns.nameservers.iter().map(|ip| ip.to_string()).collect(),
+        search: dns.search.clone(),
+        options: dns.options.clone(),
+        hosts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dns_into_proto_pins_extra_hosts() {
+        let extra_hosts = vec![
+            ("db.internal".to_string(), "10.0.0.5".parse().unwrap()),
+            (
+                "host.docker.internal".to_string(),
+                crate::runtime::options::HOST_GATEWAY_IP,
+            ),
+        ];
+        let proto = dns_into_proto(&DnsConfig::default(), &extra_hosts, &NetworkSpec::default())
+            .expect("extra hosts need a DNS config");
+
+        // Default nameservers: gvproxy still resolves the host alias itself.
+        assert!(proto.nameservers.is_empty());
+        assert_eq!(proto.hosts.len(), 2);
+        assert_eq!(proto.hosts["db.internal"], "10.0.0.5");
+        assert_eq!(proto.hosts["host.docker.internal"], HOST_IP);
+    }
+
+    #[test]
+    fn dns_into_proto_defaults_to_none() {
+        assert!(dns_into_proto(&DnsConfig::default(), &[], &NetworkSpec::default()).is_none());
+    }
 }
@@ -229,8 +229,8 @@ async fn prepare_guest_rootfs(
 async fn pull_guest_rootfs_image(
     runtime: &SharedRuntimeImpl,
 ) -> BoxliteResult<crate::images::ImageObject> {
-    // ImageManager has internal locking - direct access
-    runtime.image_manager.pull(images::INIT_ROOTFS).await
+    // Goes through the runtime so the image store cap is enforced
+    runtime.pull_image(images::INIT_ROOTFS).await
 }
 
 async fn extract_env_from_image(
@@ -36,6 +36,7 @@ use super::types::InitPipelineContext;
 use crate::runtime::id::BoxID;
 use boxlite_shared::errors::BoxliteError;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 pub type InitCtx = Arc<Mutex<InitPipelineContext>>;
@@ -50,6 +51,13 @@ fn log_task_error(box_id: &BoxID, task_name: &str, err: &BoxliteError) {
     tracing::error!(box_id = %box_id, task = %task_name, "Task failed: {}", err);
 }
 
+/// What is left of the boot budget, which starts when the VM is spawned.
+fn boot_time_left(vmm_spawned_at: Option<Instant>, boot_timeout: Duration) -> Duration {
+    vmm_spawned_at.map_or(boot_timeout, |spawned| {
+        boot_timeout.saturating_sub(spawned.elapsed())
+    })
+}
+
 pub use container_rootfs::ContainerRootfsTask;
 pub use filesystem::FilesystemTask;
 pub use guest_connect::GuestConnectTask;
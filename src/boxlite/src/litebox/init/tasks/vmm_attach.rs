@@ -96,6 +96,8 @@ impl PipelineTask<InitCtx> for VmmAttachTask {
                 allow_net,
                 secrets,
                 ca_dir: layout.ca_dir(),
+                rate_limit: None,
+                guest_mac: None,
             };
             runtime.network_factory.create(&config)
         });
@@ -14,12 +14,12 @@ use crate::rootfs::guest::{GuestRootfs, Strategy};
 use crate::runtime::constants::{guest_paths, mount_tags};
 use crate::runtime::id::BoxID;
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::BoxOptions;
+use crate::runtime::options::{BoxOptions, DiskSpec};
 use crate::runtime::rt_impl::SharedRuntimeImpl;
-use crate::runtime::types::ContainerID;
+use crate::runtime::types::{ContainerID, PortMapping};
 use crate::util::find_binary;
 use crate::vmm::controller::{ShimController, VmmController, VmmHandler};
-use crate::vmm::{Entrypoint, InstanceSpec, VmmKind};
+use crate::vmm::{Entrypoint, InstanceSpec, VmmKind, VsockPort};
 use crate::volumes::{
     ContainerMount, ContainerVolumeManager, GuestVolumeManager, stage_single_file,
 };
@@ -93,12 +93,14 @@ impl PipelineTask<InitCtx> for VmmSpawnTask {
             .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
 
         // Spawn VM
+        let spawned_at = std::time::Instant::now();
         let handler = spawn_vm(&box_id, &instance_spec, &options, &layout)
             .await
             .inspect_err(|e| log_task_error(&box_id, task_name, e))?;
 
         let mut ctx = ctx.lock().await;
         ctx.guard.set_handler(handler);
+        ctx.vmm_spawned_at = Some(spawned_at);
         ctx.volume_mgr = Some(volume_mgr);
         ctx.rootfs_init = Some(rootfs_init);
         ctx.container_mounts = Some(container_mounts);
@@ -109,6 +111,7 @@ impl PipelineTask<InitCtx> for VmmSpawnTask {
             .network_backend_spec
             .as_ref()
             .and_then(|s| s.ca_cert_pem.clone());
+        ctx.published_ports = Some(published_ports(&instance_spec));
         Ok(())
     }
 
@@ -117,6 +120,21 @@ impl PipelineTask<InitCtx> for VmmSpawnTask {
     }
 }
 
+/// Port mappings reported by `BoxInfo`, sorted by host port.
+fn published_ports(instance_spec: &InstanceSpec) -> Vec<PortMapping> {
+    let mut ports: Vec<PortMapping> = instance_spec
+        .network_backend_spec
+        .iter()
+        .flat_map(|spec| &spec.port_mappings)
+        .map(|&(host_port, guest_port)| PortMapping {
+            host_port,
+            guest_port,
+        })
+        .collect();
+    ports.sort_by_key(|p| (p.host_port, p.guest_port));
+    ports
+}
+
 /// Build VMM config from prepared rootfs outputs.
 #[allow(clippy::too_many_arguments)]
 async fn build_config(
@@ -170,13 +188,6 @@ async fn build_config(
         need_resize, // need_resize: only on fresh start with custom disk size
     );
 
-    // Update rootfs_init with actual device path and resize flag
-    let rootfs_init = crate::portal::interfaces::ContainerRootfsInitConfig::DiskImage {
-        device: rootfs_device,
-        need_format: false, // COW child uses pre-formatted base
-        need_resize,        // Only on fresh start with custom disk size
-    };
-
     // Add user volumes via ContainerVolumeManager
     let mut container_mgr = ContainerVolumeManager::new(&mut volume_mgr);
     for vol in &user_volumes {
@@ -203,6 +214,15 @@ async fn build_config(
             vol.subpath.clone(),
         );
     }
+    for (index, tmpfs) in options.tmpfs.iter().enumerate() {
+        container_mgr.add_tmpfs(
+            container_id.as_str(),
+            &format!("tmpfs{index}"),
+            &tmpfs.guest_path,
+            tmpfs.size_mib,
+            tmpfs.mode.unwrap_or(0o1777),
+        );
+    }
     let container_mounts = container_mgr.build_container_mounts();
 
     // Get guest rootfs from runtime cache and configure with disk
@@ -214,6 +234,28 @@ async fn build_config(
 
     let guest_rootfs = configure_guest_rootfs(guest_rootfs, guest_disk_path, &mut volume_mgr)?;
 
+    // User-declared disks go last so the box's own disks keep their device names.
+    let data_devices = attach_user_disks(&options.disks, &mut volume_mgr)?;
+    let overlay_upper_device = options
+        .overlay_upper_disk
+        .as_ref()
+        .map(|id| {
+            data_devices.get(id).cloned().ok_or_else(|| {
+                BoxliteError::Config(format!(
+                    "overlay_upper_disk {id:?} does not match any declared disk"
+                ))
+            })
+        })
+        .transpose()?;
+
+    // Update rootfs_init with actual device path and resize flag
+    let rootfs_init = crate::portal::interfaces::ContainerRootfsInitConfig::DiskImage {
+        device: rootfs_device,
+        need_format: false, // COW child uses pre-formatted base
+        need_resize,        // Only on fresh start with custom disk size
+        overlay_upper_device,
+    };
+
     // Build VMM config from volume manager
     let vmm_config = volume_mgr.build_vmm_config();
 
@@ -223,7 +265,7 @@ async fn build_config(
 
     // The box's one network backend: it produces the wire spec now, and is
     // threaded on to LiveState (via the init ctx) for runtime control.
-    let network_backend = build_network_backend(container_image_config, options, layout, runtime);
+    let network_backend = build_network_backend(container_image_config, options, layout, runtime)?;
     let network_backend_spec = network_backend.as_ref().map(|backend| backend.spec());
 
     // Assemble VMM instance spec
@@ -231,16 +273,26 @@ async fn build_config(
         engine: VmmKind::Libkrun, // only engine — will be dynamic when others are added
         // Box identification and security
         box_id: box_id.to_string(),
-        security: options.advanced.security.clone(),
+        security: options.jailer_security(),
         // VM resources
         cpus: options.cpus,
         memory_mib: options.memory_mib,
+        cpu_affinity: options.cpu_affinity.clone(),
+        split_irqchip: options.advanced.split_irqchip,
         // Filesystem and devices
         fs_shares: vmm_config.fs_shares,
         block_devices: vmm_config.block_devices,
         guest_entrypoint,
         transport: transport.clone(),
         ready_transport: ready_transport.clone(),
+        vsock_ports: options
+            .vsock_ports
+            .iter()
+            .map(|&port| VsockPort {
+                port,
+                socket_path: layout.sockets().vsock_sock(port),
+            })
+            .collect(),
         guest_rootfs,
         network_backend_spec,
         network_backend_endpoint: None,
@@ -251,6 +303,16 @@ async fn build_config(
         home_dir: runtime.layout.home_dir().to_path_buf(),
         // Diagnostic files in box_dir (preserved on crash)
         console_output: Some(layout.console_output_path()),
+        console_ring_lines: Some(
+            options
+                .console_ring_lines
+                .unwrap_or(crate::runtime::constants::vm_defaults::DEFAULT_CONSOLE_RING_LINES),
+        ),
+        kernel: options.kernel.clone(),
+        kernel_cmdline_append: options.kernel_cmdline_append.clone(),
+        ulimits: options.ulimits.clone(),
+        idle_timeout: options.idle_timeout(),
+        control_socket: Some(layout.control_socket_path()),
         exit_file: layout.exit_file_path(),
         detach: options.detach,
     };
@@ -293,6 +355,46 @@ fn configure_guest_rootfs(
     Ok(guest_rootfs)
 }
 
+/// Attach user-declared disks as guest block devices.
+///
+/// Returns a map from disk id to guest device path (e.g., "data" → "/dev/vdc").
+fn attach_user_disks(
+    disks: &[DiskSpec],
+    volume_mgr: &mut GuestVolumeManager,
+) -> BoxliteResult<HashMap<String, String>> {
+    let mut devices = HashMap::with_capacity(disks.len());
+    for disk in disks {
+        let host_path = Path::new(&disk.host_path);
+        if disk.passthrough {
+            crate::disk::validate_passthrough_device(host_path, disk.read_only)?;
+            let device_path = volume_mgr.add_passthrough_device(host_path, disk.read_only);
+            devices.insert(disk.id.clone(), device_path);
+            continue;
+        }
+        if !host_path.is_file() {
+            return Err(BoxliteError::Config(format!(
+                "disk {:?}: image not found at {}",
+                disk.id, disk.host_path
+            )));
+        }
+        let format = if crate::disk::has_qcow2_magic(host_path)? {
+            DiskFormat::Qcow2
+        } else {
+            DiskFormat::Ext4
+        };
+        let device_path = volume_mgr.add_block_device(
+            host_path,
+            format,
+            disk.read_only,
+            None,  // mounted by whichever option consumes it
+            false, // need_format
+            false, // need_resize
+        );
+        devices.insert(disk.id.clone(), device_path);
+    }
+    Ok(devices)
+}
+
 fn build_guest_entrypoint(
     transport: &BoxTransport,
     ready_transport: &BoxTransport,
@@ -334,7 +436,8 @@ fn build_guest_entrypoint(
 /// Create the box's **one** network backend by routing box-level policy (port
 /// mappings + allowlist) through the abstraction: assemble a
 /// [`NetworkBackendConfig`] and hand it to the factory. `None` when networking is
-/// disabled. The returned backend is used for both
+/// disabled; `Unsupported` when a rate limit is requested from a backend that
+/// cannot apply one. The returned backend is used for both
 /// its wire spec (`spec()`) and, threaded on to `LiveState`, runtime control — no
 /// caller here names a concrete backend.
 fn build_network_backend(
@@ -342,11 +445,11 @@ fn build_network_backend(
     options: &crate::runtime::options::BoxOptions,
     layout: &BoxFilesystemLayout,
     runtime: &SharedRuntimeImpl,
-) -> Option<Box<dyn NetworkBackend>> {
+) -> BoxliteResult<Option<Box<dyn NetworkBackend>>> {
     // Disabled = no network at all.
     let allow_net = match &options.network {
         crate::runtime::options::NetworkSpec::Enabled { allow_net } => allow_net.clone(),
-        crate::runtime::options::NetworkSpec::Disabled => return None,
+        crate::runtime::options::NetworkSpec::Disabled => return Ok(None),
     };
 
     // Port mappings (box-level policy): image EXPOSE gets a default 1:1 mapping
@@ -377,10 +480,22 @@ fn build_network_backend(
         allow_net,
         secrets: options.secrets.clone(),
         ca_dir: layout.ca_dir(),
+        rate_limit: options.network_rate_limit,
+        guest_mac: options.mac_address,
     };
 
     // Hand the config to the backend abstraction — the one backend for this box.
-    runtime.network_factory.create(&config)
+    let backend = runtime.network_factory.create(&config);
+    if config.rate_limit.is_some()
+        && let Some(backend) = &backend
+        && !backend.capabilities().rate_limiting
+    {
+        return Err(BoxliteError::Unsupported(format!(
+            "network backend {} does not support rate limiting",
+            backend.name()
+        )));
+    }
+    Ok(backend)
 }
 
 /// Spawn VM subprocess and return handler.
@@ -11,11 +11,14 @@ use crate::portal::interfaces::ContainerRootfsInitConfig;
 use crate::runtime::layout::BoxFilesystemLayout;
 use crate::runtime::options::VolumeSpec;
 use crate::runtime::rt_impl::SharedRuntimeImpl;
+use crate::runtime::types::PortMapping;
 use crate::vmm::controller::VmmHandler;
 use crate::volumes::{ContainerMount, GuestVolumeManager, VolumeShare, classify_volume_share};
+use boxlite_shared::GuestBootTiming;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use std::path::PathBuf;
 use std::sync::atomic::Ordering;
+use std::time::Instant;
 
 /// Switch between merged and overlayfs rootfs strategies.
 /// - true: overlayfs (allows COW writes, keeps layers separate)
@@ -304,6 +307,15 @@ pub struct InitPipelineContext {
     pub network_backend: Option<Box<dyn crate::net::NetworkBackend>>,
     /// MITM CA cert PEM (set by vmm_spawn, read by guest_init for Container.Init gRPC).
     pub ca_cert_pem: Option<String>,
+    /// Port mappings the VM was spawned with (set by vmm_spawn; `None` on
+    /// reattach, where the persisted mappings stay authoritative).
+    pub published_ports: Option<Vec<PortMapping>>,
+    /// When vmm_spawn started the VM subprocess (guest boot is measured from here).
+    pub vmm_spawned_at: Option<Instant>,
+    /// Time from VM subprocess spawn to guest ready (set by guest_connect).
+    pub guest_boot_duration_ms: Option<u128>,
+    /// Guest-side boot timing reported in the Guest.Init response (set by guest_init).
+    pub guest_boot_timing: Option<GuestBootTiming>,
 
     #[cfg(target_os = "linux")]
     pub bind_mount: Option<BindMountHandle>,
@@ -333,6 +345,10 @@ impl InitPipelineContext {
             guest_session: None,
             network_backend: None,
             ca_cert_pem: None,
+            published_ports: None,
+            vmm_spawned_at: None,
+            guest_boot_duration_ms: None,
+            guest_boot_timing: None,
             #[cfg(target_os = "linux")]
             bind_mount: None,
         }
@@ -417,6 +433,14 @@ mod tests {
         let runtime = RuntimeImpl::new(BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: vec![],
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .expect("create runtime");
 
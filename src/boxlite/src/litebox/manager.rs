@@ -2,15 +2,22 @@
 //!
 //! Pure database access layer for box persistence.
 //! No in-memory cache - queries go directly to database.
+//! Status changes are published to [`BoxEvent`] subscribers.
 
 use std::sync::Arc;
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use chrono::Utc;
+use tokio::sync::broadcast;
 
 use crate::db::BoxStore;
 use crate::litebox::config::BoxConfig;
 use crate::runtime::id::BoxID;
-use crate::runtime::types::BoxState;
+use crate::runtime::types::{BoxEvent, BoxState, BoxStatus};
+
+/// Events buffered per subscriber. A subscriber that falls further behind
+/// skips the oldest events instead of blocking publishers.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
 
 /// State backend for box persistence.
 ///
@@ -19,6 +26,7 @@ use crate::runtime::types::BoxState;
 #[derive(Clone)]
 pub struct BoxManager {
     store: Arc<BoxStore>,
+    events: broadcast::Sender<BoxEvent>,
 }
 
 impl std::fmt::Debug for BoxManager {
@@ -30,11 +38,28 @@ impl std::fmt::Debug for BoxManager {
 impl BoxManager {
     /// Create a new manager with the given store.
     pub fn new(store: BoxStore) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
         Self {
             store: Arc::new(store),
+            events,
         }
     }
 
+    /// Receive a [`BoxEvent`] for every status change from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<BoxEvent> {
+        self.events.subscribe()
+    }
+
+    fn publish(&self, box_id: &BoxID, old_state: Option<BoxStatus>, new_state: BoxStatus) {
+        // Sending only fails when nobody is subscribed.
+        let _ = self.events.send(BoxEvent {
+            box_id: box_id.clone(),
+            old_state,
+            new_state,
+            timestamp: Utc::now(),
+        });
+    }
+
     /// Get a reference to the underlying database.
     #[allow(dead_code)] // Used by snapshots (temporarily disabled)
     pub(crate) fn db(&self) -> crate::db::Database {
@@ -66,6 +91,7 @@ impl BoxManager {
         }
 
         self.store.save(config, state)?;
+        self.publish(&config.id, None, state.status);
 
         tracing::debug!(
             box_id = %config.id,
@@ -91,6 +117,17 @@ impl BoxManager {
         Ok(())
     }
 
+    /// Change the name of a persisted box.
+    ///
+    /// Fails with `AlreadyExists` if another box has `new_name`.
+    pub fn rename_box(&self, id: &BoxID, new_name: &str) -> BoxliteResult<()> {
+        self.store.rename(id.as_str(), new_name)?;
+
+        tracing::debug!(box_id = %id, name = %new_name, "Renamed box");
+
+        Ok(())
+    }
+
     /// Get a box by exact ID.
     pub fn box_by_id(&self, id: &BoxID) -> BoxliteResult<Option<(BoxConfig, BoxState)>> {
         self.store.load(id.as_str())
@@ -155,8 +192,21 @@ impl BoxManager {
     ///
     /// Reads state from the provided BoxState and persists to DB.
     pub fn save_box(&self, id: &BoxID, state: &BoxState) -> BoxliteResult<()> {
+        // The previous status is only needed to publish a change.
+        let old_status = if self.events.receiver_count() > 0 {
+            self.store.load_state(id.as_str())?.map(|s| s.status)
+        } else {
+            None
+        };
+
         self.store.update_state(id.as_str(), state)?;
 
+        if let Some(old_status) = old_status
+            && old_status != state.status
+        {
+            self.publish(id, Some(old_status), state.status);
+        }
+
         tracing::trace!(
             box_id = %id,
             status = ?state.status,
@@ -422,4 +472,60 @@ mod tests {
         assert_eq!(loaded_state.status, BoxStatus::Running);
         assert_eq!(loaded_state.pid, Some(12345));
     }
+
+    #[test]
+    fn test_status_changes_are_published() {
+        let store = create_test_store();
+        let manager = BoxManager::new(store);
+        let config = create_test_config(TEST_ID_1);
+        let mut events = manager.subscribe();
+
+        let mut state = BoxState::new();
+        manager.add_box(&config, &state).unwrap();
+        for status in [BoxStatus::Running, BoxStatus::Running, BoxStatus::Stopped] {
+            state.set_status(status);
+            manager.save_box(&config.id, &state).unwrap();
+        }
+
+        let mut seen = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            assert_eq!(event.box_id, config.id);
+            seen.push((event.old_state, event.new_state));
+        }
+        // Saving an unchanged status publishes nothing.
+        assert_eq!(
+            seen,
+            vec![
+                (None, BoxStatus::Configured),
+                (Some(BoxStatus::Configured), BoxStatus::Running),
+                (Some(BoxStatus::Running), BoxStatus::Stopped),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slow_subscriber_lags_instead_of_blocking() {
+        let store = create_test_store();
+        let manager = BoxManager::new(store);
+        let config = create_test_config(TEST_ID_1);
+        let mut events = manager.subscribe();
+
+        let mut state = BoxState::new();
+        manager.add_box(&config, &state).unwrap();
+        for i in 0..EVENT_CHANNEL_CAPACITY {
+            let status = if i % 2 == 0 {
+                BoxStatus::Running
+            } else {
+                BoxStatus::Stopped
+            };
+            state.set_status(status);
+            manager.save_box(&config.id, &state).unwrap();
+        }
+
+        assert!(matches!(
+            events.try_recv(),
+            Err(broadcast::error::TryRecvError::Lagged(1))
+        ));
+        assert_eq!(events.len(), EVENT_CHANNEL_CAPACITY);
+    }
 }
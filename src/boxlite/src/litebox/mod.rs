@@ -5,10 +5,13 @@
 pub(crate) mod archive;
 pub(crate) mod box_impl;
 mod clone_export;
+mod commit;
 pub(crate) mod config;
 pub mod copy;
 mod crash_report;
 mod exec;
+mod guest_log;
+mod health;
 mod init;
 pub(crate) mod local_snapshot;
 mod manager;
@@ -16,14 +19,22 @@ mod network;
 mod snapshot;
 pub(crate) mod snapshot_mgr;
 mod state;
+mod trace;
 
 pub use copy::CopyOptions;
 pub(crate) use crash_report::CrashReport;
-pub use exec::{BoxCommand, ExecResult, ExecStderr, ExecStdin, ExecStdout, Execution, ExecutionId};
+pub use exec::{
+    BoxCommand, ExecOutput, ExecResult, ExecStderr, ExecStdin, ExecStdout, Execution, ExecutionId,
+    OutputMode, TerminalSizeFollower,
+};
+pub(crate) use exec::{DEFAULT_OUTPUT_BUFFER_SIZE, OutputBudget};
+pub use guest_log::{GuestLogStream, LogLine};
+pub use health::HealthProbe;
 pub(crate) use manager::BoxManager;
 pub use network::{BoxConnection, BoxTunnel, NetworkHandle};
 pub use snapshot::SnapshotHandle;
-pub use state::{BoxState, BoxStatus, HealthState, HealthStatus};
+pub use state::{BoxEvent, BoxState, BoxStatus, HealthState, HealthStatus};
+pub use trace::{BoxTraceLayer, TraceEvent, TraceFilter, TraceStream};
 
 pub(crate) use box_impl::SharedBoxImpl;
 pub(crate) use init::BoxBuilder;
@@ -33,8 +44,10 @@ use std::path::Path;
 use std::sync::Arc;
 
 use crate::metrics::BoxMetrics;
+use crate::net::BackendCapabilities;
 use crate::runtime::backend::{BoxBackend, BoxNetworkBackend, SnapshotBackend};
-use crate::runtime::options::{BoxArchive, CloneOptions, ExportOptions};
+use crate::runtime::options::{BoxArchive, CloneOptions, CommitOptions, ExportOptions};
+use crate::runtime::types::{ExecutionInfo, ImageInfo, ProcessInfo};
 use crate::{BoxID, BoxInfo};
 use boxlite_shared::errors::BoxliteResult;
 pub use config::BoxConfig;
@@ -66,7 +79,7 @@ impl LiteBox {
         snapshot_backend: Arc<dyn SnapshotBackend>,
     ) -> Self {
         let id = box_backend.id().clone();
-        let name = box_backend.name().map(|s| s.to_string());
+        let name = box_backend.name();
         Self {
             id,
             name,
@@ -104,12 +117,26 @@ impl LiteBox {
         self.box_backend.exec(command).await
     }
 
+    /// Run `command` to completion and capture its output.
+    ///
+    /// The command's timeout, if set, is enforced here: on expiry the
+    /// process is killed and the result has `timed_out` set.
+    pub async fn run(&self, mut command: BoxCommand) -> BoxliteResult<ExecOutput> {
+        let timeout = command.timeout.take();
+        self.exec(command).await?.collect_output(timeout).await
+    }
+
     /// Reattach to a running execution by id, returning a fresh
     /// `Execution` handle. The caller discards any previous handle for
     /// the same id. Used after a transient WebSocket drop to resume
     /// stdio without restarting the underlying process. Returns
     /// `BoxliteError::SessionReaped` if the session is no longer
     /// attachable on the server side.
+    ///
+    /// On a local box the previous handle must have been dropped first.
+    /// Output produced while detached is delivered before live output, and
+    /// an execution that already exited yields its remaining output and
+    /// exit status. The handle has no stdin.
     pub async fn attach(&self, execution_id: &str) -> BoxliteResult<Execution> {
         self.box_backend.attach(execution_id).await
     }
@@ -118,10 +145,47 @@ impl LiteBox {
         self.box_backend.metrics().await
     }
 
+    /// Resize the memory available to the running box.
+    ///
+    /// `mib` may not exceed the box's configured `memory_mib` (the memory the
+    /// VM booted with). Returns the guest's memory after the resize, which can
+    /// stay above `mib` if the guest kernel can't release enough memory.
+    /// Returns `BoxliteError::Unsupported` when the VM engine can't reclaim
+    /// guest memory (see `EngineFeatures::memory_balloon`).
+    pub async fn set_memory_mib(&self, mib: u32) -> BoxliteResult<u32> {
+        self.box_backend.set_memory_mib(mib).await
+    }
+
+    /// Processes running in the box's container, ordered by PID.
+    ///
+    /// Only the container's PID namespace is listed, not the guest agent or
+    /// other guest processes. Starts the box if needed.
+    pub async fn processes(&self) -> BoxliteResult<Vec<ProcessInfo>> {
+        self.box_backend.processes().await
+    }
+
+    /// Executions started on this box, oldest first.
+    ///
+    /// Includes executions started through other handles or before a
+    /// runtime restart, so their IDs can be passed to `attach`. Exited
+    /// executions are listed with `running: false` until the box stops.
+    pub async fn executions(&self) -> BoxliteResult<Vec<ExecutionInfo>> {
+        self.box_backend.executions().await
+    }
+
     pub async fn stop(&self) -> BoxliteResult<()> {
         self.box_backend.stop().await
     }
 
+    /// The last `lines` lines of guest console output (kernel and init
+    /// messages), oldest first.
+    ///
+    /// At most `BoxOptions::console_ring_lines` lines are kept. Available
+    /// after the VM exits too, which helps diagnose boot failures.
+    pub fn console_logs(&self, lines: usize) -> BoxliteResult<Vec<String>> {
+        self.box_backend.console_logs(lines)
+    }
+
     /// Copy files/directories from host into the container rootfs.
     pub async fn copy_into(
         &self,
@@ -151,6 +215,59 @@ impl LiteBox {
         NetworkHandle::new(Arc::clone(&self.network_backend))
     }
 
+    /// Optional features supported by this box's network backend.
+    ///
+    /// Check this before calling a control operation (stats, runtime port
+    /// forwarding, ...) instead of probing for `Unsupported`.
+    pub async fn network_capabilities(&self) -> BoxliteResult<BackendCapabilities> {
+        self.network().capabilities().await
+    }
+
+    /// Forward host TCP `host_port` to the guest's `guest_port` on the running
+    /// box (starting it if needed). Returns `BoxliteError::Network` when the
+    /// host port is already forwarded or bound by another process.
+    pub async fn expose_port(&self, host_port: u16, guest_port: u16) -> BoxliteResult<()> {
+        self.network().expose_port(host_port, guest_port).await
+    }
+
+    /// Remove a forward added by [`expose_port`](Self::expose_port) (or a
+    /// boot-time port mapping) bound at host `host_port`.
+    pub async fn unexpose_port(&self, host_port: u16) -> BoxliteResult<()> {
+        self.network().unexpose_port(host_port).await
+    }
+
+    /// Open a byte stream to a guest application listening on AF_VSOCK
+    /// `port` (starting the box if needed).
+    ///
+    /// The port must be listed in `BoxOptions::vsock_ports`, which bridges it
+    /// to a host Unix socket when the VM boots. Inside the guest, bind
+    /// `VMADDR_CID_ANY:port` and accept; each call here is one accepted
+    /// connection. If nothing is listening yet the stream is closed
+    /// immediately by the peer.
+    pub async fn vsock_connect(&self, port: u32) -> BoxliteResult<Box<dyn BoxConnection>> {
+        self.network().vsock_connect(port).await
+    }
+
+    /// Stream this box's tracing events, narrowed by `filter`.
+    ///
+    /// Receives events that carry this box's `box_id` or are emitted inside
+    /// one of its operations. Requires [`BoxTraceLayer`] in the host's
+    /// subscriber (installed by `init_logging_for`); only events from this
+    /// process are seen, so REST-backed boxes yield nothing.
+    pub fn trace_stream(&self, filter: TraceFilter) -> TraceStream {
+        TraceStream::subscribe(&self.id, filter)
+    }
+
+    /// Stream the guest agent's own log lines as they are emitted.
+    ///
+    /// Unlike [`trace_stream`](Self::trace_stream), these come from inside
+    /// the VM; unlike the console file, they are the agent's tracing events,
+    /// not kernel messages. The guest's `RUST_LOG` bounds what is seen.
+    /// Starts the box if needed; the stream ends when the box stops.
+    pub async fn guest_log_stream(&self) -> BoxliteResult<GuestLogStream> {
+        self.box_backend.guest_log_stream().await
+    }
+
     /// Get a snapshot handle for snapshot operations.
     pub fn snapshots(&self) -> SnapshotHandle {
         SnapshotHandle::new(Arc::clone(&self.snapshot_backend))
@@ -182,6 +299,32 @@ impl LiteBox {
     pub async fn export(&self, options: ExportOptions, dest: &Path) -> BoxliteResult<BoxArchive> {
         self.box_backend.export_box(options, dest).await
     }
+
+    /// Save this box's current rootfs as a local image named `reference`.
+    ///
+    /// Like `docker commit`: the box's changes on top of its base image become
+    /// a new top layer, and the image config keeps the base entrypoint and env
+    /// unless `options` (or the box's own overrides) replace them. Boot the
+    /// result with `RootfsSpec::Image(reference)`.
+    pub async fn commit(
+        &self,
+        reference: &str,
+        options: CommitOptions,
+    ) -> BoxliteResult<ImageInfo> {
+        self.box_backend.commit(reference, options).await
+    }
+
+    /// Stream this box's merged container rootfs into `writer` as a tar.
+    ///
+    /// Like `docker export`: a single flattened filesystem with no image
+    /// config or layer history. Device nodes and sockets are skipped. Starts
+    /// the box if needed.
+    pub async fn export_rootfs<W>(&self, writer: &mut W) -> BoxliteResult<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin + Send,
+    {
+        self.box_backend.export_rootfs(writer).await
+    }
 }
 
 // ============================================================================
@@ -7,7 +7,7 @@ use std::sync::Arc;
 
 use boxlite_shared::errors::BoxliteResult;
 
-use crate::net::BoxInternalTunnel;
+use crate::net::{BackendCapabilities, BoxInternalTunnel, Forward};
 use crate::runtime::backend::BoxNetworkBackend;
 
 /// Lazily opens the raw byte stream backing a [`BoxTunnel`]. Each backend
@@ -88,6 +88,36 @@ impl NetworkHandle {
     pub async fn tunnel(&self, target: SocketAddr) -> BoxliteResult<BoxTunnel> {
         self.network_backend.tunnel(target).await
     }
+
+    /// Open a byte stream to the guest listener on vsock `port`.
+    pub async fn vsock_connect(&self, port: u32) -> BoxliteResult<Box<dyn BoxConnection>> {
+        self.network_backend.vsock_connect(port).await
+    }
+
+    /// Optional features supported by the box's network backend (metrics,
+    /// runtime port forwarding, UDP, ...). Starts the box if needed;
+    /// `Unsupported` when the box has no network.
+    pub async fn capabilities(&self) -> BoxliteResult<BackendCapabilities> {
+        self.network_backend.capabilities().await
+    }
+
+    /// Forward host TCP `host_port` to the guest's `guest_port` while the box
+    /// runs. `Network` error when the host port is already forwarded or bound.
+    pub async fn expose_port(&self, host_port: u16, guest_port: u16) -> BoxliteResult<()> {
+        self.network_backend
+            .expose_port(host_port, guest_port)
+            .await
+    }
+
+    /// Remove the forward bound at host `host_port`.
+    pub async fn unexpose_port(&self, host_port: u16) -> BoxliteResult<()> {
+        self.network_backend.unexpose_port(host_port).await
+    }
+
+    /// Active forwards: the box's port mappings plus any exposed at runtime.
+    pub async fn port_forwards(&self) -> BoxliteResult<Vec<Forward>> {
+        self.network_backend.port_forwards().await
+    }
 }
 
 #[cfg(test)]
@@ -120,6 +150,13 @@ mod tests {
                 }),
             ))
         }
+
+        async fn capabilities(&self) -> BoxliteResult<BackendCapabilities> {
+            Ok(BackendCapabilities {
+                metrics: true,
+                ..Default::default()
+            })
+        }
     }
 
     #[tokio::test]
@@ -145,6 +182,15 @@ mod tests {
         assert_eq!(backend.connected.load(Ordering::Relaxed), 1);
     }
 
+    #[tokio::test]
+    async fn capabilities_come_from_the_backend() {
+        let network = NetworkHandle::new(Arc::new(TestBackend::default()));
+
+        let caps = network.capabilities().await.unwrap();
+        assert!(caps.metrics);
+        assert!(!caps.runtime_port_forwarding);
+    }
+
     struct LocalBackend {
         peer: Arc<tokio::sync::Mutex<Option<UnixStream>>>,
     }
@@ -170,6 +216,10 @@ mod tests {
                 }),
             ))
         }
+
+        async fn capabilities(&self) -> BoxliteResult<BackendCapabilities> {
+            Ok(BackendCapabilities::default())
+        }
     }
 
     #[tokio::test]
@@ -4,6 +4,8 @@
 
 use crate::ContainerID;
 use crate::lock::LockId;
+use crate::runtime::id::BoxID;
+use crate::runtime::types::PortMapping;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -217,6 +219,10 @@ pub struct BoxState {
     /// Serde default keeps existing DB rows readable without migration.
     #[serde(default)]
     pub error_reason: Option<String>,
+    /// Port mappings of the most recent start, so `BoxInfo` can report
+    /// them after a runtime restart.
+    #[serde(default)]
+    pub ports: Vec<PortMapping>,
 }
 
 /// Health status of a box.
@@ -315,6 +321,7 @@ impl BoxState {
             lock_id: None,
             health_status: HealthStatus::new(),
             error_reason: None,
+            ports: Vec::new(),
         }
     }
 
@@ -357,6 +364,12 @@ impl BoxState {
         self.last_updated = Utc::now();
     }
 
+    /// Record the port mappings the VM was started with.
+    pub fn set_ports(&mut self, ports: Vec<PortMapping>) {
+        self.ports = ports;
+        self.last_updated = Utc::now();
+    }
+
     /// Mark box as crashed (sets status to Stopped since VM is no longer running).
     ///
     /// In our simplified state model, crashed VMs become Stopped
@@ -428,6 +441,20 @@ impl Default for BoxState {
     }
 }
 
+/// A box lifecycle transition, published by
+/// [`BoxliteRuntime::subscribe`](crate::BoxliteRuntime::subscribe).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BoxEvent {
+    /// Box whose status changed.
+    pub box_id: BoxID,
+    /// Status before the change; `None` when the box was just created.
+    pub old_state: Option<BoxStatus>,
+    /// Status after the change.
+    pub new_state: BoxStatus,
+    /// When the change was persisted (UTC).
+    pub timestamp: DateTime<Utc>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
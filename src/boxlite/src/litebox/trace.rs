@@ -0,0 +1,313 @@
+//! Per-box tracing log streams.
+//!
+//! Every box shares the host's tracing subscriber. [`BoxTraceLayer`] is a
+//! `tracing_subscriber` layer that attributes each event to a box — from the
+//! event's own `box_id` field or from the nearest enclosing span carrying one
+//! — and fans it out to the [`TraceStream`]s opened for that box via
+//! `LiteBox::trace_stream()`.
+//!
+//! The layer only sees events that reach the subscriber, so the host's
+//! global filter (e.g. `RUST_LOG`) still bounds what a stream can observe.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::LazyLock;
+use std::task::{Context, Poll};
+
+use chrono::{DateTime, Utc};
+use futures::Stream;
+use parking_lot::RwLock;
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context as LayerContext, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::BoxID;
+
+/// Field name that ties an event or span to a box.
+const BOX_ID_FIELD: &str = "box_id";
+
+/// Open streams, keyed by box ID.
+static STREAMS: LazyLock<RwLock<HashMap<String, Vec<Subscription>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+struct Subscription {
+    filter: TraceFilter,
+    sender: mpsc::UnboundedSender<TraceEvent>,
+}
+
+// ============================================================================
+// PUBLIC TYPES
+// ============================================================================
+
+/// A tracing event attributed to a box.
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// Box the event was attributed to.
+    pub box_id: String,
+    /// When the layer observed the event.
+    pub timestamp: DateTime<Utc>,
+    pub level: Level,
+    /// Event target (usually the emitting module path).
+    pub target: String,
+    /// The event's formatted message.
+    pub message: String,
+    /// Remaining event fields (other than `box_id`), formatted with `Debug`.
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Selects which of a box's events a [`TraceStream`] receives.
+///
+/// The default accepts every event attributed to the box.
+#[derive(Debug, Clone)]
+pub struct TraceFilter {
+    level: Level,
+    target_prefix: Option<String>,
+}
+
+impl Default for TraceFilter {
+    fn default() -> Self {
+        Self {
+            level: Level::TRACE,
+            target_prefix: None,
+        }
+    }
+}
+
+impl TraceFilter {
+    /// Only pass events at `level` or more severe.
+    pub fn level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Only pass events whose target starts with `prefix`.
+    pub fn target(mut self, prefix: impl Into<String>) -> Self {
+        self.target_prefix = Some(prefix.into());
+        self
+    }
+
+    fn matches(&self, level: &Level, target: &str) -> bool {
+        *level <= self.level
+            && self
+                .target_prefix
+                .as_deref()
+                .is_none_or(|prefix| target.starts_with(prefix))
+    }
+}
+
+/// Stream of tracing events for one box. Dropping it unsubscribes.
+pub struct TraceStream {
+    receiver: mpsc::UnboundedReceiver<TraceEvent>,
+}
+
+impl TraceStream {
+    /// Subscribe to events attributed to `box_id`.
+    pub(crate) fn subscribe(box_id: &BoxID, filter: TraceFilter) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        STREAMS
+            .write()
+            .entry(box_id.as_str().to_string())
+            .or_default()
+            .push(Subscription { filter, sender });
+        Self { receiver }
+    }
+}
+
+impl Stream for TraceStream {
+    type Item = TraceEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+// ============================================================================
+// LAYER
+// ============================================================================
+
+/// Tracing layer that routes box-scoped events to [`TraceStream`]s.
+///
+/// Add it to the host subscriber to enable `LiteBox::trace_stream()`;
+/// [`init_logging_for`](crate::init_logging_for) installs it automatically.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BoxTraceLayer;
+
+impl BoxTraceLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+/// Box ID stored in the extensions of a span that declared `box_id`.
+struct SpanBoxId(String);
+
+impl<S> Layer<S> for BoxTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: LayerContext<'_, S>) {
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        if let Some(box_id) = visitor.box_id
+            && let Some(span) = ctx.span(id)
+        {
+            span.extensions_mut().insert(SpanBoxId(box_id));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: LayerContext<'_, S>) {
+        // Fast path: nobody is listening.
+        if STREAMS.read().is_empty() {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let box_id = visitor.box_id.take().or_else(|| {
+            ctx.event_scope(event)?.find_map(|span| {
+                span.extensions()
+                    .get::<SpanBoxId>()
+                    .map(|SpanBoxId(id)| id.clone())
+            })
+        });
+        let Some(box_id) = box_id else {
+            return;
+        };
+
+        let meta = event.metadata();
+        let trace_event = TraceEvent {
+            box_id: box_id.clone(),
+            timestamp: Utc::now(),
+            level: *meta.level(),
+            target: meta.target().to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        };
+        dispatch(&box_id, trace_event);
+    }
+}
+
+/// Deliver an event to the box's matching streams, pruning closed ones.
+fn dispatch(box_id: &str, event: TraceEvent) {
+    let mut closed = false;
+    if let Some(subscriptions) = STREAMS.read().get(box_id) {
+        for sub in subscriptions {
+            if sub.filter.matches(&event.level, &event.target) {
+                closed |= sub.sender.send(event.clone()).is_err();
+            } else {
+                closed |= sub.sender.is_closed();
+            }
+        }
+    }
+
+    if closed {
+        let mut streams = STREAMS.write();
+        if let Some(subscriptions) = streams.get_mut(box_id) {
+            subscriptions.retain(|sub| !sub.sender.is_closed());
+            if subscriptions.is_empty() {
+                streams.remove(box_id);
+            }
+        }
+    }
+}
+
+/// Collects an event's message, `box_id`, and other fields.
+#[derive(Default)]
+struct FieldVisitor {
+    box_id: Option<String>,
+    message: String,
+    fields: BTreeMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            BOX_ID_FIELD => self.box_id = Some(value.to_string()),
+            "message" => self.message = value.to_string(),
+            name => {
+                self.fields.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{value:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BoxIDMint;
+    use futures::{FutureExt, StreamExt};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn drain(stream: &mut TraceStream) -> Vec<TraceEvent> {
+        let mut events = Vec::new();
+        while let Some(Some(event)) = stream.next().now_or_never() {
+            events.push(event);
+        }
+        events
+    }
+
+    #[test]
+    fn events_are_routed_by_field_and_span() {
+        let a = BoxIDMint::mint();
+        let b = BoxIDMint::mint();
+        let mut a_stream = TraceStream::subscribe(&a, TraceFilter::default());
+        let mut b_stream = TraceStream::subscribe(&b, TraceFilter::default());
+
+        let subscriber = tracing_subscriber::registry().with(BoxTraceLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(box_id = %a, "tagged event");
+            let span = tracing::info_span!("box", box_id = %a);
+            span.in_scope(|| tracing::debug!(step = 2, "inside span"));
+            tracing::info!("unscoped event");
+        });
+
+        let events = drain(&mut a_stream);
+        let messages: Vec<_> = events.iter().map(|e| e.message.as_str()).collect();
+        assert_eq!(messages, vec!["tagged event", "inside span"]);
+        assert_eq!(events[1].fields.get("step").map(String::as_str), Some("2"));
+        assert!(drain(&mut b_stream).is_empty());
+    }
+
+    #[test]
+    fn filter_applies_level_and_target() {
+        let id = BoxIDMint::mint();
+        let mut stream = TraceStream::subscribe(
+            &id,
+            TraceFilter::default()
+                .level(Level::INFO)
+                .target("boxlite::litebox"),
+        );
+
+        let subscriber = tracing_subscriber::registry().with(BoxTraceLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(box_id = %id, "too verbose");
+            tracing::warn!(target: "other", box_id = %id, "wrong target");
+            tracing::warn!(box_id = %id, "kept");
+        });
+
+        let messages: Vec<_> = drain(&mut stream).into_iter().map(|e| e.message).collect();
+        assert_eq!(messages, vec!["kept".to_string()]);
+    }
+
+    #[test]
+    fn dropped_stream_is_pruned() {
+        let id = BoxIDMint::mint();
+        drop(TraceStream::subscribe(&id, TraceFilter::default()));
+
+        let subscriber = tracing_subscriber::registry().with(BoxTraceLayer::new());
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(box_id = %id, "nobody listening");
+        });
+
+        assert!(!STREAMS.read().contains_key(id.as_str()));
+    }
+}
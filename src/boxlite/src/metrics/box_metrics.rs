@@ -22,6 +22,10 @@ pub struct BoxMetricsStorage {
     pub(crate) total_create_duration_ms: Option<u128>,
     /// Time from box subprocess spawn to guest agent ready
     pub(crate) guest_boot_duration_ms: Option<u128>,
+    /// Kernel portion of guest boot (guest boot minus agent init)
+    pub(crate) guest_kernel_boot_ms: Option<u128>,
+    /// Agent portion of guest boot (agent start to host notified)
+    pub(crate) guest_agent_init_ms: Option<u128>,
 
     // Stage-level timing breakdown (set once during initialization)
     /// Time to create box directory structure (Stage 1)
@@ -47,6 +51,8 @@ impl Clone for BoxMetricsStorage {
             bytes_received: AtomicU64::new(self.bytes_received.load(Ordering::Relaxed)),
             total_create_duration_ms: self.total_create_duration_ms,
             guest_boot_duration_ms: self.guest_boot_duration_ms,
+            guest_kernel_boot_ms: self.guest_kernel_boot_ms,
+            guest_agent_init_ms: self.guest_agent_init_ms,
             stage_filesystem_setup_ms: self.stage_filesystem_setup_ms,
             stage_image_prepare_ms: self.stage_image_prepare_ms,
             stage_guest_rootfs_ms: self.stage_guest_rootfs_ms,
@@ -69,11 +75,25 @@ impl BoxMetricsStorage {
     }
 
     /// Set guest boot duration (called once after guest is ready).
-    #[allow(dead_code)] // API designed but not yet wired up
     pub(crate) fn set_guest_boot_duration(&mut self, duration_ms: u128) {
         self.guest_boot_duration_ms = Some(duration_ms);
     }
 
+    /// Split guest boot into kernel and agent portions.
+    ///
+    /// `agent_init_ms` is reported by the guest agent; the kernel portion is
+    /// the remainder of the host-measured guest boot duration, so it also
+    /// covers VMM setup before the kernel starts. No-op until
+    /// `set_guest_boot_duration` has been called.
+    pub(crate) fn set_guest_boot_breakdown(&mut self, agent_init_ms: u128) {
+        let Some(total_ms) = self.guest_boot_duration_ms else {
+            return;
+        };
+        let agent_init_ms = agent_init_ms.min(total_ms);
+        self.guest_agent_init_ms = Some(agent_init_ms);
+        self.guest_kernel_boot_ms = Some(total_ms - agent_init_ms);
+    }
+
     /// Set filesystem setup stage duration.
     pub(crate) fn set_stage_filesystem_setup(&mut self, duration_ms: u128) {
         self.stage_filesystem_setup_ms = Some(duration_ms);
@@ -109,6 +129,9 @@ impl BoxMetricsStorage {
     pub(crate) fn log_init_stages(&self) {
         tracing::debug!(
             total_create_duration_ms = self.total_create_duration_ms.unwrap_or(0),
+            guest_boot_duration_ms = self.guest_boot_duration_ms.unwrap_or(0),
+            guest_kernel_boot_ms = self.guest_kernel_boot_ms.unwrap_or(0),
+            guest_agent_init_ms = self.guest_agent_init_ms.unwrap_or(0),
             stage_filesystem_setup_ms = self.stage_filesystem_setup_ms.unwrap_or(0),
             stage_image_prepare_ms = self.stage_image_prepare_ms.unwrap_or(0),
             stage_guest_rootfs_ms = self.stage_guest_rootfs_ms.unwrap_or(0),
@@ -160,6 +183,10 @@ pub struct BoxMetrics {
     pub total_create_duration_ms: Option<u128>,
     /// Time from box subprocess spawn to guest agent ready (milliseconds)
     pub guest_boot_duration_ms: Option<u128>,
+    /// Kernel portion of guest boot (milliseconds)
+    pub guest_kernel_boot_ms: Option<u128>,
+    /// Guest agent portion of guest boot (milliseconds)
+    pub guest_agent_init_ms: Option<u128>,
     /// CPU usage percent (0.0-100.0)
     pub cpu_percent: Option<f32>,
     /// Memory usage in bytes
@@ -172,6 +199,10 @@ pub struct BoxMetrics {
     pub network_tcp_connections: Option<u64>,
     /// Total TCP connection errors
     pub network_tcp_errors: Option<u64>,
+    /// Bytes used on the filesystem backing the container rootfs
+    pub disk_bytes_used: Option<u64>,
+    /// Size of the filesystem backing the container rootfs
+    pub disk_bytes_total: Option<u64>,
 
     // Stage-level timing breakdown
     /// Time to create box directory structure (milliseconds)
@@ -206,12 +237,16 @@ impl BoxMetrics {
             bytes_received_total: storage.bytes_received.load(Ordering::Relaxed),
             total_create_duration_ms: storage.total_create_duration_ms,
             guest_boot_duration_ms: storage.guest_boot_duration_ms,
+            guest_kernel_boot_ms: storage.guest_kernel_boot_ms,
+            guest_agent_init_ms: storage.guest_agent_init_ms,
             cpu_percent,
             memory_bytes,
             network_bytes_sent,
             network_bytes_received,
             network_tcp_connections,
             network_tcp_errors,
+            disk_bytes_used: None,
+            disk_bytes_total: None,
             stage_filesystem_setup_ms: storage.stage_filesystem_setup_ms,
             stage_image_prepare_ms: storage.stage_image_prepare_ms,
             stage_guest_rootfs_ms: storage.stage_guest_rootfs_ms,
@@ -268,6 +303,23 @@ impl BoxMetrics {
         self.guest_boot_duration_ms
     }
 
+    /// Kernel portion of guest boot (milliseconds).
+    ///
+    /// Guest boot time minus guest agent init time, so it includes VMM
+    /// setup as well as kernel boot.
+    /// Returns None if the guest agent did not report its timing.
+    pub fn guest_kernel_boot_ms(&self) -> Option<u128> {
+        self.guest_kernel_boot_ms
+    }
+
+    /// Guest agent portion of guest boot (milliseconds).
+    ///
+    /// Time from guest agent start until it signaled readiness to the host.
+    /// Returns None if the guest agent did not report its timing.
+    pub fn guest_agent_init_ms(&self) -> Option<u128> {
+        self.guest_agent_init_ms
+    }
+
     /// CPU usage percent (0.0-100.0).
     ///
     /// Returns None if box not started or process not found.
@@ -310,6 +362,22 @@ impl BoxMetrics {
         self.network_tcp_errors
     }
 
+    /// Bytes used on the filesystem backing the container rootfs.
+    ///
+    /// Measured in the guest with `statvfs`: the virtio-blk disk for
+    /// block-device rootfs, the host share for virtiofs rootfs.
+    /// Returns None if the box is not running or the guest agent is too old.
+    pub fn disk_bytes_used(&self) -> Option<u64> {
+        self.disk_bytes_used
+    }
+
+    /// Size of the filesystem backing the container rootfs.
+    ///
+    /// Returns None under the same conditions as [`Self::disk_bytes_used`].
+    pub fn disk_bytes_total(&self) -> Option<u64> {
+        self.disk_bytes_total
+    }
+
     // Stage-level timing getters
 
     /// Time to create box directory structure (milliseconds).
@@ -365,3 +433,42 @@ impl BoxMetrics {
         self.stage_container_init_ms
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn boot_breakdown_splits_total_into_kernel_and_agent() {
+        let mut storage = BoxMetricsStorage::new();
+        storage.set_guest_boot_duration(900);
+        storage.set_guest_boot_breakdown(250);
+
+        let metrics = BoxMetrics::from_storage(&storage, None, None, None, None, None, None);
+        assert_eq!(metrics.guest_agent_init_ms(), Some(250));
+        assert_eq!(metrics.guest_kernel_boot_ms(), Some(650));
+        assert_eq!(
+            metrics.guest_kernel_boot_ms().unwrap() + metrics.guest_agent_init_ms().unwrap(),
+            metrics.guest_boot_duration_ms().unwrap()
+        );
+    }
+
+    #[test]
+    fn boot_breakdown_clamps_agent_time_to_total() {
+        let mut storage = BoxMetricsStorage::new();
+        storage.set_guest_boot_duration(100);
+        storage.set_guest_boot_breakdown(400);
+
+        assert_eq!(storage.guest_agent_init_ms, Some(100));
+        assert_eq!(storage.guest_kernel_boot_ms, Some(0));
+    }
+
+    #[test]
+    fn boot_breakdown_requires_total() {
+        let mut storage = BoxMetricsStorage::new();
+        storage.set_guest_boot_breakdown(250);
+
+        assert_eq!(storage.guest_agent_init_ms, None);
+        assert_eq!(storage.guest_kernel_boot_ms, None);
+    }
+}
@@ -32,6 +32,8 @@
 
 mod box_metrics;
 mod runtime_metrics;
+mod runtime_stats;
 
 pub use box_metrics::{BoxMetrics, BoxMetricsStorage};
 pub use runtime_metrics::{RuntimeMetrics, RuntimeMetricsStorage};
+pub use runtime_stats::RuntimeStats;
@@ -19,6 +19,8 @@ pub struct RuntimeMetricsStorage {
     pub(crate) total_commands: Arc<AtomicU64>,
     /// Total command execution errors across all boxes
     pub(crate) total_exec_errors: Arc<AtomicU64>,
+    /// Total guest gRPC connections established across all boxes
+    pub(crate) guest_connections: Arc<AtomicU64>,
 }
 
 impl RuntimeMetricsStorage {
@@ -92,6 +94,16 @@ impl RuntimeMetrics {
     pub fn total_exec_errors(&self) -> u64 {
         self.storage.total_exec_errors.load(Ordering::Relaxed)
     }
+
+    /// Total guest gRPC connections established across all boxes.
+    ///
+    /// Each box session connects once and reuses the channel for every
+    /// exec, copy, and guest call, so this grows with box starts rather
+    /// than with commands executed.
+    /// Never decreases (monotonic counter).
+    pub fn guest_connections_total(&self) -> u64 {
+        self.storage.guest_connections.load(Ordering::Relaxed)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,91 @@
+//! Periodic runtime snapshots for `BoxliteRuntime::stats_stream`.
+
+use super::{BoxMetrics, RuntimeMetrics};
+
+/// Point-in-time runtime counters plus usage summed over running boxes.
+///
+/// Unlike [`RuntimeMetrics`], which reads live counters, every field is
+/// fixed when the snapshot is taken.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuntimeStats {
+    /// See [`RuntimeMetrics::boxes_created_total`].
+    pub boxes_created_total: u64,
+    /// See [`RuntimeMetrics::boxes_failed_total`].
+    pub boxes_failed_total: u64,
+    /// See [`RuntimeMetrics::boxes_stopped_total`].
+    pub boxes_stopped_total: u64,
+    /// See [`RuntimeMetrics::num_running_boxes`].
+    pub num_running_boxes: u64,
+    /// See [`RuntimeMetrics::total_commands_executed`].
+    pub total_commands_executed: u64,
+    /// See [`RuntimeMetrics::total_exec_errors`].
+    pub total_exec_errors: u64,
+    /// Running boxes whose metrics were read for the sums below.
+    pub boxes_sampled: usize,
+    /// Sum of the sampled boxes' CPU usage, in percent of one CPU.
+    pub cpu_percent: f32,
+    /// Sum of the sampled boxes' memory usage, in bytes.
+    pub memory_bytes: u64,
+    /// Sum of the sampled boxes' network bytes sent (host to guest).
+    pub network_bytes_sent: u64,
+    /// Sum of the sampled boxes' network bytes received (guest to host).
+    pub network_bytes_received: u64,
+}
+
+impl RuntimeStats {
+    /// Snapshot `runtime` and sum the usage reported in `boxes`.
+    ///
+    /// Usage a box does not report (`None`) counts as zero.
+    pub(crate) fn collect(runtime: &RuntimeMetrics, boxes: &[BoxMetrics]) -> Self {
+        Self {
+            boxes_created_total: runtime.boxes_created_total(),
+            boxes_failed_total: runtime.boxes_failed_total(),
+            boxes_stopped_total: runtime.boxes_stopped_total(),
+            num_running_boxes: runtime.num_running_boxes(),
+            total_commands_executed: runtime.total_commands_executed(),
+            total_exec_errors: runtime.total_exec_errors(),
+            boxes_sampled: boxes.len(),
+            cpu_percent: boxes.iter().filter_map(|b| b.cpu_percent).sum(),
+            memory_bytes: boxes.iter().filter_map(|b| b.memory_bytes).sum(),
+            network_bytes_sent: boxes.iter().filter_map(|b| b.network_bytes_sent).sum(),
+            network_bytes_received: boxes.iter().filter_map(|b| b.network_bytes_received).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::{BoxMetricsStorage, RuntimeMetricsStorage};
+    use std::sync::atomic::Ordering;
+
+    fn box_metrics(cpu: Option<f32>, memory: Option<u64>, net: Option<u64>) -> BoxMetrics {
+        BoxMetrics::from_storage(&BoxMetricsStorage::new(), cpu, memory, net, net, None, None)
+    }
+
+    #[test]
+    fn collect_sums_reported_usage() {
+        let storage = RuntimeMetricsStorage::new();
+        storage.boxes_created.fetch_add(3, Ordering::Relaxed);
+        storage.boxes_stopped.fetch_add(1, Ordering::Relaxed);
+        let runtime = RuntimeMetrics::new(storage.clone());
+
+        let stats = RuntimeStats::collect(
+            &runtime,
+            &[
+                box_metrics(Some(12.5), Some(100), Some(10)),
+                box_metrics(Some(7.5), None, Some(5)),
+            ],
+        );
+        assert_eq!(stats.num_running_boxes, 2);
+        assert_eq!(stats.boxes_sampled, 2);
+        assert_eq!(stats.cpu_percent, 20.0);
+        assert_eq!(stats.memory_bytes, 100);
+        assert_eq!(stats.network_bytes_sent, 15);
+        assert_eq!(stats.network_bytes_received, 15);
+
+        // The snapshot does not follow later counter changes.
+        storage.boxes_created.fetch_add(1, Ordering::Relaxed);
+        assert_eq!(stats.boxes_created_total, 3);
+    }
+}
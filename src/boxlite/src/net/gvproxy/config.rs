@@ -259,6 +259,21 @@ impl GvproxyConfig {
         config
     }
 
+    /// Build the config a box's gvproxy is created with from the wire spec
+    /// the core produced.
+    pub fn from_spec(spec: &crate::net::NetworkBackendSpec) -> Self {
+        let mut config = Self::new(spec.socket_path.clone(), spec.port_mappings.clone())
+            .with_control_socket_path(super::control_socket_path(&spec.socket_path))
+            .with_allow_net(spec.allow_net.clone())
+            .with_secrets(spec.secrets.iter().map(Into::into).collect())
+            .with_guest_mac(spec.guest_mac());
+
+        if let (Some(cert), Some(key)) = (&spec.ca_cert_pem, &spec.ca_key_pem) {
+            config = config.with_ca(cert.clone(), key.clone());
+        }
+        config
+    }
+
     /// Enable debug logging
     pub fn with_debug(mut self, debug: bool) -> Self {
         self.debug = debug;
@@ -280,6 +295,12 @@ impl GvproxyConfig {
         self
     }
 
+    /// Set the guest MAC address the DHCP static lease is bound to.
+    pub fn with_guest_mac(mut self, mac: [u8; 6]) -> Self {
+        self.guest_mac = crate::net::constants::mac_to_string(&mac);
+        self
+    }
+
     /// Set custom MTU
     pub fn with_mtu(mut self, mtu: u16) -> Self {
         self.mtu = mtu;
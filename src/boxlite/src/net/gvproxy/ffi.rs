@@ -9,7 +9,8 @@ use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 use super::config::GvproxyConfig;
 use libgvproxy_sys::{
-    gvproxy_create, gvproxy_destroy, gvproxy_free_string, gvproxy_get_stats, gvproxy_get_version,
+    gvproxy_create, gvproxy_destroy, gvproxy_free_string, gvproxy_get_connections,
+    gvproxy_get_stats, gvproxy_get_version, gvproxy_set_rate_limit,
 };
 
 /// Create a new gvproxy instance with full configuration
@@ -79,6 +80,35 @@ pub fn destroy_instance(id: i64) -> BoxliteResult<()> {
     Ok(())
 }
 
+/// Cap the bandwidth of a gvproxy instance
+///
+/// # Arguments
+/// * `id` - Instance ID returned from `create_instance`
+/// * `ingress_bps` / `egress_bps` - Bytes per second to / from the guest;
+///   0 means unlimited
+///
+/// # Returns
+/// Ok(()) on success, error if the instance doesn't exist
+pub fn set_rate_limit(id: i64, ingress_bps: u64, egress_bps: u64) -> BoxliteResult<()> {
+    let result = unsafe { gvproxy_set_rate_limit(id, ingress_bps, egress_bps) };
+
+    if result != 0 {
+        return Err(BoxliteError::Network(format!(
+            "gvproxy_set_rate_limit failed for instance {}: code {}",
+            id, result
+        )));
+    }
+
+    tracing::info!(
+        id,
+        ingress_bps,
+        egress_bps,
+        "Set gvproxy rate limit via FFI"
+    );
+
+    Ok(())
+}
+
 /// Get the gvproxy version string
 ///
 /// # Returns
@@ -131,6 +161,35 @@ pub fn get_stats_json(id: i64) -> BoxliteResult<String> {
     Ok(json_str)
 }
 
+/// Get the per-connection TCP table for a gvproxy instance
+///
+/// # Arguments
+/// * `id` - Instance ID returned from `create_instance`
+///
+/// # Returns
+/// JSON array of connections (`[]` if the VirtualNetwork isn't initialized
+/// yet), or error if the instance doesn't exist
+pub fn get_connections_json(id: i64) -> BoxliteResult<String> {
+    let c_str = unsafe { gvproxy_get_connections(id) };
+
+    if c_str.is_null() {
+        return Err(BoxliteError::Network(format!(
+            "gvproxy_get_connections failed for instance {} (not found)",
+            id
+        )));
+    }
+
+    let json_str = unsafe { CStr::from_ptr(c_str) }
+        .to_str()
+        .map_err(|e| BoxliteError::Network(format!("Invalid UTF-8 in connections JSON: {}", e)))
+        .map(str::to_string);
+
+    // Free the string returned by CGO
+    unsafe { gvproxy_free_string(c_str) };
+
+    json_str
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,12 +4,13 @@
 //! Instances are automatically cleaned up when dropped.
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 use super::ffi;
 use super::logging;
-use super::stats::NetworkStats;
+use super::stats::{ConnectionInfo, NetworkStats};
 
 /// Safe wrapper for gvproxy library with automatic resource management
 ///
@@ -27,7 +28,8 @@ use super::stats::NetworkStats;
 ///
 /// ## Resource Management
 ///
-/// The instance automatically calls `gvproxy_destroy` when dropped, ensuring
+/// [`shutdown`](Self::shutdown) calls `gvproxy_destroy` explicitly; the
+/// instance also calls it when dropped if it was not shut down, ensuring
 /// proper cleanup of Go resources and Unix sockets.
 ///
 /// ## Thread Safety
@@ -45,6 +47,7 @@ use super::stats::NetworkStats;
 pub struct GvproxyInstance {
     id: i64,
     socket_path: PathBuf,
+    destroyed: AtomicBool,
 }
 
 impl GvproxyInstance {
@@ -64,27 +67,36 @@ impl GvproxyInstance {
         ca_cert_pem: Option<&str>,
         ca_key_pem: Option<&str>,
     ) -> BoxliteResult<Self> {
-        // Initialize logging callback (one-time setup)
-        logging::init_logging();
-
         // Derive gvproxy's control socket as a sibling of the data socket, so the
         // path is never plumbed through neutral config/layout/socket types.
         let control_socket_path = super::control_socket_path(&socket_path);
-        let mut config =
-            super::config::GvproxyConfig::new(socket_path.clone(), port_mappings.to_vec())
-                .with_control_socket_path(control_socket_path)
-                .with_allow_net(allow_net)
-                .with_secrets(secrets);
+        let mut config = super::config::GvproxyConfig::new(socket_path, port_mappings.to_vec())
+            .with_control_socket_path(control_socket_path)
+            .with_allow_net(allow_net)
+            .with_secrets(secrets);
 
         if let (Some(cert), Some(key)) = (ca_cert_pem, ca_key_pem) {
             config = config.with_ca(cert.to_string(), key.to_string());
         }
 
-        let id = ffi::create_instance(&config)?;
+        Self::create(&config)
+    }
+
+    /// Create the gvproxy instance described by `config`.
+    fn create(config: &super::config::GvproxyConfig) -> BoxliteResult<Self> {
+        // Initialize logging callback (one-time setup)
+        logging::init_logging();
+
+        let id = ffi::create_instance(config)?;
+        let socket_path = config.socket_path.clone();
 
         tracing::info!(id, ?socket_path, "Created GvproxyInstance");
 
-        Ok(Self { id, socket_path })
+        Ok(Self {
+            id,
+            socket_path,
+            destroyed: AtomicBool::new(false),
+        })
     }
 
     /// Unix socket path for the network tap interface.
@@ -104,30 +116,23 @@ impl GvproxyInstance {
     pub fn from_config(
         spec: &super::super::NetworkBackendSpec,
     ) -> BoxliteResult<(Self, super::super::NetworkBackendEndpoint)> {
-        let secrets = spec.secrets.iter().map(Into::into).collect();
-        let instance = Self::new(
-            spec.socket_path.clone(),
-            &spec.port_mappings,
-            spec.allow_net.clone(),
-            secrets,
-            spec.ca_cert_pem.as_deref(),
-            spec.ca_key_pem.as_deref(),
-        )?;
-
-        let connection_type = if cfg!(target_os = "macos") {
-            super::super::ConnectionType::UnixDgram
-        } else {
-            super::super::ConnectionType::UnixStream
-        };
-
-        use crate::net::constants::GUEST_MAC;
-        let endpoint = super::super::NetworkBackendEndpoint::UnixSocket {
-            path: spec.socket_path.clone(),
-            connection_type,
-            mac_address: GUEST_MAC,
-        };
-
-        Ok((instance, endpoint))
+        let instance = Self::create(&super::config::GvproxyConfig::from_spec(spec))?;
+        if let Some(limit) = spec.rate_limit {
+            instance.set_rate_limit(limit)?;
+        }
+
+        Ok((instance, super::endpoint(spec)))
+    }
+
+    /// Cap the bandwidth of the VM's NIC
+    ///
+    /// Replaces any previous limit; a direction left `None` is unlimited.
+    pub fn set_rate_limit(&self, limit: super::super::RateLimit) -> BoxliteResult<()> {
+        ffi::set_rate_limit(
+            self.id,
+            limit.ingress_bps.unwrap_or(0),
+            limit.egress_bps.unwrap_or(0),
+        )
     }
 
     /// Get network statistics from this gvproxy instance
@@ -159,6 +164,29 @@ impl GvproxyInstance {
         })
     }
 
+    /// Get the per-connection TCP table from this gvproxy instance
+    ///
+    /// Lists every TCP endpoint in the netstack with its addresses, state and
+    /// byte counts — for debugging connection pileups that the aggregate
+    /// [`get_stats`](Self::get_stats) counters only hint at.
+    ///
+    /// # Returns
+    ///
+    /// The connections (empty if the VirtualNetwork isn't initialized yet) or
+    /// an error if:
+    /// - Instance not found (already destroyed)
+    /// - The table is not a JSON array
+    pub fn get_connection_table(&self) -> BoxliteResult<Vec<ConnectionInfo>> {
+        let json_str = ffi::get_connections_json(self.id)?;
+
+        ConnectionInfo::parse_table(&json_str).map_err(|e| {
+            BoxliteError::Network(format!(
+                "Failed to parse connections JSON from gvproxy: {} (JSON: {})",
+                e, json_str
+            ))
+        })
+    }
+
     /// Get the gvproxy version string
     ///
     /// Returns the version of the gvproxy-bridge library.
@@ -180,6 +208,19 @@ impl GvproxyInstance {
         ffi::get_version()
     }
 
+    /// Destroy the gvproxy instance now instead of on drop.
+    ///
+    /// Idempotent: only the first call reaches the FFI layer, and its error
+    /// is returned; later calls return `Ok(())`.
+    pub fn shutdown(&self) -> BoxliteResult<()> {
+        if self.destroyed.swap(true, Ordering::AcqRel) {
+            return Ok(());
+        }
+        ffi::destroy_instance(self.id)?;
+        tracing::debug!(id = self.id, "Successfully destroyed gvproxy instance");
+        Ok(())
+    }
+
     /// Get the instance ID
     ///
     /// This is the internal handle used by the CGO layer.
@@ -192,13 +233,12 @@ impl Drop for GvproxyInstance {
     fn drop(&mut self) {
         tracing::debug!(id = self.id, "Dropping GvproxyInstance");
 
-        match ffi::destroy_instance(self.id) {
-            Ok(()) => tracing::debug!(id = self.id, "Successfully destroyed gvproxy instance"),
-            Err(e) => tracing::error!(
+        if let Err(e) = self.shutdown() {
+            tracing::error!(
                 id = self.id,
                 error = %e,
                 "Failed to destroy gvproxy instance"
-            ),
+            );
         }
     }
 }
@@ -238,6 +278,24 @@ mod tests {
         // Instance will be destroyed automatically when dropped
     }
 
+    #[test]
+    #[ignore] // Requires libgvproxy.dylib to be available
+    fn test_gvproxy_shutdown_is_idempotent() {
+        let instance = GvproxyInstance::new(
+            PathBuf::from("/tmp/test-gvproxy-shutdown.sock"),
+            &[],
+            Vec::new(),
+            Vec::new(),
+            None,
+            None,
+        )
+        .unwrap();
+
+        instance.shutdown().unwrap();
+        instance.shutdown().unwrap();
+        assert!(instance.get_stats().is_err(), "instance still alive");
+    }
+
     #[test]
     #[ignore] // Requires libgvproxy.dylib to be available
     fn test_multiple_instances() {
@@ -44,7 +44,7 @@ pub use instance::GvproxyInstance;
 #[cfg(feature = "gvproxy")]
 pub use logging::init_logging;
 pub use services::GvproxyBackend;
-pub use stats::{NetworkStats, TcpStats};
+pub use stats::{ConnectionInfo, NetworkStats, TcpStats};
 
 use std::path::{Path, PathBuf};
 
@@ -64,6 +64,22 @@ pub(crate) fn control_socket_path(data_socket: &Path) -> PathBuf {
     data_socket.with_file_name(CONTROL_SOCK)
 }
 
+/// The endpoint the engine connects the guest NIC to for a gvproxy started
+/// from `spec`.
+pub fn endpoint(spec: &super::NetworkBackendSpec) -> super::NetworkBackendEndpoint {
+    let connection_type = if cfg!(target_os = "macos") {
+        super::ConnectionType::UnixDgram
+    } else {
+        super::ConnectionType::UnixStream
+    };
+
+    super::NetworkBackendEndpoint::UnixSocket {
+        path: spec.socket_path.clone(),
+        connection_type,
+        mac_address: spec.guest_mac(),
+    }
+}
+
 /// Concrete [`NetworkBackendFactory`](super::NetworkBackendFactory) for gvproxy —
 /// produces a [`GvproxyBackend`] from the box's [`NetworkBackendConfig`].
 pub struct GvproxyFactory;
@@ -14,6 +14,8 @@
 
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use async_trait::async_trait;
@@ -28,8 +30,8 @@ use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::net::UnixStream;
 
 use crate::net::{
-    BoxInternalTunnel, DnsZoneSpec, Forward, NetworkBackend, NetworkBackendConfig,
-    NetworkBackendSpec, NetworkBackendStats, TransportProtocol,
+    BackendCapabilities, BoxInternalTunnel, DnsZoneSpec, Forward, NetworkBackend,
+    NetworkBackendConfig, NetworkBackendSpec, NetworkBackendStats, TransportProtocol,
 };
 
 /// Upper bound on a single control exchange. A bound-but-unserved socket (the
@@ -47,6 +49,9 @@ pub struct GvproxyBackend {
     config: NetworkBackendConfig,
     /// gvproxy's control socket (`gvproxy-ctl.sock`) — dialed for control.
     control_socket_path: PathBuf,
+    /// Set by [`shutdown`](NetworkBackend::shutdown); later control calls fail
+    /// without dialing a socket the stopping shim may still be serving.
+    shut_down: Arc<AtomicBool>,
 }
 
 impl GvproxyBackend {
@@ -58,9 +63,19 @@ impl GvproxyBackend {
         Self {
             control_socket_path: super::control_socket_path(&config.socket_path),
             config: config.clone(),
+            shut_down: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    fn ensure_running(&self) -> BoxliteResult<()> {
+        if self.shut_down.load(Ordering::Acquire) {
+            return Err(BoxliteError::Network(
+                "gvproxy backend is shut down".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// One-shot HTTP/1.1 request to the services socket. Returns `(status, body)`.
     async fn request(
         &self,
@@ -68,6 +83,7 @@ impl GvproxyBackend {
         path: &str,
         body: Option<String>,
     ) -> BoxliteResult<(u16, String)> {
+        self.ensure_running()?;
         let exchange = async move {
             let stream = UnixStream::connect(&self.control_socket_path)
                 .await
@@ -158,6 +174,8 @@ impl NetworkBackend for GvproxyBackend {
             secrets: cfg.secrets.clone(),
             ca_cert_pem: None,
             ca_key_pem: None,
+            rate_limit: cfg.rate_limit,
+            guest_mac: cfg.guest_mac,
         };
 
         // Mint the ephemeral MITM CA when secrets are configured. The cert+key
@@ -179,6 +197,21 @@ impl NetworkBackend for GvproxyBackend {
         spec
     }
 
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            // `/stats` — aggregate bytes + TCP health only.
+            metrics: true,
+            per_connection_stats: false,
+            // `/services/forwarder/{expose,unexpose}`, tcp and udp.
+            runtime_port_forwarding: true,
+            udp: true,
+            // gvisor-tap-vsock's virtual network is IPv4-only.
+            ipv6: false,
+            // Token buckets on the VM's NIC connection.
+            rate_limiting: true,
+        }
+    }
+
     async fn expose(
         &self,
         local: &str,
@@ -257,6 +290,7 @@ impl NetworkBackend for GvproxyBackend {
         // so we speak it raw (not via hyper): send gvproxy's request, read its
         // literal "OK" ack, and the socket becomes a raw pipe to the guest target.
         // Mirrors gvproxy's own `transport.Tunnel` (`POST /tunnel?ip=&port=`).
+        self.ensure_running()?;
         let ctl = self.control_socket_path.clone();
         let handshake = async {
             let mut stream = UnixStream::connect(&ctl).await.map_err(|e| {
@@ -405,6 +439,8 @@ mod tests {
             allow_net: vec!["example.com".to_string()],
             secrets: Vec::new(),
             ca_dir: PathBuf::from("/tmp/bl-box/does-not-exist"),
+            rate_limit: None,
+            guest_mac: None,
         };
         let spec = GvproxyBackend::from_config(&config).spec();
         assert_eq!(spec.port_mappings, config.port_mappings);
@@ -414,6 +450,91 @@ mod tests {
         assert!(spec.ca_key_pem.is_none());
     }
 
+    #[test]
+    fn spec_carries_rate_limit() {
+        let limit = crate::net::RateLimit {
+            ingress_bps: Some(1 << 20),
+            egress_bps: None,
+        };
+        let config = NetworkBackendConfig {
+            port_mappings: Vec::new(),
+            socket_path: PathBuf::from("/tmp/bl-box/net.sock"),
+            allow_net: Vec::new(),
+            secrets: Vec::new(),
+            ca_dir: PathBuf::from("/tmp/bl-box/ca"),
+            rate_limit: Some(limit),
+            guest_mac: None,
+        };
+        let spec = GvproxyBackend::from_config(&config).spec();
+        assert_eq!(spec.rate_limit, Some(limit));
+
+        let json = serde_json::to_string(&spec).unwrap();
+        let back: NetworkBackendSpec = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.rate_limit, Some(limit));
+    }
+
+    #[test]
+    fn guest_mac_override_reaches_endpoint_and_gvproxy_config() {
+        let mac = [0x02, 0x42, 0xac, 0x11, 0x00, 0x07];
+        let config = NetworkBackendConfig {
+            port_mappings: vec![(8080, 80)],
+            socket_path: PathBuf::from("/tmp/bl-box/net.sock"),
+            allow_net: Vec::new(),
+            secrets: Vec::new(),
+            ca_dir: PathBuf::from("/tmp/bl-box/ca"),
+            rate_limit: None,
+            guest_mac: Some(mac),
+        };
+        let spec = GvproxyBackend::from_config(&config).spec();
+        let json = serde_json::to_string(&spec).unwrap();
+        let spec: NetworkBackendSpec = serde_json::from_str(&json).unwrap();
+
+        let crate::net::NetworkBackendEndpoint::UnixSocket { mac_address, .. } =
+            super::super::endpoint(&spec);
+        assert_eq!(mac_address, mac);
+
+        let gvproxy = serde_json::to_value(super::super::GvproxyConfig::from_spec(&spec)).unwrap();
+        assert_eq!(gvproxy["guest_mac"], "02:42:ac:11:00:07");
+        assert_eq!(gvproxy["port_mappings"][0]["host_port"], 8080);
+
+        // Without an override both sides keep the shared default.
+        let spec = GvproxyBackend::from_config(&NetworkBackendConfig {
+            guest_mac: None,
+            ..config
+        })
+        .spec();
+        let crate::net::NetworkBackendEndpoint::UnixSocket { mac_address, .. } =
+            super::super::endpoint(&spec);
+        assert_eq!(mac_address, crate::net::constants::GUEST_MAC);
+        assert_eq!(
+            super::super::GvproxyConfig::from_spec(&spec).guest_mac,
+            crate::net::constants::GUEST_MAC_STRING
+        );
+    }
+
+    #[test]
+    fn capabilities_match_implemented_control_methods() {
+        let config = NetworkBackendConfig {
+            port_mappings: Vec::new(),
+            socket_path: PathBuf::from("/tmp/bl-box/net.sock"),
+            allow_net: Vec::new(),
+            secrets: Vec::new(),
+            ca_dir: PathBuf::from("/tmp/bl-box/ca"),
+            rate_limit: None,
+            guest_mac: None,
+        };
+        let caps = GvproxyBackend::from_config(&config).capabilities();
+
+        // stats() and expose()/unexpose() are implemented (tested above).
+        assert!(caps.metrics);
+        assert!(caps.runtime_port_forwarding);
+        assert!(caps.udp);
+        // Only aggregate counters, IPv4-only; the NIC can be rate limited.
+        assert!(!caps.per_connection_stats);
+        assert!(!caps.ipv6);
+        assert!(caps.rate_limiting);
+    }
+
     #[test]
     fn parse_stats_maps_gvproxy_stats_to_typed_getters() {
         // gvproxy /stats (PascalCase, bytes at root + TCP group) → typed getters.
@@ -483,6 +604,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: Vec::new(),
             ca_dir: dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
         };
         (
             GvproxyBackend::from_config(&config),
@@ -553,6 +676,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: vec![test_secret()],
             ca_dir: ca_dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
         };
         let spec = GvproxyBackend::from_config(&config).spec();
         assert!(
@@ -585,6 +710,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: vec![test_secret()],
             ca_dir,
+            rate_limit: None,
+            guest_mac: None,
         };
 
         let spec = GvproxyBackend::from_config(&config).spec();
@@ -612,6 +739,29 @@ mod tests {
         assert!(err.contains(&ctl.display().to_string()), "err: {err}");
     }
 
+    #[tokio::test]
+    async fn shutdown_is_idempotent_and_ends_control_calls() {
+        let dir = tempfile::Builder::new()
+            .prefix("bl-svctest-shutdown-")
+            .tempdir_in("/tmp")
+            .unwrap();
+        let (backend, ctl, _) = test_backend(&dir);
+        let server = spawn_services_response(&ctl, 200, "[]");
+        assert!(backend.list_forwards().await.unwrap().is_empty());
+        server.await.unwrap();
+
+        backend.shutdown().unwrap();
+        backend.shutdown().unwrap();
+
+        // Clones share the shutdown; nothing dials the socket any more.
+        let clone = backend.clone();
+        let err = clone.list_forwards().await.unwrap_err();
+        assert!(format!("{err}").contains("shut down"), "err: {err}");
+        let target: SocketAddr = "192.168.127.2:8080".parse().unwrap();
+        let err = backend.tunnel(target).await.unwrap_err();
+        assert!(format!("{err}").contains("shut down"), "err: {err}");
+    }
+
     #[tokio::test]
     async fn tunnel_reports_missing_services_socket_path() {
         let dir = tempfile::Builder::new()
@@ -893,6 +1043,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: Vec::new(),
             ca_dir: dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
         };
         let target: SocketAddr = "192.168.127.2:8080".parse().unwrap();
         let mut tunnel = GvproxyBackend::from_config(&config)
@@ -943,6 +1095,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: Vec::new(),
             ca_dir: dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
         };
         let target: SocketAddr = "192.168.127.2:8080".parse().unwrap();
         let err = GvproxyBackend::from_config(&config)
@@ -982,6 +1136,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: Vec::new(),
             ca_dir: dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
         };
         let ctl = GvproxyBackend::from_config(&config);
 
@@ -1021,6 +1177,59 @@ mod tests {
         );
     }
 
+    /// Over a live gvproxy instance: exposing a host port twice fails with a
+    /// `Network` error and leaves the first forward in place. Requires the
+    /// libgvproxy dylib; run with `--ignored`.
+    #[cfg(feature = "gvproxy")]
+    #[tokio::test]
+    #[ignore]
+    async fn double_expose_of_host_port_fails_over_services_socket() {
+        use crate::net::gvproxy::GvproxyInstance;
+        use std::time::Duration;
+
+        let dir = tempfile::Builder::new()
+            .prefix("bl-dup-test-")
+            .tempdir_in("/tmp")
+            .unwrap();
+        let net_sock = dir.path().join("net.sock");
+        let _instance =
+            GvproxyInstance::new(net_sock.clone(), &[], Vec::new(), Vec::new(), None, None)
+                .expect("create gvproxy instance");
+
+        let config = NetworkBackendConfig {
+            port_mappings: Vec::new(),
+            socket_path: net_sock.clone(),
+            allow_net: Vec::new(),
+            secrets: Vec::new(),
+            ca_dir: dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
+        };
+        let ctl = GvproxyBackend::from_config(&config);
+        for _ in 0..50 {
+            if ctl.list_forwards().await.is_ok() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+
+        crate::net::expose_tcp_port(&ctl, 18081, 80)
+            .await
+            .expect("first expose");
+        let err = crate::net::expose_tcp_port(&ctl, 18081, 81)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, BoxliteError::Network(_)), "err: {err:?}");
+
+        let forwards = ctl.list_forwards().await.unwrap();
+        let local = crate::net::forward_local(18081);
+        assert_eq!(forwards.iter().filter(|f| f.local == local).count(), 1);
+
+        crate::net::unexpose_tcp_port(&ctl, 18081)
+            .await
+            .expect("unexpose");
+    }
+
     /// End-to-end over a live gvproxy instance: dial `/tunnel` and verify the
     /// `"OK"` handshake. gvproxy writes `"OK"` *before* it dials the guest, so the
     /// handshake completes even with no guest listening — enough to exercise the
@@ -1047,6 +1256,8 @@ mod tests {
             allow_net: Vec::new(),
             secrets: Vec::new(),
             ca_dir: dir.path().to_path_buf(),
+            rate_limit: None,
+            guest_mac: None,
         };
         let backend = GvproxyBackend::from_config(&config);
 
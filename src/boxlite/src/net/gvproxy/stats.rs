@@ -94,10 +94,67 @@ impl NetworkStats {
     }
 }
 
+/// One TCP connection from gvproxy's per-connection table.
+///
+/// Addresses are from the netstack's point of view: `local_addr` is the
+/// destination the guest dialed, `remote_addr` is the guest. `bytes_in` counts
+/// bytes received from the guest, `bytes_out` bytes sent to it; both are 0 for
+/// connections not relayed by the bridge's own forwarder.
+///
+/// Every field defaults when missing, so a partial row still parses.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ConnectionInfo {
+    /// Netstack-side address, `ip:port`
+    pub local_addr: String,
+
+    /// Guest-side address, `ip:port`
+    pub remote_addr: String,
+
+    /// TCP state name (e.g. `ESTABLISHED`)
+    pub state: String,
+
+    /// Bytes received from the guest
+    pub bytes_in: u64,
+
+    /// Bytes sent to the guest
+    pub bytes_out: u64,
+}
+
+impl ConnectionInfo {
+    /// Parses the connection table (a JSON array) from gvproxy.
+    ///
+    /// Malformed rows (non-objects, wrong field types) are skipped rather than
+    /// failing the whole table; only a non-array document is an error.
+    pub fn parse_table(json: &str) -> Result<Vec<Self>, serde_json::Error> {
+        let rows: Vec<serde_json::Value> = serde_json::from_str(json)?;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| match serde_json::from_value(row) {
+                Ok(conn) => Some(conn),
+                Err(e) => {
+                    tracing::debug!(error = %e, "Skipping malformed gvproxy connection row");
+                    None
+                }
+            })
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Table as produced by `gvproxy_get_connections`, with broken rows mixed in.
+    const CONNECTIONS_FIXTURE: &str = r#"[
+        {"local_addr":"93.184.216.34:443","remote_addr":"192.168.127.2:40112","state":"ESTABLISHED","bytes_in":517,"bytes_out":4096},
+        {"local_addr":"192.168.127.1:53","remote_addr":"192.168.127.2:40113","state":"TIME-WAIT"},
+        {"local_addr":"10.0.0.1:80","bytes_in":"lots"},
+        "not an object",
+        null,
+        42
+    ]"#;
+
     #[test]
     fn test_deserialize_network_stats() {
         let json = r#"{
@@ -123,6 +180,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_connection_table() {
+        let conns = ConnectionInfo::parse_table(CONNECTIONS_FIXTURE).unwrap();
+        assert_eq!(conns.len(), 2);
+        assert_eq!(
+            conns[0],
+            ConnectionInfo {
+                local_addr: "93.184.216.34:443".into(),
+                remote_addr: "192.168.127.2:40112".into(),
+                state: "ESTABLISHED".into(),
+                bytes_in: 517,
+                bytes_out: 4096,
+            }
+        );
+        // Partial row: missing counters default to zero.
+        assert_eq!(conns[1].state, "TIME-WAIT");
+        assert_eq!(conns[1].bytes_in, 0);
+        assert_eq!(conns[1].bytes_out, 0);
+    }
+
+    #[test]
+    fn test_parse_connection_table_edge_cases() {
+        assert!(ConnectionInfo::parse_table("[]").unwrap().is_empty());
+        assert!(ConnectionInfo::parse_table("{}").is_err());
+        assert!(ConnectionInfo::parse_table("[{").is_err());
+    }
+
     #[test]
     fn test_stats_equality() {
         // Test uses snake_case field names (Rust side)
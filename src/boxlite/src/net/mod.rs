@@ -27,8 +27,12 @@ pub mod constants;
 pub mod socket_path;
 
 pub mod gvproxy;
+#[cfg(feature = "passt-backend")]
+pub mod passt;
 
 pub use gvproxy::GvproxyBackend;
+#[cfg(feature = "passt-backend")]
+pub use passt::PasstBackend;
 
 /// How the Box connects to the network backend.
 ///
@@ -68,6 +72,23 @@ pub struct NetworkBackendConfig {
     /// Directory in which to mint the ephemeral MITM CA — used only when
     /// `secrets` is non-empty. The backend mints the CA in [`NetworkBackend::spec`].
     pub ca_dir: PathBuf,
+    /// Bandwidth cap for the box's NIC. Requires
+    /// [`BackendCapabilities::rate_limiting`].
+    pub rate_limit: Option<RateLimit>,
+    /// Guest NIC MAC overriding [`constants::GUEST_MAC`].
+    pub guest_mac: Option<[u8; 6]>,
+}
+
+/// Bandwidth cap for a box's network, in bytes per second.
+///
+/// `ingress_bps` limits traffic to the guest, `egress_bps` traffic from it.
+/// `None` leaves that direction unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RateLimit {
+    #[serde(default)]
+    pub ingress_bps: Option<u64>,
+    #[serde(default)]
+    pub egress_bps: Option<u64>,
 }
 
 /// The wire blob a [`NetworkBackend`] produces (via [`NetworkBackend::spec`]) for
@@ -95,6 +116,19 @@ pub struct NetworkBackendSpec {
     /// PEM-encoded MITM CA private key (PKCS8, minted when secrets are configured).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ca_key_pem: Option<String>,
+    /// Bandwidth cap applied when the backend server starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimit>,
+    /// Guest NIC MAC overriding [`constants::GUEST_MAC`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guest_mac: Option<[u8; 6]>,
+}
+
+impl NetworkBackendSpec {
+    /// MAC address of the guest NIC, which the backend's DHCP lease must match.
+    pub fn guest_mac(&self) -> [u8; 6] {
+        self.guest_mac.unwrap_or(constants::GUEST_MAC)
+    }
 }
 
 impl std::fmt::Debug for NetworkBackendSpec {
@@ -112,6 +146,8 @@ impl std::fmt::Debug for NetworkBackendSpec {
                 "ca_key_pem",
                 &self.ca_key_pem.as_ref().map(|_| "[REDACTED]"),
             )
+            .field("rate_limit", &self.rate_limit)
+            .field("guest_mac", &self.guest_mac)
             .finish()
     }
 }
@@ -219,6 +255,27 @@ impl NetworkBackendStats {
     }
 }
 
+/// What a [`NetworkBackend`] actually implements, so callers can check before
+/// calling a control method instead of probing for `Unsupported`.
+///
+/// Produced by [`NetworkBackend::capabilities`]. A backend that doesn't override
+/// it reports everything unsupported (the [`Default`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BackendCapabilities {
+    /// [`NetworkBackend::stats`] returns aggregate counters.
+    pub metrics: bool,
+    /// Stats can be broken down per TCP connection.
+    pub per_connection_stats: bool,
+    /// [`NetworkBackend::expose`]/[`NetworkBackend::unexpose`] work on a running box.
+    pub runtime_port_forwarding: bool,
+    /// Forwards and guest traffic may use UDP, not just TCP.
+    pub udp: bool,
+    /// The guest network carries IPv6.
+    pub ipv6: bool,
+    /// The backend can cap guest bandwidth.
+    pub rate_limiting: bool,
+}
+
 /// The transport backing a [`BoxInternalTunnel`] — a small, closed set (one variant per
 /// transport). Only the local gvproxy unix socket exists today; a cloud WS/TLS
 /// variant lands with the cloud data plane. Keeping the concrete type (rather than
@@ -359,6 +416,11 @@ pub trait NetworkBackend: Send + Sync + std::fmt::Debug {
     /// backend-specific material (e.g. gvproxy's MITM CA).
     fn spec(&self) -> NetworkBackendSpec;
 
+    /// Describe which optional features this backend supports.
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities::default()
+    }
+
     /// Add a forward: bind host `local` (`ip:port`) → guest `remote` (`ip:port`).
     async fn expose(
         &self,
@@ -410,16 +472,77 @@ pub trait NetworkBackend: Send + Sync + std::fmt::Debug {
     async fn tunnel(&self, _target: SocketAddr) -> BoxliteResult<BoxInternalTunnel> {
         Err(control_unsupported("tunnel"))
     }
+
+    /// Tear the backend down. The box calls this on stop, after the VM is
+    /// stopped and before the backend is dropped, so teardown happens in a
+    /// fixed order rather than whenever the last reference goes away.
+    ///
+    /// Idempotent: calls after the first return `Ok(())`. Backends that hold
+    /// nothing to release inherit the no-op.
+    fn shutdown(&self) -> BoxliteResult<()> {
+        Ok(())
+    }
+}
+
+/// Host bind address for a forwarded host port — all interfaces, like the
+/// port mappings a box boots with, so both kinds share one key space.
+pub(crate) fn forward_local(host_port: u16) -> String {
+    format!("0.0.0.0:{host_port}")
+}
+
+/// Forward host `host_port` to the guest's `guest_port` over TCP.
+///
+/// Fails with `Network` when the host port is already forwarded (at boot or
+/// at runtime) or the backend can't bind it. The backend rejects duplicates
+/// itself; the up-front check only makes the common case's error readable.
+pub(crate) async fn expose_tcp_port(
+    backend: &dyn NetworkBackend,
+    host_port: u16,
+    guest_port: u16,
+) -> BoxliteResult<()> {
+    let local = forward_local(host_port);
+    if backend
+        .list_forwards()
+        .await?
+        .iter()
+        .any(|forward| forward.local == local)
+    {
+        return Err(BoxliteError::Network(format!(
+            "host port {host_port} is already forwarded"
+        )));
+    }
+    let remote = format!("{}:{guest_port}", constants::GUEST_IP);
+    backend
+        .expose(&local, &remote, TransportProtocol::Tcp)
+        .await
+}
+
+/// Remove the TCP forward bound at host `host_port`.
+pub(crate) async fn unexpose_tcp_port(
+    backend: &dyn NetworkBackend,
+    host_port: u16,
+) -> BoxliteResult<()> {
+    backend
+        .unexpose(&forward_local(host_port), TransportProtocol::Tcp)
+        .await
 }
 
 /// The protocol type for network connections.
-#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum ConnectionType {
     /// Unix stream socket (SOCK_STREAM) - used by passt, socket_vmnet, libslirp, gvproxy (Linux)
     UnixStream,
 
     /// Unix datagram socket (SOCK_DGRAM) - used by gvproxy (macOS)
     UnixDgram,
+
+    /// vhost-user socket: the backend runs the virtio-net data path itself
+    /// and the engine only negotiates features and hands over guest memory.
+    ///
+    /// Of the backends boxlite can run, only passt (`--vhost-user`) can
+    /// serve one; gvproxy cannot. No engine attaches it yet — libkrun has no
+    /// vhost-user net frontend — so engines reject it with `Unsupported`.
+    VhostUser,
 }
 
 /// Abstract factory for a box's host-side network backend.
@@ -443,10 +566,41 @@ impl NetworkBackendFactory for NoBackendFactory {
     }
 }
 
+/// Factory that tries each inner factory in priority order and returns the
+/// first backend produced. Lets a backend that depends on the host (e.g. an
+/// external binary) decline with `None` and fall through to the next.
+pub struct FallbackFactory {
+    factories: Vec<Arc<dyn NetworkBackendFactory>>,
+}
+
+impl FallbackFactory {
+    /// Build a factory trying `factories` in order.
+    pub fn new(factories: Vec<Arc<dyn NetworkBackendFactory>>) -> Self {
+        Self { factories }
+    }
+}
+
+impl NetworkBackendFactory for FallbackFactory {
+    fn create(&self, config: &NetworkBackendConfig) -> Option<Box<dyn NetworkBackend>> {
+        self.factories.iter().find_map(|f| f.create(config))
+    }
+}
+
 /// The process's default factory — the single composition root where the
-/// concrete factory is chosen for the compiled-in backend.
+/// concrete factory is chosen for the compiled-in backends. gvproxy comes
+/// first; passt (with `passt-backend`) is the fallback.
 pub fn default_factory() -> Arc<dyn NetworkBackendFactory> {
-    Arc::new(gvproxy::GvproxyFactory)
+    #[cfg(feature = "passt-backend")]
+    {
+        Arc::new(FallbackFactory::new(vec![
+            Arc::new(gvproxy::GvproxyFactory),
+            Arc::new(passt::PasstFactory),
+        ]))
+    }
+    #[cfg(not(feature = "passt-backend"))]
+    {
+        Arc::new(gvproxy::GvproxyFactory)
+    }
 }
 
 #[cfg(test)]
@@ -466,6 +620,8 @@ mod tests {
             secrets: Vec::new(),
             ca_cert_pem: Some(cert_sentinel.to_string()),
             ca_key_pem: Some(key_sentinel.to_string()),
+            rate_limit: None,
+            guest_mac: None,
         };
 
         let rendered = format!("{:?}", spec);
@@ -499,6 +655,8 @@ mod tests {
             secrets: Vec::new(),
             ca_cert_pem: Some("CERTDATA".to_string()),
             ca_key_pem: Some("KEYDATA".to_string()),
+            rate_limit: None,
+            guest_mac: None,
         };
         let json = serde_json::to_string(&spec).unwrap();
         let back: NetworkBackendSpec = serde_json::from_str(&json).unwrap();
@@ -517,6 +675,7 @@ mod tests {
         assert!(spec.secrets.is_empty());
         assert!(spec.ca_cert_pem.is_none());
         assert!(spec.ca_key_pem.is_none());
+        assert!(spec.rate_limit.is_none());
     }
 
     #[test]
@@ -527,6 +686,8 @@ mod tests {
             allow_net: vec!["example.com".to_string()],
             secrets: Vec::new(),
             ca_dir: PathBuf::from("/tmp/default-factory/ca"),
+            rate_limit: None,
+            guest_mac: None,
         };
 
         let backend = default_factory()
@@ -609,6 +770,8 @@ mod tests {
                 secrets: Vec::new(),
                 ca_cert_pem: None,
                 ca_key_pem: None,
+                rate_limit: None,
+                guest_mac: None,
             }
         }
     }
@@ -658,6 +821,114 @@ mod tests {
         assert_unsupported(backend.tunnel(target).await, "tunnel");
     }
 
+    #[test]
+    fn default_capabilities_report_nothing_supported() {
+        assert_eq!(
+            UnsupportedBackend.capabilities(),
+            BackendCapabilities::default()
+        );
+        assert!(!UnsupportedBackend.capabilities().metrics);
+    }
+
+    /// In-memory forwarder that, like gvproxy's, rejects a second forward on
+    /// the same local address.
+    #[derive(Debug, Default)]
+    struct ForwardingBackend {
+        forwards: std::sync::Mutex<Vec<Forward>>,
+    }
+
+    #[async_trait::async_trait]
+    impl NetworkBackend for ForwardingBackend {
+        fn name(&self) -> &'static str {
+            "forwarding-test"
+        }
+
+        fn spec(&self) -> NetworkBackendSpec {
+            UnsupportedBackend.spec()
+        }
+
+        async fn expose(
+            &self,
+            local: &str,
+            remote: &str,
+            protocol: TransportProtocol,
+        ) -> BoxliteResult<()> {
+            let mut forwards = self.forwards.lock().unwrap();
+            if forwards.iter().any(|f| f.local == local) {
+                return Err(BoxliteError::Network("proxy already running".into()));
+            }
+            forwards.push(Forward {
+                local: local.to_string(),
+                remote: remote.to_string(),
+                protocol: protocol.as_str().to_string(),
+            });
+            Ok(())
+        }
+
+        async fn unexpose(&self, local: &str, _protocol: TransportProtocol) -> BoxliteResult<()> {
+            let mut forwards = self.forwards.lock().unwrap();
+            let before = forwards.len();
+            forwards.retain(|f| f.local != local);
+            if forwards.len() == before {
+                return Err(BoxliteError::Network(format!("no forward on {local}")));
+            }
+            Ok(())
+        }
+
+        async fn list_forwards(&self) -> BoxliteResult<Vec<Forward>> {
+            Ok(self.forwards.lock().unwrap().clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn expose_tcp_port_adds_forward_to_guest_and_unexpose_removes_it() {
+        let backend = ForwardingBackend::default();
+
+        expose_tcp_port(&backend, 18080, 80).await.unwrap();
+        assert_eq!(
+            backend.list_forwards().await.unwrap(),
+            vec![Forward {
+                local: "0.0.0.0:18080".to_string(),
+                remote: format!("{}:80", constants::GUEST_IP),
+                protocol: "tcp".to_string(),
+            }]
+        );
+
+        unexpose_tcp_port(&backend, 18080).await.unwrap();
+        assert!(backend.list_forwards().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn expose_tcp_port_rejects_an_already_forwarded_host_port() {
+        let backend = ForwardingBackend::default();
+        expose_tcp_port(&backend, 18080, 80).await.unwrap();
+
+        let err = expose_tcp_port(&backend, 18080, 81).await.unwrap_err();
+        assert!(matches!(err, BoxliteError::Network(_)), "err: {err:?}");
+        assert!(err.to_string().contains("already forwarded"), "err: {err}");
+        assert_eq!(backend.list_forwards().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_exposes_of_one_host_port_admit_exactly_one() {
+        let backend = Arc::new(ForwardingBackend::default());
+        let tasks: Vec<_> = (0..8)
+            .map(|i| {
+                let backend = Arc::clone(&backend);
+                tokio::spawn(async move { expose_tcp_port(&*backend, 18080, 80 + i).await })
+            })
+            .collect();
+
+        let mut admitted = 0;
+        for task in tasks {
+            if task.await.unwrap().is_ok() {
+                admitted += 1;
+            }
+        }
+        assert_eq!(admitted, 1);
+        assert_eq!(backend.list_forwards().await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn box_tunnel_pipes_bytes_and_carries_peer() {
         use tokio::io::{AsyncReadExt, AsyncWriteExt};
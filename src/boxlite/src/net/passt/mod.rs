@@ -0,0 +1,375 @@
+//! passt ("plug a simple socket transport") integration.
+//!
+//! [`PasstBackend`] runs the external `passt` binary as the box's user-mode
+//! network stack. passt serves the guest's virtio-net frames over a Unix
+//! stream socket (the qemu socket protocol), so the engine attaches it the same
+//! way it attaches gvproxy on Linux.
+//!
+//! Unlike gvproxy, passt has no control API: ports are forwarded at spawn time
+//! only, and runtime control methods inherit the trait's `Unsupported`
+//! defaults. Traffic counters are read back from passt's `--log-file`.
+//!
+//! Compiled only with the `passt-backend` feature. [`PasstFactory`] yields a
+//! backend only when `passt` is on `PATH`, so it sits after gvproxy in
+//! [`default_factory`](super::default_factory)'s priority order.
+
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+use crate::net::constants::{GATEWAY_IP, GUEST_IP, GUEST_MAC};
+use crate::net::{
+    BackendCapabilities, ConnectionType, NetworkBackend, NetworkBackendConfig,
+    NetworkBackendEndpoint, NetworkBackendFactory, NetworkBackendSpec, NetworkBackendStats,
+};
+
+/// Name of the passt executable looked up on `PATH`.
+const PASST_BINARY: &str = "passt";
+
+/// Filename of passt's log — a sibling of the data socket (`net.sock`).
+const LOG_FILE: &str = "passt.log";
+
+/// How long [`PasstBackend::start`] waits for passt to bind its socket.
+const SOCKET_WAIT: Duration = Duration::from_secs(5);
+const SOCKET_POLL: Duration = Duration::from_millis(20);
+
+/// passt backend — spawns and owns the `passt` process for one box.
+///
+/// [`start`](Self::start) spawns passt listening on the box's data socket and
+/// returns the [`NetworkBackendEndpoint`] the engine wires into the NIC. The
+/// process is killed when the backend is dropped.
+#[derive(Debug)]
+pub struct PasstBackend {
+    config: NetworkBackendConfig,
+    /// passt's `--log-file`, parsed by [`metrics`](Self::metrics).
+    log_path: PathBuf,
+    child: Mutex<Option<Child>>,
+}
+
+impl PasstBackend {
+    /// Build the passt backend for the box described by `config`. Nothing is
+    /// spawned until [`start`](Self::start).
+    pub fn from_config(config: &NetworkBackendConfig) -> Self {
+        Self {
+            log_path: config.socket_path.with_file_name(LOG_FILE),
+            config: config.clone(),
+            child: Mutex::new(None),
+        }
+    }
+
+    /// Spawn passt and wait for it to bind the data socket.
+    ///
+    /// Fails with [`BoxliteError::Network`] if `passt` is not on `PATH`, if
+    /// the process cannot be spawned, or if it exits before binding.
+    pub async fn start(&self) -> BoxliteResult<NetworkBackendEndpoint> {
+        let binary = find_on_path(PASST_BINARY).ok_or_else(|| {
+            BoxliteError::Network(format!(
+                "`{PASST_BINARY}` not found on PATH; install passt \
+                 (https://passt.top, packaged as `passt` on most distributions) \
+                 or build with the gvproxy backend"
+            ))
+        })?;
+
+        // A stale socket from a previous run would satisfy the wait below.
+        let _ = std::fs::remove_file(&self.config.socket_path);
+
+        let child = Command::new(&binary)
+            .args(self.args())
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| {
+                BoxliteError::Network(format!("failed to spawn {}: {e}", binary.display()))
+            })?;
+        tracing::debug!(
+            pid = child.id(),
+            socket = %self.config.socket_path.display(),
+            "Spawned passt"
+        );
+        *self.child.lock().unwrap() = Some(child);
+
+        let deadline = tokio::time::Instant::now() + SOCKET_WAIT;
+        while !self.config.socket_path.exists() {
+            if let Some(status) = self.try_wait() {
+                return Err(BoxliteError::Network(format!(
+                    "passt exited before binding {} ({status}); see {}",
+                    self.config.socket_path.display(),
+                    self.log_path.display()
+                )));
+            }
+            if tokio::time::Instant::now() >= deadline {
+                self.kill();
+                return Err(BoxliteError::Network(format!(
+                    "passt did not bind {} within {:?}",
+                    self.config.socket_path.display(),
+                    SOCKET_WAIT
+                )));
+            }
+            tokio::time::sleep(SOCKET_POLL).await;
+        }
+
+        Ok(self.endpoint())
+    }
+
+    /// The endpoint the engine connects the guest NIC to.
+    pub fn endpoint(&self) -> NetworkBackendEndpoint {
+        NetworkBackendEndpoint::UnixSocket {
+            path: self.config.socket_path.clone(),
+            connection_type: ConnectionType::UnixStream,
+            mac_address: self.config.guest_mac.unwrap_or(GUEST_MAC),
+        }
+    }
+
+    /// Traffic counters parsed from passt's log file.
+    pub fn metrics(&self) -> BoxliteResult<NetworkBackendStats> {
+        let log = std::fs::read_to_string(&self.log_path).map_err(|e| {
+            BoxliteError::Network(format!(
+                "failed to read passt log {}: {e}",
+                self.log_path.display()
+            ))
+        })?;
+        Ok(parse_log_counters(&log))
+    }
+
+    /// Command-line arguments for the passt process.
+    fn args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--foreground".to_string(),
+            "--socket".to_string(),
+            self.config.socket_path.display().to_string(),
+            "--log-file".to_string(),
+            self.log_path.display().to_string(),
+            "--address".to_string(),
+            GUEST_IP.to_string(),
+            "--gateway".to_string(),
+            GATEWAY_IP.to_string(),
+        ];
+        for (host_port, guest_port) in &self.config.port_mappings {
+            args.push("--tcp-ports".to_string());
+            args.push(format!("{host_port}:{guest_port}"));
+        }
+        args
+    }
+
+    fn try_wait(&self) -> Option<std::process::ExitStatus> {
+        let mut child = self.child.lock().unwrap();
+        child.as_mut().and_then(|c| c.try_wait().ok().flatten())
+    }
+
+    fn kill(&self) {
+        if let Some(mut child) = self.child.lock().unwrap().take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for PasstBackend {
+    fn drop(&mut self) {
+        self.kill();
+    }
+}
+
+#[async_trait]
+impl NetworkBackend for PasstBackend {
+    fn name(&self) -> &'static str {
+        "passt"
+    }
+
+    fn spec(&self) -> NetworkBackendSpec {
+        NetworkBackendSpec {
+            port_mappings: self.config.port_mappings.clone(),
+            socket_path: self.config.socket_path.clone(),
+            allow_net: self.config.allow_net.clone(),
+            secrets: Vec::new(),
+            ca_cert_pem: None,
+            ca_key_pem: None,
+            rate_limit: None,
+            guest_mac: self.config.guest_mac,
+        }
+    }
+
+    fn capabilities(&self) -> BackendCapabilities {
+        BackendCapabilities {
+            // Aggregate counters from the log file.
+            metrics: true,
+            per_connection_stats: false,
+            // Forwards are fixed at spawn time.
+            runtime_port_forwarding: false,
+            udp: true,
+            ipv6: true,
+            rate_limiting: false,
+        }
+    }
+
+    async fn stats(&self) -> BoxliteResult<NetworkBackendStats> {
+        self.metrics()
+    }
+
+    fn shutdown(&self) -> BoxliteResult<()> {
+        self.kill();
+        Ok(())
+    }
+}
+
+/// Concrete [`NetworkBackendFactory`] for passt — produces a [`PasstBackend`]
+/// when the `passt` binary is on `PATH`, and `None` otherwise so a
+/// [`FallbackFactory`](super::FallbackFactory) moves on.
+pub struct PasstFactory;
+
+impl NetworkBackendFactory for PasstFactory {
+    fn create(&self, config: &NetworkBackendConfig) -> Option<Box<dyn NetworkBackend>> {
+        find_on_path(PASST_BINARY)?;
+        Some(Box::new(PasstBackend::from_config(config)) as Box<dyn NetworkBackend>)
+    }
+}
+
+/// First executable named `name` in `PATH`.
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(name))
+        .find(|candidate| is_executable(candidate))
+}
+
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Parse `key=value` counters from passt's log. Counters are cumulative, so
+/// the last occurrence of each key wins; unknown keys are ignored.
+fn parse_log_counters(log: &str) -> NetworkBackendStats {
+    let mut stats = NetworkBackendStats {
+        bytes_sent: 0,
+        bytes_received: 0,
+        tcp_established: 0,
+        tcp_failed_connections: 0,
+        tcp_retransmits: 0,
+        tcp_timeouts: 0,
+        tcp_forward_max_inflight_drop: 0,
+    };
+    for token in log.split_whitespace() {
+        let Some((key, value)) = token.split_once('=') else {
+            continue;
+        };
+        let Ok(value) = value.trim_end_matches(',').parse::<u64>() else {
+            continue;
+        };
+        let field = match key {
+            "bytes_sent" | "tx_bytes" => &mut stats.bytes_sent,
+            "bytes_received" | "rx_bytes" => &mut stats.bytes_received,
+            "tcp_established" => &mut stats.tcp_established,
+            "tcp_failed" => &mut stats.tcp_failed_connections,
+            "tcp_retransmits" => &mut stats.tcp_retransmits,
+            "tcp_timeouts" => &mut stats.tcp_timeouts,
+            _ => continue,
+        };
+        *field = value;
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::{FallbackFactory, NoBackendFactory};
+    use std::sync::Arc;
+
+    fn config() -> NetworkBackendConfig {
+        NetworkBackendConfig {
+            port_mappings: vec![(8080, 80)],
+            socket_path: PathBuf::from("/tmp/bl-box/net.sock"),
+            allow_net: Vec::new(),
+            secrets: Vec::new(),
+            ca_dir: PathBuf::from("/tmp/bl-box/ca"),
+            rate_limit: None,
+            guest_mac: None,
+        }
+    }
+
+    /// Stand-in for `PasstFactory` with passt installed.
+    struct InstalledPasst;
+
+    impl NetworkBackendFactory for InstalledPasst {
+        fn create(&self, config: &NetworkBackendConfig) -> Option<Box<dyn NetworkBackend>> {
+            Some(Box::new(PasstBackend::from_config(config)))
+        }
+    }
+
+    #[test]
+    fn endpoint_is_unix_stream_on_data_socket() {
+        let backend = PasstBackend::from_config(&config());
+        let NetworkBackendEndpoint::UnixSocket {
+            path,
+            connection_type,
+            mac_address,
+        } = backend.endpoint();
+        assert_eq!(path, PathBuf::from("/tmp/bl-box/net.sock"));
+        assert_eq!(connection_type, ConnectionType::UnixStream);
+        assert_eq!(mac_address, GUEST_MAC);
+        assert_eq!(backend.log_path, PathBuf::from("/tmp/bl-box/passt.log"));
+    }
+
+    #[test]
+    fn shutdown_kills_passt_once() {
+        let backend = PasstBackend::from_config(&config());
+        let child = Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+        *backend.child.lock().unwrap() = Some(child);
+
+        backend.shutdown().unwrap();
+        assert!(backend.child.lock().unwrap().is_none());
+        assert!(!crate::util::is_process_alive(pid));
+        backend.shutdown().unwrap();
+    }
+
+    #[test]
+    fn args_forward_port_mappings() {
+        let args = PasstBackend::from_config(&config()).args();
+        assert!(args.windows(2).any(|w| w == ["--tcp-ports", "8080:80"]));
+        assert!(
+            args.windows(2)
+                .any(|w| w == ["--socket", "/tmp/bl-box/net.sock"])
+        );
+    }
+
+    #[test]
+    fn fallback_prefers_gvproxy_over_passt() {
+        let factory = FallbackFactory::new(vec![
+            Arc::new(crate::net::gvproxy::GvproxyFactory),
+            Arc::new(InstalledPasst),
+        ]);
+        let backend = factory.create(&config()).expect("backend");
+        assert_eq!(backend.name(), "gvisor-tap-vsock");
+    }
+
+    #[test]
+    fn fallback_selects_passt_when_earlier_factories_decline() {
+        let factory =
+            FallbackFactory::new(vec![Arc::new(NoBackendFactory), Arc::new(InstalledPasst)]);
+        let backend = factory.create(&config()).expect("backend");
+        assert_eq!(backend.name(), "passt");
+    }
+
+    #[test]
+    fn log_counters_take_latest_values() {
+        let log = "\
+            passt: counters tx_bytes=100 rx_bytes=200 tcp_established=1\n\
+            passt: counters tx_bytes=150 rx_bytes=260 tcp_established=2 tcp_retransmits=4\n\
+            unrelated line with key=notanumber\n";
+        let stats = parse_log_counters(log);
+        assert_eq!(stats.bytes_sent(), 150);
+        assert_eq!(stats.bytes_received(), 260);
+        assert_eq!(stats.tcp_established(), 2);
+        assert_eq!(stats.tcp_retransmits(), 4);
+        assert_eq!(stats.tcp_timeouts(), 0);
+    }
+}
@@ -64,6 +64,11 @@ const BOX_SOCK: &str = "box.sock";
 const READY_SOCK: &str = "ready.sock";
 /// Network backend (gvproxy) socket filename.
 const NET_SOCK: &str = "net.sock";
+/// Shim control socket filename.
+const CONTROL_SOCK: &str = "control.sock";
+/// Prefix of user vsock port sockets (`vs-{port}.sock`). The longest,
+/// `vs-4294967294.sock`, still fits within [`LONGEST_SOCKET_NAME`].
+const VSOCK_SOCK_PREFIX: &str = "vs-";
 
 /// Base directory for binding symlinks. Deliberately literal — see module docs.
 const SYMLINK_BASE: &str = "/tmp";
@@ -130,6 +135,17 @@ impl BoxSockets {
         self.binding_dir().join(NET_SOCK)
     }
 
+    /// Shim control socket (the shim's control helper binds; host dials).
+    pub fn control_sock(&self) -> PathBuf {
+        self.binding_dir().join(CONTROL_SOCK)
+    }
+
+    /// Socket bridged to user vsock `port` (krun binds; host dials).
+    pub fn vsock_sock(&self, port: u32) -> PathBuf {
+        self.binding_dir()
+            .join(format!("{VSOCK_SOCK_PREFIX}{port}.sock"))
+    }
+
     /// Ensure the binding symlink exists and is correct. Idempotent;
     /// tolerates concurrent callers for the same box.
     pub fn ensure(&self) -> BoxliteResult<()> {
@@ -331,6 +347,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn vsock_sockets_fit_within_longest_name() {
+        let (_tmp, s) = unique_sockets("vsockpath1");
+        assert_eq!(s.vsock_sock(4096), s.binding_dir().join("vs-4096.sock"));
+        let widest = s.vsock_sock(u32::MAX - 1);
+        let name = widest.file_name().unwrap().len();
+        assert!(name <= LONGEST_SOCKET_NAME.len(), "{}", widest.display());
+    }
+
     #[test]
     fn dead_zone_real_dir_still_binds_short() {
         // Regression guard for the original bug: a real dir where ready.sock
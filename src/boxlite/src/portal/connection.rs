@@ -3,9 +3,10 @@
 //! Converts BoxTransport to tonic Channel with lazy initialization.
 
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 
-use boxlite_shared::{BoxTransport, BoxliteError, BoxliteResult};
+use boxlite_shared::{BoxTransport, BoxliteError, BoxliteResult, TlsFiles};
 use hyper_util::rt::TokioIo;
 use tokio::sync::OnceCell;
 use tonic::transport::{Channel, Endpoint, Uri};
@@ -18,6 +19,8 @@ use tower::service_fn;
 pub struct Connection {
     transport: BoxTransport,
     channel: Arc<OnceCell<Channel>>,
+    /// Incremented each time a channel is actually established.
+    connects: Option<Arc<AtomicU64>>,
 }
 
 impl Connection {
@@ -26,14 +29,27 @@ impl Connection {
         Self {
             transport,
             channel: Arc::new(OnceCell::new()),
+            connects: None,
         }
     }
 
+    /// Count established channels into `counter`.
+    pub(crate) fn with_connect_counter(mut self, counter: Arc<AtomicU64>) -> Self {
+        self.connects = Some(counter);
+        self
+    }
+
     /// Get or establish the channel.
     pub async fn channel(&self) -> BoxliteResult<Channel> {
         let channel = self
             .channel
-            .get_or_try_init(|| async { connect_transport(&self.transport).await })
+            .get_or_try_init(|| async {
+                let channel = connect_transport(&self.transport).await?;
+                if let Some(connects) = &self.connects {
+                    connects.fetch_add(1, Ordering::Relaxed);
+                }
+                Ok::<_, BoxliteError>(channel)
+            })
             .await?;
 
         Ok(channel.clone())
@@ -51,6 +67,10 @@ async fn connect_transport(transport: &BoxTransport) -> BoxliteResult<Channel> {
             tracing::debug!("Connecting via TCP: 127.0.0.1:{}", port);
             connect_tcp(*port).await
         }
+        BoxTransport::Tls { host, port, files } => {
+            tracing::debug!("Connecting via TLS: {}:{}", host, port);
+            connect_tls(host, *port, files).await
+        }
         BoxTransport::Vsock { port } => Err(BoxliteError::Internal(format!(
             "Vsock client not yet implemented (port: {})",
             port
@@ -86,3 +106,13 @@ async fn connect_tcp(port: u16) -> BoxliteResult<Channel> {
     tracing::debug!("Connected via TCP");
     Ok(channel)
 }
+
+async fn connect_tls(host: &str, port: u16, files: &TlsFiles) -> BoxliteResult<Channel> {
+    let channel = boxlite_shared::tls::client_endpoint(host, port, files)?
+        .connect_timeout(Duration::from_secs(30))
+        .connect()
+        .await?;
+
+    tracing::debug!("Connected via TLS");
+    Ok(channel)
+}
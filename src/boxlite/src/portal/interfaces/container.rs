@@ -2,11 +2,15 @@
 
 use boxlite_shared::{
     BindMount, BoxliteError, BoxliteResult, CaCert, ContainerClient,
-    ContainerConfig as ProtoContainerConfig, ContainerInitRequest, DiskRootfs, MergedRootfs,
-    OverlayRootfs, RootfsInit, container_init_response,
+    ContainerConfig as ProtoContainerConfig, ContainerDiffRequest, ContainerExportRequest,
+    ContainerInitRequest, ContainerProcess, DiskRootfs, DnsConfig as ProtoDnsConfig,
+    IdMapping as ProtoIdMapping, ListProcessesRequest, MergedRootfs, OverlayRootfs, RootfsInit,
+    UserNamespace, container_init_response,
 };
+use tokio::io::AsyncWriteExt;
 use tonic::transport::Channel;
 
+use crate::runtime::options::{IdMapping, UserNsMapping};
 use crate::volumes::ContainerMount;
 
 /// Container rootfs initialization strategy.
@@ -32,9 +36,27 @@ pub enum ContainerRootfsInitConfig {
         need_format: bool,
         /// Whether to resize filesystem after mounting to fill disk
         need_resize: bool,
+        /// Block device for the overlay upper/work dirs (None = write to `device`)
+        overlay_upper_device: Option<String>,
     },
 }
 
+fn userns_into_proto(mapping: UserNsMapping) -> UserNamespace {
+    let convert = |map: Vec<IdMapping>| {
+        map.into_iter()
+            .map(|m| ProtoIdMapping {
+                container_id: m.container_id,
+                host_id: m.host_id,
+                size: m.size,
+            })
+            .collect()
+    };
+    UserNamespace {
+        uid_mappings: convert(mapping.uid_map),
+        gid_mappings: convert(mapping.gid_map),
+    }
+}
+
 impl ContainerRootfsInitConfig {
     pub(crate) fn into_proto(self) -> RootfsInit {
         match self {
@@ -58,11 +80,13 @@ impl ContainerRootfsInitConfig {
                 device,
                 need_format,
                 need_resize,
+                overlay_upper_device,
             } => RootfsInit {
                 strategy: Some(boxlite_shared::rootfs_init::Strategy::Disk(DiskRootfs {
                     device,
                     need_format,
                     need_resize,
+                    overlay_upper_device,
                 })),
             },
         }
@@ -89,9 +113,13 @@ impl ContainerInterface {
     /// * `image_config` - Image-derived container config (entrypoint, env, workdir)
     /// * `rootfs` - Rootfs initialization strategy
     /// * `mounts` - Bind mounts from guest VM paths into container
+    /// * `userns` - User namespace ID mappings (None shares the guest's namespace)
+    /// * `read_only_rootfs` - Mount the container rootfs read-only
+    /// * `cap_add` / `cap_drop` - `CAP_*` names (or `ALL`) applied to the default capabilities
     ///
     /// # Returns
     /// Container ID on success
+    #[allow(clippy::too_many_arguments)]
     pub async fn init(
         &mut self,
         container_id: &str,
@@ -99,6 +127,11 @@ impl ContainerInterface {
         rootfs: ContainerRootfsInitConfig,
         mounts: Vec<ContainerMount>,
         ca_certs: Vec<String>,
+        userns: Option<UserNsMapping>,
+        dns: Option<ProtoDnsConfig>,
+        read_only_rootfs: bool,
+        cap_add: Vec<String>,
+        cap_drop: Vec<String>,
     ) -> BoxliteResult<String> {
         let proto_config = ProtoContainerConfig {
             entrypoint: image_config.final_cmd(),
@@ -131,6 +164,11 @@ impl ContainerInterface {
             env_count = image_config.env.len(),
             rootfs = ?rootfs,
             mounts_count = proto_mounts.len(),
+            userns = ?userns,
+            dns = ?dns,
+            read_only_rootfs,
+            cap_add = ?cap_add,
+            cap_drop = ?cap_drop,
             "Container configuration"
         );
 
@@ -140,6 +178,11 @@ impl ContainerInterface {
             rootfs: Some(rootfs.into_proto()),
             mounts: proto_mounts,
             ca_certs: ca_certs.into_iter().map(|pem| CaCert { pem }).collect(),
+            userns: userns.map(userns_into_proto),
+            dns,
+            read_only_rootfs,
+            cap_add,
+            cap_drop,
         };
 
         let response = self.client.init(request).await?.into_inner();
@@ -161,4 +204,69 @@ impl ContainerInterface {
             )),
         }
     }
+
+    /// Download the container's overlay upper layer as an OCI layer tar.
+    ///
+    /// Whiteouts are already converted to `.wh.` entries by the guest. Fails
+    /// unless the container runs on a disk rootfs with an overlay upper disk.
+    pub async fn diff_tar(
+        &mut self,
+        container_id: &str,
+        tar_dest: &std::path::Path,
+    ) -> BoxliteResult<()> {
+        let request = ContainerDiffRequest {
+            container_id: container_id.to_string(),
+        };
+        let mut stream = self.client.diff(request).await?.into_inner();
+
+        let mut file = tokio::fs::File::create(tar_dest)
+            .await
+            .map_err(|e| BoxliteError::Storage(format!("Failed to create tar file: {}", e)))?;
+        while let Some(chunk) = stream.message().await? {
+            file.write_all(&chunk.data)
+                .await
+                .map_err(|e| BoxliteError::Storage(format!("Failed to write tar file: {}", e)))?;
+        }
+        file.flush()
+            .await
+            .map_err(|e| BoxliteError::Storage(format!("Failed to flush tar file: {}", e)))?;
+        Ok(())
+    }
+
+    /// Stream the container's merged rootfs as a flat tar into `writer`.
+    ///
+    /// Device nodes and sockets are skipped by the guest.
+    pub async fn export_tar(
+        &mut self,
+        container_id: &str,
+        writer: &mut (dyn tokio::io::AsyncWrite + Unpin + Send),
+    ) -> BoxliteResult<()> {
+        let request = ContainerExportRequest {
+            container_id: container_id.to_string(),
+        };
+        let mut stream = self.client.export(request).await?.into_inner();
+
+        while let Some(chunk) = stream.message().await? {
+            writer
+                .write_all(&chunk.data)
+                .await
+                .map_err(|e| BoxliteError::Storage(format!("Failed to write export: {}", e)))?;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| BoxliteError::Storage(format!("Failed to flush export: {}", e)))?;
+        Ok(())
+    }
+    /// List the processes in the container's PID namespace, ordered by PID.
+    pub async fn list_processes(
+        &mut self,
+        container_id: &str,
+    ) -> BoxliteResult<Vec<ContainerProcess>> {
+        let request = ListProcessesRequest {
+            container_id: container_id.to_string(),
+        };
+        let response = self.client.list_processes(request).await?.into_inner();
+        Ok(response.processes)
+    }
 }
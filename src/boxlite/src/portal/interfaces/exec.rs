@@ -3,7 +3,9 @@
 //! High-level API for execution operations (unary Exec + output-only Attach +
 //! blocking Wait).
 
-use crate::litebox::{BoxCommand, ExecResult};
+use crate::litebox::{
+    BoxCommand, DEFAULT_OUTPUT_BUFFER_SIZE, ExecResult, OutputBudget, OutputMode,
+};
 use boxlite_shared::{
     AttachRequest, BoxliteError, BoxliteResult, ExecOutput, ExecRequest, ExecStdin,
     ExecutionClient, KillRequest, WaitRequest, WaitResponse, exec_output,
@@ -22,9 +24,14 @@ pub struct ExecutionInterface {
 /// Components for building an Execution.
 pub struct ExecComponents {
     pub execution_id: String,
-    pub stdin_tx: mpsc::UnboundedSender<Vec<u8>>,
+    /// `None` for a re-attach, which only recovers output.
+    pub stdin_tx: Option<mpsc::UnboundedSender<Vec<u8>>>,
     pub stdout_rx: mpsc::UnboundedReceiver<String>,
     pub stderr_rx: mpsc::UnboundedReceiver<String>,
+    /// Released as `stdout_rx` is read; the pump waits on it.
+    pub stdout_budget: OutputBudget,
+    /// Released as `stderr_rx` is read; the pump waits on it.
+    pub stderr_budget: OutputBudget,
     pub result_rx: mpsc::UnboundedReceiver<ExecResult>,
 }
 
@@ -51,6 +58,8 @@ impl ExecutionInterface {
         let (stdout_tx, stdout_rx) = mpsc::unbounded_channel::<String>();
         let (stderr_tx, stderr_rx) = mpsc::unbounded_channel::<String>();
         let (result_tx, result_rx) = mpsc::unbounded_channel();
+        let stdout_budget = OutputBudget::new(command.output_buffer_size);
+        let stderr_budget = OutputBudget::new(command.output_buffer_size);
 
         // Build request
         let request = ExecProtocol::build_exec_request(&command);
@@ -82,8 +91,8 @@ impl ExecutionInterface {
         ExecProtocol::spawn_attach(
             self.client.clone(),
             execution_id.clone(),
-            stdout_tx,
-            stderr_tx,
+            DecodedStream::new(stdout_tx).with_budget(stdout_budget.clone()),
+            DecodedStream::new(stderr_tx).with_budget(stderr_budget.clone()),
             shutdown_token.clone(),
         );
 
@@ -97,9 +106,73 @@ impl ExecutionInterface {
 
         Ok(ExecComponents {
             execution_id,
-            stdin_tx,
+            stdin_tx: command.feed_stdin(stdin_tx),
             stdout_rx,
             stderr_rx,
+            stdout_budget,
+            stderr_budget,
+            result_rx,
+        })
+    }
+
+    /// Re-attach to an execution started by an earlier `exec`.
+    ///
+    /// Output the guest held while nothing was attached is delivered first.
+    /// If the process already exited, the streams carry what it left behind
+    /// and the result arrives immediately.
+    pub async fn attach(
+        &mut self,
+        execution_id: &str,
+        shutdown_token: CancellationToken,
+    ) -> BoxliteResult<ExecComponents> {
+        let (stdout_tx, stdout_rx) = mpsc::unbounded_channel::<String>();
+        let (stderr_tx, stderr_rx) = mpsc::unbounded_channel::<String>();
+        let (result_tx, result_rx) = mpsc::unbounded_channel();
+        let stdout_budget = OutputBudget::new(DEFAULT_OUTPUT_BUFFER_SIZE);
+        let stderr_budget = OutputBudget::new(DEFAULT_OUTPUT_BUFFER_SIZE);
+
+        let request = AttachRequest {
+            execution_id: execution_id.to_string(),
+        };
+        let stream = match self.client.attach(request).await {
+            Ok(response) => response.into_inner(),
+            Err(status) => {
+                return Err(match status.code() {
+                    tonic::Code::NotFound => BoxliteError::NotFound(format!(
+                        "execution {execution_id}: {}",
+                        status.message()
+                    )),
+                    tonic::Code::AlreadyExists => BoxliteError::AlreadyExists(format!(
+                        "execution {execution_id} is already attached"
+                    )),
+                    _ => status.into(),
+                });
+            }
+        };
+
+        tracing::debug!(execution_id = %execution_id, "re-attached to execution");
+
+        let task_token = shutdown_token.clone();
+        let id = execution_id.to_string();
+        let stdout = DecodedStream::new(stdout_tx).with_budget(stdout_budget.clone());
+        let stderr = DecodedStream::new(stderr_tx).with_budget(stderr_budget.clone());
+        tokio::spawn(async move {
+            ExecProtocol::pump_output(stream, &id, stdout, stderr, &task_token).await;
+        });
+        ExecProtocol::spawn_wait(
+            self.client.clone(),
+            execution_id.to_string(),
+            result_tx,
+            shutdown_token,
+        );
+
+        Ok(ExecComponents {
+            execution_id: execution_id.to_string(),
+            stdin_tx: None,
+            stdout_rx,
+            stderr_rx,
+            stdout_budget,
+            stderr_budget,
             result_rx,
         })
     }
@@ -122,6 +195,7 @@ impl ExecutionInterface {
         let request = KillRequest {
             execution_id: execution_id.to_string(),
             signal,
+            grace_ms: None,
         };
 
         let response = self.client.kill(request).await?.into_inner();
@@ -168,6 +242,18 @@ impl ExecutionInterface {
             ))
         }
     }
+
+    /// List the executions the guest knows about, oldest first.
+    pub async fn list(&mut self) -> BoxliteResult<Vec<boxlite_shared::ExecutionInfo>> {
+        use boxlite_shared::ListExecutionsRequest;
+
+        let response = self
+            .client
+            .list(ListExecutionsRequest {})
+            .await?
+            .into_inner();
+        Ok(response.executions)
+    }
 }
 
 // ============================================================================
@@ -219,7 +305,9 @@ impl ExecProtocol {
             workdir: command.working_dir.clone().unwrap_or_default(),
             timeout_ms: command.timeout.map(|d| d.as_millis() as u64).unwrap_or(0),
             tty: if command.tty {
-                let (rows, cols) = crate::util::get_terminal_size();
+                let (rows, cols) = command
+                    .tty_size
+                    .unwrap_or_else(crate::util::get_terminal_size);
                 Some(TtyConfig {
                     rows,
                     cols,
@@ -230,6 +318,19 @@ impl ExecProtocol {
                 None
             },
             user: command.user.clone(),
+            groups: command.groups.clone(),
+            resource_limits: command.resource_limits.as_ref().map(|limits| {
+                boxlite_shared::ExecResourceLimits {
+                    cpus: limits.cpus,
+                    max_memory: limits.max_memory,
+                    max_processes: limits.max_processes,
+                }
+            }),
+            output_mode: match command.output_mode {
+                OutputMode::Bytes => boxlite_shared::OutputMode::Bytes,
+                OutputMode::Lines => boxlite_shared::OutputMode::Lines,
+                OutputMode::Raw => boxlite_shared::OutputMode::Raw,
+            } as i32,
         }
     }
 
@@ -247,14 +348,18 @@ impl ExecProtocol {
         ExecResult {
             exit_code: code,
             error_message,
+            timed_out: resp.timed_out,
+            duration_ms: resp.duration_ms,
+            oom_killed: resp.oom_killed,
+            signal_name: (!resp.signal_name.is_empty()).then_some(resp.signal_name),
         }
     }
 
     fn spawn_attach(
         mut client: ExecutionClient<Channel>,
         execution_id: String,
-        stdout_tx: mpsc::UnboundedSender<String>,
-        stderr_tx: mpsc::UnboundedSender<String>,
+        stdout: DecodedStream,
+        stderr: DecodedStream,
         shutdown_token: CancellationToken,
     ) {
         tokio::spawn(async move {
@@ -275,92 +380,140 @@ impl ExecProtocol {
             match response {
                 Ok(response) => {
                     tracing::debug!(execution_id = %execution_id, "attach stream connected");
-                    let mut stream = response.into_inner();
-                    let mut message_count = 0u64;
-                    // Per-stream UTF-8 decoder state. The gRPC layer chunks the
-                    // PTY's byte stream at arbitrary offsets, which can land
-                    // mid-codepoint for any multi-byte char (e.g. `─` is 3
-                    // bytes, `👋` is 4). Decoding each chunk independently with
-                    // `from_utf8_lossy` substitutes U+FFFD on both sides of the
-                    // cut, doubling visible columns and desyncing TUI cursor
-                    // math (see https://github.com/.../issues/...). Holding
-                    // the trailing partial across chunks fixes this.
-                    let mut stdout = DecodedStream::new(stdout_tx);
-                    let mut stderr = DecodedStream::new(stderr_tx);
-
-                    loop {
-                        // Use select! to handle cancellation while streaming
-                        let output = tokio::select! {
-                            biased;
-                            _ = shutdown_token.cancelled() => {
-                                tracing::debug!(
-                                    execution_id = %execution_id,
-                                    message_count,
-                                    "Attach stream cancelled during shutdown"
-                                );
-                                stdout.flush();
-                                stderr.flush();
-                                break;
-                            }
-                            msg = stream.message() => msg,
-                        };
-
-                        match output.transpose() {
-                            Some(Ok(output)) => {
-                                message_count += 1;
-                                Self::route_output(output, &mut stdout, &mut stderr);
-                            }
-                            Some(Err(e)) => {
-                                tracing::debug!(
-                                    execution_id = %execution_id,
-                                    error = %e,
-                                    message_count,
-                                    "Attach stream error, breaking"
-                                );
-                                // Flush before pushing the error message so
-                                // the held-over partial bytes (as U+FFFD)
-                                // arrive in correct order ahead of the
-                                // synthesized "Attach stream error: …" line.
-                                stdout.flush();
-                                stderr.flush();
-                                let _ = stderr.tx.send(format!("Attach stream error: {}", e));
-                                break;
-                            }
-                            None => {
-                                // Stream ended normally — flush any partial
-                                // bytes still in the decoders as U+FFFD,
-                                // matching `from_utf8_lossy` semantics for
-                                // a truncated input at EOF.
-                                stdout.flush();
-                                stderr.flush();
-                                break;
-                            }
+                    Self::pump_output(
+                        response.into_inner(),
+                        &execution_id,
+                        stdout,
+                        stderr,
+                        &shutdown_token,
+                    )
+                    .await;
+                }
+                Err(e) => {
+                    tracing::debug!(execution_id = %execution_id, error = %e, "Attach failed");
+                    let _ = stderr.tx.send(format!("Attach failed: {}", e));
+                }
+            }
+        });
+    }
+
+    /// Route an attach stream into the stdout/stderr channels until it ends.
+    ///
+    /// Also stops once both receivers are gone (the `Execution` was dropped),
+    /// so the guest holds further output for a later re-attach instead of
+    /// streaming it into the void.
+    ///
+    /// While a stream's budget is used up the pump stops reading, so gRPC
+    /// flow control backs up into the guest, which stops reading the
+    /// process's pipes until the caller catches up.
+    async fn pump_output(
+        mut stream: tonic::Streaming<ExecOutput>,
+        execution_id: &str,
+        mut stdout: DecodedStream,
+        mut stderr: DecodedStream,
+        shutdown_token: &CancellationToken,
+    ) {
+        let mut message_count = 0u64;
+        // Per-stream UTF-8 decoder state. The gRPC layer chunks the
+        // PTY's byte stream at arbitrary offsets, which can land
+        // mid-codepoint for any multi-byte char (e.g. `─` is 3
+        // bytes, `👋` is 4). Decoding each chunk independently with
+        // `from_utf8_lossy` substitutes U+FFFD on both sides of the
+        // cut, doubling visible columns and desyncing TUI cursor
+        // math (see https://github.com/.../issues/...). Holding
+        // the trailing partial across chunks fixes this.
+
+        loop {
+            // Use select! to handle cancellation while streaming
+            let output = tokio::select! {
+                biased;
+                _ = shutdown_token.cancelled() => {
+                    tracing::debug!(
+                        execution_id = %execution_id,
+                        message_count,
+                        "Attach stream cancelled during shutdown"
+                    );
+                    stdout.flush();
+                    stderr.flush();
+                    break;
+                }
+                _ = async { tokio::join!(stdout.tx.closed(), stderr.tx.closed()) } => {
+                    tracing::debug!(
+                        execution_id = %execution_id,
+                        message_count,
+                        "Attach receivers dropped, detaching"
+                    );
+                    break;
+                }
+                msg = stream.message() => msg,
+            };
+
+            match output.transpose() {
+                Some(Ok(output)) => {
+                    message_count += 1;
+                    tokio::select! {
+                        biased;
+                        _ = shutdown_token.cancelled() => {
+                            tracing::debug!(
+                                execution_id = %execution_id,
+                                message_count,
+                                "Attach stream cancelled while waiting for a reader"
+                            );
+                            stdout.flush();
+                            stderr.flush();
+                            break;
                         }
+                        _ = Self::route_output(output, &mut stdout, &mut stderr) => {}
                     }
-
+                }
+                Some(Err(e)) => {
                     tracing::debug!(
                         execution_id = %execution_id,
+                        error = %e,
                         message_count,
-                        "Attach stream ended"
+                        "Attach stream error, breaking"
                     );
+                    // Flush before pushing the error message so
+                    // the held-over partial bytes (as U+FFFD)
+                    // arrive in correct order ahead of the
+                    // synthesized "Attach stream error: …" line.
+                    stdout.flush();
+                    stderr.flush();
+                    let _ = stderr.tx.send(format!("Attach stream error: {}", e));
+                    break;
                 }
-                Err(e) => {
-                    tracing::debug!(execution_id = %execution_id, error = %e, "Attach failed");
-                    let _ = stderr_tx.send(format!("Attach failed: {}", e));
+                None => {
+                    // Stream ended normally — flush any partial
+                    // bytes still in the decoders as U+FFFD,
+                    // matching `from_utf8_lossy` semantics for
+                    // a truncated input at EOF.
+                    stdout.flush();
+                    stderr.flush();
+                    break;
                 }
             }
-        });
+        }
+
+        tracing::debug!(
+            execution_id = %execution_id,
+            message_count,
+            "Attach stream ended"
+        );
     }
 
-    fn route_output(output: ExecOutput, stdout: &mut DecodedStream, stderr: &mut DecodedStream) {
+    async fn route_output(
+        output: ExecOutput,
+        stdout: &mut DecodedStream,
+        stderr: &mut DecodedStream,
+    ) {
         match output.event {
             Some(exec_output::Event::Stdout(chunk)) => {
                 tracing::trace!(len = chunk.data.len(), "Received exec stdout");
-                stdout.send_bytes(chunk.data);
+                stdout.send_bytes(chunk.data).await;
             }
             Some(exec_output::Event::Stderr(chunk)) => {
                 tracing::trace!(len = chunk.data.len(), "Received exec stderr");
-                stderr.send_bytes(chunk.data);
+                stderr.send_bytes(chunk.data).await;
             }
             None => {}
         }
@@ -386,7 +539,7 @@ impl ExecProtocol {
                     tracing::debug!(execution_id = %execution_id, "Wait cancelled during shutdown");
                     // Send a special result indicating cancellation
                     // Using exit code -1 to indicate abnormal termination
-                    let _ = result_tx.send(ExecResult { exit_code: -1, error_message: None });
+                    let _ = result_tx.send(ExecResult { exit_code: -1, error_message: None, timed_out: false, duration_ms: 0, oom_killed: false, signal_name: None });
                     return;
                 }
                 result = client.wait(request) => result,
@@ -406,6 +559,10 @@ impl ExecProtocol {
                     let _ = result_tx.send(ExecResult {
                         exit_code: -1,
                         error_message: None,
+                        timed_out: false,
+                        duration_ms: 0,
+                        oom_killed: false,
+                        signal_name: None,
                     });
                 }
             }
@@ -640,6 +797,8 @@ impl Utf8StreamDecoder {
 struct DecodedStream {
     tx: mpsc::UnboundedSender<String>,
     decoder: Utf8StreamDecoder,
+    /// Unread bytes allowed in `tx`; `None` is unbounded.
+    budget: Option<OutputBudget>,
 }
 
 impl DecodedStream {
@@ -647,15 +806,30 @@ impl DecodedStream {
         Self {
             tx,
             decoder: Utf8StreamDecoder::default(),
+            budget: None,
         }
     }
 
-    /// Decode a wire chunk and forward any completed text to the receiver.
-    fn send_bytes(&mut self, data: Vec<u8>) {
+    fn with_budget(mut self, budget: OutputBudget) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Decode a wire chunk and forward any completed text to the receiver,
+    /// first waiting for room in the budget. Output for a dropped receiver
+    /// is discarded without waiting.
+    async fn send_bytes(&mut self, data: Vec<u8>) {
         let text = self.decoder.decode(data);
-        if !text.is_empty() {
-            let _ = self.tx.send(text);
+        if text.is_empty() {
+            return;
+        }
+        if let Some(budget) = &self.budget {
+            tokio::select! {
+                _ = budget.reserve(text.len()) => {}
+                _ = self.tx.closed() => return,
+            }
         }
+        let _ = self.tx.send(text);
     }
 
     /// Drain a held partial codepoint as U+FFFD when the stream ends.
@@ -676,6 +850,56 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn test_map_wait_response_surfaces_oom_and_signal_name() {
+        let result = ExecProtocol::map_wait_response(WaitResponse {
+            signal: 9,
+            oom_killed: true,
+            signal_name: "SIGKILL".to_string(),
+            error_message: "killed by the OOM killer (out of memory)".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(result.exit_code, -9);
+        assert!(result.oom_killed);
+        assert_eq!(result.signal_name.as_deref(), Some("SIGKILL"));
+
+        let result = ExecProtocol::map_wait_response(WaitResponse {
+            exit_code: 1,
+            ..Default::default()
+        });
+        assert!(!result.oom_killed);
+        assert_eq!(result.signal_name, None);
+    }
+
+    #[test]
+    fn test_map_wait_response_surfaces_timeout_and_duration() {
+        let result = ExecProtocol::map_wait_response(WaitResponse {
+            signal: 15,
+            timed_out: true,
+            duration_ms: 512,
+            signal_name: "SIGTERM".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(result.exit_code, -15);
+        assert!(result.timed_out);
+        assert_eq!(result.duration_ms, 512);
+    }
+
+    #[test]
+    fn test_build_exec_request_tty() {
+        let request = ExecProtocol::build_exec_request(&BoxCommand::new("sh"));
+        assert!(request.tty.is_none(), "no PTY unless requested");
+
+        let request =
+            ExecProtocol::build_exec_request(&BoxCommand::new("sh").tty(true).tty_size(40, 120));
+        let tty = request.tty.expect("tty(true) must request a PTY");
+        assert_eq!((tty.rows, tty.cols), (40, 120));
+
+        // A size alone does not allocate a PTY.
+        let request = ExecProtocol::build_exec_request(&BoxCommand::new("sh").tty_size(40, 120));
+        assert!(request.tty.is_none());
+    }
+
     /// Test that CancellationToken correctly signals cancelled state.
     #[tokio::test]
     async fn test_cancellation_token_basic() {
@@ -805,7 +1029,7 @@ mod tests {
             tokio::select! {
                 biased;
                 _ = token_clone.cancelled() => {
-                    let _ = result_tx.send(ExecResult { exit_code: -1, error_message: None });
+                    let _ = result_tx.send(ExecResult { exit_code: -1, error_message: None, timed_out: false, duration_ms: 0, oom_killed: false, signal_name: None });
                 }
                 _ = tokio::time::sleep(Duration::from_secs(3600)) => {
                     // Would normally wait for gRPC response
@@ -1088,8 +1312,8 @@ mod tests {
     /// route_output uses the decoder; verify it doesn't double-emit U+FFFD
     /// when a 3-byte char straddles two ExecOutput messages. This is the
     /// integration-shaped reproducer for the original bug.
-    #[test]
-    fn route_output_recovers_split_codepoint_across_messages() {
+    #[tokio::test]
+    async fn route_output_recovers_split_codepoint_across_messages() {
         use boxlite_shared::{Stdout as StdoutMsg, exec_output};
 
         let (stdout_tx, mut stdout_rx) = mpsc::unbounded_channel::<String>();
@@ -1102,8 +1326,8 @@ mod tests {
         };
 
         // "─" split into [E2] and [94 80] across two messages.
-        ExecProtocol::route_output(mk_stdout(vec![0xE2]), &mut stdout, &mut stderr);
-        ExecProtocol::route_output(mk_stdout(vec![0x94, 0x80]), &mut stdout, &mut stderr);
+        ExecProtocol::route_output(mk_stdout(vec![0xE2]), &mut stdout, &mut stderr).await;
+        ExecProtocol::route_output(mk_stdout(vec![0x94, 0x80]), &mut stdout, &mut stderr).await;
 
         // First message: holdover only, no emission.
         // Second message: complete "─" emitted.
@@ -1121,8 +1345,8 @@ mod tests {
     /// shutdown cancellation) so trailing partial UTF-8 bytes are never
     /// silently dropped — keeping the helper correct keeps all three paths
     /// correct.
-    #[test]
-    fn decoded_stream_flush_drains_held_bytes_on_any_exit_path() {
+    #[tokio::test]
+    async fn decoded_stream_flush_drains_held_bytes_on_any_exit_path() {
         let (stdout_tx, mut stdout_rx) = mpsc::unbounded_channel::<String>();
         let (stderr_tx, mut stderr_rx) = mpsc::unbounded_channel::<String>();
         let mut stdout = DecodedStream::new(stdout_tx);
@@ -1130,8 +1354,8 @@ mod tests {
 
         // Seed each stream with the first byte of a 3-byte codepoint so it
         // has held-over bytes that would be lost without an explicit flush.
-        stdout.send_bytes(vec![0xE2]);
-        stderr.send_bytes(vec![0xE2]);
+        stdout.send_bytes(vec![0xE2]).await;
+        stderr.send_bytes(vec![0xE2]).await;
         assert!(stdout_rx.try_recv().is_err());
         assert!(stderr_rx.try_recv().is_err());
 
@@ -1149,4 +1373,53 @@ mod tests {
         assert!(stdout_rx.try_recv().is_err());
         assert!(stderr_rx.try_recv().is_err());
     }
+
+    /// A fast producer against a slow reader: every byte arrives, and the
+    /// unread backlog never exceeds the budget.
+    #[tokio::test]
+    async fn budgeted_stream_applies_backpressure_without_loss() {
+        use crate::litebox::ExecStdout;
+        use futures::StreamExt;
+
+        const CAPACITY: usize = 64;
+        let budget = OutputBudget::new(CAPACITY);
+        let (tx, rx) = mpsc::unbounded_channel::<String>();
+        let mut producer = DecodedStream::new(tx).with_budget(budget.clone());
+        let expected: String = (0..200).map(|i| format!("line {i:03}\n")).collect();
+
+        let chunks = expected.clone().into_bytes();
+        let pump = tokio::spawn(async move {
+            for chunk in chunks.chunks(9) {
+                producer.send_bytes(chunk.to_vec()).await;
+            }
+        });
+
+        let mut reader = ExecStdout::new(rx).with_budget(budget.clone());
+        let mut received = String::new();
+        while received.len() < expected.len() {
+            assert!(budget.used() <= CAPACITY, "{} bytes unread", budget.used());
+            tokio::time::sleep(Duration::from_millis(1)).await;
+            received.push_str(&reader.next().await.unwrap());
+        }
+        pump.await.unwrap();
+        assert_eq!(received, expected);
+        assert_eq!(budget.used(), 0);
+    }
+
+    #[tokio::test]
+    async fn budgeted_stream_discards_output_for_dropped_reader() {
+        let budget = OutputBudget::new(4);
+        let (tx, rx) = mpsc::unbounded_channel::<String>();
+        let mut producer = DecodedStream::new(tx).with_budget(budget);
+        drop(rx);
+
+        // Would wait forever on the full budget if the drop went unnoticed.
+        tokio::time::timeout(Duration::from_secs(5), async {
+            for _ in 0..10 {
+                producer.send_bytes(b"data".to_vec()).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
 }
@@ -1,10 +1,12 @@
 //! Guest service interface.
 
 use boxlite_shared::{
-    BlockDeviceSource, BoxliteError, BoxliteResult, Filesystem, GuestClient, GuestInitRequest,
-    NetworkInit, PingRequest, QuiesceRequest, ShutdownRequest, ThawRequest, VirtiofsSource, Volume,
-    guest_init_response,
+    BlockDeviceSource, BoxliteError, BoxliteResult, ClaimIdleRequest, DiskStatsRequest,
+    DiskStatsResponse, Filesystem, GuestBootTiming, GuestClient, GuestInitRequest, NetworkInit,
+    PingRequest, QuiesceRequest, ResizeMemoryRequest, ShutdownRequest, StreamLogsRequest,
+    ThawRequest, TmpfsSource, VirtiofsSource, Volume, guest_init_response,
 };
+use std::time::Duration;
 use tonic::transport::Channel;
 
 /// Guest service interface.
@@ -24,11 +26,16 @@ impl GuestInterface {
     ///
     /// This must be called first after connection, before Container.Init.
     /// Sets up volumes (virtiofs + block devices) and network.
-    pub async fn init(&mut self, config: GuestInitConfig) -> BoxliteResult<()> {
+    /// Returns the guest's boot timing, if the guest agent reported one.
+    pub async fn init(
+        &mut self,
+        config: GuestInitConfig,
+    ) -> BoxliteResult<Option<GuestBootTiming>> {
         tracing::debug!("Sending GuestInit request");
         tracing::trace!(
             volumes = config.volumes.len(),
             network = ?config.network,
+            kernel_modules = ?config.kernel_modules,
             "Guest init configuration"
         );
 
@@ -39,14 +46,15 @@ impl GuestInterface {
                 ip: n.ip,
                 gateway: n.gateway,
             }),
+            kernel_modules: config.kernel_modules,
         };
 
         let response = self.client.init(request).await?.into_inner();
 
         match response.result {
-            Some(guest_init_response::Result::Success(_)) => {
+            Some(guest_init_response::Result::Success(success)) => {
                 tracing::debug!("Guest initialized");
-                Ok(())
+                Ok(success.boot_timing)
             }
             Some(guest_init_response::Result::Error(err)) => {
                 tracing::error!("Guest init failed: {}", err.reason);
@@ -69,8 +77,14 @@ impl GuestInterface {
     }
 
     /// Shutdown the guest agent.
-    pub async fn shutdown(&mut self) -> BoxliteResult<()> {
-        let _response = self.client.shutdown(ShutdownRequest {}).await?;
+    ///
+    /// Running executions get SIGTERM, then SIGKILL once `exec_grace`
+    /// expires. `None` uses the guest's short default.
+    pub async fn shutdown(&mut self, exec_grace: Option<Duration>) -> BoxliteResult<()> {
+        let request = ShutdownRequest {
+            exec_grace_ms: exec_grace.map(|grace| grace.as_millis() as u64),
+        };
+        let _response = self.client.shutdown(request).await?;
         Ok(())
     }
 
@@ -91,6 +105,65 @@ impl GuestInterface {
         let response = self.client.thaw(ThawRequest {}).await?.into_inner();
         Ok(response.thawed_count)
     }
+
+    /// Resize guest-usable memory to `target_mib`.
+    ///
+    /// Returns the guest's online memory afterwards, in MiB.
+    pub async fn resize_memory(&mut self, target_mib: u32) -> BoxliteResult<u32> {
+        let response = self
+            .client
+            .resize_memory(ResizeMemoryRequest { target_mib })
+            .await
+            .map_err(|status| match status.code() {
+                tonic::Code::InvalidArgument => {
+                    BoxliteError::InvalidArgument(status.message().to_string())
+                }
+                tonic::Code::Unimplemented => {
+                    BoxliteError::Unsupported(status.message().to_string())
+                }
+                _ => status.into(),
+            })?
+            .into_inner();
+        Ok(response.actual_mib)
+    }
+
+    /// Filesystem usage of `container_id`'s rootfs, measured in the guest.
+    pub async fn disk_stats(&mut self, container_id: &str) -> BoxliteResult<DiskStatsResponse> {
+        let response = self
+            .client
+            .disk_stats(DiskStatsRequest {
+                container_id: container_id.to_string(),
+            })
+            .await?
+            .into_inner();
+        Ok(response)
+    }
+
+    /// Claim the guest for idle shutdown.
+    ///
+    /// Returns true when no execution ran for `idle_timeout`; the guest then
+    /// refuses new executions until it shuts down.
+    pub async fn claim_idle(&mut self, idle_timeout: Duration) -> BoxliteResult<bool> {
+        let response = self
+            .client
+            .claim_idle(ClaimIdleRequest {
+                idle_timeout_ms: idle_timeout.as_millis() as u64,
+            })
+            .await?
+            .into_inner();
+        Ok(response.claimed)
+    }
+
+    /// Subscribe to the guest agent's tracing events from now on.
+    pub async fn stream_logs(
+        &mut self,
+    ) -> BoxliteResult<tonic::Streaming<boxlite_shared::GuestLogEvent>> {
+        Ok(self
+            .client
+            .stream_logs(StreamLogsRequest {})
+            .await?
+            .into_inner())
+    }
 }
 
 /// Configuration for guest initialization.
@@ -100,6 +173,8 @@ pub struct GuestInitConfig {
     pub volumes: Vec<VolumeConfig>,
     /// Network configuration (optional)
     pub network: Option<NetworkInitConfig>,
+    /// Kernel modules to load before mounting volumes
+    pub kernel_modules: Vec<String>,
 }
 
 /// Volume configuration.
@@ -127,6 +202,17 @@ pub enum VolumeConfig {
         need_format: bool,
         /// If true, resize filesystem after mounting to fill disk
         need_resize: bool,
+        read_only: bool,
+    },
+    /// In-memory tmpfs at the convention path for `name`
+    Tmpfs {
+        /// Volume name
+        name: String,
+        /// Size limit in MiB
+        size_mib: u64,
+        /// Permission bits of the mount root
+        mode: u32,
+        container_id: String,
     },
 }
 
@@ -153,6 +239,7 @@ impl VolumeConfig {
         filesystem: Filesystem,
         need_format: bool,
         need_resize: bool,
+        read_only: bool,
     ) -> Self {
         Self::BlockDevice {
             device: device.into(),
@@ -160,6 +247,22 @@ impl VolumeConfig {
             filesystem,
             need_format,
             need_resize,
+            read_only,
+        }
+    }
+
+    /// Create tmpfs volume config.
+    pub fn tmpfs(
+        name: impl Into<String>,
+        size_mib: u64,
+        mode: u32,
+        container_id: impl Into<String>,
+    ) -> Self {
+        Self::Tmpfs {
+            name: name.into(),
+            size_mib,
+            mode,
+            container_id: container_id.into(),
         }
     }
 
@@ -184,6 +287,7 @@ impl VolumeConfig {
                 filesystem,
                 need_format,
                 need_resize,
+                read_only,
             } => Volume {
                 mount_point,
                 source: Some(boxlite_shared::volume::Source::BlockDevice(
@@ -192,10 +296,26 @@ impl VolumeConfig {
                         filesystem: filesystem.into(),
                         need_format,
                         need_resize,
+                        read_only,
                     },
                 )),
                 container_id: String::new(),
             },
+            VolumeConfig::Tmpfs {
+                name,
+                size_mib,
+                mode,
+                container_id,
+            } => Volume {
+                // Empty = guest resolves the convention path from the name
+                mount_point: String::new(),
+                source: Some(boxlite_shared::volume::Source::Tmpfs(TmpfsSource {
+                    name,
+                    size_mib,
+                    mode,
+                })),
+                container_id,
+            },
         }
     }
 }
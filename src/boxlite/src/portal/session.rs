@@ -6,6 +6,8 @@ use crate::portal::connection::Connection;
 use crate::portal::interfaces::FilesInterface;
 use crate::portal::interfaces::{ContainerInterface, ExecutionInterface, GuestInterface};
 use boxlite_shared::{BoxTransport, BoxliteResult};
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
 
 /// High-level guest session.
 ///
@@ -23,6 +25,15 @@ impl GuestSession {
         }
     }
 
+    /// Count guest connections established by this session into `counter`.
+    ///
+    /// The channel is cached, so a healthy session bumps the counter once no
+    /// matter how many interfaces are requested.
+    pub(crate) fn with_connect_counter(mut self, counter: Arc<AtomicU64>) -> Self {
+        self.connection = self.connection.with_connect_counter(counter);
+        self
+    }
+
     /// Get execution interface.
     pub async fn execution(&self) -> BoxliteResult<ExecutionInterface> {
         let channel = self.connection.channel().await?;
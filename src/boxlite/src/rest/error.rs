@@ -38,7 +38,7 @@ pub(crate) fn map_http_error(status: StatusCode, body: &ErrorModel) -> BoxliteEr
         "database_error" => BoxliteError::Database(msg),
         "metadata_error" => BoxliteError::MetadataError(msg),
         "config_error" => BoxliteError::Config(msg),
-        "timeout" => BoxliteError::Internal(format!("server timed out: {}", msg)),
+        "timeout" => BoxliteError::Timeout(msg),
         "internal" => BoxliteError::Internal(msg),
         // Forward-compat: unknown code from a newer server — fall back
         // to status-driven mapping, preserving the body text.
@@ -170,7 +170,7 @@ mod tests {
                 matches!(e, BoxliteError::Internal(_))
             }),
             (504, "TimeoutError", "timeout", |e| {
-                matches!(e, BoxliteError::Internal(_))
+                matches!(e, BoxliteError::Timeout(_))
             }),
         ];
 
@@ -61,12 +61,8 @@ impl BoxBackend for RestBox {
         }
     }
 
-    fn name(&self) -> Option<&str> {
-        // Same pattern as id() — name is immutable after construction.
-        unsafe {
-            let info = self.cached_info.data_ptr();
-            (*info).name.as_deref()
-        }
+    fn name(&self) -> Option<String> {
+        self.cached_info.read().name.clone()
     }
 
     fn info(&self) -> BoxInfo {
@@ -119,13 +115,13 @@ impl BoxBackend for RestBox {
         let control = RestExecControl::new(self.client.clone(), box_id);
         let stdout = ExecStdout::new(stdout_rx);
         let stderr = ExecStderr::new(stderr_rx);
-        let stdin = ExecStdin::new(stdin_tx);
+        let stdin = command.feed_stdin(stdin_tx).map(ExecStdin::new);
 
         Ok(Execution::new(
             execution_id,
             Box::new(control),
             result_rx,
-            Some(stdin),
+            stdin,
             Some(stdout),
             Some(stderr),
         ))
@@ -733,6 +729,10 @@ async fn attach_ws_pump(
                                 let _ = result_tx.send(ExecResult {
                                     exit_code,
                                     error_message: None,
+                                    timed_out: false,
+                                    duration_ms: 0,
+                                    oom_killed: false,
+                                    signal_name: None,
                                 });
                                 return;
                             }
@@ -857,6 +857,10 @@ async fn probe_execution_status(
             "completed" | "killed" | "timed_out" => ProbeResult::Terminal(ExecResult {
                 exit_code: info.exit_code.unwrap_or(-1),
                 error_message: None,
+                timed_out: info.status == "timed_out",
+                duration_ms: 0,
+                oom_killed: false,
+                signal_name: None,
             }),
             _ => ProbeResult::StillRunning,
         },
@@ -929,6 +933,10 @@ async fn emit_or_fallback(
                 let _ = result_tx.send(ExecResult {
                     exit_code: info.exit_code.unwrap_or(-1),
                     error_message: Some(cause.clone()),
+                    timed_out: info.status == "timed_out",
+                    duration_ms: 0,
+                    oom_killed: false,
+                    signal_name: None,
                 });
                 return;
             }
@@ -941,6 +949,10 @@ async fn emit_or_fallback(
     let _ = result_tx.send(ExecResult {
         exit_code: -1,
         error_message: Some(cause),
+        timed_out: false,
+        duration_ms: 0,
+        oom_killed: false,
+        signal_name: None,
     });
 }
 
@@ -1086,6 +1098,8 @@ fn box_metrics_from_response(resp: &BoxMetricsResponse) -> BoxMetrics {
     let (
         total_create_ms,
         guest_boot_ms,
+        guest_kernel_boot_ms,
+        guest_agent_init_ms,
         fs_setup_ms,
         img_prepare_ms,
         guest_rootfs_ms,
@@ -1096,6 +1110,8 @@ fn box_metrics_from_response(resp: &BoxMetricsResponse) -> BoxMetrics {
         (
             timing.total_create_ms.map(|v| v as u128),
             timing.guest_boot_ms.map(|v| v as u128),
+            timing.guest_kernel_boot_ms.map(|v| v as u128),
+            timing.guest_agent_init_ms.map(|v| v as u128),
             timing.filesystem_setup_ms.map(|v| v as u128),
             timing.image_prepare_ms.map(|v| v as u128),
             timing.guest_rootfs_ms.map(|v| v as u128),
@@ -1104,7 +1120,7 @@ fn box_metrics_from_response(resp: &BoxMetricsResponse) -> BoxMetrics {
             timing.container_init_ms.map(|v| v as u128),
         )
     } else {
-        (None, None, None, None, None, None, None, None)
+        (None, None, None, None, None, None, None, None, None, None)
     };
 
     BoxMetrics {
@@ -1114,12 +1130,16 @@ fn box_metrics_from_response(resp: &BoxMetricsResponse) -> BoxMetrics {
         bytes_received_total: resp.bytes_received_total,
         total_create_duration_ms: total_create_ms,
         guest_boot_duration_ms: guest_boot_ms,
+        guest_kernel_boot_ms,
+        guest_agent_init_ms,
         cpu_percent: resp.cpu_percent,
         memory_bytes: resp.memory_bytes,
         network_bytes_sent: resp.network_bytes_sent,
         network_bytes_received: resp.network_bytes_received,
         network_tcp_connections: resp.network_tcp_connections,
         network_tcp_errors: resp.network_tcp_errors,
+        disk_bytes_used: resp.disk_bytes_used,
+        disk_bytes_total: resp.disk_bytes_total,
         stage_filesystem_setup_ms: fs_setup_ms,
         stage_image_prepare_ms: img_prepare_ms,
         stage_guest_rootfs_ms: guest_rootfs_ms,
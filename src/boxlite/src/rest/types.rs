@@ -117,6 +117,8 @@ pub(crate) struct CreateBoxRequest {
     pub auto_remove: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub detach: Option<bool>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub labels: HashMap<String, String>,
 }
 
 impl CreateBoxRequest {
@@ -129,6 +131,7 @@ impl CreateBoxRequest {
         let (image, rootfs_path) = match &options.rootfs {
             RootfsSpec::Image(img) => (Some(img.clone()), None),
             RootfsSpec::RootfsPath(path) => (None, Some(path.clone())),
+            RootfsSpec::OciBundle(path) => (None, Some(path.display().to_string())),
         };
 
         let env = if options.env.is_empty() {
@@ -167,6 +170,7 @@ impl CreateBoxRequest {
             secrets,
             auto_remove: Some(options.auto_remove),
             detach: Some(options.detach),
+            labels: options.labels.clone(),
         }
     }
 }
@@ -262,6 +266,7 @@ impl BoxResponse {
             memory_mib: self.memory_mib,
             labels: self.labels.clone(),
             health_status: crate::litebox::HealthStatus::new(), // REST API doesn't provide health status
+            ports: Vec::new(),
         })
     }
 }
@@ -446,6 +451,10 @@ pub(crate) struct BoxMetricsResponse {
     pub network_bytes_received: Option<u64>,
     pub network_tcp_connections: Option<u64>,
     pub network_tcp_errors: Option<u64>,
+    #[serde(default)]
+    pub disk_bytes_used: Option<u64>,
+    #[serde(default)]
+    pub disk_bytes_total: Option<u64>,
     pub boot_timing: Option<BootTimingResponse>,
 }
 
@@ -453,6 +462,10 @@ pub(crate) struct BoxMetricsResponse {
 pub(crate) struct BootTimingResponse {
     pub total_create_ms: Option<u64>,
     pub guest_boot_ms: Option<u64>,
+    #[serde(default)]
+    pub guest_kernel_boot_ms: Option<u64>,
+    #[serde(default)]
+    pub guest_agent_init_ms: Option<u64>,
     pub filesystem_setup_ms: Option<u64>,
     pub image_prepare_ms: Option<u64>,
     pub guest_rootfs_ms: Option<u64>,
@@ -502,6 +515,7 @@ mod tests {
             }]),
             auto_remove: Some(true),
             detach: None,
+            labels: HashMap::new(),
         };
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"name\":\"mybox\""));
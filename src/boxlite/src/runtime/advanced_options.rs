@@ -165,6 +165,13 @@ pub struct SecurityOptions {
     /// If None, uses the built-in modular sandbox profile.
     pub sandbox_profile: Option<PathBuf>,
 
+    /// Custom syscall allowlist (Linux only).
+    ///
+    /// Installed in the shim after all other setup, right before it hands
+    /// control to the VM. Stacks on top of the built-in VMM filter, so a
+    /// syscall must be allowed by both. Requires `jailer_enabled`.
+    pub seccomp_profile: Option<SeccompProfile>,
+
     /// Allow network access inside the sandbox profile.
     ///
     /// Cross-platform: feeds the macOS seatbelt network policy and the Linux
@@ -195,6 +202,100 @@ pub struct ResourceLimits {
     /// Maximum CPU time in seconds (RLIMIT_CPU).
     #[serde(default)]
     pub max_cpu_time: Option<u64>,
+
+    /// Relative CPU share of the box's cgroup (`cpu.weight`, 1-10000,
+    /// kernel default 100).
+    #[serde(default)]
+    pub cpu_weight: Option<u32>,
+
+    /// CPU bandwidth cap as `(quota, period)` in microseconds (`cpu.max`).
+    ///
+    /// The box may use `quota` of CPU time per `period`: `(50_000, 100_000)`
+    /// is half of one core, `(200_000, 100_000)` two cores. Takes precedence
+    /// over the cap derived from `max_cpu_time`.
+    #[serde(default)]
+    pub cpu_quota: Option<(u64, u64)>,
+}
+
+/// Valid `cpu.weight` values.
+const CPU_WEIGHT_RANGE: std::ops::RangeInclusive<u32> = 1..=10_000;
+/// Valid `cpu.max` periods in microseconds.
+const CPU_PERIOD_RANGE_US: std::ops::RangeInclusive<u64> = 1_000..=1_000_000;
+/// Smallest `cpu.max` quota the kernel accepts, in microseconds.
+const CPU_QUOTA_MIN_US: u64 = 1_000;
+
+impl ResourceLimits {
+    /// Check the cgroup CPU controls are values the kernel accepts.
+    pub fn validate(&self) -> boxlite_shared::errors::BoxliteResult<()> {
+        use boxlite_shared::errors::BoxliteError;
+
+        if let Some(weight) = self.cpu_weight
+            && !CPU_WEIGHT_RANGE.contains(&weight)
+        {
+            return Err(BoxliteError::Config(format!(
+                "cpu_weight must be between 1 and 10000, got {weight}"
+            )));
+        }
+        if let Some((quota, period)) = self.cpu_quota {
+            if !CPU_PERIOD_RANGE_US.contains(&period) {
+                return Err(BoxliteError::Config(format!(
+                    "cpu_quota period must be between 1000 and 1000000 microseconds, got {period}"
+                )));
+            }
+            if quota < CPU_QUOTA_MIN_US {
+                return Err(BoxliteError::Config(format!(
+                    "cpu_quota quota must be at least 1000 microseconds, got {quota}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// What a [`SeccompProfile`] does with a syscall outside its allowlist.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeccompDefaultAction {
+    /// Fail the syscall with `EPERM` (`SCMP_ACT_ERRNO(EPERM)`).
+    #[default]
+    Errno,
+    /// Kill the process (`SCMP_ACT_KILL`).
+    Kill,
+}
+
+/// A seccomp filter built from an allowlist of syscall names (Linux only).
+///
+/// Names are resolved for the host architecture when the filter is built;
+/// an unknown name fails the build rather than being silently dropped.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SeccompProfile {
+    /// Syscalls the process may make, e.g. `"read"`, `"exit_group"`.
+    #[serde(default)]
+    pub allowed_syscalls: Vec<String>,
+
+    /// Action for every other syscall.
+    #[serde(default)]
+    pub default_action: SeccompDefaultAction,
+}
+
+impl SeccompProfile {
+    /// Profile allowing `syscalls` and failing everything else with `EPERM`.
+    pub fn allow<I, S>(syscalls: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            allowed_syscalls: syscalls.into_iter().map(Into::into).collect(),
+            default_action: SeccompDefaultAction::Errno,
+        }
+    }
+
+    /// Use `action` for syscalls outside the allowlist.
+    pub fn with_default_action(mut self, action: SeccompDefaultAction) -> Self {
+        self.default_action = action;
+        self
+    }
 }
 
 // Internal helpers shared by `Default` and `disabled()`. The per-field serde
@@ -238,8 +339,11 @@ impl Default for SecurityOptions {
                 max_processes: Some(1024),
                 max_memory: None,   // VM config handles this
                 max_cpu_time: None, // VM config handles this
+                cpu_weight: None,
+                cpu_quota: None,
             },
             sandbox_profile: None,
+            seccomp_profile: None,
             network_enabled: default_network_enabled(),
         }
     }
@@ -271,6 +375,7 @@ impl SecurityOptions {
             env_allowlist: Vec::new(),
             resource_limits: ResourceLimits::default(),
             sandbox_profile: None,
+            seccomp_profile: None,
             network_enabled: default_network_enabled(),
         }
     }
@@ -309,6 +414,9 @@ impl SecurityOptions {
             if self.seccomp_enabled {
                 ignored.push("seccomp_enabled");
             }
+            if self.seccomp_profile.is_some() {
+                ignored.push("seccomp_profile");
+            }
             if self.new_pid_ns {
                 ignored.push("new_pid_ns");
             }
@@ -422,6 +530,12 @@ impl SecurityOptionsBuilder {
         self
     }
 
+    /// Set a custom seccomp syscall allowlist (Linux only).
+    pub fn seccomp_profile(&mut self, profile: SeccompProfile) -> &mut Self {
+        self.inner.seccomp_profile = Some(profile);
+        self
+    }
+
     /// Set UID to drop to after setup (Linux only).
     pub fn uid(&mut self, uid: u32) -> &mut Self {
         self.inner.uid = Some(uid);
@@ -530,6 +644,18 @@ impl SecurityOptionsBuilder {
         self
     }
 
+    /// Set the box's relative CPU share (cgroup `cpu.weight`, 1-10000).
+    pub fn cpu_weight(&mut self, weight: u32) -> &mut Self {
+        self.inner.resource_limits.cpu_weight = Some(weight);
+        self
+    }
+
+    /// Cap CPU time at `quota_us` per `period_us` (cgroup `cpu.max`).
+    pub fn cpu_quota(&mut self, quota_us: u64, period_us: u64) -> &mut Self {
+        self.inner.resource_limits.cpu_quota = Some((quota_us, period_us));
+        self
+    }
+
     // ─────────────────────────────────────────────────────────────────────
     // macOS-specific settings
     // ─────────────────────────────────────────────────────────────────────
@@ -599,4 +725,12 @@ pub struct AdvancedBoxOptions {
     /// Most users should rely on the defaults.
     #[serde(default)]
     pub health_check: Option<HealthCheckOptions>,
+
+    /// Split the guest interrupt controller between the host kernel and the VMM.
+    ///
+    /// Maps to libkrun's `krun_split_irqchip`. Only meaningful on x86_64 (KVM);
+    /// setting it on any other platform is rejected with `Unsupported` rather
+    /// than ignored. `None` leaves the engine default in place.
+    #[serde(default)]
+    pub split_irqchip: Option<bool>,
 }
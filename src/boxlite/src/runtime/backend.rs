@@ -7,12 +7,16 @@ use async_trait::async_trait;
 
 use crate::litebox::copy::CopyOptions;
 use crate::litebox::snapshot_mgr::SnapshotInfo;
-use crate::litebox::{BoxCommand, BoxTunnel, Execution, LiteBox};
+use crate::litebox::{BoxCommand, BoxConnection, BoxTunnel, Execution, GuestLogStream, LiteBox};
 use crate::metrics::{BoxMetrics, RuntimeMetrics};
+use crate::net::{BackendCapabilities, Forward};
 use crate::runtime::options::{
-    BoxArchive, BoxOptions, CloneOptions, ExportOptions, SnapshotOptions,
+    BoxArchive, BoxOptions, CloneOptions, CommitOptions, ExportOptions, SnapshotOptions,
 };
-use crate::runtime::types::BoxInfo;
+use crate::runtime::types::{
+    BoxEvent, BoxInfo, ExecutionInfo, ImageInfo, ProcessInfo, ValidationReport,
+};
+use crate::vmm::EngineFeatures;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
 use super::id::BoxID;
@@ -46,6 +50,12 @@ pub(crate) trait RuntimeBackend: Send + Sync {
 
     async fn remove(&self, id_or_name: &str, force: bool) -> BoxliteResult<()>;
 
+    async fn rename(&self, _id_or_name: &str, _new_name: &str) -> BoxliteResult<()> {
+        Err(BoxliteError::Unsupported(
+            "Rename is only available for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
     async fn shutdown(&self, timeout: Option<i32>) -> BoxliteResult<()>;
 
     async fn import_box(
@@ -58,6 +68,27 @@ pub(crate) trait RuntimeBackend: Send + Sync {
         ))
     }
 
+    /// Check `options` without creating a box or pulling layers.
+    async fn validate(&self, _options: &BoxOptions) -> BoxliteResult<ValidationReport> {
+        Err(BoxliteError::Unsupported(
+            "Validation is only available for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    /// Features of the VM engine boxes run on.
+    fn engine_features(&self) -> BoxliteResult<EngineFeatures> {
+        Err(BoxliteError::Unsupported(
+            "Engine features are only available for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    /// Receive box status changes from now on.
+    fn subscribe(&self) -> BoxliteResult<tokio::sync::broadcast::Receiver<BoxEvent>> {
+        Err(BoxliteError::Unsupported(
+            "Box events are only available for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
     /// Synchronous shutdown for atexit/Drop contexts.
     /// Default no-op (REST backend doesn't manage local processes).
     fn shutdown_sync(&self) {}
@@ -71,7 +102,7 @@ pub(crate) trait RuntimeBackend: Send + Sync {
 pub(crate) trait BoxBackend: Send + Sync {
     fn id(&self) -> &BoxID;
 
-    fn name(&self) -> Option<&str>;
+    fn name(&self) -> Option<String>;
 
     fn info(&self) -> BoxInfo;
 
@@ -85,8 +116,7 @@ pub(crate) trait BoxBackend: Send + Sync {
     /// for the same id. Returns `BoxliteError::SessionReaped` if the
     /// server reports the session is no longer attachable.
     ///
-    /// Default impl returns `Unsupported` — backends that don't model
-    /// long-lived attachable sessions (local in-process) don't need it.
+    /// Default impl returns `Unsupported`.
     async fn attach(&self, _execution_id: &str) -> BoxliteResult<Execution> {
         Err(BoxliteError::Unsupported(
             "this backend does not support reattaching to existing executions".into(),
@@ -95,6 +125,19 @@ pub(crate) trait BoxBackend: Send + Sync {
 
     async fn metrics(&self) -> BoxliteResult<BoxMetrics>;
 
+    async fn set_memory_mib(&self, _mib: u32) -> BoxliteResult<u32> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    /// The last `lines` lines of guest console output.
+    fn console_logs(&self, _lines: usize) -> BoxliteResult<Vec<String>> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
     async fn stop(&self) -> BoxliteResult<()>;
 
     async fn copy_into(
@@ -125,6 +168,39 @@ pub(crate) trait BoxBackend: Send + Sync {
     ) -> BoxliteResult<Vec<LiteBox>>;
 
     async fn export_box(&self, options: ExportOptions, dest: &Path) -> BoxliteResult<BoxArchive>;
+
+    async fn commit(&self, _reference: &str, _options: CommitOptions) -> BoxliteResult<ImageInfo> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    async fn export_rootfs(
+        &self,
+        _writer: &mut (dyn tokio::io::AsyncWrite + Unpin + Send),
+    ) -> BoxliteResult<()> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    async fn guest_log_stream(&self) -> BoxliteResult<GuestLogStream> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    async fn processes(&self) -> BoxliteResult<Vec<ProcessInfo>> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
+
+    async fn executions(&self) -> BoxliteResult<Vec<ExecutionInfo>> {
+        Err(BoxliteError::Unsupported(
+            "This operation is only supported for local runtimes (not REST backends)".to_string(),
+        ))
+    }
 }
 
 /// Backend abstraction for box network operations.
@@ -137,6 +213,37 @@ pub(crate) trait BoxNetworkBackend: Send + Sync {
     /// remote backends attach a public URL, and either kind can lazily open
     /// the raw byte stream via [`BoxTunnel::connect`].
     async fn tunnel(&self, target: SocketAddr) -> BoxliteResult<BoxTunnel>;
+
+    /// Optional features supported by the box's network backend.
+    async fn capabilities(&self) -> BoxliteResult<BackendCapabilities>;
+
+    /// Forward host TCP `host_port` to the guest's `guest_port` on a running box.
+    async fn expose_port(&self, _host_port: u16, _guest_port: u16) -> BoxliteResult<()> {
+        Err(BoxliteError::Unsupported(
+            "runtime port forwarding is unavailable".into(),
+        ))
+    }
+
+    /// Remove the forward bound at host `host_port`.
+    async fn unexpose_port(&self, _host_port: u16) -> BoxliteResult<()> {
+        Err(BoxliteError::Unsupported(
+            "runtime port forwarding is unavailable".into(),
+        ))
+    }
+
+    /// Active forwards, both the box's boot-time port mappings and runtime ones.
+    async fn port_forwards(&self) -> BoxliteResult<Vec<Forward>> {
+        Err(BoxliteError::Unsupported(
+            "runtime port forwarding is unavailable".into(),
+        ))
+    }
+
+    /// Open a stream to the guest listener on registered vsock `port`.
+    async fn vsock_connect(&self, _port: u32) -> BoxliteResult<Box<dyn BoxConnection>> {
+        Err(BoxliteError::Unsupported(
+            "vsock channels are only available for local boxes".into(),
+        ))
+    }
 }
 
 /// Network backend used when the current runtime does not provide networking.
@@ -151,6 +258,12 @@ impl BoxNetworkBackend for UnsupportedNetworkBackend {
             "box networking is unavailable".into(),
         ))
     }
+
+    async fn capabilities(&self) -> BoxliteResult<BackendCapabilities> {
+        Err(BoxliteError::Unsupported(
+            "box networking is unavailable".into(),
+        ))
+    }
 }
 
 /// Backend abstraction for snapshot lifecycle operations on a box.
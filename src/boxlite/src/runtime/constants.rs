@@ -22,6 +22,13 @@ pub mod envs {
     pub(crate) const BOXLITE_KRUNFW_KERNEL_PATH: &str = "BOXLITE_KRUNFW_KERNEL_PATH";
     pub(crate) const BOXLITE_KRUNFW_KERNEL_FORMAT: &str = "BOXLITE_KRUNFW_KERNEL_FORMAT";
 
+    /// Firecracker binary (default: `firecracker` on `PATH`).
+    pub(crate) const BOXLITE_FIRECRACKER_BIN: &str = "BOXLITE_FIRECRACKER_BIN";
+    /// Uncompressed guest kernel for Firecracker boxes.
+    pub(crate) const BOXLITE_FIRECRACKER_KERNEL: &str = "BOXLITE_FIRECRACKER_KERNEL";
+    /// Host tap device backing a Firecracker box's NIC.
+    pub(crate) const BOXLITE_FIRECRACKER_TAP: &str = "BOXLITE_FIRECRACKER_TAP";
+
     /// REST API base URL (required for REST mode).
     #[cfg(feature = "rest")]
     pub const BOXLITE_REST_URL: &str = "BOXLITE_REST_URL";
@@ -71,6 +78,9 @@ pub mod vm_defaults {
 
     /// Default disk size in GB for the container rootfs (sparse, grows as needed)
     pub const DEFAULT_DISK_SIZE_GB: u64 = 10;
+
+    /// Default lines of console output kept per Box
+    pub const DEFAULT_CONSOLE_RING_LINES: usize = 1000;
 }
 
 /// File naming patterns
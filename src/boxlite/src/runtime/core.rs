@@ -4,14 +4,18 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, OnceLock};
 
 use crate::litebox::LiteBox;
-use crate::metrics::RuntimeMetrics;
+use crate::metrics::{RuntimeMetrics, RuntimeStats};
 use crate::runtime::backend::RuntimeBackend;
 use crate::runtime::images::ImageBackend;
-use crate::runtime::options::{BoxArchive, BoxOptions, BoxliteOptions};
+use crate::runtime::options::{BoxArchive, BoxOptions, BoxliteOptions, PruneOptions};
 use crate::runtime::rt_impl::{LocalRuntime, RuntimeImpl};
 use crate::runtime::signal_handler::install_signal_handler;
-use crate::runtime::types::BoxInfo;
+use crate::runtime::types::{BoxEvent, BoxFilter, BoxInfo, PrunedReport, ValidationReport};
+use crate::vmm::EngineFeatures;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use futures::Stream;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
 
 #[cfg(feature = "rest")]
 use crate::rest::runtime::RestRuntime;
@@ -285,6 +289,21 @@ impl BoxliteRuntime {
         self.backend.get_or_create(options, name).await
     }
 
+    /// Check `options` without creating a box.
+    ///
+    /// Runs the side-effect-free parts of `create` and `start`: option
+    /// sanitizing, host resource limits, volume host paths, and image
+    /// resolution against the configured registries. No box is persisted
+    /// and no layers are pulled. Problems are collected in the report
+    /// rather than returned as the first error.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoxliteError::Unsupported` for REST runtimes.
+    pub async fn validate(&self, options: &BoxOptions) -> BoxliteResult<ValidationReport> {
+        self.backend.validate(options).await
+    }
+
     /// Get a handle to an existing box by ID or name.
     ///
     /// The `id_or_name` parameter can be either:
@@ -304,6 +323,15 @@ impl BoxliteRuntime {
         self.backend.list_info().await
     }
 
+    /// List the boxes matching `filter`, newest first.
+    ///
+    /// Filters on labels, status and image, like `docker ps --filter`.
+    pub async fn list_info_filtered(&self, filter: BoxFilter) -> BoxliteResult<Vec<BoxInfo>> {
+        let mut infos = self.list_info().await?;
+        infos.retain(|info| filter.matches(info));
+        Ok(infos)
+    }
+
     /// Check if a box with the given ID or name exists.
     pub async fn exists(&self, id_or_name: &str) -> BoxliteResult<bool> {
         self.backend.exists(id_or_name).await
@@ -314,11 +342,90 @@ impl BoxliteRuntime {
         self.backend.metrics().await
     }
 
+    /// Stream [`RuntimeStats`] snapshots, one per `interval`.
+    ///
+    /// The first snapshot is taken immediately. Each one sums the usage of
+    /// the boxes running at that moment, whose metrics are read
+    /// concurrently, so boxes created or removed later show up in later
+    /// snapshots. A tick whose runtime metrics cannot be read is skipped.
+    /// The stream never ends on its own; drop it to stop sampling.
+    pub fn stats_stream(
+        &self,
+        interval: Duration,
+    ) -> impl Stream<Item = RuntimeStats> + Send + 'static {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        futures::stream::unfold(
+            (self.backend.clone(), ticker),
+            |(backend, mut ticker)| async move {
+                loop {
+                    ticker.tick().await;
+                    match collect_runtime_stats(backend.as_ref()).await {
+                        Ok(stats) => return Some((stats, (backend, ticker))),
+                        Err(e) => tracing::warn!("Skipping runtime stats sample: {}", e),
+                    }
+                }
+            },
+        )
+    }
+
+    /// Features supported by the VM engine this runtime runs boxes on.
+    ///
+    /// Check these before requesting an engine-dependent option (disk format,
+    /// nested virtualization, ...) in [`BoxOptions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoxliteError::Unsupported` for REST runtimes, whose engine
+    /// runs remotely.
+    pub fn engine_features(&self) -> BoxliteResult<EngineFeatures> {
+        self.backend.engine_features()
+    }
+
+    /// Stream box status changes (created, running, stopped, failed, ...).
+    ///
+    /// Only changes made after subscribing are delivered. A subscriber that
+    /// falls behind skips the oldest events rather than holding up the
+    /// runtime; the stream ends when the runtime is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoxliteError::Unsupported` for REST runtimes.
+    pub fn subscribe(&self) -> BoxliteResult<impl Stream<Item = BoxEvent> + Send + 'static> {
+        let rx = self.backend.subscribe()?;
+        Ok(futures::stream::unfold(rx, |mut rx| async move {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => return Some((event, rx)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!(skipped, "Box event subscriber lagged; events dropped");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }))
+    }
+
     /// Remove a box completely by ID or name.
     pub async fn remove(&self, id_or_name: &str, force: bool) -> BoxliteResult<()> {
         self.backend.remove(id_or_name, force).await
     }
 
+    /// Rename a box, found by ID or name, like `docker rename`.
+    ///
+    /// The old name stops resolving immediately. Handles obtained before the
+    /// rename keep returning the old name from [`LiteBox::name`]; their
+    /// `info()` reports the new one.
+    ///
+    /// # Errors
+    ///
+    /// - `InvalidArgument` if `new_name` is empty or contains `/`.
+    /// - `AlreadyExists` if another box has `new_name`.
+    /// - `Unsupported` for REST runtimes.
+    pub async fn rename(&self, id_or_name: &str, new_name: &str) -> BoxliteResult<()> {
+        self.backend.rename(id_or_name, new_name).await
+    }
+
     /// Import a box from a `.boxlite` archive.
     ///
     /// Creates a new box with a new ID from archived disk images and configuration.
@@ -419,6 +526,39 @@ impl BoxliteRuntime {
         }
     }
 
+    /// Delete image store blobs that no cached image or existing box uses.
+    ///
+    /// Layers, configs, and disk images orphaned by removed images are
+    /// deleted, along with caches of local OCI bundles that no box uses.
+    /// Set `dry_run` to only report what would go, and `until` to spare
+    /// blobs modified since then.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BoxliteError::Unsupported` on a REST runtime.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use boxlite::PruneOptions;
+    /// use boxlite::runtime::BoxliteRuntime;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let runtime = BoxliteRuntime::with_defaults()?;
+    /// let report = runtime
+    ///     .prune_images(PruneOptions {
+    ///         dry_run: true,
+    ///         ..Default::default()
+    ///     })
+    ///     .await?;
+    /// println!("Would reclaim {} bytes", report.reclaimed_bytes);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn prune_images(&self, options: PruneOptions) -> BoxliteResult<PrunedReport> {
+        self.images()?.prune(options).await
+    }
+
     /// Get a handle for identity operations (`whoami`).
     ///
     /// Returns an [`AuthHandle`](crate::AuthHandle) that resolves the calling
@@ -457,6 +597,29 @@ impl BoxliteRuntime {
     }
 }
 
+/// Take one [`RuntimeStats`] sample from `backend`.
+///
+/// Box metrics are read concurrently, each behind its own box's lock; a box
+/// that stops or disappears mid-sample is left out of the sums.
+async fn collect_runtime_stats(backend: &dyn RuntimeBackend) -> BoxliteResult<RuntimeStats> {
+    let runtime = backend.metrics().await?;
+    let running: Vec<BoxInfo> = backend
+        .list_info()
+        .await?
+        .into_iter()
+        .filter(|info| info.status.is_running())
+        .collect();
+
+    let samples = futures::future::join_all(running.iter().map(|info| async move {
+        let litebox = backend.get(info.id.as_str()).await.ok().flatten()?;
+        litebox.metrics().await.ok()
+    }))
+    .await;
+    let boxes: Vec<_> = samples.into_iter().flatten().collect();
+
+    Ok(RuntimeStats::collect(&runtime, &boxes))
+}
+
 // ============================================================================
 // DEBUG
 // ============================================================================
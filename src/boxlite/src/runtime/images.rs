@@ -9,7 +9,8 @@ use std::sync::Arc;
 
 use crate::BoxliteResult;
 use crate::images::ImageObject;
-use crate::runtime::types::ImageInfo;
+use crate::runtime::options::{ImportSource, PruneOptions};
+use crate::runtime::types::{ImageInfo, PrunedReport, PullProgress, PullProgressCallback};
 
 /// Internal trait for image management.
 ///
@@ -20,8 +21,21 @@ pub(crate) trait ImageBackend: Send + Sync {
     /// Pull an image from a registry.
     async fn pull_image(&self, image_ref: &str) -> BoxliteResult<ImageObject>;
 
+    /// Pull an image, reporting progress through `progress`.
+    async fn pull_image_with_progress(
+        &self,
+        image_ref: &str,
+        progress: PullProgressCallback,
+    ) -> BoxliteResult<ImageObject>;
+
     /// List all locally cached images.
     async fn list_images(&self) -> BoxliteResult<Vec<ImageInfo>>;
+
+    /// Delete image store blobs that no image or box references.
+    async fn prune_images(&self, options: PruneOptions) -> BoxliteResult<PrunedReport>;
+
+    /// Create a single-layer image from a local rootfs.
+    async fn import_rootfs(&self, source: ImportSource, tag: &str) -> BoxliteResult<ImageInfo>;
 }
 
 /// Handle for performing image operations.
@@ -85,6 +99,38 @@ impl ImageHandle {
         self.manager.pull_image(image_ref).await
     }
 
+    /// Pull an image, calling `progress` as it downloads.
+    ///
+    /// `progress` sees [`PullPhase::Manifest`](crate::runtime::types::PullPhase),
+    /// then `Downloading` updates for each layer (interleaved, since layers
+    /// download in parallel), then `Done`. A cached image only reports `Done`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use boxlite::{Boxlite, Options};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let runtime = Boxlite::new(Options::default())?;
+    /// let images = runtime.images()?;
+    /// let image = images
+    ///     .pull_with_progress("alpine:latest", |p| {
+    ///         println!("{:?} {:?} {}", p.phase, p.layer_digest, p.bytes_downloaded);
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pull_with_progress(
+        &self,
+        image_ref: &str,
+        progress: impl Fn(PullProgress) + Send + Sync + 'static,
+    ) -> BoxliteResult<ImageObject> {
+        self.manager
+            .pull_image_with_progress(image_ref, PullProgressCallback::new(progress))
+            .await
+    }
+
     /// List all locally cached images.
     ///
     /// Returns metadata for all images stored in the local cache.
@@ -107,4 +153,39 @@ impl ImageHandle {
     pub async fn list(&self) -> BoxliteResult<Vec<ImageInfo>> {
         self.manager.list_images().await
     }
+
+    /// Delete cached blobs that no image or box references.
+    ///
+    /// Removes layers, configs, manifests, and disk images left behind by
+    /// images that are no longer cached, plus caches of local bundles no box
+    /// uses. Cached images and everything an existing box depends on are
+    /// kept. See [`BoxliteRuntime::prune_images`](crate::BoxliteRuntime::prune_images).
+    pub async fn prune(&self, options: PruneOptions) -> BoxliteResult<PrunedReport> {
+        self.manager.prune_images(options).await
+    }
+
+    /// Create an image from a local rootfs directory or tarball, like
+    /// `docker import`.
+    ///
+    /// The rootfs becomes the image's only layer, with a minimal config (no
+    /// entrypoint or command). The image is stored under `tag`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// # use boxlite::{Boxlite, ImportSource, Options};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let runtime = Boxlite::new(Options::default())?;
+    /// let images = runtime.images()?;
+    /// let info = images
+    ///     .import_rootfs(ImportSource::Tarball("rootfs.tar".into()), "local/app:latest")
+    ///     .await?;
+    /// println!("Imported: {}", info.id);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn import_rootfs(&self, source: ImportSource, tag: &str) -> BoxliteResult<ImageInfo> {
+        self.manager.import_rootfs(source, tag).await
+    }
 }
@@ -400,6 +400,11 @@ impl BoxFilesystemLayout {
         self.sockets().ready_sock()
     }
 
+    /// Shim control socket binding path (see [`BoxSockets::control_sock`]).
+    pub fn control_socket_path(&self) -> PathBuf {
+        self.sockets().control_sock()
+    }
+
     /// Network backend socket binding path (gvproxy; libkrun derives a
     /// sibling `net.sock-krun.sock` — see [`BoxSockets::net_backend_sock`]).
     pub fn net_backend_socket_path(&self) -> PathBuf {
@@ -619,6 +624,7 @@ impl BoxFilesystemLayout {
 /// ~/.boxlite/images/
 /// ├── layers/                      # Downloaded layer tarballs (sha256-named)
 /// ├── extracted/                   # Extracted layer directories
+/// ├── cas/                         # Hardlink pool for deduplicated layer files
 /// ├── disk-images/                 # Cached ext4 disk images for COW overlays
 /// ├── manifests/                   # Image manifest JSON files
 /// ├── configs/                     # Image config JSON files
@@ -661,11 +667,15 @@ impl ImageFilesystemLayout {
             .unwrap_or(manifest_digest);
         let manifest_short = &manifest_short[..8.min(manifest_short.len())];
 
-        self.images_dir
-            .join("local")
+        self.local_dir()
             .join(format!("{}-{}", path_short, manifest_short))
     }
 
+    /// Local bundle caches directory: ~/.boxlite/images/local
+    pub fn local_dir(&self) -> PathBuf {
+        self.images_dir.join("local")
+    }
+
     /// Root directory: ~/.boxlite/images
     pub fn root(&self) -> &Path {
         &self.images_dir
@@ -681,6 +691,11 @@ impl ImageFilesystemLayout {
         self.images_dir.join("extracted")
     }
 
+    /// Hardlink pool for deduplicated layer files: ~/.boxlite/images/cas
+    pub fn cas_dir(&self) -> PathBuf {
+        self.images_dir.join("cas")
+    }
+
     /// Disk images directory: ~/.boxlite/images/disk-images
     pub fn disk_images_dir(&self) -> PathBuf {
         self.images_dir.join("disk-images")
@@ -6,6 +6,7 @@ pub mod id;
 pub mod images;
 pub mod layout;
 pub(crate) mod lock;
+pub mod names;
 pub mod options;
 pub(crate) mod signal_handler;
 pub mod types;
@@ -0,0 +1,77 @@
+//! Random names for boxes created without one.
+//!
+//! Mirrors Docker's `adjective_noun` container names, so `boxlite ls` stays
+//! readable when callers don't name their boxes.
+
+use rand::seq::IndexedRandom;
+
+const ADJECTIVES: &[&str] = &[
+    "agile", "bold", "brave", "bright", "calm", "clever", "cosmic", "crisp", "daring", "eager",
+    "fancy", "fierce", "gentle", "glad", "happy", "hardy", "humble", "jolly", "keen", "kind",
+    "lively", "lucid", "merry", "mighty", "nimble", "noble", "patient", "plucky", "proud", "quick",
+    "quiet", "rapid", "serene", "sharp", "shiny", "steady", "sunny", "swift", "tidy", "vivid",
+    "warm", "wise", "witty", "zesty",
+];
+
+const NOUNS: &[&str] = &[
+    "badger", "beacon", "bison", "comet", "condor", "coyote", "crane", "falcon", "ferret", "finch",
+    "fox", "gecko", "glacier", "harbor", "heron", "ibex", "jaguar", "kestrel", "koala", "lantern",
+    "lynx", "marmot", "meadow", "meteor", "narwhal", "nebula", "otter", "owl", "panda", "pebble",
+    "pelican", "puffin", "quasar", "raven", "river", "salmon", "sparrow", "summit", "tiger",
+    "walrus", "willow", "wombat", "yak", "zebra",
+];
+
+/// How many names are proposed before `create` gives up.
+pub(crate) const MAX_NAME_ATTEMPTS: u32 = 16;
+
+/// Names boxes created without one.
+///
+/// Set [`BoxliteOptions::name_generator`](crate::BoxliteOptions::name_generator)
+/// to override the default [`RandomNameGenerator`].
+pub trait BoxNameGenerator: Send + Sync + std::fmt::Debug {
+    /// Propose a name for a new box.
+    ///
+    /// `attempt` counts from 0 and goes up each time the previous proposal
+    /// was already taken, so implementations can widen the name space on
+    /// retries.
+    fn generate(&self, attempt: u32) -> String;
+}
+
+/// Docker-style `adjective_noun` names.
+///
+/// Retries append a digit (`swift_otter3`), as Docker does.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RandomNameGenerator;
+
+impl BoxNameGenerator for RandomNameGenerator {
+    fn generate(&self, attempt: u32) -> String {
+        let mut rng = rand::rng();
+        let adjective = ADJECTIVES.choose(&mut rng).copied().unwrap_or("anonymous");
+        let noun = NOUNS.choose(&mut rng).copied().unwrap_or("box");
+        if attempt == 0 {
+            format!("{adjective}_{noun}")
+        } else {
+            format!("{adjective}_{noun}{}", rand::random_range(0..10))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_name_shape() {
+        let name = RandomNameGenerator.generate(0);
+        let (adjective, noun) = name.split_once('_').expect("adjective_noun");
+        assert!(ADJECTIVES.contains(&adjective), "{name}");
+        assert!(NOUNS.contains(&noun), "{name}");
+    }
+
+    #[test]
+    fn test_retry_appends_digit() {
+        let name = RandomNameGenerator.generate(1);
+        assert!(name.ends_with(|c: char| c.is_ascii_digit()), "{name}");
+        assert!(!name.contains('/'), "{name}");
+    }
+}
@@ -1,14 +1,21 @@
 //! Configuration for Boxlite.
 
+use crate::images::ImageBlobStore;
 use crate::runtime::constants::envs as const_envs;
 use crate::runtime::layout::dirs as const_dirs;
+use crate::runtime::names::BoxNameGenerator;
 use boxlite_shared::errors::BoxliteResult;
 use dirs::home_dir;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
-use crate::runtime::advanced_options::AdvancedBoxOptions;
+use crate::runtime::advanced_options::{AdvancedBoxOptions, ResourceLimits, SecurityOptions};
+use crate::runtime::types::PullProgressCallback;
 use std::fmt;
+use std::sync::Arc;
 
 // ============================================================================
 // Runtime Options
@@ -46,6 +53,58 @@ pub struct BoxliteOptions {
     /// ```
     #[serde(default)]
     pub image_registries: Vec<ImageRegistry>,
+    /// Upper bound on the image store's on-disk size, in bytes.
+    ///
+    /// When a pull pushes the store past this cap, least-recently-used images
+    /// that no running box depends on are evicted until it fits again.
+    /// `None` (default) leaves the store unbounded.
+    #[serde(default)]
+    pub image_store_max_bytes: Option<u64>,
+    /// Factor applied to host memory when admitting boxes.
+    ///
+    /// A box's `memory_mib` may not exceed host memory times this factor;
+    /// values above 1.0 allow overcommit. `None` (default) means 1.0.
+    #[serde(default)]
+    pub memory_overcommit: Option<f64>,
+    /// Retry policy for transient registry failures during image pulls.
+    ///
+    /// `None` (default) uses [`RetryPolicy::default`].
+    #[serde(default)]
+    pub pull_retry: Option<RetryPolicy>,
+    /// Hardlink identical files across extracted image layers to a shared
+    /// pool under `images/cas/`, so each is stored once.
+    ///
+    /// Needs a filesystem with hardlink support; files that cannot be
+    /// linked keep their own copy. Defaults to false.
+    #[serde(default)]
+    pub dedup_layers: bool,
+    /// Number of parsed image configs kept in memory, so boxes created from
+    /// the same image skip re-reading its config from disk.
+    ///
+    /// `None` (default) keeps 64; `Some(0)` disables the cache.
+    #[serde(default)]
+    pub image_config_cache_capacity: Option<usize>,
+    /// Maximum number of boxes booting at once.
+    ///
+    /// Further starts queue until a running boot reaches ready or fails,
+    /// so bulk creation does not exhaust host memory, FDs or KVM slots.
+    /// `None` (default) leaves boots unlimited; `Some(0)` is rejected.
+    #[serde(default)]
+    pub max_concurrent_boots: Option<usize>,
+    /// Backend for image manifests, configs, and layers.
+    ///
+    /// `None` (default) stores blobs under `home_dir/images`. The image
+    /// index stays in the runtime database either way, and `dedup_layers`
+    /// only applies to the default store. Not serialized.
+    #[serde(skip)]
+    pub image_blob_store: Option<Arc<dyn ImageBlobStore>>,
+    /// Names boxes created without one.
+    ///
+    /// `None` (default) uses [`RandomNameGenerator`](crate::RandomNameGenerator)
+    /// (`adjective_noun`). Proposals that collide with an existing box are
+    /// retried. Not serialized.
+    #[serde(skip)]
+    pub name_generator: Option<Arc<dyn BoxNameGenerator>>,
 }
 
 /// Registry host configuration for OCI image pulls.
@@ -63,6 +122,10 @@ pub struct ImageRegistry {
     #[serde(default)]
     pub search: bool,
     /// Authentication credentials for this registry.
+    ///
+    /// When `Anonymous`, pulls use the credentials `docker login` stored for
+    /// the host in `$DOCKER_CONFIG/config.json` (default
+    /// `~/.docker/config.json`), if any.
     #[serde(default)]
     pub auth: ImageRegistryAuth,
 }
@@ -156,6 +219,48 @@ impl fmt::Debug for ImageRegistryAuth {
     }
 }
 
+/// Retry behavior for transient registry failures during image pulls.
+///
+/// Connection errors, 5xx responses, and interrupted blob downloads are
+/// retried with exponential backoff and jitter; an interrupted layer resumes
+/// with a `Range` request when the registry supports it. 4xx responses
+/// (authentication required, not found) fail immediately.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt. 0 disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each later one.
+    pub initial_backoff_ms: u64,
+    /// Upper bound on the delay between retries.
+    pub max_backoff_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff_ms: 500,
+            max_backoff_ms: 10_000,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retry number `retry` (1-based).
+    ///
+    /// The exponential delay is capped at `max_backoff_ms`, then jittered
+    /// into its upper half so concurrent pulls don't retry in lockstep.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(32);
+        let delay = self
+            .initial_backoff_ms
+            .saturating_mul(1 << exponent)
+            .min(self.max_backoff_ms);
+        let jitter = rand::random_range(0..=delay / 2);
+        Duration::from_millis(delay - delay / 2 + jitter)
+    }
+}
+
 fn default_home_dir() -> PathBuf {
     std::env::var(const_envs::BOXLITE_HOME)
         .map(PathBuf::from)
@@ -171,6 +276,14 @@ impl Default for BoxliteOptions {
         Self {
             home_dir: default_home_dir(),
             image_registries: Vec::new(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         }
     }
 }
@@ -255,6 +368,14 @@ mod registry_options_tests {
                     .with_basic_auth("alice", password.as_str()),
                 ImageRegistry::https("registry.example.com").with_bearer_auth(token.as_str()),
             ],
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         };
 
         let value = serde_json::to_value(options).unwrap();
@@ -308,6 +429,45 @@ mod registry_options_tests {
         assert!(!basic.contains(&password));
         assert!(!bearer.contains(&token));
     }
+
+    #[test]
+    fn retry_policy_backoff_doubles_with_jitter_up_to_cap() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff_ms: 100,
+            max_backoff_ms: 300,
+        };
+        for _ in 0..20 {
+            let first = policy.backoff(1).as_millis();
+            let second = policy.backoff(2).as_millis();
+            let capped = policy.backoff(10).as_millis();
+            assert!((50..=100).contains(&first), "{first}");
+            assert!((100..=200).contains(&second), "{second}");
+            assert!((150..=300).contains(&capped), "{capped}");
+        }
+    }
+
+    #[test]
+    fn options_deserialize_dedup_layers() {
+        let options: BoxliteOptions = serde_json::from_value(json!({})).unwrap();
+        assert!(!options.dedup_layers);
+
+        let options: BoxliteOptions =
+            serde_json::from_value(json!({"dedup_layers": true})).unwrap();
+        assert!(options.dedup_layers);
+    }
+
+    #[test]
+    fn options_deserialize_pull_retry() {
+        let options: BoxliteOptions = serde_json::from_value(json!({
+            "pull_retry": {"max_retries": 1, "initial_backoff_ms": 10, "max_backoff_ms": 20}
+        }))
+        .unwrap();
+        assert_eq!(options.pull_retry.unwrap().max_retries, 1);
+
+        let options: BoxliteOptions = serde_json::from_value(json!({})).unwrap();
+        assert!(options.pull_retry.is_none());
+    }
 }
 
 /// Options used when constructing a box.
@@ -316,18 +476,79 @@ mod registry_options_tests {
 pub struct BoxOptions {
     pub cpus: Option<u8>,
     pub memory_mib: Option<u32>,
+    /// Host CPU cores (0-based) to pin the box's VM to.
+    ///
+    /// On Linux the shim process, and with it every vCPU thread, is
+    /// restricted to these cores via `sched_setaffinity`. Ignored with a
+    /// warning on other platforms. Indices must name existing host CPUs.
+    /// Use [`parse_cpu_list`] to build this from a `"0-3,6"` style list.
+    #[serde(default)]
+    pub cpu_affinity: Option<Vec<usize>>,
     /// Disk size in GB for the container rootfs (sparse, grows as needed).
     ///
     /// The actual disk will be at least as large as the base image.
     /// If set, the COW overlay will have this virtual size, allowing
     /// the container to write more data than the base image size.
     pub disk_size_gb: Option<u64>,
+    /// Default working directory, like a Dockerfile `WORKDIR`.
+    ///
+    /// Overrides the image's `WORKDIR` for the container and every exec that
+    /// does not set [`BoxCommand::working_dir`](crate::BoxCommand::working_dir).
     pub working_dir: Option<String>,
+    /// Default environment, like a Dockerfile `ENV`.
+    ///
+    /// Merged over the image's environment; a command's own `env` entries
+    /// override these per exec.
     pub env: Vec<(String, String)>,
     pub rootfs: RootfsSpec,
+    /// Platform to pull from a multi-platform image.
+    ///
+    /// Defaults to Linux on the host architecture.
+    #[serde(default)]
+    pub platform: Option<Platform>,
+    /// When to contact the registry for the rootfs image.
+    ///
+    /// Defaults to [`PullPolicy::IfNotPresent`]. Ignored for
+    /// [`RootfsSpec::RootfsPath`] and [`RootfsSpec::OciBundle`].
+    #[serde(default)]
+    pub pull_policy: PullPolicy,
     pub volumes: Vec<VolumeSpec>,
+    /// In-memory tmpfs mounts, for scratch space that should not touch disk.
+    #[serde(default)]
+    pub tmpfs: Vec<TmpfsMount>,
     pub network: NetworkSpec,
+    /// Bandwidth cap for the box's network, in bytes per second.
+    ///
+    /// Box start fails with `Unsupported` if the network backend cannot
+    /// rate limit. Ignored when the network is disabled.
+    #[serde(default)]
+    pub network_rate_limit: Option<crate::net::RateLimit>,
+    /// MAC address of the guest NIC. Must be a locally administered unicast
+    /// address. Defaults to a fixed MAC shared by all boxes.
+    #[serde(default)]
+    pub mac_address: Option<[u8; 6]>,
     pub ports: Vec<PortSpec>,
+    /// Resolver settings for the container's `/etc/resolv.conf`.
+    ///
+    /// By default the container resolves through gvproxy's DNS server.
+    #[serde(default)]
+    pub dns: DnsConfig,
+    /// Extra `/etc/hosts` entries for the container, like `--add-host`.
+    ///
+    /// See [`parse_extra_host`] for the `name:ip` form and `host-gateway`.
+    #[serde(default)]
+    pub extra_hosts: Vec<(String, IpAddr)>,
+    /// Guest vsock ports applications listen on, reachable from the host via
+    /// `LiteBox::vsock_connect`.
+    ///
+    /// Ports below `USER_VSOCK_PORT_MIN` (4096) are reserved for BoxLite's
+    /// agent channels (2695, 2696) and are rejected.
+    #[serde(default)]
+    pub vsock_ports: Vec<u32>,
+    /// Lines of guest console output (kernel and init messages) kept for
+    /// `LiteBox::console_logs`. Defaults to 1000.
+    #[serde(default)]
+    pub console_ring_lines: Option<usize>,
     /// Automatically remove box when stopped.
     ///
     /// When true (default), the box is removed from the database and its
@@ -352,6 +573,74 @@ pub struct BoxOptions {
     #[serde(default = "default_detach")]
     pub detach: bool,
 
+    /// Seconds running executions get to exit after SIGTERM when the box
+    /// stops, before they are SIGKILLed. Defaults to 10, matching the
+    /// default `BoxliteRuntime::shutdown` timeout.
+    #[serde(default)]
+    pub stop_grace_secs: Option<u64>,
+
+    /// Seconds the guest gets to boot, from VM spawn until the container is
+    /// initialized. On timeout the VM is torn down and start fails.
+    /// Defaults to 60.
+    #[serde(default)]
+    pub boot_timeout_secs: Option<u64>,
+
+    /// Stop the box after this many seconds with no executions running.
+    ///
+    /// The window restarts whenever an execution starts or completes. When
+    /// it elapses the shim shuts the VM down gracefully, as on `stop()`, and
+    /// the box becomes `Stopped`; later exec calls fail until it is started
+    /// again. `None` (the default) keeps the box up until stopped.
+    #[serde(default)]
+    pub idle_timeout_secs: Option<u64>,
+
+    /// Boot a custom guest kernel instead of the bundled one.
+    ///
+    /// Linux only; start fails with `Unsupported` on macOS, where libkrun
+    /// always boots its bundled kernel.
+    #[serde(default)]
+    pub kernel: Option<KernelSpec>,
+
+    /// Parameters appended, in order, to the guest kernel command line
+    /// (e.g. `"loglevel=7"`, `"cgroup_no_v1=all"`).
+    ///
+    /// libkrun can only change the command line of a kernel it is handed, so
+    /// this needs `kernel` or the external libkrunfw kernel; start fails with
+    /// `Unsupported` on the bundled one.
+    #[serde(default)]
+    pub kernel_cmdline_append: Vec<String>,
+
+    /// Mount the container rootfs read-only, like `docker run --read-only`.
+    ///
+    /// Writes outside `volumes`, `tmpfs` and the runtime's own mounts
+    /// (`/dev`, `/proc`, `/etc/hosts`, ...) fail with `EROFS`.
+    #[serde(default)]
+    pub read_only_rootfs: bool,
+
+    /// Capabilities granted to the container on top of the default set,
+    /// like `docker run --cap-add`.
+    ///
+    /// Names are case-insensitive, with or without the `CAP_` prefix
+    /// (`"NET_ADMIN"`, `"cap_sys_ptrace"`); `"ALL"` grants every capability.
+    /// The default set matches Docker's: `CHOWN`, `DAC_OVERRIDE`, `FOWNER`,
+    /// `FSETID`, `KILL`, `SETGID`, `SETUID`, `SETPCAP`, `NET_BIND_SERVICE`,
+    /// `NET_RAW`, `SYS_CHROOT`, `MKNOD`, `AUDIT_WRITE` and `SETFCAP`.
+    #[serde(default)]
+    pub cap_add: Vec<String>,
+
+    /// Capabilities removed from the default set, like `docker run
+    /// --cap-drop`. `"ALL"` leaves only `cap_add`. An entry in both lists
+    /// is granted.
+    #[serde(default)]
+    pub cap_drop: Vec<String>,
+
+    /// User-defined key/value labels, like Docker's `--label`.
+    ///
+    /// Stored with the box and reported in [`BoxInfo`](crate::BoxInfo), so
+    /// boxes can be selected with a [`BoxFilter`](crate::BoxFilter).
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+
     /// Advanced options for expert users (security, mount isolation).
     ///
     /// Defaults are secure — most users can ignore this entirely.
@@ -361,8 +650,9 @@ pub struct BoxOptions {
 
     /// Override the image's ENTRYPOINT directive.
     ///
-    /// When set, completely replaces the image's ENTRYPOINT.
-    /// Use with `cmd` to build the full command:
+    /// When set, completely replaces the image's ENTRYPOINT and, as with
+    /// `docker run --entrypoint`, drops the image's CMD. Use with `cmd` to
+    /// build the full command:
     ///   Final execution = entrypoint + cmd
     ///
     /// Example: For `docker:dind`, bypass the failing entrypoint script:
@@ -386,6 +676,55 @@ pub struct BoxOptions {
     #[serde(default)]
     pub user: Option<String>,
 
+    /// Run the container in its own user namespace with these ID mappings.
+    ///
+    /// Lets container root map to an unprivileged guest uid. The guest
+    /// idmaps the container rootfs with the same mappings, so files owned by
+    /// root in the image still appear root-owned inside the container.
+    /// If None, the container shares the guest's user namespace.
+    #[serde(default)]
+    pub userns_mapping: Option<UserNsMapping>,
+
+    /// Extra disk images attached to the guest as block devices.
+    ///
+    /// Disks are attached in order after the box's own disks and are not
+    /// mounted unless referenced by another option (e.g. `overlay_upper_disk`).
+    #[serde(default)]
+    pub disks: Vec<DiskSpec>,
+
+    /// Place the container rootfs' writable overlay layer on this disk.
+    ///
+    /// Must name the `id` of a writable entry in `disks`. The guest mounts
+    /// the rootfs disk as the read-only lower layer and keeps the overlay
+    /// upper/work dirs on this disk (formatting it as ext4 if blank).
+    /// If None, writes go straight to the rootfs disk.
+    #[serde(default)]
+    pub overlay_upper_disk: Option<String>,
+
+    /// Kernel modules the guest loads during init (e.g. `"tun"`, `"nf_tables"`).
+    ///
+    /// Modules are resolved from the guest rootfs (`/lib/modules/<release>`);
+    /// already-loaded or built-in modules are skipped. Box start fails if a
+    /// module cannot be found or loaded.
+    #[serde(default)]
+    pub kernel_modules: Vec<String>,
+
+    /// Named cgroup resource profiles for execs (e.g. `"heavy"`, `"light"`).
+    ///
+    /// A command selects one with `BoxCommand::resource_profile()`; the guest
+    /// runs it in its own cgroup with these limits, nested under the box-wide
+    /// limits.
+    #[serde(default)]
+    pub exec_profiles: HashMap<String, ExecResourceLimits>,
+
+    /// Resource limits for processes in the box, like `docker run --ulimit`.
+    ///
+    /// Each entry replaces the guest default for its limit. The jailer's
+    /// `resource_limits` for the box's host process are raised to fit, never
+    /// lowered.
+    #[serde(default)]
+    pub ulimits: Vec<Ulimit>,
+
     /// Secrets for MITM proxy injection into outbound HTTP(S) requests.
     ///
     /// Each secret maps a placeholder string to a real value. When the box
@@ -396,6 +735,14 @@ pub struct BoxOptions {
     /// guest; the real value never enters the VM.
     #[serde(default)]
     pub secrets: Vec<Secret>,
+
+    /// Called with image pull progress while the box is created.
+    ///
+    /// Reports the registry download, then `Extracting` while the rootfs is
+    /// built from the layers, then `Done`. Not persisted: restarts reuse the
+    /// existing rootfs and report nothing.
+    #[serde(skip)]
+    pub pull_progress: Option<PullProgressCallback>,
 }
 
 /// A secret for MITM proxy injection.
@@ -470,6 +817,144 @@ impl std::fmt::Display for Secret {
     }
 }
 
+/// A contiguous range of IDs mapped into a user namespace.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IdMapping {
+    /// First ID as seen inside the container.
+    pub container_id: u32,
+    /// First guest ID that `container_id` maps to.
+    pub host_id: u32,
+    /// Number of consecutive IDs in the range.
+    pub size: u32,
+}
+
+/// UID/GID mappings for running the container in a user namespace.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct UserNsMapping {
+    pub uid_map: Vec<IdMapping>,
+    pub gid_map: Vec<IdMapping>,
+}
+
+impl UserNsMapping {
+    /// Map container IDs `[0, size)` to guest IDs `[host_id, host_id + size)`
+    /// for both users and groups.
+    pub fn root_to(host_id: u32, size: u32) -> Self {
+        let range = IdMapping {
+            container_id: 0,
+            host_id,
+            size,
+        };
+        Self {
+            uid_map: vec![range.clone()],
+            gid_map: vec![range],
+        }
+    }
+
+    fn validate(&self) -> BoxliteResult<()> {
+        validate_id_map("uid_map", &self.uid_map)?;
+        validate_id_map("gid_map", &self.gid_map)
+    }
+}
+
+/// Reject maps the kernel would refuse when writing /proc/<pid>/{uid,gid}_map.
+fn validate_id_map(name: &str, map: &[IdMapping]) -> BoxliteResult<()> {
+    use boxlite_shared::errors::BoxliteError;
+
+    if map.is_empty() {
+        return Err(BoxliteError::Config(format!(
+            "userns_mapping.{name} must contain at least one range"
+        )));
+    }
+
+    let mut container_ranges = Vec::with_capacity(map.len());
+    let mut host_ranges = Vec::with_capacity(map.len());
+    for m in map {
+        if m.size == 0 {
+            return Err(BoxliteError::Config(format!(
+                "userns_mapping.{name} range {m:?} has size 0"
+            )));
+        }
+        let container_end = m.container_id.checked_add(m.size);
+        let host_end = m.host_id.checked_add(m.size);
+        let (Some(container_end), Some(host_end)) = (container_end, host_end) else {
+            return Err(BoxliteError::Config(format!(
+                "userns_mapping.{name} range {m:?} overflows the 32-bit ID space"
+            )));
+        };
+        container_ranges.push((m.container_id, container_end));
+        host_ranges.push((m.host_id, host_end));
+    }
+
+    for (side, ranges) in [
+        ("container", &mut container_ranges),
+        ("host", &mut host_ranges),
+    ] {
+        ranges.sort_unstable();
+        if ranges.windows(2).any(|w| w[0].1 > w[1].0) {
+            return Err(BoxliteError::Config(format!(
+                "userns_mapping.{name} has overlapping {side} ID ranges"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Parse a cpuset list such as `"0-3,6"` into sorted, deduplicated core indices.
+pub fn parse_cpu_list(list: &str) -> BoxliteResult<Vec<usize>> {
+    use boxlite_shared::errors::BoxliteError;
+
+    let invalid = |part: &str| {
+        BoxliteError::InvalidArgument(format!(
+            "invalid CPU list {list:?}: {part:?} is not a core index or range"
+        ))
+    };
+    let mut cores = Vec::new();
+    for part in list.split(',').map(str::trim) {
+        let (start, end) = match part.split_once('-') {
+            Some((start, end)) => (start.trim(), end.trim()),
+            None => (part, part),
+        };
+        let start: usize = start.parse().map_err(|_| invalid(part))?;
+        let end: usize = end.parse().map_err(|_| invalid(part))?;
+        if start > end {
+            return Err(invalid(part));
+        }
+        cores.extend(start..=end);
+    }
+    cores.sort_unstable();
+    cores.dedup();
+    Ok(cores)
+}
+
+/// Reject affinity lists that are empty or name cores the host doesn't have.
+fn validate_cpu_affinity(cores: &[usize], available: usize) -> BoxliteResult<()> {
+    use boxlite_shared::errors::BoxliteError;
+
+    if cores.is_empty() {
+        return Err(BoxliteError::InvalidArgument(
+            "cpu_affinity must list at least one core".to_string(),
+        ));
+    }
+    if let Some(core) = cores.iter().find(|&&core| core >= available) {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "cpu_affinity core {core} is out of range: host has {available} CPUs (0-{})",
+            available.saturating_sub(1)
+        )));
+    }
+    Ok(())
+}
+
+/// CPUs configured on the host, including ones outside this process's mask.
+fn host_cpu_count() -> usize {
+    // SAFETY: sysconf has no preconditions.
+    let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_CONF) };
+    if count > 0 {
+        count as usize
+    } else {
+        std::thread::available_parallelism().map_or(1, |n| n.get())
+    }
+}
+
 fn default_auto_remove() -> bool {
     true
 }
@@ -478,35 +963,121 @@ fn default_detach() -> bool {
     false
 }
 
+/// Default for [`BoxOptions::stop_grace_secs`].
+pub const DEFAULT_STOP_GRACE: Duration = Duration::from_secs(10);
+
+/// Default for [`BoxOptions::boot_timeout_secs`].
+pub const DEFAULT_BOOT_TIMEOUT: Duration = Duration::from_secs(60);
+
 impl Default for BoxOptions {
     fn default() -> Self {
         Self {
             cpus: None,
             memory_mib: None,
+            cpu_affinity: None,
             disk_size_gb: None,
             working_dir: None,
             env: Vec::new(),
             rootfs: RootfsSpec::default(),
+            platform: None,
+            pull_policy: PullPolicy::default(),
             volumes: Vec::new(),
+            tmpfs: Vec::new(),
             network: NetworkSpec::default(),
+            network_rate_limit: None,
+            mac_address: None,
             ports: Vec::new(),
+            dns: DnsConfig::default(),
+            extra_hosts: Vec::new(),
+            vsock_ports: Vec::new(),
+            console_ring_lines: None,
             auto_remove: default_auto_remove(),
             detach: default_detach(),
+            stop_grace_secs: None,
+            boot_timeout_secs: None,
+            idle_timeout_secs: None,
+            kernel: None,
+            kernel_cmdline_append: Vec::new(),
+            read_only_rootfs: false,
+            cap_add: Vec::new(),
+            cap_drop: Vec::new(),
+            labels: HashMap::new(),
             advanced: AdvancedBoxOptions::default(),
             entrypoint: None,
             cmd: None,
             user: None,
+            userns_mapping: None,
+            disks: Vec::new(),
+            overlay_upper_disk: None,
+            kernel_modules: Vec::new(),
+            exec_profiles: HashMap::new(),
+            ulimits: Vec::new(),
             secrets: Vec::new(),
+            pull_progress: None,
         }
     }
 }
 
 impl BoxOptions {
+    /// Jailer security options with `resource_limits` raised to fit `ulimits`.
+    pub(crate) fn jailer_security(&self) -> SecurityOptions {
+        let mut security = self.advanced.security.clone();
+        merge_ulimits(&mut security.resource_limits, &self.ulimits);
+        security
+    }
+
+    /// Grace between SIGTERM and SIGKILL for executions on stop.
+    pub fn stop_grace(&self) -> Duration {
+        self.stop_grace_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_STOP_GRACE)
+    }
+
+    /// Time allowed from VM spawn until the container is initialized.
+    pub fn boot_timeout(&self) -> Duration {
+        self.boot_timeout_secs
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_BOOT_TIMEOUT)
+    }
+
+    /// Idle period after which the box stops itself, if enabled.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout_secs.map(Duration::from_secs)
+    }
+
+    /// `cap_add` and `cap_drop` as canonical `CAP_*` names (or `ALL`).
+    pub(crate) fn capability_changes(&self) -> BoxliteResult<(Vec<String>, Vec<String>)> {
+        let normalize = |names: &[String]| {
+            names
+                .iter()
+                .map(|name| normalize_capability(name))
+                .collect::<BoxliteResult<Vec<_>>>()
+        };
+        Ok((normalize(&self.cap_add)?, normalize(&self.cap_drop)?))
+    }
+
     /// Sanitize and validate options.
     ///
     /// Validates option combinations:
     /// - `auto_remove=true` with `detach=true` is invalid (detached boxes need manual lifecycle control)
     /// - `advanced.isolate_mounts=true` is only supported on Linux
+    /// - `userns_mapping` ranges must be non-empty, non-overlapping, and fit in 32 bits
+    /// - `disks` ids must be unique and `overlay_upper_disk` must name a writable one
+    /// - `tmpfs` mounts need an absolute path, a positive size, and valid mode bits
+    /// - `ports` need non-zero ports, a valid host IP, and distinct host ports
+    /// - `extra_hosts` names must be valid hostnames, each listed once
+    /// - `mac_address` must be a locally administered unicast address
+    /// - `boot_timeout_secs` must be positive
+    /// - `idle_timeout_secs` must be positive
+    /// - `kernel` files must be readable
+    /// - `kernel_cmdline_append` entries must be single kernel parameters
+    /// - `labels` keys must be non-empty
+    /// - `kernel_modules` entries must be bare module names
+    /// - `cap_add` / `cap_drop` entries must name Linux capabilities or `ALL`
+    /// - `exec_profiles` names must be non-empty and limits positive
+    /// - `ulimits` must name known limits, once each, with `soft <= hard`
+    /// - `cpu_weight` must be 1-10000 and `cpu_quota` a valid `cpu.max` pair
+    /// - an `OciBundle` rootfs must contain `oci-layout` and `index.json`
     pub fn sanitize(&self) -> BoxliteResult<()> {
         // Validate auto_remove + detach combination
         // A detached box that auto-removes doesn't make practical sense:
@@ -527,6 +1098,154 @@ impl BoxOptions {
                 "isolate_mounts is only supported on Linux".to_string(),
             ));
         }
+
+        if let Some(mapping) = &self.userns_mapping {
+            mapping.validate()?;
+        }
+
+        if let RootfsSpec::OciBundle(path) = &self.rootfs {
+            validate_oci_bundle(path)?;
+        }
+        self.validate_disks()?;
+        self.validate_tmpfs()?;
+        if let Some(kernel) = &self.kernel {
+            kernel.validate()?;
+        }
+        validate_kernel_cmdline_append(&self.kernel_cmdline_append)?;
+        self.advanced.security.resource_limits.validate()?;
+        validate_ulimits(&self.ulimits)?;
+        if self.labels.keys().any(|key| key.trim().is_empty()) {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "label keys must be non-empty".to_string(),
+            ));
+        }
+        self.dns.validate()?;
+        validate_extra_hosts(&self.extra_hosts)?;
+
+        if let Some(limit) = &self.network_rate_limit
+            && (limit.ingress_bps == Some(0) || limit.egress_bps == Some(0))
+        {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "network_rate_limit must be positive; leave a direction unset for no limit"
+                    .to_string(),
+            ));
+        }
+        validate_ports(&self.ports)?;
+        validate_vsock_ports(&self.vsock_ports)?;
+        if let Some(mac) = &self.mac_address {
+            validate_mac_address(mac)?;
+        }
+        if self.boot_timeout_secs == Some(0) {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "boot_timeout_secs must be positive".to_string(),
+            ));
+        }
+        if self.idle_timeout_secs == Some(0) {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "idle_timeout_secs must be positive".to_string(),
+            ));
+        }
+        if self.console_ring_lines == Some(0) {
+            return Err(boxlite_shared::errors::BoxliteError::Config(
+                "console_ring_lines must be positive".to_string(),
+            ));
+        }
+
+        if let Some(cores) = &self.cpu_affinity {
+            validate_cpu_affinity(cores, host_cpu_count())?;
+        }
+
+        self.capability_changes()?;
+
+        for module in &self.kernel_modules {
+            if module.is_empty()
+                || !module
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+                    "invalid kernel module name {module:?}: expected a bare module name like \"tun\""
+                )));
+            }
+        }
+
+        for (name, limits) in &self.exec_profiles {
+            if name.is_empty() {
+                return Err(boxlite_shared::errors::BoxliteError::Config(
+                    "exec profile names must be non-empty".to_string(),
+                ));
+            }
+            limits.validate(name)?;
+        }
+
+        #[cfg(not(target_arch = "x86_64"))]
+        if self.advanced.split_irqchip.is_some() {
+            return Err(boxlite_shared::errors::BoxliteError::Unsupported(
+                "split_irqchip is only supported on x86_64".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_disks(&self) -> BoxliteResult<()> {
+        use boxlite_shared::errors::BoxliteError;
+
+        let mut ids = std::collections::HashSet::new();
+        for disk in &self.disks {
+            if disk.id.is_empty() {
+                return Err(BoxliteError::Config(format!(
+                    "disk {:?} must have a non-empty id",
+                    disk.host_path
+                )));
+            }
+            if !ids.insert(disk.id.as_str()) {
+                return Err(BoxliteError::Config(format!(
+                    "duplicate disk id {:?}",
+                    disk.id
+                )));
+            }
+        }
+
+        if let Some(id) = &self.overlay_upper_disk {
+            let disk = self.disks.iter().find(|d| &d.id == id).ok_or_else(|| {
+                BoxliteError::Config(format!(
+                    "overlay_upper_disk {id:?} does not match any declared disk"
+                ))
+            })?;
+            if disk.read_only {
+                return Err(BoxliteError::Config(format!(
+                    "overlay_upper_disk {id:?} must not be read-only"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_tmpfs(&self) -> BoxliteResult<()> {
+        use boxlite_shared::errors::BoxliteError;
+
+        for mount in &self.tmpfs {
+            if !mount.guest_path.starts_with('/') {
+                return Err(BoxliteError::Config(format!(
+                    "tmpfs guest_path {:?} must be absolute",
+                    mount.guest_path
+                )));
+            }
+            if mount.size_mib == 0 {
+                return Err(BoxliteError::Config(format!(
+                    "tmpfs {:?} must have a positive size_mib",
+                    mount.guest_path
+                )));
+            }
+            if let Some(mode) = mount.mode
+                && mode > 0o7777
+            {
+                return Err(BoxliteError::Config(format!(
+                    "tmpfs {:?} has invalid mode {mode:#o}",
+                    mount.guest_path
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -535,9 +1254,30 @@ impl BoxOptions {
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub enum RootfsSpec {
     /// Pull/resolve this registry image reference.
+    ///
+    /// A digest form (`alpine@sha256:...`) pins the image: the pulled
+    /// manifest must have that digest or the pull fails.
     Image(String),
     /// Use an already prepared rootfs at the given host path.
     RootfsPath(String),
+    /// Use the image in an OCI image layout directory (`oci-layout`,
+    /// `index.json`, `blobs/`), e.g. one written by `skopeo copy oci:DIR`.
+    ///
+    /// Layers are read from the directory in place rather than copied into
+    /// the image store; their extracted cache is keyed by the bundle's
+    /// manifest digest, so rewriting the bundle invalidates it.
+    OciBundle(PathBuf),
+}
+
+impl RootfsSpec {
+    /// Local image layout directory backing this rootfs, if any.
+    pub(crate) fn local_bundle(&self) -> Option<PathBuf> {
+        match self {
+            Self::Image(_) => None,
+            Self::RootfsPath(path) => Some(PathBuf::from(path)),
+            Self::OciBundle(path) => Some(path.clone()),
+        }
+    }
 }
 
 impl Default for RootfsSpec {
@@ -546,6 +1286,80 @@ impl Default for RootfsSpec {
     }
 }
 
+/// When an image is fetched from its registry, like Kubernetes'
+/// `imagePullPolicy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PullPolicy {
+    /// Fetch the manifest on every pull to pick up a newer digest. Layers
+    /// already in the store are reused.
+    Always,
+    /// Use the cached image if present, otherwise pull it.
+    #[default]
+    IfNotPresent,
+    /// Only use the cached image; fail with `BoxliteError::Image` if it is
+    /// not cached.
+    Never,
+}
+
+/// Image platform to select from a multi-platform image index.
+///
+/// Values use OCI names: `os` like `"linux"`, `arch` like `"amd64"` or
+/// `"arm64"`, and `variant` like `"v7"`. A `None` variant matches any.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Platform {
+    pub os: String,
+    pub arch: String,
+    #[serde(default)]
+    pub variant: Option<String>,
+}
+
+impl Platform {
+    pub fn new(os: impl Into<String>, arch: impl Into<String>) -> Self {
+        Self {
+            os: os.into(),
+            arch: arch.into(),
+            variant: None,
+        }
+    }
+
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// Linux on the host's CPU architecture — what the guest runs natively.
+    pub fn host() -> Self {
+        let arch = match std::env::consts::ARCH {
+            "aarch64" => "arm64",
+            "x86_64" => "amd64",
+            "x86" => "386",
+            other => other,
+        };
+        Self::new("linux", arch)
+    }
+
+    /// Whether an image built for `os`/`arch`/`variant` satisfies this platform.
+    pub fn matches(&self, os: &str, arch: &str, variant: Option<&str>) -> bool {
+        self.os == os
+            && self.arch == arch
+            && self
+                .variant
+                .as_deref()
+                .is_none_or(|wanted| variant == Some(wanted))
+    }
+}
+
+impl std::fmt::Display for Platform {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.os, self.arch)?;
+        if let Some(variant) = &self.variant {
+            write!(f, "/{variant}")?;
+        }
+        Ok(())
+    }
+}
+
 /// Filesystem mount specification.
 #[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct VolumeSpec {
@@ -554,6 +1368,225 @@ pub struct VolumeSpec {
     pub read_only: bool,
 }
 
+/// In-memory tmpfs mounted in the container.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct TmpfsMount {
+    /// Absolute mount path in the container.
+    pub guest_path: String,
+    /// Size limit in MiB. Pages count against the box's memory.
+    pub size_mib: u64,
+    /// Permission bits of the mount root. Defaults to `0o1777`.
+    #[serde(default)]
+    pub mode: Option<u32>,
+}
+
+/// Custom guest kernel for a box.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct KernelSpec {
+    /// Host path of the kernel image.
+    pub kernel_path: PathBuf,
+    /// Host path of an initramfs to load with the kernel.
+    #[serde(default)]
+    pub initramfs: Option<PathBuf>,
+    /// Parameters appended to the engine's default kernel command line.
+    #[serde(default)]
+    pub cmdline_extra: String,
+}
+
+impl KernelSpec {
+    /// Check that the kernel and initramfs are readable files and the extra
+    /// command line can be passed to the engine.
+    pub fn validate(&self) -> BoxliteResult<()> {
+        use boxlite_shared::errors::BoxliteError;
+
+        let files = std::iter::once(("kernel", &self.kernel_path))
+            .chain(self.initramfs.iter().map(|path| ("initramfs", path)));
+        for (what, path) in files {
+            let readable = std::fs::File::open(path).and_then(|file| file.metadata());
+            match readable {
+                Ok(meta) if meta.is_file() => {}
+                Ok(_) => {
+                    return Err(BoxliteError::Config(format!(
+                        "{what} {} is not a file",
+                        path.display()
+                    )));
+                }
+                Err(e) => {
+                    return Err(BoxliteError::Config(format!(
+                        "{what} {} is not readable: {e}",
+                        path.display()
+                    )));
+                }
+            }
+        }
+        if self.cmdline_extra.contains('\0') {
+            return Err(BoxliteError::Config(
+                "kernel cmdline_extra must not contain NUL bytes".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// `default` followed by [`cmdline_extra`](Self::cmdline_extra).
+    pub fn cmdline(&self, default: &str) -> String {
+        match self.cmdline_extra.trim() {
+            "" => default.to_string(),
+            extra => format!("{default} {extra}"),
+        }
+    }
+}
+
+/// Disk image attached to the guest as a block device.
+///
+/// The image may be raw or qcow2; the format is detected from its header.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct DiskSpec {
+    /// Identifier other options use to refer to this disk.
+    pub id: String,
+    pub host_path: String,
+    pub read_only: bool,
+    /// `host_path` is a host block device (e.g. `/dev/loop0` or an NVMe
+    /// namespace) to attach directly, bypassing the host page cache, rather
+    /// than a disk image.
+    #[serde(default)]
+    pub passthrough: bool,
+}
+
+/// Cgroup limits for execs run under a named profile in `BoxOptions::exec_profiles`.
+///
+/// Unset fields leave the corresponding resource unlimited (within the box).
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ExecResourceLimits {
+    /// CPU bandwidth in cores (cgroup `cpu.max`), e.g. `0.5` for half a core.
+    pub cpus: Option<f64>,
+    /// Memory limit in bytes (cgroup `memory.max`).
+    pub max_memory: Option<u64>,
+    /// Maximum number of tasks (cgroup `pids.max`).
+    pub max_processes: Option<u64>,
+}
+
+impl ExecResourceLimits {
+    fn validate(&self, profile: &str) -> BoxliteResult<()> {
+        use boxlite_shared::errors::BoxliteError;
+
+        if let Some(cpus) = self.cpus
+            && !(cpus.is_finite() && cpus > 0.0)
+        {
+            return Err(BoxliteError::Config(format!(
+                "exec profile {profile:?}: cpus must be a positive number, got {cpus}"
+            )));
+        }
+        if self.max_memory == Some(0) || self.max_processes == Some(0) {
+            return Err(BoxliteError::Config(format!(
+                "exec profile {profile:?}: limits must be greater than zero"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A per-box resource limit, as in `docker run --ulimit name=soft:hard`.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Ulimit {
+    /// Limit name as spelled by `ulimit`/Docker: `nofile`, `nproc`, `core`, ...
+    pub name: String,
+    pub soft: u64,
+    pub hard: u64,
+}
+
+/// Linux `RLIMIT_*` numbers by Docker ulimit name. The guest is always
+/// Linux, so these hold whatever the host platform.
+const ULIMIT_RESOURCES: &[(&str, u32)] = &[
+    ("cpu", 0),
+    ("fsize", 1),
+    ("data", 2),
+    ("stack", 3),
+    ("core", 4),
+    ("rss", 5),
+    ("nproc", 6),
+    ("nofile", 7),
+    ("memlock", 8),
+    ("as", 9),
+    ("locks", 10),
+    ("sigpending", 11),
+    ("msgqueue", 12),
+    ("nice", 13),
+    ("rtprio", 14),
+    ("rttime", 15),
+];
+
+impl Ulimit {
+    pub fn new(name: impl Into<String>, soft: u64, hard: u64) -> Self {
+        Self {
+            name: name.into(),
+            soft,
+            hard,
+        }
+    }
+
+    /// Linux `RLIMIT_*` number, or `None` for an unknown name.
+    pub(crate) fn resource(&self) -> Option<u32> {
+        ULIMIT_RESOURCES
+            .iter()
+            .find(|(name, _)| *name == self.name)
+            .map(|&(_, resource)| resource)
+    }
+
+    /// `RESOURCE=soft:hard`, the form libkrun takes for guest rlimits.
+    pub(crate) fn to_krun_rlimit(&self) -> Option<String> {
+        self.resource()
+            .map(|resource| format!("{}={}:{}", resource, self.soft, self.hard))
+    }
+}
+
+fn validate_ulimits(ulimits: &[Ulimit]) -> BoxliteResult<()> {
+    use boxlite_shared::errors::BoxliteError;
+
+    let mut seen = std::collections::HashSet::new();
+    for ulimit in ulimits {
+        if ulimit.resource().is_none() {
+            return Err(BoxliteError::Config(format!(
+                "unknown ulimit {:?}",
+                ulimit.name
+            )));
+        }
+        if ulimit.soft > ulimit.hard {
+            return Err(BoxliteError::Config(format!(
+                "ulimit {}: soft limit {} exceeds hard limit {}",
+                ulimit.name, ulimit.soft, ulimit.hard
+            )));
+        }
+        if !seen.insert(ulimit.name.as_str()) {
+            return Err(BoxliteError::Config(format!(
+                "ulimit {} is set more than once",
+                ulimit.name
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Raise the jailer's host-side `limits` to fit the box's `ulimits`.
+///
+/// Only limits the jailer tracks are touched, and only upwards: an unset
+/// (unlimited) jailer limit stays unset.
+fn merge_ulimits(limits: &mut ResourceLimits, ulimits: &[Ulimit]) {
+    for ulimit in ulimits {
+        let field = match ulimit.name.as_str() {
+            "nofile" => &mut limits.max_open_files,
+            "fsize" => &mut limits.max_file_size,
+            "nproc" => &mut limits.max_processes,
+            "as" => &mut limits.max_memory,
+            "cpu" => &mut limits.max_cpu_time,
+            _ => continue,
+        };
+        if let Some(current) = field {
+            *current = (*current).max(ulimit.hard);
+        }
+    }
+}
+
 /// Network mode for public box configuration surfaces.
 #[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -622,6 +1655,252 @@ impl From<&NetworkSpec> for NetworkConfig {
     }
 }
 
+/// Each entry must be one kernel parameter: non-empty, no whitespace (which
+/// would split it), no NUL (which ends the C string), and no quotes or shell
+/// metacharacters that the kernel or init could parse differently.
+fn validate_kernel_cmdline_append(params: &[String]) -> BoxliteResult<()> {
+    const FORBIDDEN: &[char] = &[
+        '"', '\'', '`', '\\', '$', ';', '&', '|', '<', '>', '(', ')', '{', '}', '*', '?', '!', '#',
+        '~',
+    ];
+    for param in params {
+        if param.is_empty()
+            || param
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control() || FORBIDDEN.contains(&c))
+        {
+            return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+                "invalid kernel_cmdline_append entry {param:?}: expected a single parameter like \"loglevel=7\""
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// An OCI image layout names itself with `oci-layout` and lists its
+/// manifests in `index.json`.
+fn validate_oci_bundle(path: &Path) -> BoxliteResult<()> {
+    for file in ["oci-layout", "index.json"] {
+        if !path.join(file).is_file() {
+            return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+                "OCI bundle {} has no {file}",
+                path.display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_ports(ports: &[PortSpec]) -> BoxliteResult<()> {
+    use boxlite_shared::errors::BoxliteError;
+
+    // Every mapping is forwarded by host port alone, so two specs for one
+    // host port collide even when their protocol or bind IP differ.
+    for (i, port) in ports.iter().enumerate() {
+        if port.guest_port == 0 || port.host_port == Some(0) {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "port mapping {:?}:{} uses port 0; ports must be 1-65535",
+                port.host_port, port.guest_port
+            )));
+        }
+        if let Some(ip) = &port.host_ip
+            && ip.parse::<IpAddr>().is_err()
+        {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "port mapping host IP {ip:?} is not an IP address"
+            )));
+        }
+        let host_port = port.effective_host_port();
+        if ports[..i]
+            .iter()
+            .any(|prev| prev.effective_host_port() == host_port)
+        {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "host port {host_port} is mapped more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Canonical form of a capability name: upper case with the `CAP_` prefix,
+/// or `ALL`.
+fn normalize_capability(name: &str) -> BoxliteResult<String> {
+    use boxlite_shared::constants::capabilities::{ALL, ALL_KEYWORD};
+    use boxlite_shared::errors::BoxliteError;
+
+    let upper = name.trim().to_ascii_uppercase();
+    if upper == ALL_KEYWORD {
+        return Ok(upper);
+    }
+    let canonical = if upper.starts_with("CAP_") {
+        upper
+    } else {
+        format!("CAP_{upper}")
+    };
+    if ALL.contains(&canonical.as_str()) {
+        Ok(canonical)
+    } else {
+        Err(BoxliteError::Config(format!(
+            "unknown capability {name:?}: expected a name like \"NET_ADMIN\" or \"ALL\""
+        )))
+    }
+}
+
+fn validate_vsock_ports(ports: &[u32]) -> BoxliteResult<()> {
+    use boxlite_shared::constants::network::{USER_VSOCK_PORT_MIN, VSOCK_PORT_ANY};
+    use boxlite_shared::errors::BoxliteError;
+
+    for (i, &port) in ports.iter().enumerate() {
+        if !(USER_VSOCK_PORT_MIN..VSOCK_PORT_ANY).contains(&port) {
+            return Err(BoxliteError::Config(format!(
+                "vsock port {port} is outside the user range \
+                 {USER_VSOCK_PORT_MIN}..{VSOCK_PORT_ANY} (lower ports are reserved for BoxLite)"
+            )));
+        }
+        if ports[..i].contains(&port) {
+            return Err(BoxliteError::Config(format!(
+                "vsock port {port} is listed more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_mac_address(mac: &[u8; 6]) -> BoxliteResult<()> {
+    // Bit 0 of the first octet marks multicast, bit 1 a locally
+    // administered address.
+    if mac[0] & 0x01 != 0 || mac[0] & 0x02 == 0 {
+        return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+            "mac_address {} must be a locally administered unicast address",
+            crate::net::constants::mac_to_string(mac)
+        )));
+    }
+    Ok(())
+}
+
+/// `--add-host` address that maps a name to the host, e.g.
+/// `host.docker.internal:host-gateway`.
+pub const HOST_GATEWAY: &str = "host-gateway";
+
+/// Address [`HOST_GATEWAY`] resolves to: the gvproxy address NATed to the
+/// host's loopback ([`HOST_IP`](crate::net::constants::HOST_IP)).
+pub const HOST_GATEWAY_IP: IpAddr = IpAddr::V4(std::net::Ipv4Addr::new(192, 168, 127, 254));
+
+/// Parse an extra-hosts entry: `name:ip` or `name:host-gateway`.
+///
+/// Only the first `:` separates the name, so IPv6 addresses need no
+/// brackets (`myhost:fd00::1`).
+pub fn parse_extra_host(s: &str) -> BoxliteResult<(String, IpAddr)> {
+    use boxlite_shared::errors::BoxliteError;
+
+    let (name, addr) = s
+        .split_once(':')
+        .ok_or_else(|| BoxliteError::Config(format!("invalid extra host {s:?}; use name:ip")))?;
+    let name = name.trim();
+    validate_hostname(name)?;
+    let addr = addr.trim();
+    let ip = if addr == HOST_GATEWAY {
+        HOST_GATEWAY_IP
+    } else {
+        let addr = addr
+            .strip_prefix('[')
+            .and_then(|a| a.strip_suffix(']'))
+            .unwrap_or(addr);
+        addr.parse().map_err(|_| {
+            BoxliteError::Config(format!(
+                "invalid extra host {s:?}: {addr:?} is not an IP address or {HOST_GATEWAY:?}"
+            ))
+        })?
+    };
+    Ok((name.to_string(), ip))
+}
+
+/// Check `name` is an RFC 1123 hostname.
+fn validate_hostname(name: &str) -> BoxliteResult<()> {
+    let valid_label = |label: &str| {
+        (1..=63).contains(&label.len())
+            && label
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-')
+            && !label.starts_with('-')
+            && !label.ends_with('-')
+    };
+    if name.is_empty() || name.len() > 253 || !name.split('.').all(valid_label) {
+        return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+            "extra host name {name:?} is not a valid hostname"
+        )));
+    }
+    Ok(())
+}
+
+fn validate_extra_hosts(hosts: &[(String, IpAddr)]) -> BoxliteResult<()> {
+    for (i, (name, _)) in hosts.iter().enumerate() {
+        validate_hostname(name)?;
+        if hosts[..i]
+            .iter()
+            .any(|(prev, _)| prev.eq_ignore_ascii_case(name))
+        {
+            return Err(boxlite_shared::errors::BoxliteError::Config(format!(
+                "extra host {name:?} is listed more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Maximum nameservers the guest's resolver reads from resolv.conf (MAXNS).
+const MAX_NAMESERVERS: usize = 3;
+
+/// DNS resolver configuration for a box's container.
+///
+/// Empty `nameservers` keeps gvproxy's DNS server, which also serves the
+/// box's DNS zones. With custom nameservers, lookups bypass gvproxy; the
+/// boot-time zone records (such as `host.boxlite.internal`) are pinned in
+/// `/etc/hosts` so they keep resolving.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DnsConfig {
+    /// Nameserver addresses, in resolution order (at most 3).
+    #[serde(default)]
+    pub nameservers: Vec<IpAddr>,
+    /// Search domains. Empty uses `localdomain`.
+    #[serde(default)]
+    pub search: Vec<String>,
+    /// resolv.conf `options` entries, e.g. `"ndots:2"`.
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+impl DnsConfig {
+    fn validate(&self) -> BoxliteResult<()> {
+        use boxlite_shared::errors::BoxliteError;
+
+        if self.nameservers.len() > MAX_NAMESERVERS {
+            return Err(BoxliteError::Config(format!(
+                "dns.nameservers has {} entries; at most {MAX_NAMESERVERS} are used",
+                self.nameservers.len()
+            )));
+        }
+        for ns in &self.nameservers {
+            if ns.is_unspecified() || ns.is_multicast() {
+                return Err(BoxliteError::Config(format!(
+                    "dns.nameservers: {ns} is not a usable nameserver address"
+                )));
+            }
+        }
+        for (field, values) in [("search", &self.search), ("options", &self.options)] {
+            for value in values {
+                if value.is_empty() || value.contains(char::is_whitespace) {
+                    return Err(BoxliteError::Config(format!(
+                        "dns.{field}: invalid entry {value:?}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Internal Rust network configuration for a box.
 ///
 /// Controls whether the box has network access and what hosts it can reach.
@@ -677,6 +1956,84 @@ pub struct PortSpec {
     pub host_ip: Option<String>, // Optional bind IP, defaults to 0.0.0.0/:: if None
 }
 
+impl PortSpec {
+    /// Parse a publish spec: `[[hostIp:]hostPort:]guestPort[/tcp|udp]`.
+    ///
+    /// An IPv6 `hostIp` is written in brackets (`[::1]:8080:80`).
+    pub fn parse(s: &str) -> BoxliteResult<Self> {
+        use boxlite_shared::errors::BoxliteError;
+
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(BoxliteError::InvalidArgument("empty port spec".to_string()));
+        }
+        let (rest, protocol) = match s.rsplit_once('/') {
+            Some((rest, proto)) => {
+                let protocol = if proto.eq_ignore_ascii_case("tcp") {
+                    PortProtocol::Tcp
+                } else if proto.eq_ignore_ascii_case("udp") {
+                    PortProtocol::Udp
+                } else {
+                    return Err(BoxliteError::InvalidArgument(format!(
+                        "invalid protocol {proto:?} in port spec {s:?}; use tcp or udp"
+                    )));
+                };
+                (rest.trim(), protocol)
+            }
+            None => (s, PortProtocol::Tcp),
+        };
+
+        // Split from the right so a bracketed IPv6 host IP keeps its colons.
+        let parts: Vec<&str> = rest.rsplitn(3, ':').map(str::trim).collect();
+        let (host_ip, host_port, guest_port) = match parts.as_slice() {
+            [guest] => (None, None, parse_port_number(guest, s)?),
+            [guest, host] => (
+                None,
+                Some(parse_port_number(host, s)?),
+                parse_port_number(guest, s)?,
+            ),
+            [guest, host, ip] => {
+                let ip = ip
+                    .strip_prefix('[')
+                    .and_then(|ip| ip.strip_suffix(']'))
+                    .unwrap_or(ip);
+                let addr: IpAddr = ip.parse().map_err(|_| {
+                    BoxliteError::InvalidArgument(format!(
+                        "invalid host IP {ip:?} in port spec {s:?}"
+                    ))
+                })?;
+                (
+                    Some(addr.to_string()),
+                    Some(parse_port_number(host, s)?),
+                    parse_port_number(guest, s)?,
+                )
+            }
+            _ => unreachable!("rsplitn(3) yields 1..=3 parts"),
+        };
+
+        Ok(Self {
+            host_port,
+            guest_port,
+            protocol,
+            host_ip,
+        })
+    }
+
+    /// Host port this mapping binds: `host_port`, or `guest_port` when unset.
+    pub fn effective_host_port(&self) -> u16 {
+        self.host_port.unwrap_or(self.guest_port)
+    }
+}
+
+fn parse_port_number(port: &str, spec: &str) -> BoxliteResult<u16> {
+    match port.parse::<u16>() {
+        Ok(n) if n != 0 => Ok(n),
+        _ => Err(boxlite_shared::errors::BoxliteError::InvalidArgument(
+            format!("invalid port {port:?} in port spec {spec:?}; use 1-65535"),
+        )),
+    }
+}
+
 /// A portable box archive (`.boxlite` file).
 ///
 /// Self-contained bundle: disk images + configuration manifest.
@@ -704,11 +2061,46 @@ pub struct SnapshotOptions {}
 
 /// Forward-compatible options for exporting a box archive.
 #[derive(Debug, Clone, Default)]
-pub struct ExportOptions {}
+pub struct ExportOptions {}
+
+/// Forward-compatible options for cloning a box.
+#[derive(Debug, Clone, Default)]
+pub struct CloneOptions {}
+
+/// Options for committing a box as an image.
+///
+/// Unset fields keep the box's effective values: its `BoxOptions` overrides,
+/// falling back to the base image config.
+#[derive(Debug, Clone, Default)]
+pub struct CommitOptions {
+    /// Replace the image entrypoint.
+    pub entrypoint: Option<Vec<String>>,
+    /// Replace the image command.
+    pub cmd: Option<Vec<String>>,
+    /// Environment variables merged over the image's, by key.
+    pub env: Vec<(String, String)>,
+    /// Comment recorded in the image history.
+    pub message: Option<String>,
+}
 
-/// Forward-compatible options for cloning a box.
+/// Where [`ImageHandle::import_rootfs`](crate::ImageHandle::import_rootfs)
+/// reads a root filesystem from.
+#[derive(Debug, Clone)]
+pub enum ImportSource {
+    /// A directory holding the rootfs tree.
+    Directory(PathBuf),
+    /// A tar archive of the rootfs, optionally gzip-compressed.
+    Tarball(PathBuf),
+}
+
+/// Options for pruning unreferenced blobs from the image store.
 #[derive(Debug, Clone, Default)]
-pub struct CloneOptions {}
+pub struct PruneOptions {
+    /// Report what would be removed without deleting anything.
+    pub dry_run: bool,
+    /// Only prune blobs last modified before this instant.
+    pub until: Option<chrono::DateTime<chrono::Utc>>,
+}
 
 #[cfg(test)]
 mod tests {
@@ -1374,4 +2766,772 @@ mod tests {
         assert!(opts1.resource_limits.max_processes.is_none());
         assert_eq!(opts2.resource_limits.max_processes, Some(50));
     }
+
+    #[test]
+    fn test_sanitize_split_irqchip_platform() {
+        let mut opts = BoxOptions::default();
+        opts.advanced.split_irqchip = Some(true);
+
+        let result = opts.sanitize();
+        if cfg!(target_arch = "x86_64") {
+            assert!(result.is_ok());
+        } else {
+            assert!(matches!(
+                result,
+                Err(boxlite_shared::errors::BoxliteError::Unsupported(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,6").unwrap(), vec![0, 1, 2, 3, 6]);
+        assert_eq!(parse_cpu_list(" 5, 1 - 2 ,2").unwrap(), vec![1, 2, 5]);
+        assert_eq!(parse_cpu_list("7").unwrap(), vec![7]);
+
+        for bad in ["", "a", "1,", "3-1", "-2", "1-2-3"] {
+            assert!(
+                matches!(
+                    parse_cpu_list(bad),
+                    Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+                ),
+                "{bad:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_cpu_affinity_range() {
+        use boxlite_shared::errors::BoxliteError;
+
+        assert!(validate_cpu_affinity(&[0, 3], 4).is_ok());
+        assert!(matches!(
+            validate_cpu_affinity(&[0, 4], 4),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            validate_cpu_affinity(&[], 4),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_oci_bundle_requires_layout_files() {
+        let bundle = tempfile::tempdir().unwrap();
+        let opts = BoxOptions {
+            rootfs: RootfsSpec::OciBundle(bundle.path().to_path_buf()),
+            ..Default::default()
+        };
+        let err = opts.sanitize().unwrap_err();
+        assert!(err.to_string().contains("no oci-layout"), "{err}");
+
+        std::fs::write(bundle.path().join("oci-layout"), "{}").unwrap();
+        let err = opts.sanitize().unwrap_err();
+        assert!(err.to_string().contains("no index.json"), "{err}");
+
+        std::fs::write(bundle.path().join("index.json"), "{}").unwrap();
+        assert!(opts.sanitize().is_ok());
+    }
+
+    #[test]
+    fn test_sanitize_cpu_affinity() {
+        let mut opts = BoxOptions {
+            cpu_affinity: Some(vec![0]),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.cpu_affinity = Some(vec![host_cpu_count()]);
+        assert!(matches!(
+            opts.sanitize(),
+            Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_platform_matches_and_display() {
+        let arm = Platform::new("linux", "arm");
+        assert!(arm.matches("linux", "arm", Some("v7")));
+        assert!(arm.matches("linux", "arm", None));
+        assert!(!arm.matches("linux", "arm64", None));
+        assert!(!arm.matches("windows", "arm", None));
+        assert_eq!(arm.to_string(), "linux/arm");
+
+        let armv7 = arm.with_variant("v7");
+        assert!(armv7.matches("linux", "arm", Some("v7")));
+        assert!(!armv7.matches("linux", "arm", Some("v6")));
+        assert!(!armv7.matches("linux", "arm", None));
+        assert_eq!(armv7.to_string(), "linux/arm/v7");
+    }
+
+    #[test]
+    fn test_platform_host() {
+        let host = Platform::host();
+        assert_eq!(host.os, "linux");
+        assert!(host.variant.is_none());
+        #[cfg(target_arch = "aarch64")]
+        assert_eq!(host.arch, "arm64");
+        #[cfg(target_arch = "x86_64")]
+        assert_eq!(host.arch, "amd64");
+    }
+
+    #[test]
+    fn test_sanitize_network_rate_limit() {
+        let mut opts = BoxOptions {
+            network_rate_limit: Some(crate::net::RateLimit {
+                ingress_bps: Some(1 << 20),
+                egress_bps: None,
+            }),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.network_rate_limit = Some(crate::net::RateLimit {
+            ingress_bps: None,
+            egress_bps: Some(0),
+        });
+        assert!(matches!(
+            opts.sanitize(),
+            Err(boxlite_shared::errors::BoxliteError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_cpu_weight_and_quota() {
+        let mut opts = BoxOptions::default();
+        opts.advanced.security = SecurityOptionsBuilder::new()
+            .cpu_weight(10_000)
+            .cpu_quota(50_000, 100_000)
+            .build();
+        assert!(opts.sanitize().is_ok());
+
+        for (weight, quota) in [
+            (0, (50_000, 100_000)),
+            (10_001, (50_000, 100_000)),
+            (100, (50_000, 999)),
+            (100, (50_000, 1_000_001)),
+            (100, (999, 100_000)),
+        ] {
+            opts.advanced.security = SecurityOptionsBuilder::new()
+                .cpu_weight(weight)
+                .cpu_quota(quota.0, quota.1)
+                .build();
+            assert!(
+                matches!(
+                    opts.sanitize(),
+                    Err(boxlite_shared::errors::BoxliteError::Config(_))
+                ),
+                "weight {weight}, quota {quota:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_ulimits() {
+        let mut opts = BoxOptions {
+            ulimits: vec![Ulimit::new("nofile", 1024, 2048), Ulimit::new("core", 0, 0)],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        for ulimits in [
+            vec![Ulimit::new("files", 1, 1)],
+            vec![Ulimit::new("nofile", 4096, 2048)],
+            vec![Ulimit::new("nproc", 1, 2), Ulimit::new("nproc", 3, 4)],
+        ] {
+            opts.ulimits = ulimits.clone();
+            assert!(
+                matches!(
+                    opts.sanitize(),
+                    Err(boxlite_shared::errors::BoxliteError::Config(_))
+                ),
+                "{ulimits:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ulimits_raise_jailer_limits() {
+        let opts = BoxOptions {
+            ulimits: vec![
+                Ulimit::new("nofile", 1024, 4096),
+                Ulimit::new("fsize", 1, 1),
+                Ulimit::new("as", 1 << 30, 1 << 30),
+            ],
+            ..Default::default()
+        };
+        let limits = opts.jailer_security().resource_limits;
+        let defaults = SecurityOptions::default().resource_limits;
+
+        assert_eq!(limits.max_open_files, Some(4096));
+        // Never lowered, and unset stays unlimited.
+        assert_eq!(limits.max_file_size, defaults.max_file_size);
+        assert_eq!(limits.max_memory, None);
+        assert_eq!(
+            Ulimit::new("nofile", 1024, 2048)
+                .to_krun_rlimit()
+                .as_deref(),
+            Some("7=1024:2048")
+        );
+    }
+
+    #[test]
+    fn test_sanitize_mac_address() {
+        let mut opts = BoxOptions {
+            mac_address: Some([0x02, 0x00, 0x00, 0x00, 0x00, 0x01]),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        // Universally administered, then multicast.
+        for mac in [
+            [0x00, 0x16, 0x3e, 0x00, 0x00, 0x01],
+            [0x03, 0x00, 0x00, 0x00, 0x00, 0x01],
+        ] {
+            opts.mac_address = Some(mac);
+            assert!(matches!(
+                opts.sanitize(),
+                Err(boxlite_shared::errors::BoxliteError::Config(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sanitize_console_ring_lines() {
+        let mut opts = BoxOptions {
+            console_ring_lines: Some(50),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.console_ring_lines = Some(0);
+        assert!(matches!(
+            opts.sanitize(),
+            Err(boxlite_shared::errors::BoxliteError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_extra_host() {
+        assert_eq!(
+            parse_extra_host("db.internal:10.0.0.5").unwrap(),
+            ("db.internal".to_string(), "10.0.0.5".parse().unwrap())
+        );
+        assert_eq!(
+            parse_extra_host("v6host:fd00::1").unwrap(),
+            ("v6host".to_string(), "fd00::1".parse().unwrap())
+        );
+        assert_eq!(
+            parse_extra_host("host.docker.internal:host-gateway").unwrap(),
+            ("host.docker.internal".to_string(), HOST_GATEWAY_IP)
+        );
+        assert_eq!(HOST_GATEWAY_IP.to_string(), crate::net::constants::HOST_IP);
+
+        for bad in [
+            "noaddr",
+            "db:not-an-ip",
+            ":10.0.0.5",
+            "-db:10.0.0.5",
+            "db_1:10.0.0.5",
+            "a..b:10.0.0.5",
+        ] {
+            assert!(parse_extra_host(bad).is_err(), "{bad:?}");
+        }
+    }
+
+    #[test]
+    fn test_sanitize_extra_hosts() {
+        let mut opts = BoxOptions {
+            extra_hosts: vec![
+                parse_extra_host("db:10.0.0.5").unwrap(),
+                parse_extra_host("gw:host-gateway").unwrap(),
+            ],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.extra_hosts
+            .push(("DB".to_string(), "10.0.0.6".parse().unwrap()));
+        assert!(opts.sanitize().is_err());
+
+        opts.extra_hosts = vec![("bad name".to_string(), "10.0.0.5".parse().unwrap())];
+        assert!(opts.sanitize().is_err());
+    }
+
+    #[test]
+    fn test_sanitize_dns() {
+        let mut opts = BoxOptions {
+            dns: DnsConfig {
+                nameservers: vec![
+                    "1.1.1.1".parse().unwrap(),
+                    "2606:4700::1111".parse().unwrap(),
+                ],
+                search: vec!["corp.example".into()],
+                options: vec!["ndots:2".into()],
+            },
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.dns.nameservers = vec!["0.0.0.0".parse().unwrap()];
+        assert!(opts.sanitize().is_err());
+
+        opts.dns.nameservers = vec!["10.0.0.1".parse().unwrap(); 4];
+        assert!(opts.sanitize().is_err());
+
+        opts.dns.nameservers.clear();
+        opts.dns.search = vec!["a b".into()];
+        assert!(opts.sanitize().is_err());
+
+        opts.dns.search.clear();
+        opts.dns.options = vec![String::new()];
+        assert!(opts.sanitize().is_err());
+    }
+
+    #[test]
+    fn test_port_spec_parse_forms() {
+        let spec = PortSpec::parse("8080:80").unwrap();
+        assert_eq!(spec.host_port, Some(8080));
+        assert_eq!(spec.guest_port, 80);
+        assert!(matches!(spec.protocol, PortProtocol::Tcp));
+        assert_eq!(spec.host_ip, None);
+
+        let spec = PortSpec::parse("8080:80/tcp").unwrap();
+        assert_eq!((spec.host_port, spec.guest_port), (Some(8080), 80));
+        assert!(matches!(spec.protocol, PortProtocol::Tcp));
+
+        let spec = PortSpec::parse("53:53/UDP").unwrap();
+        assert!(matches!(spec.protocol, PortProtocol::Udp));
+
+        let spec = PortSpec::parse("127.0.0.1:8080:80").unwrap();
+        assert_eq!(spec.host_ip.as_deref(), Some("127.0.0.1"));
+        assert_eq!((spec.host_port, spec.guest_port), (Some(8080), 80));
+
+        let spec = PortSpec::parse("[::1]:8080:80/tcp").unwrap();
+        assert_eq!(spec.host_ip.as_deref(), Some("::1"));
+        assert_eq!((spec.host_port, spec.guest_port), (Some(8080), 80));
+
+        let spec = PortSpec::parse("80").unwrap();
+        assert_eq!(spec.host_port, None);
+        assert_eq!(spec.effective_host_port(), 80);
+    }
+
+    #[test]
+    fn test_port_spec_parse_rejects_invalid() {
+        for bad in [
+            "",
+            "0:80",
+            "8080:0",
+            "99999:80",
+            "8080:80/sctp",
+            "localhost:8080:80",
+            "a:b",
+        ] {
+            assert!(
+                matches!(
+                    PortSpec::parse(bad),
+                    Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+                ),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_ports() {
+        let mut opts = BoxOptions {
+            ports: vec![
+                PortSpec::parse("8080:80").unwrap(),
+                PortSpec::parse("127.0.0.1:8443:443").unwrap(),
+                PortSpec::parse("53/udp").unwrap(),
+            ],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        for bad in [
+            vec!["8080:80", "8080:81"],
+            // `80` binds host port 80, same as the explicit mapping.
+            vec!["80", "80:8080"],
+            vec!["127.0.0.1:8080:80", "8080:80/udp"],
+        ] {
+            opts.ports = bad.iter().map(|s| PortSpec::parse(s).unwrap()).collect();
+            assert!(
+                matches!(
+                    opts.sanitize(),
+                    Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+                ),
+                "{bad:?}"
+            );
+        }
+
+        opts.ports = vec![PortSpec {
+            guest_port: 0,
+            ..Default::default()
+        }];
+        assert!(matches!(
+            opts.sanitize(),
+            Err(boxlite_shared::errors::BoxliteError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_capability_changes_normalize_names() {
+        let opts = BoxOptions {
+            cap_add: vec!["net_admin".into(), "CAP_SYS_PTRACE".into()],
+            cap_drop: vec!["all".into()],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+        let (add, drop) = opts.capability_changes().unwrap();
+        assert_eq!(add, vec!["CAP_NET_ADMIN", "CAP_SYS_PTRACE"]);
+        assert_eq!(drop, vec!["ALL"]);
+
+        for bad in ["NET_ADMINS", "", "CAP_"] {
+            let opts = BoxOptions {
+                cap_add: vec![bad.to_string()],
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    opts.sanitize(),
+                    Err(boxlite_shared::errors::BoxliteError::Config(_))
+                ),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_vsock_ports() {
+        let mut opts = BoxOptions {
+            vsock_ports: vec![4096, 50000],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        for bad in [
+            vec![boxlite_shared::constants::network::GUEST_AGENT_PORT],
+            vec![u32::MAX],
+            vec![5000, 5000],
+        ] {
+            opts.vsock_ports = bad.clone();
+            assert!(
+                matches!(
+                    opts.sanitize(),
+                    Err(boxlite_shared::errors::BoxliteError::Config(_))
+                ),
+                "{bad:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dns_config_serde_default() {
+        let opts: BoxOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(opts.dns, DnsConfig::default());
+
+        let dns: DnsConfig = serde_json::from_str(r#"{"nameservers": ["9.9.9.9"]}"#).unwrap();
+        assert_eq!(dns.nameservers, vec!["9.9.9.9".parse::<IpAddr>().unwrap()]);
+        assert!(serde_json::from_str::<DnsConfig>(r#"{"nameservers": ["nope"]}"#).is_err());
+    }
+
+    #[test]
+    fn test_sanitize_userns_mapping() {
+        let mut opts = BoxOptions {
+            userns_mapping: Some(UserNsMapping::root_to(100000, 65536)),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        let mut mapping = UserNsMapping::root_to(100000, 65536);
+        mapping.gid_map.clear();
+        opts.userns_mapping = Some(mapping);
+        assert!(opts.sanitize().is_err(), "empty gid_map must be rejected");
+
+        opts.userns_mapping = Some(UserNsMapping::root_to(100000, 0));
+        assert!(opts.sanitize().is_err(), "zero-size range must be rejected");
+
+        opts.userns_mapping = Some(UserNsMapping::root_to(u32::MAX, 2));
+        assert!(
+            opts.sanitize().is_err(),
+            "overflowing range must be rejected"
+        );
+
+        let mut mapping = UserNsMapping::root_to(100000, 1000);
+        mapping.uid_map.push(IdMapping {
+            container_id: 500,
+            host_id: 200000,
+            size: 10,
+        });
+        opts.userns_mapping = Some(mapping);
+        assert!(
+            opts.sanitize().is_err(),
+            "overlapping container ranges must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_overlay_upper_disk() {
+        let data = DiskSpec {
+            id: "data".into(),
+            host_path: "/tmp/data.img".into(),
+            read_only: false,
+            passthrough: false,
+        };
+        let mut opts = BoxOptions {
+            disks: vec![data.clone()],
+            overlay_upper_disk: Some("data".into()),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.overlay_upper_disk = Some("missing".into());
+        assert!(opts.sanitize().is_err(), "unknown disk id must be rejected");
+
+        opts.overlay_upper_disk = Some("data".into());
+        opts.disks[0].read_only = true;
+        assert!(
+            opts.sanitize().is_err(),
+            "read-only overlay upper disk must be rejected"
+        );
+
+        opts.overlay_upper_disk = None;
+        opts.disks = vec![data.clone(), data];
+        assert!(
+            opts.sanitize().is_err(),
+            "duplicate disk ids must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_labels() {
+        let mut opts = BoxOptions {
+            labels: HashMap::from([("team".to_string(), String::new())]),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.labels.insert(" ".to_string(), "x".to_string());
+        assert!(matches!(
+            opts.sanitize(),
+            Err(boxlite_shared::errors::BoxliteError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_sanitize_kernel_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let kernel_path = dir.path().join("vmlinux");
+        std::fs::write(&kernel_path, b"kernel").unwrap();
+
+        let mut opts = BoxOptions {
+            kernel: Some(KernelSpec {
+                kernel_path: kernel_path.clone(),
+                initramfs: None,
+                cmdline_extra: "loglevel=7".to_string(),
+            }),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        let bad = [
+            KernelSpec {
+                kernel_path: dir.path().join("missing"),
+                ..Default::default()
+            },
+            KernelSpec {
+                kernel_path: dir.path().to_path_buf(),
+                ..Default::default()
+            },
+            KernelSpec {
+                kernel_path: kernel_path.clone(),
+                initramfs: Some(dir.path().join("missing.img")),
+                ..Default::default()
+            },
+            KernelSpec {
+                kernel_path,
+                cmdline_extra: "a\0b".to_string(),
+                ..Default::default()
+            },
+        ];
+        for spec in bad {
+            opts.kernel = Some(spec.clone());
+            assert!(
+                matches!(
+                    opts.sanitize(),
+                    Err(boxlite_shared::errors::BoxliteError::Config(_))
+                ),
+                "{spec:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_kernel_cmdline_composition() {
+        let mut spec = KernelSpec::default();
+        assert_eq!(spec.cmdline("console=hvc0 rw"), "console=hvc0 rw");
+
+        spec.cmdline_extra = "  ".to_string();
+        assert_eq!(spec.cmdline("console=hvc0 rw"), "console=hvc0 rw");
+
+        spec.cmdline_extra = "loglevel=7 nokaslr".to_string();
+        assert_eq!(
+            spec.cmdline("console=hvc0 rw"),
+            "console=hvc0 rw loglevel=7 nokaslr"
+        );
+    }
+
+    #[test]
+    fn test_boot_timeout() {
+        let mut opts = BoxOptions::default();
+        assert_eq!(opts.boot_timeout(), DEFAULT_BOOT_TIMEOUT);
+        assert!(opts.sanitize().is_ok());
+
+        opts.boot_timeout_secs = Some(5);
+        assert_eq!(opts.boot_timeout(), Duration::from_secs(5));
+
+        opts.boot_timeout_secs = Some(0);
+        assert!(
+            opts.sanitize().is_err(),
+            "zero boot timeout must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_idle_timeout() {
+        let mut opts = BoxOptions::default();
+        assert_eq!(opts.idle_timeout(), None);
+
+        opts.idle_timeout_secs = Some(30);
+        assert_eq!(opts.idle_timeout(), Some(Duration::from_secs(30)));
+        assert!(opts.sanitize().is_ok());
+
+        opts.idle_timeout_secs = Some(0);
+        assert!(
+            opts.sanitize().is_err(),
+            "zero idle timeout must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_tmpfs() {
+        let scratch = TmpfsMount {
+            guest_path: "/scratch".into(),
+            size_mib: 16,
+            mode: None,
+        };
+        let mut opts = BoxOptions {
+            tmpfs: vec![scratch.clone()],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        opts.tmpfs = vec![TmpfsMount {
+            guest_path: "scratch".into(),
+            ..scratch.clone()
+        }];
+        assert!(opts.sanitize().is_err(), "relative path must be rejected");
+
+        opts.tmpfs = vec![TmpfsMount {
+            size_mib: 0,
+            ..scratch.clone()
+        }];
+        assert!(opts.sanitize().is_err(), "zero size must be rejected");
+
+        opts.tmpfs = vec![TmpfsMount {
+            mode: Some(0o10000),
+            ..scratch
+        }];
+        assert!(
+            opts.sanitize().is_err(),
+            "out-of-range mode must be rejected"
+        );
+    }
+
+    #[test]
+    fn test_sanitize_kernel_cmdline_append() {
+        let mut opts = BoxOptions {
+            kernel_cmdline_append: vec![
+                "quiet".into(),
+                "console=hvc0".into(),
+                "cgroup_no_v1=all".into(),
+                "systemd.log_level=debug".into(),
+            ],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        for bad in [
+            "",
+            "quiet loglevel=7",
+            "a\0b",
+            "init=/bin/sh;reboot",
+            "x=$(id)",
+            "x=`id`",
+            "x=\"y\"",
+            "line\nbreak",
+        ] {
+            opts.kernel_cmdline_append = vec![bad.into()];
+            assert!(
+                opts.sanitize().is_err(),
+                "cmdline entry {bad:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_kernel_modules() {
+        let mut opts = BoxOptions {
+            kernel_modules: vec!["tun".into(), "nf_tables".into(), "ip6-tables".into()],
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        for bad in ["", "../tun", "tun.ko", "tun nf_tables"] {
+            opts.kernel_modules = vec![bad.into()];
+            assert!(
+                opts.sanitize().is_err(),
+                "module name {bad:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_sanitize_exec_profiles() {
+        let light = ExecResourceLimits {
+            cpus: Some(0.25),
+            max_memory: Some(64 * 1024 * 1024),
+            max_processes: None,
+        };
+        let mut opts = BoxOptions {
+            exec_profiles: HashMap::from([("light".to_string(), light)]),
+            ..Default::default()
+        };
+        assert!(opts.sanitize().is_ok());
+
+        for bad in [
+            ExecResourceLimits {
+                cpus: Some(0.0),
+                ..Default::default()
+            },
+            ExecResourceLimits {
+                cpus: Some(f64::NAN),
+                ..Default::default()
+            },
+            ExecResourceLimits {
+                max_processes: Some(0),
+                ..Default::default()
+            },
+        ] {
+            opts.exec_profiles = HashMap::from([("bad".to_string(), bad.clone())]);
+            assert!(opts.sanitize().is_err(), "{bad:?} must be rejected");
+        }
+
+        opts.exec_profiles = HashMap::from([(String::new(), ExecResourceLimits::default())]);
+        assert!(
+            opts.sanitize().is_err(),
+            "empty profile name must be rejected"
+        );
+    }
 }
@@ -8,16 +8,19 @@ use crate::rootfs::guest::{GuestRootfs, GuestRootfsManager};
 use crate::runtime::id::{BoxID, BoxIDMint};
 use crate::runtime::layout::{BoxFilesystemLayout, FilesystemLayout, FsLayoutConfig};
 use crate::runtime::lock::RuntimeLock;
-use crate::runtime::options::{BoxArchive, BoxOptions, BoxliteOptions};
+use crate::runtime::options::{BoxArchive, BoxOptions, BoxliteOptions, PruneOptions, RootfsSpec};
 use crate::runtime::signal_handler::timeout_to_duration;
-use crate::runtime::types::{BoxInfo, BoxState, BoxStatus, ContainerID};
-use crate::vmm::VmmKind;
+use crate::runtime::types::{
+    BoxInfo, BoxState, BoxStatus, ContainerID, PrunedReport, PullPhase, PullProgress,
+    PullProgressCallback, ValidationReport,
+};
 use crate::vmm::controller::{ShimHandler, VmmHandler};
+use crate::vmm::{EngineFeatures, VmmConfig, VmmKind};
 use boxlite_shared::{BoxliteError, BoxliteResult};
 use chrono::Utc;
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock, Weak};
-use tokio::sync::OnceCell;
+use tokio::sync::{OnceCell, OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::CancellationToken;
 
 fn litebox_from_impl(box_impl: SharedBoxImpl) -> LiteBox {
@@ -137,6 +140,13 @@ pub struct RuntimeImpl {
     /// concrete backend is chosen). Boxes create their backend through this, so
     /// no call site names a concrete backend — see [`crate::net::NetworkBackendFactory`].
     pub(crate) network_factory: Arc<dyn crate::net::NetworkBackendFactory>,
+    /// Memory overcommit factor for admitting new boxes.
+    pub(crate) memory_overcommit: f64,
+    /// Boot slots when `BoxliteOptions::max_concurrent_boots` is set.
+    /// `None` means boots are not limited.
+    pub(crate) boot_permits: Option<Arc<Semaphore>>,
+    /// Names boxes created without one.
+    pub(crate) name_generator: Arc<dyn crate::runtime::names::BoxNameGenerator>,
 
     /// Runtime filesystem lock (held for lifetime). Prevent from multiple process run on same
     /// BOXLITE_HOME directory
@@ -231,16 +241,38 @@ impl RuntimeImpl {
             ))
         })?;
 
-        let image_manager =
-            ImageManager::new(layout.images_dir(), db.clone(), options.image_registries).map_err(
-                |e| {
-                    BoxliteError::Storage(format!(
-                        "Failed to initialize image manager at {}: {}",
-                        layout.images_dir().display(),
-                        e
-                    ))
-                },
-            )?;
+        let memory_overcommit =
+            crate::vmm::host_check::validate_overcommit(options.memory_overcommit.unwrap_or(1.0))?;
+        let boot_permits = match options.max_concurrent_boots {
+            Some(0) => {
+                return Err(BoxliteError::Config(
+                    "max_concurrent_boots must be at least 1".into(),
+                ));
+            }
+            Some(limit) => Some(Arc::new(Semaphore::new(limit))),
+            None => None,
+        };
+
+        let image_manager = ImageManager::with_store_options(
+            layout.images_dir(),
+            db.clone(),
+            options.image_registries,
+            options.image_store_max_bytes,
+            options.pull_retry.unwrap_or_default(),
+            options.dedup_layers,
+            options.image_blob_store,
+        )
+        .map(|manager| match options.image_config_cache_capacity {
+            Some(capacity) => manager.with_config_cache_capacity(capacity),
+            None => manager,
+        })
+        .map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to initialize image manager at {}: {}",
+                layout.images_dir().display(),
+                e
+            ))
+        })?;
 
         let base_disk_store = crate::db::BaseDiskStore::new(db.clone());
         let base_disk_mgr =
@@ -284,6 +316,11 @@ impl RuntimeImpl {
             snapshot_mgr,
             lock_manager,
             network_factory: crate::net::default_factory(),
+            memory_overcommit,
+            boot_permits,
+            name_generator: options
+                .name_generator
+                .unwrap_or_else(|| Arc::new(crate::runtime::names::RandomNameGenerator)),
             _runtime_lock: runtime_lock,
             shutdown_token: CancellationToken::new(),
         });
@@ -296,6 +333,24 @@ impl RuntimeImpl {
         Ok(inner)
     }
 
+    /// Wait for a boot slot under `max_concurrent_boots`.
+    ///
+    /// Returns `None` when boots are unlimited. The permit is held until the
+    /// box reaches ready or its boot fails, then dropped to admit the next.
+    pub(crate) async fn acquire_boot_permit(&self) -> BoxliteResult<Option<OwnedSemaphorePermit>> {
+        let Some(permits) = &self.boot_permits else {
+            return Ok(None);
+        };
+        tokio::select! {
+            permit = Arc::clone(permits).acquire_owned() => permit
+                .map(Some)
+                .map_err(|_| BoxliteError::Internal("boot semaphore closed".into())),
+            _ = self.shutdown_token.cancelled() => Err(BoxliteError::Stopped(
+                "Runtime was shut down while waiting to boot".into(),
+            )),
+        }
+    }
+
     // ========================================================================
     // PUBLIC API - BOX OPERATIONS
     // ========================================================================
@@ -358,6 +413,11 @@ impl RuntimeImpl {
             ));
         }
 
+        let name = match name {
+            Some(name) => Some(name),
+            None => Some(self.generate_box_name()?),
+        };
+
         // Check DB for existing name — use lookup_box to get full (config, state)
         // so we can build the LiteBox directly without a second lookup
         if let Some(ref name) = name
@@ -374,6 +434,13 @@ impl RuntimeImpl {
             };
         }
 
+        crate::vmm::host_check::check_resources(
+            options.cpus,
+            options.memory_mib,
+            crate::vmm::host_check::HostResources::detect(),
+            self.memory_overcommit,
+        )?;
+
         // Initialize box variables with defaults
         let (config, mut state) = self.init_box_variables(&options, name.clone());
 
@@ -496,6 +563,79 @@ impl RuntimeImpl {
         self.remove_box(&box_id, force)
     }
 
+    /// Pick a name for a box created without one.
+    ///
+    /// Proposals from the name generator that are already taken, by a
+    /// persisted box or one still only in the cache, are retried.
+    fn generate_box_name(&self) -> BoxliteResult<String> {
+        use crate::runtime::names::MAX_NAME_ATTEMPTS;
+
+        for attempt in 0..MAX_NAME_ATTEMPTS {
+            let name = self.name_generator.generate(attempt);
+            if name.is_empty() || name.contains('/') {
+                return Err(BoxliteError::InvalidArgument(format!(
+                    "name generator produced invalid box name {:?}",
+                    name
+                )));
+            }
+            let cached = self
+                .sync_state
+                .read()
+                .unwrap()
+                .active_boxes_by_name
+                .get(&name)
+                .is_some_and(|weak| weak.strong_count() > 0);
+            if !cached && self.box_manager.lookup_box_id(&name)?.is_none() {
+                return Ok(name);
+            }
+            tracing::debug!(name = %name, attempt, "Generated box name taken, retrying");
+        }
+        Err(BoxliteError::AlreadyExists(format!(
+            "no free box name after {} attempts",
+            MAX_NAME_ATTEMPTS
+        )))
+    }
+
+    /// Rename a box, found by ID or name, to `new_name`.
+    ///
+    /// The cached `BoxImpl` (if any) is re-keyed under the new name, so a
+    /// running box keeps its live state.
+    pub fn rename(&self, id_or_name: &str, new_name: &str) -> BoxliteResult<()> {
+        if new_name.is_empty() || new_name.contains('/') {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "invalid box name {:?}: must be non-empty and must not contain '/'",
+                new_name
+            )));
+        }
+        let box_id = self.resolve_id(id_or_name)?;
+
+        // Hold the cache lock across the DB update so a concurrent create
+        // can't claim the name in between.
+        let mut sync = self.sync_state.write().unwrap();
+        if let Some(weak) = sync.active_boxes_by_name.get(new_name)
+            && let Some(other) = weak.upgrade()
+            && other.id() != &box_id
+        {
+            return Err(BoxliteError::AlreadyExists(format!(
+                "box with name '{}' already exists",
+                new_name
+            )));
+        }
+        self.box_manager.rename_box(&box_id, new_name)?;
+
+        if let Some(weak) = sync.active_boxes_by_id.get(&box_id).cloned()
+            && let Some(box_impl) = weak.upgrade()
+        {
+            if let Some(old) = box_impl.set_name(Some(new_name.to_string())) {
+                sync.active_boxes_by_name.remove(&old);
+            }
+            sync.active_boxes_by_name.insert(new_name.to_string(), weak);
+        }
+
+        tracing::info!(box_id = %box_id, name = %new_name, "Renamed box");
+        Ok(())
+    }
+
     // ========================================================================
     // PUBLIC API - QUERY OPERATIONS
     // ========================================================================
@@ -982,7 +1122,7 @@ impl RuntimeImpl {
             }
 
             // Invalidate cache (removes from in-memory maps)
-            self.invalidate_box_impl(id, box_impl.config.name.as_deref());
+            self.invalidate_box_impl(id, box_impl.name().as_deref());
 
             // Delete box directory + its socket binding symlink
             box_impl.config.sockets().remove();
@@ -1115,6 +1255,149 @@ impl RuntimeImpl {
         })
     }
 
+    /// Run the side-effect-free checks of `create` and `start` on `options`.
+    ///
+    /// Sanitizes the options, checks host resources, looks for volume host
+    /// paths and resolves the rootfs. Image resolution only fetches the
+    /// manifest digest; no layers are pulled and nothing is persisted.
+    pub(crate) async fn validate(&self, options: &BoxOptions) -> ValidationReport {
+        let mut report = ValidationReport::default();
+
+        if let Err(e) = options.clone().sanitize() {
+            report.errors.push(e.to_string());
+        }
+
+        if let Err(e) = crate::vmm::host_check::check_resources(
+            options.cpus,
+            options.memory_mib,
+            crate::vmm::host_check::HostResources::detect(),
+            self.memory_overcommit,
+        ) {
+            report.errors.push(e.to_string());
+        }
+
+        // Start fails on a missing volume path, but callers often create it
+        // between create and start, so only warn here.
+        for vol in &options.volumes {
+            if !std::path::Path::new(&vol.host_path).exists() {
+                report.warnings.push(format!(
+                    "Volume host path does not exist: {}",
+                    vol.host_path
+                ));
+            }
+        }
+
+        match &options.rootfs {
+            RootfsSpec::Image(image_ref) => {
+                if let Err(e) = self.image_manager.resolve(image_ref).await {
+                    report.errors.push(e.to_string());
+                }
+            }
+            RootfsSpec::RootfsPath(path) => {
+                if !std::path::Path::new(path).exists() {
+                    report
+                        .errors
+                        .push(format!("Rootfs path does not exist: {}", path));
+                }
+            }
+            RootfsSpec::OciBundle(_) => {
+                // Checked by sanitize() above.
+            }
+        }
+
+        report
+    }
+
+    /// Features of the VM engine boxes run on.
+    pub(crate) fn engine_features(&self) -> BoxliteResult<EngineFeatures> {
+        // Boxes always run on libkrun; the engine is cheap to construct and
+        // reports features without touching the hypervisor.
+        let engine = crate::vmm::create_engine(VmmKind::Libkrun, VmmConfig::default())?;
+        Ok(engine.features())
+    }
+
+    /// Pull an image, then evict images over `image_store_max_bytes`.
+    ///
+    /// The pulled image, the guest rootfs image, and the images of active
+    /// boxes are never evicted. Eviction failures are logged, not returned:
+    /// the pull itself succeeded.
+    pub(crate) async fn pull_image(
+        &self,
+        image_ref: &str,
+    ) -> BoxliteResult<crate::images::ImageObject> {
+        self.pull_image_reporting(
+            image_ref,
+            None,
+            crate::runtime::options::PullPolicy::IfNotPresent,
+            None,
+        )
+        .await
+    }
+
+    /// [`pull_image`](Self::pull_image), reporting download progress to
+    /// `progress`. Does not report `Done`; the caller decides when the
+    /// image is ready. `platform` selects from a multi-platform image and
+    /// defaults to the host's; `policy` decides whether the registry is
+    /// contacted.
+    pub(crate) async fn pull_image_reporting(
+        &self,
+        image_ref: &str,
+        platform: Option<&crate::runtime::options::Platform>,
+        policy: crate::runtime::options::PullPolicy,
+        progress: Option<&PullProgressCallback>,
+    ) -> BoxliteResult<crate::images::ImageObject> {
+        let image = self
+            .image_manager
+            .pull_reporting(image_ref, platform, policy, progress)
+            .await?;
+
+        let mut in_use = self.images_in_use()?;
+        in_use.push(image_ref.to_string());
+        if let Err(e) = self.image_manager.enforce_size_cap(&in_use).await {
+            tracing::warn!(image_ref = %image_ref, error = %e, "Image store eviction failed");
+        }
+
+        Ok(image)
+    }
+
+    /// Delete image store blobs that nothing references.
+    ///
+    /// Beyond the blobs of cached images, keeps every disk image that backs a
+    /// box disk or base disk, and the local bundle caches of existing boxes.
+    pub(crate) async fn prune_images(&self, options: &PruneOptions) -> BoxliteResult<PrunedReport> {
+        use crate::runtime::options::RootfsSpec;
+
+        let mut local_bundles = Vec::new();
+        let mut disk_roots = vec![self.layout.bases_dir()];
+        for (config, _) in self.box_manager.all_boxes(false)? {
+            if let Some(path) = config.options.rootfs.local_bundle() {
+                local_bundles.push(path);
+            }
+            disk_roots.push(config.box_home);
+        }
+        let backing_files = backing_files_under(&disk_roots);
+
+        self.image_manager
+            .prune(options, &local_bundles, &backing_files)
+            .await
+    }
+
+    /// Image references the store must keep: the guest rootfs image plus the
+    /// rootfs image of every active box.
+    fn images_in_use(&self) -> BoxliteResult<Vec<String>> {
+        use crate::runtime::options::RootfsSpec;
+
+        let mut in_use = vec![crate::runtime::constants::images::INIT_ROOTFS.to_string()];
+        for (config, state) in self.box_manager.all_boxes(false)? {
+            if let RootfsSpec::Image(image_ref) = &config.options.rootfs
+                && (state.status.is_active() || state.status.is_transient())
+            {
+                in_use.push(image_ref.clone());
+            }
+        }
+        Ok(in_use)
+    }
+
     /// Recover boxes from persistent storage on runtime startup.
     fn recover_boxes(&self) -> BoxliteResult<()> {
         use crate::util::{PidFileReader, ProcessIdentity};
@@ -1599,6 +1882,10 @@ impl super::backend::RuntimeBackend for LocalRuntime {
         self.0.remove(id_or_name, force)
     }
 
+    async fn rename(&self, id_or_name: &str, new_name: &str) -> BoxliteResult<()> {
+        self.0.rename(id_or_name, new_name)
+    }
+
     async fn shutdown(&self, timeout: Option<i32>) -> BoxliteResult<()> {
         self.0.shutdown(timeout).await
     }
@@ -1611,6 +1898,20 @@ impl super::backend::RuntimeBackend for LocalRuntime {
         self.0.import_box(archive, name).await
     }
 
+    async fn validate(&self, options: &BoxOptions) -> BoxliteResult<ValidationReport> {
+        Ok(self.0.validate(options).await)
+    }
+
+    fn engine_features(&self) -> BoxliteResult<EngineFeatures> {
+        self.0.engine_features()
+    }
+
+    fn subscribe(
+        &self,
+    ) -> BoxliteResult<tokio::sync::broadcast::Receiver<crate::runtime::types::BoxEvent>> {
+        Ok(self.0.box_manager.subscribe())
+    }
+
     fn shutdown_sync(&self) {
         self.0.shutdown_sync();
     }
@@ -1625,7 +1926,30 @@ impl super::images::ImageBackend for LocalRuntime {
                 "Cannot pull image: runtime has been shut down".into(),
             ));
         }
-        self.0.image_manager.pull(image_ref).await
+        self.0.pull_image(image_ref).await
+    }
+
+    async fn pull_image_with_progress(
+        &self,
+        image_ref: &str,
+        progress: PullProgressCallback,
+    ) -> BoxliteResult<crate::images::ImageObject> {
+        if self.0.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Cannot pull image: runtime has been shut down".into(),
+            ));
+        }
+        let image = self
+            .0
+            .pull_image_reporting(
+                image_ref,
+                None,
+                crate::runtime::options::PullPolicy::IfNotPresent,
+                Some(&progress),
+            )
+            .await?;
+        progress.report(PullProgress::image(PullPhase::Done));
+        Ok(image)
     }
 
     async fn list_images(&self) -> BoxliteResult<Vec<crate::runtime::types::ImageInfo>> {
@@ -1636,6 +1960,44 @@ impl super::images::ImageBackend for LocalRuntime {
         }
         self.0.image_manager.list().await
     }
+
+    async fn prune_images(&self, options: PruneOptions) -> BoxliteResult<PrunedReport> {
+        if self.0.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Cannot prune images: runtime has been shut down".into(),
+            ));
+        }
+        self.0.prune_images(&options).await
+    }
+
+    async fn import_rootfs(
+        &self,
+        source: crate::runtime::options::ImportSource,
+        tag: &str,
+    ) -> BoxliteResult<crate::runtime::types::ImageInfo> {
+        if self.0.shutdown_token.is_cancelled() {
+            return Err(BoxliteError::Stopped(
+                "Cannot import image: runtime has been shut down".into(),
+            ));
+        }
+        self.0.image_manager.import_rootfs(source, tag).await
+    }
+}
+
+/// Every file that a qcow2 disk under `roots` chains to.
+fn backing_files_under(
+    roots: &[std::path::PathBuf],
+) -> std::collections::HashSet<std::path::PathBuf> {
+    roots
+        .iter()
+        .flat_map(|root| {
+            walkdir::WalkDir::new(root)
+                .into_iter()
+                .filter_map(Result::ok)
+        })
+        .filter(|entry| entry.file_type().is_file())
+        .flat_map(|entry| crate::disk::read_backing_chain(entry.path()))
+        .collect()
 }
 
 // ============================================================================
@@ -1672,6 +2034,14 @@ mod tests {
         let options = BoxliteOptions {
             home_dir: temp_dir.path().to_path_buf(),
             image_registries: vec![],
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         };
         let runtime = RuntimeImpl::new(options).expect("Failed to create runtime");
         (runtime, temp_dir)
@@ -2595,6 +2965,70 @@ mod tests {
     // Post-shutdown operation rejection
     // ====================================================================
 
+    #[tokio::test]
+    async fn test_boot_permits_limit_concurrent_boots() {
+        let temp_dir = TempDir::new_in("/tmp").expect("Failed to create temp dir");
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: temp_dir.path().to_path_buf(),
+            max_concurrent_boots: Some(1),
+            image_blob_store: None,
+            name_generator: None,
+            ..Default::default()
+        })
+        .expect("Failed to create runtime");
+
+        let first = runtime.acquire_boot_permit().await.unwrap();
+        assert!(first.is_some());
+
+        // The second boot queues until the first releases its slot.
+        let waiter = {
+            let runtime = Arc::clone(&runtime);
+            tokio::spawn(async move { runtime.acquire_boot_permit().await })
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!waiter.is_finished());
+
+        drop(first);
+        let second = waiter.await.unwrap().unwrap();
+        assert!(second.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_boot_permits_unlimited_and_shutdown() {
+        let (runtime, _dir) = create_test_runtime();
+        assert!(runtime.acquire_boot_permit().await.unwrap().is_none());
+
+        let temp_dir = TempDir::new_in("/tmp").expect("Failed to create temp dir");
+        let runtime = RuntimeImpl::new(BoxliteOptions {
+            home_dir: temp_dir.path().to_path_buf(),
+            max_concurrent_boots: Some(1),
+            image_blob_store: None,
+            name_generator: None,
+            ..Default::default()
+        })
+        .expect("Failed to create runtime");
+        let _held = runtime.acquire_boot_permit().await.unwrap();
+        runtime.shutdown_token.cancel();
+        assert!(matches!(
+            runtime.acquire_boot_permit().await,
+            Err(BoxliteError::Stopped(_))
+        ));
+    }
+
+    #[test]
+    fn test_zero_max_concurrent_boots_is_rejected() {
+        let temp_dir = TempDir::new_in("/tmp").expect("Failed to create temp dir");
+        let err = RuntimeImpl::new(BoxliteOptions {
+            home_dir: temp_dir.path().to_path_buf(),
+            max_concurrent_boots: Some(0),
+            image_blob_store: None,
+            name_generator: None,
+            ..Default::default()
+        })
+        .unwrap_err();
+        assert!(matches!(err, BoxliteError::Config(_)), "{err:?}");
+    }
+
     #[tokio::test]
     async fn test_create_after_shutdown_returns_stopped() {
         let (runtime, _dir) = create_test_runtime();
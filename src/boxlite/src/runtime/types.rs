@@ -7,8 +7,9 @@ use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
+use std::sync::Arc;
 
-pub use crate::litebox::{BoxState, BoxStatus, HealthStatus};
+pub use crate::litebox::{BoxEvent, BoxState, BoxStatus, HealthStatus};
 use crate::runtime::id::BoxID;
 
 // ============================================================================
@@ -315,6 +316,19 @@ pub struct BoxInfo {
 
     /// Health status.
     pub health_status: HealthStatus,
+
+    /// Host ports forwarded into the box, like the `PORTS` column of
+    /// `docker ps`. Includes image `EXPOSE` ports published 1:1. Empty
+    /// unless the box is running.
+    #[serde(default)]
+    pub ports: Vec<PortMapping>,
+}
+
+/// A host port forwarded to a guest port.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortMapping {
+    pub host_port: u16,
+    pub guest_port: u16,
 }
 
 impl BoxInfo {
@@ -333,15 +347,52 @@ impl BoxInfo {
             image: match &config.options.rootfs {
                 RootfsSpec::Image(r) => r.clone(),
                 RootfsSpec::RootfsPath(p) => format!("rootfs:{}", p),
+                RootfsSpec::OciBundle(p) => format!("oci:{}", p.display()),
             },
             cpus: config.options.cpus.unwrap_or(DEFAULT_CPUS),
             memory_mib: config.options.memory_mib.unwrap_or(DEFAULT_MEMORY_MIB),
-            labels: HashMap::new(),
+            labels: config.options.labels.clone(),
             health_status: state.health_status,
+            ports: if state.status.is_active() {
+                state.ports.clone()
+            } else {
+                Vec::new()
+            },
         }
     }
 }
 
+/// Selects boxes in [`BoxliteRuntime::list_info_filtered`](crate::BoxliteRuntime::list_info_filtered).
+///
+/// Every set criterion must match; the default filter matches every box.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BoxFilter {
+    /// Labels the box must carry. A `None` value matches any value for the
+    /// key, like `--filter label=key`.
+    pub labels: Vec<(String, Option<String>)>,
+    /// Statuses to accept. Empty accepts any status.
+    pub status: Vec<BoxStatus>,
+    /// Exact image reference (or `rootfs:<path>`) the box was created from.
+    pub image: Option<String>,
+}
+
+impl BoxFilter {
+    /// Whether `info` satisfies every criterion of this filter.
+    pub fn matches(&self, info: &BoxInfo) -> bool {
+        let labels = self
+            .labels
+            .iter()
+            .all(|(key, value)| match (info.labels.get(key), value) {
+                (Some(_), None) => true,
+                (Some(actual), Some(expected)) => actual == expected,
+                (None, _) => false,
+            });
+        let status = self.status.is_empty() || self.status.contains(&info.status);
+        let image = self.image.as_ref().is_none_or(|image| *image == info.image);
+        labels && status && image
+    }
+}
+
 impl PartialEq for BoxInfo {
     fn eq(&self, other: &Self) -> bool {
         self.id == other.id
@@ -353,6 +404,7 @@ impl PartialEq for BoxInfo {
             && self.memory_mib == other.memory_mib
             && self.labels == other.labels
             && self.health_status == other.health_status
+            && self.ports == other.ports
     }
 }
 
@@ -428,6 +480,162 @@ pub struct ImageInfo {
     pub size: Option<Bytes>,
 }
 
+/// A process running inside a box's container.
+///
+/// PIDs are as seen from inside the container, so the container's init
+/// is PID 1 and its `ppid` is 0.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessInfo {
+    pub pid: i32,
+    /// Parent PID; 0 when the parent is outside the container.
+    pub ppid: i32,
+    /// Command line joined by spaces, or `[comm]` when it is empty.
+    pub cmd: String,
+    /// Resident set size in KiB.
+    pub rss_kb: u64,
+    /// Single-letter scheduler state (`R`, `S`, `D`, `Z`, ...).
+    pub state: String,
+}
+
+/// An execution started in a box, as tracked by its guest agent.
+///
+/// Exited executions stay listed until the box stops, so their output and
+/// exit status can still be recovered with `LiteBox::attach`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionInfo {
+    /// Execution ID, as accepted by `LiteBox::attach`.
+    pub id: String,
+    /// PID inside the guest.
+    pub pid: u32,
+    /// Program the execution was started with.
+    pub program: String,
+    /// False once the process has exited and been reaped.
+    pub running: bool,
+    pub started_at: DateTime<Utc>,
+}
+
+/// Kind of blob held in the image store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlobKind {
+    /// Image manifest (`manifests/`).
+    Manifest,
+    /// Image config (`configs/`).
+    Config,
+    /// Compressed layer tarball (`layers/`).
+    Layer,
+    /// Unpacked layer (`extracted/`).
+    ExtractedLayer,
+    /// ext4 rootfs built from an image's layers (`disk-images/`).
+    DiskImage,
+    /// Layer cache of a local OCI bundle (`local/`).
+    LocalBundleCache,
+}
+
+/// A blob removed (or, in a dry run, that would be removed) by an image prune.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrunedBlob {
+    pub kind: BlobKind,
+    /// Content digest (e.g. "sha256:..."); the directory name for
+    /// [`BlobKind::LocalBundleCache`].
+    pub digest: String,
+    pub path: std::path::PathBuf,
+    /// Bytes on disk.
+    pub size: u64,
+}
+
+/// Result of [`BoxliteRuntime::prune_images`](crate::BoxliteRuntime::prune_images).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PrunedReport {
+    /// Blobs removed, or that would be removed in a dry run.
+    pub removed: Vec<PrunedBlob>,
+    /// Bytes reclaimed, or that would be reclaimed in a dry run.
+    pub reclaimed_bytes: u64,
+}
+
+/// Result of [`BoxliteRuntime::validate`](crate::BoxliteRuntime::validate).
+///
+/// `errors` would make `create` or `start` fail; `warnings` may still
+/// start, e.g. a volume host path that is created before the box boots.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+impl ValidationReport {
+    /// Whether no errors were found.
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+// ============================================================================
+// IMAGE PULL PROGRESS
+// ============================================================================
+
+/// Stage of an image pull reported through a [`PullProgressCallback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PullPhase {
+    /// Resolving the image manifest.
+    Manifest,
+    /// Downloading a layer blob.
+    Downloading,
+    /// Unpacking layers into the box rootfs.
+    Extracting,
+    /// The image is ready.
+    Done,
+}
+
+/// One progress update from an image pull.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PullProgress {
+    /// Layer the update is about; `None` for image-wide phases.
+    pub layer_digest: Option<String>,
+    /// Bytes of the layer downloaded so far. Never decreases for a layer,
+    /// even when its download is retried.
+    pub bytes_downloaded: u64,
+    /// Layer size from the manifest, if the registry reported one.
+    pub total_bytes: Option<u64>,
+    pub phase: PullPhase,
+}
+
+impl PullProgress {
+    /// Image-wide update (no layer) for `phase`.
+    pub(crate) fn image(phase: PullPhase) -> Self {
+        Self {
+            layer_digest: None,
+            bytes_downloaded: 0,
+            total_bytes: None,
+            phase,
+        }
+    }
+}
+
+/// Callback receiving [`PullProgress`] updates.
+///
+/// Layers download in parallel, so updates for different layers interleave.
+/// The callback runs on the pulling task and should return quickly.
+#[derive(Clone)]
+pub struct PullProgressCallback(Arc<dyn Fn(PullProgress) + Send + Sync>);
+
+impl PullProgressCallback {
+    pub fn new(callback: impl Fn(PullProgress) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+
+    pub(crate) fn report(&self, progress: PullProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for PullProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PullProgressCallback")
+    }
+}
+
 // ============================================================================
 // BOX CONFIG (Podman-style separation)
 // ============================================================================
@@ -476,6 +684,92 @@ mod tests {
         assert_eq!(info.image, "python:3.11");
         assert_eq!(info.cpus, 4);
         assert_eq!(info.memory_mib, 1024);
+        assert!(info.ports.is_empty());
+    }
+
+    #[test]
+    fn test_box_info_reports_ports_while_active() {
+        let config = BoxConfig {
+            id: BoxID::parse("01HJK4TNRPQSXYZ8WM6NCVT9R6").unwrap(),
+            name: None,
+            created_at: Utc::now(),
+            container: ContainerRuntimeConfig {
+                id: ContainerID::new(),
+            },
+            options: BoxOptions::default(),
+            engine_kind: crate::vmm::VmmKind::Libkrun,
+            box_home: PathBuf::from("/tmp/box"),
+        };
+        let mapping = PortMapping {
+            host_port: 8080,
+            guest_port: 80,
+        };
+
+        let mut state = BoxState::new();
+        state.set_ports(vec![mapping]);
+        state.set_status(BoxStatus::Running);
+        assert_eq!(BoxInfo::new(&config, &state).ports, vec![mapping]);
+
+        // Stopped boxes publish nothing, but the mappings are kept for
+        // when the box runs again.
+        state.mark_stop();
+        assert!(BoxInfo::new(&config, &state).ports.is_empty());
+        assert_eq!(state.ports, vec![mapping]);
+    }
+
+    #[test]
+    fn test_box_filter_matches() {
+        let config = BoxConfig {
+            id: BoxID::parse("01HJK4TNRPQSXYZ8WM6NCVT9R5").unwrap(),
+            name: None,
+            created_at: Utc::now(),
+            container: ContainerRuntimeConfig {
+                id: ContainerID::new(),
+            },
+            options: BoxOptions {
+                rootfs: RootfsSpec::Image("alpine:latest".to_string()),
+                labels: HashMap::from([
+                    ("team".to_string(), "infra".to_string()),
+                    ("tier".to_string(), "ci".to_string()),
+                ]),
+                ..Default::default()
+            },
+            engine_kind: crate::vmm::VmmKind::Libkrun,
+            box_home: PathBuf::from("/tmp/box"),
+        };
+        let info = BoxInfo::new(&config, &BoxState::new());
+        assert_eq!(info.labels, config.options.labels);
+
+        let label = |key: &str, value: Option<&str>| BoxFilter {
+            labels: vec![(key.to_string(), value.map(str::to_string))],
+            ..Default::default()
+        };
+        assert!(BoxFilter::default().matches(&info));
+        assert!(label("team", Some("infra")).matches(&info));
+        assert!(label("team", None).matches(&info));
+        assert!(!label("team", Some("web")).matches(&info));
+        assert!(!label("owner", None).matches(&info));
+
+        let status = |status| BoxFilter {
+            status: vec![status],
+            ..Default::default()
+        };
+        assert!(status(BoxStatus::Configured).matches(&info));
+        assert!(!status(BoxStatus::Running).matches(&info));
+
+        let image = |image: &str| BoxFilter {
+            image: Some(image.to_string()),
+            ..Default::default()
+        };
+        assert!(image("alpine:latest").matches(&info));
+        assert!(!image("python:3.11").matches(&info));
+
+        let combined = BoxFilter {
+            labels: vec![("tier".to_string(), Some("ci".to_string()))],
+            status: vec![BoxStatus::Configured, BoxStatus::Stopped],
+            image: Some("alpine:latest".to_string()),
+        };
+        assert!(combined.matches(&info));
     }
 
     #[test]
@@ -154,6 +154,7 @@ pub fn register_to_tracing(non_blocking: NonBlocking, env_filter: EnvFilter) {
                 .with_line_number(false)
                 .with_ansi(false),
         )
+        .with(crate::litebox::BoxTraceLayer::new())
         .try_init();
 }
 
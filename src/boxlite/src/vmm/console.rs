@@ -0,0 +1,234 @@
+//! Bounded capture of the guest console (kernel and init output).
+//!
+//! The shim hands the engine a pipe in place of the console file and keeps
+//! the last lines of output in a [`ConsoleRing`]. Output is mirrored to the
+//! console file, which is compacted back to the ring's contents whenever it
+//! reaches twice the ring's size, so the file stays bounded while crash
+//! reports and [`tail_lines`] keep reading it from the host.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{ErrorKind, Read, Seek, Write};
+use std::os::fd::IntoRawFd;
+use std::path::{Path, PathBuf};
+
+/// The last `capacity` lines of console output.
+#[derive(Debug)]
+pub struct ConsoleRing {
+    lines: VecDeque<String>,
+    capacity: usize,
+    /// Output after the last newline.
+    partial: Vec<u8>,
+}
+
+impl ConsoleRing {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: VecDeque::with_capacity(capacity),
+            capacity,
+            partial: Vec::new(),
+        }
+    }
+
+    /// Append raw console output, dropping the oldest lines beyond capacity.
+    /// Returns the number of lines `bytes` completed.
+    pub fn push(&mut self, bytes: &[u8]) -> usize {
+        let mut completed = 0;
+        for segment in bytes.split_inclusive(|&b| b == b'\n') {
+            self.partial.extend_from_slice(segment);
+            if self.partial.last() != Some(&b'\n') {
+                break;
+            }
+            let line = String::from_utf8_lossy(&self.partial);
+            let line = line.trim_end_matches(['\n', '\r']).to_string();
+            self.partial.clear();
+            if self.lines.len() == self.capacity {
+                self.lines.pop_front();
+            }
+            if self.capacity > 0 {
+                self.lines.push_back(line);
+            }
+            completed += 1;
+        }
+        completed
+    }
+
+    /// Completed lines, oldest first.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(String::as_str)
+    }
+
+    /// The ring as console file contents, unterminated tail included.
+    fn contents(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        for line in &self.lines {
+            out.extend_from_slice(line.as_bytes());
+            out.push(b'\n');
+        }
+        out.extend_from_slice(&self.partial);
+        out
+    }
+}
+
+/// Capture console output into a ring of `capacity` lines mirrored at
+/// `output`, keeping the tail of what `output` already holds.
+///
+/// Returns the path the engine should write console output to. The relay
+/// runs on a background thread for the life of the process.
+pub fn spawn_ring(output: &Path, capacity: usize) -> BoxliteResult<PathBuf> {
+    let io_err = |e: std::io::Error| {
+        BoxliteError::Engine(format!(
+            "failed to set up console ring for {}: {e}",
+            output.display()
+        ))
+    };
+
+    let mut ring = ConsoleRing::new(capacity);
+    match std::fs::read(output) {
+        Ok(previous) => {
+            ring.push(&previous);
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(io_err(e)),
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .truncate(true)
+        .write(true)
+        .open(output)
+        .map_err(io_err)?;
+    file.write_all(&ring.contents()).map_err(io_err)?;
+
+    let (reader, writer) = std::io::pipe().map_err(io_err)?;
+    // The write end stays open for the life of the process; the engine
+    // opens it again by path.
+    let writer_fd = writer.into_raw_fd();
+    let writer_path = if cfg!(target_os = "linux") {
+        PathBuf::from(format!("/proc/self/fd/{writer_fd}"))
+    } else {
+        PathBuf::from(format!("/dev/fd/{writer_fd}"))
+    };
+
+    std::thread::spawn(move || relay(reader, file, ring));
+    Ok(writer_path)
+}
+
+fn relay(mut reader: impl Read, mut file: File, mut ring: ConsoleRing) {
+    let mut file_lines = ring.lines.len();
+    let mut buf = [0u8; 4096];
+    loop {
+        let chunk = match reader.read(&mut buf) {
+            Ok(0) => return,
+            Ok(n) => &buf[..n],
+            Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+            Err(e) => {
+                tracing::warn!(error = %e, "console relay stopped");
+                return;
+            }
+        };
+        file_lines += ring.push(chunk);
+
+        let written = if file_lines >= ring.capacity * 2 {
+            // Compact the file back to the ring.
+            file_lines = ring.lines.len();
+            file.set_len(0)
+                .and_then(|()| file.rewind())
+                .and_then(|()| file.write_all(&ring.contents()))
+        } else {
+            file.write_all(chunk)
+        };
+        if let Err(e) = written {
+            tracing::warn!(error = %e, "failed to write console output");
+        }
+    }
+}
+
+/// The last `lines` lines of the console file at `path`. A missing file
+/// (the box never booted) has no lines.
+pub fn tail_lines(path: &Path, lines: usize) -> BoxliteResult<Vec<String>> {
+    let content = match std::fs::read(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(BoxliteError::Storage(format!(
+                "failed to read console output {}: {e}",
+                path.display()
+            )));
+        }
+    };
+    let content = String::from_utf8_lossy(&content);
+    let all: Vec<&str> = content.lines().collect();
+    let start = all.len().saturating_sub(lines);
+    Ok(all[start..].iter().map(|l| l.to_string()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_keeps_last_lines() {
+        let mut ring = ConsoleRing::new(2);
+        assert_eq!(ring.push(b"one\r\ntwo\nth"), 2);
+        assert_eq!(ring.push(b"ree\nfour"), 1);
+
+        assert_eq!(ring.lines().collect::<Vec<_>>(), ["two", "three"]);
+        assert_eq!(ring.contents(), b"two\nthree\nfour");
+    }
+
+    #[test]
+    fn test_relay_compacts_file_to_ring() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+        let file = File::create(&path).unwrap();
+
+        let output: Vec<u8> = (0..7)
+            .flat_map(|i| format!("line {i}\n").into_bytes())
+            .collect();
+        relay(&output[..], file, ConsoleRing::new(3));
+
+        // Seven lines in one chunk reach twice the ring, so only the ring
+        // is left in the file.
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "line 4\nline 5\nline 6\n"
+        );
+        assert_eq!(tail_lines(&path, 2).unwrap(), ["line 5", "line 6"]);
+        assert_eq!(tail_lines(&path, 10).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_spawn_ring_keeps_previous_tail() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("console.log");
+        std::fs::write(&path, "old 1\nold 2\nold 3\n").unwrap();
+
+        let pipe = spawn_ring(&path, 2).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old 2\nold 3\n");
+
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&pipe)
+            .unwrap()
+            .write_all(b"new\n")
+            .unwrap();
+        for _ in 0..200 {
+            if tail_lines(&path, 1).unwrap() == ["new"] {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        panic!("console output never reached the file");
+    }
+
+    #[test]
+    fn test_tail_lines_of_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            tail_lines(&dir.path().join("console.log"), 5)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Host-side control socket for a running shim.
+//!
+//! Everything else reaches the box through the guest agent, so a hung guest
+//! leaves no way to inspect or freeze it short of killing the shim. The
+//! control socket answers without the guest: it is served by a small helper
+//! process the shim spawns next to itself, which acts on the shim's PID
+//! directly. Being a separate process is what lets `resume` work while the
+//! shim (and with it every VM thread) is stopped.
+//!
+//! ## Protocol
+//!
+//! The socket lives at `sockets/control.sock` in the box directory, mode
+//! 0600. Each request is one JSON object on its own line; each response is
+//! one JSON line. A connection may carry any number of requests.
+//!
+//! ```text
+//! → {"op":"stats"}
+//! ← {"ok":true,"stats":{"pid":4242,"paused":false,"memory_bytes":...,"cpu_percent":1.5,"run_time_secs":12}}
+//! → {"op":"pause"}                      SIGSTOP the shim (vCPUs and devices)
+//! ← {"ok":true}
+//! → {"op":"resume"}                     SIGCONT the shim
+//! ← {"ok":true}
+//! → {"op":"signal","signal":"SIGTERM"}  deliver a signal to the shim
+//! ← {"ok":true}
+//! ← {"ok":false,"error":"unknown signal \"SIGFOO\""}
+//! ```
+//!
+//! `pause`/`resume` here bypass the box state machine; they are an escape
+//! hatch for a wedged guest, not a replacement for the runtime's own API.
+
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use serde::{Deserialize, Serialize};
+
+/// First argument that starts the shim binary as the control helper.
+pub const CONTROL_HELPER_ARG: &str = "control-helper";
+
+/// How often the helper checks the shim is still alive.
+const LIVENESS_POLL: Duration = Duration::from_millis(200);
+
+/// Client-side timeout for one request/response round trip.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A control socket request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum ControlRequest {
+    /// Report process-level stats for the shim.
+    Stats,
+    /// Stop the shim process (SIGSTOP).
+    Pause,
+    /// Continue a paused shim (SIGCONT).
+    Resume,
+    /// Deliver a signal, by name (`"SIGTERM"` or `"TERM"`).
+    Signal { signal: String },
+}
+
+/// A control socket response.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ControlResponse {
+    pub ok: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ShimStats>,
+}
+
+impl ControlResponse {
+    fn ok() -> Self {
+        Self {
+            ok: true,
+            ..Default::default()
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            ok: false,
+            error: Some(message.into()),
+            stats: None,
+        }
+    }
+}
+
+/// Process-level view of the shim, gathered without the guest.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShimStats {
+    pub pid: u32,
+    /// The shim is stopped (by `pause` or any SIGSTOP).
+    pub paused: bool,
+    /// Resident memory of the shim, VM memory included.
+    pub memory_bytes: u64,
+    /// CPU usage since the previous `stats` request (0 on the first).
+    pub cpu_percent: f32,
+    pub run_time_secs: u64,
+}
+
+// ============================================================================
+// Client
+// ============================================================================
+
+/// Blocking client for a shim's control socket.
+#[derive(Debug, Clone)]
+pub struct ControlClient {
+    socket_path: PathBuf,
+}
+
+impl ControlClient {
+    pub fn new(socket_path: impl Into<PathBuf>) -> Self {
+        Self {
+            socket_path: socket_path.into(),
+        }
+    }
+
+    /// Send one request and wait for its response.
+    ///
+    /// A response with `ok: false` is returned as an `Engine` error.
+    pub fn request(&self, request: &ControlRequest) -> BoxliteResult<ControlResponse> {
+        let io_err = |e: std::io::Error| {
+            BoxliteError::Engine(format!(
+                "control socket {}: {e}",
+                self.socket_path.display()
+            ))
+        };
+        let mut stream = UnixStream::connect(&self.socket_path).map_err(io_err)?;
+        stream
+            .set_read_timeout(Some(CLIENT_TIMEOUT))
+            .map_err(io_err)?;
+        stream
+            .set_write_timeout(Some(CLIENT_TIMEOUT))
+            .map_err(io_err)?;
+
+        let mut line = serde_json::to_string(request)
+            .map_err(|e| BoxliteError::Internal(format!("encode control request: {e}")))?;
+        line.push('\n');
+        stream.write_all(line.as_bytes()).map_err(io_err)?;
+
+        let mut reply = String::new();
+        BufReader::new(&stream)
+            .read_line(&mut reply)
+            .map_err(io_err)?;
+        let response: ControlResponse = serde_json::from_str(&reply).map_err(|e| {
+            BoxliteError::Engine(format!("malformed control response {reply:?}: {e}"))
+        })?;
+        if !response.ok {
+            return Err(BoxliteError::Engine(
+                response
+                    .error
+                    .unwrap_or_else(|| "control request failed".to_string()),
+            ));
+        }
+        Ok(response)
+    }
+
+    pub fn stats(&self) -> BoxliteResult<ShimStats> {
+        self.request(&ControlRequest::Stats)?
+            .stats
+            .ok_or_else(|| BoxliteError::Engine("control response carried no stats".into()))
+    }
+
+    pub fn pause(&self) -> BoxliteResult<()> {
+        self.request(&ControlRequest::Pause).map(drop)
+    }
+
+    pub fn resume(&self) -> BoxliteResult<()> {
+        self.request(&ControlRequest::Resume).map(drop)
+    }
+
+    pub fn signal(&self, signal: impl Into<String>) -> BoxliteResult<()> {
+        self.request(&ControlRequest::Signal {
+            signal: signal.into(),
+        })
+        .map(drop)
+    }
+}
+
+// ============================================================================
+// Server (control helper process)
+// ============================================================================
+
+/// Spawn the control helper for the current process (the shim).
+///
+/// Best-effort: the box runs without a control socket if the helper cannot
+/// be started.
+pub fn spawn_helper(socket_path: &Path) {
+    let exe = match std::env::current_exe() {
+        Ok(exe) => exe,
+        Err(e) => {
+            tracing::warn!("Control socket disabled: cannot locate shim binary: {e}");
+            return;
+        }
+    };
+    let spawned = std::process::Command::new(exe)
+        .arg(CONTROL_HELPER_ARG)
+        .arg(socket_path)
+        .arg(std::process::id().to_string())
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .spawn();
+    match spawned {
+        // Never waited on: the helper exits on its own once the shim is gone.
+        Ok(child) => tracing::info!(
+            helper_pid = child.id(),
+            socket = %socket_path.display(),
+            "Control socket helper started"
+        ),
+        Err(e) => tracing::warn!("Control socket disabled: failed to spawn helper: {e}"),
+    }
+}
+
+/// Entry point of the control helper: `<shim> control-helper <socket> <pid>`.
+///
+/// Serves the socket until the shim exits.
+pub fn run_helper(args: &[String]) -> BoxliteResult<()> {
+    let [socket_path, pid] = args else {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "usage: {CONTROL_HELPER_ARG} <socket> <pid>, got {args:?}"
+        )));
+    };
+    let pid: u32 = pid
+        .parse()
+        .map_err(|e| BoxliteError::InvalidArgument(format!("invalid shim pid {pid:?}: {e}")))?;
+
+    // Die with the shim on Linux; elsewhere the liveness poll catches it.
+    #[cfg(target_os = "linux")]
+    unsafe {
+        libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL);
+    }
+
+    serve(Path::new(socket_path), pid)
+}
+
+/// Bind `socket_path` (0600) and answer requests about `pid` until it exits.
+pub fn serve(socket_path: &Path, pid: u32) -> BoxliteResult<()> {
+    let listener = bind(socket_path)?;
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| BoxliteError::Engine(format!("control socket: {e}")))?;
+    let mut server = Server::new(pid);
+
+    while crate::util::is_process_alive(pid) {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                if let Err(e) = server.handle_connection(stream) {
+                    tracing::debug!("control connection ended: {e}");
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(LIVENESS_POLL);
+            }
+            Err(e) => tracing::warn!("control socket accept failed: {e}"),
+        }
+    }
+
+    let _ = std::fs::remove_file(socket_path);
+    Ok(())
+}
+
+fn bind(socket_path: &Path) -> BoxliteResult<UnixListener> {
+    // A stale socket from a previous run of this box blocks bind.
+    let _ = std::fs::remove_file(socket_path);
+    let listener = UnixListener::bind(socket_path).map_err(|e| {
+        BoxliteError::Engine(format!(
+            "failed to bind control socket {}: {e}",
+            socket_path.display()
+        ))
+    })?;
+    std::fs::set_permissions(socket_path, std::fs::Permissions::from_mode(0o600)).map_err(|e| {
+        BoxliteError::Engine(format!(
+            "failed to restrict control socket {}: {e}",
+            socket_path.display()
+        ))
+    })?;
+    Ok(listener)
+}
+
+/// Request handling state: the CPU usage delta needs the previous sample.
+struct Server {
+    pid: u32,
+    sys: sysinfo::System,
+}
+
+impl Server {
+    fn new(pid: u32) -> Self {
+        Self {
+            pid,
+            sys: sysinfo::System::new(),
+        }
+    }
+
+    fn handle_connection(&mut self, stream: UnixStream) -> std::io::Result<()> {
+        stream.set_nonblocking(false)?;
+        stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+        let mut writer = stream.try_clone()?;
+        for line in BufReader::new(stream).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match serde_json::from_str::<ControlRequest>(&line) {
+                Ok(request) => self.handle(&request),
+                Err(e) => ControlResponse::error(format!("invalid request: {e}")),
+            };
+            let mut reply = serde_json::to_string(&response).map_err(std::io::Error::other)?;
+            reply.push('\n');
+            writer.write_all(reply.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    fn handle(&mut self, request: &ControlRequest) -> ControlResponse {
+        match request {
+            ControlRequest::Stats => match self.stats() {
+                Some(stats) => ControlResponse {
+                    stats: Some(stats),
+                    ..ControlResponse::ok()
+                },
+                None => ControlResponse::error(format!("shim process {} not found", self.pid)),
+            },
+            ControlRequest::Pause => self.kill(libc::SIGSTOP),
+            ControlRequest::Resume => self.kill(libc::SIGCONT),
+            ControlRequest::Signal { signal } => match parse_signal(signal) {
+                Some(signo) => self.kill(signo),
+                None => ControlResponse::error(format!("unknown signal {signal:?}")),
+            },
+        }
+    }
+
+    fn stats(&mut self) -> Option<ShimStats> {
+        let pid = sysinfo::Pid::from_u32(self.pid);
+        self.sys.refresh_process(pid);
+        let process = self.sys.process(pid)?;
+        Some(ShimStats {
+            pid: self.pid,
+            paused: process.status() == sysinfo::ProcessStatus::Stop,
+            memory_bytes: process.memory(),
+            cpu_percent: process.cpu_usage(),
+            run_time_secs: process.run_time(),
+        })
+    }
+
+    fn kill(&self, signo: i32) -> ControlResponse {
+        if unsafe { libc::kill(self.pid as i32, signo) } == 0 {
+            ControlResponse::ok()
+        } else {
+            ControlResponse::error(format!(
+                "kill({}, {signo}) failed: {}",
+                self.pid,
+                std::io::Error::last_os_error()
+            ))
+        }
+    }
+}
+
+/// Signals a client may send, by name with or without the `SIG` prefix.
+fn parse_signal(name: &str) -> Option<i32> {
+    let name = name.strip_prefix("SIG").unwrap_or(name);
+    Some(match name {
+        "HUP" => libc::SIGHUP,
+        "INT" => libc::SIGINT,
+        "QUIT" => libc::SIGQUIT,
+        "KILL" => libc::SIGKILL,
+        "USR1" => libc::SIGUSR1,
+        "USR2" => libc::SIGUSR2,
+        "TERM" => libc::SIGTERM,
+        "STOP" => libc::SIGSTOP,
+        "CONT" => libc::SIGCONT,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_wire_format() {
+        assert_eq!(
+            serde_json::to_string(&ControlRequest::Stats).unwrap(),
+            r#"{"op":"stats"}"#
+        );
+        let signal: ControlRequest =
+            serde_json::from_str(r#"{"op":"signal","signal":"SIGTERM"}"#).unwrap();
+        assert_eq!(
+            signal,
+            ControlRequest::Signal {
+                signal: "SIGTERM".into()
+            }
+        );
+        assert!(serde_json::from_str::<ControlRequest>(r#"{"op":"reboot"}"#).is_err());
+    }
+
+    #[test]
+    fn parses_signal_names() {
+        assert_eq!(parse_signal("SIGTERM"), Some(libc::SIGTERM));
+        assert_eq!(parse_signal("USR1"), Some(libc::SIGUSR1));
+        assert_eq!(parse_signal("SIGFOO"), None);
+        assert_eq!(parse_signal("sigterm"), None);
+    }
+
+    #[test]
+    fn serves_stats_and_signals_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("control.sock");
+
+        // A child stands in for the shim so pause/resume stop something real.
+        let mut target = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let pid = target.id();
+        let server_socket = socket.clone();
+        std::thread::spawn(move || serve(&server_socket, pid));
+        while !socket.exists() {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let mode = std::fs::metadata(&socket).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        let client = ControlClient::new(&socket);
+        let stats = client.stats().unwrap();
+        assert_eq!(stats.pid, pid);
+        assert!(!stats.paused);
+
+        client.pause().unwrap();
+        // The stop is asynchronous; give the kernel a moment to apply it.
+        let mut paused = false;
+        for _ in 0..50 {
+            if client.stats().unwrap().paused {
+                paused = true;
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(paused, "target should report paused after SIGSTOP");
+        client.resume().unwrap();
+
+        let err = client.signal("SIGFOO").unwrap_err();
+        assert!(err.to_string().contains("unknown signal"), "{err}");
+
+        client.signal("SIGKILL").unwrap();
+        let status = target.wait().unwrap();
+        assert!(!status.success());
+    }
+}
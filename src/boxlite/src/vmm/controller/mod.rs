@@ -13,7 +13,10 @@
 //! - Reconnection to existing VMs (VmmHandler::attach)
 //! - Clear lifecycle boundaries (spawn vs runtime)
 //! - Caller-controlled GuestSession creation
+//!
+//! The shim also serves a guest-independent control socket; see [`control`].
 
+pub mod control;
 mod handler;
 mod shim;
 mod spawn;
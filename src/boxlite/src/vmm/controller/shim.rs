@@ -9,6 +9,7 @@ use crate::{
 };
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
+use super::control::ControlClient;
 use super::watchdog;
 use super::{
     VmmController, VmmHandler as VmmHandlerTrait, VmmMetrics,
@@ -40,6 +41,8 @@ pub struct ShimHandler {
     /// Shared System instance for CPU metrics calculation across calls.
     /// CPU usage requires comparing snapshots over time, so we must reuse the same System.
     metrics_sys: Mutex<sysinfo::System>,
+    /// The shim's control socket, when it serves one.
+    control_socket: Option<PathBuf>,
 }
 
 impl ShimHandler {
@@ -56,6 +59,7 @@ impl ShimHandler {
             process: Some(spawned.child),
             keepalive: spawned.keepalive,
             metrics_sys: Mutex::new(sysinfo::System::new()),
+            control_socket: None,
         }
     }
 
@@ -74,9 +78,22 @@ impl ShimHandler {
             process: None,
             keepalive: None,
             metrics_sys: Mutex::new(sysinfo::System::new()),
+            control_socket: None,
         }
     }
 
+    /// Record where the shim serves its control socket.
+    pub fn with_control_socket(mut self, socket_path: Option<PathBuf>) -> Self {
+        self.control_socket = socket_path;
+        self
+    }
+
+    /// Client for the shim's control socket, which answers even when the
+    /// guest agent does not. `None` if the shim was started without one.
+    pub fn control(&self) -> Option<ControlClient> {
+        self.control_socket.as_ref().map(ControlClient::new)
+    }
+
     /// Graceful shutdown of the recorded process: SIGTERM, wait, then SIGKILL.
     ///
     /// Signals only `self.pid` (the outer launcher). The full process-tree
@@ -322,21 +339,30 @@ impl VmmController for ShimController {
             engine: self.engine_type,
             // Box identification and security (from ShimController)
             box_id: self.box_id.to_string(),
-            security: self.options.advanced.security.clone(),
+            security: self.options.jailer_security(),
             // VM configuration
             cpus: config.cpus,
             memory_mib: config.memory_mib,
+            cpu_affinity: config.cpu_affinity.clone(),
+            split_irqchip: config.split_irqchip,
             fs_shares: config.fs_shares.clone(),
             block_devices: config.block_devices.clone(),
             guest_entrypoint,
             transport: config.transport.clone(),
             ready_transport: config.ready_transport.clone(),
+            vsock_ports: config.vsock_ports.clone(),
             guest_rootfs: config.guest_rootfs.clone(),
             network_backend_spec: config.network_backend_spec.clone(), // provisioning spec passed to the shim (stands up gvproxy)
             network_backend_endpoint: None, // Will be populated by shim (not serialized)
             disable_network: config.disable_network,
             home_dir: config.home_dir.clone(),
             console_output: config.console_output.clone(),
+            console_ring_lines: config.console_ring_lines,
+            kernel: config.kernel.clone(),
+            kernel_cmdline_append: config.kernel_cmdline_append.clone(),
+            ulimits: config.ulimits.clone(),
+            idle_timeout: config.idle_timeout,
+            control_socket: config.control_socket.clone(),
             exit_file: config.exit_file.clone(),
             detach: config.detach,
         };
@@ -353,6 +379,15 @@ impl VmmController for ShimController {
             tracing::warn!("Removing stale Unix socket: {}", socket_path.display());
             let _ = std::fs::remove_file(socket_path);
         }
+        for vsock in &config.vsock_ports {
+            if vsock.socket_path.exists() {
+                tracing::warn!(
+                    "Removing stale vsock socket: {}",
+                    vsock.socket_path.display()
+                );
+                let _ = std::fs::remove_file(&vsock.socket_path);
+            }
+        }
 
         // Spawn Box subprocess with piped stdio
         tracing::info!(
@@ -388,7 +423,8 @@ impl VmmController for ShimController {
         // which allows reusing that task across spawn/restart/reconnect.
 
         // Create handler from spawned shim (takes ownership of child + keepalive)
-        let handler = ShimHandler::from_spawned(spawned, self.box_id.clone());
+        let handler = ShimHandler::from_spawned(spawned, self.box_id.clone())
+            .with_control_socket(config.control_socket.clone());
 
         tracing::info!(
             box_id = %self.box_id,
@@ -6,8 +6,9 @@ use std::{
 };
 
 use crate::jailer::{Jail, JailerBuilder, process_env::shim_process_env};
+use crate::runtime::advanced_options::SecurityOptions;
 use crate::runtime::layout::BoxFilesystemLayout;
-use crate::runtime::options::BoxOptions;
+use crate::runtime::options::{BoxOptions, NetworkSpec};
 use crate::util::configure_library_env;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
@@ -77,8 +78,10 @@ impl<'a> ShimSpawner<'a> {
         let mut builder = JailerBuilder::new()
             .with_box_id(self.box_id)
             .with_layout(self.layout.clone())
-            .with_security(self.options.advanced.security.clone())
+            .with_security(self.jail_security())
             .with_volumes(self.options.volumes.clone())
+            .with_disks(self.options.disks.clone())
+            .with_cpu_affinity(self.options.cpu_affinity.clone())
             .with_detach(detach);
 
         if let Some(ref setup) = child_setup {
@@ -136,6 +139,17 @@ impl<'a> ShimSpawner<'a> {
         Ok(SpawnedShim { child, keepalive })
     }
 
+    /// Security options for the jail. A box with `NetworkSpec::Disabled`
+    /// starts no gvproxy, so the shim gets no network grant either
+    /// (seatbelt network section omitted, landlock denies TCP).
+    fn jail_security(&self) -> SecurityOptions {
+        let mut security = self.options.jailer_security();
+        if matches!(self.options.network, NetworkSpec::Disabled) {
+            security.network_enabled = false;
+        }
+        security
+    }
+
     fn configure_env(&self, cmd: &mut std::process::Command) {
         // Non-sensitive process marker used by recovery to validate shim PIDs
         // without putting the full InstanceSpec back into /proc/<pid>/cmdline.
@@ -259,6 +273,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_disabled_network_drops_jail_network_grant() {
+        use crate::runtime::layout::{BoxFilesystemLayout, FsLayoutConfig};
+        use std::path::PathBuf;
+
+        let layout = BoxFilesystemLayout::new(
+            PathBuf::from("/tmp/box"),
+            FsLayoutConfig::without_bind_mount(),
+            false,
+        );
+        let binary = Path::new("/usr/bin/boxlite-shim");
+
+        let options = BoxOptions::default();
+        let spawner = ShimSpawner::new(binary, &layout, "test-box", &options);
+        assert!(spawner.jail_security().network_enabled);
+
+        let options = BoxOptions {
+            network: NetworkSpec::Disabled,
+            ..BoxOptions::default()
+        };
+        let spawner = ShimSpawner::new(binary, &layout, "test-box", &options);
+        assert!(!spawner.jail_security().network_enabled);
+    }
+
     #[test]
     fn test_configure_env_does_not_override_temp_for_custom_profile() {
         use crate::runtime::advanced_options::{AdvancedBoxOptions, SecurityOptions};
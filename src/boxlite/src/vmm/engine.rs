@@ -1,8 +1,10 @@
 //! Vmm trait for engine-specific Box implementations.
 
-use super::InstanceSpec;
+use super::{DiskFormat, InstanceSpec};
+use crate::net::ConnectionType;
 use crate::runtime::constants::vm_defaults::{DEFAULT_CPUS, DEFAULT_MEMORY_MIB};
 use boxlite_shared::errors::BoxliteResult;
+use serde::{Deserialize, Serialize};
 
 /// Configuration options for creating VMM engines.
 ///
@@ -17,6 +19,11 @@ pub struct VmmConfig {
 
     /// Memory in MiB to allocate to Boxes (see vm_defaults::DEFAULT_MEMORY_MIB)
     pub memory_mib: Option<u32>,
+
+    /// Engine-wide split IRQ chip default (x86_64 only). A per-box
+    /// `InstanceSpec::split_irqchip` takes precedence; `None` leaves the
+    /// hypervisor default untouched.
+    pub split_irqchip: Option<bool>,
 }
 
 impl Default for VmmConfig {
@@ -24,6 +31,7 @@ impl Default for VmmConfig {
         Self {
             cpus: Some(DEFAULT_CPUS),
             memory_mib: Some(DEFAULT_MEMORY_MIB),
+            split_irqchip: None,
         }
     }
 }
@@ -45,6 +53,46 @@ impl VmmConfig {
         self.memory_mib = Some(memory_mib);
         self
     }
+
+    /// Set the split IRQ chip default (x86_64 only)
+    pub fn with_split_irqchip(mut self, enable: bool) -> Self {
+        self.split_irqchip = Some(enable);
+        self
+    }
+}
+
+/// Features supported by the linked VMM engine.
+///
+/// Produced by [`Vmm::features`]. Describes what the engine library was built
+/// with, so callers can check support before configuring a box. An engine that
+/// doesn't override `features()` reports nothing supported (the [`Default`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EngineFeatures {
+    /// Disk image formats accepted for block devices.
+    pub disk_formats: Vec<DiskFormat>,
+    /// Socket types a network backend can be attached over.
+    pub net_connections: Vec<ConnectionType>,
+    /// Guests can be started with nested virtualization enabled.
+    pub nested_virt: bool,
+    /// virtio-gpu acceleration is available.
+    pub gpu: bool,
+    /// The engine can snapshot and restore a running VM's state.
+    pub snapshot: bool,
+    /// Memory released by the guest at runtime is returned to the host
+    /// (virtio-balloon), so `LiteBox::set_memory_mib` can shrink a box.
+    pub memory_balloon: bool,
+}
+
+impl EngineFeatures {
+    /// Whether block devices in `format` can be attached.
+    pub fn supports_disk_format(&self, format: DiskFormat) -> bool {
+        self.disk_formats.contains(&format)
+    }
+
+    /// Whether a network backend can be attached over `connection`.
+    pub fn supports_net_connection(&self, connection: ConnectionType) -> bool {
+        self.net_connections.contains(&connection)
+    }
 }
 
 /// Internal trait for engine-specific VMM instance implementations.
@@ -101,4 +149,9 @@ pub trait Vmm {
     /// * `Ok(VmmInstance)` - Successfully created Box instance
     /// * `Err(...)` - Failed to create or configure the Box
     fn create(&mut self, config: InstanceSpec) -> BoxliteResult<VmmInstance>;
+
+    /// Report the features supported by the linked engine library.
+    fn features(&self) -> EngineFeatures {
+        EngineFeatures::default()
+    }
 }
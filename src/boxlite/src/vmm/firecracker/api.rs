@@ -0,0 +1,349 @@
+//! Firecracker API request bodies and a minimal client for its API socket.
+//!
+//! Firecracker serves HTTP/1.1 on a Unix socket. Configuration is a handful
+//! of `PUT`s with small JSON bodies, so the client speaks the protocol
+//! directly instead of pulling in an HTTP stack.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// `PUT /boot-source`
+#[derive(Debug, Serialize)]
+pub(super) struct BootSource {
+    pub kernel_image_path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub initrd_path: Option<PathBuf>,
+    pub boot_args: String,
+}
+
+/// `PUT /drives/{drive_id}`
+#[derive(Debug, Serialize)]
+pub(super) struct Drive {
+    pub drive_id: String,
+    pub path_on_host: PathBuf,
+    pub is_root_device: bool,
+    pub is_read_only: bool,
+}
+
+/// `PUT /machine-config`
+#[derive(Debug, Serialize)]
+pub(super) struct MachineConfig {
+    pub vcpu_count: u8,
+    pub mem_size_mib: u32,
+}
+
+/// `PUT /network-interfaces/{iface_id}`
+#[derive(Debug, Serialize)]
+pub(super) struct NetworkInterface {
+    pub iface_id: String,
+    pub host_dev_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guest_mac: Option<String>,
+}
+
+/// `PUT /vsock`
+#[derive(Debug, Serialize)]
+pub(super) struct Vsock {
+    pub guest_cid: u32,
+    pub uds_path: PathBuf,
+}
+
+/// `PUT /actions`
+#[derive(Debug, Serialize)]
+pub(super) struct InstanceAction {
+    pub action_type: &'static str,
+}
+
+impl InstanceAction {
+    pub fn start() -> Self {
+        Self {
+            action_type: "InstanceStart",
+        }
+    }
+}
+
+/// Format a MAC address the way Firecracker expects (`aa:bb:cc:dd:ee:ff`).
+pub(super) fn format_mac(mac: [u8; 6]) -> String {
+    mac.iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Client for one Firecracker process's API socket.
+pub(super) struct ApiClient {
+    socket: PathBuf,
+}
+
+impl ApiClient {
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self {
+            socket: socket.into(),
+        }
+    }
+
+    /// Wait until Firecracker accepts connections on its API socket.
+    pub fn wait_ready(&self, timeout: Duration) -> BoxliteResult<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match UnixStream::connect(&self.socket) {
+                Ok(_) => return Ok(()),
+                Err(e) if Instant::now() >= deadline => {
+                    return Err(BoxliteError::Engine(format!(
+                        "firecracker API socket {} not ready after {:?}: {e}",
+                        self.socket.display(),
+                        timeout
+                    )));
+                }
+                Err(_) => std::thread::sleep(Duration::from_millis(10)),
+            }
+        }
+    }
+
+    /// `PUT path` with `body` serialized as JSON. Fails on a non-2xx reply.
+    pub fn put<T: Serialize>(&self, path: &str, body: &T) -> BoxliteResult<()> {
+        let body = serde_json::to_string(body).map_err(|e| {
+            BoxliteError::Engine(format!("failed to encode firecracker {path} request: {e}"))
+        })?;
+        let io_err =
+            |e: std::io::Error| BoxliteError::Engine(format!("firecracker PUT {path} failed: {e}"));
+
+        let mut stream = UnixStream::connect(&self.socket).map_err(io_err)?;
+        stream
+            .write_all(format_request("PUT", path, &body).as_bytes())
+            .map_err(io_err)?;
+        let (status, reply) = read_response(&mut stream).map_err(io_err)?;
+
+        tracing::debug!(path, status, "firecracker API request");
+        if (200..300).contains(&status) {
+            Ok(())
+        } else {
+            Err(BoxliteError::Engine(format!(
+                "firecracker PUT {path} returned {status}: {reply}"
+            )))
+        }
+    }
+}
+
+fn format_request(method: &str, path: &str, body: &str) -> String {
+    format!(
+        "{method} {path} HTTP/1.1\r\n\
+         Host: localhost\r\n\
+         Accept: application/json\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         \r\n\
+         {body}",
+        body.len()
+    )
+}
+
+/// Read one HTTP response, returning its status code and body.
+fn read_response<R: Read>(reader: R) -> std::io::Result<(u16, String)> {
+    let invalid = |msg: String| std::io::Error::new(std::io::ErrorKind::InvalidData, msg);
+    let mut reader = BufReader::new(reader);
+
+    let mut status_line = String::new();
+    reader.read_line(&mut status_line)?;
+    let status = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse::<u16>().ok())
+        .ok_or_else(|| invalid(format!("malformed status line {status_line:?}")))?;
+
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = value
+                .trim()
+                .parse()
+                .map_err(|_| invalid(format!("malformed header {line:?}")))?;
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok((status, String::from_utf8_lossy(&body).into_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn to_json<T: Serialize>(body: &T) -> serde_json::Value {
+        serde_json::to_value(body).unwrap()
+    }
+
+    #[test]
+    fn test_boot_source_body() {
+        let boot = BootSource {
+            kernel_image_path: "/k/vmlinux".into(),
+            initrd_path: None,
+            boot_args: "console=ttyS0 root=/dev/vda".into(),
+        };
+        assert_eq!(
+            to_json(&boot),
+            json!({
+                "kernel_image_path": "/k/vmlinux",
+                "boot_args": "console=ttyS0 root=/dev/vda",
+            })
+        );
+
+        let boot = BootSource {
+            initrd_path: Some("/k/initrd".into()),
+            ..boot
+        };
+        assert_eq!(to_json(&boot)["initrd_path"], "/k/initrd");
+    }
+
+    #[test]
+    fn test_drive_machine_and_vsock_bodies() {
+        let drive = Drive {
+            drive_id: "vda".into(),
+            path_on_host: "/disks/root.raw".into(),
+            is_root_device: false,
+            is_read_only: true,
+        };
+        assert_eq!(
+            to_json(&drive),
+            json!({
+                "drive_id": "vda",
+                "path_on_host": "/disks/root.raw",
+                "is_root_device": false,
+                "is_read_only": true,
+            })
+        );
+
+        let machine = MachineConfig {
+            vcpu_count: 2,
+            mem_size_mib: 512,
+        };
+        assert_eq!(
+            to_json(&machine),
+            json!({"vcpu_count": 2, "mem_size_mib": 512})
+        );
+
+        let vsock = Vsock {
+            guest_cid: 3,
+            uds_path: "/run/box/fc-vsock.sock".into(),
+        };
+        assert_eq!(
+            to_json(&vsock),
+            json!({"guest_cid": 3, "uds_path": "/run/box/fc-vsock.sock"})
+        );
+
+        assert_eq!(
+            to_json(&InstanceAction::start()),
+            json!({"action_type": "InstanceStart"})
+        );
+    }
+
+    #[test]
+    fn test_network_interface_body() {
+        let nic = NetworkInterface {
+            iface_id: "eth0".into(),
+            host_dev_name: "tap0".into(),
+            guest_mac: Some(format_mac([0x5a, 0x94, 0xef, 0xe4, 0x0c, 0xee])),
+        };
+        assert_eq!(
+            to_json(&nic),
+            json!({
+                "iface_id": "eth0",
+                "host_dev_name": "tap0",
+                "guest_mac": "5a:94:ef:e4:0c:ee",
+            })
+        );
+
+        let nic = NetworkInterface {
+            guest_mac: None,
+            ..nic
+        };
+        assert!(to_json(&nic).get("guest_mac").is_none());
+    }
+
+    #[test]
+    fn test_format_request() {
+        let request = format_request("PUT", "/machine-config", r#"{"vcpu_count":1}"#);
+        assert!(request.starts_with("PUT /machine-config HTTP/1.1\r\n"));
+        assert!(request.contains("Content-Length: 16\r\n"));
+        assert!(request.ends_with("\r\n\r\n{\"vcpu_count\":1}"));
+    }
+
+    #[test]
+    fn test_read_response() {
+        let (status, body) =
+            read_response(&b"HTTP/1.1 204 \r\nServer: Firecracker API\r\n\r\n"[..]).unwrap();
+        assert_eq!((status, body.as_str()), (204, ""));
+
+        let reply = b"HTTP/1.1 400 Bad Request\r\nContent-Type: application/json\r\n\
+                      Content-Length: 22\r\n\r\n{\"fault_message\":\"x\"}\n";
+        let (status, body) = read_response(&reply[..]).unwrap();
+        assert_eq!(status, 400);
+        assert_eq!(body, "{\"fault_message\":\"x\"}\n");
+
+        assert!(read_response(&b"garbage\r\n\r\n"[..]).is_err());
+    }
+
+    #[test]
+    fn test_put_round_trip() {
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("api.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut head = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(len) = line.strip_prefix("Content-Length: ") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                head.push_str(&line);
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            (&stream)
+                .write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 4\r\n\r\nnope")
+                .unwrap();
+            (head, String::from_utf8(body).unwrap())
+        });
+
+        let client = ApiClient::new(&socket);
+        let err = client
+            .put(
+                "/machine-config",
+                &MachineConfig {
+                    vcpu_count: 1,
+                    mem_size_mib: 128,
+                },
+            )
+            .unwrap_err();
+        assert!(err.to_string().contains("400: nope"), "{err}");
+
+        let (head, body) = server.join().unwrap();
+        assert!(head.starts_with("PUT /machine-config HTTP/1.1\r\n"));
+        assert_eq!(body, r#"{"vcpu_count":1,"mem_size_mib":128}"#);
+    }
+}
@@ -0,0 +1,476 @@
+//! Firecracker - VMM implementation driving the firecracker binary.
+
+use super::api::{
+    ApiClient, BootSource, Drive, InstanceAction, MachineConfig, NetworkInterface, Vsock,
+    format_mac,
+};
+use super::vsock;
+use crate::net::NetworkBackendEndpoint;
+use crate::runtime::constants::envs::{
+    BOXLITE_FIRECRACKER_BIN as ENV_FIRECRACKER_BIN,
+    BOXLITE_FIRECRACKER_KERNEL as ENV_FIRECRACKER_KERNEL,
+    BOXLITE_FIRECRACKER_TAP as ENV_FIRECRACKER_TAP,
+};
+use crate::runtime::constants::network;
+use crate::runtime::constants::vm_defaults::{DEFAULT_CPUS, DEFAULT_MEMORY_MIB};
+use crate::vmm::{
+    DiskFormat, EngineFeatures, InstanceSpec, Vmm, VmmConfig, VmmInstance, engine::VmmInstanceImpl,
+};
+use boxlite_shared::BoxTransport;
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// Context ID the guest sees for itself on the vsock device.
+const GUEST_CID: u32 = 3;
+
+/// How long Firecracker gets to open its API socket after spawning.
+const API_READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Everything needed to launch one VM, resolved up front by `create`.
+struct FirecrackerVmmInstance {
+    binary: PathBuf,
+    api_socket: PathBuf,
+    machine: MachineConfig,
+    boot: BootSource,
+    drives: Vec<Drive>,
+    nic: Option<NetworkInterface>,
+    vsock: Vsock,
+    /// Host sockets bridged to guest vsock ports: (host socket, guest port).
+    guest_ports: Vec<(PathBuf, u32)>,
+    /// Host socket the guest's ready notification is routed to.
+    ready_socket: PathBuf,
+    console_output: Option<PathBuf>,
+    /// Shared locks on read-write disks, held until the VM exits so the
+    /// disks can't be snapshotted underneath it.
+    _disk_locks: Vec<std::fs::File>,
+}
+
+impl FirecrackerVmmInstance {
+    fn spawn(&self) -> BoxliteResult<Child> {
+        use std::os::unix::process::CommandExt;
+
+        let _ = std::fs::remove_file(&self.api_socket);
+        let mut cmd = Command::new(&self.binary);
+        cmd.arg("--api-sock").arg(&self.api_socket);
+        cmd.stdin(Stdio::null());
+        if let Some(path) = &self.console_output {
+            // Firecracker writes the guest serial console to its stdout.
+            let console = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|e| {
+                    BoxliteError::Engine(format!(
+                        "failed to open console output {}: {e}",
+                        path.display()
+                    ))
+                })?;
+            let stderr = console
+                .try_clone()
+                .map_err(|e| BoxliteError::Engine(format!("failed to open console output: {e}")))?;
+            cmd.stdout(console).stderr(stderr);
+        }
+        // SAFETY: prctl is async-signal-safe. The VM must not outlive the
+        // shim, which is what the rest of the runtime tracks.
+        unsafe {
+            cmd.pre_exec(|| {
+                if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGKILL) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+
+        cmd.spawn().map_err(|e| {
+            BoxliteError::Engine(format!(
+                "failed to spawn firecracker ({}): {e}",
+                self.binary.display()
+            ))
+        })
+    }
+
+    /// Configure the VM over the API socket and start it.
+    fn boot(&self) -> BoxliteResult<()> {
+        let api = ApiClient::new(&self.api_socket);
+        api.wait_ready(API_READY_TIMEOUT)?;
+
+        api.put("/machine-config", &self.machine)?;
+        api.put("/boot-source", &self.boot)?;
+        for drive in &self.drives {
+            api.put(&format!("/drives/{}", drive.drive_id), drive)?;
+        }
+        if let Some(nic) = &self.nic {
+            api.put(&format!("/network-interfaces/{}", nic.iface_id), nic)?;
+        }
+        api.put("/vsock", &self.vsock)?;
+
+        // Bridges go up before the guest boots so its first connections land.
+        vsock::route_to_host(
+            &self.vsock.uds_path,
+            network::GUEST_READY_PORT,
+            &self.ready_socket,
+        )?;
+        for (socket, port) in &self.guest_ports {
+            vsock::bridge_to_guest(&self.vsock.uds_path, socket, *port)?;
+        }
+
+        api.put("/actions", &InstanceAction::start())
+    }
+}
+
+impl VmmInstanceImpl for FirecrackerVmmInstance {
+    fn enter(self: Box<Self>) -> BoxliteResult<()> {
+        let mut child = self.spawn()?;
+        if let Err(e) = self.boot() {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(e);
+        }
+        tracing::info!(pid = child.id(), "firecracker VM started");
+
+        let status = child
+            .wait()
+            .map_err(|e| BoxliteError::Engine(format!("failed to wait for firecracker: {e}")))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(BoxliteError::Engine(format!(
+                "firecracker exited with {status}"
+            )))
+        }
+    }
+}
+
+/// Firecracker runs each box in a separate `firecracker` process.
+///
+/// Firecracker has no virtiofs, so only boxes with a disk-based guest rootfs
+/// and no filesystem shares can run on it, and block devices must be raw
+/// images. Networking goes through a host tap device.
+pub struct Firecracker {
+    options: VmmConfig,
+}
+
+impl Firecracker {
+    /// Create a new Firecracker engine with the specified options.
+    pub fn new(options: VmmConfig) -> BoxliteResult<Self> {
+        Ok(Self { options })
+    }
+}
+
+impl Vmm for Firecracker {
+    fn create(&mut self, config: InstanceSpec) -> BoxliteResult<VmmInstance> {
+        if let Some(share) = config.fs_shares.shares().first() {
+            return Err(BoxliteError::Unsupported(format!(
+                "firecracker has no virtiofs; filesystem share '{}' cannot be attached",
+                share.tag
+            )));
+        }
+        let root_device = match &config.guest_rootfs.strategy {
+            crate::rootfs::guest::Strategy::Disk {
+                device_path: Some(device_path),
+                ..
+            } => device_path.clone(),
+            _ => {
+                return Err(BoxliteError::Unsupported(
+                    "firecracker can only boot a disk-based guest rootfs".into(),
+                ));
+            }
+        };
+
+        let mut drives = Vec::new();
+        let mut disk_locks = Vec::new();
+        for device in config.block_devices.devices() {
+            if device.format != DiskFormat::Raw {
+                return Err(BoxliteError::Unsupported(format!(
+                    "firecracker only attaches raw disk images; {} is {}",
+                    device.disk_path.display(),
+                    device.format.as_str()
+                )));
+            }
+            if !device.disk_path.exists() {
+                return Err(BoxliteError::Engine(format!(
+                    "Disk image not found: {}",
+                    device.disk_path.display()
+                )));
+            }
+            if !device.read_only {
+                disk_locks.push(crate::disk::lock_attached_disk(&device.disk_path)?);
+            }
+            // Never mark a root device: Firecracker would move it to the
+            // front and shift the /dev/vdX names the guest expects.
+            drives.push(Drive {
+                drive_id: device.block_id.clone(),
+                path_on_host: device.disk_path.clone(),
+                is_root_device: false,
+                is_read_only: device.read_only,
+            });
+        }
+
+        let grpc_socket = unix_socket_path(&config.transport, "gRPC transport")?;
+        let ready_socket = unix_socket_path(&config.ready_transport, "ready transport")?;
+        let socket_dir = grpc_socket.parent().unwrap_or(Path::new("/"));
+        let uds_path = socket_dir.join("fc-vsock.sock");
+
+        let mut guest_ports = vec![(grpc_socket.clone(), network::GUEST_AGENT_PORT)];
+        guest_ports.extend(
+            config
+                .vsock_ports
+                .iter()
+                .map(|v| (v.socket_path.clone(), v.port)),
+        );
+
+        if let Some(spec) = &config.kernel {
+            spec.validate()?;
+        }
+        let kernel = match (&config.kernel, &config.guest_rootfs.kernel) {
+            (Some(spec), _) => spec.kernel_path.clone(),
+            (None, Some(kernel)) => kernel.clone(),
+            (None, None) => std::env::var_os(ENV_FIRECRACKER_KERNEL)
+                .map(PathBuf::from)
+                .ok_or_else(|| {
+                    BoxliteError::Config(format!(
+                        "firecracker needs an uncompressed guest kernel; set {ENV_FIRECRACKER_KERNEL}"
+                    ))
+                })?,
+        };
+        let guest_args =
+            crate::vmm::guest_args::transform_guest_args(config.guest_entrypoint.args.clone());
+        let extra = std::iter::once(
+            config
+                .kernel
+                .as_ref()
+                .map_or("", |spec| spec.cmdline_extra.as_str()),
+        )
+        .chain(config.kernel_cmdline_append.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ");
+        let boot_args = boot_args(
+            &root_device,
+            &config.guest_entrypoint.executable,
+            &extra,
+            &config.guest_entrypoint.env,
+            &guest_args,
+        )?;
+        let initrd_path = config
+            .kernel
+            .as_ref()
+            .and_then(|spec| spec.initramfs.clone())
+            .or_else(|| config.guest_rootfs.initrd.clone());
+
+        let tap = std::env::var(ENV_FIRECRACKER_TAP)
+            .ok()
+            .filter(|t| !t.is_empty());
+        let nic = if config.disable_network {
+            None
+        } else {
+            network_interface(tap, config.network_backend_endpoint.as_ref())?
+        };
+
+        let instance = FirecrackerVmmInstance {
+            binary: std::env::var_os(ENV_FIRECRACKER_BIN)
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("firecracker")),
+            api_socket: socket_dir.join("fc-api.sock"),
+            machine: MachineConfig {
+                vcpu_count: config.cpus.or(self.options.cpus).unwrap_or(DEFAULT_CPUS),
+                mem_size_mib: config
+                    .memory_mib
+                    .or(self.options.memory_mib)
+                    .unwrap_or(DEFAULT_MEMORY_MIB),
+            },
+            boot: BootSource {
+                kernel_image_path: kernel,
+                initrd_path,
+                boot_args,
+            },
+            drives,
+            nic,
+            vsock: Vsock {
+                guest_cid: GUEST_CID,
+                uds_path,
+            },
+            guest_ports,
+            ready_socket,
+            console_output: config.console_output.clone(),
+            _disk_locks: disk_locks,
+        };
+        Ok(VmmInstance::new(Box::new(instance)))
+    }
+
+    fn features(&self) -> EngineFeatures {
+        EngineFeatures {
+            disk_formats: vec![DiskFormat::Raw],
+            // Guest NICs are backed by tap devices, not backend sockets.
+            net_connections: vec![],
+            nested_virt: false,
+            gpu: false,
+            snapshot: false,
+            memory_balloon: false,
+        }
+    }
+}
+
+fn unix_socket_path(transport: &BoxTransport, what: &str) -> BoxliteResult<PathBuf> {
+    match transport {
+        BoxTransport::Unix { socket_path } => Ok(socket_path.clone()),
+        _ => Err(BoxliteError::Engine(format!(
+            "{what} must be Unix socket on host side"
+        ))),
+    }
+}
+
+/// Kernel command line that boots `root_device` straight into the guest agent.
+///
+/// `extra` kernel parameters follow the defaults. `env` is passed as
+/// `KEY=VALUE` parameters, which the kernel hands to init as its
+/// environment; `args` follow `--` as init's arguments.
+fn boot_args(
+    root_device: &str,
+    executable: &str,
+    extra: &str,
+    env: &[(String, String)],
+    args: &[String],
+) -> BoxliteResult<String> {
+    let mut params = vec![
+        "console=ttyS0".to_string(),
+        "reboot=k".to_string(),
+        "panic=1".to_string(),
+        "pci=off".to_string(),
+        format!("root={root_device}"),
+        "rootfstype=ext4".to_string(),
+        "rw".to_string(),
+        format!("init={}", kernel_param(executable)?),
+    ];
+    params.extend(extra.split_whitespace().map(str::to_string));
+    for (key, value) in env {
+        params.push(kernel_param(&format!("{key}={value}"))?);
+    }
+    if !args.is_empty() {
+        params.push("--".to_string());
+        for arg in args {
+            params.push(kernel_param(arg)?);
+        }
+    }
+    Ok(params.join(" "))
+}
+
+/// Quote `value` for the kernel command line, which has no escapes: spaces
+/// are kept inside double quotes and a double quote cannot be passed at all.
+fn kernel_param(value: &str) -> BoxliteResult<String> {
+    if value.contains('"') {
+        return Err(BoxliteError::Unsupported(format!(
+            "firecracker cannot pass {value:?} on the kernel command line"
+        )));
+    }
+    if value.is_empty() || value.contains(char::is_whitespace) {
+        Ok(format!("\"{value}\""))
+    } else {
+        Ok(value.to_string())
+    }
+}
+
+/// The guest NIC, backed by the host tap device `tap`.
+///
+/// Firecracker cannot attach to the network backend's socket, so a box with a
+/// network backend needs a tap. The guest MAC follows the backend endpoint so
+/// DHCP leases keyed on it still match.
+fn network_interface(
+    tap: Option<String>,
+    endpoint: Option<&NetworkBackendEndpoint>,
+) -> BoxliteResult<Option<NetworkInterface>> {
+    let mac = endpoint.map(|endpoint| match endpoint {
+        NetworkBackendEndpoint::UnixSocket { mac_address, .. } => format_mac(*mac_address),
+    });
+    match (tap, mac) {
+        (Some(tap), guest_mac) => Ok(Some(NetworkInterface {
+            iface_id: "eth0".to_string(),
+            host_dev_name: tap,
+            guest_mac,
+        })),
+        (None, Some(_)) => Err(BoxliteError::Unsupported(format!(
+            "firecracker networking needs a host tap device; set {ENV_FIRECRACKER_TAP} or disable networking"
+        ))),
+        (None, None) => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::ConnectionType;
+
+    #[test]
+    fn features_report_raw_disks_only() {
+        let features = Firecracker::new(VmmConfig::default()).unwrap().features();
+        assert!(features.supports_disk_format(DiskFormat::Raw));
+        assert!(!features.supports_disk_format(DiskFormat::Qcow2));
+        assert!(!features.supports_net_connection(ConnectionType::UnixStream));
+    }
+
+    #[test]
+    fn boot_args_pass_env_and_quoted_args_to_init() {
+        let args = boot_args(
+            "/dev/vdc",
+            "/boxlite/bin/boxlite-guest",
+            "",
+            &[("RUST_LOG".into(), "info".into())],
+            &[
+                "--listen".into(),
+                "vsock://2695".into(),
+                "-c".into(),
+                "echo hi".into(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            args,
+            "console=ttyS0 reboot=k panic=1 pci=off root=/dev/vdc rootfstype=ext4 rw \
+             init=/boxlite/bin/boxlite-guest RUST_LOG=info -- --listen vsock://2695 -c \"echo hi\""
+        );
+    }
+
+    #[test]
+    fn boot_args_append_extra_kernel_params_before_init_args() {
+        let args = boot_args(
+            "/dev/vda",
+            "/init",
+            " loglevel=7  nokaslr ",
+            &[],
+            &["--listen".into()],
+        )
+        .unwrap();
+        assert!(
+            args.ends_with("init=/init loglevel=7 nokaslr -- --listen"),
+            "{args}"
+        );
+    }
+
+    #[test]
+    fn boot_args_reject_double_quotes() {
+        let err = boot_args("/dev/vda", "/init", "", &[], &["say \"hi\"".into()]).unwrap_err();
+        assert!(matches!(err, BoxliteError::Unsupported(_)));
+    }
+
+    #[test]
+    fn network_interface_uses_tap_and_backend_mac() {
+        let endpoint = NetworkBackendEndpoint::UnixSocket {
+            path: "/run/net.sock".into(),
+            connection_type: ConnectionType::UnixStream,
+            mac_address: [0x5a, 0x94, 0xef, 0xe4, 0x0c, 0xee],
+        };
+
+        let nic = network_interface(Some("tap-box".into()), Some(&endpoint))
+            .unwrap()
+            .unwrap();
+        assert_eq!(nic.host_dev_name, "tap-box");
+        assert_eq!(nic.guest_mac.as_deref(), Some("5a:94:ef:e4:0c:ee"));
+
+        assert!(matches!(
+            network_interface(None, Some(&endpoint)),
+            Err(BoxliteError::Unsupported(_))
+        ));
+        assert!(network_interface(None, None).unwrap().is_none());
+    }
+}
@@ -0,0 +1,27 @@
+//! Firecracker engine factory implementation.
+
+use crate::vmm::{
+    VmmConfig, VmmKind, factory::VmmFactory, firecracker::Firecracker,
+    registry::EngineFactoryRegistration,
+};
+use boxlite_shared::errors::BoxliteResult;
+
+pub struct FirecrackerFactory;
+
+impl VmmFactory for FirecrackerFactory {
+    type Engine = Firecracker;
+
+    fn create(options: VmmConfig) -> BoxliteResult<Self::Engine> {
+        Firecracker::new(options)
+    }
+}
+
+// Auto-register this factory with the global registry at compile time
+inventory::submit! {
+    EngineFactoryRegistration {
+        kind: VmmKind::Firecracker,
+        factory: |options| {
+            Ok(Box::new(FirecrackerFactory::create(options)?))
+        }
+    }
+}
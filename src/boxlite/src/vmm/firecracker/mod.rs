@@ -0,0 +1,11 @@
+//! Firecracker-based engine implementation.
+//!
+//! Drives an external `firecracker` process through its API socket.
+
+mod api;
+pub mod engine;
+pub mod factory;
+mod vsock;
+
+pub use engine::Firecracker;
+pub use factory::FirecrackerFactory;
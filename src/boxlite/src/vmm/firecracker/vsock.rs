@@ -0,0 +1,157 @@
+//! Host side of Firecracker's vsock device.
+//!
+//! Firecracker exposes the guest's vsock as a single Unix socket. A host
+//! process connects to it and writes `CONNECT <port>\n`; after the `OK` reply
+//! the stream reaches the guest listener on that port. Guest connections to
+//! host port N are made to `<uds_path>_N`, which the host must listen on.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::io::{BufRead, BufReader, Write};
+use std::net::Shutdown;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+/// Listen on `listen_path` and splice each connection to guest `port`.
+///
+/// Runs on background threads for the life of the process.
+pub(super) fn bridge_to_guest(uds_path: &Path, listen_path: &Path, port: u32) -> BoxliteResult<()> {
+    let _ = std::fs::remove_file(listen_path);
+    let listener = UnixListener::bind(listen_path).map_err(|e| {
+        BoxliteError::Engine(format!(
+            "failed to bind vsock bridge socket {}: {e}",
+            listen_path.display()
+        ))
+    })?;
+
+    let uds_path = uds_path.to_path_buf();
+    std::thread::spawn(move || {
+        for host in listener.incoming() {
+            let host = match host {
+                Ok(host) => host,
+                Err(e) => {
+                    tracing::warn!(port, error = %e, "vsock bridge accept failed");
+                    continue;
+                }
+            };
+            match connect_guest(&uds_path, port) {
+                Ok(guest) => splice(host, guest),
+                Err(e) => tracing::debug!(port, error = %e, "guest vsock port not reachable"),
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Route guest connections to host `port` to the socket at `target`.
+pub(super) fn route_to_host(uds_path: &Path, port: u32, target: &Path) -> BoxliteResult<()> {
+    let link = host_port_path(uds_path, port);
+    let _ = std::fs::remove_file(&link);
+    std::os::unix::fs::symlink(target, &link).map_err(|e| {
+        BoxliteError::Engine(format!(
+            "failed to link vsock port {port} to {}: {e}",
+            target.display()
+        ))
+    })
+}
+
+/// Socket Firecracker connects to for guest connections to host `port`.
+fn host_port_path(uds_path: &Path, port: u32) -> PathBuf {
+    let mut path = uds_path.as_os_str().to_owned();
+    path.push(format!("_{port}"));
+    PathBuf::from(path)
+}
+
+/// Open a stream to guest `port` through the vsock device.
+fn connect_guest(uds_path: &Path, port: u32) -> std::io::Result<UnixStream> {
+    let mut stream = UnixStream::connect(uds_path)?;
+    stream.write_all(format!("CONNECT {port}\n").as_bytes())?;
+
+    // Read the reply a byte at a time so nothing after it is buffered away.
+    let mut reply = Vec::new();
+    let mut reader = BufReader::with_capacity(1, &stream);
+    reader.read_until(b'\n', &mut reply)?;
+    if !reply.starts_with(b"OK ") {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::ConnectionRefused,
+            format!(
+                "vsock CONNECT {port} rejected: {:?}",
+                String::from_utf8_lossy(&reply).trim_end()
+            ),
+        ));
+    }
+    Ok(stream)
+}
+
+/// Copy bytes both ways between `a` and `b` until each side closes.
+fn splice(a: UnixStream, b: UnixStream) {
+    fn pump(mut from: UnixStream, mut to: UnixStream) {
+        let _ = std::io::copy(&mut from, &mut to);
+        let _ = to.shutdown(Shutdown::Write);
+    }
+
+    match (a.try_clone(), b.try_clone()) {
+        (Ok(a2), Ok(b2)) => {
+            std::thread::spawn(move || pump(a, b2));
+            std::thread::spawn(move || pump(b, a2));
+        }
+        (Err(e), _) | (_, Err(e)) => tracing::warn!(error = %e, "vsock bridge clone failed"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn test_host_port_path() {
+        assert_eq!(
+            host_port_path(Path::new("/run/box/fc-vsock.sock"), 2696),
+            PathBuf::from("/run/box/fc-vsock.sock_2696")
+        );
+    }
+
+    #[test]
+    fn test_bridge_handshakes_and_splices() {
+        let dir = tempfile::tempdir().unwrap();
+        let uds_path = dir.path().join("fc-vsock.sock");
+        let listen_path = dir.path().join("grpc.sock");
+
+        // Stand-in for Firecracker: accept CONNECT, then echo upper-cased.
+        let device = UnixListener::bind(&uds_path).unwrap();
+        let fake = std::thread::spawn(move || {
+            let (mut stream, _) = device.accept().unwrap();
+            let mut line = Vec::new();
+            BufReader::with_capacity(1, &stream)
+                .read_until(b'\n', &mut line)
+                .unwrap();
+            stream.write_all(b"OK 1073741824\n").unwrap();
+            let mut payload = [0u8; 4];
+            stream.read_exact(&mut payload).unwrap();
+            stream.write_all(&payload.to_ascii_uppercase()).unwrap();
+            String::from_utf8(line).unwrap()
+        });
+
+        bridge_to_guest(&uds_path, &listen_path, 2695).unwrap();
+        let mut client = UnixStream::connect(&listen_path).unwrap();
+        client.write_all(b"ping").unwrap();
+        let mut reply = [0u8; 4];
+        client.read_exact(&mut reply).unwrap();
+
+        assert_eq!(&reply, b"PING");
+        assert_eq!(fake.join().unwrap(), "CONNECT 2695\n");
+    }
+
+    #[test]
+    fn test_connect_guest_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let uds_path = dir.path().join("fc-vsock.sock");
+        let device = UnixListener::bind(&uds_path).unwrap();
+        std::thread::spawn(move || {
+            let (stream, _) = device.accept().unwrap();
+            drop(stream);
+        });
+
+        assert!(connect_guest(&uds_path, 5000).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! Host capacity gate for box resource requests.
+//!
+//! Rejects boxes that ask for more vCPUs than the host has, or more memory
+//! than the host has times the runtime's overcommit factor, before anything
+//! is persisted or a VM is launched.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+/// CPU and memory capacity of the host.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HostResources {
+    pub cpus: usize,
+    pub memory_mib: u64,
+}
+
+impl HostResources {
+    /// Read the host's configured CPUs and physical memory.
+    pub fn detect() -> Self {
+        // SAFETY: sysconf has no preconditions.
+        let (cpus, pages, page_size) = unsafe {
+            (
+                libc::sysconf(libc::_SC_NPROCESSORS_CONF),
+                libc::sysconf(libc::_SC_PHYS_PAGES),
+                libc::sysconf(libc::_SC_PAGE_SIZE),
+            )
+        };
+        let cpus = if cpus > 0 {
+            cpus as usize
+        } else {
+            std::thread::available_parallelism().map_or(1, |n| n.get())
+        };
+        let memory_mib = if pages > 0 && page_size > 0 {
+            (pages as u64).saturating_mul(page_size as u64) / (1024 * 1024)
+        } else {
+            u64::MAX
+        };
+        Self { cpus, memory_mib }
+    }
+}
+
+/// Validate a memory overcommit factor from `BoxliteOptions`.
+pub fn validate_overcommit(factor: f64) -> BoxliteResult<f64> {
+    if factor.is_finite() && factor > 0.0 {
+        Ok(factor)
+    } else {
+        Err(BoxliteError::Config(format!(
+            "memory_overcommit must be a positive number, got {factor}"
+        )))
+    }
+}
+
+/// Check requested `cpus` and `memory_mib` against `host`.
+///
+/// Only explicitly requested values are checked; `None` falls back to the
+/// engine defaults, which are small enough for any supported host.
+pub fn check_resources(
+    cpus: Option<u8>,
+    memory_mib: Option<u32>,
+    host: HostResources,
+    overcommit: f64,
+) -> BoxliteResult<()> {
+    if let Some(cpus) = cpus
+        && usize::from(cpus) > host.cpus
+    {
+        return Err(BoxliteError::InvalidArgument(format!(
+            "cpus={cpus} exceeds host capacity of {} CPUs",
+            host.cpus
+        )));
+    }
+
+    if let Some(memory_mib) = memory_mib {
+        let limit = (host.memory_mib as f64 * overcommit).floor();
+        if f64::from(memory_mib) > limit {
+            return Err(BoxliteError::InvalidArgument(format!(
+                "memory_mib={memory_mib} exceeds host limit of {limit} MiB \
+                 ({} MiB available x {overcommit} overcommit)",
+                host.memory_mib
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: HostResources = HostResources {
+        cpus: 4,
+        memory_mib: 8192,
+    };
+
+    #[test]
+    fn test_cpus_boundary() {
+        assert!(check_resources(Some(4), None, HOST, 1.0).is_ok());
+
+        let err = check_resources(Some(5), None, HOST, 1.0).unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err}");
+        assert!(err.to_string().contains("4 CPUs"), "{err}");
+    }
+
+    #[test]
+    fn test_memory_boundary() {
+        assert!(check_resources(None, Some(8192), HOST, 1.0).is_ok());
+
+        let err = check_resources(None, Some(8193), HOST, 1.0).unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err}");
+        assert!(err.to_string().contains("8192 MiB"), "{err}");
+    }
+
+    #[test]
+    fn test_memory_overcommit_boundary() {
+        assert!(check_resources(None, Some(12288), HOST, 1.5).is_ok());
+        assert!(check_resources(None, Some(12289), HOST, 1.5).is_err());
+
+        // Undercommit lowers the limit below physical memory.
+        assert!(check_resources(None, Some(4096), HOST, 0.5).is_ok());
+        assert!(check_resources(None, Some(4097), HOST, 0.5).is_err());
+    }
+
+    #[test]
+    fn test_unset_values_are_not_checked() {
+        let tiny = HostResources {
+            cpus: 1,
+            memory_mib: 64,
+        };
+        assert!(check_resources(None, None, tiny, 1.0).is_ok());
+    }
+
+    #[test]
+    fn test_validate_overcommit() {
+        assert_eq!(validate_overcommit(1.0).unwrap(), 1.0);
+        assert_eq!(validate_overcommit(2.5).unwrap(), 2.5);
+        for bad in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(
+                matches!(validate_overcommit(bad), Err(BoxliteError::Config(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_detect_reports_capacity() {
+        let host = HostResources::detect();
+        assert!(host.cpus >= 1);
+        assert!(host.memory_mib > 0);
+    }
+}
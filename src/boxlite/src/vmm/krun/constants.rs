@@ -25,6 +25,15 @@ impl TsiFeatures {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::net::ConnectionType;
+
+    #[test]
+    fn net_flags_per_connection_type() {
+        use network_features::{NET_FLAG_VFKIT, net_flags};
+        assert_eq!(net_flags(ConnectionType::UnixStream), Some(0));
+        assert_eq!(net_flags(ConnectionType::UnixDgram), Some(NET_FLAG_VFKIT));
+        assert_eq!(net_flags(ConnectionType::VhostUser), None);
+    }
 
     #[test]
     fn tsi_features_none_is_zero() {
@@ -86,4 +95,16 @@ pub mod network_features {
     // NET_FLAG_VFKIT: Send the VFKIT magic ("VFKT") after establishing connection
     // This is required by gvproxy when using VFKit protocol with unixgram sockets
     pub const NET_FLAG_VFKIT: u32 = 1 << 0;
+
+    /// `flags` argument for the `krun_add_net_*` call attaching a backend over
+    /// `connection`, or `None` when libkrun cannot attach it at all.
+    pub fn net_flags(connection: crate::net::ConnectionType) -> Option<u32> {
+        use crate::net::ConnectionType;
+        match connection {
+            ConnectionType::UnixStream => Some(0),
+            ConnectionType::UnixDgram => Some(NET_FLAG_VFKIT),
+            // libkrun has no vhost-user net frontend to negotiate with.
+            ConnectionType::VhostUser => None,
+        }
+    }
 }
@@ -6,17 +6,18 @@
 
 #![allow(clippy::missing_safety_doc)]
 
-use std::{ffi::CString, ptr};
+use std::{ffi::CString, os::unix::ffi::OsStrExt, path::Path, ptr};
 
+use crate::vmm::DiskFormat;
 use crate::vmm::krun::check_status;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use libkrun_sys::{
-    krun_add_disk2, krun_add_net_unixgram, krun_add_net_unixstream, krun_add_virtiofs3,
-    krun_add_vsock, krun_add_vsock_port2, krun_create_ctx, krun_disable_implicit_vsock,
-    krun_free_ctx, krun_set_console_output, krun_set_env, krun_set_exec, krun_set_gpu_options,
-    krun_set_kernel, krun_set_nested_virt, krun_set_port_map, krun_set_rlimits, krun_set_root,
-    krun_set_root_disk_remount, krun_set_vm_config, krun_set_workdir, krun_setgid, krun_setuid,
-    krun_split_irqchip, krun_start_enter,
+    krun_add_disk2, krun_add_disk3, krun_add_net_unixgram, krun_add_net_unixstream,
+    krun_add_virtiofs3, krun_add_vsock, krun_add_vsock_port2, krun_create_ctx,
+    krun_disable_implicit_vsock, krun_free_ctx, krun_set_console_output, krun_set_env,
+    krun_set_exec, krun_set_gpu_options, krun_set_kernel, krun_set_nested_virt, krun_set_port_map,
+    krun_set_rlimits, krun_set_root, krun_set_root_disk_remount, krun_set_vm_config,
+    krun_set_workdir, krun_setgid, krun_setuid, krun_split_irqchip, krun_start_enter,
 };
 
 /// Thin wrapper that owns a libkrun context.
@@ -36,9 +37,7 @@ impl KrunContext {
         let ctx = unsafe { krun_create_ctx() };
         if ctx < 0 {
             tracing::error!(status = ctx, "krun_create_ctx failed");
-            return Err(BoxliteError::Engine(format!(
-                "krun_create_ctx failed with status {ctx}"
-            )));
+            return Err(super::KrunError::from_status("krun_create_ctx", ctx).into());
         }
         tracing::trace!(ctx_id = ctx, "krun_create_ctx succeeded");
         Ok(Self { ctx_id: ctx as u32 })
@@ -74,7 +73,7 @@ impl KrunContext {
     /// * `options` - Mount options or None for defaults
     ///
     /// # Note
-    /// The block device must be configured via `add_disk_with_format` before calling this.
+    /// The block device must be configured via `add_disk` before calling this.
     pub unsafe fn set_root_disk_remount(
         &self,
         device: &str,
@@ -340,6 +339,13 @@ impl KrunContext {
         let socket_path_c = CString::new(socket_path)
             .map_err(|e| BoxliteError::Engine(format!("invalid socket path: {e}")))?;
 
+        let flags = crate::vmm::krun::constants::network_features::net_flags(connection_type)
+            .ok_or_else(|| {
+                BoxliteError::Unsupported(format!(
+                    "libkrun cannot attach a {connection_type:?} network backend"
+                ))
+            })?;
+
         // Use the appropriate libkrun function based on socket type:
         // - UnixStream for passt/socket_vmnet (SOCK_STREAM)
         // - UnixGram for gvproxy/vmnet-helper (SOCK_DGRAM)
@@ -355,7 +361,7 @@ impl KrunContext {
                         -1,                     // fd: -1 (use path instead)
                         mac_address.as_ptr(),   // c_mac: valid MAC address (required, not NULL!)
                         features,               // features: virtio-net features bitmask
-                        0,                      // flags: 0 for default
+                        flags,                  // flags: 0 for default
                     )
                 })
             }
@@ -367,10 +373,11 @@ impl KrunContext {
                         -1,                     // fd: -1 (use path instead)
                         mac_address.as_ptr(),   // c_mac: valid MAC address (required, not NULL!)
                         features,               // features: virtio-net features bitmask
-                        crate::vmm::krun::constants::network_features::NET_FLAG_VFKIT, // flags: Send VFKIT magic handshake
+                        flags,                  // flags: Send VFKIT magic handshake
                     )
                 })
             }
+            crate::net::ConnectionType::VhostUser => unreachable!("rejected by net_flags"),
         }
     }
 
@@ -442,16 +449,12 @@ impl KrunContext {
         })
     }
 
-    /// Add a disk image with explicit format specification.
-    ///
-    /// This API supports multiple disk formats (raw, qcow2, etc.) by explicitly
-    /// specifying the format. This is safer than auto-probing which can be dangerous.
+    /// Attach a disk image as virtio-blk device `block_id`.
     ///
-    /// # Arguments
-    /// * `block_id` - Identifier for the block device (e.g., "vda", "vdb")
-    /// * `disk_path` - Path to the disk images file on the host
-    /// * `read_only` - Whether to mount the disk as read-only
-    /// * `format` - Disk images format: "raw", "qcow2", etc.
+    /// The format is passed to libkrun explicitly rather than probed, which
+    /// would let a guest-written raw image masquerade as qcow2. Qcow2 images
+    /// are checked for the `QFI\xfb` magic first so a mislabeled disk fails
+    /// here with a clear error instead of deep inside libkrun.
     ///
     /// # Security Note
     /// Non-raw images (like qcow2) can reference other files, which libkrun will
@@ -459,51 +462,70 @@ impl KrunContext {
     ///
     /// # Example
     /// ```ignore
-    /// // Attach a qcow2 disk images
-    /// ctx.add_disk_with_format("vda", "/path/to/disk.qcow2", false, "qcow2")?;
-    ///
-    /// // Attach a raw disk (same as add_disk but more explicit)
-    /// ctx.add_disk_with_format("vdb", "/path/to/disk.raw", true, "raw")?;
+    /// ctx.add_disk("vda", Path::new("/path/to/disk.qcow2"), DiskFormat::Qcow2, false)?;
+    /// ctx.add_disk("vdb", Path::new("/path/to/disk.raw"), DiskFormat::Raw, true)?;
     /// ```
-    pub unsafe fn add_disk_with_format(
+    pub unsafe fn add_disk(
         &self,
         block_id: &str,
-        disk_path: &str,
+        path: &Path,
+        format: DiskFormat,
         read_only: bool,
-        format: &str,
     ) -> BoxliteResult<()> {
         tracing::debug!(
             block_id,
-            disk_path,
+            path = %path.display(),
+            format = format.as_str(),
             read_only,
-            format,
-            "Adding disk images with format"
+            "Adding disk image"
         );
 
         let block_id_c = CString::new(block_id)
-            .map_err(|e| BoxliteError::Engine(format!("invalid block_id: {e}")))?;
-        let disk_path_c = CString::new(disk_path)
-            .map_err(|e| BoxliteError::Engine(format!("invalid disk path: {e}")))?;
-
-        // Convert format string to libkrun constant
-        let disk_format = match format {
-            "raw" => libkrun_sys::KRUN_DISK_FORMAT_RAW,
-            "qcow2" => libkrun_sys::KRUN_DISK_FORMAT_QCOW2,
-            _ => {
-                return Err(BoxliteError::Engine(format!(
-                    "unsupported disk format: {} (supported: raw, qcow2)",
-                    format
-                )));
-            }
-        };
+            .map_err(|e| BoxliteError::InvalidArgument(format!("invalid block_id: {e}")))?;
+        let disk_path_c = path_to_cstring(path)?;
+        validate_disk_image(path, format)?;
 
         check_status("krun_add_disk2", unsafe {
             krun_add_disk2(
                 self.ctx_id,
                 block_id_c.as_ptr(),
                 disk_path_c.as_ptr(),
-                disk_format,
+                krun_disk_format(format),
+                read_only,
+            )
+        })
+    }
+
+    /// Attach host block device `path` as virtio-blk device `block_id`.
+    ///
+    /// The device is opened with `O_DIRECT` so guest I/O bypasses the host
+    /// page cache, and flushes are passed through to the device.
+    pub unsafe fn add_passthrough_disk(
+        &self,
+        block_id: &str,
+        path: &Path,
+        read_only: bool,
+    ) -> BoxliteResult<()> {
+        tracing::debug!(
+            block_id,
+            path = %path.display(),
+            read_only,
+            "Adding passthrough block device"
+        );
+
+        let block_id_c = CString::new(block_id)
+            .map_err(|e| BoxliteError::InvalidArgument(format!("invalid block_id: {e}")))?;
+        let disk_path_c = path_to_cstring(path)?;
+
+        check_status("krun_add_disk3", unsafe {
+            krun_add_disk3(
+                self.ctx_id,
+                block_id_c.as_ptr(),
+                disk_path_c.as_ptr(),
+                libkrun_sys::KRUN_DISK_FORMAT_RAW,
                 read_only,
+                true,
+                libkrun_sys::KRUN_SYNC_FULL,
             )
         })
     }
@@ -569,3 +591,68 @@ impl Drop for KrunContext {
         }
     }
 }
+
+/// libkrun `KRUN_DISK_FORMAT_*` constant for `format`.
+fn krun_disk_format(format: DiskFormat) -> u32 {
+    match format {
+        DiskFormat::Raw => libkrun_sys::KRUN_DISK_FORMAT_RAW,
+        DiskFormat::Qcow2 => libkrun_sys::KRUN_DISK_FORMAT_QCOW2,
+    }
+}
+
+fn path_to_cstring(path: &Path) -> BoxliteResult<CString> {
+    CString::new(path.as_os_str().as_bytes()).map_err(|_| {
+        BoxliteError::InvalidArgument(format!("disk path contains a NUL byte: {}", path.display()))
+    })
+}
+
+/// Reject qcow2-labeled images that don't carry the qcow2 magic.
+fn validate_disk_image(path: &Path, format: DiskFormat) -> BoxliteResult<()> {
+    if format == DiskFormat::Qcow2 && !crate::disk::has_qcow2_magic(path)? {
+        return Err(BoxliteError::Engine(format!(
+            "disk {} is declared qcow2 but lacks the qcow2 magic (QFI\\xfb)",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn disk_formats_map_to_krun_constants() {
+        assert_eq!(
+            krun_disk_format(DiskFormat::Raw),
+            libkrun_sys::KRUN_DISK_FORMAT_RAW
+        );
+        assert_eq!(
+            krun_disk_format(DiskFormat::Qcow2),
+            libkrun_sys::KRUN_DISK_FORMAT_QCOW2
+        );
+    }
+
+    #[test]
+    fn qcow2_without_magic_is_rejected() {
+        let dir = TempDir::new().unwrap();
+        let raw = dir.path().join("disk.img");
+        std::fs::write(&raw, vec![0u8; 1024]).unwrap();
+
+        let err = validate_disk_image(&raw, DiskFormat::Qcow2).unwrap_err();
+        assert!(matches!(err, BoxliteError::Engine(_)), "{err:?}");
+        assert!(err.to_string().contains("qcow2 magic"), "{err}");
+        validate_disk_image(&raw, DiskFormat::Raw).unwrap();
+
+        let qcow2 = dir.path().join("disk.qcow2");
+        crate::disk::qcow2::write_test_qcow2(&qcow2, None);
+        validate_disk_image(&qcow2, DiskFormat::Qcow2).unwrap();
+    }
+
+    #[test]
+    fn nul_in_disk_path_is_invalid_argument() {
+        let err = path_to_cstring(Path::new("/tmp/bad\0disk.img")).unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err:?}");
+    }
+}
@@ -1,17 +1,23 @@
 //! Krun - VMM implementation using libkrun.
 
 use super::context::KrunContext;
+use crate::net::ConnectionType;
 use crate::runtime::constants::network;
 use crate::runtime::constants::vm_defaults::{DEFAULT_CPUS, DEFAULT_MEMORY_MIB};
-use crate::vmm::{InstanceSpec, Vmm, VmmConfig, VmmInstance, engine::VmmInstanceImpl};
+use crate::vmm::{
+    DiskFormat, EngineFeatures, InstanceSpec, Vmm, VmmConfig, VmmInstance, engine::VmmInstanceImpl,
+};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 
-use super::kernel::KrunfwKernelConfig;
+use super::kernel::{KrunfwKernelConfig, apply_custom_kernel};
 
 /// Libkrun-specific VMM instance implementation.
 struct KrunVmmInstance {
     context: KrunContext,
     probe: Box<dyn crate::system_check::HypervisorProbe>,
+    /// Shared locks on read-write disks, held until the VM exits so the
+    /// disks can't be snapshotted underneath it.
+    _disk_locks: Vec<std::fs::File>,
 }
 
 impl VmmInstanceImpl for KrunVmmInstance {
@@ -52,7 +58,6 @@ impl VmmInstanceImpl for KrunVmmInstance {
 /// configuration. The actual VM execution happens when `VmmInstance::enter()`
 /// is called, which performs process takeover via libkrun's `krun_start_enter()`.
 pub struct Krun {
-    #[allow(dead_code)]
     options: VmmConfig,
 }
 
@@ -69,105 +74,19 @@ impl Krun {
         Ok(Self { options })
     }
 
-    /// Transform Unix socket URIs to vsock URIs in a shell command string.
+    /// Resolve the split IRQ chip setting for a box.
     ///
-    /// Replaces `--{arg_name} unix://...` with `--{arg_name} vsock://PORT`
-    fn transform_shell_arg_unix_to_vsock(input: &str, arg_name: &str, vsock_port: u32) -> String {
-        use boxlite_shared::BoxTransport;
-        let vsock_uri = BoxTransport::vsock(vsock_port).to_uri();
-        let pattern = format!("--{} unix://", arg_name);
-
-        let mut result = String::new();
-        let mut chars = input.chars().peekable();
-        let mut pos = 0;
-
-        while let Some(c) = chars.next() {
-            // Check if we're at the start of the pattern
-            if c == '-' && input[pos..].starts_with(&pattern) {
-                // Copy "--{arg_name} "
-                result.push_str(&format!("--{} ", arg_name));
-
-                // Skip past the pattern
-                let skip_len = pattern.len() - 1; // -1 because we already consumed '-'
-                for _ in 0..skip_len {
-                    chars.next();
-                }
-                pos += pattern.len();
-
-                // Skip the rest of the unix path until whitespace
-                while let Some(&next) = chars.peek() {
-                    if next.is_whitespace() {
-                        break;
-                    }
-                    chars.next();
-                    pos += 1;
-                }
-
-                // Add the vsock URI
-                result.push_str(&vsock_uri);
-            } else {
-                result.push(c);
-                pos += c.len_utf8();
-            }
+    /// The per-box spec overrides the engine default. libkrun only implements
+    /// split IRQ chip for KVM on x86_64, so an explicit setting anywhere else
+    /// is an error instead of a silent no-op.
+    fn split_irqchip_setting(&self, requested: Option<bool>) -> BoxliteResult<Option<bool>> {
+        let setting = requested.or(self.options.split_irqchip);
+        if setting.is_some() && !cfg!(target_arch = "x86_64") {
+            return Err(BoxliteError::Unsupported(
+                "split_irqchip is only supported on x86_64".into(),
+            ));
         }
-
-        result
-    }
-
-    /// Transform a single Unix socket argument to vsock.
-    ///
-    /// Handles two cases:
-    /// 1. Separate arguments: ["--{arg_name}", "unix://..."]
-    /// 2. Shell command string: ["-c", "... --{arg_name} unix://... "]
-    fn transform_arg_unix_to_vsock(guest_args: &mut [String], arg_name: &str, vsock_port: u32) {
-        use boxlite_shared::BoxTransport;
-        let vsock_uri = BoxTransport::vsock(vsock_port).to_uri();
-        let pattern = format!("--{} unix://", arg_name);
-
-        for i in 0..guest_args.len() {
-            // Case 1: Separate arguments ["--{arg_name}", "unix://..."]
-            if guest_args[i] == format!("--{}", arg_name)
-                && i + 1 < guest_args.len()
-                && guest_args[i + 1].starts_with("unix://")
-            {
-                tracing::debug!(
-                    arg = arg_name,
-                    original = %guest_args[i + 1],
-                    transformed = %vsock_uri,
-                    "Transforming Unix socket to vsock URI"
-                );
-                guest_args[i + 1] = vsock_uri;
-                return;
-            }
-
-            // Case 2: Shell command string (e.g., -c "... --{arg_name} unix://... ")
-            if guest_args[i].contains(&pattern) {
-                let transformed =
-                    Self::transform_shell_arg_unix_to_vsock(&guest_args[i], arg_name, vsock_port);
-                tracing::debug!(
-                    arg = arg_name,
-                    original = %guest_args[i],
-                    transformed = %transformed,
-                    "Transforming shell command string"
-                );
-                guest_args[i] = transformed;
-                return;
-            }
-        }
-    }
-
-    /// Transform guest arguments to replace Unix socket URIs with vsock URIs.
-    ///
-    /// Transforms both --listen and --notify from Unix to vsock.
-    /// The engine bridges Unix sockets on host to vsock ports inside VM.
-    fn transform_guest_args(mut guest_args: Vec<String>) -> Vec<String> {
-        // Transform --listen unix://... -> --listen vsock://2695
-        Self::transform_arg_unix_to_vsock(&mut guest_args, "listen", network::GUEST_AGENT_PORT);
-
-        // Transform --notify unix://... -> --notify vsock://2696
-        Self::transform_arg_unix_to_vsock(&mut guest_args, "notify", network::GUEST_READY_PORT);
-
-        guest_args
+        Ok(setting)
     }
 
     fn set_entrypoint(config: &InstanceSpec, ctx: &mut KrunContext) -> Result<(), BoxliteError> {
@@ -178,7 +97,8 @@ impl Krun {
         let guest_executable = &config.guest_entrypoint.executable;
 
         // Transform guest arguments (engine handles transport-specific transformations)
-        let guest_args = Self::transform_guest_args(config.guest_entrypoint.args.clone());
+        let guest_args =
+            crate::vmm::guest_args::transform_guest_args(config.guest_entrypoint.args.clone());
         tracing::debug!(executable = %guest_executable,
                             args_count = guest_args.len(),
                             "Configuring entrypoint");
@@ -216,13 +136,22 @@ impl Vmm for Krun {
         }
 
         // Validate disk images exist
+        let mut disk_locks = Vec::new();
         for block_device in config.block_devices.devices() {
-            if !block_device.disk_path.exists() {
+            if block_device.passthrough {
+                crate::disk::validate_passthrough_device(
+                    &block_device.disk_path,
+                    block_device.read_only,
+                )?;
+            } else if !block_device.disk_path.exists() {
                 return Err(BoxliteError::Engine(format!(
                     "Disk image not found: {}",
                     block_device.disk_path.display()
                 )));
             }
+            if !block_device.read_only {
+                disk_locks.push(crate::disk::lock_attached_disk(&block_device.disk_path)?);
+            }
             tracing::debug!(
                 block_id = %block_device.block_id,
                 path = %block_device.disk_path.display(),
@@ -232,13 +161,29 @@ impl Vmm for Krun {
             );
         }
 
+        let split_irqchip = self.split_irqchip_setting(config.split_irqchip)?;
+        if let Some(cores) = &config.cpu_affinity {
+            // The jailer pinned the shim before exec; libkrun's vCPU threads
+            // inherit that mask when krun_start_enter spawns them.
+            tracing::debug!(?cores, "vCPU threads pinned via shim CPU affinity");
+        }
+
         // Create and configure libkrun context
         let ctx = unsafe {
             tracing::debug!("Creating libkrun context");
             let mut ctx = KrunContext::create()?;
 
-            if let Some(kernel) = KrunfwKernelConfig::from_env()? {
-                kernel.apply(&ctx)?;
+            let append = &config.kernel_cmdline_append;
+            if let Some(spec) = &config.kernel {
+                apply_custom_kernel(spec, append, &ctx)?;
+            } else if let Some(kernel) = KrunfwKernelConfig::from_env()? {
+                kernel.apply(append, &ctx)?;
+            } else if !append.is_empty() {
+                return Err(BoxliteError::Unsupported(
+                    "kernel_cmdline_append needs a custom kernel or the external libkrunfw kernel; \
+                     the bundled kernel's command line cannot be changed"
+                        .to_string(),
+                ));
             }
             tracing::debug!(
                 cpus = config.cpus.unwrap_or(DEFAULT_CPUS),
@@ -250,6 +195,11 @@ impl Vmm for Krun {
                 config.memory_mib.unwrap_or(DEFAULT_MEMORY_MIB),
             )?;
 
+            if let Some(enable) = split_irqchip {
+                tracing::debug!(enable, "Configuring split IRQ chip");
+                ctx.split_irqchip(enable)?;
+            }
+
             // Configure net from connection info passed by parent process
             if let Some(connection) = &config.network_backend_endpoint {
                 tracing::info!(connection = ?connection, "Configuring network connection");
@@ -336,13 +286,7 @@ impl Vmm for Krun {
                 }
             }
 
-            // Configure rlimits that will be set in the guest
-            // Format: "RLIMIT_NAME=soft:hard" where soft and hard are limits
-            // These limits ensure the guest has adequate resources for container workloads
-            let rlimits = vec![
-                "6=4096:8192".to_string(),       // RLIMIT_NPROC = 6
-                "7=1048576:1048576".to_string(), // RLIMIT_NOFILE = 7
-            ];
+            let rlimits = guest_rlimits(&config.ulimits);
             tracing::debug!("Configuring guest rlimits: {:?}", rlimits);
             ctx.set_rlimits(&rlimits)?;
 
@@ -366,18 +310,15 @@ impl Vmm for Krun {
             if !config.block_devices.devices().is_empty() {
                 tracing::info!("Attaching block devices:");
                 for disk in config.block_devices.devices() {
-                    let path_str = disk.disk_path.to_str().ok_or_else(|| {
-                        BoxliteError::Engine(format!(
-                            "Invalid disk path: {}",
-                            disk.disk_path.display()
-                        ))
-                    })?;
-
                     tracing::info!(
                         "  {} → {} ({}, {})",
                         disk.block_id,
                         disk.disk_path.display(),
-                        disk.format.as_str(),
+                        if disk.passthrough {
+                            "passthrough"
+                        } else {
+                            disk.format.as_str()
+                        },
                         if disk.read_only {
                             "read-only"
                         } else {
@@ -385,12 +326,11 @@ impl Vmm for Krun {
                         }
                     );
 
-                    ctx.add_disk_with_format(
-                        &disk.block_id,
-                        path_str,
-                        disk.read_only,
-                        disk.format.as_str(),
-                    )?;
+                    if disk.passthrough {
+                        ctx.add_passthrough_disk(&disk.block_id, &disk.disk_path, disk.read_only)?;
+                    } else {
+                        ctx.add_disk(&disk.block_id, &disk.disk_path, disk.format, disk.read_only)?;
+                    }
                 }
             }
 
@@ -459,6 +399,23 @@ impl Vmm for Krun {
             );
             ctx.add_vsock_port(network::GUEST_READY_PORT, ready_socket_path, false)?;
 
+            // User vsock ports: listen=true, libkrun binds the socket and
+            // bridges each host connection to the guest listener on `port`.
+            for vsock in &config.vsock_ports {
+                let socket_path = vsock.socket_path.to_str().ok_or_else(|| {
+                    BoxliteError::Engine(format!(
+                        "invalid vsock socket path: {}",
+                        vsock.socket_path.display()
+                    ))
+                })?;
+                tracing::debug!(
+                    socket_path,
+                    guest_port = vsock.port,
+                    "Configuring user vsock port"
+                );
+                ctx.add_vsock_port(vsock.port, socket_path, true)?;
+            }
+
             // Configure console output redirection if specified
             if let Some(console_path) = &config.console_output {
                 let console_path_str = console_path.to_str().ok_or_else(|| {
@@ -479,7 +436,102 @@ impl Vmm for Krun {
         let instance = KrunVmmInstance {
             context: ctx,
             probe: crate::system_check::hypervisor_probe(),
+            _disk_locks: disk_locks,
         };
         Ok(VmmInstance::new(Box::new(instance)))
     }
+
+    fn features(&self) -> EngineFeatures {
+        EngineFeatures {
+            // libkrun-sys builds libkrun with the `blk` and `net` features only.
+            disk_formats: vec![DiskFormat::Raw, DiskFormat::Qcow2],
+            net_connections: vec![ConnectionType::UnixStream, ConnectionType::UnixDgram],
+            // krun_set_nested_virt is only honored by the Hypervisor.framework backend.
+            nested_virt: cfg!(target_os = "macos"),
+            gpu: false,
+            // Box snapshots are disk-level; libkrun has no VM-state snapshot API.
+            snapshot: false,
+            // libkrun exposes no control over a balloon target size.
+            memory_balloon: false,
+        }
+    }
+}
+
+/// Rlimits libkrun sets in the guest before starting the agent, as
+/// `RESOURCE=soft:hard`.
+///
+/// The defaults give container workloads room to work; a box's ulimits
+/// replace the default for the same resource.
+fn guest_rlimits(ulimits: &[crate::runtime::options::Ulimit]) -> Vec<String> {
+    const DEFAULTS: [(u32, &str); 2] = [
+        (6, "6=4096:8192"),       // RLIMIT_NPROC
+        (7, "7=1048576:1048576"), // RLIMIT_NOFILE
+    ];
+
+    let mut rlimits: Vec<String> = DEFAULTS
+        .iter()
+        .filter(|(resource, _)| !ulimits.iter().any(|u| u.resource() == Some(*resource)))
+        .map(|(_, rlimit)| rlimit.to_string())
+        .collect();
+    rlimits.extend(ulimits.iter().filter_map(|u| u.to_krun_rlimit()));
+    rlimits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guest_rlimits_override_defaults() {
+        use crate::runtime::options::Ulimit;
+
+        assert_eq!(
+            guest_rlimits(&[]),
+            vec!["6=4096:8192".to_string(), "7=1048576:1048576".to_string()]
+        );
+        let rlimits = guest_rlimits(&[Ulimit::new("nofile", 1024, 2048)]);
+        assert_eq!(
+            rlimits,
+            vec!["6=4096:8192".to_string(), "7=1024:2048".to_string()]
+        );
+    }
+
+    #[test]
+    fn features_report_raw_and_qcow2_disks() {
+        let features = Krun::new(VmmConfig::default()).unwrap().features();
+        assert!(features.supports_disk_format(DiskFormat::Raw));
+        assert!(features.supports_disk_format(DiskFormat::Qcow2));
+        assert!(features.supports_net_connection(ConnectionType::UnixStream));
+        assert!(!features.supports_net_connection(ConnectionType::VhostUser));
+        assert!(!features.snapshot);
+        assert!(!features.memory_balloon);
+    }
+
+    #[test]
+    fn split_irqchip_unset_leaves_engine_default() {
+        let krun = Krun::new(VmmConfig::default()).unwrap();
+        assert_eq!(krun.split_irqchip_setting(None).unwrap(), None);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn split_irqchip_spec_overrides_engine_config() {
+        let krun = Krun::new(VmmConfig::default().with_split_irqchip(false)).unwrap();
+        assert_eq!(krun.split_irqchip_setting(None).unwrap(), Some(false));
+        assert_eq!(krun.split_irqchip_setting(Some(true)).unwrap(), Some(true));
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    #[test]
+    fn split_irqchip_rejected_off_x86_64() {
+        let krun = Krun::new(VmmConfig::default()).unwrap();
+        let err = krun.split_irqchip_setting(Some(true)).unwrap_err();
+        assert!(matches!(err, BoxliteError::Unsupported(_)));
+
+        let krun = Krun::new(VmmConfig::default().with_split_irqchip(false)).unwrap();
+        assert!(matches!(
+            krun.split_irqchip_setting(None),
+            Err(BoxliteError::Unsupported(_))
+        ));
+    }
 }
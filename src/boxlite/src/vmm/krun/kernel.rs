@@ -1,4 +1,4 @@
-//! External libkrunfw kernel configuration.
+//! External libkrunfw kernel and per-box custom kernel configuration.
 
 use super::context::KrunContext;
 use crate::runtime::constants::envs::{
@@ -6,11 +6,69 @@ use crate::runtime::constants::envs::{
     BOXLITE_KRUNFW_KERNEL_FORMAT as ENV_KRUNFW_KERNEL_FORMAT,
     BOXLITE_KRUNFW_KERNEL_PATH as ENV_KRUNFW_KERNEL_PATH,
 };
+use crate::runtime::options::KernelSpec;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 const LIBKRUNFW_KERNEL_FILE: &str = "libkrunfw.bin";
 
+/// libkrun's default kernel command line, which `krun_set_kernel` replaces
+/// whenever it is given one.
+const KRUN_DEFAULT_CMDLINE: &str =
+    "reboot=k panic=-1 panic_print=0 nomodule console=hvc0 rootfstype=virtiofs rw quiet no-kvmapf";
+
+/// Boot the box's custom kernel instead of the bundled one.
+///
+/// The image format follows `BOXLITE_KRUNFW_KERNEL_FORMAT`, defaulting to
+/// the architecture's usual format.
+pub(crate) fn apply_custom_kernel(
+    spec: &KernelSpec,
+    append: &[String],
+    ctx: &KrunContext,
+) -> BoxliteResult<()> {
+    if cfg!(target_os = "macos") {
+        return Err(BoxliteError::Unsupported(
+            "custom kernels are not supported on macOS; libkrun boots its bundled kernel"
+                .to_string(),
+        ));
+    }
+    spec.validate()?;
+
+    let kernel = utf8_path("kernel", &spec.kernel_path)?;
+    let initramfs = spec
+        .initramfs
+        .as_deref()
+        .map(|path| utf8_path("initramfs", path))
+        .transpose()?;
+    let cmdline = compose_cmdline(&spec.cmdline(KRUN_DEFAULT_CMDLINE), append);
+    let format = KrunfwKernelConfig::format_from_env()?;
+    tracing::info!(
+        kernel,
+        initramfs,
+        format,
+        cmdline,
+        "Configuring libkrun with custom kernel"
+    );
+    unsafe { ctx.set_kernel(kernel, format, initramfs, Some(&cmdline)) }
+}
+
+/// `base` followed by the `append` parameters, in order.
+fn compose_cmdline(base: &str, append: &[String]) -> String {
+    std::iter::once(base)
+        .chain(append.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn utf8_path<'a>(what: &str, path: &'a Path) -> BoxliteResult<&'a str> {
+    path.to_str().ok_or_else(|| {
+        BoxliteError::Config(format!(
+            "{what} path contains invalid UTF-8: {}",
+            path.display()
+        ))
+    })
+}
+
 /// A validated external kernel ready to be applied to a libkrun context.
 pub(crate) struct KrunfwKernelConfig {
     path: PathBuf,
@@ -68,19 +126,23 @@ impl KrunfwKernelConfig {
         }
     }
 
-    pub(crate) fn apply(&self, ctx: &KrunContext) -> BoxliteResult<()> {
+    /// Load the kernel. libkrun keeps its default command line unless
+    /// `append` asks for extra parameters.
+    pub(crate) fn apply(&self, append: &[String], ctx: &KrunContext) -> BoxliteResult<()> {
         let path = self.path.to_str().ok_or_else(|| {
             BoxliteError::Config(format!(
                 "krunfw kernel path contains invalid UTF-8: {}",
                 self.path.display()
             ))
         })?;
+        let cmdline = (!append.is_empty()).then(|| compose_cmdline(KRUN_DEFAULT_CMDLINE, append));
         tracing::info!(
             kernel = %self.path.display(),
             format = self.format,
+            cmdline,
             "Configuring libkrun with krunfw kernel"
         );
-        unsafe { ctx.set_kernel(path, self.format, None, None) }
+        unsafe { ctx.set_kernel(path, self.format, None, cmdline.as_deref()) }
     }
 
     fn parse_format(value: &str) -> BoxliteResult<u32> {
@@ -171,6 +233,40 @@ impl KrunfwKernelConfig {
 mod tests {
     use super::*;
 
+    #[test]
+    fn custom_kernel_cmdline_extends_libkrun_default() {
+        let mut spec = KernelSpec {
+            kernel_path: PathBuf::from("/boot/vmlinux"),
+            ..Default::default()
+        };
+        assert_eq!(spec.cmdline(KRUN_DEFAULT_CMDLINE), KRUN_DEFAULT_CMDLINE);
+
+        spec.cmdline_extra = " loglevel=7 ".to_string();
+        assert_eq!(
+            spec.cmdline(KRUN_DEFAULT_CMDLINE),
+            format!("{KRUN_DEFAULT_CMDLINE} loglevel=7")
+        );
+    }
+
+    #[test]
+    fn appended_cmdline_params_follow_defaults_in_order() {
+        let spec = KernelSpec {
+            kernel_path: PathBuf::from("/boot/vmlinux"),
+            cmdline_extra: "loglevel=7".to_string(),
+            ..Default::default()
+        };
+        let append = vec!["quiet".to_string(), "cgroup_no_v1=all".to_string()];
+
+        assert_eq!(
+            compose_cmdline(&spec.cmdline(KRUN_DEFAULT_CMDLINE), &append),
+            format!("{KRUN_DEFAULT_CMDLINE} loglevel=7 quiet cgroup_no_v1=all")
+        );
+        assert_eq!(
+            compose_cmdline(KRUN_DEFAULT_CMDLINE, &[]),
+            KRUN_DEFAULT_CMDLINE
+        );
+    }
+
     #[test]
     fn parses_external_kernel_gate() {
         for value in ["1", "true", "on", "yes", " TRUE "] {
@@ -10,23 +10,128 @@ use boxlite_shared::{BoxliteError, BoxliteResult};
 pub use engine::Krun;
 pub use factory::KrunFactory;
 
+/// A failed libkrun FFI call, keyed by the negative errno it returned.
+///
+/// Each variant names the function that failed. The message lists the
+/// likely causes; it reaches callers as [`BoxliteError::Engine`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum KrunError {
+    #[error(
+        "libkrun function '{function}' returned EINVAL (-22). Possible causes:\n\
+         - macOS: VM address space limit reached (kern.hv.max_address_spaces)\n\
+         - Invalid rootfs structure (missing kernel or initrd)\n\
+         Run `boxlite list` to check active boxes."
+    )]
+    InvalidArgument { function: String },
+
+    #[error(
+        "libkrun function '{function}' returned ENOENT (-2): a path it was given \
+         (kernel, rootfs, disk image, or socket) does not exist"
+    )]
+    NotFound { function: String },
+
+    #[error(
+        "libkrun function '{function}' returned EACCES (-13): permission denied; \
+         check access to /dev/kvm (Linux) or the Hypervisor entitlement (macOS), \
+         and to the box's disk images"
+    )]
+    PermissionDenied { function: String },
+
+    #[error(
+        "libkrun function '{function}' returned ENOMEM (-12): the host cannot back \
+         the requested guest memory; lower memory_mib or free host memory"
+    )]
+    OutOfMemory { function: String },
+
+    #[error(
+        "libkrun function '{function}' returned ENOSPC (-28): no space left on the \
+         filesystem holding the box's disks"
+    )]
+    NoSpace { function: String },
+
+    #[error("libkrun function '{function}' failed with status {status}")]
+    Other { function: String, status: i32 },
+}
+
+impl KrunError {
+    /// Classify the negative `status` returned by libkrun `function`.
+    pub fn from_status(function: &str, status: i32) -> Self {
+        let function = function.to_string();
+        match -status {
+            libc::EINVAL => Self::InvalidArgument { function },
+            libc::ENOENT => Self::NotFound { function },
+            libc::EACCES => Self::PermissionDenied { function },
+            libc::ENOMEM => Self::OutOfMemory { function },
+            libc::ENOSPC => Self::NoSpace { function },
+            _ => Self::Other { function, status },
+        }
+    }
+
+    /// The libkrun function that failed.
+    pub fn function(&self) -> &str {
+        match self {
+            Self::InvalidArgument { function }
+            | Self::NotFound { function }
+            | Self::PermissionDenied { function }
+            | Self::OutOfMemory { function }
+            | Self::NoSpace { function }
+            | Self::Other { function, .. } => function,
+        }
+    }
+}
+
+impl From<KrunError> for BoxliteError {
+    fn from(e: KrunError) -> Self {
+        BoxliteError::Engine(e.to_string())
+    }
+}
+
 pub(crate) fn check_status(label: &str, status: i32) -> BoxliteResult<()> {
     if status < 0 {
         tracing::error!(function = label, status, "libkrun FFI call failed");
-        if status == -22 {
-            return Err(BoxliteError::Engine(format!(
-                "libkrun function '{}' returned EINVAL (-22). Possible causes:\n\
-                 - macOS: VM address space limit reached (kern.hv.max_address_spaces)\n\
-                 - Invalid rootfs structure (missing kernel or initrd)\n\
-                 Run `boxlite list` to check active boxes.",
-                label
-            )));
-        }
-        Err(BoxliteError::Engine(format!(
-            "libkrun function '{}' failed with status {}",
-            label, status
-        )))
+        Err(KrunError::from_status(label, status).into())
     } else {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_errno_maps_to_variant() {
+        let f = "krun_set_root";
+        let cases = [
+            (-22, KrunError::InvalidArgument { function: f.into() }),
+            (-2, KrunError::NotFound { function: f.into() }),
+            (-13, KrunError::PermissionDenied { function: f.into() }),
+            (-12, KrunError::OutOfMemory { function: f.into() }),
+            (-28, KrunError::NoSpace { function: f.into() }),
+            (
+                -5,
+                KrunError::Other {
+                    function: f.into(),
+                    status: -5,
+                },
+            ),
+        ];
+        for (status, expected) in cases {
+            let err = KrunError::from_status(f, status);
+            assert_eq!(err, expected, "status {status}");
+            assert_eq!(err.function(), f);
+            assert!(err.to_string().contains("'krun_set_root'"), "{err}");
+        }
+    }
+
+    #[test]
+    fn test_check_status_wraps_engine_error() {
+        assert!(check_status("krun_start_enter", 0).is_ok());
+
+        let err = check_status("krun_set_vm_config", -12).unwrap_err();
+        assert!(
+            matches!(&err, BoxliteError::Engine(msg) if msg.contains("ENOMEM")),
+            "{err}"
+        );
+    }
+}
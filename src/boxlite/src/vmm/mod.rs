@@ -5,18 +5,24 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::str::FromStr;
 
+pub mod console;
 pub mod controller;
 pub mod engine;
 pub mod exit_info;
 pub mod factory;
+#[cfg(all(feature = "firecracker", target_os = "linux"))]
+pub mod firecracker;
+#[cfg(any(feature = "krun", all(feature = "firecracker", target_os = "linux")))]
+mod guest_args;
 pub mod guest_check;
+pub mod host_check;
 #[cfg(feature = "krun")]
 pub mod krun;
 pub mod registry;
 
 use crate::jailer::SecurityOptions;
 use crate::rootfs::guest::GuestRootfs;
-pub use engine::{Vmm, VmmConfig, VmmInstance};
+pub use engine::{EngineFeatures, Vmm, VmmConfig, VmmInstance};
 pub use exit_info::ExitInfo;
 pub use factory::VmmFactory;
 pub use registry::create_engine;
@@ -117,6 +123,10 @@ pub struct BlockDevice {
     pub read_only: bool,
     /// Disk image format.
     pub format: DiskFormat,
+    /// `disk_path` is a host block device attached directly (with
+    /// `O_DIRECT`) rather than a disk image. Passthrough devices are raw.
+    #[serde(default)]
+    pub passthrough: bool,
 }
 
 /// Collection of block device attachments from host to guest.
@@ -141,6 +151,16 @@ impl BlockDevices {
     }
 }
 
+/// A user vsock port bridged to a host Unix socket.
+///
+/// The engine binds `socket_path`; each host connection is forwarded to the
+/// guest listener on `port`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VsockPort {
+    pub port: u32,
+    pub socket_path: PathBuf,
+}
+
 /// Complete configuration for a Box instance.
 ///
 /// BoxConfig contains volume mounts, guest agent entrypoint,
@@ -159,6 +179,12 @@ pub struct InstanceSpec {
     pub security: SecurityOptions,
     pub cpus: Option<u8>,
     pub memory_mib: Option<u32>,
+    /// Host cores the VM's threads are pinned to (Linux only).
+    #[serde(default)]
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// Split IRQ chip override (x86_64 only). `None` keeps the engine default.
+    #[serde(default)]
+    pub split_irqchip: Option<bool>,
     /// Filesystem shares from host to guest
     pub fs_shares: FsShares,
     /// Block device attachments via virtio-blk
@@ -169,6 +195,9 @@ pub struct InstanceSpec {
     pub transport: boxlite_shared::BoxTransport,
     /// Host-side transport for ready notification (host listens, guest connects when ready)
     pub ready_transport: boxlite_shared::BoxTransport,
+    /// User vsock ports (`BoxOptions::vsock_ports`) and their host sockets.
+    #[serde(default)]
+    pub vsock_ports: Vec<VsockPort>,
     /// Resolved guest rootfs path and assembly strategy
     pub guest_rootfs: GuestRootfs,
     /// Network provisioning spec passed to the shim subprocess. The shim stands
@@ -187,6 +216,29 @@ pub struct InstanceSpec {
     pub home_dir: PathBuf,
     /// Optional file path to redirect console output (kernel/init messages)
     pub console_output: Option<PathBuf>,
+    /// Lines of console output the shim keeps in `console_output`. `None`
+    /// lets the file grow without bound.
+    #[serde(default)]
+    pub console_ring_lines: Option<usize>,
+    /// Custom guest kernel replacing the engine's default.
+    #[serde(default)]
+    pub kernel: Option<crate::runtime::options::KernelSpec>,
+    /// Parameters appended to the kernel command line
+    /// (`BoxOptions::kernel_cmdline_append`).
+    #[serde(default)]
+    pub kernel_cmdline_append: Vec<String>,
+    /// Guest resource limits overriding the engine defaults
+    /// (`BoxOptions::ulimits`).
+    #[serde(default)]
+    pub ulimits: Vec<crate::runtime::options::Ulimit>,
+    /// Idle period after which the shim shuts the VM down
+    /// (`BoxOptions::idle_timeout_secs`). `None` disables idle shutdown.
+    #[serde(default)]
+    pub idle_timeout: Option<std::time::Duration>,
+    /// Where the shim serves its control socket
+    /// (see [`controller::control`]). `None` disables it.
+    #[serde(default)]
+    pub control_socket: Option<PathBuf>,
     /// Exit file for shim to write on panic (Podman pattern).
     pub exit_file: PathBuf,
     /// Whether the box should continue running when the parent process exits.
@@ -219,6 +271,7 @@ mod tests {
             disk_path: PathBuf::from("/tmp/test.qcow2"),
             read_only: false,
             format: DiskFormat::Qcow2,
+            passthrough: false,
         };
 
         assert_eq!(device.block_id, "vda");
@@ -237,6 +290,7 @@ mod tests {
             disk_path: PathBuf::from("/tmp/test.qcow2"),
             read_only: false,
             format: DiskFormat::Qcow2,
+            passthrough: false,
         });
         assert_eq!(devices.devices().len(), 1);
 
@@ -245,6 +299,7 @@ mod tests {
             disk_path: PathBuf::from("/tmp/scratch.raw"),
             read_only: true,
             format: DiskFormat::Raw,
+            passthrough: false,
         });
         assert_eq!(devices.devices().len(), 2);
 
@@ -282,6 +337,7 @@ mod tests {
             disk_path: PathBuf::from("/tmp/test.qcow2"),
             read_only: true,
             format: DiskFormat::Qcow2,
+            passthrough: false,
         };
 
         let json = serde_json::to_string(&device).unwrap();
@@ -89,6 +89,13 @@ mod tests {
         assert!(available.contains(&VmmKind::Libkrun));
     }
 
+    #[test]
+    #[cfg(all(feature = "firecracker", target_os = "linux"))]
+    fn test_firecracker_registered() {
+        assert!(is_registered(VmmKind::Firecracker));
+        assert!(create_engine(VmmKind::Firecracker, VmmConfig::default()).is_ok());
+    }
+
     #[test]
     fn test_unregistered_engine() {
         let options = VmmConfig::default();
@@ -102,10 +102,25 @@ impl<'a> ContainerVolumeManager<'a> {
         });
     }
 
+    /// Add a tmpfs bind-mounted into the container at `container_path`.
+    ///
+    /// The guest mounts the tmpfs at the convention path for `volume_name`.
+    pub fn add_tmpfs(
+        &mut self,
+        container_id: &str,
+        volume_name: &str,
+        container_path: &str,
+        size_mib: u64,
+        mode: u32,
+    ) {
+        self.guest
+            .add_tmpfs(volume_name, size_mib, mode, container_id);
+        self.add_bind(volume_name, container_path, false);
+    }
+
     /// Add a container bind mount directly.
     ///
     /// Use when guest path already exists (e.g., from block device mount).
-    #[allow(dead_code)]
     pub fn add_bind(&mut self, volume_name: &str, container_path: &str, read_only: bool) {
         self.container_mounts.push(ContainerMount {
             volume_name: volume_name.to_string(),
@@ -126,6 +141,7 @@ impl<'a> ContainerVolumeManager<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::portal::interfaces::VolumeConfig;
 
     #[test]
     fn add_volume_carries_subpath_for_single_file() {
@@ -147,4 +163,25 @@ mod tests {
         assert_eq!(mounts.len(), 1);
         assert_eq!(mounts[0].subpath, Some("app.conf".to_string()));
     }
+
+    #[test]
+    fn add_tmpfs_mounts_in_guest_and_binds_into_container() {
+        let mut guest = GuestVolumeManager::new();
+        let mut mgr = ContainerVolumeManager::new(&mut guest);
+        mgr.add_tmpfs("cid", "tmpfs0", "/scratch", 16, 0o1777);
+
+        let mounts = mgr.build_container_mounts();
+        assert_eq!(mounts.len(), 1);
+        assert_eq!(mounts[0].volume_name, "tmpfs0");
+        assert_eq!(mounts[0].destination, "/scratch");
+        assert!(!mounts[0].read_only);
+
+        let guest_mounts = guest.build_guest_mounts();
+        assert!(matches!(
+            guest_mounts.as_slice(),
+            [VolumeConfig::Tmpfs { name, size_mib: 16, mode: 0o1777, container_id }]
+                if name == "tmpfs0" && container_id == "cid"
+        ));
+        assert!(guest.build_vmm_config().block_devices.devices().is_empty());
+    }
 }
@@ -3,6 +3,7 @@
 //! Manages volumes visible to the guest VM:
 //! - Virtiofs shares (host directory → guest mount point)
 //! - Block devices (disk image → /dev/vdX)
+//! - Tmpfs mounts (in guest memory, no host backing)
 //!
 //! Generates configuration for both VMM layer and guest mount instructions.
 
@@ -39,6 +40,18 @@ pub struct BlockDeviceEntry {
     pub need_format: bool,
     /// If true, guest should resize filesystem after mounting
     pub need_resize: bool,
+    /// `disk_path` is a host block device attached directly
+    pub passthrough: bool,
+}
+
+/// Tracked tmpfs entry.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct TmpfsEntry {
+    pub name: String,
+    pub size_mib: u64,
+    pub mode: u32,
+    pub container_id: String,
 }
 
 /// VMM layer mount configuration.
@@ -57,6 +70,7 @@ pub struct VmmMountConfig {
 pub struct GuestVolumeManager {
     fs_shares: Vec<FsShareEntry>,
     block_devices: Vec<BlockDeviceEntry>,
+    tmpfs: Vec<TmpfsEntry>,
     next_block_index: u8,
     next_auto_tag_index: u32,
 }
@@ -68,6 +82,7 @@ impl GuestVolumeManager {
         Self {
             fs_shares: Vec::new(),
             block_devices: Vec::new(),
+            tmpfs: Vec::new(),
             next_block_index: 0,
             next_auto_tag_index: 0,
         }
@@ -128,6 +143,7 @@ impl GuestVolumeManager {
             guest_mount: guest_mount.map(String::from),
             need_format,
             need_resize,
+            passthrough: false,
         });
 
         tracing::debug!(
@@ -143,6 +159,31 @@ impl GuestVolumeManager {
         device_path
     }
 
+    /// Add a host block device attached directly rather than as an image.
+    ///
+    /// Returns the device path in guest (e.g., "/dev/vdc").
+    pub fn add_passthrough_device(&mut self, host_device: &Path, read_only: bool) -> String {
+        // Ext4 maps to a raw VMM block device
+        let device_path =
+            self.add_block_device(host_device, DiskFormat::Ext4, read_only, None, false, false);
+        if let Some(entry) = self.block_devices.last_mut() {
+            entry.passthrough = true;
+        }
+        device_path
+    }
+
+    /// Add a tmpfs, mounted by the guest at the convention path for `name`.
+    ///
+    /// Tmpfs needs no VMM device, so it only appears in guest mounts.
+    pub fn add_tmpfs(&mut self, name: &str, size_mib: u64, mode: u32, container_id: &str) {
+        self.tmpfs.push(TmpfsEntry {
+            name: name.to_string(),
+            size_mib,
+            mode,
+            container_id: container_id.to_string(),
+        });
+    }
+
     /// Allocate next sequential auto-tag (vol0, vol1, ...).
     pub fn next_auto_tag(&mut self) -> String {
         let tag = format!("vol{}", self.next_auto_tag_index);
@@ -171,6 +212,7 @@ impl GuestVolumeManager {
                 disk_path: entry.disk_path.clone(),
                 read_only: entry.read_only,
                 format: vmm_format,
+                passthrough: entry.passthrough,
             });
         }
 
@@ -203,10 +245,20 @@ impl GuestVolumeManager {
                     boxlite_shared::Filesystem::Ext4,
                     entry.need_format,
                     entry.need_resize,
+                    entry.read_only,
                 ));
             }
         }
 
+        for entry in &self.tmpfs {
+            volumes.push(VolumeConfig::tmpfs(
+                &entry.name,
+                entry.size_mib,
+                entry.mode,
+                &entry.container_id,
+            ));
+        }
+
         volumes
     }
 
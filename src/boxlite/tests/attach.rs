@@ -0,0 +1,66 @@
+//! Integration tests for `LiteBox::attach` on local boxes.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test attach -- --test-threads=1`
+
+mod common;
+
+use std::time::Duration;
+
+use boxlite::{BoxCommand, BoxliteError};
+use common::box_test::BoxTestBase;
+use futures::StreamExt;
+
+#[tokio::test]
+async fn attach_recovers_output_after_handle_dropped() {
+    let t = BoxTestBase::new().await;
+
+    let execution =
+        t.bx.exec(BoxCommand::new("sh").args(["-c", "sleep 5 && echo done"]))
+            .await
+            .unwrap();
+    let id = execution.id().clone();
+    drop(execution);
+
+    let mut reattached = t.bx.attach(&id).await.unwrap();
+    assert!(reattached.stdin().is_none(), "re-attach carries no stdin");
+
+    let mut stdout = reattached.stdout().unwrap();
+    let line = tokio::time::timeout(Duration::from_secs(15), stdout.next())
+        .await
+        .expect("timed out waiting for output")
+        .expect("stdout ended without output");
+    assert_eq!(line.trim(), "done");
+
+    let result = reattached.wait().await.unwrap();
+    assert_eq!(result.exit_code, 0);
+}
+
+#[tokio::test]
+async fn attach_after_exit_returns_output_and_status() {
+    let t = BoxTestBase::new().await;
+
+    let execution =
+        t.bx.exec(BoxCommand::new("sh").args(["-c", "echo finished; exit 3"]))
+            .await
+            .unwrap();
+    let id = execution.id().clone();
+    drop(execution);
+    tokio::time::sleep(Duration::from_secs(1)).await;
+
+    let mut reattached = t.bx.attach(&id).await.unwrap();
+    let output: Vec<String> = reattached.stdout().unwrap().collect().await;
+    assert_eq!(output.concat().trim(), "finished");
+    assert_eq!(reattached.wait().await.unwrap().exit_code, 3);
+}
+
+#[tokio::test]
+async fn attach_unknown_execution_is_not_found() {
+    let t = BoxTestBase::new().await;
+
+    let err = t.bx.attach("no-such-execution").await.err().unwrap();
+    assert!(matches!(err, BoxliteError::NotFound(_)), "{err}");
+}
@@ -0,0 +1,61 @@
+//! Integration tests for `BoxliteOptions::max_concurrent_boots`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test boot_concurrency -- --test-threads=1`
+
+mod common;
+
+use boxlite::BoxliteRuntime;
+use boxlite::runtime::options::BoxliteOptions;
+use std::time::{Duration, Instant};
+
+const BOXES: usize = 3;
+
+#[tokio::test]
+async fn boots_beyond_the_limit_queue() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: Some(1),
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let mut handles = Vec::new();
+    for _ in 0..BOXES {
+        handles.push(runtime.create(common::alpine_opts(), None).await.unwrap());
+    }
+
+    // Start every box at once; record when each reaches ready.
+    let started = Instant::now();
+    let mut ready: Vec<Duration> =
+        futures::future::join_all(handles.iter().map(|handle| async move {
+            handle.start().await.unwrap();
+            started.elapsed()
+        }))
+        .await;
+    ready.sort();
+    // The first box boots alone, so its time is one boot's worth. With one
+    // boot slot the rest finish one after another, not all together.
+    let one_boot = ready[0];
+    for pair in ready.windows(2) {
+        assert!(
+            pair[1] - pair[0] >= one_boot / 2,
+            "boots overlapped despite max_concurrent_boots=1: ready at {ready:?}"
+        );
+    }
+
+    for handle in &handles {
+        handle.stop().await.unwrap();
+    }
+}
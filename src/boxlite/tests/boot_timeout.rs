@@ -0,0 +1,104 @@
+#![cfg(target_os = "linux")]
+
+//! Integration tests for the box boot timeout.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test boot_timeout -- --test-threads=1`
+
+mod common;
+
+use boxlite::runtime::options::{BoxOptions, BoxliteOptions};
+use boxlite::runtime::types::BoxStatus;
+use boxlite::{BoxliteError, BoxliteRuntime};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Freeze every process whose argv references `box_id`, returning how many
+/// were stopped. The whole box tree carries the id in its bind paths.
+fn freeze_box_processes(box_id: &str) -> usize {
+    let mut stopped = 0;
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return 0;
+    };
+    for entry in entries.flatten() {
+        let Some(pid) = entry.file_name().to_str().and_then(|n| n.parse().ok()) else {
+            continue;
+        };
+        if let Ok(cmdline) = std::fs::read(entry.path().join("cmdline"))
+            && cmdline
+                .windows(box_id.len())
+                .any(|w| w == box_id.as_bytes())
+            && unsafe { libc::kill(pid, libc::SIGSTOP) } == 0
+        {
+            stopped += 1;
+        }
+    }
+    stopped
+}
+
+#[tokio::test]
+async fn unresponsive_guest_fails_start_after_boot_timeout() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let handle = Arc::new(
+        runtime
+            .create(
+                BoxOptions {
+                    boot_timeout_secs: Some(5),
+                    ..common::alpine_opts()
+                },
+                None,
+            )
+            .await
+            .unwrap(),
+    );
+    let box_id = handle.id().to_string();
+    let pid_file = home.path.join("boxes").join(&box_id).join("shim.pid");
+
+    let started = Instant::now();
+    let start = {
+        let handle = Arc::clone(&handle);
+        tokio::spawn(async move { handle.start().await })
+    };
+
+    // Freeze the VM as soon as the shim is up, so the guest never answers.
+    while !pid_file.exists() && !start.is_finished() {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    assert!(
+        freeze_box_processes(&box_id) > 0,
+        "no box processes to freeze"
+    );
+
+    let err = start
+        .await
+        .unwrap()
+        .expect_err("start should fail when the guest never answers");
+    let elapsed = started.elapsed();
+
+    assert!(matches!(err, BoxliteError::Timeout(_)), "{err:?}");
+    assert!(err.to_string().contains("boot timed out"), "{err}");
+    assert!(
+        elapsed < Duration::from_secs(30),
+        "start took {elapsed:?} with a 5s boot timeout"
+    );
+    assert_eq!(handle.info().status, BoxStatus::Failed);
+
+    runtime.remove(&box_id, true).await.unwrap();
+}
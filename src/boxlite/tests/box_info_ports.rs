@@ -0,0 +1,80 @@
+//! Integration tests for `BoxInfo::ports`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test box_info_ports -- --test-threads=1`
+
+mod common;
+
+use boxlite::runtime::options::{BoxOptions, BoxliteOptions, PortSpec};
+use boxlite::{BoxliteRuntime, PortMapping};
+
+fn runtime_at(home: &boxlite_test_utils::home::PerTestBoxHome) -> BoxliteRuntime {
+    BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime")
+}
+
+#[tokio::test]
+async fn get_info_reports_published_ports_across_restart() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let expected = PortMapping {
+        host_port: 18080,
+        guest_port: 80,
+    };
+    let box_id: String;
+
+    {
+        let runtime = runtime_at(&home);
+        let handle = runtime
+            .create(
+                BoxOptions {
+                    detach: true,
+                    ports: vec![PortSpec {
+                        host_port: Some(18080),
+                        guest_port: 80,
+                        protocol: Default::default(),
+                        host_ip: None,
+                    }],
+                    ..common::alpine_opts()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(handle.info().ports.is_empty(), "not started yet");
+
+        handle.start().await.unwrap();
+        assert!(handle.info().ports.contains(&expected));
+        box_id = handle.id().to_string();
+    }
+
+    // A new runtime reads the mappings back from the database.
+    let runtime = runtime_at(&home);
+    let info = runtime
+        .get_info(&box_id)
+        .await
+        .unwrap()
+        .expect("box should be recovered");
+    assert!(info.ports.contains(&expected), "{:?}", info.ports);
+
+    let handle = runtime.get(&box_id).await.unwrap().unwrap();
+    handle.stop().await.unwrap();
+    let info = runtime.get_info(&box_id).await.unwrap().unwrap();
+    assert!(info.ports.is_empty(), "stopped box publishes nothing");
+
+    let _ = runtime.remove(&box_id, true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
@@ -0,0 +1,79 @@
+//! Integration tests for generated names of anonymous boxes.
+//!
+//! Boxes are only created, never started, so no VM is needed.
+
+mod common;
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use boxlite::runtime::options::BoxliteOptions;
+use boxlite::{BoxNameGenerator, BoxliteRuntime};
+use common::home::PerTestBoxHome;
+
+fn runtime_at(
+    home: &PerTestBoxHome,
+    generator: Option<Arc<dyn BoxNameGenerator>>,
+) -> BoxliteRuntime {
+    BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: generator,
+    })
+    .unwrap()
+}
+
+/// Proposes `taken` first, then `free`, counting calls.
+#[derive(Debug, Default)]
+struct CollidingGenerator {
+    calls: AtomicU32,
+}
+
+impl BoxNameGenerator for CollidingGenerator {
+    fn generate(&self, attempt: u32) -> String {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if attempt == 0 { "taken" } else { "free" }.to_string()
+    }
+}
+
+#[tokio::test]
+async fn anonymous_boxes_get_distinct_generated_names() {
+    let home = PerTestBoxHome::new();
+    let runtime = runtime_at(&home, None);
+
+    let first = runtime.create(common::alpine_opts(), None).await.unwrap();
+    let second = runtime.create(common::alpine_opts(), None).await.unwrap();
+
+    let first_name = first.name().expect("generated name").to_string();
+    let second_name = second.name().expect("generated name").to_string();
+    assert_ne!(first_name, second_name);
+    assert!(first_name.contains('_'), "{first_name}");
+
+    // The generated name is persisted and resolves like a chosen one.
+    let info = runtime.get_info(&first_name).await.unwrap().unwrap();
+    assert_eq!(info.id, *first.id());
+    assert_eq!(info.name.as_deref(), Some(first_name.as_str()));
+}
+
+#[tokio::test]
+async fn generated_name_retries_on_collision() {
+    let home = PerTestBoxHome::new();
+    let generator = Arc::new(CollidingGenerator::default());
+    let runtime = runtime_at(&home, Some(generator.clone()));
+    runtime
+        .create(common::alpine_opts(), Some("taken".into()))
+        .await
+        .unwrap();
+
+    let anonymous = runtime.create(common::alpine_opts(), None).await.unwrap();
+
+    assert_eq!(anonymous.name(), Some("free"));
+    assert_eq!(generator.calls.load(Ordering::SeqCst), 2);
+}
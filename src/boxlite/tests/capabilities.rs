@@ -0,0 +1,65 @@
+//! Integration tests for `BoxOptions::cap_add` / `cap_drop`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test capabilities -- --test-threads=1`
+
+mod common;
+
+use common::box_test::BoxTestBase;
+
+/// Link changes go through rtnetlink, which requires `CAP_NET_ADMIN`.
+const SET_LINK: &str = "ip link set lo up 2>&1; echo exit=$?";
+
+#[tokio::test]
+async fn default_capabilities_exclude_net_admin() {
+    let t = BoxTestBase::with_options(common::alpine_opts()).await;
+    t.bx.start().await.unwrap();
+
+    let out = t.exec_stdout("sh", &["-c", SET_LINK]).await;
+    assert!(!out.contains("exit=0"), "{out}");
+}
+
+#[tokio::test]
+async fn cap_add_net_admin_allows_link_changes() {
+    let t = BoxTestBase::with_options(boxlite::BoxOptions {
+        cap_add: vec!["NET_ADMIN".into()],
+        ..common::alpine_opts()
+    })
+    .await;
+    t.bx.start().await.unwrap();
+
+    let out = t.exec_stdout("sh", &["-c", SET_LINK]).await;
+    assert!(out.contains("exit=0"), "{out}");
+}
+
+#[tokio::test]
+async fn cap_drop_all_empties_the_bounding_set() {
+    let t = BoxTestBase::with_options(boxlite::BoxOptions {
+        cap_drop: vec!["ALL".into()],
+        ..common::alpine_opts()
+    })
+    .await;
+    t.bx.start().await.unwrap();
+
+    let status = t.read_file("/proc/self/status").await;
+    let bounding = status
+        .lines()
+        .find_map(|line| line.strip_prefix("CapBnd:"))
+        .unwrap_or_else(|| panic!("no CapBnd in:\n{status}"));
+    assert_eq!(u64::from_str_radix(bounding.trim(), 16).unwrap(), 0);
+}
+
+#[tokio::test]
+async fn unknown_capability_fails_start() {
+    let t = BoxTestBase::with_options(boxlite::BoxOptions {
+        cap_add: vec!["NET_WIZARD".into()],
+        ..common::alpine_opts()
+    })
+    .await;
+
+    let err = t.bx.start().await.unwrap_err();
+    assert!(err.to_string().contains("unknown capability"), "{err}");
+}
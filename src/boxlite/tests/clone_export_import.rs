@@ -55,6 +55,14 @@ async fn test_clone_produces_independent_box() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_stopped_box(&runtime).await;
@@ -84,6 +92,14 @@ async fn test_export_import_roundtrip() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_stopped_box(&runtime).await;
@@ -124,6 +140,14 @@ async fn test_export_import_preserves_box_options() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -161,6 +185,14 @@ async fn test_clone_running_box() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_running_box(&runtime, "clone-src").await;
@@ -205,6 +237,14 @@ async fn test_export_running_box() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_running_box(&runtime, "export-running").await;
@@ -251,6 +291,14 @@ async fn test_export_import_running_box_roundtrip() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_running_box(&runtime, "roundtrip-running").await;
@@ -312,6 +360,14 @@ async fn test_clone_snapshot_isolation() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_running_box(&runtime, "isolation-src").await;
@@ -368,6 +424,14 @@ async fn test_clone_10x_benchmark() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_stopped_box(&runtime).await;
@@ -414,6 +478,14 @@ async fn test_export_under_write_pressure() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let source = create_running_box(&runtime, "write-stress").await;
@@ -0,0 +1,157 @@
+//! Integration tests for committing a box as a local image.
+//!
+//! Verifies that `LiteBox::commit` captures rootfs changes in a new image
+//! that `RootfsSpec::Image` can boot, both from a full rootfs diff and from
+//! an overlay upper disk.
+
+mod common;
+
+use boxlite::{BoxCommand, BoxOptions, BoxliteRuntime, CommitOptions, DiskSpec, RootfsSpec};
+use futures::StreamExt;
+use tempfile::TempDir;
+
+const COMMITTED_REF: &str = "boxlite-test/committed:latest";
+const UPPER_COMMITTED_REF: &str = "boxlite-test/committed-upper:latest";
+const DATA_DISK_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Boot `reference` and check it has `/committed.txt` and no `/etc/motd`.
+async fn assert_committed_image(runtime: &BoxliteRuntime, reference: &str) {
+    let opts = BoxOptions {
+        rootfs: RootfsSpec::Image(reference.into()),
+        ..common::alpine_opts()
+    };
+    let derived = runtime.create(opts, None).await.unwrap();
+    derived.start().await.unwrap();
+
+    let mut execution = derived
+        .exec(BoxCommand::new("sh").args(["-c", "cat /committed.txt && test ! -e /etc/motd"]))
+        .await
+        .expect("exec failed");
+    let mut output = String::new();
+    if let Some(mut stdout) = execution.stdout() {
+        while let Some(chunk) = stdout.next().await {
+            output.push_str(&chunk);
+        }
+    }
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(
+        result.exit_code, 0,
+        "file missing or deletion not committed"
+    );
+    assert_eq!(output.trim(), "committed");
+
+    derived.stop().await.unwrap();
+    let _ = runtime.remove(derived.id().as_str(), true).await;
+}
+
+#[tokio::test]
+async fn committed_file_persists_in_new_box() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let source = runtime.create(common::alpine_opts(), None).await.unwrap();
+    source.start().await.unwrap();
+
+    let mut execution = source
+        .exec(
+            BoxCommand::new("sh")
+                .args(["-c", "echo committed > /committed.txt && rm -f /etc/motd"]),
+        )
+        .await
+        .expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0);
+
+    let info = source
+        .commit(
+            COMMITTED_REF,
+            CommitOptions {
+                message: Some("add committed.txt".into()),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("commit failed");
+    assert!(
+        info.id.starts_with("sha256:"),
+        "unexpected image id {}",
+        info.id
+    );
+    source.stop().await.unwrap();
+
+    assert_committed_image(&runtime, COMMITTED_REF).await;
+
+    let _ = runtime.remove(source.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
+
+#[tokio::test]
+async fn committed_upper_layer_persists_in_new_box() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let disk_dir = TempDir::new_in("/tmp").unwrap();
+    let data_disk = disk_dir.path().join("data.img");
+    std::fs::File::create(&data_disk)
+        .unwrap()
+        .set_len(DATA_DISK_BYTES)
+        .unwrap();
+
+    let opts = BoxOptions {
+        disks: vec![DiskSpec {
+            id: "data".into(),
+            host_path: data_disk.to_string_lossy().into_owned(),
+            read_only: false,
+            passthrough: false,
+        }],
+        overlay_upper_disk: Some("data".into()),
+        ..common::alpine_opts()
+    };
+    let source = runtime.create(opts, None).await.unwrap();
+    source.start().await.unwrap();
+
+    let mut execution = source
+        .exec(
+            BoxCommand::new("sh")
+                .args(["-c", "echo committed > /committed.txt && rm -f /etc/motd"]),
+        )
+        .await
+        .expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0);
+
+    source
+        .commit(UPPER_COMMITTED_REF, CommitOptions::default())
+        .await
+        .expect("commit failed");
+    source.stop().await.unwrap();
+
+    assert_committed_image(&runtime, UPPER_COMMITTED_REF).await;
+
+    let _ = runtime.remove(source.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
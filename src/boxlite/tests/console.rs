@@ -0,0 +1,45 @@
+//! Integration tests for `LiteBox::console_logs`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test console -- --test-threads=1`
+
+mod common;
+
+use boxlite::runtime::options::{BoxOptions, RootfsSpec};
+use common::box_test::BoxTestBase;
+
+#[tokio::test]
+async fn console_logs_contain_boot_output() {
+    let t = BoxTestBase::new().await;
+
+    let logs = t.bx.console_logs(1000).unwrap();
+    assert!(!logs.is_empty(), "console ring is empty after boot");
+    assert!(
+        logs.iter().any(|line| line.contains("[guest]")),
+        "no guest agent boot output in console ring: {logs:#?}"
+    );
+
+    let tail = t.bx.console_logs(3).unwrap();
+    assert_eq!(tail.len(), 3);
+    assert_eq!(tail[..], logs[logs.len() - 3..]);
+}
+
+#[tokio::test]
+async fn console_ring_is_bounded_by_options() {
+    let t = BoxTestBase::with_options(BoxOptions {
+        rootfs: RootfsSpec::Image("alpine:latest".into()),
+        auto_remove: false,
+        console_ring_lines: Some(5),
+        ..Default::default()
+    })
+    .await;
+    t.bx.start().await.unwrap();
+
+    // The file holds under twice the ring between compactions.
+    let logs = t.bx.console_logs(1000).unwrap();
+    assert!(!logs.is_empty());
+    assert!(logs.len() < 10, "console kept {} lines", logs.len());
+}
@@ -54,6 +54,14 @@ async fn copy_integration() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let bx = runtime
@@ -82,6 +90,7 @@ async fn copy_integration() {
     follow_symlinks_true_dereferences(&bx, tmp.path()).await;
     include_parent_true_nests_dir(&bx, tmp.path()).await;
     include_parent_false_flattens(&bx, tmp.path()).await;
+    mode_and_mtime_roundtrip(&bx, tmp.path()).await;
     copy_in_creates_intermediate_dirs(&bx, tmp.path()).await;
     copy_out_nonexistent_errors(&bx, tmp.path()).await;
     concurrent_copy_roundtrip(&bx, tmp.path()).await;
@@ -458,6 +467,73 @@ async fn include_parent_false_flattens(bx: &LiteBox, tmp: &Path) {
     assert_eq!(out, "flat\n");
 }
 
+// ============================================================================
+// METADATA
+// ============================================================================
+
+async fn mode_and_mtime_roundtrip(bx: &LiteBox, tmp: &Path) {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+    eprintln!("  [copy] mode_and_mtime_roundtrip");
+    let mtime = std::time::UNIX_EPOCH + std::time::Duration::from_secs(1_600_000_000);
+    let set_meta = |path: &Path, mode: u32| {
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode)).unwrap();
+        std::fs::File::open(path)
+            .unwrap()
+            .set_modified(mtime)
+            .unwrap();
+    };
+
+    let src = tmp.join("meta");
+    std::fs::create_dir_all(src.join("private")).unwrap();
+    std::fs::write(src.join("run.sh"), "#!/bin/sh\n").unwrap();
+    std::fs::write(src.join("private/key"), "secret\n").unwrap();
+    set_meta(&src.join("run.sh"), 0o755);
+    set_meta(&src.join("private/key"), 0o600);
+    set_meta(&src.join("private"), 0o700);
+
+    bx.copy_into(&src, "/root", CopyOptions::default())
+        .await
+        .expect("copy_into metadata tree failed");
+
+    let out = exec_stdout(
+        bx,
+        BoxCommand::new("stat").args([
+            "-c",
+            "%n %a %Y",
+            "/root/meta/run.sh",
+            "/root/meta/private",
+            "/root/meta/private/key",
+        ]),
+    )
+    .await;
+    assert_eq!(
+        out,
+        "/root/meta/run.sh 755 1600000000\n\
+         /root/meta/private 700 1600000000\n\
+         /root/meta/private/key 600 1600000000\n"
+    );
+
+    let dst = tmp.join("meta-out");
+    std::fs::create_dir(&dst).unwrap();
+    bx.copy_out("/root/meta", &dst, CopyOptions::default())
+        .await
+        .expect("copy_out metadata tree failed");
+
+    for (rel, mode) in [
+        ("meta/run.sh", 0o755),
+        ("meta/private", 0o700),
+        ("meta/private/key", 0o600),
+    ] {
+        let meta = std::fs::metadata(dst.join(rel)).unwrap();
+        assert_eq!(meta.mode() & 0o7777, mode, "mode of {rel}");
+        assert_eq!(meta.mtime(), 1_600_000_000, "mtime of {rel}");
+    }
+    assert_eq!(
+        std::fs::read_to_string(dst.join("meta/private/key")).unwrap(),
+        "secret\n"
+    );
+}
+
 // ============================================================================
 // ERROR / EDGE CASES
 // ============================================================================
@@ -74,6 +74,14 @@ async fn detached_box_creates_pid_file() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -108,6 +116,14 @@ async fn detached_box_survives_runtime_drop() {
         let runtime = BoxliteRuntime::new(BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .unwrap();
 
@@ -145,6 +161,14 @@ async fn detached_box_survives_runtime_drop() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
     runtime.remove(&box_id, true).await.unwrap();
@@ -167,6 +191,14 @@ async fn non_detached_box_exits_on_runtime_drop() {
         let runtime = BoxliteRuntime::new(BoxliteOptions {
             home_dir: home_dir.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .unwrap();
 
@@ -213,6 +245,14 @@ async fn multiple_detached_boxes_each_have_pid_file() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -268,6 +308,14 @@ async fn detached_box_recoverable_after_restart() {
         let runtime = BoxliteRuntime::new(BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .unwrap();
 
@@ -291,6 +339,14 @@ async fn detached_box_recoverable_after_restart() {
         let runtime = BoxliteRuntime::new(BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .unwrap();
 
@@ -339,6 +395,14 @@ async fn detached_box_force_remove_reaps_whole_tree() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -383,6 +447,14 @@ async fn detached_box_stop_reaps_whole_tree_and_keeps_box() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
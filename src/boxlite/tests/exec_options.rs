@@ -1,13 +1,16 @@
 //! Integration tests for per-exec working_dir and timeout options.
 //!
 //! Verifies that `BoxCommand::working_dir()` and `BoxCommand::timeout()`
-//! correctly affect command execution inside the VM guest.
+//! correctly affect command execution inside the VM guest, that the box-level
+//! `working_dir`/`env` defaults apply unless a command overrides them, that
+//! exec'd processes see shell-like SIGPIPE behavior, and that a slow reader
+//! loses no output.
 
 mod common;
 
 use std::time::Duration;
 
-use boxlite::BoxCommand;
+use boxlite::{BoxCommand, BoxliteError};
 use tokio_stream::StreamExt;
 
 /// Helper: run a command, collect stdout, assert exit code 0.
@@ -35,13 +38,25 @@ struct TestBox {
 
 impl TestBox {
     async fn new() -> Self {
+        Self::with_options(common::alpine_opts()).await
+    }
+
+    async fn with_options(options: boxlite::runtime::options::BoxOptions) -> Self {
         let home = boxlite_test_utils::home::PerTestBoxHome::new();
         let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .expect("create runtime");
-        let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+        let handle = runtime.create(options, None).await.unwrap();
         handle.start().await.unwrap();
         Self {
             handle,
@@ -69,7 +84,123 @@ async fn test_working_dir() {
     tb.teardown().await;
 }
 
-/// timeout kills a long-running command.
+/// A command without a working_dir runs in the box's default working_dir.
+#[tokio::test]
+async fn test_box_default_working_dir() {
+    let tb = TestBox::with_options(boxlite::runtime::options::BoxOptions {
+        working_dir: Some("/tmp".into()),
+        ..common::alpine_opts()
+    })
+    .await;
+
+    let stdout = run_stdout(&tb.handle, BoxCommand::new("pwd")).await;
+    assert_eq!(
+        stdout.trim(),
+        "/tmp",
+        "box working_dir should be the default"
+    );
+
+    let stdout = run_stdout(&tb.handle, BoxCommand::new("pwd").working_dir("/")).await;
+    assert_eq!(stdout.trim(), "/", "command working_dir should win");
+
+    tb.teardown().await;
+}
+
+/// Box-level env reaches every command; a command's own env overrides it.
+#[tokio::test]
+async fn test_command_env_overrides_box_default() {
+    let tb = TestBox::with_options(boxlite::runtime::options::BoxOptions {
+        env: vec![
+            ("GREETING".into(), "box".into()),
+            ("TARGET".into(), "world".into()),
+        ],
+        ..common::alpine_opts()
+    })
+    .await;
+
+    let echo = || BoxCommand::new("sh").args(["-c", "echo $GREETING $TARGET"]);
+
+    let stdout = run_stdout(&tb.handle, echo()).await;
+    assert_eq!(stdout.trim(), "box world");
+
+    let stdout = run_stdout(&tb.handle, echo().env("GREETING", "command")).await;
+    assert_eq!(stdout.trim(), "command world");
+
+    tb.teardown().await;
+}
+
+/// A box's `nofile` ulimit is what its commands see.
+#[tokio::test]
+async fn test_ulimit_nofile_applies_in_exec() {
+    let tb = TestBox::with_options(boxlite::runtime::options::BoxOptions {
+        ulimits: vec![boxlite::Ulimit::new("nofile", 1024, 2048)],
+        ..common::alpine_opts()
+    })
+    .await;
+
+    let stdout = run_stdout(
+        &tb.handle,
+        BoxCommand::new("sh").args(["-c", "ulimit -n; ulimit -Hn"]),
+    )
+    .await;
+    let limits: Vec<&str> = stdout.lines().map(str::trim).collect();
+    assert_eq!(limits, ["1024", "2048"]);
+
+    tb.teardown().await;
+}
+
+/// A producer piped into `head` is killed by SIGPIPE, as under a shell,
+/// instead of ignoring it and failing with EPIPE.
+#[tokio::test]
+async fn test_pipe_producer_exits_via_sigpipe() {
+    let tb = TestBox::new().await;
+    // Record the producer's exit status: 141 (128 + SIGPIPE) when the signal
+    // kills it, 1 when it sees EPIPE and gives up.
+    let stdout = run_stdout(
+        &tb.handle,
+        BoxCommand::new("sh").args([
+            "-c",
+            "(yes; echo $? > /tmp/producer_rc) | head -n 1 > /dev/null; cat /tmp/producer_rc",
+        ]),
+    )
+    .await;
+    assert_eq!(
+        stdout.trim(),
+        "141",
+        "producer should be terminated by SIGPIPE, not exit on EPIPE"
+    );
+    tb.teardown().await;
+}
+
+/// A producer far faster than its reader is paused, not truncated.
+#[tokio::test]
+async fn test_slow_reader_receives_all_output() {
+    const TOTAL: usize = 4 * 1024 * 1024;
+    let tb = TestBox::new().await;
+
+    let mut execution = tb
+        .handle
+        .exec(
+            BoxCommand::new("sh")
+                .args(["-c", &format!("head -c {TOTAL} /dev/zero | tr '\\0' x")])
+                .output_buffer_size(64 * 1024),
+        )
+        .await
+        .expect("exec failed");
+
+    let mut received = 0;
+    let mut stream = execution.stdout().unwrap();
+    while let Some(chunk) = stream.next().await {
+        assert!(chunk.bytes().all(|b| b == b'x'));
+        received += chunk.len();
+        tokio::time::sleep(Duration::from_micros(500)).await;
+    }
+    assert_eq!(received, TOTAL);
+    assert_eq!(execution.wait().await.unwrap().exit_code, 0);
+    tb.teardown().await;
+}
+
+/// timeout kills a long-running command and `wait` reports it as a timeout.
 #[tokio::test]
 async fn test_timeout_kills_long_command() {
     let tb = TestBox::new().await;
@@ -84,10 +215,35 @@ async fn test_timeout_kills_long_command() {
         .await
         .expect("exec failed");
 
-    let result = execution.wait().await.expect("wait failed");
-    assert_ne!(
-        result.exit_code, 0,
-        "timed-out command should have non-zero exit code"
+    let err = execution
+        .wait()
+        .await
+        .expect_err("timed-out command should not report an exit status");
+    assert!(matches!(err, BoxliteError::Timeout(_)), "{err:?}");
+
+    tb.teardown().await;
+}
+
+/// The guest reports a timed-out exec as such, with its real run time.
+#[tokio::test]
+async fn test_timeout_reports_timed_out_and_duration() {
+    let tb = TestBox::new().await;
+
+    let execution = tb
+        .handle
+        .exec(
+            BoxCommand::new("sleep")
+                .arg("10")
+                .timeout(Duration::from_millis(500)),
+        )
+        .await
+        .expect("exec failed");
+
+    let result = execution.wait_status().await.expect("wait failed");
+    assert!(result.timed_out, "{result:?}");
+    assert!(
+        (450..2000).contains(&result.duration_ms),
+        "duration_ms should be near 500: {result:?}"
     );
 
     tb.teardown().await;
@@ -101,7 +257,8 @@ async fn test_timeout_kills_long_command() {
 /// the workload below — a shell that installs `trap '' ALRM` and then
 /// sleeps for 15 seconds — absorbs the signal, the underlying `sleep`
 /// runs to its natural end, and exec returns `exit_code=0` after ~15s,
-/// bypassing the 2-second deadline.
+/// bypassing the 2-second deadline. A killed workload surfaces as
+/// `BoxliteError::Timeout` from `wait`.
 ///
 /// The fix lives in `src/guest/src/service/exec/timeout.rs`.
 #[tokio::test]
@@ -119,12 +276,12 @@ async fn test_timeout_kills_sigalrm_ignoring_process() {
         .await
         .expect("exec failed");
 
-    let result = execution.wait().await.expect("wait failed");
+    let result = execution.wait().await;
     let elapsed = start.elapsed();
 
-    assert_ne!(
-        result.exit_code, 0,
-        "timeout bypass: shell exited with exit_code=0 after {elapsed:?} \
+    assert!(
+        matches!(result, Err(BoxliteError::Timeout(_))),
+        "timeout bypass: wait returned {result:?} after {elapsed:?} \
          despite timeout=2s — the guest is sending a catchable signal that \
          the shell absorbs via `trap '' ALRM`; the kill must use SIGKILL"
     );
@@ -166,6 +323,46 @@ async fn test_tty_exec_collects_natural_exit_code() {
     tb.teardown().await;
 }
 
+/// `tty(true)` gives the execution a PTY on stdin; the default does not.
+#[tokio::test]
+async fn test_tty_allocation_is_explicit() {
+    let tb = TestBox::new().await;
+
+    let with_tty = tb
+        .handle
+        .exec(BoxCommand::new("tty").arg("-s").tty(true))
+        .await
+        .expect("tty exec failed to spawn");
+    assert_eq!(with_tty.wait().await.unwrap().exit_code, 0);
+
+    let without_tty = tb
+        .handle
+        .exec(BoxCommand::new("tty").arg("-s"))
+        .await
+        .expect("exec failed to spawn");
+    assert_ne!(without_tty.wait().await.unwrap().exit_code, 0);
+
+    tb.teardown().await;
+}
+
+/// `tty_size` sets the PTY's initial dimensions.
+#[tokio::test]
+async fn test_tty_initial_size() {
+    let tb = TestBox::new().await;
+
+    let stdout = run_stdout(
+        &tb.handle,
+        BoxCommand::new("stty")
+            .arg("size")
+            .tty(true)
+            .tty_size(40, 120),
+    )
+    .await;
+    assert_eq!(stdout.trim(), "40 120");
+
+    tb.teardown().await;
+}
+
 /// Combine working_dir and user in a single command.
 #[tokio::test]
 async fn test_working_dir_with_user() {
@@ -0,0 +1,98 @@
+//! Integration tests for named exec resource profiles.
+//!
+//! Verifies that `BoxCommand::resource_profile()` runs the command under the
+//! cgroup limits of the matching `BoxOptions::exec_profiles` entry, and that
+//! unknown profile names are rejected.
+
+mod common;
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use boxlite::{BoxCommand, BoxOptions, BoxliteError, ExecResourceLimits};
+
+/// Fixed amount of busy work, long enough for CPU throttling to dominate.
+const BUSY_LOOP: &str = "i=0; while [ $i -lt 200000 ]; do i=$((i+1)); done";
+
+async fn timed_busy_loop(handle: &boxlite::LiteBox, cmd: BoxCommand) -> Duration {
+    let started = Instant::now();
+    let mut execution = handle.exec(cmd).await.expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0, "busy loop should exit 0");
+    started.elapsed()
+}
+
+#[tokio::test]
+async fn light_profile_throttles_cpu() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let light = ExecResourceLimits {
+        cpus: Some(0.1),
+        ..Default::default()
+    };
+    let opts = BoxOptions {
+        exec_profiles: HashMap::from([("light".to_string(), light)]),
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    handle.start().await.unwrap();
+
+    let busy = || BoxCommand::new("sh").args(["-c", BUSY_LOOP]);
+    let unthrottled = timed_busy_loop(&handle, busy()).await;
+    let throttled = timed_busy_loop(&handle, busy().resource_profile("light")).await;
+
+    // A 10% cap should make the same work several times slower; 3x leaves
+    // room for exec overhead and scheduler noise.
+    assert!(
+        throttled >= unthrottled * 3,
+        "light profile should throttle: default {unthrottled:?}, light {throttled:?}"
+    );
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
+
+#[tokio::test]
+async fn unknown_profile_is_rejected() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+    let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+    handle.start().await.unwrap();
+
+    let err = handle
+        .exec(BoxCommand::new("true").resource_profile("heavy"))
+        .await
+        .err()
+        .expect("exec with an unknown profile should fail");
+    assert!(matches!(err, BoxliteError::Config(_)), "got {err:?}");
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
@@ -0,0 +1,131 @@
+//! Integration tests for `LiteBox::run`, the capture-everything exec helper.
+//!
+//! Verifies exit codes and both streams are captured, that output larger
+//! than a pipe buffer does not deadlock, and that a timeout kills the
+//! command.
+
+mod common;
+
+use std::time::Duration;
+
+use boxlite::BoxCommand;
+
+/// RAII wrapper that creates/starts a box and cleans up on teardown.
+struct TestBox {
+    handle: boxlite::LiteBox,
+    runtime: boxlite::BoxliteRuntime,
+    _home: boxlite_test_utils::home::PerTestBoxHome,
+}
+
+impl TestBox {
+    async fn new() -> Self {
+        let home = boxlite_test_utils::home::PerTestBoxHome::new();
+        let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+            home_dir: home.path.clone(),
+            image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
+        })
+        .expect("create runtime");
+        let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+        handle.start().await.unwrap();
+        Self {
+            handle,
+            runtime,
+            _home: home,
+        }
+    }
+
+    async fn teardown(self) {
+        self.handle.stop().await.unwrap();
+        let _ = self.runtime.remove(self.handle.id().as_str(), true).await;
+        let _ = self
+            .runtime
+            .shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT))
+            .await;
+    }
+}
+
+#[tokio::test]
+async fn run_captures_nonzero_exit_and_both_streams() {
+    let tb = TestBox::new().await;
+
+    let output = tb
+        .handle
+        .run(BoxCommand::new("sh").args(["-c", "echo out; echo err >&2; exit 7"]))
+        .await
+        .expect("run failed");
+
+    assert_eq!(output.exit_code, 7);
+    assert!(!output.timed_out);
+    assert!(!output.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "out");
+    assert_eq!(String::from_utf8_lossy(&output.stderr).trim(), "err");
+
+    tb.teardown().await;
+}
+
+#[tokio::test]
+async fn run_pipes_stdin_bytes_to_cat() {
+    let tb = TestBox::new().await;
+
+    let output = tb
+        .handle
+        .run(BoxCommand::new("cat").stdin_bytes("hello\n"))
+        .await
+        .expect("run failed");
+
+    assert_eq!(output.exit_code, 0);
+    assert_eq!(output.stdout, b"hello\n");
+
+    tb.teardown().await;
+}
+
+#[tokio::test]
+async fn run_drains_output_larger_than_a_pipe_buffer() {
+    let tb = TestBox::new().await;
+
+    // 256 KiB on each stream, interleaved, well past a 64 KiB pipe buffer.
+    let output = tb
+        .handle
+        .run(BoxCommand::new("sh").args([
+            "-c",
+            "head -c 262144 /dev/zero | tr '\\0' o & head -c 262144 /dev/zero | tr '\\0' e >&2; wait",
+        ]))
+        .await
+        .expect("run failed");
+
+    assert!(output.success(), "exit={}", output.exit_code);
+    assert_eq!(output.stdout.len(), 262144);
+    assert_eq!(output.stderr.len(), 262144);
+
+    tb.teardown().await;
+}
+
+#[tokio::test]
+async fn run_kills_command_on_timeout() {
+    let tb = TestBox::new().await;
+
+    let output = tb
+        .handle
+        .run(
+            BoxCommand::new("sh")
+                .args(["-c", "echo started; exec sleep 60"])
+                .timeout(Duration::from_secs(2)),
+        )
+        .await
+        .expect("run failed");
+
+    assert!(output.timed_out);
+    assert!(!output.success());
+    assert!(output.duration_ms < 30_000, "took {}ms", output.duration_ms);
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "started");
+
+    tb.teardown().await;
+}
@@ -37,6 +37,14 @@ impl TestBox {
         let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .expect("create runtime");
         let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
@@ -116,3 +124,30 @@ async fn test_exec_user_overrides() {
 
     tb.teardown().await;
 }
+
+/// Numeric uid absent from /etc/passwd, with supplementary groups.
+#[tokio::test]
+async fn test_exec_user_with_supplementary_groups() {
+    let tb = TestBox::new().await;
+
+    let stdout = exec_stdout(
+        &tb.handle,
+        BoxCommand::new("id").arg("-u").user("1000:1000"),
+    )
+    .await;
+    assert_eq!(stdout.trim(), "1000");
+
+    let stdout = exec_stdout(
+        &tb.handle,
+        BoxCommand::new("id")
+            .arg("-G")
+            .user("1000:1000")
+            .groups([3000, 4000]),
+    )
+    .await;
+    let mut groups: Vec<&str> = stdout.split_whitespace().collect();
+    groups.sort_unstable();
+    assert_eq!(groups, ["1000", "3000", "4000"], "id -G output: {stdout:?}");
+
+    tb.teardown().await;
+}
@@ -0,0 +1,86 @@
+//! Integration tests for `LiteBox::executions`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test executions -- --test-threads=1`
+
+mod common;
+
+use boxlite::BoxliteRuntime;
+use boxlite::runtime::options::{BoxOptions, BoxliteOptions};
+use boxlite::{BoxCommand, ExecutionInfo};
+
+fn runtime_at(home: &boxlite_test_utils::home::PerTestBoxHome) -> BoxliteRuntime {
+    BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime")
+}
+
+#[tokio::test]
+async fn executions_are_listed_after_reconnect() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let box_id: String;
+    let mut started = Vec::new();
+
+    {
+        let runtime = runtime_at(&home);
+        let handle = runtime
+            .create(
+                BoxOptions {
+                    detach: true,
+                    ..common::alpine_opts()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        handle.start().await.unwrap();
+
+        for secs in ["300", "301"] {
+            let execution = handle
+                .exec(BoxCommand::new("sleep").arg(secs))
+                .await
+                .unwrap();
+            started.push(execution.id().clone());
+        }
+        box_id = handle.id().to_string();
+    }
+
+    // A new runtime has no record of the executions; the guest does.
+    let runtime = runtime_at(&home);
+    let handle = runtime.get(&box_id).await.unwrap().expect("box recovered");
+    let executions = handle.executions().await.unwrap();
+
+    let mut ids: Vec<String> = executions.iter().map(|e| e.id.clone()).collect();
+    ids.sort();
+    started.sort();
+    assert_eq!(ids, started);
+    for ExecutionInfo {
+        pid,
+        program,
+        running,
+        ..
+    } in &executions
+    {
+        assert_eq!(program, "sleep");
+        assert!(*running);
+        assert!(*pid > 0);
+    }
+    assert!(executions[0].started_at <= executions[1].started_at);
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(&box_id, true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
@@ -0,0 +1,40 @@
+//! Integration tests for `LiteBox::export_rootfs`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test export_rootfs -- --test-threads=1`
+
+mod common;
+
+use common::box_test::BoxTestBase;
+
+fn entry_names(tar: &[u8]) -> Vec<String> {
+    let mut archive = tar::Archive::new(tar);
+    archive
+        .entries()
+        .unwrap()
+        .map(|e| e.unwrap().path().unwrap().display().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn export_rootfs_contains_base_image_and_box_files() {
+    let t = BoxTestBase::new().await;
+    t.bx.start().await.unwrap();
+    t.write_file("/root/exported.txt", "from the box").await;
+
+    let mut tar = Vec::new();
+    t.bx.export_rootfs(&mut tar).await.unwrap();
+
+    let names = entry_names(&tar);
+    assert!(
+        names.iter().any(|n| n == "etc/alpine-release"),
+        "base image file missing: {names:?}"
+    );
+    assert!(
+        names.iter().any(|n| n == "root/exported.txt"),
+        "box file missing: {names:?}"
+    );
+}
@@ -0,0 +1,62 @@
+//! Integration tests for single-file volumes.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test file_volume -- --test-threads=1`
+
+mod common;
+
+use boxlite::BoxCommand;
+use boxlite::runtime::options::{BoxOptions, VolumeSpec};
+use common::box_test::BoxTestBase;
+
+#[tokio::test]
+async fn single_file_volume_appears_with_host_contents() {
+    let tmp = tempfile::TempDir::new_in("/tmp").unwrap();
+    let config = tmp.path().join("app.conf");
+    std::fs::write(&config, "listen = 8080\n").unwrap();
+    // A sibling of the mounted file must not leak into the guest.
+    std::fs::write(tmp.path().join("secret.txt"), "host only\n").unwrap();
+
+    let t = BoxTestBase::with_options(BoxOptions {
+        volumes: vec![VolumeSpec {
+            host_path: config.to_str().unwrap().into(),
+            // The parent dir doesn't exist in the image; the target file and
+            // its parents are created for the bind mount.
+            guest_path: "/etc/myapp/app.conf".into(),
+            read_only: true,
+        }],
+        ..common::alpine_opts()
+    })
+    .await;
+
+    let cat =
+        t.bx.run(BoxCommand::new("cat").arg("/etc/myapp/app.conf"))
+            .await
+            .unwrap();
+    assert_eq!(cat.exit_code, 0, "{}", String::from_utf8_lossy(&cat.stderr));
+    assert_eq!(String::from_utf8_lossy(&cat.stdout), "listen = 8080\n");
+
+    let is_file =
+        t.bx.run(BoxCommand::new("test").args(["-f", "/etc/myapp/app.conf"]))
+            .await
+            .unwrap();
+    assert_eq!(
+        is_file.exit_code, 0,
+        "mount target should be a regular file"
+    );
+
+    let ls =
+        t.bx.run(BoxCommand::new("ls").arg("/etc/myapp"))
+            .await
+            .unwrap();
+    assert_eq!(String::from_utf8_lossy(&ls.stdout).trim(), "app.conf");
+
+    let write =
+        t.bx.run(BoxCommand::new("sh").args(["-c", "echo x > /etc/myapp/app.conf"]))
+            .await
+            .unwrap();
+    assert_ne!(write.exit_code, 0, "read-only file volume accepted a write");
+}
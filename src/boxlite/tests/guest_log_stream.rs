@@ -0,0 +1,40 @@
+//! Integration tests for `LiteBox::guest_log_stream`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test guest_log_stream -- --test-threads=1`
+
+mod common;
+
+use std::time::Duration;
+
+use common::box_test::BoxTestBase;
+use futures::StreamExt;
+
+#[tokio::test]
+async fn exec_attach_is_logged_on_guest_stream() {
+    let t = BoxTestBase::new().await;
+    t.bx.start().await.unwrap();
+    let mut logs = t.bx.guest_log_stream().await.unwrap();
+
+    // Every exec attaches to its output, which the agent logs at info.
+    assert_eq!(t.exec_stdout("echo", &["ok"]).await.trim(), "ok");
+
+    let line = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(line) = logs.next().await {
+            if line.message == "attach request" {
+                return Some(line);
+            }
+        }
+        None
+    })
+    .await
+    .expect("no attach log within 10s")
+    .expect("guest log stream ended early");
+
+    assert_eq!(line.level, tracing::Level::INFO);
+    assert!(line.target.starts_with("boxlite_guest"), "{line:?}");
+    assert!(line.fields.contains_key("execution_id"), "{line:?}");
+}
@@ -35,6 +35,8 @@ fn backend_for(
         secrets: Vec::new(),
         ca_cert_pem: None,
         ca_key_pem: None,
+        rate_limit: None,
+        guest_mac: None,
     };
     let (instance, endpoint) = GvproxyInstance::from_config(&spec).expect("create gvproxy");
     let config = NetworkBackendConfig {
@@ -43,6 +45,8 @@ fn backend_for(
         allow_net: Vec::new(),
         secrets: Vec::new(),
         ca_dir: dir.path().to_path_buf(),
+        rate_limit: None,
+        guest_mac: None,
     };
     (
         instance,
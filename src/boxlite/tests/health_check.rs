@@ -8,7 +8,8 @@
 
 mod common;
 
-use boxlite::litebox::HealthState;
+use boxlite::BoxCommand;
+use boxlite::litebox::{HealthProbe, HealthState};
 use boxlite::runtime::advanced_options::{AdvancedBoxOptions, HealthCheckOptions};
 use boxlite::runtime::options::{BoxOptions, RootfsSpec};
 use boxlite::runtime::types::BoxStatus;
@@ -161,3 +162,56 @@ async fn health_check_becomes_unhealthy_when_shim_killed() {
         .await
         .expect("force remove at teardown");
 }
+
+// ============================================================================
+// READINESS PROBES (LiteBox::healthcheck)
+// ============================================================================
+
+fn exec_probe(program: &str, retries: u32) -> HealthProbe {
+    HealthProbe::Exec {
+        cmd: BoxCommand::new(program),
+        interval: Duration::from_millis(200),
+        retries,
+    }
+}
+
+#[tokio::test]
+async fn exec_probe_reports_healthy_for_true() {
+    let t = BoxTestBase::new().await;
+
+    let state = t.bx.healthcheck(exec_probe("true", 3)).await.unwrap();
+    assert_eq!(state, HealthState::Healthy);
+}
+
+#[tokio::test]
+async fn exec_probe_reports_unhealthy_after_retries_for_false() {
+    let t = BoxTestBase::new().await;
+
+    // Count attempts through a file so exhaustion is observable.
+    let probe = HealthProbe::Exec {
+        cmd: BoxCommand::new("sh").args(["-c", "echo x >> /tmp/probe-attempts; false"]),
+        interval: Duration::from_millis(200),
+        retries: 3,
+    };
+    let state = t.bx.healthcheck(probe).await.unwrap();
+    assert_eq!(state, HealthState::Unhealthy);
+
+    let output =
+        t.bx.run(BoxCommand::new("wc").args(["-l", "/tmp/probe-attempts"]))
+            .await
+            .unwrap();
+    let attempts = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        attempts.trim_start().starts_with('3'),
+        "expected 3 attempts, got {attempts:?}"
+    );
+}
+
+#[tokio::test]
+async fn probe_on_unstarted_box_reports_starting() {
+    let t = BoxTestBase::with_options(common::alpine_opts()).await;
+
+    let state = t.bx.healthcheck(exec_probe("true", 1)).await.unwrap();
+    assert_eq!(state, HealthState::Starting);
+    assert_eq!(t.bx.info().status, BoxStatus::Configured);
+}
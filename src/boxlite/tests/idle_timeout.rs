@@ -0,0 +1,42 @@
+//! Integration tests for `BoxOptions::idle_timeout_secs`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test idle_timeout -- --test-threads=1`
+
+mod common;
+
+use boxlite::runtime::types::BoxStatus;
+use common::box_test::BoxTestBase;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn idle_box_stops_itself() {
+    let t = BoxTestBase::with_options(boxlite::BoxOptions {
+        idle_timeout_secs: Some(3),
+        ..common::alpine_opts()
+    })
+    .await;
+    t.bx.start().await.unwrap();
+
+    // Execs inside the window keep resetting it.
+    for _ in 0..3 {
+        assert_eq!(t.exec_stdout("echo", &["ok"]).await.trim(), "ok");
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+    assert_eq!(t.bx.info().status, BoxStatus::Running);
+
+    // With no execs the box stops once the window (plus a poll) has passed.
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while t.bx.info().status != BoxStatus::Stopped {
+        assert!(
+            Instant::now() < deadline,
+            "box still {:?} long after its idle timeout",
+            t.bx.info().status
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    assert!(t.bx.info().pid.is_none());
+}
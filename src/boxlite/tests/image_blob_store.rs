@@ -0,0 +1,141 @@
+//! Integration tests for `BoxliteOptions::image_blob_store`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test image_blob_store -- --test-threads=1`
+
+mod common;
+
+use async_trait::async_trait;
+use boxlite::runtime::options::BoxliteOptions;
+use boxlite::{
+    BoxCommand, BoxliteResult, BoxliteRuntime, ImageBlobStore, ImageStorage, StagedDownload,
+    StoredBlob,
+};
+use oci_client::manifest::OciManifest;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Filesystem store in its own directory that counts downloads and reads.
+#[derive(Debug)]
+struct CountingStore {
+    inner: ImageStorage,
+    staged: AtomicUsize,
+    layer_reads: AtomicUsize,
+}
+
+#[async_trait]
+impl ImageBlobStore for CountingStore {
+    fn save_manifest(&self, manifest: &OciManifest, digest: &str) -> BoxliteResult<()> {
+        self.inner.save_manifest(manifest, digest)
+    }
+    fn load_manifest(&self, digest: &str) -> BoxliteResult<OciManifest> {
+        self.inner.load_manifest(digest)
+    }
+    fn has_manifest(&self, digest: &str) -> bool {
+        self.inner.has_manifest(digest)
+    }
+    fn manifest_path(&self, digest: &str) -> PathBuf {
+        self.inner.manifest_path(digest)
+    }
+    fn has_layer(&self, digest: &str) -> bool {
+        self.inner.has_layer(digest)
+    }
+    async fn verify_layer(&self, digest: &str) -> BoxliteResult<bool> {
+        self.inner.verify_layer(digest).await
+    }
+    fn layer_tarball_path(&self, digest: &str) -> PathBuf {
+        self.layer_reads.fetch_add(1, Ordering::SeqCst);
+        self.inner.layer_tarball_path(digest)
+    }
+    fn layer_extracted_path(&self, digest: &str) -> PathBuf {
+        self.inner.layer_extracted_path(digest)
+    }
+    fn extract_layer(&self, digest: &str, tarball_path: &Path) -> BoxliteResult<()> {
+        self.inner.extract_layer(digest, tarball_path)
+    }
+    async fn stage_layer_download(
+        &self,
+        digest: &str,
+        expected_size: i64,
+    ) -> BoxliteResult<StagedDownload> {
+        self.staged.fetch_add(1, Ordering::SeqCst);
+        self.inner.stage_layer_download(digest, expected_size).await
+    }
+    fn has_config(&self, digest: &str) -> bool {
+        self.inner.has_config(digest)
+    }
+    fn config_path(&self, digest: &str) -> PathBuf {
+        self.inner.config_path(digest)
+    }
+    async fn stage_config_download(&self, digest: &str) -> BoxliteResult<StagedDownload> {
+        self.staged.fetch_add(1, Ordering::SeqCst);
+        self.inner.stage_config_download(digest).await
+    }
+    fn disk_image_path(&self, image_digest: &str) -> PathBuf {
+        self.inner.disk_image_path(image_digest)
+    }
+    fn list_blobs(&self) -> BoxliteResult<Vec<StoredBlob>> {
+        self.inner.list_blobs()
+    }
+    fn local_bundle_cache_dir(&self, bundle_path: &Path, manifest_digest: &str) -> PathBuf {
+        self.inner
+            .local_bundle_cache_dir(bundle_path, manifest_digest)
+    }
+}
+
+#[tokio::test]
+async fn pull_and_boot_go_through_custom_store() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let store_dir = home.path.join("custom-blobs");
+    let store = Arc::new(CountingStore {
+        inner: ImageStorage::new(store_dir.clone()).unwrap(),
+        staged: AtomicUsize::new(0),
+        layer_reads: AtomicUsize::new(0),
+    });
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: Some(store.clone()),
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+    handle.start().await.unwrap();
+    let result = handle
+        .exec(BoxCommand::new("true"))
+        .await
+        .expect("exec failed")
+        .wait()
+        .await
+        .expect("wait failed");
+    assert_eq!(result.exit_code, 0);
+
+    assert!(
+        store.staged.load(Ordering::SeqCst) > 0,
+        "pull should download into the custom store"
+    );
+    assert!(
+        store.layer_reads.load(Ordering::SeqCst) > 0,
+        "boot should read layers from the custom store"
+    );
+    assert!(
+        !store.list_blobs().unwrap().is_empty(),
+        "blobs should land under {}",
+        store_dir.display()
+    );
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
@@ -0,0 +1,122 @@
+//! Integration tests for `ImageHandle::import_rootfs`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test image_import -- --test-threads=1`
+
+mod common;
+
+use boxlite::{BoxCommand, BoxOptions, BoxliteRuntime, ImportSource, RootfsSpec};
+use futures::StreamExt;
+use tempfile::TempDir;
+
+const IMPORTED_REF: &str = "boxlite-test/imported:latest";
+
+#[tokio::test]
+async fn imported_directory_boots_with_its_files() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    // Unpack an alpine rootfs into a plain directory to import from.
+    let source = runtime.create(common::alpine_opts(), None).await.unwrap();
+    source.start().await.unwrap();
+    let mut tar = Vec::new();
+    source.export_rootfs(&mut tar).await.unwrap();
+    source.stop().await.unwrap();
+    let _ = runtime.remove(source.id().as_str(), true).await;
+
+    let rootfs = TempDir::new().unwrap();
+    tar::Archive::new(tar.as_slice())
+        .unpack(rootfs.path())
+        .unwrap();
+    std::fs::write(rootfs.path().join("imported.txt"), "imported\n").unwrap();
+
+    let info = runtime
+        .images()
+        .unwrap()
+        .import_rootfs(
+            ImportSource::Directory(rootfs.path().to_path_buf()),
+            IMPORTED_REF,
+        )
+        .await
+        .expect("import failed");
+    assert!(info.id.starts_with("sha256:"), "{info:?}");
+    let images = runtime.images().unwrap().list().await.unwrap();
+    assert!(
+        images.iter().any(|i| i.id == info.id),
+        "imported image not listed: {images:?}"
+    );
+
+    let imported = runtime
+        .create(
+            BoxOptions {
+                rootfs: RootfsSpec::Image(IMPORTED_REF.into()),
+                ..common::alpine_opts()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    imported.start().await.unwrap();
+
+    let mut execution = imported
+        .exec(BoxCommand::new("cat").arg("/imported.txt"))
+        .await
+        .expect("exec failed");
+    let mut output = String::new();
+    if let Some(mut stdout) = execution.stdout() {
+        while let Some(chunk) = stdout.next().await {
+            output.push_str(&chunk);
+        }
+    }
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0);
+    assert_eq!(output.trim(), "imported");
+
+    imported.stop().await.unwrap();
+    let _ = runtime.remove(imported.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
+
+#[tokio::test]
+async fn import_rejects_missing_source() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let err = runtime
+        .images()
+        .unwrap()
+        .import_rootfs(
+            ImportSource::Tarball(home.path.join("missing.tar")),
+            IMPORTED_REF,
+        )
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not a file"), "{err}");
+}
@@ -12,6 +12,14 @@ async fn structured_image_registries_pull_unqualified_image() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -39,6 +47,14 @@ fn structured_image_registries_validate_at_runtime_start() {
     let result = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: vec![ImageRegistry::https("https://registry.local")],
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     });
 
     assert!(result.is_err());
@@ -0,0 +1,87 @@
+//! Integration tests for loading guest kernel modules at boot.
+//!
+//! Verifies that `BoxOptions::kernel_modules` are loaded during guest init
+//! and that a module missing from the guest rootfs fails the start.
+
+mod common;
+
+use boxlite::{BoxCommand, BoxOptions};
+
+/// Module shipped with the guest kernel build.
+const TEST_MODULE: &str = "tun";
+
+#[tokio::test]
+async fn requested_module_is_loaded() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let opts = BoxOptions {
+        kernel_modules: vec![TEST_MODULE.into()],
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    handle.start().await.unwrap();
+
+    // Loadable modules are listed in /proc/modules; built-in ones only
+    // appear under /sys/module.
+    let mut execution = handle
+        .exec(BoxCommand::new("sh").args([
+            "-c",
+            &format!("grep -qw ^{TEST_MODULE} /proc/modules || test -d /sys/module/{TEST_MODULE}"),
+        ]))
+        .await
+        .expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0, "{TEST_MODULE} should be loaded");
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
+
+#[tokio::test]
+async fn missing_module_fails_start() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let opts = BoxOptions {
+        kernel_modules: vec!["boxlite_no_such_module".into()],
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    let err = handle
+        .start()
+        .await
+        .expect_err("start should fail for a missing module");
+    assert!(
+        err.to_string().contains("boxlite_no_such_module"),
+        "error should name the missing module: {err}"
+    );
+
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
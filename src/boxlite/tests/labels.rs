@@ -0,0 +1,142 @@
+//! Integration tests for box labels and `list_info_filtered`.
+//!
+//! Boxes are only created, never started, so no VM is needed.
+
+mod common;
+
+use std::collections::HashMap;
+
+use boxlite::runtime::options::{BoxOptions, BoxliteOptions, RootfsSpec};
+use boxlite::runtime::types::BoxStatus;
+use boxlite::{BoxFilter, BoxliteRuntime};
+use common::home::PerTestBoxHome;
+
+fn runtime_at(home: &PerTestBoxHome) -> BoxliteRuntime {
+    BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .unwrap()
+}
+
+fn labeled(image: &str, labels: &[(&str, &str)]) -> BoxOptions {
+    BoxOptions {
+        rootfs: RootfsSpec::Image(image.into()),
+        auto_remove: false,
+        labels: labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>(),
+        ..Default::default()
+    }
+}
+
+async fn names(runtime: &BoxliteRuntime, filter: BoxFilter) -> Vec<String> {
+    let mut names: Vec<String> = runtime
+        .list_info_filtered(filter)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter_map(|info| info.name)
+        .collect();
+    names.sort();
+    names
+}
+
+fn label(key: &str, value: Option<&str>) -> BoxFilter {
+    BoxFilter {
+        labels: vec![(key.to_string(), value.map(str::to_string))],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn filters_select_boxes_by_label_status_and_image() {
+    let home = PerTestBoxHome::isolated_in("/tmp");
+    let runtime = runtime_at(&home);
+
+    for (name, opts) in [
+        ("web", labeled("alpine:latest", &[("team", "web")])),
+        (
+            "ci",
+            labeled("alpine:latest", &[("team", "infra"), ("tier", "ci")]),
+        ),
+        ("db", labeled("postgres:16", &[("team", "infra")])),
+        ("bare", labeled("alpine:latest", &[])),
+    ] {
+        runtime.create(opts, Some(name.into())).await.unwrap();
+    }
+
+    assert_eq!(
+        names(&runtime, BoxFilter::default()).await,
+        ["bare", "ci", "db", "web"]
+    );
+    assert_eq!(
+        names(&runtime, label("team", Some("infra"))).await,
+        ["ci", "db"]
+    );
+    assert_eq!(
+        names(&runtime, label("team", None)).await,
+        ["ci", "db", "web"]
+    );
+    assert_eq!(names(&runtime, label("tier", Some("ci"))).await, ["ci"]);
+    assert!(names(&runtime, label("team", Some("ops"))).await.is_empty());
+
+    let infra_alpine = BoxFilter {
+        labels: vec![("team".into(), Some("infra".into()))],
+        image: Some("alpine:latest".into()),
+        ..Default::default()
+    };
+    assert_eq!(names(&runtime, infra_alpine).await, ["ci"]);
+
+    let configured = BoxFilter {
+        status: vec![BoxStatus::Configured],
+        ..Default::default()
+    };
+    assert_eq!(names(&runtime, configured).await.len(), 4);
+    let running = BoxFilter {
+        status: vec![BoxStatus::Running],
+        ..Default::default()
+    };
+    assert!(names(&runtime, running).await.is_empty());
+
+    runtime
+        .shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn labels_persist_across_runtime_restart() {
+    let home = PerTestBoxHome::isolated_in("/tmp");
+    {
+        let runtime = runtime_at(&home);
+        runtime
+            .create(
+                labeled("alpine:latest", &[("team", "infra")]),
+                Some("kept".into()),
+            )
+            .await
+            .unwrap();
+        runtime
+            .shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT))
+            .await
+            .unwrap();
+    }
+
+    let runtime = runtime_at(&home);
+    let info = runtime.get_info("kept").await.unwrap().unwrap();
+    assert_eq!(info.labels.get("team").map(String::as_str), Some("infra"));
+    assert_eq!(
+        names(&runtime, label("team", Some("infra"))).await,
+        ["kept"]
+    );
+}
@@ -17,6 +17,14 @@ async fn runtime_initialization_creates_empty_list() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     assert!(runtime.list_info().await.unwrap().is_empty());
@@ -32,6 +40,14 @@ async fn create_generates_unique_ids() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let box1 = runtime.create(common::alpine_opts(), None).await.unwrap();
@@ -65,6 +81,14 @@ async fn create_stores_custom_options() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime.create(options, None).await.unwrap();
@@ -92,6 +116,14 @@ async fn list_info_returns_all_boxes() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -123,6 +155,14 @@ async fn list_info_sorted_by_creation_time_newest_first() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -160,6 +200,14 @@ async fn get_info_returns_box_metadata() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -188,6 +236,14 @@ async fn get_info_returns_none_for_nonexistent() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let missing = runtime.get_info("nonexistent-id").await.unwrap();
@@ -200,6 +256,14 @@ async fn exists_returns_true_for_existing_box() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -220,6 +284,14 @@ async fn exists_returns_false_for_nonexistent() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     assert!(!runtime.exists("nonexistent-id").await.unwrap());
@@ -235,6 +307,14 @@ async fn remove_nonexistent_returns_not_found() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let result = runtime.remove("nonexistent-id", false).await;
@@ -254,6 +334,14 @@ async fn remove_stopped_box_succeeds() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
@@ -275,6 +363,14 @@ async fn remove_active_without_force_fails() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -314,6 +410,14 @@ async fn remove_active_with_force_stops_and_removes() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -343,6 +447,14 @@ async fn remove_deletes_box_from_database() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -371,6 +483,14 @@ async fn stop_marks_box_as_stopped() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
@@ -391,6 +511,54 @@ async fn stop_marks_box_as_stopped() {
     let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
 }
 
+#[tokio::test]
+async fn subscribe_reports_create_start_and_stop() {
+    use futures::StreamExt;
+
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+    let mut events = Box::pin(runtime.subscribe().unwrap());
+
+    let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+    let box_id = handle.id().clone();
+    handle.start().await.unwrap();
+    handle.stop().await.unwrap();
+
+    let mut seen = Vec::new();
+    while seen.last().map(|(_, new)| *new) != Some(BoxStatus::Stopped) {
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.next())
+            .await
+            .expect("timed out waiting for box event")
+            .expect("event stream ended");
+        assert_eq!(event.box_id, box_id);
+        seen.push((event.old_state, event.new_state));
+    }
+    assert_eq!(
+        seen,
+        vec![
+            (None, BoxStatus::Configured),
+            (Some(BoxStatus::Configured), BoxStatus::Running),
+            (Some(BoxStatus::Running), BoxStatus::Stopped),
+        ]
+    );
+
+    // Cleanup
+    runtime.remove(box_id.as_str(), false).await.unwrap();
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
+
 // ============================================================================
 // LITEBOX INFO TESTS
 // ============================================================================
@@ -401,6 +569,14 @@ async fn litebox_info_returns_correct_metadata() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -434,12 +610,28 @@ async fn multiple_runtimes_are_isolated() {
     let runtime1 = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home1.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let home2 = boxlite_test_utils::home::PerTestBoxHome::isolated();
     let runtime2 = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home2.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -481,6 +673,14 @@ async fn boxes_persist_across_runtime_restart() {
         let options = BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         };
         let runtime = BoxliteRuntime::new(options).expect("Failed to create runtime");
         let litebox = runtime.create(common::alpine_opts(), None).await.unwrap();
@@ -500,6 +700,14 @@ async fn boxes_persist_across_runtime_restart() {
         let options = BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         };
         let runtime = BoxliteRuntime::new(options).expect("Failed to create runtime");
 
@@ -530,6 +738,14 @@ async fn multiple_boxes_persist_and_recover_without_lock_errors() {
         let options = BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         };
         let runtime = BoxliteRuntime::new(options).expect("Failed to create runtime");
 
@@ -563,6 +779,14 @@ async fn multiple_boxes_persist_and_recover_without_lock_errors() {
         let options = BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: common::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         };
         let runtime = BoxliteRuntime::new(options).expect("Failed to create runtime after restart");
 
@@ -615,6 +839,14 @@ async fn auto_remove_true_removes_box_on_stop() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime
@@ -642,6 +874,14 @@ async fn auto_remove_false_preserves_box_on_stop() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
     let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
@@ -687,6 +927,14 @@ async fn detach_option_is_stored_in_box_config() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -711,3 +959,18 @@ async fn detach_option_is_stored_in_box_config() {
     // Cleanup
     runtime.remove(box_id.as_str(), true).await.unwrap();
 }
+
+// ============================================================================
+// METRICS TESTS
+// ============================================================================
+
+#[tokio::test]
+async fn metrics_report_rootfs_disk_usage() {
+    let t = common::box_test::BoxTestBase::new().await;
+
+    let metrics = t.bx.metrics().await.unwrap();
+    let total = metrics.disk_bytes_total().expect("disk_bytes_total");
+    let used = metrics.disk_bytes_used().expect("disk_bytes_used");
+    assert!(total > 0, "rootfs filesystem reports zero size");
+    assert!(used > 0 && used <= total, "used={used} total={total}");
+}
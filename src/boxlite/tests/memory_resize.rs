@@ -0,0 +1,59 @@
+//! Integration tests for runtime memory resizing.
+//!
+//! Verifies that `LiteBox::set_memory_mib` rejects targets above the box's
+//! boot memory, and either round-trips through the guest agent or reports
+//! `Unsupported` when the engine has no memory balloon.
+
+mod common;
+
+use boxlite::BoxOptions;
+use boxlite::BoxliteError;
+
+const BOOT_MEMORY_MIB: u32 = 1024;
+
+#[tokio::test]
+async fn set_memory_mib_round_trips_or_is_unsupported() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let opts = BoxOptions {
+        memory_mib: Some(BOOT_MEMORY_MIB),
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    handle.start().await.unwrap();
+
+    let err = handle
+        .set_memory_mib(BOOT_MEMORY_MIB * 2)
+        .await
+        .expect_err("growing past boot memory must fail");
+    assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err:?}");
+
+    let balloon = runtime.engine_features().unwrap().memory_balloon;
+    match handle.set_memory_mib(BOOT_MEMORY_MIB / 2).await {
+        Ok(actual) => {
+            assert!(balloon, "resize succeeded without a memory balloon");
+            assert!(actual <= BOOT_MEMORY_MIB, "guest reported {actual} MiB");
+            let restored = handle.set_memory_mib(BOOT_MEMORY_MIB).await.unwrap();
+            assert!(restored >= actual);
+        }
+        Err(BoxliteError::Unsupported(_)) => assert!(!balloon),
+        Err(e) => panic!("unexpected error: {e:?}"),
+    }
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
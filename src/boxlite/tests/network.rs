@@ -11,6 +11,8 @@ fn test_config(socket_path: PathBuf) -> NetworkBackendConfig {
         allow_net: Vec::new(),
         secrets: Vec::new(),
         ca_dir: PathBuf::from("/tmp/test-ca"),
+        rate_limit: None,
+        guest_mac: None,
     }
 }
 
@@ -26,6 +28,8 @@ fn spec_carries_unique_socket_path_across_serde() {
         secrets: Vec::new(),
         ca_cert_pem: None,
         ca_key_pem: None,
+        rate_limit: None,
+        guest_mac: None,
     };
 
     // socket_path survives serde — this is how it crosses to the shim.
@@ -2,6 +2,7 @@
 
 mod common;
 
+use boxlite::net::RateLimit;
 use boxlite::net::constants::{HOST_HOSTNAME, HOST_IP};
 use boxlite::runtime::options::{BoxOptions, BoxliteOptions, NetworkSpec};
 use boxlite::{BoxCommand, BoxliteRuntime};
@@ -72,6 +73,14 @@ async fn disabled_network_returns_no_network_config() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -90,6 +99,14 @@ async fn disabled_network_runs_without_eth0() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -126,6 +143,14 @@ async fn enabled_network_runs_with_eth0_and_host_alias_dns() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -259,6 +284,14 @@ async fn dns_sinkhole_blocks_unlisted_host() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -289,6 +322,14 @@ async fn dns_sinkhole_allows_listed_host() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -319,6 +360,14 @@ async fn empty_allowlist_allows_all() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -346,6 +395,14 @@ async fn tcp_filter_blocks_direct_ip_connection() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -382,6 +439,14 @@ async fn tcp_filter_sni_allows_https_to_allowed_host() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -417,6 +482,14 @@ async fn host_alias_resolves_to_dedicated_host_ip() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -439,6 +512,14 @@ async fn host_alias_reaches_host_loopback_service() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -465,6 +546,14 @@ async fn host_alias_reaches_host_loopback_service_with_restrictive_allowlist() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -504,6 +593,14 @@ async fn disabled_network_cannot_reach_host_virtual_ip() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .unwrap();
 
@@ -534,3 +631,132 @@ async fn disabled_network_cannot_reach_host_virtual_ip() {
     server.join().unwrap();
     litebox.stop().await.unwrap();
 }
+
+#[tokio::test]
+#[ignore = "requires VM runtime (run with make test)"]
+async fn disabled_network_cannot_reach_external_host() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .unwrap();
+    let command = wget_url_command("http://example.com/");
+
+    // Baseline: a box with the default network reaches the host.
+    let online = runtime.create(common::alpine_opts(), None).await.unwrap();
+    online.start().await.unwrap();
+    let out = run_stdout(&online, "sh", &["-c", &command]).await;
+    assert!(
+        out.contains("EXIT:0"),
+        "default network should reach example.com, got: {out}"
+    );
+    online.stop().await.unwrap();
+
+    let opts = BoxOptions {
+        network: NetworkSpec::Disabled,
+        ..common::alpine_opts()
+    };
+    let offline = runtime.create(opts, None).await.unwrap();
+    offline.start().await.unwrap();
+    let out = run_stdout(&offline, "sh", &["-c", &command]).await;
+    assert!(
+        out.contains("EXIT:") && !out.contains("EXIT:0"),
+        "disabled network should not reach example.com, got: {out}"
+    );
+    offline.stop().await.unwrap();
+}
+
+/// Serve an endless HTTP body of zeros to the first client until it hangs up
+/// or `duration` passes.
+fn start_host_bulk_server(duration: Duration) -> (u16, thread::JoinHandle<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind host bulk server");
+    let port = listener.local_addr().expect("host bulk server addr").port();
+
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().expect("accept host bulk server");
+        let mut request_buf = [0_u8; 1024];
+        let _ = stream.read(&mut request_buf);
+
+        let deadline = Instant::now() + duration;
+        let chunk = [0_u8; 64 * 1024];
+        let _ = stream.write_all(b"HTTP/1.1 200 OK\r\nConnection: close\r\n\r\n");
+        while Instant::now() < deadline {
+            if stream.write_all(&chunk).is_err() {
+                break;
+            }
+        }
+    });
+
+    (port, handle)
+}
+
+#[tokio::test]
+#[ignore = "requires VM runtime (run with make test)"]
+async fn network_rate_limit_caps_ingress_throughput() {
+    const INGRESS_BPS: u64 = 256 * 1024;
+    const WINDOW: Duration = Duration::from_secs(4);
+
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .unwrap();
+
+    let opts = BoxOptions {
+        network_rate_limit: Some(RateLimit {
+            ingress_bps: Some(INGRESS_BPS),
+            egress_bps: None,
+        }),
+        ..common::alpine_opts()
+    };
+    let litebox = runtime.create(opts, None).await.unwrap();
+    litebox.start().await.unwrap();
+
+    let (port, server) = start_host_bulk_server(Duration::from_secs(15));
+    let download = litebox
+        .exec(BoxCommand::new("sh").args([
+            "-c",
+            &format!("wget -q -O /dev/null http://{HOST_IP}:{port}/ || true"),
+        ]))
+        .await
+        .unwrap();
+
+    // Let the transfer ramp up and spend the initial burst.
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let before = litebox.metrics().await.unwrap().network_bytes_sent();
+    tokio::time::sleep(WINDOW).await;
+    let after = litebox.metrics().await.unwrap().network_bytes_sent();
+
+    let (Some(before), Some(after)) = (before, after) else {
+        panic!("network byte counters unavailable: {before:?} -> {after:?}");
+    };
+    let delta = after - before;
+    let ceiling = INGRESS_BPS * WINDOW.as_secs() * 3 / 2;
+    assert!(delta > 0, "no traffic reached the guest");
+    assert!(
+        delta <= ceiling,
+        "{delta} bytes in {WINDOW:?} exceeds {INGRESS_BPS} B/s limit (ceiling {ceiling})"
+    );
+
+    drop(download);
+    litebox.stop().await.unwrap();
+    server.join().unwrap();
+}
@@ -0,0 +1,173 @@
+//! Integration tests for `RootfsSpec::OciBundle`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test oci_bundle -- --test-threads=1`
+
+mod common;
+
+use std::io::Write;
+use std::path::Path;
+
+use boxlite::runtime::options::BoxliteOptions;
+use boxlite::{BoxCommand, BoxOptions, BoxliteRuntime, RootfsSpec};
+use futures::StreamExt;
+use sha2::{Digest, Sha256};
+use tempfile::TempDir;
+
+fn runtime_at(home: &boxlite_test_utils::home::PerTestBoxHome) -> BoxliteRuntime {
+    BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime")
+}
+
+/// Store `data` under `blobs/sha256/` and return its digest.
+fn write_blob(bundle: &Path, data: &[u8]) -> String {
+    let hex = format!("{:x}", Sha256::digest(data));
+    std::fs::write(bundle.join("blobs/sha256").join(&hex), data).unwrap();
+    format!("sha256:{hex}")
+}
+
+/// Write a single-layer OCI image layout of `rootfs` into `bundle`.
+fn write_oci_bundle(rootfs: &Path, bundle: &Path) {
+    std::fs::create_dir_all(bundle.join("blobs/sha256")).unwrap();
+
+    let mut builder = tar::Builder::new(Vec::new());
+    builder.follow_symlinks(false);
+    builder.append_dir_all(".", rootfs).unwrap();
+    let layer_tar = builder.into_inner().unwrap();
+    let diff_id = format!("sha256:{:x}", Sha256::digest(&layer_tar));
+
+    let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::fast());
+    gz.write_all(&layer_tar).unwrap();
+    let layer = gz.finish().unwrap();
+    let layer_digest = write_blob(bundle, &layer);
+
+    let platform = boxlite::runtime::options::Platform::host();
+    let config = serde_json::to_vec(&serde_json::json!({
+        "architecture": platform.arch,
+        "os": platform.os,
+        "config": { "Cmd": ["/bin/sh"] },
+        "rootfs": { "type": "layers", "diff_ids": [diff_id] },
+    }))
+    .unwrap();
+    let config_digest = write_blob(bundle, &config);
+
+    let manifest = serde_json::to_vec(&serde_json::json!({
+        "schemaVersion": 2,
+        "mediaType": "application/vnd.oci.image.manifest.v1+json",
+        "config": {
+            "mediaType": "application/vnd.oci.image.config.v1+json",
+            "digest": config_digest,
+            "size": config.len(),
+        },
+        "layers": [{
+            "mediaType": "application/vnd.oci.image.layer.v1.tar+gzip",
+            "digest": layer_digest,
+            "size": layer.len(),
+        }],
+    }))
+    .unwrap();
+    let manifest_digest = write_blob(bundle, &manifest);
+
+    let index = serde_json::json!({
+        "schemaVersion": 2,
+        "manifests": [{
+            "mediaType": "application/vnd.oci.image.manifest.v1+json",
+            "digest": manifest_digest,
+            "size": manifest.len(),
+        }],
+    });
+    std::fs::write(bundle.join("index.json"), index.to_string()).unwrap();
+    std::fs::write(
+        bundle.join("oci-layout"),
+        r#"{"imageLayoutVersion":"1.0.0"}"#,
+    )
+    .unwrap();
+}
+
+#[tokio::test]
+async fn box_boots_from_oci_bundle_directory() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = runtime_at(&home);
+
+    // Unpack an alpine rootfs and repackage it as an OCI layout.
+    let source = runtime.create(common::alpine_opts(), None).await.unwrap();
+    source.start().await.unwrap();
+    let mut tar = Vec::new();
+    source.export_rootfs(&mut tar).await.unwrap();
+    source.stop().await.unwrap();
+    let _ = runtime.remove(source.id().as_str(), true).await;
+
+    let rootfs = TempDir::new().unwrap();
+    tar::Archive::new(tar.as_slice())
+        .unpack(rootfs.path())
+        .unwrap();
+    std::fs::write(rootfs.path().join("bundle.txt"), "from bundle\n").unwrap();
+    let bundle = TempDir::new().unwrap();
+    write_oci_bundle(rootfs.path(), bundle.path());
+
+    let handle = runtime
+        .create(
+            BoxOptions {
+                rootfs: RootfsSpec::OciBundle(bundle.path().to_path_buf()),
+                ..common::alpine_opts()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    handle.start().await.unwrap();
+
+    let mut execution = handle
+        .exec(BoxCommand::new("cat").arg("/bundle.txt"))
+        .await
+        .expect("exec failed");
+    let mut output = String::new();
+    if let Some(mut stdout) = execution.stdout() {
+        while let Some(chunk) = stdout.next().await {
+            output.push_str(&chunk);
+        }
+    }
+    assert_eq!(execution.wait().await.unwrap().exit_code, 0);
+    assert_eq!(output.trim(), "from bundle");
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
+
+#[tokio::test]
+async fn start_rejects_directory_without_oci_layout() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = runtime_at(&home);
+    let bundle = TempDir::new().unwrap();
+    std::fs::write(bundle.path().join("index.json"), "{}").unwrap();
+
+    let handle = runtime
+        .create(
+            BoxOptions {
+                rootfs: RootfsSpec::OciBundle(bundle.path().to_path_buf()),
+                ..common::alpine_opts()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let err = handle.start().await.unwrap_err();
+    assert!(err.to_string().contains("no oci-layout"), "{err}");
+
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+}
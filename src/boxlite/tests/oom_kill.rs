@@ -0,0 +1,67 @@
+//! Integration tests for OOM-kill reporting on exec results.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test oom_kill -- --test-threads=1`
+
+mod common;
+
+use boxlite::{BoxCommand, BoxOptions};
+
+#[tokio::test]
+async fn memory_hog_is_reported_as_oom_killed() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let opts = BoxOptions {
+        memory_mib: Some(256),
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    handle.start().await.unwrap();
+
+    // `tail` buffers its input until a newline that /dev/zero never sends,
+    // so it grows until the OOM killer takes it.
+    let execution = handle
+        .exec(BoxCommand::new("tail").arg("/dev/zero"))
+        .await
+        .expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, -9, "{result:?}");
+    assert!(
+        result.oom_killed,
+        "memory hog should be OOM-killed: {result:?}"
+    );
+    assert_eq!(result.signal_name.as_deref(), Some("SIGKILL"));
+
+    // A SIGKILL from the caller is not mistaken for an OOM kill.
+    let execution = handle
+        .exec(BoxCommand::new("sleep").arg("60"))
+        .await
+        .expect("exec failed");
+    execution.kill().await.expect("kill failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, -9, "{result:?}");
+    assert!(
+        !result.oom_killed,
+        "plain SIGKILL is not an OOM kill: {result:?}"
+    );
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
@@ -0,0 +1,82 @@
+//! Integration tests for placing the rootfs overlay upper layer on a data disk.
+//!
+//! Verifies that `BoxOptions::overlay_upper_disk` routes container writes to
+//! the declared disk instead of the rootfs disk.
+
+mod common;
+
+use std::os::unix::fs::MetadataExt;
+
+use boxlite::{BoxCommand, BoxOptions, DiskSpec};
+use tempfile::TempDir;
+
+const DATA_DISK_BYTES: u64 = 512 * 1024 * 1024;
+const WRITE_MIB: u64 = 64;
+
+/// Bytes actually allocated on the host for a (sparse) file.
+fn allocated_bytes(path: &std::path::Path) -> u64 {
+    std::fs::metadata(path).unwrap().blocks() * 512
+}
+
+#[tokio::test]
+async fn large_write_lands_on_overlay_upper_disk() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    // Blank sparse raw image; the guest formats it on first use.
+    let disk_dir = TempDir::new_in("/tmp").unwrap();
+    let data_disk = disk_dir.path().join("data.img");
+    std::fs::File::create(&data_disk)
+        .unwrap()
+        .set_len(DATA_DISK_BYTES)
+        .unwrap();
+
+    let opts = BoxOptions {
+        disks: vec![DiskSpec {
+            id: "data".into(),
+            host_path: data_disk.to_string_lossy().into_owned(),
+            read_only: false,
+            passthrough: false,
+        }],
+        overlay_upper_disk: Some("data".into()),
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    handle.start().await.unwrap();
+
+    let before = allocated_bytes(&data_disk);
+
+    let mut execution = handle
+        .exec(BoxCommand::new("sh").args([
+            "-c",
+            &format!("dd if=/dev/urandom of=/big.bin bs=1M count={WRITE_MIB} && sync"),
+        ]))
+        .await
+        .expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0, "large write should succeed");
+
+    // Stop flushes the VM's block caches to the host image.
+    handle.stop().await.unwrap();
+
+    let grown = allocated_bytes(&data_disk) - before;
+    assert!(
+        grown >= WRITE_MIB * 1024 * 1024,
+        "data disk grew by {grown} bytes, expected at least {WRITE_MIB} MiB"
+    );
+
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
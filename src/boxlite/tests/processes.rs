@@ -0,0 +1,52 @@
+//! Integration tests for `LiteBox::processes`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test processes -- --test-threads=1`
+
+mod common;
+
+use std::time::Duration;
+
+use boxlite::BoxCommand;
+use common::box_test::BoxTestBase;
+
+#[tokio::test]
+async fn processes_lists_spawned_sleep() {
+    let t = BoxTestBase::new().await;
+    let execution =
+        t.bx.exec(BoxCommand::new("sleep").arg("300"))
+            .await
+            .unwrap();
+
+    let sleep = tokio::time::timeout(Duration::from_secs(10), async {
+        loop {
+            let processes = t.bx.processes().await.unwrap();
+            if let Some(p) = processes.into_iter().find(|p| p.cmd == "sleep 300") {
+                return p;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .expect("sleep never showed up in the process list");
+
+    assert!(sleep.pid > 1, "{sleep:?}");
+    assert!(!sleep.state.is_empty(), "{sleep:?}");
+
+    // Only the container's namespace is listed: its init is PID 1 and
+    // nothing from the guest (e.g. the agent) leaks in.
+    let processes = t.bx.processes().await.unwrap();
+    assert!(
+        processes.iter().any(|p| p.pid == 1 && p.ppid == 0),
+        "{processes:?}"
+    );
+    assert!(
+        !processes.iter().any(|p| p.cmd.contains("boxlite-guest")),
+        "{processes:?}"
+    );
+
+    execution.kill().await.unwrap();
+}
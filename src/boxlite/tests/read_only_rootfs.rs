@@ -0,0 +1,47 @@
+//! Integration tests for `BoxOptions::read_only_rootfs`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test read_only_rootfs -- --test-threads=1`
+
+mod common;
+
+use boxlite::TmpfsMount;
+use common::box_test::BoxTestBase;
+
+#[tokio::test]
+async fn read_only_rootfs_rejects_writes_outside_mounts() {
+    let t = BoxTestBase::with_options(boxlite::BoxOptions {
+        read_only_rootfs: true,
+        tmpfs: vec![TmpfsMount {
+            guest_path: "/scratch".into(),
+            size_mib: 16,
+            mode: None,
+        }],
+        ..common::alpine_opts()
+    })
+    .await;
+    t.bx.start().await.unwrap();
+
+    // Writing to the rootfs fails with EROFS.
+    let out = t
+        .exec_stdout("sh", &["-c", "touch /probe 2>&1; echo exit=$?"])
+        .await;
+    assert!(out.contains("Read-only file system"), "{out}");
+    assert!(out.contains("exit=1"), "{out}");
+
+    let mounts = t.read_file("/proc/mounts").await;
+    let root = mounts
+        .lines()
+        .find(|line| line.split_whitespace().nth(1) == Some("/"))
+        .unwrap_or_else(|| panic!("/ not in /proc/mounts:\n{mounts}"));
+    let root_opts = root.split_whitespace().nth(3).unwrap_or_default();
+    assert!(root_opts.split(',').any(|opt| opt == "ro"), "{root}");
+
+    // Declared mounts stay writable, and so does exec itself.
+    t.write_file("/scratch/hello.txt", "still writable").await;
+    assert_eq!(t.read_file("/scratch/hello.txt").await, "still writable");
+    assert_eq!(t.exec_stdout("echo", &["ok"]).await.trim(), "ok");
+}
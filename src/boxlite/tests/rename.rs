@@ -0,0 +1,93 @@
+//! Integration tests for `BoxliteRuntime::rename`.
+//!
+//! Boxes are only created, never started, so no VM is needed.
+
+mod common;
+
+use boxlite::BoxliteError;
+use boxlite::BoxliteRuntime;
+use boxlite::runtime::options::BoxliteOptions;
+use common::home::PerTestBoxHome;
+
+fn runtime_at(home: &PerTestBoxHome) -> BoxliteRuntime {
+    BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn rename_moves_box_to_new_name() {
+    let home = PerTestBoxHome::new();
+    let runtime = runtime_at(&home);
+    let handle = runtime
+        .create(common::alpine_opts(), Some("before".into()))
+        .await
+        .unwrap();
+
+    runtime.rename("before", "after").await.unwrap();
+
+    let info = runtime.get_info("after").await.unwrap().expect("new name");
+    assert_eq!(info.id, *handle.id());
+    assert_eq!(info.name.as_deref(), Some("after"));
+    assert_eq!(handle.info().name.as_deref(), Some("after"));
+    let fresh = runtime.get("after").await.unwrap().expect("new name");
+    assert_eq!(fresh.name(), Some("after"));
+}
+
+#[tokio::test]
+async fn old_name_no_longer_resolves() {
+    let home = PerTestBoxHome::new();
+    let runtime = runtime_at(&home);
+    let handle = runtime
+        .create(common::alpine_opts(), Some("old".into()))
+        .await
+        .unwrap();
+
+    runtime.rename(handle.id().as_str(), "new").await.unwrap();
+
+    assert!(runtime.get_info("old").await.unwrap().is_none());
+    assert!(!runtime.exists("old").await.unwrap());
+
+    // The rename is persisted, not just cached.
+    drop(handle);
+    drop(runtime);
+    let runtime = runtime_at(&home);
+    assert!(runtime.get_info("old").await.unwrap().is_none());
+    assert!(runtime.get_info("new").await.unwrap().is_some());
+}
+
+#[tokio::test]
+async fn rename_rejects_taken_and_invalid_names() {
+    let home = PerTestBoxHome::new();
+    let runtime = runtime_at(&home);
+    runtime
+        .create(common::alpine_opts(), Some("first".into()))
+        .await
+        .unwrap();
+    runtime
+        .create(common::alpine_opts(), Some("second".into()))
+        .await
+        .unwrap();
+
+    let err = runtime.rename("second", "first").await.unwrap_err();
+    assert!(matches!(err, BoxliteError::AlreadyExists(_)), "{err:?}");
+
+    for bad in ["", "a/b"] {
+        let err = runtime.rename("second", bad).await.unwrap_err();
+        assert!(matches!(err, BoxliteError::InvalidArgument(_)), "{err:?}");
+    }
+
+    // Nothing changed.
+    let info = runtime.get_info("second").await.unwrap().unwrap();
+    assert_eq!(info.name.as_deref(), Some("second"));
+}
@@ -0,0 +1,72 @@
+//! Integration tests for copy-on-write container rootfs disks.
+//!
+//! Every image-backed box boots from a thin qcow2 overlay whose backing file
+//! is the image's cached ext4 base, shared by all boxes of that image.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test rootfs_cow -- --test-threads=1`
+
+mod common;
+
+use std::path::{Path, PathBuf};
+
+use boxlite::BoxliteRuntime;
+use boxlite::runtime::options::BoxliteOptions;
+use boxlite_test_utils::home::PerTestBoxHome;
+
+/// Backing file recorded in a qcow2 header (offset at byte 8, size at 16).
+fn qcow2_backing_file(path: &Path) -> Option<PathBuf> {
+    let bytes = std::fs::read(path).unwrap();
+    assert_eq!(&bytes[..4], b"QFI\xfb", "{} is not qcow2", path.display());
+    let offset = u64::from_be_bytes(bytes[8..16].try_into().unwrap()) as usize;
+    let size = u32::from_be_bytes(bytes[16..20].try_into().unwrap()) as usize;
+    (offset != 0)
+        .then(|| PathBuf::from(String::from_utf8_lossy(&bytes[offset..offset + size]).into_owned()))
+}
+
+#[tokio::test]
+async fn boxes_of_one_image_share_a_base_disk() {
+    let home = PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let mut overlays = Vec::new();
+    for _ in 0..2 {
+        let bx = runtime.create(common::alpine_opts(), None).await.unwrap();
+        bx.start().await.unwrap();
+        overlays.push(
+            home.path
+                .join("boxes")
+                .join(bx.id().as_str())
+                .join("disks/disk.qcow2"),
+        );
+        bx.stop().await.unwrap();
+    }
+
+    assert_ne!(overlays[0], overlays[1]);
+    let bases: Vec<PathBuf> = overlays
+        .iter()
+        .map(|overlay| qcow2_backing_file(overlay).expect("overlay has no backing file"))
+        .collect();
+    assert_eq!(bases[0], bases[1], "boxes must share one base");
+    assert!(bases[0].exists(), "base {} missing", bases[0].display());
+    assert!(
+        std::fs::metadata(&overlays[0]).unwrap().len()
+            < std::fs::metadata(&bases[0]).unwrap().len(),
+        "overlay should be thinner than its base"
+    );
+}
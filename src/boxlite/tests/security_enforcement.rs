@@ -63,6 +63,14 @@ async fn virtiofs_readonly_and_capabilities() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -98,6 +106,7 @@ async fn virtiofs_readonly_and_capabilities() {
 
     readonly_volume_readable(&bx).await;
     readonly_volume_blocks_write(&bx).await;
+    readonly_volume_write_fails_with_erofs(&bx).await;
     readonly_volume_blocks_remount(&bx).await;
     rw_volume_allows_write(&bx).await;
     capabilities_exclude_sys_admin(&bx).await;
@@ -130,6 +139,26 @@ async fn readonly_volume_blocks_write(bx: &LiteBox) {
     assert_ne!(check, 0, "file should not exist on read-only volume");
 }
 
+/// The guest mounts read-only volumes with MS_RDONLY, so the kernel itself
+/// rejects the write with EROFS rather than the host refusing it later.
+async fn readonly_volume_write_fails_with_erofs(bx: &LiteBox) {
+    let (exit, output) = exec_full(
+        bx,
+        BoxCommand::new("sh").args(["-c", "touch /data/readonly/hack.txt 2>&1"]),
+    )
+    .await;
+    assert_ne!(exit, 0, "touch on read-only volume should fail");
+    assert!(output.contains("Read-only file system"), "{output}");
+
+    let mounts = exec_stdout(bx, BoxCommand::new("cat").arg("/proc/mounts")).await;
+    let line = mounts
+        .lines()
+        .find(|l| l.split_whitespace().nth(1) == Some("/data/readonly"))
+        .unwrap_or_else(|| panic!("/data/readonly not mounted:\n{mounts}"));
+    let options = line.split_whitespace().nth(3).unwrap_or_default();
+    assert!(options.split(',').any(|o| o == "ro"), "{line}");
+}
+
 /// Guest without CAP_SYS_ADMIN cannot remount read-only volume as read-write.
 async fn readonly_volume_blocks_remount(bx: &LiteBox) {
     let (exit, output) = exec_full(
@@ -237,6 +266,14 @@ async fn disabled_network_blocks_tsi_socket_forwarding() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -0,0 +1,86 @@
+//! Integration tests for the shim control socket.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test shim_control -- --test-threads=1`
+
+mod common;
+
+use std::time::Duration;
+
+use boxlite::BoxCommand;
+use boxlite::net::socket_path::BoxSockets;
+use boxlite::vmm::controller::control::ControlClient;
+
+#[tokio::test]
+async fn stats_answer_while_guest_is_frozen() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+    handle.start().await.unwrap();
+
+    let id = handle.id().to_string();
+    let socket =
+        BoxSockets::new(&id, home.path.join("boxes").join(&id).join("sockets")).control_sock();
+    let client = ControlClient::new(&socket);
+
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::metadata(&socket).unwrap().permissions().mode()
+    };
+    assert_eq!(mode & 0o777, 0o600, "control socket must be owner-only");
+
+    let stats = tokio::task::spawn_blocking({
+        let client = client.clone();
+        move || client.stats()
+    })
+    .await
+    .unwrap()
+    .expect("stats from running shim");
+    assert!(!stats.paused);
+    assert!(stats.memory_bytes > 0);
+
+    // Freeze the shim: the guest agent cannot answer anything now.
+    let frozen = tokio::task::spawn_blocking({
+        let client = client.clone();
+        move || {
+            client.pause()?;
+            std::thread::sleep(Duration::from_millis(200));
+            client.stats()
+        }
+    })
+    .await
+    .unwrap()
+    .expect("stats from frozen shim");
+    assert!(frozen.paused, "{frozen:?}");
+    assert_eq!(frozen.pid, stats.pid);
+
+    tokio::task::spawn_blocking(move || client.resume())
+        .await
+        .unwrap()
+        .expect("resume");
+    let output = handle
+        .run(BoxCommand::new("true"))
+        .await
+        .expect("exec after resume");
+    assert_eq!(output.exit_code, 0);
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
@@ -0,0 +1,44 @@
+//! Integration tests for `BoxliteRuntime::stats_stream`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test stats_stream -- --test-threads=1`
+
+mod common;
+
+use std::time::Duration;
+
+use common::box_test::BoxTestBase;
+use futures::StreamExt;
+
+#[tokio::test]
+async fn stats_stream_follows_box_creation() {
+    let t = BoxTestBase::new().await;
+    let mut stats = Box::pin(t.runtime.stats_stream(Duration::from_millis(200)));
+
+    let before = stats.next().await.expect("stats stream ended");
+    assert!(before.num_running_boxes >= 1, "{before:?}");
+    assert_eq!(before.boxes_sampled, 1, "{before:?}");
+
+    let second = t.runtime.create(common::alpine_opts(), None).await.unwrap();
+    second.start().await.unwrap();
+
+    let after = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(sample) = stats.next().await {
+            if sample.num_running_boxes > before.num_running_boxes {
+                return Some(sample);
+            }
+        }
+        None
+    })
+    .await
+    .expect("no sample saw the new box within 10s")
+    .expect("stats stream ended early");
+
+    assert_eq!(after.num_running_boxes, before.num_running_boxes + 1);
+    assert_eq!(after.boxes_sampled, 2, "{after:?}");
+
+    second.stop().await.unwrap();
+}
@@ -45,6 +45,14 @@ async fn boot_timing_profile() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -117,6 +125,12 @@ async fn boot_timing_profile() {
             "  total_create_duration:   {:>6}ms",
             metrics.total_create_duration_ms.unwrap_or(0)
         );
+        println!(
+            "  guest_boot_duration:     {:>6}ms (kernel {}ms + agent {}ms)",
+            metrics.guest_boot_duration_ms.unwrap_or(0),
+            metrics.guest_kernel_boot_ms.unwrap_or(0),
+            metrics.guest_agent_init_ms.unwrap_or(0)
+        );
         println!(
             "  stage_filesystem_setup:  {:>6}ms",
             metrics.stage_filesystem_setup_ms.unwrap_or(0)
@@ -179,6 +193,14 @@ async fn boot_timing_profile_no_jailer() {
     let runtime = BoxliteRuntime::new(BoxliteOptions {
         home_dir: home.path.clone(),
         image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
     })
     .expect("create runtime");
 
@@ -239,3 +261,50 @@ async fn boot_timing_profile_no_jailer() {
     runtime.remove(box_id.as_str(), false).await.unwrap();
     let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
 }
+
+/// Guest boot is split into kernel and agent portions that add up to the total.
+#[tokio::test]
+async fn guest_boot_breakdown_sums_to_total() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let handle = runtime.create(common::alpine_opts(), None).await.unwrap();
+    let box_id = handle.id().clone();
+    handle.start().await.unwrap();
+
+    let metrics = handle.metrics().await.unwrap();
+    let total = metrics
+        .guest_boot_duration_ms()
+        .expect("guest boot duration recorded");
+    let kernel = metrics
+        .guest_kernel_boot_ms()
+        .expect("kernel boot portion reported");
+    let agent = metrics
+        .guest_agent_init_ms()
+        .expect("agent init portion reported");
+
+    assert!(total > 0, "guest boot should take measurable time");
+    assert!(agent > 0, "agent init should take measurable time");
+    // Kernel is derived as the remainder, so the parts sum to the total
+    // (allow 1ms for rounding of the guest-reported value).
+    assert!(
+        (kernel + agent).abs_diff(total) <= 1,
+        "kernel ({kernel}ms) + agent ({agent}ms) should equal total ({total}ms)"
+    );
+
+    handle.stop().await.unwrap();
+    runtime.remove(box_id.as_str(), false).await.unwrap();
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
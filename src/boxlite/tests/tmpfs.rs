@@ -0,0 +1,46 @@
+//! Integration tests for `BoxOptions::tmpfs`.
+//!
+//! # Prerequisites
+//!
+//! These tests require a real VM environment:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test tmpfs -- --test-threads=1`
+
+mod common;
+
+use boxlite::TmpfsMount;
+use common::box_test::BoxTestBase;
+
+#[tokio::test]
+async fn tmpfs_mount_is_writable_and_size_limited() {
+    let t = BoxTestBase::with_options(boxlite::BoxOptions {
+        tmpfs: vec![TmpfsMount {
+            guest_path: "/scratch".into(),
+            size_mib: 16,
+            mode: None,
+        }],
+        ..common::alpine_opts()
+    })
+    .await;
+    t.bx.start().await.unwrap();
+
+    t.write_file("/scratch/hello.txt", "in memory").await;
+    assert_eq!(t.read_file("/scratch/hello.txt").await, "in memory");
+
+    let mounts = t.read_file("/proc/mounts").await;
+    let entry = mounts
+        .lines()
+        .find(|line| line.split_whitespace().nth(1) == Some("/scratch"))
+        .unwrap_or_else(|| panic!("/scratch not in /proc/mounts:\n{mounts}"));
+    assert_eq!(entry.split_whitespace().nth(2), Some("tmpfs"), "{entry}");
+    assert!(entry.contains("size=16384k"), "{entry}");
+
+    // Writing past the size limit fails with ENOSPC.
+    let code = t
+        .exec_exit_code(
+            "dd",
+            &["if=/dev/zero", "of=/scratch/big", "bs=1M", "count=32"],
+        )
+        .await;
+    assert_ne!(code, 0, "write beyond 16MiB should fail");
+}
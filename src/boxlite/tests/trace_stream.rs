@@ -0,0 +1,84 @@
+//! Integration tests for per-box tracing streams.
+//!
+//! Verifies that `LiteBox::trace_stream()` only receives events attributed
+//! to its own box when two boxes share one runtime.
+
+mod common;
+
+use std::time::Duration;
+
+use boxlite::{BoxCommand, BoxTraceLayer, TraceEvent, TraceFilter, TraceStream};
+use futures::{FutureExt, StreamExt};
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+/// Collect whatever is already buffered on the stream.
+fn drain(stream: &mut TraceStream) -> Vec<TraceEvent> {
+    let mut events = Vec::new();
+    while let Some(Some(event)) = stream.next().now_or_never() {
+        events.push(event);
+    }
+    events
+}
+
+#[tokio::test]
+async fn exec_events_reach_only_their_box_stream() {
+    let _ = tracing_subscriber::registry()
+        .with(BoxTraceLayer::new())
+        .try_init();
+
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let box_a = runtime.create(common::alpine_opts(), None).await.unwrap();
+    let box_b = runtime.create(common::alpine_opts(), None).await.unwrap();
+    box_a.start().await.unwrap();
+    box_b.start().await.unwrap();
+
+    let mut a_stream = box_a.trace_stream(TraceFilter::default());
+    let mut b_stream = box_b.trace_stream(TraceFilter::default());
+
+    let mut execution = box_a
+        .exec(BoxCommand::new("echo").arg("traced"))
+        .await
+        .expect("exec failed");
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0);
+
+    let first = tokio::time::timeout(Duration::from_secs(5), a_stream.next())
+        .await
+        .expect("box A stream should receive exec events")
+        .expect("box A stream closed");
+    let mut a_events = vec![first];
+    a_events.extend(drain(&mut a_stream));
+    for event in &a_events {
+        assert_eq!(event.box_id, box_a.id().as_str());
+    }
+
+    // B was idle: anything it saw came from B itself, never from A's exec.
+    for event in drain(&mut b_stream) {
+        assert_eq!(
+            event.box_id,
+            box_b.id().as_str(),
+            "box B stream received a foreign event: {event:?}"
+        );
+    }
+
+    box_a.stop().await.unwrap();
+    box_b.stop().await.unwrap();
+    let _ = runtime.remove(box_a.id().as_str(), true).await;
+    let _ = runtime.remove(box_b.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
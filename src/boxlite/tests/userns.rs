@@ -0,0 +1,97 @@
+//! Integration tests for user-namespace ID mapping.
+//!
+//! Verifies that `BoxOptions::userns_mapping` runs the container in its own
+//! user namespace: container root maps to the configured unprivileged guest
+//! uid, while the idmapped rootfs still looks root-owned from inside.
+
+mod common;
+
+use boxlite::{BoxCommand, BoxOptions, UserNsMapping};
+use tokio_stream::StreamExt;
+
+const GUEST_BASE_ID: u32 = 100000;
+const RANGE_SIZE: u32 = 65536;
+
+/// Helper: exec a command, collect stdout, assert exit code 0.
+async fn exec_stdout(handle: &boxlite::LiteBox, cmd: BoxCommand) -> String {
+    let mut execution = handle.exec(cmd).await.expect("exec failed");
+
+    let mut stdout = String::new();
+    if let Some(mut stream) = execution.stdout() {
+        while let Some(chunk) = stream.next().await {
+            stdout.push_str(&chunk);
+        }
+    }
+
+    let result = execution.wait().await.expect("wait failed");
+    assert_eq!(result.exit_code, 0, "command should exit 0");
+    stdout
+}
+
+/// Parse the first `container_id host_id size` line of a uid_map/gid_map.
+fn first_map_line(map: &str) -> (u32, u32, u32) {
+    let fields: Vec<u32> = map
+        .lines()
+        .next()
+        .expect("map should not be empty")
+        .split_whitespace()
+        .map(|f| f.parse().expect("numeric map field"))
+        .collect();
+    assert_eq!(fields.len(), 3, "unexpected map line: {map:?}");
+    (fields[0], fields[1], fields[2])
+}
+
+#[tokio::test]
+async fn container_root_maps_to_unprivileged_guest_uid() {
+    let home = boxlite_test_utils::home::PerTestBoxHome::new();
+    let runtime = boxlite::BoxliteRuntime::new(boxlite::runtime::options::BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .expect("create runtime");
+
+    let opts = BoxOptions {
+        userns_mapping: Some(UserNsMapping::root_to(GUEST_BASE_ID, RANGE_SIZE)),
+        ..common::alpine_opts()
+    };
+    let handle = runtime.create(opts, None).await.unwrap();
+    handle.start().await.unwrap();
+
+    // Inside the container the process is root...
+    let uid = exec_stdout(&handle, BoxCommand::new("id").arg("-u")).await;
+    assert_eq!(
+        uid.trim(),
+        "0",
+        "container process should see itself as root"
+    );
+
+    // ...but the guest kernel maps that root to the unprivileged base uid.
+    let uid_map = exec_stdout(&handle, BoxCommand::new("cat").arg("/proc/self/uid_map")).await;
+    assert_eq!(first_map_line(&uid_map), (0, GUEST_BASE_ID, RANGE_SIZE));
+    let gid_map = exec_stdout(&handle, BoxCommand::new("cat").arg("/proc/self/gid_map")).await;
+    assert_eq!(first_map_line(&gid_map), (0, GUEST_BASE_ID, RANGE_SIZE));
+
+    // Image files keep their ownership through the idmapped rootfs.
+    let owner = exec_stdout(
+        &handle,
+        BoxCommand::new("stat").args(["-c", "%u:%g", "/etc/passwd"]),
+    )
+    .await;
+    assert_eq!(
+        owner.trim(),
+        "0:0",
+        "rootfs should stay root-owned in userns"
+    );
+
+    handle.stop().await.unwrap();
+    let _ = runtime.remove(handle.id().as_str(), true).await;
+    let _ = runtime.shutdown(Some(common::TEST_SHUTDOWN_TIMEOUT)).await;
+}
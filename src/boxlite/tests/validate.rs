@@ -0,0 +1,91 @@
+//! Integration tests for `BoxliteRuntime::validate`.
+//!
+//! # Prerequisites
+//!
+//! The volume test resolves `alpine:latest` from the shared image cache:
+//! 1. Build the runtime: `BUILD_PROFILE=debug make runtime`
+//! 2. Run with: `cargo test -p boxlite --test validate`
+
+mod common;
+
+use boxlite::runtime::options::{BoxOptions, BoxliteOptions, RetryPolicy, RootfsSpec, VolumeSpec};
+use boxlite::{BoxliteRuntime, ImageRegistry};
+use common::home::PerTestBoxHome;
+
+#[tokio::test]
+async fn missing_volume_host_path_is_a_warning() {
+    let home = PerTestBoxHome::new();
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        image_registries: common::test_registries(),
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: None,
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .unwrap();
+
+    let missing = home.path.join("does-not-exist");
+    let report = runtime
+        .validate(&BoxOptions {
+            volumes: vec![VolumeSpec {
+                host_path: missing.display().to_string(),
+                guest_path: "/data".into(),
+                read_only: false,
+            }],
+            ..common::alpine_opts()
+        })
+        .await
+        .unwrap();
+
+    assert!(report.is_ok(), "{:?}", report.errors);
+    assert_eq!(report.warnings.len(), 1);
+    assert!(report.warnings[0].contains("does-not-exist"));
+    assert!(runtime.list_info().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn unreachable_image_is_an_error() {
+    let home = PerTestBoxHome::isolated_in("/tmp");
+    let runtime = BoxliteRuntime::new(BoxliteOptions {
+        home_dir: home.path.clone(),
+        // Nothing listens on port 1, so the manifest lookup is refused.
+        image_registries: vec![ImageRegistry::http("127.0.0.1:1")],
+        image_store_max_bytes: None,
+        memory_overcommit: None,
+        pull_retry: Some(RetryPolicy {
+            max_retries: 0,
+            ..Default::default()
+        }),
+        dedup_layers: false,
+        image_config_cache_capacity: None,
+        max_concurrent_boots: None,
+        image_blob_store: None,
+        name_generator: None,
+    })
+    .unwrap();
+
+    let report = runtime
+        .validate(&BoxOptions {
+            rootfs: RootfsSpec::Image("boxlite/missing:latest".into()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+    assert!(!report.is_ok());
+    assert!(
+        report
+            .errors
+            .iter()
+            .any(|e| e.contains("boxlite/missing:latest")),
+        "{:?}",
+        report.errors
+    );
+    assert!(runtime.list_info().await.unwrap().is_empty());
+    assert!(runtime.images().unwrap().list().await.unwrap().is_empty());
+}
@@ -2,7 +2,9 @@
 //! This module contains all CLI-related code including the main CLI structure,
 //! subcommands, and flag definitions.
 
-use boxlite::runtime::options::{NetworkConfig, NetworkMode, PortProtocol, PortSpec, VolumeSpec};
+use boxlite::runtime::options::{
+    NetworkConfig, NetworkMode, PortProtocol, PortSpec, VolumeSpec, parse_extra_host,
+};
 use boxlite::{
     BoxCommand, BoxOptions, BoxliteOptions, BoxliteRestOptions, BoxliteRuntime, ImageRegistry,
     NetworkSpec,
@@ -448,10 +450,20 @@ pub struct NetworkFlags {
     /// "*.example.com", IP, or CIDR. Incompatible with `--network disabled`.
     #[arg(long = "allow-net", value_name = "HOST")]
     pub allow_net: Vec<String>,
+
+    /// Add an /etc/hosts entry (format: name:ip, repeatable). Use
+    /// "host-gateway" as the ip to reach the host, e.g.
+    /// host.docker.internal:host-gateway.
+    #[arg(long = "add-host", value_name = "HOST:IP")]
+    pub add_host: Vec<String>,
 }
 
 impl NetworkFlags {
     pub fn apply_to(&self, opts: &mut BoxOptions) -> anyhow::Result<()> {
+        for entry in &self.add_host {
+            opts.extra_hosts.push(parse_extra_host(entry)?);
+        }
+
         // Leave BoxOptions::default() (Enabled, full access) untouched when
         // neither flag is given, so a bare `run` behaves as before.
         if self.network.is_none() && self.allow_net.is_empty() {
@@ -496,61 +508,14 @@ impl PublishFlags {
     }
 }
 
-/// Parse a single publish spec: `[hostPort:]boxPort[/tcp|udp]`.
+/// Parse a single publish spec: `[[hostIp:]hostPort:]boxPort[/tcp|udp]`.
 /// - `boxPort` → host_port=None, guest_port=boxPort
 /// - `hostPort:boxPort` → host_port=Some(hostPort), guest_port=boxPort
+/// - `hostIp:hostPort:boxPort` → additionally binds only `hostIp`
 ///
 /// Only TCP is forwarded by the runtime today; UDP is accepted but not yet implemented.
 fn parse_publish_spec(s: &str) -> anyhow::Result<PortSpec> {
-    let s = s.trim();
-    if s.is_empty() {
-        anyhow::bail!("empty port spec");
-    }
-    let (rest, protocol) = match s.split_once('/') {
-        Some((r, proto)) => {
-            let p = if proto.eq_ignore_ascii_case("tcp") {
-                PortProtocol::Tcp
-            } else if proto.eq_ignore_ascii_case("udp") {
-                PortProtocol::Udp
-            } else {
-                anyhow::bail!("invalid protocol {:?}; use tcp or udp", proto)
-            };
-            (r.trim(), p)
-        }
-        None => (s, PortProtocol::Tcp),
-    };
-    let parts: Vec<&str> = rest.splitn(2, ':').map(str::trim).collect();
-    let (host_port, guest_port) = match parts.as_slice() {
-        [guest] => {
-            let g = parse_port(guest)?;
-            (None, g)
-        }
-        [host, guest] => {
-            let h = parse_port(host)?;
-            let g = parse_port(guest)?;
-            (Some(h), g)
-        }
-        _ => anyhow::bail!(
-            "invalid port spec {:?}; use hostPort:boxPort or boxPort[/tcp]",
-            s
-        ),
-    };
-    Ok(PortSpec {
-        host_port,
-        guest_port,
-        protocol,
-        host_ip: None,
-    })
-}
-
-fn parse_port(s: &str) -> anyhow::Result<u16> {
-    let n: u16 = s
-        .parse()
-        .map_err(|_| anyhow::anyhow!("invalid port number {:?}", s))?;
-    if n == 0 {
-        anyhow::bail!("port must be 1-65535");
-    }
-    Ok(n)
+    Ok(PortSpec::parse(s)?)
 }
 
 // ============================================================================
@@ -994,7 +959,30 @@ mod tests {
         NetworkFlags {
             network: network.map(str::to_string),
             allow_net: allow_net.iter().map(|s| s.to_string()).collect(),
+            add_host: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_network_flags_add_host() {
+        let mut opts = BoxOptions::default();
+        NetworkFlags {
+            add_host: vec![
+                "db:10.0.0.5".to_string(),
+                "host.docker.internal:host-gateway".to_string(),
+            ],
+            ..network_flags(None, &[])
         }
+        .apply_to(&mut opts)
+        .expect("valid hosts");
+
+        assert_eq!(opts.extra_hosts.len(), 2);
+        assert_eq!(opts.extra_hosts[0].0, "db");
+        assert_eq!(
+            opts.extra_hosts[1].1,
+            boxlite::runtime::options::HOST_GATEWAY_IP
+        );
+        assert!(matches!(opts.network, NetworkSpec::Enabled { .. }));
     }
 
     #[test]
@@ -1140,6 +1128,14 @@ mod tests {
         assert!(super::parse_publish_spec("99999:80").is_err());
     }
 
+    #[test]
+    fn test_parse_publish_spec_host_ip() {
+        let spec = super::parse_publish_spec("127.0.0.1:8080:80").unwrap();
+        assert_eq!(spec.host_ip.as_deref(), Some("127.0.0.1"));
+        assert_eq!(spec.host_port, Some(8080));
+        assert_eq!(spec.guest_port, 80);
+    }
+
     #[test]
     fn test_publish_flags_apply_to() {
         let flags = PublishFlags {
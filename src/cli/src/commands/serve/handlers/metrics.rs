@@ -43,6 +43,8 @@ pub(in crate::commands::serve) async fn box_metrics(
                 Some(BootTimingResponse {
                     total_create_ms: m.total_create_duration_ms.map(|v| v as u64),
                     guest_boot_ms: m.guest_boot_duration_ms.map(|v| v as u64),
+                    guest_kernel_boot_ms: m.guest_kernel_boot_ms.map(|v| v as u64),
+                    guest_agent_init_ms: m.guest_agent_init_ms.map(|v| v as u64),
                     filesystem_setup_ms: m.stage_filesystem_setup_ms.map(|v| v as u64),
                     image_prepare_ms: m.stage_image_prepare_ms.map(|v| v as u64),
                     guest_rootfs_ms: m.stage_guest_rootfs_ms.map(|v| v as u64),
@@ -65,6 +67,8 @@ pub(in crate::commands::serve) async fn box_metrics(
                 network_bytes_received: m.network_bytes_received,
                 network_tcp_connections: m.network_tcp_connections,
                 network_tcp_errors: m.network_tcp_errors,
+                disk_bytes_used: m.disk_bytes_used,
+                disk_bytes_total: m.disk_bytes_total,
                 boot_timing,
             })
             .into_response()
@@ -292,7 +292,7 @@ impl ActiveExecution {
         {
             let active = Arc::clone(&active);
             tokio::spawn(async move {
-                if let Ok(result) = active.execution.wait().await {
+                if let Ok(result) = active.execution.wait_status().await {
                     active
                         .exit_code
                         .store(result.exit_code, std::sync::atomic::Ordering::SeqCst);
@@ -1201,6 +1201,10 @@ mod tests {
             .send(boxlite::ExecResult {
                 exit_code: 0,
                 error_message: None,
+                timed_out: false,
+                duration_ms: 0,
+                oom_killed: false,
+                signal_name: None,
             })
             .unwrap();
 
@@ -1244,6 +1248,10 @@ mod tests {
             .send(boxlite::ExecResult {
                 exit_code: 42,
                 error_message: None,
+                timed_out: false,
+                duration_ms: 0,
+                oom_killed: false,
+                signal_name: None,
             })
             .unwrap();
         for _ in 0..20 {
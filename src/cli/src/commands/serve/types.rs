@@ -199,6 +199,8 @@ pub(super) struct BoxMetricsResponse {
     pub network_bytes_received: Option<u64>,
     pub network_tcp_connections: Option<u64>,
     pub network_tcp_errors: Option<u64>,
+    pub disk_bytes_used: Option<u64>,
+    pub disk_bytes_total: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub boot_timing: Option<BootTimingResponse>,
 }
@@ -207,6 +209,8 @@ pub(super) struct BoxMetricsResponse {
 pub(super) struct BootTimingResponse {
     pub total_create_ms: Option<u64>,
     pub guest_boot_ms: Option<u64>,
+    pub guest_kernel_boot_ms: Option<u64>,
+    pub guest_agent_init_ms: Option<u64>,
     pub filesystem_setup_ms: Option<u64>,
     pub image_prepare_ms: Option<u64>,
     pub guest_rootfs_ms: Option<u64>,
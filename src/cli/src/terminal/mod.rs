@@ -158,20 +158,13 @@ impl<'a> StreamManager<'a> {
         let mut sighup = signal(SignalKind::hangup())?;
         let mut sigquit = signal(SignalKind::quit())?;
 
-        // SIGWINCH setup (only if TTY)
-        let mut sigwinch = if self.tty {
-            Some(signal(SignalKind::window_change())?)
+        // Follow host terminal resizes (only if TTY)
+        let _resize_follower = if self.tty {
+            Some(self.execution.follow_terminal_size()?)
         } else {
             None
         };
 
-        // Initial resize
-        if self.tty
-            && let Some((w, h)) = term_size::dimensions()
-        {
-            let _ = self.execution.resize_tty(h as u32, w as u32).await;
-        }
-
         let mut io_done = false;
         let mut exit_status: Option<boxlite::ExecResult> = None;
 
@@ -183,7 +176,7 @@ impl<'a> StreamManager<'a> {
 
         let exit_code = loop {
             select! {
-                res = self.execution.wait(), if exit_status.is_none() => {
+                res = self.execution.wait_status(), if exit_status.is_none() => {
                     match res {
                         Ok(status) => {
                             exit_status = Some(status);
@@ -218,17 +211,6 @@ impl<'a> StreamManager<'a> {
                 _ = sigquit.recv() => {
                     let _ = self.execution.signal(Signal::SIGQUIT as i32).await;
                 }
-                Some(_) = async {
-                    if let Some(s) = sigwinch.as_mut() {
-                        s.recv().await
-                    } else {
-                        std::future::pending().await
-                    }
-                } => {
-                    if let Some((w, h)) = term_size::dimensions() {
-                        let _ = self.execution.resize_tty(h as u32, w as u32).await;
-                    }
-                }
             }
         };
 
@@ -435,6 +417,10 @@ mod tests {
                     let _ = result_tx.send(boxlite::ExecResult {
                         exit_code: 0,
                         error_message: None,
+                        timed_out: false,
+                        duration_ms: 0,
+                        oom_killed: false,
+                        signal_name: None,
                     });
                     drop(stdout_tx);
                     drop(stderr_tx);
@@ -493,6 +479,10 @@ mod tests {
                     error_message: Some(
                         "WS connect failed: WS auth rejected (401 Unauthorized)".to_string(),
                     ),
+                    timed_out: false,
+                    duration_ms: 0,
+                    oom_killed: false,
+                    signal_name: None,
                 });
                 drop(stdout_tx);
                 drop(stderr_tx);
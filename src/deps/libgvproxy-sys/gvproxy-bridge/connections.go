@@ -0,0 +1,113 @@
+package main
+
+// connections.go — per-connection TCP table for gvproxy_get_connections.
+//
+// The connection list comes from the netstack's registered TCP endpoints.
+// Byte counts are only known for connections relayed by our forked forwarder
+// (forked_tcp.go), which wraps the guest-side conn in a countingConn; other
+// connections report zero bytes.
+
+import (
+	"encoding/json"
+	"fmt"
+	"net"
+	"sync"
+	"sync/atomic"
+
+	"github.com/containers/gvisor-tap-vsock/pkg/virtualnetwork"
+	logrus "github.com/sirupsen/logrus"
+	"gvisor.dev/gvisor/pkg/tcpip"
+	"gvisor.dev/gvisor/pkg/tcpip/stack"
+	"gvisor.dev/gvisor/pkg/tcpip/transport/tcp"
+)
+
+// ConnectionInfo is one row of the connection table. Addresses are from the
+// netstack's point of view: local is the destination the guest dialed,
+// remote is the guest. BytesIn counts bytes received from the guest,
+// BytesOut bytes sent to it.
+type ConnectionInfo struct {
+	LocalAddr  string `json:"local_addr"`
+	RemoteAddr string `json:"remote_addr"`
+	State      string `json:"state"`
+	BytesIn    uint64 `json:"bytes_in"`
+	BytesOut   uint64 `json:"bytes_out"`
+}
+
+type byteCounters struct {
+	in  atomic.Uint64
+	out atomic.Uint64
+}
+
+// relayCounters maps a relayed connection's endpoint ID to its byte counters.
+var relayCounters sync.Map // stack.TransportEndpointID -> *byteCounters
+
+// countingConn counts bytes read from and written to the guest.
+type countingConn struct {
+	net.Conn
+	counters *byteCounters
+}
+
+func (c *countingConn) Read(p []byte) (int, error) {
+	n, err := c.Conn.Read(p)
+	c.counters.in.Add(uint64(n))
+	return n, err
+}
+
+func (c *countingConn) Write(p []byte) (int, error) {
+	n, err := c.Conn.Write(p)
+	c.counters.out.Add(uint64(n))
+	return n, err
+}
+
+// trackConn registers byte counters for the guest connection `id` and returns
+// the counting wrapper plus a func that unregisters it once the relay ends.
+func trackConn(id stack.TransportEndpointID, conn net.Conn) (net.Conn, func()) {
+	counters := &byteCounters{}
+	relayCounters.Store(id, counters)
+	return &countingConn{Conn: conn, counters: counters}, func() { relayCounters.Delete(id) }
+}
+
+// collectConnections returns the TCP connection table as a JSON array.
+func collectConnections(vn *virtualnetwork.VirtualNetwork) string {
+	conns := []ConnectionInfo{}
+	if vn != nil {
+		s, err := stackOf(vn)
+		if err != nil {
+			logrus.WithError(err).Warn("connections: cannot access netstack")
+		} else {
+			conns = tcpConnections(s)
+		}
+	}
+
+	data, err := json.Marshal(conns)
+	if err != nil {
+		return ""
+	}
+	return string(data)
+}
+
+func tcpConnections(s *stack.Stack) []ConnectionInfo {
+	conns := []ConnectionInfo{}
+	for _, registered := range s.RegisteredEndpoints() {
+		ep, ok := registered.(tcpip.Endpoint)
+		if !ok {
+			continue
+		}
+		info, ok := ep.Info().(*stack.TransportEndpointInfo)
+		if !ok || info.TransProto != tcp.ProtocolNumber {
+			continue
+		}
+		conn := ConnectionInfo{
+			LocalAddr:  fmt.Sprintf("%s:%d", info.ID.LocalAddress, info.ID.LocalPort),
+			RemoteAddr: fmt.Sprintf("%s:%d", info.ID.RemoteAddress, info.ID.RemotePort),
+			State:      tcp.EndpointState(ep.State()).String(),
+		}
+		if c, ok := relayCounters.Load(info.ID); ok {
+			counters := c.(*byteCounters)
+			conn.BytesIn = counters.in.Load()
+			conn.BytesOut = counters.out.Load()
+		}
+		conns = append(conns, conn)
+	}
+	return conns
+}
@@ -34,16 +34,11 @@ func OverrideTCPHandler(
 	ca *BoxCA,
 	secretMatcher *SecretHostMatcher,
 ) error {
-	// Access private stack field via reflect
-	v := reflect.ValueOf(vn).Elem()
-	stackField := v.FieldByName("stack")
-	if !stackField.IsValid() {
-		return fmt.Errorf("VirtualNetwork has no 'stack' field (gvisor-tap-vsock API changed?)")
+	s, err := stackOf(vn)
+	if err != nil {
+		return err
 	}
 
-	// #nosec G103 — accessing private field to override TCP handler
-	s := (*stack.Stack)(unsafe.Pointer(stackField.Pointer()))
-
 	// Rebuild NAT table (same logic as upstream parseNATTable in services.go)
 	nat := make(map[tcpip.Address]tcpip.Address)
 	for source, destination := range config.NAT {
@@ -59,3 +54,16 @@ func OverrideTCPHandler(
 	logrus.Info("allowNet TCP: handler overridden with SNI-inspecting forwarder")
 	return nil
 }
+
+// stackOf returns VirtualNetwork's private netstack.
+func stackOf(vn *virtualnetwork.VirtualNetwork) (*stack.Stack, error) {
+	// Access private stack field via reflect
+	v := reflect.ValueOf(vn).Elem()
+	stackField := v.FieldByName("stack")
+	if !stackField.IsValid() {
+		return nil, fmt.Errorf("VirtualNetwork has no 'stack' field (gvisor-tap-vsock API changed?)")
+	}
+
+	// #nosec G103 — accessing private field to reach the netstack
+	return (*stack.Stack)(unsafe.Pointer(stackField.Pointer())), nil
+}
@@ -158,7 +158,9 @@ func standardForward(r *tcp.ForwarderRequest, destAddr string) {
 			return outbound, nil
 		},
 	}
-	remote.HandleConn(gonet.NewTCPConn(&wq, ep))
+	guestConn, untrack := trackConn(r.ID(), gonet.NewTCPConn(&wq, ep))
+	defer untrack()
+	remote.HandleConn(guestConn)
 }
 
 // inspectAndForward: Accept → Peek SNI/Host → check allowlist → Dial → relay.
@@ -177,7 +179,8 @@ func inspectAndForward(r *tcp.ForwarderRequest, destAddr string, destPort uint16
 		}
 		return
 	}
-	guestConn := gonet.NewTCPConn(&wq, ep)
+	guestConn, untrack := trackConn(r.ID(), gonet.NewTCPConn(&wq, ep))
+	defer untrack()
 
 	// Step 2: Peek to extract hostname (non-consuming read via bufio.Reader)
 	br := bufio.NewReaderSize(guestConn, 16384)
@@ -217,6 +217,7 @@ type GvproxyInstance struct {
 	vnMu          sync.RWMutex                   // Protects vn field
 	ca            *BoxCA                         // Ephemeral MITM CA (nil if no secrets)
 	secretMatcher *SecretHostMatcher             // Hostname→secrets lookup (nil if no secrets)
+	rateLimit     *RateLimit                     // Bandwidth caps on the VM connection
 }
 
 func buildDNSZones(config GvproxyConfig) []types.Zone {
@@ -387,6 +388,7 @@ func gvproxy_create(configJSON *C.char, errOut **C.char) C.longlong {
 		Cancel:     cancel,
 		conn:       conn,
 		listener:   listener,
+		rateLimit:  &RateLimit{},
 	}
 
 	// Parse MITM CA from config (generated by Rust) when secrets are configured
@@ -510,7 +512,8 @@ func gvproxy_create(configJSON *C.char, errOut **C.char) C.longlong {
 				logrus.WithFields(logrus.Fields{"id": id, "remote": wrappedConn.RemoteAddr().String()}).Info("VFKit connection accepted")
 
 				// Handle the VFKit protocol with the wrapped connection
-				if err := vn.AcceptVfkit(ctx, wrappedConn); err != nil {
+				limitedConn := newRateLimitedConn(ctx, wrappedConn, instance.rateLimit)
+				if err := vn.AcceptVfkit(ctx, limitedConn); err != nil {
 					if ctx.Err() == nil {
 						logrus.WithFields(logrus.Fields{"error": err, "id": id}).Error("AcceptVfkit error")
 					}
@@ -536,7 +539,8 @@ func gvproxy_create(configJSON *C.char, errOut **C.char) C.longlong {
 				listener.Close()
 
 				// Handle the Qemu protocol
-				if err := vn.AcceptQemu(ctx, acceptedConn); err != nil {
+				limitedConn := newRateLimitedConn(ctx, acceptedConn, instance.rateLimit)
+				if err := vn.AcceptQemu(ctx, limitedConn); err != nil {
 					if ctx.Err() == nil {
 						logrus.WithFields(logrus.Fields{"error": err, "id": id}).Error("AcceptQemu error")
 					}
@@ -641,6 +645,50 @@ func gvproxy_get_stats(id C.longlong) *C.char {
 	return C.CString(stats)
 }
 
+//export gvproxy_get_connections
+func gvproxy_get_connections(id C.longlong) *C.char {
+	instancesMu.RLock()
+	instance, ok := instances[int64(id)]
+	instancesMu.RUnlock()
+
+	if !ok {
+		return nil
+	}
+
+	// An uninitialized VirtualNetwork has no connections yet: collectConnections
+	// reports an empty table rather than failing.
+	instance.vnMu.RLock()
+	vn := instance.vn
+	instance.vnMu.RUnlock()
+
+	table := collectConnections(vn)
+	if table == "" {
+		return nil
+	}
+
+	// Explicit: CString allocates memory, caller must free it
+	return C.CString(table)
+}
+
+//export gvproxy_set_rate_limit
+//
+// Caps the VM's bandwidth in bytes per second: ingress is traffic to the
+// guest, egress traffic from it. Zero removes the cap for that direction.
+// Returns 0 on success, -1 if the instance doesn't exist.
+func gvproxy_set_rate_limit(id C.longlong, ingressBps C.ulonglong, egressBps C.ulonglong) C.int {
+	instancesMu.RLock()
+	instance, ok := instances[int64(id)]
+	instancesMu.RUnlock()
+
+	if !ok {
+		return -1
+	}
+
+	instance.rateLimit.Set(uint64(ingressBps), uint64(egressBps))
+	logrus.WithFields(logrus.Fields{"id": id, "ingress_bps": uint64(ingressBps), "egress_bps": uint64(egressBps)}).Info("Set rate limit")
+	return 0
+}
+
 //export gvproxy_get_version
 func gvproxy_get_version() *C.char {
 	// Get gvisor-tap-vsock version from build info
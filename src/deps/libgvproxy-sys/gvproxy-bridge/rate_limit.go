@@ -0,0 +1,101 @@
+package main
+
+// rate_limit.go — per-instance bandwidth caps for gvproxy_set_rate_limit.
+//
+// The VM's NIC connection (Qemu stream or VFKit datagram) is wrapped in a
+// rateLimitedConn whose reads (guest → host, egress) and writes (host →
+// guest, ingress) draw from token buckets. Limits can change at any time,
+// including before the VM connects; a zero rate means unlimited.
+
+import (
+	"context"
+	"net"
+	"sync"
+
+	"golang.org/x/time/rate"
+)
+
+// minBurst keeps a low rate from rejecting a single full-size frame.
+const minBurst = 64 * 1024
+
+// RateLimit holds the two token buckets for one instance.
+type RateLimit struct {
+	mu      sync.RWMutex
+	ingress *rate.Limiter // nil = unlimited
+	egress  *rate.Limiter // nil = unlimited
+}
+
+func newLimiter(bytesPerSec uint64) *rate.Limiter {
+	if bytesPerSec == 0 {
+		return nil
+	}
+	burst := minBurst
+	if bytesPerSec > uint64(burst) {
+		burst = int(bytesPerSec)
+	}
+	return rate.NewLimiter(rate.Limit(bytesPerSec), burst)
+}
+
+// Set replaces both limits. Zero disables the limit for that direction.
+func (r *RateLimit) Set(ingressBps, egressBps uint64) {
+	r.mu.Lock()
+	r.ingress = newLimiter(ingressBps)
+	r.egress = newLimiter(egressBps)
+	r.mu.Unlock()
+}
+
+func (r *RateLimit) limiters() (ingress, egress *rate.Limiter) {
+	r.mu.RLock()
+	defer r.mu.RUnlock()
+	return r.ingress, r.egress
+}
+
+// wait blocks until n bytes are available from l, in burst-sized steps.
+func wait(ctx context.Context, l *rate.Limiter, n int) error {
+	if l == nil {
+		return nil
+	}
+	for n > 0 {
+		step := n
+		if burst := l.Burst(); step > burst {
+			step = burst
+		}
+		if err := l.WaitN(ctx, step); err != nil {
+			return err
+		}
+		n -= step
+	}
+	return nil
+}
+
+// rateLimitedConn throttles the VM's NIC connection.
+type rateLimitedConn struct {
+	net.Conn
+	ctx   context.Context
+	limit *RateLimit
+}
+
+func newRateLimitedConn(ctx context.Context, conn net.Conn, limit *RateLimit) net.Conn {
+	return &rateLimitedConn{Conn: conn, ctx: ctx, limit: limit}
+}
+
+// Read pays for egress after the fact, so the next read is delayed until the
+// bucket has recovered.
+func (c *rateLimitedConn) Read(p []byte) (int, error) {
+	n, err := c.Conn.Read(p)
+	if n > 0 {
+		_, egress := c.limit.limiters()
+		if werr := wait(c.ctx, egress, n); werr != nil && err == nil {
+			err = werr
+		}
+	}
+	return n, err
+}
+
+func (c *rateLimitedConn) Write(p []byte) (int, error) {
+	ingress, _ := c.limit.limiters()
+	if err := wait(c.ctx, ingress, len(p)); err != nil {
+		return 0, err
+	}
+	return c.Conn.Write(p)
+}
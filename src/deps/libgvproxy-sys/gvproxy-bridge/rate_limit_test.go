@@ -0,0 +1,80 @@
+package main
+
+import (
+	"context"
+	"net"
+	"testing"
+	"time"
+)
+
+func TestRateLimitZeroIsUnlimited(t *testing.T) {
+	var limit RateLimit
+	limit.Set(0, 0)
+	ingress, egress := limit.limiters()
+	if ingress != nil || egress != nil {
+		t.Fatalf("expected no limiters, got %v %v", ingress, egress)
+	}
+}
+
+func TestRateLimitBurstCoversFrame(t *testing.T) {
+	var limit RateLimit
+	limit.Set(1024, 10*1024*1024)
+	ingress, egress := limit.limiters()
+	if ingress.Burst() != minBurst {
+		t.Errorf("low rate burst = %d, want %d", ingress.Burst(), minBurst)
+	}
+	if egress.Burst() != 10*1024*1024 {
+		t.Errorf("high rate burst = %d, want one second of traffic", egress.Burst())
+	}
+}
+
+func TestRateLimitedConnThrottlesWrites(t *testing.T) {
+	client, server := net.Pipe()
+	defer client.Close()
+	defer server.Close()
+
+	var limit RateLimit
+	limit.Set(minBurst, 0)
+	conn := newRateLimitedConn(context.Background(), client, &limit)
+
+	go func() {
+		buf := make([]byte, 4096)
+		for {
+			if _, err := server.Read(buf); err != nil {
+				return
+			}
+		}
+	}()
+
+	// The first burst is free; the second must wait about a second.
+	payload := make([]byte, minBurst)
+	start := time.Now()
+	for i := 0; i < 2; i++ {
+		if _, err := conn.Write(payload); err != nil {
+			t.Fatalf("write: %v", err)
+		}
+	}
+	if elapsed := time.Since(start); elapsed < 800*time.Millisecond {
+		t.Errorf("two bursts at %d B/s took %v, expected about 1s", minBurst, elapsed)
+	}
+}
+
+func TestRateLimitedConnStopsOnCancel(t *testing.T) {
+	client, server := net.Pipe()
+	defer client.Close()
+	defer server.Close()
+
+	var limit RateLimit
+	limit.Set(1, 0)
+	ctx, cancel := context.WithCancel(context.Background())
+	conn := newRateLimitedConn(ctx, client, &limit)
+
+	// Drain the initial burst so the next write has to wait.
+	ingress, _ := limit.limiters()
+	ingress.AllowN(time.Now(), minBurst)
+
+	cancel()
+	if _, err := conn.Write([]byte("x")); err == nil {
+		t.Fatal("expected write to fail after cancel")
+	}
+}
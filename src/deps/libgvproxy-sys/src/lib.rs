@@ -3,7 +3,7 @@
 //! This crate provides raw, unsafe bindings to the gvproxy-bridge C library.
 //! For a safe, idiomatic Rust API, use the higher-level wrapper in the boxlite crate.
 
-use std::os::raw::{c_char, c_int, c_longlong, c_void};
+use std::os::raw::{c_char, c_int, c_longlong, c_ulonglong, c_void};
 
 /// Logging callback function type
 ///
@@ -67,6 +67,47 @@ extern "C" {
     /// - Do not use pointer after calling gvproxy_free_string
     pub fn gvproxy_get_stats(id: c_longlong) -> *mut c_char;
 
+    /// Get the per-connection TCP table for a gvproxy instance
+    ///
+    /// Returns a JSON array of objects with `local_addr`, `remote_addr`,
+    /// `state`, `bytes_in` and `bytes_out`. Byte counts are zero for
+    /// connections not relayed by the bridge's own forwarder.
+    ///
+    /// # Arguments
+    /// * `id` - Instance ID returned from gvproxy_create
+    ///
+    /// # Returns
+    /// Pointer to JSON string (must be freed with gvproxy_free_string), or NULL if:
+    /// - Instance doesn't exist
+    /// - Serialization failed
+    ///
+    /// An instance whose VirtualNetwork isn't initialized yet returns `[]`.
+    ///
+    /// # Safety
+    /// - `id` must be a valid instance ID
+    /// - Returned pointer must be freed with gvproxy_free_string
+    pub fn gvproxy_get_connections(id: c_longlong) -> *mut c_char;
+
+    /// Cap a gvproxy instance's bandwidth
+    ///
+    /// Token buckets on the VM's NIC connection limit traffic to the guest
+    /// (`ingress_bps`) and from it (`egress_bps`), in bytes per second. Zero
+    /// removes the cap for that direction. May be called before the VM
+    /// connects and again at any time to change the limits.
+    ///
+    /// # Arguments
+    /// * `id` - Instance ID returned from gvproxy_create
+    /// * `ingress_bps` - Host-to-guest limit in bytes per second (0 = unlimited)
+    /// * `egress_bps` - Guest-to-host limit in bytes per second (0 = unlimited)
+    ///
+    /// # Returns
+    /// 0 on success, -1 if the instance doesn't exist
+    pub fn gvproxy_set_rate_limit(
+        id: c_longlong,
+        ingress_bps: c_ulonglong,
+        egress_bps: c_ulonglong,
+    ) -> c_int;
+
     /// Get the libgvproxy version string
     ///
     /// # Returns
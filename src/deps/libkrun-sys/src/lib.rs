@@ -15,6 +15,11 @@ use std::os::raw::c_char;
 pub const KRUN_DISK_FORMAT_RAW: u32 = 0;
 pub const KRUN_DISK_FORMAT_QCOW2: u32 = 1;
 
+// Disk sync mode constants from libkrun.h
+pub const KRUN_SYNC_NONE: u32 = 0;
+pub const KRUN_SYNC_RELAXED: u32 = 1;
+pub const KRUN_SYNC_FULL: u32 = 2;
+
 // Kernel format constants from libkrun.h
 pub const KRUN_KERNEL_FORMAT_RAW: u32 = 0;
 pub const KRUN_KERNEL_FORMAT_ELF: u32 = 1;
@@ -150,6 +155,29 @@ pub unsafe fn krun_add_disk2(
     unsafe { krun::krun_add_disk2(ctx_id, block_id, disk_path, disk_format, read_only) }
 }
 
+#[cfg(feature = "krun")]
+pub unsafe fn krun_add_disk3(
+    ctx_id: u32,
+    block_id: *const c_char,
+    disk_path: *const c_char,
+    disk_format: u32,
+    read_only: bool,
+    direct_io: bool,
+    sync_mode: u32,
+) -> i32 {
+    unsafe {
+        krun::krun_add_disk3(
+            ctx_id,
+            block_id,
+            disk_path,
+            disk_format,
+            read_only,
+            direct_io,
+            sync_mode,
+        )
+    }
+}
+
 #[cfg(feature = "krun")]
 pub unsafe fn krun_add_net_unixstream(
     ctx_id: u32,
@@ -1,10 +1,13 @@
 //! Linux capabilities for container processes.
 //!
-//! Defines the default capability set matching Docker/OCI defaults.
+//! Defines the default capability set matching Docker/OCI defaults and
+//! applies the box's `cap_add` / `cap_drop` lists to it.
 //! Used by:
 //! - OCI spec builder (process.capabilities)
 //! - Tenant process spawning (exec capabilities)
 
+use boxlite_shared::constants::capabilities::{ALL, ALL_KEYWORD};
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use oci_spec::runtime::Capability;
 use std::collections::HashSet;
 
@@ -58,6 +61,46 @@ pub fn capability_names() -> Vec<String> {
     .collect()
 }
 
+/// Apply `cap_add` and `cap_drop` to the default set, like Docker.
+///
+/// `ALL` in `cap_add` grants every capability not in `cap_drop`; `ALL` in
+/// `cap_drop` keeps only `cap_add`. Otherwise `cap_drop` is removed from the
+/// defaults and `cap_add` appended. Names are `CAP_*` (the host normalizes
+/// them before sending).
+pub fn resolve_capabilities(cap_add: &[String], cap_drop: &[String]) -> Vec<String> {
+    let listed = |list: &[String], name: &str| list.iter().any(|cap| cap == name);
+
+    let mut caps: Vec<String> = if listed(cap_add, ALL_KEYWORD) {
+        ALL.iter()
+            .filter(|cap| !listed(cap_drop, cap))
+            .map(|cap| cap.to_string())
+            .collect()
+    } else if listed(cap_drop, ALL_KEYWORD) {
+        cap_add.to_vec()
+    } else {
+        let mut caps: Vec<String> = capability_names()
+            .into_iter()
+            .filter(|cap| !listed(cap_drop, cap))
+            .collect();
+        caps.extend(cap_add.iter().cloned());
+        caps
+    };
+    caps.sort();
+    caps.dedup();
+    caps
+}
+
+/// Convert `CAP_*` names to OCI capabilities.
+pub fn parse_capabilities(names: &[String]) -> BoxliteResult<HashSet<Capability>> {
+    names
+        .iter()
+        .map(|name| {
+            serde_json::from_value(serde_json::Value::String(name.clone()))
+                .map_err(|_| BoxliteError::Config(format!("unknown capability {name:?}")))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,4 +174,50 @@ mod tests {
             assert_eq!(name, name.to_uppercase(), "should be uppercase: {}", name);
         }
     }
+
+    fn names(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn resolve_capabilities_without_changes_is_default_set() {
+        let mut defaults = capability_names();
+        defaults.sort();
+        assert_eq!(resolve_capabilities(&[], &[]), defaults);
+    }
+
+    #[test]
+    fn resolve_capabilities_adds_and_drops() {
+        let caps = resolve_capabilities(&names(&["CAP_NET_ADMIN"]), &names(&["CAP_NET_RAW"]));
+        assert!(caps.contains(&"CAP_NET_ADMIN".to_string()));
+        assert!(!caps.contains(&"CAP_NET_RAW".to_string()));
+        assert_eq!(caps.len(), capability_names().len());
+
+        // An explicit add wins over a drop of the same capability.
+        let caps = resolve_capabilities(&names(&["CAP_KILL"]), &names(&["CAP_KILL"]));
+        assert!(caps.contains(&"CAP_KILL".to_string()));
+    }
+
+    #[test]
+    fn resolve_capabilities_handles_all() {
+        assert_eq!(
+            resolve_capabilities(&names(&["CAP_CHOWN"]), &names(&["ALL"])),
+            names(&["CAP_CHOWN"])
+        );
+
+        let caps = resolve_capabilities(&names(&["ALL"]), &names(&["CAP_SYS_ADMIN"]));
+        assert_eq!(caps.len(), ALL.len() - 1);
+        assert!(!caps.contains(&"CAP_SYS_ADMIN".to_string()));
+    }
+
+    #[test]
+    fn parse_capabilities_accepts_every_known_name() {
+        let all = names(ALL);
+        assert_eq!(parse_capabilities(&all).unwrap().len(), ALL.len());
+        assert_eq!(
+            parse_capabilities(&capability_names()).unwrap(),
+            default_capabilities()
+        );
+        assert!(parse_capabilities(&names(&["CAP_BOGUS"])).is_err());
+    }
 }
@@ -3,6 +3,7 @@
 //! Provides a builder pattern for spawning processes inside containers,
 //! following the `std::process::Command` pattern.
 
+use super::capabilities::capability_names;
 use super::zygote::{self, BuildSpec};
 use crate::service::exec::exec_handle::{ExecHandle, PtyConfig};
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
@@ -54,6 +55,12 @@ pub struct ContainerCommand {
     /// When set, resolved at spawn time via resolve_user().
     user_override: Option<String>,
 
+    /// Supplementary group IDs for the exec process.
+    groups: Vec<u32>,
+
+    /// Capabilities (`CAP_*` names) for the exec process.
+    capabilities: Vec<String>,
+
     /// Rootfs path for resolving user overrides from /etc/passwd.
     rootfs: Option<PathBuf>,
 
@@ -65,6 +72,10 @@ pub struct ContainerCommand {
 
     /// PTY configuration (set via with_pty())
     pty_config: Option<PtyConfig>,
+
+    /// Reset SIGPIPE to its default disposition in the spawned process
+    /// (set via with_default_sigpipe(), default true).
+    default_sigpipe: bool,
 }
 
 impl ContainerCommand {
@@ -85,10 +96,13 @@ impl ContainerCommand {
             env,
             user,
             user_override: None,
+            groups: Vec::new(),
+            capabilities: capability_names(),
             rootfs: Some(rootfs),
             cwd: None,
             console_socket: None,
             pty_config: None,
+            default_sigpipe: true,
             id,
             state_root,
         }
@@ -113,6 +127,35 @@ impl ContainerCommand {
         self
     }
 
+    /// Set supplementary group IDs for this exec.
+    ///
+    /// Numeric IDs are applied as-is; they need not exist in /etc/group.
+    pub fn with_groups(mut self, groups: Vec<u32>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Set the capabilities (`CAP_*` names) of this exec.
+    ///
+    /// Defaults to the Docker default set; `Container::cmd()` passes the
+    /// container's own set.
+    pub(super) fn with_capabilities(mut self, capabilities: Vec<String>) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    /// Choose how the spawned process treats SIGPIPE.
+    ///
+    /// With `true` (the default) the process starts with SIGPIPE at its
+    /// default disposition, like under a shell: a producer writing into a
+    /// closed pipe (`yes | head -n1`) is killed by SIGPIPE. With `false` it
+    /// inherits the agent's SIG_IGN and sees `EPIPE` write errors instead.
+    #[allow(dead_code)] // Opt-out for callers that rely on EPIPE semantics
+    pub fn with_default_sigpipe(mut self, enabled: bool) -> Self {
+        self.default_sigpipe = enabled;
+        self
+    }
+
     /// Set the program to execute
     ///
     /// # Example
@@ -386,6 +429,9 @@ impl ContainerCommand {
             args: container_args.clone(),
             uid,
             gid,
+            additional_gids: self.groups.clone(),
+            capabilities: self.capabilities.clone(),
+            default_sigpipe: self.default_sigpipe,
         };
 
         // Blocking IPC to zygote — use spawn_blocking to not block tokio.
@@ -578,6 +624,13 @@ mod tests {
         assert_eq!(cmd.user_override, Some("1000:1000".to_string()));
     }
 
+    #[test]
+    fn test_with_groups_sets_field() {
+        assert!(make_cmd().groups.is_empty());
+        let cmd = make_cmd().with_groups(vec![3000, 4000]);
+        assert_eq!(cmd.groups, vec![3000, 4000]);
+    }
+
     // ========================================================================
     // BUILDER PATTERN TESTS
     // ========================================================================
@@ -633,6 +686,16 @@ mod tests {
         assert_eq!(pty.cols, 80);
     }
 
+    #[test]
+    fn test_builder_default_sigpipe() {
+        assert!(
+            make_cmd().default_sigpipe,
+            "SIGPIPE should reset by default"
+        );
+        let cmd = make_cmd().with_default_sigpipe(false);
+        assert!(!cmd.default_sigpipe);
+    }
+
     #[test]
     fn test_builder_full_chain() {
         let cmd = make_cmd()
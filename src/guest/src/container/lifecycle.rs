@@ -5,6 +5,7 @@
 
 use super::command::ContainerCommand;
 use super::spec::UserMount;
+use super::start::DnsSettings;
 use super::stdio::ContainerStdio;
 use super::{kill, spec, start};
 use crate::layout::GuestLayout;
@@ -45,8 +46,12 @@ pub struct Container {
     state_root: PathBuf,
     bundle_path: PathBuf,
     env: HashMap<String, String>,
+    /// Init process working directory, the default cwd of exec commands.
+    workdir: String,
     /// Resolved (uid, gid) from image USER directive, propagated to exec commands.
     user: (u32, u32),
+    /// Capabilities (`CAP_*` names) of the init process, shared by exec commands.
+    capabilities: Vec<String>,
     /// Stdio pipes that keep init process alive.
     /// Dropping this closes pipes → init gets EOF → init exits.
     #[allow(dead_code)]
@@ -74,6 +79,10 @@ impl Container {
     /// - `env`: Environment variables in "KEY=VALUE" format
     /// - `workdir`: Working directory inside container
     /// - `user_mounts`: Bind mounts from guest VM paths into container
+    /// - `userns`: User namespace ID mappings; `None` shares the guest's namespace
+    /// - `dns`: Resolver settings for `/etc/resolv.conf` and `/etc/hosts`
+    /// - `read_only_rootfs`: Mount the rootfs read-only inside the container
+    /// - `capabilities`: `CAP_*` names granted to the init process and execs
     ///
     /// # Errors
     ///
@@ -81,6 +90,7 @@ impl Container {
     /// - Failed to create container directory
     /// - Failed to create or start container
     /// - Init process exited immediately
+    #[allow(clippy::too_many_arguments)]
     pub fn start(
         container_id: &str,
         rootfs: impl AsRef<Path>,
@@ -89,6 +99,10 @@ impl Container {
         workdir: impl AsRef<Path>,
         user: &str,
         user_mounts: Vec<UserMount>,
+        userns: Option<spec::UserNamespaceConfig>,
+        dns: &DnsSettings,
+        read_only_rootfs: bool,
+        capabilities: Vec<String>,
     ) -> BoxliteResult<Self> {
         let rootfs = rootfs.as_ref();
         let workdir = workdir.as_ref();
@@ -119,16 +133,23 @@ impl Container {
             .ok_or_else(|| BoxliteError::Internal("Invalid rootfs path".to_string()))?;
         let (uid, gid) = spec::resolve_user(rootfs_str, user)?;
 
+        // Guest-side identity of the container user. Volume idmaps target this,
+        // since inside a user namespace the container uid is translated again.
+        let (guest_uid, guest_gid) = match &userns {
+            Some(userns) => Self::prepare_userns_rootfs(rootfs, userns, uid, gid)?,
+            None => (uid, gid),
+        };
+
         // Auto-idmap: remap volume UIDs when host owner differs from container user.
         // Uses a full-range swap mapping so all UIDs remain valid (no overflow).
         for mount in &user_mounts {
-            if mount.read_only || mount.owner_uid == uid {
+            if mount.read_only || mount.owner_uid == guest_uid {
                 continue;
             }
             let uid_mappings =
-                crate::storage::idmap::build_swap_mapping(mount.owner_uid, uid, 65536);
+                crate::storage::idmap::build_swap_mapping(mount.owner_uid, guest_uid, 65536);
             let gid_mappings =
-                crate::storage::idmap::build_swap_mapping(mount.owner_gid, gid, 65536);
+                crate::storage::idmap::build_swap_mapping(mount.owner_gid, guest_gid, 65536);
 
             let mount_path = std::path::Path::new(&mount.source);
             match crate::storage::idmap::remap_mount(mount_path, &uid_mappings, &gid_mappings) {
@@ -136,8 +157,8 @@ impl Container {
                     "Auto-idmap: {}:{} → {}:{} on {}",
                     mount.owner_uid,
                     mount.owner_gid,
-                    uid,
-                    gid,
+                    guest_uid,
+                    guest_gid,
                     mount.source
                 ),
                 Ok(false) => {
@@ -163,6 +184,10 @@ impl Container {
             gid,
             &layout.containers_dir(),
             &user_mounts,
+            userns.as_ref(),
+            dns,
+            read_only_rootfs,
+            &capabilities,
         )?;
 
         // Create stdio pipes before container creation.
@@ -178,12 +203,54 @@ impl Container {
             state_root,
             bundle_path,
             env: env_map,
+            workdir: workdir.to_string_lossy().into_owned(),
             user: (uid, gid),
+            capabilities,
             stdio,
             is_shutdown: std::sync::atomic::AtomicBool::new(false),
         })
     }
 
+    /// Idmap the container rootfs with the user namespace mappings.
+    ///
+    /// Files stored as uid 0 on disk must show up as container root, which
+    /// means the mount has to present them as the guest uid that container
+    /// root maps to. Without idmapped mounts the rootfs would appear owned by
+    /// the overflow uid, so this is a hard error rather than a warning.
+    ///
+    /// Returns the guest (uid, gid) the container user maps to.
+    fn prepare_userns_rootfs(
+        rootfs: &Path,
+        userns: &spec::UserNamespaceConfig,
+        uid: u32,
+        gid: u32,
+    ) -> BoxliteResult<(u32, u32)> {
+        let (Some(guest_uid), Some(guest_gid)) = (userns.host_uid(uid), userns.host_gid(gid))
+        else {
+            return Err(BoxliteError::Config(format!(
+                "container user {}:{} is not covered by the user namespace mappings",
+                uid, gid
+            )));
+        };
+
+        if !crate::storage::idmap::remap_mount(rootfs, &userns.uid_mappings, &userns.gid_mappings)?
+        {
+            return Err(BoxliteError::Unsupported(format!(
+                "user namespace requires idmapped mount support for rootfs {}",
+                rootfs.display()
+            )));
+        }
+
+        tracing::info!(
+            uid,
+            gid,
+            guest_uid,
+            guest_gid,
+            "Container runs in a user namespace with idmapped rootfs"
+        );
+        Ok((guest_uid, guest_gid))
+    }
+
     /// Check if container init process is running
     ///
     /// Returns `true` if the container is in Running state, `false` otherwise.
@@ -240,6 +307,14 @@ impl Container {
         &self.id
     }
 
+    /// PID (in the guest) of the container's init process, if it is alive.
+    pub fn init_pid(&self) -> Option<i32> {
+        LibContainer::load(self.container_state_path())
+            .ok()?
+            .pid()
+            .map(|pid| pid.as_raw())
+    }
+
     /// Create a command builder for executing processes in this container
     ///
     /// Returns a Command builder. Use `.cmd()` to set the program to execute.
@@ -267,6 +342,8 @@ impl Container {
             self.user,
             self.bundle_path.join("rootfs"),
         )
+        .current_dir(&self.workdir)
+        .with_capabilities(self.capabilities.clone())
     }
 
     /// Drain init process stdout and stderr.
@@ -6,6 +6,7 @@
 //!
 //! - [`Container`]: OCI container lifecycle (create, start, check status)
 //! - [`ContainerCommand`]: Builder for executing commands inside container
+//! - [`list_namespace_processes`]: Processes in a container's PID namespace
 //! - [`crate::service::exec::exec_handle::ExecHandle`]: Handle to a running process
 //!
 //! # Example
@@ -68,6 +69,8 @@ mod kill;
 #[cfg(target_os = "linux")]
 mod lifecycle;
 #[cfg(target_os = "linux")]
+mod processes;
+#[cfg(target_os = "linux")]
 mod spec;
 #[cfg(target_os = "linux")]
 mod start;
@@ -76,9 +79,15 @@ mod stdio;
 #[cfg(target_os = "linux")]
 pub(crate) mod zygote;
 
+#[cfg(target_os = "linux")]
+pub use capabilities::resolve_capabilities;
 #[cfg(target_os = "linux")]
 pub(crate) use command::SpawnResult;
 #[cfg(target_os = "linux")]
 pub use lifecycle::Container;
 #[cfg(target_os = "linux")]
-pub use spec::UserMount;
+pub use processes::{list_namespace_processes, ProcessEntry};
+#[cfg(target_os = "linux")]
+pub use spec::{UserMount, UserNamespaceConfig};
+#[cfg(target_os = "linux")]
+pub use start::DnsSettings;
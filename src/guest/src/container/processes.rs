@@ -0,0 +1,156 @@
+//! Process listing for a container's PID namespace.
+//!
+//! Walks the guest's `/proc` and keeps the processes whose PID namespace is
+//! the container init's. PIDs are translated to the ones seen inside the
+//! container, so the listing matches what `ps` would print there.
+
+use std::collections::HashMap;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+
+/// One process in a container's PID namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessEntry {
+    /// PID inside the container.
+    pub pid: i32,
+    /// Parent PID inside the container; 0 when the parent is outside it.
+    pub ppid: i32,
+    /// Command line joined by spaces, or `[comm]` when it is empty.
+    pub cmd: String,
+    /// Resident set size in KiB.
+    pub rss_kb: u64,
+    /// Single-letter scheduler state (`R`, `S`, `D`, `Z`, ...).
+    pub state: String,
+}
+
+/// List the processes sharing `init_pid`'s PID namespace, ordered by PID.
+///
+/// Processes that exit mid-scan are skipped. Fails only when `init_pid`'s
+/// namespace can't be read, e.g. because the container already stopped.
+pub fn list_namespace_processes(init_pid: i32) -> BoxliteResult<Vec<ProcessEntry>> {
+    let target_ns = pid_namespace(init_pid).map_err(|e| {
+        BoxliteError::Internal(format!(
+            "Failed to read PID namespace of container init {}: {}",
+            init_pid, e
+        ))
+    })?;
+
+    let all = procfs::process::all_processes()
+        .map_err(|e| BoxliteError::Internal(format!("Failed to scan /proc: {}", e)))?;
+
+    // (guest pid, guest ppid, entry with container pid)
+    let mut scanned = Vec::new();
+    for process in all.flatten() {
+        if pid_namespace(process.pid).ok().as_ref() != Some(&target_ns) {
+            continue;
+        }
+        let Ok(status) = process.status() else {
+            continue;
+        };
+        let cmdline = process.cmdline().unwrap_or_default();
+        let ns_pid = status
+            .nspid
+            .as_ref()
+            .and_then(|pids| pids.last().copied())
+            .unwrap_or(process.pid);
+        scanned.push((
+            process.pid,
+            status.ppid,
+            ProcessEntry {
+                pid: ns_pid,
+                ppid: 0,
+                cmd: format_cmd(&cmdline, &status.name),
+                rss_kb: status.vmrss.unwrap_or(0),
+                state: state_letter(&status.state),
+            },
+        ));
+    }
+
+    Ok(resolve_parents(scanned))
+}
+
+/// Identity of `pid`'s PID namespace, e.g. `pid:[4026531836]`.
+fn pid_namespace(pid: i32) -> std::io::Result<std::path::PathBuf> {
+    std::fs::read_link(format!("/proc/{}/ns/pid", pid))
+}
+
+/// Rewrite guest parent PIDs to container PIDs and sort by PID.
+fn resolve_parents(scanned: Vec<(i32, i32, ProcessEntry)>) -> Vec<ProcessEntry> {
+    let to_ns: HashMap<i32, i32> = scanned
+        .iter()
+        .map(|(guest_pid, _, entry)| (*guest_pid, entry.pid))
+        .collect();
+
+    let mut entries: Vec<ProcessEntry> = scanned
+        .into_iter()
+        .map(|(_, guest_ppid, mut entry)| {
+            entry.ppid = to_ns.get(&guest_ppid).copied().unwrap_or(0);
+            entry
+        })
+        .collect();
+    entries.sort_by_key(|entry| entry.pid);
+    entries
+}
+
+fn format_cmd(cmdline: &[String], comm: &str) -> String {
+    if cmdline.is_empty() {
+        format!("[{}]", comm)
+    } else {
+        cmdline.join(" ")
+    }
+}
+
+/// `S (sleeping)` -> `S`.
+fn state_letter(state: &str) -> String {
+    state.chars().next().map(String::from).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pid: i32) -> ProcessEntry {
+        ProcessEntry {
+            pid,
+            ppid: -1,
+            cmd: String::new(),
+            rss_kb: 0,
+            state: "S".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_parents_maps_to_container_pids() {
+        // Guest pids 500/501/502 are container pids 1/2/3; init's parent
+        // (guest pid 400) lives outside the container.
+        let entries = resolve_parents(vec![
+            (502, 501, entry(3)),
+            (500, 400, entry(1)),
+            (501, 500, entry(2)),
+        ]);
+
+        let pairs: Vec<(i32, i32)> = entries.iter().map(|e| (e.pid, e.ppid)).collect();
+        assert_eq!(pairs, vec![(1, 0), (2, 1), (3, 2)]);
+    }
+
+    #[test]
+    fn test_format_cmd_and_state() {
+        assert_eq!(
+            format_cmd(&["sleep".to_string(), "300".to_string()], "sleep"),
+            "sleep 300"
+        );
+        assert_eq!(format_cmd(&[], "kworker/0:1"), "[kworker/0:1]");
+        assert_eq!(state_letter("S (sleeping)"), "S");
+        assert_eq!(state_letter(""), "");
+    }
+
+    #[test]
+    fn test_lists_own_namespace() {
+        let me = std::process::id() as i32;
+        let entries = list_namespace_processes(me).unwrap();
+        assert!(
+            entries.iter().any(|e| e.rss_kb > 0 && !e.cmd.is_empty()),
+            "{entries:?}"
+        );
+    }
+}
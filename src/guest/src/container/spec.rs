@@ -2,7 +2,8 @@
 //!
 //! Creates OCI-compliant runtime specifications following the runtime-spec standard.
 
-use super::capabilities::default_capabilities;
+use super::capabilities::parse_capabilities;
+use crate::storage::idmap::IdMapping;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use std::path::Path;
 
@@ -27,13 +28,42 @@ pub struct UserMount {
     pub owner_gid: u32,
 }
 
+/// User namespace ID mappings for the container.
+///
+/// Reuses the idmapped-mount `IdMapping` so the same ranges drive both the
+/// OCI spec's uid/gid maps and the rootfs idmap.
+#[derive(Debug, Clone)]
+pub struct UserNamespaceConfig {
+    pub uid_mappings: Vec<IdMapping>,
+    pub gid_mappings: Vec<IdMapping>,
+}
+
+impl UserNamespaceConfig {
+    /// Guest UID that a container UID maps to, if it is mapped at all.
+    pub fn host_uid(&self, uid: u32) -> Option<u32> {
+        map_to_host(&self.uid_mappings, uid)
+    }
+
+    /// Guest GID that a container GID maps to, if it is mapped at all.
+    pub fn host_gid(&self, gid: u32) -> Option<u32> {
+        map_to_host(&self.gid_mappings, gid)
+    }
+}
+
+fn map_to_host(mappings: &[IdMapping], id: u32) -> Option<u32> {
+    mappings.iter().find_map(|m| {
+        let offset = id.checked_sub(m.container_id)?;
+        (offset < m.count).then(|| m.host_id + offset)
+    })
+}
+
 /// Create OCI runtime specification with default configuration
 ///
 /// Builds an OCI spec with:
 /// - Standard mounts (/proc, /dev, /sys, etc.)
 /// - User-specified bind mounts (volumes)
-/// - Default capabilities (matching runc defaults)
-/// - Standard namespaces (pid, ipc, uts, mount)
+/// - `capabilities` (`CAP_*` names) as every capability set of the process
+/// - Standard namespaces (pid, ipc, uts, mount), plus user when `userns` is set
 /// - UID/GID mappings for user namespace
 /// - Configurable user (resolved uid/gid)
 /// - Resource limits (rlimits)
@@ -54,9 +84,15 @@ pub fn create_oci_spec(
     gid: u32,
     bundle_path: &Path,
     user_mounts: &[UserMount],
+    userns: Option<&UserNamespaceConfig>,
+    read_only_rootfs: bool,
+    capabilities: &[String],
 ) -> BoxliteResult<Spec> {
-    let caps = build_default_capabilities()?;
-    let namespaces = build_default_namespaces()?;
+    let caps = build_capabilities(capabilities)?;
+    let mut namespaces = build_default_namespaces()?;
+    if userns.is_some() {
+        namespaces.push(build_namespace(LinuxNamespaceType::User)?);
+    }
     let mut mounts = build_standard_mounts(bundle_path)?;
 
     // Add user-specified bind mounts
@@ -91,8 +127,8 @@ pub fn create_oci_spec(
     }
 
     let process = build_process_spec(entrypoint, env, workdir, uid, gid, caps)?;
-    let root = build_root_spec(rootfs)?;
-    let linux = build_linux_spec(container_id, namespaces)?;
+    let root = build_root_spec(rootfs, read_only_rootfs)?;
+    let linux = build_linux_spec(container_id, namespaces, userns)?;
 
     SpecBuilder::default()
         .version("1.0.2")
@@ -264,9 +300,9 @@ fn find_group_in_group_file(rootfs: &str, name: &str) -> BoxliteResult<u32> {
 // Spec Component Builders
 // ====================
 
-/// Build default Linux capabilities matching Docker/OCI defaults.
-fn build_default_capabilities() -> BoxliteResult<oci_spec::runtime::LinuxCapabilities> {
-    let caps = default_capabilities();
+/// Build Linux capabilities granting `names` in every set.
+fn build_capabilities(names: &[String]) -> BoxliteResult<oci_spec::runtime::LinuxCapabilities> {
+    let caps = parse_capabilities(names)?;
 
     LinuxCapabilitiesBuilder::default()
         .bounding(caps.clone())
@@ -318,13 +354,14 @@ fn build_process_spec(
         .map_err(|e| BoxliteError::Internal(format!("Failed to build user spec: {}", e)))?;
 
     // Build rlimits
-    // Set NOFILE to 1048576 to match Docker's defaults
-    // This allows applications to open many files/connections (databases, web servers, etc.)
+    // NOFILE follows the agent's own limit, which libkrun sets at boot: 1048576
+    // (Docker's default) unless the box was given a `nofile` ulimit.
+    let (soft, hard) = agent_nofile_limit();
     #[allow(unused)]
     let rlimits = vec![PosixRlimitBuilder::default()
         .typ(PosixRlimitType::RlimitNofile)
-        .hard(1024u64 * 1024u64)
-        .soft(1024u64 * 1024u64)
+        .hard(hard)
+        .soft(soft)
         .build()
         .map_err(|e| BoxliteError::Internal(format!("Failed to build rlimit: {}", e)))?];
 
@@ -341,6 +378,16 @@ fn build_process_spec(
         .map_err(|e| BoxliteError::Internal(format!("Failed to build process spec: {}", e)))
 }
 
+/// The agent's `RLIMIT_NOFILE`, falling back to 1048576 if it can't be read.
+fn agent_nofile_limit() -> (u64, u64) {
+    use nix::sys::resource::{getrlimit, Resource};
+
+    const DEFAULT_NOFILE: u64 = 1024 * 1024;
+    getrlimit(Resource::RLIMIT_NOFILE)
+        .map(|(soft, hard)| (soft as u64, hard as u64))
+        .unwrap_or((DEFAULT_NOFILE, DEFAULT_NOFILE))
+}
+
 /// Build an OCI `Process` for a TTY tenant exec, with `terminal=true`.
 ///
 /// libcontainer 0.6's `check_terminal` rejects a console socket unless the
@@ -354,10 +401,13 @@ pub(crate) fn build_tty_exec_process(
     cwd: &str,
     uid: u32,
     gid: u32,
+    additional_gids: Vec<u32>,
+    capabilities: &[String],
 ) -> BoxliteResult<oci_spec::runtime::Process> {
     let user = UserBuilder::default()
         .uid(uid)
         .gid(gid)
+        .additional_gids(additional_gids)
         .build()
         .map_err(|e| BoxliteError::Internal(format!("Failed to build user spec: {}", e)))?;
 
@@ -367,17 +417,19 @@ pub(crate) fn build_tty_exec_process(
         .args(args.to_vec())
         .env(env)
         .cwd(cwd)
-        .capabilities(build_default_capabilities()?)
+        .capabilities(build_capabilities(capabilities)?)
         .no_new_privileges(false)
         .build()
         .map_err(|e| BoxliteError::Internal(format!("Failed to build tty exec process: {}", e)))
 }
 
 /// Build root filesystem specification
-fn build_root_spec(rootfs: &str) -> BoxliteResult<oci_spec::runtime::Root> {
+/// Build the root spec. A read-only root is remounted `ro` by the runtime
+/// after the spec's mounts are in place, so those mounts stay writable.
+fn build_root_spec(rootfs: &str, readonly: bool) -> BoxliteResult<oci_spec::runtime::Root> {
     RootBuilder::default()
         .path(rootfs)
-        .readonly(false)
+        .readonly(readonly)
         .build()
         .map_err(|e| BoxliteError::Internal(format!("Failed to build root spec: {}", e)))
 }
@@ -386,22 +438,28 @@ fn build_root_spec(rootfs: &str) -> BoxliteResult<oci_spec::runtime::Root> {
 fn build_linux_spec(
     container_id: &str,
     namespaces: Vec<oci_spec::runtime::LinuxNamespace>,
+    userns: Option<&UserNamespaceConfig>,
 ) -> BoxliteResult<oci_spec::runtime::Linux> {
     // UID/GID mappings for user namespace
-    // Map full range of UIDs/GIDs to allow non-root users (nginx=33, etc.)
-    let uid_mappings = vec![LinuxIdMappingBuilder::default()
-        .host_id(0u32)
-        .container_id(0u32)
-        .size(65536u32)  // Map 0-65535 to cover all common users
-        .build()
-        .map_err(|e| BoxliteError::Internal(format!("Failed to build UID mapping: {}", e)))?];
-
-    let gid_mappings = vec![LinuxIdMappingBuilder::default()
-        .host_id(0u32)
-        .container_id(0u32)
-        .size(65536u32)  // Map 0-65535 to cover all common groups
-        .build()
-        .map_err(|e| BoxliteError::Internal(format!("Failed to build GID mapping: {}", e)))?];
+    // Without an explicit userns, map the full range of UIDs/GIDs 1:1 to allow
+    // non-root users (nginx=33, etc.)
+    let (uid_mappings, gid_mappings) = match userns {
+        Some(userns) => (
+            build_id_mappings(&userns.uid_mappings, "UID")?,
+            build_id_mappings(&userns.gid_mappings, "GID")?,
+        ),
+        None => {
+            let identity = [IdMapping {
+                host_id: 0,
+                container_id: 0,
+                count: 65536, // Map 0-65535 to cover all common users and groups
+            }];
+            (
+                build_id_mappings(&identity, "UID")?,
+                build_id_mappings(&identity, "GID")?,
+            )
+        }
+    };
 
     // Masked paths for security (hide sensitive /proc and /sys entries)
     #[allow(unused)]
@@ -444,6 +502,25 @@ fn build_linux_spec(
         .map_err(|e| BoxliteError::Internal(format!("Failed to build linux spec: {}", e)))
 }
 
+fn build_id_mappings(
+    mappings: &[IdMapping],
+    kind: &str,
+) -> BoxliteResult<Vec<oci_spec::runtime::LinuxIdMapping>> {
+    mappings
+        .iter()
+        .map(|m| {
+            LinuxIdMappingBuilder::default()
+                .host_id(m.host_id)
+                .container_id(m.container_id)
+                .size(m.count)
+                .build()
+                .map_err(|e| {
+                    BoxliteError::Internal(format!("Failed to build {} mapping: {}", kind, e))
+                })
+        })
+        .collect()
+}
+
 /// Build standard mounts for container filesystem
 fn build_standard_mounts(bundle_path: &Path) -> BoxliteResult<Vec<Mount>> {
     let mut mounts = vec![
@@ -581,7 +658,9 @@ fn build_standard_mounts(bundle_path: &Path) -> BoxliteResult<Vec<Mount>> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::capabilities::capability_names;
     use super::*;
+    use oci_spec::runtime::Capability;
     use std::fs;
 
     /// Create a temp rootfs with /etc/passwd and /etc/group for testing.
@@ -965,4 +1044,150 @@ mod tests {
         let err = resolve_user(r, "short").unwrap_err().to_string();
         assert!(err.contains("User 'short' not found"), "got: {}", err);
     }
+
+    // ==================
+    // User namespace
+    // ==================
+
+    fn test_userns() -> UserNamespaceConfig {
+        let range = IdMapping {
+            host_id: 100000,
+            container_id: 0,
+            count: 65536,
+        };
+        UserNamespaceConfig {
+            uid_mappings: vec![range.clone()],
+            gid_mappings: vec![range],
+        }
+    }
+
+    #[test]
+    fn test_tty_exec_process_sets_additional_gids() {
+        let process = build_tty_exec_process(
+            &["id".to_string()],
+            &[],
+            "/",
+            1000,
+            1000,
+            vec![3000],
+            &capability_names(),
+        )
+        .unwrap();
+        assert_eq!(process.user().uid(), 1000);
+        assert_eq!(process.user().additional_gids(), &Some(vec![3000]));
+    }
+
+    #[test]
+    fn test_userns_maps_container_ids_to_guest() {
+        let userns = test_userns();
+        assert_eq!(userns.host_uid(0), Some(100000));
+        assert_eq!(userns.host_gid(1000), Some(101000));
+        assert_eq!(userns.host_uid(65535), Some(165535));
+        assert_eq!(userns.host_uid(65536), None);
+    }
+
+    #[test]
+    fn test_oci_spec_read_only_rootfs() {
+        let bundle = tempfile::tempdir().unwrap();
+        let build = |read_only_rootfs: bool| {
+            create_oci_spec(
+                "test",
+                "/rootfs",
+                &["sh".to_string()],
+                &[],
+                "/",
+                0,
+                0,
+                bundle.path(),
+                &[],
+                None,
+                read_only_rootfs,
+                &capability_names(),
+            )
+            .unwrap()
+        };
+
+        assert_eq!(
+            build(false).root().as_ref().unwrap().readonly(),
+            Some(false)
+        );
+        assert_eq!(build(true).root().as_ref().unwrap().readonly(), Some(true));
+    }
+
+    #[test]
+    fn test_oci_spec_capabilities() {
+        let bundle = tempfile::tempdir().unwrap();
+        let spec = create_oci_spec(
+            "test",
+            "/rootfs",
+            &["sh".to_string()],
+            &[],
+            "/",
+            0,
+            0,
+            bundle.path(),
+            &[],
+            None,
+            false,
+            &["CAP_NET_ADMIN".to_string()],
+        )
+        .unwrap();
+        let caps = spec
+            .process()
+            .as_ref()
+            .unwrap()
+            .capabilities()
+            .as_ref()
+            .unwrap();
+        let bounding = caps.bounding().as_ref().unwrap();
+        assert_eq!(bounding.len(), 1);
+        assert!(bounding.contains(&Capability::NetAdmin));
+        assert_eq!(caps.effective(), caps.bounding());
+    }
+
+    #[test]
+    fn test_oci_spec_userns() {
+        let bundle = tempfile::tempdir().unwrap();
+        let build = |userns: Option<&UserNamespaceConfig>| {
+            create_oci_spec(
+                "test",
+                "/rootfs",
+                &["sh".to_string()],
+                &[],
+                "/",
+                0,
+                0,
+                bundle.path(),
+                &[],
+                userns,
+                false,
+                &capability_names(),
+            )
+            .unwrap()
+        };
+        let has_user_ns = |spec: &Spec| {
+            spec.linux()
+                .as_ref()
+                .unwrap()
+                .namespaces()
+                .as_ref()
+                .unwrap()
+                .iter()
+                .any(|ns| ns.typ() == LinuxNamespaceType::User)
+        };
+
+        let shared = build(None);
+        assert!(!has_user_ns(&shared));
+
+        let userns = test_userns();
+        let spec = build(Some(&userns));
+        assert!(has_user_ns(&spec));
+        let linux = spec.linux().as_ref().unwrap();
+        let uid_map = linux.uid_mappings().as_ref().unwrap();
+        assert_eq!(uid_map.len(), 1);
+        assert_eq!(uid_map[0].container_id(), 0);
+        assert_eq!(uid_map[0].host_id(), 100000);
+        assert_eq!(uid_map[0].size(), 65536);
+        assert_eq!(linux.gid_mappings().as_ref().unwrap()[0].host_id(), 100000);
+    }
 }
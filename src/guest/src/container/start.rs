@@ -9,8 +9,27 @@ use libcontainer::container::builder::ContainerBuilder;
 use libcontainer::container::Container as LibContainer;
 use libcontainer::syscall::syscall::SyscallType;
 use std::fs;
+use std::net::IpAddr;
 use std::path::{Path, PathBuf};
 
+/// gvproxy's DNS server, the default container nameserver.
+const DNS_GATEWAY: &str = "192.168.127.1";
+/// Search domain used when none are configured.
+const DEFAULT_SEARCH: &str = "localdomain";
+
+/// Container resolver settings (from `ContainerInitRequest.dns`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DnsSettings {
+    /// Nameservers; empty uses the gvproxy gateway.
+    pub nameservers: Vec<IpAddr>,
+    /// Search domains; empty uses `localdomain`.
+    pub search: Vec<String>,
+    /// resolv.conf `options` entries.
+    pub options: Vec<String>,
+    /// Extra `/etc/hosts` entries as `(ip, name)`.
+    pub hosts: Vec<(IpAddr, String)>,
+}
+
 // ====================
 // Setup Functions (Prepare Phase)
 // ====================
@@ -46,11 +65,11 @@ pub(crate) fn validate_container_inputs(
 pub(crate) fn create_container_etc_files(
     bundle_path: &Path,
     _container_id: &str,
+    dns: &DnsSettings,
 ) -> BoxliteResult<()> {
     use std::os::unix::fs::OpenOptionsExt;
 
     const DEFAULT_HOSTNAME: &str = "boxlite";
-    const DNS_GATEWAY: &str = "192.168.127.1";
     const FILE_MODE: u32 = 0o644; // matches containerd WriteFile(path, content, 0644)
 
     let write_etc_file = |name: &str, content: &str| -> BoxliteResult<()> {
@@ -69,29 +88,47 @@ pub(crate) fn create_container_etc_files(
 
     write_etc_file("hostname", &format!("{DEFAULT_HOSTNAME}\n"))?;
 
-    write_etc_file(
-        "hosts",
-        &format!(
-            "127.0.0.1\tlocalhost\n\
-             ::1\t\tlocalhost ip6-localhost ip6-loopback\n\
-             fe00::0\t\tip6-localnet\n\
-             ff00::0\t\tip6-mcastprefix\n\
-             ff02::1\t\tip6-allnodes\n\
-             ff02::2\t\tip6-allrouters\n\
-             127.0.1.1\t{DEFAULT_HOSTNAME}\n"
-        ),
-    )?;
+    let mut hosts = format!(
+        "127.0.0.1\tlocalhost\n\
+         ::1\t\tlocalhost ip6-localhost ip6-loopback\n\
+         fe00::0\t\tip6-localnet\n\
+         ff00::0\t\tip6-mcastprefix\n\
+         ff02::1\t\tip6-allnodes\n\
+         ff02::2\t\tip6-allrouters\n\
+         127.0.1.1\t{DEFAULT_HOSTNAME}\n"
+    );
+    for (ip, name) in &dns.hosts {
+        hosts.push_str(&format!("{ip}\t{name}\n"));
+    }
+    write_etc_file("hosts", &hosts)?;
 
-    write_etc_file(
-        "resolv.conf",
-        &format!("# Generated by BoxLite\nnameserver {DNS_GATEWAY}\nsearch localdomain\n"),
-    )?;
+    write_etc_file("resolv.conf", &render_resolv_conf(dns))?;
 
     tracing::debug!("Created /etc/hostname, /etc/hosts, /etc/resolv.conf in bundle dir");
 
     Ok(())
 }
 
+/// Render `/etc/resolv.conf` for `dns`.
+pub(crate) fn render_resolv_conf(dns: &DnsSettings) -> String {
+    let mut out = String::from("# Generated by BoxLite\n");
+    if dns.nameservers.is_empty() {
+        out.push_str(&format!("nameserver {DNS_GATEWAY}\n"));
+    }
+    for ns in &dns.nameservers {
+        out.push_str(&format!("nameserver {ns}\n"));
+    }
+    if dns.search.is_empty() {
+        out.push_str(&format!("search {DEFAULT_SEARCH}\n"));
+    } else {
+        out.push_str(&format!("search {}\n", dns.search.join(" ")));
+    }
+    if !dns.options.is_empty() {
+        out.push_str(&format!("options {}\n", dns.options.join(" ")));
+    }
+    out
+}
+
 /// Create OCI bundle (config.json + rootfs reference)
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn create_oci_bundle(
@@ -104,6 +141,10 @@ pub(crate) fn create_oci_bundle(
     gid: u32,
     bundle_root: &Path,
     user_mounts: &[spec::UserMount],
+    userns: Option<&spec::UserNamespaceConfig>,
+    dns: &DnsSettings,
+    read_only_rootfs: bool,
+    capabilities: &[String],
 ) -> BoxliteResult<PathBuf> {
     let bundle_path = bundle_root.join(container_id);
 
@@ -117,7 +158,7 @@ pub(crate) fn create_oci_bundle(
 
     // Create /etc/hosts, /etc/hostname and /etc/resolv.conf files
     // These will be bind-mounted into the container to provide hostname and DNS resolution
-    create_container_etc_files(&bundle_path, container_id)?;
+    create_container_etc_files(&bundle_path, container_id, dns)?;
 
     let spec = spec::create_oci_spec(
         container_id,
@@ -133,6 +174,9 @@ pub(crate) fn create_oci_bundle(
         gid,
         &bundle_path,
         user_mounts,
+        userns,
+        read_only_rootfs,
+        capabilities,
     )?;
     let config_path = bundle_path.join("config.json");
 
@@ -148,6 +192,7 @@ pub(crate) fn create_oci_bundle(
         container_id,
         bundle_path = %bundle_path.display(),
         user_mounts_count = user_mounts.len(),
+        userns = userns.is_some(),
         "Created OCI bundle"
     );
 
@@ -313,4 +358,48 @@ mod tests {
 
         assert_eq!(status, ContainerStatus::Running);
     }
+
+    #[test]
+    fn resolv_conf_defaults_to_gvproxy_gateway() {
+        assert_eq!(
+            render_resolv_conf(&DnsSettings::default()),
+            "# Generated by BoxLite\nnameserver 192.168.127.1\nsearch localdomain\n"
+        );
+    }
+
+    #[test]
+    fn resolv_conf_and_hosts_from_custom_dns() {
+        let dns = DnsSettings {
+            nameservers: vec![
+                "1.1.1.1".parse().unwrap(),
+                "2606:4700::1111".parse().unwrap(),
+            ],
+            search: vec!["corp.example".into(), "example".into()],
+            options: vec!["ndots:2".into(), "timeout:1".into()],
+            hosts: vec![(
+                "192.168.127.254".parse().unwrap(),
+                "host.boxlite.internal".into(),
+            )],
+        };
+        assert_eq!(
+            render_resolv_conf(&dns),
+            "# Generated by BoxLite\n\
+             nameserver 1.1.1.1\n\
+             nameserver 2606:4700::1111\n\
+             search corp.example example\n\
+             options ndots:2 timeout:1\n"
+        );
+
+        let dir = tempfile::TempDir::new().unwrap();
+        create_container_etc_files(dir.path(), "test-container", &dns).unwrap();
+        let hosts = fs::read_to_string(dir.path().join("hosts")).unwrap();
+        assert!(
+            hosts.ends_with("192.168.127.254\thost.boxlite.internal\n"),
+            "{hosts}"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("resolv.conf")).unwrap(),
+            render_resolv_conf(&dns)
+        );
+    }
 }
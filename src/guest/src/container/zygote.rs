@@ -11,7 +11,6 @@
 //!
 //! See `docs/investigations/concurrent-exec-deadlock.md` for full analysis.
 
-use super::capabilities::capability_names;
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use libcontainer::container::builder::ContainerBuilder;
 use libcontainer::syscall::syscall::SyscallType;
@@ -58,6 +57,13 @@ pub(crate) struct BuildSpec {
     pub args: Vec<String>,
     pub uid: u32,
     pub gid: u32,
+    /// Supplementary group IDs.
+    pub additional_gids: Vec<u32>,
+    /// Capabilities (`CAP_*` names) of the process.
+    pub capabilities: Vec<String>,
+    /// Start the process with the default SIGPIPE disposition instead of
+    /// inheriting the agent's SIG_IGN (see `do_build`).
+    pub default_sigpipe: bool,
 }
 
 /// Build outcome. Invalid states are unrepresentable.
@@ -265,9 +271,16 @@ fn do_build(spec: BuildSpec, fds: Option<[RawFd; 3]>) -> BuildResult {
             // path passes no stdio fds — youki wires the PTY slave instead.
             let env_vec: Vec<String> = spec.env.iter().map(|(k, v)| format!("{k}={v}")).collect();
             let cwd = spec.cwd.to_str().unwrap_or("/");
-            let process =
-                super::spec::build_tty_exec_process(&spec.args, &env_vec, cwd, spec.uid, spec.gid)
-                    .map_err(|e| format!("build tty exec process: {e}"))?;
+            let process = super::spec::build_tty_exec_process(
+                &spec.args,
+                &env_vec,
+                cwd,
+                spec.uid,
+                spec.gid,
+                spec.additional_gids,
+                &spec.capabilities,
+            )
+            .map_err(|e| format!("build tty exec process: {e}"))?;
             let process_json = serde_json::to_vec(&process)
                 .map_err(|e| format!("serialize tty process.json: {e}"))?;
             let process_path = spec
@@ -288,7 +301,7 @@ fn do_build(spec: BuildSpec, fds: Option<[RawFd; 3]>) -> BuildResult {
             // terminal=false, non-detached.
             builder
                 .as_tenant()
-                .with_capabilities(capability_names())
+                .with_capabilities(spec.capabilities)
                 .with_no_new_privs(false)
                 .with_detach(false)
                 .with_cwd(Some(spec.cwd))
@@ -296,6 +309,7 @@ fn do_build(spec: BuildSpec, fds: Option<[RawFd; 3]>) -> BuildResult {
                 .with_container_args(spec.args)
                 .with_user(Some(spec.uid))
                 .with_group(Some(spec.gid))
+                .with_additional_gids(spec.additional_gids)
                 .build()
                 .map_err(|e| format!("build failed: {e}"))?
         };
@@ -320,12 +334,17 @@ fn do_build(spec: BuildSpec, fds: Option<[RawFd; 3]>) -> BuildResult {
     // restore SIG_IGN immediately after — the long-lived single-threaded zygote
     // keeps the agent's EPIPE-as-error behavior on its own IPC socket, while the
     // forked child (and everything it execs) starts with the standard default.
+    // Callers that want EPIPE semantics opt out via `default_sigpipe = false`.
     use nix::sys::signal::{signal, SigHandler, Signal};
-    let prev_sigpipe = unsafe { signal(Signal::SIGPIPE, SigHandler::SigDfl) };
+    let prev_sigpipe = if spec.default_sigpipe {
+        Some(unsafe { signal(Signal::SIGPIPE, SigHandler::SigDfl) })
+    } else {
+        None
+    };
 
     let result = build_fn();
 
-    if let Ok(prev) = prev_sigpipe {
+    if let Some(Ok(prev)) = prev_sigpipe {
         // SAFETY: restoring the disposition we just read; single-threaded zygote.
         unsafe {
             let _ = signal(Signal::SIGPIPE, prev);
@@ -487,6 +506,7 @@ fn recv_response(sock: RawFd) -> BoxliteResult<ZygoteResponse> {
 
 #[cfg(test)]
 mod tests {
+    use super::super::capabilities::capability_names;
     use super::*;
 
     // ========================================================================
@@ -511,6 +531,9 @@ mod tests {
             ],
             uid: 1000,
             gid: 1000,
+            additional_gids: vec![],
+            capabilities: capability_names(),
+            default_sigpipe: true,
         }
     }
 
@@ -606,6 +629,9 @@ mod tests {
             args: vec![],
             uid: 0,
             gid: 0,
+            additional_gids: vec![],
+            capabilities: capability_names(),
+            default_sigpipe: true,
         };
         let json = serde_json::to_vec(&spec).unwrap();
         let decoded: BuildSpec = serde_json::from_slice(&json).unwrap();
@@ -829,6 +855,9 @@ mod tests {
             args,
             uid: 65534,
             gid: 65534,
+            additional_gids: vec![],
+            capabilities: capability_names(),
+            default_sigpipe: true,
         };
 
         send_request(fd_a, &ZygoteRequest::Build(spec.clone()), None).unwrap();
@@ -858,6 +887,9 @@ mod tests {
             args: vec![],
             uid: 0,
             gid: 0,
+            additional_gids: vec![],
+            capabilities: capability_names(),
+            default_sigpipe: true,
         };
 
         let (a, _b) = socketpair(
@@ -1010,6 +1042,9 @@ mod tests {
                     args: vec!["echo".to_string()],
                     uid: 0,
                     gid: 0,
+                    additional_gids: vec![],
+                    capabilities: capability_names(),
+                    default_sigpipe: true,
                 };
                 z.build(spec, None).unwrap()
             }));
@@ -0,0 +1,145 @@
+//! Kernel module loading for guest init.
+//!
+//! Loads the modules requested via `BoxOptions::kernel_modules` from the
+//! guest rootfs. `modprobe` is preferred because it resolves dependencies;
+//! when it is missing or fails, the module file is located under
+//! `/lib/modules/<release>` and loaded with `insmod`.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SYS_MODULE_DIR: &str = "/sys/module";
+const MODULES_ROOT: &str = "/lib/modules";
+const OSRELEASE: &str = "/proc/sys/kernel/osrelease";
+
+/// Load every module in `names`, skipping ones already loaded or built in.
+///
+/// Stops at the first module that cannot be loaded.
+pub fn load_modules(names: &[String]) -> BoxliteResult<()> {
+    for name in names {
+        if is_loaded(Path::new(SYS_MODULE_DIR), name) {
+            tracing::debug!(module = %name, "Kernel module already available");
+            continue;
+        }
+        load_module(name)?;
+        tracing::info!(module = %name, "Loaded kernel module");
+    }
+    Ok(())
+}
+
+fn load_module(name: &str) -> BoxliteResult<()> {
+    let modprobe_err = match Command::new("modprobe").arg(name).output() {
+        Ok(output) if output.status.success() => return Ok(()),
+        Ok(output) => String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        Err(e) => format!("modprobe unavailable: {e}"),
+    };
+    tracing::debug!(module = %name, error = %modprobe_err, "modprobe failed, trying insmod");
+
+    let release = std::fs::read_to_string(OSRELEASE)
+        .map_err(|e| BoxliteError::Internal(format!("Failed to read {OSRELEASE}: {e}")))?;
+    let modules_dir = Path::new(MODULES_ROOT).join(release.trim());
+    let path = find_module_file(&modules_dir, name).ok_or_else(|| {
+        BoxliteError::NotFound(format!(
+            "kernel module {name:?} not found in {} ({modprobe_err})",
+            modules_dir.display()
+        ))
+    })?;
+
+    let output = Command::new("insmod")
+        .arg(&path)
+        .output()
+        .map_err(|e| BoxliteError::Internal(format!("Failed to execute insmod: {e}")))?;
+    if !output.status.success() {
+        return Err(BoxliteError::Internal(format!(
+            "insmod {} failed: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the kernel already provides `name` (loaded or built in).
+///
+/// The kernel reports module names with underscores, while users often
+/// write dashes (`ip6-tables`); both spellings refer to the same module.
+fn is_loaded(sys_module_dir: &Path, name: &str) -> bool {
+    sys_module_dir.join(canonical_name(name)).exists()
+}
+
+/// Find `<name>.ko[.gz|.xz|.zst]` anywhere under `modules_dir`.
+fn find_module_file(modules_dir: &Path, name: &str) -> Option<PathBuf> {
+    let wanted = canonical_name(name);
+    let mut dirs = vec![modules_dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+                continue;
+            }
+            let file_name = entry.file_name();
+            let Some(stem) = file_name.to_str().and_then(module_stem) else {
+                continue;
+            };
+            if canonical_name(stem) == wanted {
+                return Some(path);
+            }
+        }
+    }
+    None
+}
+
+/// Module name from a module file name, e.g. `tun.ko.xz` -> `tun`.
+fn module_stem(file_name: &str) -> Option<&str> {
+    [".ko", ".ko.gz", ".ko.xz", ".ko.zst"]
+        .iter()
+        .find_map(|ext| file_name.strip_suffix(ext))
+}
+
+fn canonical_name(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn module_stem_strips_compressed_extensions() {
+        assert_eq!(module_stem("tun.ko"), Some("tun"));
+        assert_eq!(module_stem("nf_tables.ko.xz"), Some("nf_tables"));
+        assert_eq!(module_stem("overlay.ko.zst"), Some("overlay"));
+        assert_eq!(module_stem("modules.dep"), None);
+    }
+
+    #[test]
+    fn find_module_file_searches_nested_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("kernel/drivers/net");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("tun.ko.gz"), b"").unwrap();
+        std::fs::write(dir.path().join("modules.dep"), b"").unwrap();
+
+        assert_eq!(
+            find_module_file(dir.path(), "tun"),
+            Some(nested.join("tun.ko.gz"))
+        );
+        assert_eq!(find_module_file(dir.path(), "nf_tables"), None);
+    }
+
+    #[test]
+    fn dashes_and_underscores_name_the_same_module() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ip6_tables.ko"), b"").unwrap();
+        std::fs::create_dir(dir.path().join("ip6_tables")).unwrap();
+
+        assert!(find_module_file(dir.path(), "ip6-tables").is_some());
+        assert!(is_loaded(dir.path(), "ip6-tables"));
+        assert!(!is_loaded(dir.path(), "tun"));
+    }
+}
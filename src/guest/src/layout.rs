@@ -42,6 +42,20 @@ impl ContainerLayout {
         self.root.join(dirs::ROOTFS)
     }
 
+    /// Lower directory: /run/boxlite/containers/{cid}/lower
+    ///
+    /// Mount point of the rootfs disk when it is the overlay lower layer.
+    pub fn lower_dir(&self) -> PathBuf {
+        self.root.join("lower")
+    }
+
+    /// Upper disk directory: /run/boxlite/containers/{cid}/upper-disk
+    ///
+    /// Mount point of the disk holding the overlay upper/work dirs.
+    pub fn upper_disk_dir(&self) -> PathBuf {
+        self.root.join("upper-disk")
+    }
+
     /// Prepare container directory.
     pub fn prepare(&self) -> std::io::Result<()> {
         std::fs::create_dir_all(self.rootfs_dir())
@@ -69,8 +83,10 @@ impl ContainerLayout {
 /// └── containers/                     # OCI containers
 ///     └── {cid}/
 ///         ├── config.json             # OCI bundle config
+///         ├── lower/                  # rootfs disk (when overlay upper disk set)
 ///         ├── rootfs/                 # bind mount to shared/.../rootfs
-///         └── state/                  # libcontainer state
+///         ├── state/                  # libcontainer state
+///         └── upper-disk/             # overlay upper/work disk (optional)
 /// ```
 #[derive(Clone, Debug)]
 pub struct GuestLayout {
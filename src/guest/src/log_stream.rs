@@ -0,0 +1,112 @@
+//! Guest agent log streaming.
+//!
+//! [`LogStreamLayer`] tees the agent's own tracing events into a broadcast
+//! channel that the Guest.StreamLogs RPC forwards to the host. It sits beside
+//! the stderr formatter, so `RUST_LOG` bounds both. Events from the gRPC
+//! transport crates are dropped: streaming a log line emits some of those
+//! itself and would otherwise feed back into the stream.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use boxlite_shared::GuestLogEvent;
+use tokio::sync::broadcast;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+
+/// Events buffered per subscriber before a slow one starts missing lines.
+const CHANNEL_CAPACITY: usize = 1024;
+
+/// Targets never streamed (see module docs).
+const TRANSPORT_TARGETS: &[&str] = &["h2", "hyper", "tonic", "tower"];
+
+static EVENTS: LazyLock<broadcast::Sender<GuestLogEvent>> =
+    LazyLock::new(|| broadcast::channel(CHANNEL_CAPACITY).0);
+
+/// Subscribe to log events emitted from now on.
+pub fn subscribe() -> broadcast::Receiver<GuestLogEvent> {
+    EVENTS.subscribe()
+}
+
+/// Tracing layer feeding [`subscribe`]rs.
+pub struct LogStreamLayer;
+
+impl<S: Subscriber> Layer<S> for LogStreamLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        // Fast path: nobody is listening.
+        if EVENTS.receiver_count() == 0 {
+            return;
+        }
+        let meta = event.metadata();
+        let target = meta.target();
+        if TRANSPORT_TARGETS.iter().any(|t| {
+            target
+                .strip_prefix(t)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+        }) {
+            return;
+        }
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64;
+        let _ = EVENTS.send(GuestLogEvent {
+            timestamp_ms,
+            level: meta.level().to_string(),
+            target: target.to_string(),
+            message: visitor.message,
+            fields: visitor.fields,
+        });
+    }
+}
+
+/// Collects an event's message and other fields.
+#[derive(Default)]
+struct FieldVisitor {
+    message: String,
+    fields: HashMap<String, String>,
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = value.to_string(),
+            name => {
+                self.fields.insert(name.to_string(), value.to_string());
+            }
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{value:?}"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn layer_streams_agent_events_but_not_transport() {
+        let mut rx = subscribe();
+        let subscriber = tracing_subscriber::registry().with(LogStreamLayer);
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!(target: "h2::codec", "frame sent");
+            tracing::warn!(target: "boxlite_guest::test", pid = 7, "process started");
+        });
+
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.level, "WARN");
+        assert_eq!(event.target, "boxlite_guest::test");
+        assert_eq!(event.message, "process started");
+        assert_eq!(event.fields.get("pid").map(String::as_str), Some("7"));
+        assert!(rx.try_recv().is_err(), "transport event must be dropped");
+    }
+}
@@ -8,8 +8,14 @@ mod ca_trust;
 #[cfg(target_os = "linux")]
 mod container;
 #[cfg(target_os = "linux")]
+mod kmod;
+#[cfg(target_os = "linux")]
 mod layout;
 #[cfg(target_os = "linux")]
+mod log_stream;
+#[cfg(target_os = "linux")]
+mod memory;
+#[cfg(target_os = "linux")]
 mod mounts;
 #[cfg(target_os = "linux")]
 mod network;
@@ -37,12 +43,50 @@ use tracing::info;
 #[cfg(target_os = "linux")]
 static BOOT_T0: OnceLock<Instant> = OnceLock::new();
 
+/// Kernel uptime (from /proc/uptime) at guest agent startup, in milliseconds.
+#[cfg(target_os = "linux")]
+static KERNEL_UPTIME_AT_START_MS: OnceLock<u64> = OnceLock::new();
+
+/// Milliseconds from guest agent startup until the host was notified.
+#[cfg(target_os = "linux")]
+static AGENT_READY_MS: OnceLock<u64> = OnceLock::new();
+
 /// Milliseconds elapsed since guest agent startup.
 #[cfg(target_os = "linux")]
 pub(crate) fn boot_elapsed_ms() -> u128 {
     BOOT_T0.get().map(|t| t.elapsed().as_millis()).unwrap_or(0)
 }
 
+/// Record that the host has been notified of readiness (first call wins).
+#[cfg(target_os = "linux")]
+pub(crate) fn mark_agent_ready() {
+    let _ = AGENT_READY_MS.set(boot_elapsed_ms() as u64);
+}
+
+/// Boot timing reported to the host in the Guest.Init response.
+#[cfg(target_os = "linux")]
+pub(crate) fn boot_timing() -> boxlite_shared::GuestBootTiming {
+    boxlite_shared::GuestBootTiming {
+        kernel_uptime_ms: KERNEL_UPTIME_AT_START_MS.get().copied().unwrap_or(0),
+        agent_ready_ms: AGENT_READY_MS.get().copied().unwrap_or(0),
+    }
+}
+
+/// Read kernel uptime in milliseconds from `/proc/uptime`.
+///
+/// The first field is seconds since boot with two decimal places.
+#[cfg(target_os = "linux")]
+fn read_kernel_uptime_ms() -> Option<u64> {
+    let contents = std::fs::read_to_string("/proc/uptime").ok()?;
+    parse_uptime_ms(&contents)
+}
+
+#[cfg(target_os = "linux")]
+fn parse_uptime_ms(contents: &str) -> Option<u64> {
+    let secs: f64 = contents.split_whitespace().next()?.parse().ok()?;
+    Some((secs * 1000.0) as u64)
+}
+
 /// BoxLite Guest Agent - runs inside the isolated Box to execute containers
 #[cfg(target_os = "linux")]
 #[derive(Parser, Debug)]
@@ -59,21 +103,82 @@ struct GuestArgs {
 
     /// Notify URI to signal host when ready
     ///
-    /// Guest connects to this URI after gRPC server is ready to serve.
+    /// Guest connects to this URI after gRPC server is ready to serve,
+    /// retrying with backoff while the host isn't listening yet. The
+    /// `retries` and `timeout_ms` query params bound the retries.
     /// Examples:
     ///   --notify vsock://2696
+    ///   --notify vsock://2696?retries=5&timeout_ms=3000
     ///   --notify unix:///var/run/boxlite-ready.sock
     #[arg(short, long)]
     notify: Option<String>,
+
+    /// Log output format on stderr
+    ///
+    /// `json` emits one object per line (level, target, fields, spans)
+    /// for log collectors. `RUST_LOG` filtering applies to both.
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+}
+
+/// Guest agent log format.
+#[cfg(target_os = "linux")]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[value(rename_all = "lower")]
+enum LogFormat {
+    /// Human-readable `tracing_subscriber::fmt` output.
+    #[default]
+    Text,
+    /// Newline-delimited JSON.
+    Json,
+}
+
+/// Build the tracing subscriber for `format`, writing to `writer`.
+#[cfg(target_os = "linux")]
+fn build_subscriber<W>(
+    format: LogFormat,
+    filter: tracing_subscriber::EnvFilter,
+    writer: W,
+) -> Box<dyn tracing::Subscriber + Send + Sync>
+where
+    W: for<'w> tracing_subscriber::fmt::MakeWriter<'w> + Send + Sync + 'static,
+{
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let builder = tracing_subscriber::fmt()
+        .with_target(true) // Show module names
+        .with_writer(writer)
+        .with_env_filter(filter);
+    // Tee every event that passes the filter to Guest.StreamLogs.
+    match format {
+        LogFormat::Text => Box::new(builder.finish().with(log_stream::LogStreamLayer)),
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .with_ansi(false)
+                .with_current_span(true)
+                .with_span_list(true)
+                .finish()
+                .with(log_stream::LogStreamLayer),
+        ),
+    }
 }
 
 #[cfg(target_os = "linux")]
 fn main() -> BoxliteResult<()> {
     let t0 = Instant::now();
     BOOT_T0.set(t0).expect("BOOT_T0 already initialized");
+    // libkrun's init mounts /proc before exec'ing the agent; if it is
+    // missing, the host simply reports no kernel/agent breakdown.
+    if let Some(uptime_ms) = read_kernel_uptime_ms() {
+        let _ = KERNEL_UPTIME_AT_START_MS.set(uptime_ms);
+    }
 
     // Early diagnostic - visible even if tracing fails
-    eprintln!("[guest] T+0ms: agent starting");
+    eprintln!(
+        "[guest] T+0ms: agent starting (kernel uptime {}ms)",
+        KERNEL_UPTIME_AT_START_MS.get().copied().unwrap_or(0)
+    );
 
     // Set panic hook to ensure we see panics
     std::panic::set_hook(Box::new(|panic_info| {
@@ -81,17 +186,16 @@ fn main() -> BoxliteResult<()> {
         std::process::exit(1);
     }));
 
+    // Parse command-line arguments with clap (before tracing, which
+    // needs the log format)
+    let args = GuestArgs::parse();
+
     // Initialize tracing subscriber - respects RUST_LOG env var
     // Default to "info" level if RUST_LOG is not set (for visibility)
-    if let Err(e) = tracing_subscriber::fmt()
-        .with_target(true) // Show module names
-        .with_writer(std::io::stderr)
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
-        )
-        .try_init()
-    {
+    use tracing_subscriber::util::SubscriberInitExt;
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    if let Err(e) = build_subscriber(args.log_format, filter, std::io::stderr).try_init() {
         eprintln!("[ERROR] Failed to initialize tracing: {}", e);
         // Continue anyway - logging failure shouldn't stop the server
     }
@@ -111,21 +215,19 @@ fn main() -> BoxliteResult<()> {
     let rt = tokio::runtime::Runtime::new().map_err(|e| {
         boxlite_shared::errors::BoxliteError::Internal(format!("tokio runtime: {e}"))
     })?;
-    rt.block_on(async_main())
+    rt.block_on(async_main(args))
 }
 
 #[cfg(target_os = "linux")]
-async fn async_main() -> BoxliteResult<()> {
+async fn async_main(args: GuestArgs) -> BoxliteResult<()> {
     // Mount essential tmpfs directories early
     // Needed because virtio-fs doesn't support open-unlink-fstat pattern
     mounts::mount_essential_tmpfs()?;
     eprintln!("[guest] T+{}ms: tmpfs mounted", boot_elapsed_ms());
 
-    // Parse command-line arguments with clap
-    let args = GuestArgs::parse();
     info!(
-        "Arguments parsed: listen={}, notify={:?}",
-        args.listen, args.notify
+        "Arguments parsed: listen={}, notify={:?}, log_format={:?}",
+        args.listen, args.notify, args.log_format
     );
 
     // Prepare guest layout directories
@@ -150,8 +252,77 @@ mod tests {
         let args = GuestArgs {
             listen: "vsock://2695".to_string(),
             notify: Some("vsock://2696".to_string()),
+            log_format: LogFormat::Text,
         };
         assert_eq!(args.listen, "vsock://2695");
         assert_eq!(args.notify, Some("vsock://2696".to_string()));
     }
+
+    #[test]
+    fn test_log_format_arg() {
+        let args = GuestArgs::parse_from(["boxlite-guest", "--listen", "vsock://2695"]);
+        assert_eq!(args.log_format, LogFormat::Text);
+
+        let args = GuestArgs::parse_from([
+            "boxlite-guest",
+            "--listen",
+            "vsock://2695",
+            "--log-format",
+            "json",
+        ]);
+        assert_eq!(args.log_format, LogFormat::Json);
+    }
+
+    #[test]
+    fn test_json_log_line_fields() {
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Capture(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Capture {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let capture = Capture::default();
+        let writer = capture.clone();
+        let subscriber = build_subscriber(
+            LogFormat::Json,
+            tracing_subscriber::EnvFilter::new("info"),
+            move || writer.clone(),
+        );
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("exec", execution_id = "exec-1");
+            let _entered = span.enter();
+            tracing::debug!(target: "boxlite_guest::test", "filtered out");
+            info!(target: "boxlite_guest::test", pid = 42, "process started");
+        });
+
+        let output = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1, "debug line should be filtered: {output}");
+
+        let line: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["target"], "boxlite_guest::test");
+        assert_eq!(line["fields"]["message"], "process started");
+        assert_eq!(line["fields"]["pid"], 42);
+        assert_eq!(line["span"]["execution_id"], "exec-1");
+        assert_eq!(line["spans"][0]["name"], "exec");
+    }
+
+    #[test]
+    fn test_parse_uptime_ms() {
+        assert_eq!(parse_uptime_ms("0.42 0.10\n"), Some(420));
+        assert_eq!(parse_uptime_ms("12.05 3.00"), Some(12050));
+        assert_eq!(parse_uptime_ms(""), None);
+        assert_eq!(parse_uptime_ms("garbage"), None);
+    }
 }
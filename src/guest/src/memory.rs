@@ -0,0 +1,167 @@
+//! Guest memory resizing.
+//!
+//! Shrinks or grows the memory the guest kernel may use by offlining or
+//! onlining memory blocks under `/sys/devices/system/memory`. Offlined
+//! blocks are never handed to the page allocator, so the host's memory
+//! balloon can reclaim their backing pages. Growth is bounded by the memory
+//! the VM booted with: blocks can only be re-onlined, not added.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use std::fs;
+use std::path::Path;
+
+const MEMORY_SYSFS: &str = "/sys/devices/system/memory";
+const MIB: u64 = 1024 * 1024;
+
+/// Resize online guest memory to `target_mib` (rounded up to whole blocks).
+///
+/// Returns the online memory afterwards, in MiB. Blocks the kernel refuses
+/// to offline (e.g. holding unmovable pages) are skipped, so shrinking is
+/// best effort and may stop above the target.
+pub fn resize(target_mib: u32) -> BoxliteResult<u32> {
+    resize_in(Path::new(MEMORY_SYSFS), target_mib)
+}
+
+fn resize_in(root: &Path, target_mib: u32) -> BoxliteResult<u32> {
+    if target_mib == 0 {
+        return Err(BoxliteError::InvalidArgument(
+            "target memory must be greater than 0".into(),
+        ));
+    }
+
+    let block_size = block_size(root)?;
+    let mut blocks = list_blocks(root)?;
+    let target_blocks = (target_mib as u64 * MIB).div_ceil(block_size) as usize;
+    let mut online = blocks.iter().filter(|(_, on)| *on).count();
+
+    if online > target_blocks {
+        // Offline from the top of physical memory down; block 0 holds the
+        // kernel image and is never removable.
+        for (index, on) in blocks.iter_mut().rev() {
+            if online == target_blocks {
+                break;
+            }
+            if !*on || *index == 0 {
+                continue;
+            }
+            match set_online(root, *index, false) {
+                Ok(()) => {
+                    *on = false;
+                    online -= 1;
+                }
+                Err(e) => tracing::debug!(block = *index, error = %e, "Memory block busy"),
+            }
+        }
+    } else {
+        for (index, on) in blocks.iter_mut() {
+            if online == target_blocks {
+                break;
+            }
+            if *on {
+                continue;
+            }
+            set_online(root, *index, true)?;
+            *on = true;
+            online += 1;
+        }
+    }
+
+    let actual_mib = (online as u64 * block_size / MIB) as u32;
+    tracing::info!(target_mib, actual_mib, "Resized guest memory");
+    Ok(actual_mib)
+}
+
+/// Memory block size; the kernel reports it as bare hex.
+fn block_size(root: &Path) -> BoxliteResult<u64> {
+    let path = root.join("block_size_bytes");
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| BoxliteError::Unsupported(format!("memory hotplug unavailable: {e}")))?;
+    u64::from_str_radix(raw.trim(), 16)
+        .ok()
+        .filter(|size| *size > 0)
+        .ok_or_else(|| {
+            BoxliteError::Internal(format!("invalid {}: {:?}", path.display(), raw.trim()))
+        })
+}
+
+/// `(block index, online)` for every memory block, sorted by index.
+fn list_blocks(root: &Path) -> BoxliteResult<Vec<(u64, bool)>> {
+    let entries = fs::read_dir(root)
+        .map_err(|e| BoxliteError::Internal(format!("Failed to read {}: {e}", root.display())))?;
+    let mut blocks = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(index) = name
+            .to_str()
+            .and_then(|n| n.strip_prefix("memory"))
+            .and_then(|n| n.parse::<u64>().ok())
+        else {
+            continue;
+        };
+        let online = fs::read_to_string(entry.path().join("online"))
+            .map(|s| s.trim() == "1")
+            .unwrap_or(false);
+        blocks.push((index, online));
+    }
+    blocks.sort_unstable();
+    Ok(blocks)
+}
+
+fn set_online(root: &Path, index: u64, online: bool) -> BoxliteResult<()> {
+    let path = root.join(format!("memory{index}")).join("online");
+    fs::write(&path, if online { "1" } else { "0" })
+        .map_err(|e| BoxliteError::Internal(format!("Failed to write {}: {e}", path.display())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fake sysfs with `count` online 128 MiB blocks.
+    fn fake_sysfs(count: u64) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("block_size_bytes"), "8000000\n").unwrap();
+        for index in 0..count {
+            let block = dir.path().join(format!("memory{index}"));
+            fs::create_dir(&block).unwrap();
+            fs::write(block.join("online"), "1\n").unwrap();
+        }
+        dir
+    }
+
+    fn online_blocks(root: &Path) -> Vec<u64> {
+        list_blocks(root)
+            .unwrap()
+            .into_iter()
+            .filter(|(_, on)| *on)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn shrink_offlines_highest_blocks_and_grow_restores_them() {
+        let sysfs = fake_sysfs(8);
+
+        assert_eq!(resize_in(sysfs.path(), 300).unwrap(), 384);
+        assert_eq!(online_blocks(sysfs.path()), vec![0, 1, 2]);
+
+        assert_eq!(resize_in(sysfs.path(), 1024).unwrap(), 1024);
+        assert_eq!(online_blocks(sysfs.path()).len(), 8);
+    }
+
+    #[test]
+    fn block_zero_stays_online() {
+        let sysfs = fake_sysfs(2);
+        assert_eq!(resize_in(sysfs.path(), 1).unwrap(), 128);
+        assert_eq!(online_blocks(sysfs.path()), vec![0]);
+    }
+
+    #[test]
+    fn zero_target_is_rejected() {
+        let sysfs = fake_sysfs(2);
+        assert!(matches!(
+            resize_in(sysfs.path(), 0),
+            Err(BoxliteError::InvalidArgument(_))
+        ));
+    }
+}
@@ -52,10 +52,11 @@ pub fn mount_overlayfs_direct(
     tracing::info!("  workdir:  {}", work_dir);
     tracing::info!("  merged:   {}", merged_dir);
 
-    // Ensure directories exist and are clean
-    // work_dir MUST be empty for overlayfs to mount successfully
+    // Ensure directories exist; work_dir MUST be empty for overlayfs to mount.
+    // upper_dir is kept as-is so writes survive a restart on a persistent disk.
     ensure_clean_dir(work_dir)?;
-    ensure_clean_dir(upper_dir)?;
+    std::fs::create_dir_all(upper_dir)
+        .map_err(|e| format!("Failed to create directory {}: {}", upper_dir, e))?;
     ensure_clean_dir(merged_dir)?;
 
     // Mount overlayfs using nix API
@@ -79,3 +80,251 @@ pub fn mount_overlayfs_direct(
 
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+/// Pack an overlayfs upper directory as an OCI layer tar at `dest`.
+///
+/// Overlay whiteouts (0:0 character devices) become `.wh.<name>` entries and
+/// opaque directories (`overlay.opaque` xattr) get a `.wh..wh..opq` marker,
+/// per the OCI image layer spec. Everything else is archived as-is, without
+/// following symlinks. Entries are written in sorted order so packing the
+/// same upper twice yields the same tar.
+///
+/// Returns `(changed, removed)`: the number of added or modified entries and
+/// the number of whiteouts.
+pub fn pack_upper_layer(
+    upper_dir: &std::path::Path,
+    dest: &std::path::Path,
+) -> BoxliteResult<(usize, usize)> {
+    let file = std::fs::File::create(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut builder = tar::Builder::new(std::io::BufWriter::new(file));
+    builder.follow_symlinks(false);
+
+    let mut counts = (0, 0);
+    pack_dir(
+        &mut builder,
+        upper_dir,
+        std::path::Path::new(""),
+        &mut counts,
+    )
+    .map_err(|e| format!("Failed to pack {}: {}", upper_dir.display(), e))?;
+    builder
+        .into_inner()
+        .and_then(|mut w| std::io::Write::flush(&mut w))
+        .map_err(|e| format!("Failed to finish {}: {}", dest.display(), e))?;
+    Ok(counts)
+}
+
+#[cfg(target_os = "linux")]
+fn pack_dir<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    dir: &std::path::Path,
+    rel: &std::path::Path,
+    counts: &mut (usize, usize),
+) -> std::io::Result<()> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+
+    let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = rel.join(entry.file_name());
+        let meta = std::fs::symlink_metadata(&path)?;
+
+        if meta.file_type().is_char_device() && meta.rdev() == 0 {
+            let mut whiteout = std::ffi::OsString::from(".wh.");
+            whiteout.push(entry.file_name());
+            append_marker(builder, &rel.join(whiteout))?;
+            counts.1 += 1;
+            continue;
+        }
+
+        builder.append_path_with_name(&path, &name)?;
+        counts.0 += 1;
+
+        if meta.is_dir() {
+            if is_opaque(&path) {
+                append_marker(builder, &name.join(".wh..wh..opq"))?;
+                counts.1 += 1;
+            }
+            pack_dir(builder, &path, &name, counts)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+/// Pack a container's merged rootfs as a flat tar at `dest`.
+///
+/// Like `docker export`: the result is a single filesystem image, not a
+/// layer, so there are no whiteouts. Character/block devices and sockets are
+/// skipped with a warning (the runtime recreates `/dev` anyway). Symlinks are
+/// not followed and entries are written in sorted order.
+///
+/// Returns `(packed, skipped)` entry counts.
+pub fn pack_rootfs(
+    rootfs: &std::path::Path,
+    dest: &std::path::Path,
+) -> BoxliteResult<(usize, usize)> {
+    let file = std::fs::File::create(dest)
+        .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+    let mut builder = tar::Builder::new(std::io::BufWriter::new(file));
+    builder.follow_symlinks(false);
+
+    let mut counts = (0, 0);
+    pack_rootfs_dir(&mut builder, rootfs, std::path::Path::new(""), &mut counts)
+        .map_err(|e| format!("Failed to pack {}: {}", rootfs.display(), e))?;
+    builder
+        .into_inner()
+        .and_then(|mut w| std::io::Write::flush(&mut w))
+        .map_err(|e| format!("Failed to finish {}: {}", dest.display(), e))?;
+    Ok(counts)
+}
+
+#[cfg(target_os = "linux")]
+fn pack_rootfs_dir<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    dir: &std::path::Path,
+    rel: &std::path::Path,
+    counts: &mut (usize, usize),
+) -> std::io::Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+
+    let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        let name = rel.join(entry.file_name());
+        let file_type = std::fs::symlink_metadata(&path)?.file_type();
+
+        if file_type.is_char_device() || file_type.is_block_device() || file_type.is_socket() {
+            tracing::warn!(path = %name.display(), "export: skipping device or socket");
+            counts.1 += 1;
+            continue;
+        }
+
+        builder.append_path_with_name(&path, &name)?;
+        counts.0 += 1;
+
+        if file_type.is_dir() {
+            pack_rootfs_dir(builder, &path, &name, counts)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+/// Append an empty regular file used as a whiteout marker.
+fn append_marker<W: std::io::Write>(
+    builder: &mut tar::Builder<W>,
+    name: &std::path::Path,
+) -> std::io::Result<()> {
+    let mut header = tar::Header::new_gnu();
+    header.set_entry_type(tar::EntryType::Regular);
+    header.set_size(0);
+    header.set_mode(0o644);
+    header.set_cksum();
+    builder.append_data(&mut header, name, std::io::empty())
+}
+
+#[cfg(target_os = "linux")]
+/// Whether overlayfs marked `dir` opaque (hides the lower directory's contents).
+/// Checks both the `trusted.` and (userxattr mounts) `user.` namespaces.
+fn is_opaque(dir: &std::path::Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    let Ok(path) = std::ffi::CString::new(dir.as_os_str().as_bytes()) else {
+        return false;
+    };
+    ["trusted.overlay.opaque\0", "user.overlay.opaque\0"]
+        .iter()
+        .any(|attr| {
+            let mut value = [0u8; 1];
+            // SAFETY: both names are NUL-terminated and `value` outlives the call.
+            let len = unsafe {
+                nix::libc::lgetxattr(
+                    path.as_ptr(),
+                    attr.as_ptr().cast(),
+                    value.as_mut_ptr().cast(),
+                    value.len(),
+                )
+            };
+            len == 1 && value[0] == b'y'
+        })
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn entry_names(tar_path: &Path) -> Vec<String> {
+        let mut archive = tar::Archive::new(std::fs::File::open(tar_path).unwrap());
+        archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().display().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn pack_upper_layer_archives_files_in_sorted_order() {
+        let upper = tempfile::tempdir().unwrap();
+        std::fs::create_dir(upper.path().join("etc")).unwrap();
+        std::fs::write(upper.path().join("etc/app.conf"), "x").unwrap();
+        std::fs::write(upper.path().join("added.txt"), "hello").unwrap();
+        std::os::unix::fs::symlink("/nonexistent", upper.path().join("link")).unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        let tar_path = out.path().join("layer.tar");
+        let (changed, removed) = pack_upper_layer(upper.path(), &tar_path).unwrap();
+
+        assert_eq!((changed, removed), (4, 0));
+        assert_eq!(
+            entry_names(&tar_path),
+            vec!["added.txt", "etc", "etc/app.conf", "link"]
+        );
+    }
+
+    #[test]
+    fn pack_upper_layer_converts_overlay_whiteouts() {
+        let upper = tempfile::tempdir().unwrap();
+        std::fs::create_dir(upper.path().join("etc")).unwrap();
+        let whiteout =
+            std::ffi::CString::new(upper.path().join("etc/motd").to_string_lossy().into_owned())
+                .unwrap();
+        // SAFETY: valid NUL-terminated path.
+        let rc = unsafe { nix::libc::mknod(whiteout.as_ptr(), nix::libc::S_IFCHR, 0) };
+        if rc != 0 {
+            eprintln!("skipping: mknod needs CAP_MKNOD");
+            return;
+        }
+
+        let out = tempfile::tempdir().unwrap();
+        let tar_path = out.path().join("layer.tar");
+        let (changed, removed) = pack_upper_layer(upper.path(), &tar_path).unwrap();
+
+        assert_eq!((changed, removed), (1, 1));
+        assert_eq!(entry_names(&tar_path), vec!["etc", "etc/.wh.motd"]);
+    }
+
+    #[test]
+    fn pack_rootfs_skips_sockets() {
+        let rootfs = tempfile::tempdir().unwrap();
+        std::fs::create_dir(rootfs.path().join("etc")).unwrap();
+        std::fs::write(rootfs.path().join("etc/os-release"), "ID=test").unwrap();
+        std::fs::create_dir(rootfs.path().join("run")).unwrap();
+        let _listener =
+            std::os::unix::net::UnixListener::bind(rootfs.path().join("run/app.sock")).unwrap();
+
+        let out = tempfile::tempdir().unwrap();
+        let tar_path = out.path().join("rootfs.tar");
+        let (packed, skipped) = pack_rootfs(rootfs.path(), &tar_path).unwrap();
+
+        assert_eq!((packed, skipped), (3, 1));
+        assert_eq!(entry_names(&tar_path), vec!["etc", "etc/os-release", "run"]);
+    }
+}
@@ -1,20 +1,28 @@
 #![cfg(target_os = "linux")]
 //! Container service implementation.
 //!
-//! Handles OCI container lifecycle (Init RPC).
+//! Handles OCI container lifecycle (Init RPC), upper-layer export (Diff RPC)
+//! and process listing (ListProcesses RPC).
 
+use std::net::IpAddr;
 use std::path::Path;
 
 use crate::service::server::GuestServer;
 use boxlite_shared::{
-    container_init_response, rootfs_init, Container as ContainerService, ContainerInitError,
-    ContainerInitRequest, ContainerInitResponse, ContainerInitSuccess, Filesystem, RootfsInit,
+    container_init_response, layout::dirs, rootfs_init, Container as ContainerService,
+    ContainerDiffRequest, ContainerExportRequest, ContainerInitError, ContainerInitRequest,
+    ContainerInitResponse, ContainerInitSuccess, ContainerProcess, DiskRootfs, DnsConfig,
+    DownloadChunk, Filesystem, ListProcessesRequest, ListProcessesResponse, RootfsInit,
+    UserNamespace,
 };
 use nix::mount::{mount, MsFlags};
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info, warn};
 
-use crate::container::{Container, UserMount};
+use crate::container::{
+    resolve_capabilities, Container, DnsSettings, UserMount, UserNamespaceConfig,
+};
 use crate::layout::GuestLayout;
 use crate::storage::block_device::BlockDeviceMount;
 
@@ -66,6 +74,16 @@ fn prepare_rootfs(
         Some(rootfs_init::Strategy::Disk(disk)) => {
             info!("Rootfs strategy: disk (device={})", disk.device);
 
+            if let Some(upper_device) = disk.overlay_upper_device.as_deref() {
+                return mount_disk_with_overlay_upper(
+                    disk,
+                    Path::new(upper_device),
+                    container_id,
+                    shared_rootfs,
+                    layout,
+                );
+            }
+
             std::fs::create_dir_all(shared_rootfs)
                 .map_err(|e| format!("Failed to create shared rootfs directory: {}", e))?;
 
@@ -76,6 +94,7 @@ fn prepare_rootfs(
                 Filesystem::Ext4,
                 disk.need_format,
                 disk.need_resize,
+                false,
             )
             .map_err(|e| format!("Failed to mount rootfs disk: {}", e))?;
 
@@ -85,6 +104,123 @@ fn prepare_rootfs(
     }
 }
 
+/// Mount the rootfs disk as the overlay lower layer with upper/work on another disk.
+///
+/// The upper disk is formatted only when it carries no filesystem yet, so a
+/// restarted box keeps its previous writes.
+fn mount_disk_with_overlay_upper(
+    disk: &DiskRootfs,
+    upper_device: &Path,
+    container_id: &str,
+    shared_rootfs: &Path,
+    layout: &GuestLayout,
+) -> Result<(), String> {
+    info!(
+        "Placing overlay upper/work on {} for container {}",
+        upper_device.display(),
+        container_id
+    );
+
+    let container_layout = layout.container(container_id);
+    let lower_dir = container_layout.lower_dir();
+    let upper_disk_dir = container_layout.upper_disk_dir();
+
+    BlockDeviceMount::mount(
+        Path::new(&disk.device),
+        &lower_dir,
+        Filesystem::Ext4,
+        disk.need_format,
+        disk.need_resize,
+        false,
+    )
+    .map_err(|e| format!("Failed to mount rootfs disk: {}", e))?;
+
+    let need_format = !BlockDeviceMount::has_ext4_superblock(upper_device)
+        .map_err(|e| format!("Failed to probe overlay upper disk: {}", e))?;
+    BlockDeviceMount::mount(
+        upper_device,
+        &upper_disk_dir,
+        Filesystem::Ext4,
+        need_format,
+        false,
+        false,
+    )
+    .map_err(|e| format!("Failed to mount overlay upper disk: {}", e))?;
+
+    let upper_dir = upper_disk_dir.join(dirs::UPPER);
+    let work_dir = upper_disk_dir.join(dirs::WORK);
+    crate::overlayfs::mount_overlayfs_direct(
+        &[lower_dir.to_string_lossy().into_owned()],
+        &upper_dir.to_string_lossy(),
+        &work_dir.to_string_lossy(),
+        &shared_rootfs.to_string_lossy(),
+    )
+    .map_err(|e| format!("Failed to mount rootfs overlay: {}", e))
+}
+
+/// Convert the proto user namespace into the OCI spec's mapping config.
+fn userns_from_proto(userns: &UserNamespace) -> UserNamespaceConfig {
+    use crate::storage::idmap::IdMapping;
+
+    let convert = |mappings: &[boxlite_shared::IdMapping]| {
+        mappings
+            .iter()
+            .map(|m| IdMapping {
+                host_id: m.host_id,
+                container_id: m.container_id,
+                count: m.size,
+            })
+            .collect()
+    };
+    UserNamespaceConfig {
+        uid_mappings: convert(&userns.uid_mappings),
+        gid_mappings: convert(&userns.gid_mappings),
+    }
+}
+
+/// Convert and validate the proto DNS config; `None` uses the defaults.
+fn dns_from_proto(dns: Option<&DnsConfig>) -> Result<DnsSettings, String> {
+    let Some(dns) = dns else {
+        return Ok(DnsSettings::default());
+    };
+    let parse_ip = |ip: &str| {
+        ip.parse::<IpAddr>()
+            .map_err(|_| format!("Invalid DNS config: malformed IP address {ip:?}"))
+    };
+    let check_word = |kind: &str, value: &String| {
+        if value.is_empty() || value.contains(char::is_whitespace) {
+            return Err(format!("Invalid DNS config: bad {kind} entry {value:?}"));
+        }
+        Ok(value.clone())
+    };
+
+    let mut hosts = dns
+        .hosts
+        .iter()
+        .map(|(name, ip)| Ok((parse_ip(ip)?, check_word("hosts", name)?)))
+        .collect::<Result<Vec<_>, String>>()?;
+    hosts.sort_by(|a, b| a.1.cmp(&b.1));
+
+    Ok(DnsSettings {
+        nameservers: dns
+            .nameservers
+            .iter()
+            .map(|ip| parse_ip(ip))
+            .collect::<Result<_, _>>()?,
+        search: dns
+            .search
+            .iter()
+            .map(|d| check_word("search", d))
+            .collect::<Result<_, _>>()?,
+        options: dns
+            .options
+            .iter()
+            .map(|o| check_word("options", o))
+            .collect::<Result<_, _>>()?,
+        hosts,
+    })
+}
+
 #[tonic::async_trait]
 impl ContainerService for GuestServer {
     async fn init(
@@ -234,6 +370,18 @@ impl ContainerService for GuestServer {
             })
             .collect();
 
+        let dns = match dns_from_proto(init_req.dns.as_ref()) {
+            Ok(dns) => dns,
+            Err(reason) => {
+                error!("{}", reason);
+                return Ok(Response::new(ContainerInitResponse {
+                    result: Some(container_init_response::Result::Error(ContainerInitError {
+                        reason,
+                    })),
+                }));
+            }
+        };
+
         debug!(
             entrypoint = ?config.entrypoint,
             workdir = %config.workdir,
@@ -242,6 +390,10 @@ impl ContainerService for GuestServer {
             bundle_rootfs = %bundle_rootfs.display(),
             container_id = %container_id,
             user_mounts_count = user_mounts.len(),
+            userns = init_req.userns.is_some(),
+            dns = ?dns,
+            cap_add = ?init_req.cap_add,
+            cap_drop = ?init_req.cap_drop,
             "Container configuration"
         );
 
@@ -261,6 +413,10 @@ impl ContainerService for GuestServer {
             &config.workdir,
             &config.user,
             user_mounts,
+            init_req.userns.as_ref().map(userns_from_proto),
+            &dns,
+            init_req.read_only_rootfs,
+            resolve_capabilities(&init_req.cap_add, &init_req.cap_drop),
         ) {
             Ok(mut container) => {
                 debug!(container_id = %container_id, "Container started, checking if init process is running");
@@ -311,4 +467,146 @@ impl ContainerService for GuestServer {
             }
         }
     }
+
+    type DiffStream = ReceiverStream<Result<DownloadChunk, Status>>;
+
+    async fn diff(
+        &self,
+        request: Request<ContainerDiffRequest>,
+    ) -> Result<Response<Self::DiffStream>, Status> {
+        let req = request.into_inner();
+        let container_id = self
+            .resolve_container_id(req.container_id.as_str())
+            .await
+            .map_err(Status::failed_precondition)?;
+
+        // Only disk rootfs with a separate overlay upper disk has an upper dir
+        // holding exactly the container's writes.
+        let upper_dir = self
+            .layout
+            .container(&container_id)
+            .upper_disk_dir()
+            .join(dirs::UPPER);
+        if !upper_dir.is_dir() {
+            return Err(Status::failed_precondition(format!(
+                "container {} has no overlay upper layer",
+                container_id
+            )));
+        }
+
+        let temp_path =
+            std::env::temp_dir().join(format!("boxlite-diff-{}.tar", uuid::Uuid::new_v4()));
+        let dest = temp_path.clone();
+        let packed = tokio::task::spawn_blocking(move || {
+            crate::overlayfs::pack_upper_layer(&upper_dir, &dest)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))
+        .and_then(|r| r.map_err(|e| Status::internal(e.to_string())));
+        let (changed, removed) = match packed {
+            Ok(counts) => counts,
+            Err(status) => {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                return Err(status);
+            }
+        };
+
+        info!(
+            container_id = %container_id,
+            changed,
+            removed,
+            "diff started"
+        );
+
+        let rx = super::files::stream_temp_tar(temp_path);
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
+    type ExportStream = ReceiverStream<Result<DownloadChunk, Status>>;
+
+    async fn export(
+        &self,
+        request: Request<ContainerExportRequest>,
+    ) -> Result<Response<Self::ExportStream>, Status> {
+        let req = request.into_inner();
+        let container_id = self
+            .resolve_container_id(req.container_id.as_str())
+            .await
+            .map_err(Status::failed_precondition)?;
+
+        let rootfs = self.layout.shared().container(&container_id).rootfs_dir();
+        if !rootfs.is_dir() {
+            return Err(Status::failed_precondition(format!(
+                "container {} has no rootfs",
+                container_id
+            )));
+        }
+
+        let temp_path =
+            std::env::temp_dir().join(format!("boxlite-export-{}.tar", uuid::Uuid::new_v4()));
+        let dest = temp_path.clone();
+        let packed =
+            tokio::task::spawn_blocking(move || crate::overlayfs::pack_rootfs(&rootfs, &dest))
+                .await
+                .map_err(|e| Status::internal(e.to_string()))
+                .and_then(|r| r.map_err(|e| Status::internal(e.to_string())));
+        let (packed, skipped) = match packed {
+            Ok(counts) => counts,
+            Err(status) => {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                return Err(status);
+            }
+        };
+
+        info!(
+            container_id = %container_id,
+            packed,
+            skipped,
+            "export started"
+        );
+
+        let rx = super::files::stream_temp_tar(temp_path);
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+    async fn list_processes(
+        &self,
+        request: Request<ListProcessesRequest>,
+    ) -> Result<Response<ListProcessesResponse>, Status> {
+        let req = request.into_inner();
+        let container_id = self
+            .resolve_container_id(req.container_id.as_str())
+            .await
+            .map_err(Status::failed_precondition)?;
+
+        let container = self
+            .containers
+            .lock()
+            .await
+            .get(&container_id)
+            .cloned()
+            .ok_or_else(|| Status::not_found(format!("container {container_id} not found")))?;
+        let init_pid = container.lock().await.init_pid().ok_or_else(|| {
+            Status::failed_precondition(format!("container {container_id} is not running"))
+        })?;
+
+        let entries = tokio::task::spawn_blocking(move || {
+            crate::container::list_namespace_processes(init_pid)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(|e| Status::internal(e.to_string()))?;
+        debug!(container_id = %container_id, count = entries.len(), "Listed processes");
+
+        let processes = entries
+            .into_iter()
+            .map(|e| ContainerProcess {
+                pid: e.pid,
+                ppid: e.ppid,
+                cmd: e.cmd,
+                rss_kb: e.rss_kb,
+                state: e.state,
+            })
+            .collect();
+        Ok(Response::new(ListProcessesResponse { processes }))
+    }
 }
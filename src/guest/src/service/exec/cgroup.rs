@@ -0,0 +1,197 @@
+//! Per-execution cgroup limits.
+//!
+//! Executions started with a resource profile get a dedicated cgroup v2
+//! group at `/sys/fs/cgroup/boxlite-exec/<execution_id>`. The group is
+//! created and configured before spawn, the process is moved into it right
+//! after spawn, and the group is removed once the process has been waited on.
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use boxlite_shared::ExecResourceLimits;
+use nix::mount::{mount, MsFlags};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const EXEC_PARENT: &str = "boxlite-exec";
+
+/// `cpu.max` period in microseconds (the kernel default).
+const CPU_PERIOD_US: u64 = 100_000;
+/// Smallest quota the kernel accepts for `cpu.max`.
+const CPU_MIN_QUOTA_US: u64 = 1_000;
+
+/// A configured cgroup for one execution.
+pub(super) struct ExecCgroup {
+    path: PathBuf,
+}
+
+impl ExecCgroup {
+    /// Create the execution's cgroup and write its limits.
+    pub(super) fn create(execution_id: &str, limits: &ExecResourceLimits) -> BoxliteResult<Self> {
+        ensure_mounted()?;
+
+        let controllers = controllers_for(limits);
+        let root = Path::new(CGROUP_ROOT);
+        enable_controllers(root, &controllers)?;
+        let parent = root.join(EXEC_PARENT);
+        create_dir(&parent)?;
+        enable_controllers(&parent, &controllers)?;
+
+        let group = Self {
+            path: parent.join(execution_id),
+        };
+        create_dir(&group.path)?;
+        for (file, value) in limit_files(limits) {
+            if let Err(e) = write(&group.path.join(file), &value) {
+                group.remove();
+                return Err(e);
+            }
+        }
+
+        tracing::debug!(path = %group.path.display(), ?limits, "Created exec cgroup");
+        Ok(group)
+    }
+
+    /// Move `pid` into the cgroup.
+    pub(super) fn attach(&self, pid: u32) -> BoxliteResult<()> {
+        write(&self.path.join("cgroup.procs"), &pid.to_string())
+    }
+
+    /// Remove the cgroup (best effort; fails while processes remain).
+    pub(super) fn remove(&self) {
+        if let Err(e) = fs::remove_dir(&self.path) {
+            tracing::debug!(path = %self.path.display(), error = %e, "Failed to remove exec cgroup");
+        }
+    }
+}
+
+/// Remove the cgroup of a finished execution, if it had one.
+pub(super) fn release(execution_id: &str) {
+    let path = Path::new(CGROUP_ROOT).join(EXEC_PARENT).join(execution_id);
+    if path.exists() {
+        ExecCgroup { path }.remove();
+    }
+}
+
+/// Mount the cgroup2 hierarchy in the guest if init hasn't already.
+fn ensure_mounted() -> BoxliteResult<()> {
+    let root = Path::new(CGROUP_ROOT);
+    if root.join("cgroup.controllers").exists() {
+        return Ok(());
+    }
+    fs::create_dir_all(root)
+        .map_err(|e| BoxliteError::Internal(format!("Failed to create {CGROUP_ROOT}: {e}")))?;
+    mount(
+        Some("cgroup2"),
+        root,
+        Some("cgroup2"),
+        MsFlags::MS_NOSUID | MsFlags::MS_NODEV | MsFlags::MS_NOEXEC,
+        None::<&str>,
+    )
+    .map_err(|e| BoxliteError::Internal(format!("Failed to mount cgroup2 on {CGROUP_ROOT}: {e}")))
+}
+
+/// Enable `controllers` for the children of `dir`.
+fn enable_controllers(dir: &Path, controllers: &[&str]) -> BoxliteResult<()> {
+    let control = dir.join("cgroup.subtree_control");
+    let enabled = fs::read_to_string(&control).unwrap_or_default();
+    for controller in controllers {
+        if enabled.split_whitespace().any(|c| c == *controller) {
+            continue;
+        }
+        write(&control, &format!("+{controller}"))?;
+    }
+    Ok(())
+}
+
+/// Controllers needed to enforce `limits`.
+fn controllers_for(limits: &ExecResourceLimits) -> Vec<&'static str> {
+    let mut controllers = Vec::new();
+    if limits.cpus.is_some() {
+        controllers.push("cpu");
+    }
+    if limits.max_memory.is_some() {
+        controllers.push("memory");
+    }
+    if limits.max_processes.is_some() {
+        controllers.push("pids");
+    }
+    controllers
+}
+
+/// Interface files and values that enforce `limits`.
+fn limit_files(limits: &ExecResourceLimits) -> Vec<(&'static str, String)> {
+    let mut files = Vec::new();
+    if let Some(cpus) = limits.cpus {
+        let quota = ((cpus * CPU_PERIOD_US as f64).round() as u64).max(CPU_MIN_QUOTA_US);
+        files.push(("cpu.max", format!("{quota} {CPU_PERIOD_US}")));
+    }
+    if let Some(bytes) = limits.max_memory {
+        files.push(("memory.max", bytes.to_string()));
+    }
+    if let Some(pids) = limits.max_processes {
+        files.push(("pids.max", pids.to_string()));
+    }
+    files
+}
+
+fn create_dir(path: &Path) -> BoxliteResult<()> {
+    match fs::create_dir(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(()),
+        Err(e) => Err(BoxliteError::Internal(format!(
+            "Failed to create cgroup {}: {e}",
+            path.display()
+        ))),
+    }
+}
+
+fn write(path: &Path, value: &str) -> BoxliteResult<()> {
+    fs::write(path, value).map_err(|e| {
+        BoxliteError::Internal(format!(
+            "Failed to write {value:?} to {}: {e}",
+            path.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit_files_map_each_limit() {
+        let limits = ExecResourceLimits {
+            cpus: Some(0.5),
+            max_memory: Some(64 * 1024 * 1024),
+            max_processes: Some(32),
+        };
+        assert_eq!(
+            limit_files(&limits),
+            vec![
+                ("cpu.max", "50000 100000".to_string()),
+                ("memory.max", "67108864".to_string()),
+                ("pids.max", "32".to_string()),
+            ]
+        );
+        assert_eq!(controllers_for(&limits), vec!["cpu", "memory", "pids"]);
+    }
+
+    #[test]
+    fn tiny_cpu_cap_is_clamped_to_kernel_minimum() {
+        let limits = ExecResourceLimits {
+            cpus: Some(0.001),
+            ..Default::default()
+        };
+        assert_eq!(
+            limit_files(&limits),
+            vec![("cpu.max", "1000 100000".to_string())]
+        );
+    }
+
+    #[test]
+    fn empty_limits_need_nothing() {
+        let limits = ExecResourceLimits::default();
+        assert!(limit_files(&limits).is_empty());
+        assert!(controllers_for(&limits).is_empty());
+    }
+}
@@ -76,6 +76,63 @@ impl OutputStream {
     }
 }
 
+impl OutputStream {
+    /// Re-chunk the stream into complete lines.
+    fn lines(self) -> Self {
+        use async_stream::stream;
+
+        let mut chunks = self.inner;
+        let stream = stream! {
+            let mut buffer = LineBuffer::default();
+            while let Some(chunk) = chunks.next().await {
+                for line in buffer.push(&chunk) {
+                    yield line;
+                }
+            }
+            if let Some(rest) = buffer.finish() {
+                yield rest;
+            }
+        };
+
+        Self {
+            inner: Box::pin(stream),
+        }
+    }
+}
+
+/// Longest partial line held before it is yielded anyway.
+const MAX_LINE_BYTES: usize = 64 * 1024;
+
+/// Reassembles lines split across read chunks.
+#[derive(Default)]
+struct LineBuffer {
+    partial: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Add a chunk and return the lines it completes, newline included.
+    fn push(&mut self, chunk: &[u8]) -> Vec<Vec<u8>> {
+        let mut lines = Vec::new();
+        let mut rest = chunk;
+        while let Some(pos) = rest.iter().position(|&b| b == b'\n') {
+            let mut line = std::mem::take(&mut self.partial);
+            line.extend_from_slice(&rest[..=pos]);
+            lines.push(line);
+            rest = &rest[pos + 1..];
+        }
+        self.partial.extend_from_slice(rest);
+        if self.partial.len() >= MAX_LINE_BYTES {
+            lines.push(std::mem::take(&mut self.partial));
+        }
+        lines
+    }
+
+    /// Take the unterminated tail left at end of stream.
+    fn finish(self) -> Option<Vec<u8>> {
+        (!self.partial.is_empty()).then_some(self.partial)
+    }
+}
+
 impl Stream for OutputStream {
     type Item = Vec<u8>;
 
@@ -98,6 +155,13 @@ impl ExecStdout {
             inner: OutputStream::new(fd),
         }
     }
+
+    /// Yield complete lines instead of read-sized chunks.
+    pub fn lines(self) -> Self {
+        Self {
+            inner: self.inner.lines(),
+        }
+    }
 }
 
 impl Stream for ExecStdout {
@@ -122,6 +186,13 @@ impl ExecStderr {
             inner: OutputStream::new(fd),
         }
     }
+
+    /// Yield complete lines instead of read-sized chunks.
+    pub fn lines(self) -> Self {
+        Self {
+            inner: self.inner.lines(),
+        }
+    }
 }
 
 impl Stream for ExecStderr {
@@ -303,6 +374,31 @@ impl ExecHandle {
         self.stdin = None; // Drop closes the fd
     }
 
+    /// Switch stdout and stderr to yield complete lines.
+    ///
+    /// Must be called before the streams are taken.
+    pub fn buffer_lines(&mut self) {
+        self.stdout = self.stdout.take().map(ExecStdout::lines);
+        self.stderr = self.stderr.take().map(ExecStderr::lines);
+    }
+
+    /// Turn off the PTY's output processing (`\n` -> `\r\n` and friends).
+    ///
+    /// No-op without a PTY. Output the process wrote before this call has
+    /// already been translated.
+    pub fn disable_output_processing(&self) -> BoxliteResult<()> {
+        use nix::sys::termios::{tcgetattr, tcsetattr, OutputFlags, SetArg};
+
+        let Some(controller) = &self.pty_controller else {
+            return Ok(());
+        };
+        let mut termios = tcgetattr(controller)
+            .map_err(|e| BoxliteError::Internal(format!("Failed to read PTY attributes: {}", e)))?;
+        termios.output_flags.remove(OutputFlags::OPOST);
+        tcsetattr(controller, SetArg::TCSANOW, &termios)
+            .map_err(|e| BoxliteError::Internal(format!("Failed to set PTY attributes: {}", e)))
+    }
+
     /// Take stdout stream
     ///
     /// Returns the stdout stream. After calling this, you cannot call it again.
@@ -343,3 +439,68 @@ impl ExecHandle {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(buffer: &mut LineBuffer, chunks: &[&[u8]]) -> Vec<Vec<u8>> {
+        chunks.iter().flat_map(|chunk| buffer.push(chunk)).collect()
+    }
+
+    #[test]
+    fn test_line_buffer_reassembles_lines_across_chunks() {
+        let mut buffer = LineBuffer::default();
+        let lines = collect(
+            &mut buffer,
+            &[b"hel", b"lo\nwor", b"ld\n", b"\n", b"a\nb\nta"],
+        );
+        assert_eq!(
+            lines,
+            vec![
+                b"hello\n".to_vec(),
+                b"world\n".to_vec(),
+                b"\n".to_vec(),
+                b"a\n".to_vec(),
+                b"b\n".to_vec(),
+            ]
+        );
+        assert_eq!(buffer.finish(), Some(b"ta".to_vec()));
+    }
+
+    #[test]
+    fn test_line_buffer_without_tail() {
+        let mut buffer = LineBuffer::default();
+        assert_eq!(collect(&mut buffer, &[b"one\n"]), vec![b"one\n".to_vec()]);
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn test_line_buffer_flushes_overlong_line() {
+        let mut buffer = LineBuffer::default();
+        assert!(buffer.push(&vec![b'x'; MAX_LINE_BYTES - 1]).is_empty());
+        let lines = buffer.push(b"xy");
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].len(), MAX_LINE_BYTES + 1);
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[tokio::test]
+    async fn test_lines_stream_over_pipe() {
+        use std::io::Write;
+
+        let (read, write) = nix::unistd::pipe().unwrap();
+        let mut stdout = ExecStdout::new(read).lines();
+        let mut writer = std::fs::File::from(write);
+
+        writer.write_all(b"par").unwrap();
+        writer.flush().unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        writer.write_all(b"tial\nnext").unwrap();
+        drop(writer);
+
+        assert_eq!(stdout.next().await, Some(b"partial\n".to_vec()));
+        assert_eq!(stdout.next().await, Some(b"next".to_vec()));
+        assert_eq!(stdout.next().await, None);
+    }
+}
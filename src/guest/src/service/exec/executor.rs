@@ -74,6 +74,10 @@ impl Executor for ContainerExecutor {
                 cmd = cmd.with_user(user.clone());
             }
 
+            if !req.groups.is_empty() {
+                cmd = cmd.with_groups(req.groups.clone());
+            }
+
             cmd.spawn_build().await?
             // container mutex dropped here
         };
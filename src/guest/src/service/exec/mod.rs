@@ -10,15 +10,21 @@
 //!
 //! - **Protocol Layer** (mod.rs): gRPC service implementation
 //! - **Executor Layer** (executor.rs): Process spawning abstraction
+//! - **Limits Layer** (cgroup.rs): Per-execution cgroup limits
+//! - **Diagnostics** (oom.rs): OOM-kill detection
 //! - **Lifecycle Layer** (timeout.rs): Process management
 //! - **State Layer** (registry.rs, state.rs): Execution state
+//! - **Output Layer** (output.rs): Stdout/stderr relay across re-attaches
 //! - **Types** (types.rs): Shared types
 //!
 //! Each file has a single, clear responsibility.
 
+mod cgroup;
 #[cfg(target_os = "linux")]
 pub mod exec_handle;
 pub(in crate::service) mod executor;
+mod oom;
+mod output;
 pub(in crate::service) mod registry;
 mod state;
 mod timeout;
@@ -30,8 +36,9 @@ use crate::service::exec::executor::{ContainerExecutor, GuestExecutor};
 use crate::service::server::GuestServer;
 use boxlite_shared::{
     constants::executor as executor_const, AttachRequest, ExecError, ExecOutput, ExecRequest,
-    ExecResponse, ExecStdin, Execution, KillRequest, KillResponse, ResizeTtyRequest,
-    ResizeTtyResponse, SendInputAck, WaitRequest, WaitResponse,
+    ExecResponse, ExecStdin, Execution, ExecutionInfo, KillRequest, KillResponse,
+    ListExecutionsRequest, ListExecutionsResponse, OutputMode, ResizeTtyRequest, ResizeTtyResponse,
+    SendInputAck, WaitRequest, WaitResponse,
 };
 use futures::stream::Stream;
 use std::pin::Pin;
@@ -48,6 +55,15 @@ impl Execution for GuestServer {
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
+        // Hold off idle shutdown until the execution is registered
+        let Some(_activity) = self.registry.begin_exec() else {
+            return Ok(Response::new(error_response(
+                execution_id,
+                "shutting_down",
+                "Box is shutting down after its idle timeout",
+            )));
+        };
+
         // Validate: execution doesn't already exist
         if self.registry.exists(&execution_id).await {
             return Ok(Response::new(error_response(
@@ -137,9 +153,20 @@ impl Execution for GuestServer {
             .await
             .ok_or_else(|| Status::not_found(format!("Execution not found: {}", exec_id)))?;
 
-        // Wait for process to exit
+        // Wait for process to exit. Read the OOM counter before the exec
+        // cgroup (and its memory.events) is removed.
         let exit_status = state.wait_process().await?;
-
+        let oom_killed = matches!(
+            exit_status,
+            ExitStatus::Signal(nix::sys::signal::Signal::SIGKILL)
+        ) && state.oom_fired().await;
+        cgroup::release(&exec_id);
+        self.registry.touch();
+
+        let signal_name = match exit_status {
+            ExitStatus::Signal(sig) => sig.as_str().to_string(),
+            ExitStatus::Code(_) => String::new(),
+        };
         let (exit_code, signal, error_message) = match exit_status {
             ExitStatus::Code(code) => {
                 debug!(
@@ -153,7 +180,13 @@ impl Execution for GuestServer {
                 let mut error_msg = String::new();
                 // When a process gets SIGKILL, check if container init died.
                 // PID namespace teardown sends SIGKILL to all processes when init exits.
-                if sig == nix::sys::signal::Signal::SIGKILL {
+                if oom_killed {
+                    warn!(
+                        execution_id = %exec_id,
+                        "Process was killed by the OOM killer"
+                    );
+                    error_msg = "killed by the OOM killer (out of memory)".to_string();
+                } else if sig == nix::sys::signal::Signal::SIGKILL {
                     if let Some(diagnosis) = state.check_container_death().await {
                         warn!(
                             execution_id = %exec_id,
@@ -178,9 +211,11 @@ impl Execution for GuestServer {
         Ok(Response::new(WaitResponse {
             exit_code,
             signal,
-            timed_out: false,
-            duration_ms: 0,
+            timed_out: state.timed_out().await,
+            duration_ms: state.duration().map_or(0, |d| d.as_millis() as u64),
             error_message,
+            oom_killed,
+            signal_name,
         }))
     }
 
@@ -210,8 +245,17 @@ impl Execution for GuestServer {
                 info!(
                     execution_id = %req.execution_id,
                     signal = req.signal,
+                    grace_ms = ?req.grace_ms,
                     "signal sent"
                 );
+                // Escalate to SIGKILL if the process outlives the grace period.
+                if let Some(grace_ms) = req.grace_ms.filter(|_| signal != Signal::SIGKILL) {
+                    timeout::start_escalation_watcher(
+                        state,
+                        req.execution_id,
+                        std::time::Duration::from_millis(grace_ms),
+                    );
+                }
                 Ok(Response::new(KillResponse {
                     success: true,
                     error: None,
@@ -283,6 +327,25 @@ impl Execution for GuestServer {
             }
         }
     }
+
+    async fn list(
+        &self,
+        _request: Request<ListExecutionsRequest>,
+    ) -> Result<Response<ListExecutionsResponse>, Status> {
+        let mut executions = Vec::new();
+        for (execution_id, state) in self.registry.list().await {
+            executions.push(ExecutionInfo {
+                execution_id,
+                pid: state.get_pid().await.unwrap_or(0),
+                program: state.program().to_string(),
+                running: !state.has_exited(),
+                started_at_ms: state.started_at_ms(),
+            });
+        }
+        debug!(count = executions.len(), "list executions");
+
+        Ok(Response::new(ListExecutionsResponse { executions }))
+    }
 }
 
 /// Spawn execution (orchestrates full lifecycle).
@@ -293,12 +356,49 @@ async fn spawn_execution(
 ) -> Result<ExecResponse, ExecResponse> {
     let started_at_ms = now_ms();
 
-    // Step 1: Spawn process using executor selected by BOXLITE_EXECUTOR env var
-    let (child, container_ref) = spawn_with_executor(server, &req, &execution_id).await?;
+    // Step 1: Prepare the execution's cgroup (if a resource profile was requested)
+    let exec_cgroup = match &req.resource_limits {
+        Some(limits) => Some(
+            cgroup::ExecCgroup::create(&execution_id, limits)
+                .map_err(|e| spawn_error(&execution_id, e.to_string()))?,
+        ),
+        None => None,
+    };
+
+    // Step 2: Spawn process using executor selected by BOXLITE_EXECUTOR env var
+    let (mut child, container_ref) = match spawn_with_executor(server, &req, &execution_id).await {
+        Ok(spawned) => spawned,
+        Err(resp) => {
+            if let Some(group) = &exec_cgroup {
+                group.remove();
+            }
+            return Err(resp);
+        }
+    };
 
     let pid = child.pid().as_raw() as u32;
 
-    // Step 2: Create execution state and register
+    // Step 3: Move the process into its cgroup before it does real work
+    if let Some(group) = &exec_cgroup {
+        if let Err(e) = group.attach(pid) {
+            warn!(execution_id = %execution_id, error = %e, "Failed to apply exec resource limits");
+            let _ = nix::sys::signal::kill(child.pid(), nix::sys::signal::Signal::SIGKILL);
+            return Err(spawn_error(&execution_id, e.to_string()));
+        }
+    }
+
+    // Step 4: Apply the requested output mode before anything reads output
+    match req.output_mode() {
+        OutputMode::Bytes => {}
+        OutputMode::Lines => child.buffer_lines(),
+        OutputMode::Raw => {
+            if let Err(e) = child.disable_output_processing() {
+                warn!(execution_id = %execution_id, error = %e, "Failed to switch PTY to raw output");
+            }
+        }
+    }
+
+    // Step 5: Create execution state and register
     // If running inside a container, pass the init health checker for death detection
     let state = match container_ref {
         Some(container) => {
@@ -306,13 +406,17 @@ async fn spawn_execution(
             state::ExecutionState::new_with_init_health(child, health)
         }
         None => state::ExecutionState::new(child),
-    };
+    }
+    .with_origin(&req.program, started_at_ms);
+    if let Some(watch) = oom::OomWatch::start(pid) {
+        state.set_oom_watch(watch).await;
+    }
     server
         .registry
         .register(execution_id.clone(), state.clone())
         .await;
 
-    // Step 3: Start timeout watcher (if requested)
+    // Step 6: Start timeout watcher (if requested)
     if req.timeout_ms > 0 {
         timeout::start_timeout_watcher(
             state,
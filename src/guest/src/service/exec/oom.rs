@@ -0,0 +1,95 @@
+//! OOM-kill detection for executions.
+//!
+//! The kernel counts OOM kills per cgroup in `memory.events` (`oom_kill`,
+//! hierarchical, any kind of OOM killer) and system-wide in `/proc/vmstat`.
+//! A baseline is taken right after spawn; a SIGKILLed process whose counter
+//! moved since then is reported as OOM-killed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+const VMSTAT: &str = "/proc/vmstat";
+
+/// OOM-kill counter snapshot for one execution.
+#[derive(Debug)]
+pub(super) struct OomWatch {
+    /// Counter file: the process's `memory.events`, or `/proc/vmstat` when
+    /// its cgroup has no memory controller.
+    source: PathBuf,
+    baseline: u64,
+}
+
+impl OomWatch {
+    /// Snapshot the OOM-kill counter that covers `pid`.
+    ///
+    /// Call once the process sits in its final cgroup. Returns `None` when
+    /// no counter is readable.
+    pub(super) fn start(pid: u32) -> Option<Self> {
+        let source = cgroup_events_path(pid).unwrap_or_else(|| PathBuf::from(VMSTAT));
+        let baseline = read_oom_kills(&source)?;
+        Some(Self { source, baseline })
+    }
+
+    /// Whether an OOM kill was recorded since [`OomWatch::start`].
+    pub(super) fn fired(&self) -> bool {
+        read_oom_kills(&self.source).is_some_and(|count| count > self.baseline)
+    }
+}
+
+/// `memory.events` of the cgroup `pid` belongs to, if it exists.
+fn cgroup_events_path(pid: u32) -> Option<PathBuf> {
+    let membership = fs::read_to_string(format!("/proc/{pid}/cgroup")).ok()?;
+    let path = parse_unified_cgroup(&membership)?;
+    let events = Path::new(CGROUP_ROOT)
+        .join(path.trim_start_matches('/'))
+        .join("memory.events");
+    events.exists().then_some(events)
+}
+
+/// The cgroup v2 path from `/proc/<pid>/cgroup` (the `0::<path>` line).
+fn parse_unified_cgroup(membership: &str) -> Option<&str> {
+    membership.lines().find_map(|line| line.strip_prefix("0::"))
+}
+
+fn read_oom_kills(source: &Path) -> Option<u64> {
+    parse_oom_kill(&fs::read_to_string(source).ok()?)
+}
+
+/// The `oom_kill` counter from `memory.events` or `/proc/vmstat` contents.
+fn parse_oom_kill(contents: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (key, value) = line.split_once(' ')?;
+        if key == "oom_kill" {
+            value.trim().parse().ok()
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_oom_kill_counter() {
+        let events = "low 0\nhigh 0\nmax 12\noom 3\noom_kill 2\noom_group_kill 0\n";
+        assert_eq!(parse_oom_kill(events), Some(2));
+
+        let vmstat = "nr_free_pages 1024\npgfault 99\noom_kill 7\n";
+        assert_eq!(parse_oom_kill(vmstat), Some(7));
+
+        assert_eq!(parse_oom_kill("oom 3\n"), None);
+    }
+
+    #[test]
+    fn parses_unified_cgroup_path() {
+        assert_eq!(
+            parse_unified_cgroup("0::/boxlite-exec/abc\n"),
+            Some("/boxlite-exec/abc")
+        );
+        assert_eq!(parse_unified_cgroup("12:memory:/legacy\n0::/\n"), Some("/"));
+        assert_eq!(parse_unified_cgroup("12:memory:/legacy\n"), None);
+    }
+}
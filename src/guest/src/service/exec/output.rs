@@ -0,0 +1,331 @@
+//! Output relay between an execution's pipes and its attach streams.
+//!
+//! Stdout and stderr are read by pump tasks for the life of the process,
+//! not per attach. While a client is attached, chunks go straight to its
+//! stream; while none is (it disconnected, or the host process restarted),
+//! they are held in a bounded backlog that the next attach replays first.
+//!
+//! An attached client's stream is a bounded channel, so a client that stops
+//! reading stalls the pumps, which stop reading the pipes; the process then
+//! blocks on write instead of its output being dropped.
+
+use boxlite_shared::{exec_output, ExecOutput, Stderr, Stdout};
+use futures::{Stream, StreamExt};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::sync::mpsc;
+use tonic::Status;
+use tracing::{info, warn};
+
+/// Output bytes held for a detached execution; older chunks are dropped
+/// beyond this.
+const BACKLOG_LIMIT: usize = 1024 * 1024;
+
+type OutputTx = mpsc::Sender<Result<ExecOutput, Status>>;
+pub(super) type OutputRx = mpsc::Receiver<Result<ExecOutput, Status>>;
+
+#[derive(Default)]
+struct RelayState {
+    started: bool,
+    /// Stream of the current attach.
+    client: Option<OutputTx>,
+    /// Pumps send to `client` directly. False while the backlog is replayed.
+    live: bool,
+    backlog: VecDeque<ExecOutput>,
+    backlog_bytes: usize,
+    /// Pump tasks still reading.
+    open_pumps: usize,
+}
+
+impl RelayState {
+    fn hold(&mut self, msg: ExecOutput) {
+        self.backlog_bytes += chunk_len(&msg);
+        self.backlog.push_back(msg);
+        let mut dropped = 0;
+        while self.backlog_bytes > BACKLOG_LIMIT {
+            let Some(old) = self.backlog.pop_front() else {
+                break;
+            };
+            self.backlog_bytes -= chunk_len(&old);
+            dropped += chunk_len(&old);
+        }
+        if dropped > 0 {
+            warn!(
+                dropped,
+                "Detached execution output exceeded backlog; dropped oldest"
+            );
+        }
+    }
+
+    fn next_held(&mut self) -> Option<ExecOutput> {
+        let msg = self.backlog.pop_front()?;
+        self.backlog_bytes -= chunk_len(&msg);
+        Some(msg)
+    }
+
+    fn unhold(&mut self, msg: ExecOutput) {
+        self.backlog_bytes += chunk_len(&msg);
+        self.backlog.push_front(msg);
+    }
+
+    fn is_client(&self, tx: &OutputTx) -> bool {
+        self.client.as_ref().is_some_and(|c| c.same_channel(tx))
+    }
+
+    /// Forget `tx` if it is still the current client.
+    fn detach(&mut self, tx: &OutputTx) {
+        if self.is_client(tx) {
+            self.client = None;
+            self.live = false;
+        }
+    }
+}
+
+/// Fans an execution's output out to whichever client is attached.
+#[derive(Clone, Default)]
+pub(super) struct OutputRelay {
+    state: Arc<Mutex<RelayState>>,
+}
+
+impl OutputRelay {
+    fn lock(&self) -> MutexGuard<'_, RelayState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Whether [`start`](Self::start) has been called.
+    pub(super) fn is_started(&self) -> bool {
+        self.lock().started
+    }
+
+    /// Start pumping `stdout` and `stderr` into the relay.
+    pub(super) fn start<O, E>(&self, exec_id: &str, stdout: Option<O>, stderr: Option<E>)
+    where
+        O: Stream<Item = Vec<u8>> + Unpin + Send + 'static,
+        E: Stream<Item = Vec<u8>> + Unpin + Send + 'static,
+    {
+        {
+            let mut state = self.lock();
+            state.started = true;
+            state.open_pumps = stdout.is_some() as usize + stderr.is_some() as usize;
+        }
+        if let Some(stdout) = stdout {
+            self.spawn_pump(exec_id, "stdout", stdout, |data| {
+                exec_output::Event::Stdout(Stdout { data })
+            });
+        }
+        if let Some(stderr) = stderr {
+            self.spawn_pump(exec_id, "stderr", stderr, |data| {
+                exec_output::Event::Stderr(Stderr { data })
+            });
+        }
+    }
+
+    /// Open a stream for a new client: held output first, then live output.
+    ///
+    /// The stream ends once the process's output is exhausted. Fails while
+    /// another client is attached.
+    #[allow(clippy::result_large_err)]
+    pub(super) fn attach(&self) -> Result<OutputRx, Status> {
+        let (tx, rx) = mpsc::channel(100);
+        {
+            let mut state = self.lock();
+            if state.client.as_ref().is_some_and(|c| !c.is_closed()) {
+                return Err(Status::already_exists("Already attached"));
+            }
+            state.client = Some(tx.clone());
+            state.live = false;
+        }
+        let relay = self.clone();
+        tokio::spawn(async move { relay.replay(tx).await });
+        Ok(rx)
+    }
+
+    /// Send the backlog to `tx`, then hand it the live output.
+    async fn replay(&self, tx: OutputTx) {
+        loop {
+            let msg = {
+                let mut state = self.lock();
+                match state.next_held() {
+                    Some(msg) => msg,
+                    None => {
+                        if state.is_client(&tx) {
+                            if state.open_pumps == 0 {
+                                // Output exhausted: dropping the last sender
+                                // ends the client's stream.
+                                state.client = None;
+                            } else {
+                                state.live = true;
+                            }
+                        }
+                        return;
+                    }
+                }
+            };
+            if let Err(mpsc::error::SendError(Ok(msg))) = tx.send(Ok(msg)).await {
+                let mut state = self.lock();
+                state.unhold(msg);
+                state.detach(&tx);
+                return;
+            }
+        }
+    }
+
+    fn spawn_pump<S>(
+        &self,
+        exec_id: &str,
+        name: &'static str,
+        mut stream: S,
+        event: fn(Vec<u8>) -> exec_output::Event,
+    ) where
+        S: Stream<Item = Vec<u8>> + Unpin + Send + 'static,
+    {
+        let relay = self.clone();
+        let exec_id = exec_id.to_string();
+        tokio::spawn(async move {
+            while let Some(chunk) = stream.next().await {
+                relay
+                    .deliver(ExecOutput {
+                        event: Some(event(chunk)),
+                    })
+                    .await;
+            }
+            relay.pump_done();
+            info!(execution = ?exec_id, stream = name, "Output forwarding task ended");
+        });
+    }
+
+    async fn deliver(&self, msg: ExecOutput) {
+        let client = {
+            let mut state = self.lock();
+            let live = state.live;
+            match state.client.clone().filter(|_| live) {
+                Some(client) => client,
+                None => {
+                    state.hold(msg);
+                    return;
+                }
+            }
+        };
+        if let Err(mpsc::error::SendError(Ok(msg))) = client.send(Ok(msg)).await {
+            let mut state = self.lock();
+            state.detach(&client);
+            state.hold(msg);
+        }
+    }
+
+    fn pump_done(&self) {
+        let mut state = self.lock();
+        state.open_pumps = state.open_pumps.saturating_sub(1);
+        if state.open_pumps == 0 && state.live {
+            state.client = None;
+            state.live = false;
+        }
+    }
+}
+
+fn chunk_len(msg: &ExecOutput) -> usize {
+    match &msg.event {
+        Some(exec_output::Event::Stdout(chunk)) => chunk.data.len(),
+        Some(exec_output::Event::Stderr(chunk)) => chunk.data.len(),
+        None => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio_stream::wrappers::UnboundedReceiverStream;
+
+    fn stdout_text(msg: Result<ExecOutput, Status>) -> String {
+        match msg.unwrap().event {
+            Some(exec_output::Event::Stdout(chunk)) => String::from_utf8(chunk.data).unwrap(),
+            other => panic!("expected stdout, got {other:?}"),
+        }
+    }
+
+    fn relay_with_stdout() -> (OutputRelay, mpsc::UnboundedSender<Vec<u8>>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let relay = OutputRelay::default();
+        relay.start(
+            "exec",
+            Some(UnboundedReceiverStream::new(rx)),
+            None::<UnboundedReceiverStream<Vec<u8>>>,
+        );
+        (relay, tx)
+    }
+
+    /// Wait until the relay has no live client, i.e. a dropped stream has
+    /// been noticed by the next delivery.
+    async fn wait_detached(relay: &OutputRelay) {
+        for _ in 0..200 {
+            if relay.lock().client.is_none() {
+                return;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        panic!("relay never detached");
+    }
+
+    #[tokio::test]
+    async fn test_reattach_replays_output_produced_while_detached() {
+        let (relay, stdout) = relay_with_stdout();
+
+        let mut first = relay.attach().unwrap();
+        stdout.send(b"one".to_vec()).unwrap();
+        assert_eq!(stdout_text(first.recv().await.unwrap()), "one");
+        assert!(relay.attach().is_err(), "second concurrent attach");
+        drop(first);
+
+        stdout.send(b"two".to_vec()).unwrap();
+        wait_detached(&relay).await;
+        stdout.send(b"three".to_vec()).unwrap();
+
+        let mut second = relay.attach().unwrap();
+        assert_eq!(stdout_text(second.recv().await.unwrap()), "two");
+        assert_eq!(stdout_text(second.recv().await.unwrap()), "three");
+        stdout.send(b"four".to_vec()).unwrap();
+        assert_eq!(stdout_text(second.recv().await.unwrap()), "four");
+
+        drop(stdout);
+        assert!(second.recv().await.is_none(), "stream ends with output");
+    }
+
+    #[tokio::test]
+    async fn test_attach_after_exit_returns_held_output() {
+        let (relay, stdout) = relay_with_stdout();
+        stdout.send(b"done".to_vec()).unwrap();
+        drop(stdout);
+
+        // Let the pump drain and finish.
+        for _ in 0..200 {
+            if relay.lock().open_pumps == 0 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+
+        let mut rx = relay.attach().unwrap();
+        assert_eq!(stdout_text(rx.recv().await.unwrap()), "done");
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[test]
+    fn test_backlog_is_bounded() {
+        let mut state = RelayState::default();
+        let chunk = |byte| ExecOutput {
+            event: Some(exec_output::Event::Stdout(Stdout {
+                data: vec![byte; BACKLOG_LIMIT / 2],
+            })),
+        };
+        for byte in 0..3 {
+            state.hold(chunk(byte));
+        }
+        assert_eq!(state.backlog.len(), 2);
+        assert_eq!(state.backlog_bytes, BACKLOG_LIMIT);
+        assert_eq!(chunk_len(&state.backlog[0]), BACKLOG_LIMIT / 2);
+        match &state.next_held().unwrap().event {
+            Some(exec_output::Event::Stdout(c)) => assert_eq!(c.data[0], 1),
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
@@ -4,10 +4,11 @@
 //! to execution metadata, I/O channels, and completion status.
 
 use crate::service::exec::state::ExecutionState;
-use nix::sys::signal::{kill, Signal};
-use nix::unistd::Pid;
+use crate::service::exec::timeout;
+use nix::sys::signal::Signal;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use tracing::{info, warn};
 
@@ -18,6 +19,30 @@ use tracing::{info, warn};
 #[derive(Clone)]
 pub(crate) struct ExecutionRegistry {
     executions: Arc<Mutex<HashMap<String, ExecutionState>>>,
+    activity: Arc<std::sync::Mutex<Activity>>,
+}
+
+/// Idle bookkeeping behind the ClaimIdle RPC.
+struct Activity {
+    /// Exec calls between `begin_exec` and registration.
+    in_flight: usize,
+    /// Last exec start or completion.
+    last_active: Instant,
+    /// Set once idle shutdown is claimed; refuses new executions.
+    claimed: bool,
+}
+
+/// Marks an exec call in flight until dropped.
+pub(crate) struct ExecGuard {
+    activity: Arc<std::sync::Mutex<Activity>>,
+}
+
+impl Drop for ExecGuard {
+    fn drop(&mut self) {
+        let mut activity = self.activity.lock().unwrap();
+        activity.in_flight -= 1;
+        activity.last_active = Instant::now();
+    }
 }
 
 impl ExecutionRegistry {
@@ -25,7 +50,52 @@ impl ExecutionRegistry {
     pub fn new() -> Self {
         Self {
             executions: Arc::new(Mutex::new(HashMap::new())),
+            activity: Arc::new(std::sync::Mutex::new(Activity {
+                in_flight: 0,
+                last_active: Instant::now(),
+                claimed: false,
+            })),
+        }
+    }
+
+    /// Mark an exec call in flight, holding off idle shutdown until the
+    /// returned guard drops. `None` once idle shutdown has been claimed.
+    pub fn begin_exec(&self) -> Option<ExecGuard> {
+        let mut activity = self.activity.lock().unwrap();
+        if activity.claimed {
+            return None;
+        }
+        activity.in_flight += 1;
+        activity.last_active = Instant::now();
+        Some(ExecGuard {
+            activity: Arc::clone(&self.activity),
+        })
+    }
+
+    /// Record that an execution completed, restarting the idle window.
+    pub fn touch(&self) {
+        self.activity.lock().unwrap().last_active = Instant::now();
+    }
+
+    /// Claim the guest for idle shutdown.
+    ///
+    /// Succeeds when no exec call is in flight, every registered execution
+    /// has exited, and nothing ran for `idle`. Once claimed, `begin_exec`
+    /// refuses new executions, so the shutdown cannot race one.
+    pub async fn claim_idle(&self, idle: Duration) -> bool {
+        let executions = self.executions.lock().await;
+        if executions.values().any(|state| !state.has_exited()) {
+            return false;
+        }
+        let mut activity = self.activity.lock().unwrap();
+        if activity.claimed {
+            return true;
         }
+        if activity.in_flight > 0 || activity.last_active.elapsed() < idle {
+            return false;
+        }
+        activity.claimed = true;
+        true
     }
 
     /// Check if execution exists.
@@ -38,6 +108,23 @@ impl ExecutionRegistry {
         self.executions.lock().await.get(exec_id).cloned()
     }
 
+    /// Snapshot of every registered execution, oldest first.
+    pub async fn list(&self) -> Vec<(String, ExecutionState)> {
+        let mut executions: Vec<(String, ExecutionState)> = self
+            .executions
+            .lock()
+            .await
+            .iter()
+            .map(|(id, state)| (id.clone(), state.clone()))
+            .collect();
+        executions.sort_by(|(a_id, a), (b_id, b)| {
+            a.started_at_ms()
+                .cmp(&b.started_at_ms())
+                .then_with(|| a_id.cmp(b_id))
+        });
+        executions
+    }
+
     /// Register new execution state.
     pub async fn register(&self, exec_id: String, state: ExecutionState) {
         self.executions.lock().await.insert(exec_id, state);
@@ -45,57 +132,62 @@ impl ExecutionRegistry {
 
     /// Gracefully shutdown all running executions.
     ///
-    /// Sends SIGTERM first, waits for exit with timeout, then SIGKILL if needed.
-    pub async fn shutdown_all(&self, timeout_ms: u64) {
-        // Step 1: Collect all PIDs and send SIGTERM
-        let mut pids_to_wait: Vec<(String, i32)> = Vec::new();
-
-        {
-            let executions = self.executions.lock().await;
-            for (exec_id, state) in executions.iter() {
-                if let Some(pid) = state.get_pid().await {
-                    let pid_i32 = pid as i32;
-                    // Check if process is still alive (signal 0 doesn't send anything)
-                    if kill(Pid::from_raw(pid_i32), None).is_ok() {
-                        info!(exec_id = %exec_id, pid = pid, "Sending SIGTERM to execution");
-                        let _ = kill(Pid::from_raw(pid_i32), Signal::SIGTERM);
-                        pids_to_wait.push((exec_id.clone(), pid_i32));
-                    }
-                }
-            }
-        }
+    /// Sends SIGTERM to every execution, then SIGKILL to those still
+    /// running after `grace`. Returns once every escalation has settled.
+    pub async fn shutdown_all(&self, grace: Duration) {
+        let executions: Vec<(String, ExecutionState)> = self
+            .executions
+            .lock()
+            .await
+            .iter()
+            .map(|(id, state)| (id.clone(), state.clone()))
+            .collect();
 
-        if pids_to_wait.is_empty() {
+        if executions.is_empty() {
             info!("No running executions to shutdown");
             return;
         }
 
-        // Step 2: Wait for graceful exit with timeout
-        let start = std::time::Instant::now();
-        while start.elapsed().as_millis() < timeout_ms as u128 {
-            tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let results =
+            futures::future::join_all(executions.iter().map(|(exec_id, state)| {
+                timeout::terminate(state, exec_id, Signal::SIGTERM, grace)
+            }))
+            .await;
 
-            // Check which processes are still running
-            let still_running: Vec<_> = pids_to_wait
-                .iter()
-                .filter(|(_, pid)| kill(Pid::from_raw(*pid), None).is_ok())
-                .cloned()
-                .collect();
+        let killed = results.iter().filter(|killed| **killed).count();
+        if killed == 0 {
+            info!("All executions exited gracefully");
+        } else {
+            warn!(killed, "Executions didn't exit gracefully, sent SIGKILL");
+        }
+    }
+}
 
-            if still_running.is_empty() {
-                info!("All executions exited gracefully");
-                return;
-            }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-            pids_to_wait = still_running;
-        }
+    #[tokio::test]
+    async fn claim_idle_waits_for_the_idle_window() {
+        let registry = ExecutionRegistry::new();
+        assert!(!registry.claim_idle(Duration::from_secs(60)).await);
+        assert!(registry.claim_idle(Duration::ZERO).await);
+    }
 
-        // Step 3: SIGKILL remaining executions
-        for (exec_id, pid) in &pids_to_wait {
-            if kill(Pid::from_raw(*pid), None).is_ok() {
-                warn!(exec_id = %exec_id, pid = pid, "Execution didn't exit gracefully, sending SIGKILL");
-                let _ = kill(Pid::from_raw(*pid), Signal::SIGKILL);
-            }
-        }
+    #[tokio::test]
+    async fn claim_idle_is_held_off_by_in_flight_exec() {
+        let registry = ExecutionRegistry::new();
+        let guard = registry.begin_exec().unwrap();
+        assert!(!registry.claim_idle(Duration::ZERO).await);
+
+        drop(guard);
+        assert!(registry.claim_idle(Duration::ZERO).await);
+    }
+
+    #[tokio::test]
+    async fn claimed_registry_refuses_new_execs() {
+        let registry = ExecutionRegistry::new();
+        assert!(registry.claim_idle(Duration::ZERO).await);
+        assert!(registry.begin_exec().is_none());
     }
 }
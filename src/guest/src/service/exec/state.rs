@@ -1,11 +1,12 @@
-use crate::service::exec::exec_handle::ExecHandle;
-use boxlite_shared::ExecOutput;
+use crate::service::exec::exec_handle::{ExecHandle, ExitStatus};
+use crate::service::exec::oom::OomWatch;
+use crate::service::exec::output::{OutputRelay, OutputRx};
 use std::os::unix::io::AsRawFd;
-use std::sync::Arc;
-use tokio::sync::{mpsc, Mutex};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Mutex, OnceCell};
 use tokio::task::JoinHandle;
 use tonic::Status;
-use tracing::info;
 
 /// Abstraction for checking container init health.
 ///
@@ -25,11 +26,10 @@ pub(crate) trait InitHealthCheck: Send + Sync {
 struct Inner {
     /// The process handle (owns pid, pty_controller, stdin, stdout, stderr)
     handle: Option<ExecHandle>,
-    /// Stdout/stderr forwarding tasks (set on attach)
-    output_tasks: Vec<JoinHandle<()>>,
-    /// Timeout flag
-    #[allow(dead_code)] // Will be used for timeout handling
+    /// Set when the exec's timeout fired while the process was running.
     timed_out: bool,
+    /// OOM-kill counter baseline taken after spawn.
+    oom_watch: Option<OomWatch>,
     /// Optional init health checker for the container this exec runs in.
     /// Used to detect container init death when exec gets SIGKILL.
     init_health: Option<Arc<Mutex<dyn InitHealthCheck>>>,
@@ -38,10 +38,24 @@ struct Inner {
 /// Execution state.
 ///
 /// Handle owns pid, pty_controller, stdin, stdout, stderr.
-/// stdin is taken on send_input(), stdout/stderr are taken on attach().
+/// stdin is taken on send_input(), stdout/stderr are taken on first attach().
 #[derive(Clone)]
 pub(crate) struct ExecutionState {
     inner: Arc<Mutex<Inner>>,
+    /// Flips to true once `wait_process` has reaped the process.
+    exited: Arc<watch::Sender<bool>>,
+    /// Exit status, kept so every `wait_process` call sees it.
+    status: Arc<OnceCell<ExitStatus>>,
+    /// Stdout/stderr fan-out to attached clients.
+    output: OutputRelay,
+    /// Program the execution was started with, for listing.
+    program: Arc<str>,
+    /// Start time in milliseconds since the epoch, for listing.
+    started_at_ms: u64,
+    /// Monotonic spawn time, for the run duration.
+    spawned: Instant,
+    /// Time from spawn until the process was reaped.
+    duration: Arc<OnceLock<Duration>>,
 }
 
 impl ExecutionState {
@@ -49,13 +63,20 @@ impl ExecutionState {
     pub(super) fn new(handle: ExecHandle) -> Self {
         let inner = Inner {
             handle: Some(handle),
-            output_tasks: Vec::new(),
             timed_out: false,
+            oom_watch: None,
             init_health: None,
         };
 
         Self {
             inner: Arc::new(Mutex::new(inner)),
+            exited: Arc::new(watch::channel(false).0),
+            status: Arc::new(OnceCell::new()),
+            output: OutputRelay::default(),
+            program: Arc::from(""),
+            started_at_ms: 0,
+            spawned: Instant::now(),
+            duration: Arc::new(OnceLock::new()),
         }
     }
 
@@ -69,16 +90,40 @@ impl ExecutionState {
     ) -> Self {
         let inner = Inner {
             handle: Some(handle),
-            output_tasks: Vec::new(),
             timed_out: false,
+            oom_watch: None,
             init_health: Some(init_health),
         };
 
         Self {
             inner: Arc::new(Mutex::new(inner)),
+            exited: Arc::new(watch::channel(false).0),
+            status: Arc::new(OnceCell::new()),
+            output: OutputRelay::default(),
+            program: Arc::from(""),
+            started_at_ms: 0,
+            spawned: Instant::now(),
+            duration: Arc::new(OnceLock::new()),
         }
     }
 
+    /// Record what the execution runs and when it started.
+    pub(super) fn with_origin(mut self, program: &str, started_at_ms: u64) -> Self {
+        self.program = Arc::from(program);
+        self.started_at_ms = started_at_ms;
+        self
+    }
+
+    /// Program the execution was started with.
+    pub fn program(&self) -> &str {
+        &self.program
+    }
+
+    /// Start time in milliseconds since the epoch.
+    pub fn started_at_ms(&self) -> u64 {
+        self.started_at_ms
+    }
+
     /// Check if the container init process died.
     ///
     /// Returns `Some(diagnosis)` if init is dead, `None` if alive or no health checker.
@@ -93,7 +138,6 @@ impl ExecutionState {
     }
 
     /// Get PID for execution.
-    #[allow(dead_code)] // API completeness
     pub async fn get_pid(&self) -> Option<u32> {
         let inner = self.inner.lock().await;
         inner.handle.as_ref().map(|h| h.pid().as_raw() as u32)
@@ -154,12 +198,18 @@ impl ExecutionState {
 
     /// Wait for process to exit.
     ///
+    /// The process is reaped once; every call (including ones after a
+    /// client re-attaches) returns the same status.
+    pub async fn wait_process(&self) -> Result<ExitStatus, Status> {
+        self.status.get_or_try_init(|| self.reap()).await.copied()
+    }
+
+    /// Reap the process.
+    ///
     /// Routes to the correct wait mechanism based on executor type:
     /// - Container processes (init_health.is_some()) → zygote IPC polling
     /// - Guest processes (init_health.is_none()) → direct waitpid
-    pub async fn wait_process(
-        &self,
-    ) -> Result<crate::service::exec::exec_handle::ExitStatus, Status> {
+    async fn reap(&self) -> Result<ExitStatus, Status> {
         let (pid, is_container) = {
             let inner = self.inner.lock().await;
             let pid = inner
@@ -170,11 +220,61 @@ impl ExecutionState {
             (pid, inner.init_health.is_some())
         };
 
-        if is_container {
+        let status = if is_container {
             Self::wait_via_zygote(pid).await
         } else {
             Self::wait_direct(pid).await
+        };
+        if status.is_ok() {
+            let _ = self.duration.set(self.spawned.elapsed());
+            self.exited.send_replace(true);
         }
+        status
+    }
+
+    /// How long the process ran, once `wait_process` has reaped it.
+    pub fn duration(&self) -> Option<Duration> {
+        self.duration.get().copied()
+    }
+
+    /// Whether `wait_process` has reaped the process.
+    pub fn has_exited(&self) -> bool {
+        *self.exited.borrow()
+    }
+
+    /// Record that the exec's timeout fired, so Wait reports `timed_out`.
+    pub async fn mark_timed_out(&self) {
+        self.inner.lock().await.timed_out = true;
+    }
+
+    /// Whether the exec's timeout fired while the process was running.
+    pub async fn timed_out(&self) -> bool {
+        self.inner.lock().await.timed_out
+    }
+
+    /// Keep the OOM-kill baseline taken after spawn.
+    pub(super) async fn set_oom_watch(&self, watch: OomWatch) {
+        self.inner.lock().await.oom_watch = Some(watch);
+    }
+
+    /// Whether an OOM kill was recorded for the process's cgroup since spawn.
+    pub async fn oom_fired(&self) -> bool {
+        self.inner
+            .lock()
+            .await
+            .oom_watch
+            .as_ref()
+            .is_some_and(OomWatch::fired)
+    }
+
+    /// Wait up to `timeout` for the process to be reaped by `wait_process`.
+    ///
+    /// Returns true if it exited in time.
+    pub async fn wait_exited(&self, timeout: std::time::Duration) -> bool {
+        let mut rx = self.exited.subscribe();
+        tokio::time::timeout(timeout, rx.wait_for(|exited| *exited))
+            .await
+            .is_ok_and(|r| r.is_ok())
     }
 
     /// Wait for a container process via zygote WNOHANG polling.
@@ -182,11 +282,8 @@ impl ExecutionState {
     /// Container processes are children of the zygote (created by clone3).
     /// Uses WNOHANG to avoid holding the zygote Mutex for the process lifetime.
     /// Retries every 10ms until the process exits.
-    async fn wait_via_zygote(
-        pid: nix::unistd::Pid,
-    ) -> Result<crate::service::exec::exec_handle::ExitStatus, Status> {
+    async fn wait_via_zygote(pid: nix::unistd::Pid) -> Result<ExitStatus, Status> {
         use crate::container::zygote;
-        use crate::service::exec::exec_handle::ExitStatus;
 
         loop {
             let result = tokio::task::spawn_blocking(move || {
@@ -220,10 +317,7 @@ impl ExecutionState {
     /// Guest processes are spawned by std::process::Command and are direct
     /// children of this process. Blocking waitpid is fine here since it
     /// doesn't hold any shared mutex.
-    async fn wait_direct(
-        pid: nix::unistd::Pid,
-    ) -> Result<crate::service::exec::exec_handle::ExitStatus, Status> {
-        use crate::service::exec::exec_handle::ExitStatus;
+    async fn wait_direct(pid: nix::unistd::Pid) -> Result<ExitStatus, Status> {
         use nix::sys::wait::{waitpid, WaitStatus};
 
         #[allow(clippy::result_large_err)] // Status is the standard error type in this module
@@ -241,89 +335,33 @@ impl ExecutionState {
 
     /// Attach to execution output.
     ///
-    /// Takes stdout/stderr from handle and starts forwarding tasks.
-    /// Returns stream of output chunks.
-    pub async fn attach(
-        &self,
-        exec_id: &str,
-    ) -> Result<mpsc::Receiver<Result<ExecOutput, Status>>, Status> {
-        use boxlite_shared::{exec_output, Stderr, Stdout};
-        use futures::StreamExt;
-
-        let (tx, rx) = mpsc::channel(100);
-
-        // Take stdout/stderr from handle
-        let (stdout, stderr) = {
-            let mut inner = self.inner.lock().await;
-
-            if !inner.output_tasks.is_empty() {
-                return Err(Status::already_exists("Already attached"));
-            }
-
-            let handle = inner
-                .handle
-                .as_mut()
-                .ok_or_else(|| Status::failed_precondition("Handle not available"))?;
-
-            let stdout = handle.stdout();
-            let stderr = handle.stderr();
-
-            (stdout, stderr)
-        };
-
-        // Spawn forwarding tasks
-        let mut tasks = Vec::new();
-
-        // Spawn stdout forwarding task
-        let exec_id_string = exec_id.to_string();
-        if let Some(mut stdout) = stdout {
-            let tx = tx.clone();
-            let handle = tokio::spawn(async move {
-                while let Some(chunk) = stdout.next().await {
-                    let msg = ExecOutput {
-                        event: Some(exec_output::Event::Stdout(Stdout { data: chunk })),
-                    };
-                    if tx.send(Ok(msg)).await.is_err() {
-                        break;
-                    }
-                }
-                info!(execution = ?exec_id_string, "Stdout forwarding task ended");
-            });
-            tasks.push(handle);
-        }
-
-        // Spawn stderr forwarding task
-        let exec_id_string = exec_id.to_string();
-        if let Some(mut stderr) = stderr {
-            let tx = tx.clone();
-            let handle = tokio::spawn(async move {
-                while let Some(chunk) = stderr.next().await {
-                    let msg = ExecOutput {
-                        event: Some(exec_output::Event::Stderr(Stderr { data: chunk })),
-                    };
-                    if tx.send(Ok(msg)).await.is_err() {
-                        break;
-                    }
-                }
-                info!(execution = ?exec_id_string, "Stderr forwarding task ended");
-            });
-            tasks.push(handle);
-        }
-
-        // Store tasks
+    /// The first attach takes stdout/stderr from the handle and starts
+    /// forwarding them for the life of the process. Later attaches resume
+    /// the stream: output produced while no client was attached is replayed
+    /// first. Only one client may be attached at a time.
+    pub async fn attach(&self, exec_id: &str) -> Result<OutputRx, Status> {
         {
             let mut inner = self.inner.lock().await;
-            inner.output_tasks = tasks;
+            if !self.output.is_started() {
+                let handle = inner
+                    .handle
+                    .as_mut()
+                    .ok_or_else(|| Status::failed_precondition("Handle not available"))?;
+                self.output.start(exec_id, handle.stdout(), handle.stderr());
+            }
         }
-
-        Ok(rx)
+        self.output.attach()
     }
 
     /// Kill process with signal.
     ///
     /// Returns true if signal was sent, false if already exited.
+    /// A reaped process is never signalled, so a recycled PID is safe.
     pub async fn kill(&self, signal: nix::sys::signal::Signal) -> bool {
         let inner = self.inner.lock().await;
+        if *self.exited.borrow() {
+            return false;
+        }
 
         if let Some(ref handle) = inner.handle {
             handle.kill(signal).is_ok()
@@ -1,11 +1,12 @@
-//! Timeout management.
+//! Timeout management and signal escalation.
 //!
-//! Two-stage termination when execution exceeds its deadline:
-//! SIGTERM first (cooperative cleanup), then SIGKILL after a grace
-//! period to enforce the hard deadline against workloads that ignore
-//! or trap SIGTERM.
+//! Two-stage termination when execution exceeds its deadline or is
+//! killed with a grace period: SIGTERM (or the requested signal) first
+//! for cooperative cleanup, then SIGKILL after a grace period to enforce
+//! the kill against workloads that ignore or trap SIGTERM.
 
 use crate::service::exec::state::ExecutionState;
+use nix::sys::signal::Signal;
 use std::time::Duration;
 use tracing::{info, warn};
 
@@ -30,34 +31,172 @@ pub(super) fn start_timeout_watcher(
 ) {
     tokio::spawn(async move {
         tokio::time::sleep(timeout).await;
-
-        use nix::sys::signal::Signal;
-
-        // Stage 1: SIGTERM — polite termination request.
-        if !exec_state.kill(Signal::SIGTERM).await {
-            // Process already exited on its own; nothing more to do.
+        if exec_state.has_exited() {
             return;
         }
-        info!(
+        // Flag before signalling so a Wait woken by the kill sees it.
+        exec_state.mark_timed_out().await;
+        terminate(&exec_state, &exec_id, Signal::SIGTERM, TIMEOUT_GRACE).await;
+    });
+}
+
+/// Start escalation watcher for a kill request with a grace period.
+///
+/// `signal` has already been delivered by the caller; SIGKILL follows if
+/// the process is still running after `grace`.
+pub(super) fn start_escalation_watcher(
+    exec_state: ExecutionState,
+    exec_id: String,
+    grace: Duration,
+) {
+    tokio::spawn(async move {
+        escalate(&exec_state, &exec_id, grace).await;
+    });
+}
+
+/// Send `signal`, then SIGKILL if the process outlives `grace`.
+///
+/// Returns true if SIGKILL was sent.
+pub(super) async fn terminate(
+    exec_state: &ExecutionState,
+    exec_id: &str,
+    signal: Signal,
+    grace: Duration,
+) -> bool {
+    // Stage 1: polite termination request.
+    if !exec_state.kill(signal).await {
+        // Process already exited on its own; nothing more to do.
+        return false;
+    }
+    info!(
+        execution_id = %exec_id,
+        signal = %signal,
+        grace_ms = grace.as_millis() as u64,
+        "signal sent; grace before SIGKILL"
+    );
+    escalate(exec_state, exec_id, grace).await
+}
+
+/// Wait up to `grace` for the process to exit, then SIGKILL it.
+///
+/// Returns true if SIGKILL was sent.
+async fn escalate(exec_state: &ExecutionState, exec_id: &str, grace: Duration) -> bool {
+    // Stage 2: wake as soon as the process is reaped. `exec_state.kill`
+    // refuses to signal a reaped process, so an exit inside the grace
+    // window never gets a second signal.
+    if exec_state.wait_exited(grace).await {
+        info!(execution_id = %exec_id, "exited within grace");
+        return false;
+    }
+
+    // Stage 3: SIGKILL fallback. Returns false if the process exited
+    // between the grace expiring and now.
+    if exec_state.kill(Signal::SIGKILL).await {
+        warn!(
             execution_id = %exec_id,
-            grace_ms = TIMEOUT_GRACE.as_millis() as u64,
-            "SIGTERM on timeout; grace before SIGKILL"
+            "SIGKILL after grace expired; workload did not exit on signal"
         );
+        true
+    } else {
+        info!(execution_id = %exec_id, "exited within grace");
+        false
+    }
+}
 
-        // Stage 2: wait for the grace window. `exec_state.kill` already
-        // returns false once the process is reaped, so we do not need to
-        // poll — we just sleep the full grace then ask for SIGKILL.
-        tokio::time::sleep(TIMEOUT_GRACE).await;
-
-        // Stage 3: SIGKILL fallback. Returns false if SIGTERM was honored
-        // during the grace window (clean exit, no escalation needed).
-        if exec_state.kill(Signal::SIGKILL).await {
-            warn!(
-                execution_id = %exec_id,
-                "SIGKILL after grace expired; workload did not exit on SIGTERM"
-            );
-        } else {
-            info!(execution_id = %exec_id, "exited within grace after SIGTERM");
-        }
-    });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::exec::exec_handle::{ExecHandle, ExitStatus};
+    use nix::unistd::Pid;
+    use std::io::{BufRead, BufReader};
+    use std::os::fd::OwnedFd;
+    use std::process::{Command, Stdio};
+
+    /// Spawn `sh -c script` and wait for it to print its first line, so
+    /// any trap is installed before the test signals it.
+    fn spawn_ready(script: &str) -> ExecutionState {
+        let mut child = Command::new("sh")
+            .args(["-c", script])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("spawn sh");
+        let mut stdout = BufReader::new(child.stdout.take().unwrap());
+        let mut line = String::new();
+        stdout.read_line(&mut line).unwrap();
+        assert_eq!(line.trim(), "ready");
+
+        let handle = ExecHandle::new(
+            Pid::from_raw(child.id() as i32),
+            OwnedFd::from(child.stdin.take().unwrap()),
+            OwnedFd::from(stdout.into_inner()),
+            None,
+        );
+        ExecutionState::new(handle)
+    }
+
+    #[tokio::test]
+    async fn test_escalates_when_sigterm_ignored() {
+        let state = spawn_ready("trap '' TERM; echo ready; exec sleep 60");
+        let waiter = tokio::spawn({
+            let state = state.clone();
+            async move { state.wait_process().await }
+        });
+
+        let killed = terminate(
+            &state,
+            "ignores-term",
+            Signal::SIGTERM,
+            Duration::from_millis(300),
+        )
+        .await;
+        assert!(killed, "SIGTERM-ignoring process must be SIGKILLed");
+
+        let status = waiter.await.unwrap().unwrap();
+        assert!(matches!(status, ExitStatus::Signal(Signal::SIGKILL)));
+    }
+
+    #[tokio::test]
+    async fn test_timeout_marks_state_and_records_duration() {
+        let state = spawn_ready("echo ready; exec sleep 60");
+        start_timeout_watcher(
+            state.clone(),
+            "times-out".to_string(),
+            Duration::from_millis(200),
+        );
+
+        let status = state.wait_process().await.unwrap();
+        assert!(matches!(status, ExitStatus::Signal(Signal::SIGTERM)));
+        assert!(state.timed_out().await);
+        let duration = state.duration().expect("duration set once reaped");
+        assert!(
+            duration >= Duration::from_millis(200) && duration < Duration::from_secs(2),
+            "{duration:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_no_sigkill_after_exit_in_grace() {
+        let state = spawn_ready("trap 'exit 3' TERM; echo ready; while :; do sleep 0.05; done");
+        let waiter = tokio::spawn({
+            let state = state.clone();
+            async move { state.wait_process().await }
+        });
+
+        let killed = terminate(
+            &state,
+            "honours-term",
+            Signal::SIGTERM,
+            Duration::from_secs(10),
+        )
+        .await;
+        assert!(!killed);
+
+        let status = waiter.await.unwrap().unwrap();
+        assert!(matches!(status, ExitStatus::Code(3)));
+        assert!(
+            !state.kill(Signal::SIGKILL).await,
+            "reaped process re-signalled"
+        );
+    }
 }
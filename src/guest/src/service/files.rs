@@ -155,49 +155,7 @@ impl Files for GuestServer {
         .await
         .map_err(|e| Status::internal(e.to_string()))?;
 
-        // Stream file contents
-        let (tx, rx) = mpsc::channel::<Result<DownloadChunk, Status>>(4);
-        tokio::spawn(async move {
-            let mut file = match File::open(&temp_path).await {
-                Ok(f) => f,
-                Err(e) => {
-                    let _ = tx
-                        .send(Err(Status::internal(format!(
-                            "open temp tar failed: {}",
-                            e
-                        ))))
-                        .await;
-                    return;
-                }
-            };
-            let mut buf = vec![0u8; CHUNK_SIZE];
-            loop {
-                match file.read(&mut buf).await {
-                    Ok(0) => break,
-                    Ok(n) => {
-                        if tx
-                            .send(Ok(DownloadChunk {
-                                data: buf[..n].to_vec(),
-                            }))
-                            .await
-                            .is_err()
-                        {
-                            break;
-                        }
-                    }
-                    Err(e) => {
-                        let _ = tx
-                            .send(Err(Status::internal(format!(
-                                "read temp tar failed: {}",
-                                e
-                            ))))
-                            .await;
-                        break;
-                    }
-                }
-            }
-            let _ = tokio::fs::remove_file(&temp_path).await;
-        });
+        let rx = stream_temp_tar(temp_path);
 
         info!(
             src = %req.src_path,
@@ -209,8 +167,55 @@ impl Files for GuestServer {
     }
 }
 
+/// Stream the tar at `temp_path` as download chunks, deleting it afterwards.
+pub(super) fn stream_temp_tar(temp_path: PathBuf) -> mpsc::Receiver<Result<DownloadChunk, Status>> {
+    let (tx, rx) = mpsc::channel::<Result<DownloadChunk, Status>>(4);
+    tokio::spawn(async move {
+        let mut file = match File::open(&temp_path).await {
+            Ok(f) => f,
+            Err(e) => {
+                let _ = tx
+                    .send(Err(Status::internal(format!(
+                        "open temp tar failed: {}",
+                        e
+                    ))))
+                    .await;
+                return;
+            }
+        };
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        loop {
+            match file.read(&mut buf).await {
+                Ok(0) => break,
+                Ok(n) => {
+                    if tx
+                        .send(Ok(DownloadChunk {
+                            data: buf[..n].to_vec(),
+                        }))
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx
+                        .send(Err(Status::internal(format!(
+                            "read temp tar failed: {}",
+                            e
+                        ))))
+                        .await;
+                    break;
+                }
+            }
+        }
+        let _ = tokio::fs::remove_file(&temp_path).await;
+    });
+    rx
+}
+
 impl GuestServer {
-    async fn resolve_container_id(&self, requested: &str) -> Result<String, String> {
+    pub(super) async fn resolve_container_id(&self, requested: &str) -> Result<String, String> {
         if !requested.is_empty() {
             return Ok(requested.to_string());
         }
@@ -1,14 +1,20 @@
 //! Guest service implementation.
 //!
 //! Handles guest initialization and management (Init, Ping, Shutdown,
-//! Quiesce, Thaw RPCs).
+//! Quiesce, Thaw, ResizeMemory, DiskStats, ClaimIdle, StreamLogs RPCs).
 
 use crate::service::server::GuestServer;
 use boxlite_shared::{
-    guest_init_response, Guest as GuestService, GuestInitError, GuestInitRequest,
-    GuestInitResponse, GuestInitSuccess, PingRequest, PingResponse, QuiesceRequest,
-    QuiesceResponse, ShutdownRequest, ShutdownResponse, ThawRequest, ThawResponse,
+    guest_init_response, ClaimIdleRequest, ClaimIdleResponse, DiskStatsRequest, DiskStatsResponse,
+    Guest as GuestService, GuestInitError, GuestInitRequest, GuestInitResponse, GuestInitSuccess,
+    GuestLogEvent, PingRequest, PingResponse, QuiesceRequest, QuiesceResponse, ResizeMemoryRequest,
+    ResizeMemoryResponse, ShutdownRequest, ShutdownResponse, StreamLogsRequest, ThawRequest,
+    ThawResponse,
 };
+use futures::stream::Stream;
+use std::pin::Pin;
+use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::StreamExt;
 use tonic::{Request, Response, Status};
 use tracing::{debug, error, info};
 
@@ -17,8 +23,9 @@ impl GuestService for GuestServer {
     /// Initialize guest environment.
     ///
     /// This must be called first after connection. It:
-    /// 1. Mounts all volumes (virtiofs + block devices)
-    /// 2. Configures network (if specified)
+    /// 1. Loads requested kernel modules
+    /// 2. Mounts all volumes (virtiofs + block devices)
+    /// 3. Configures network (if specified)
     ///
     /// Note: Rootfs setup is handled by Container.Init.
     async fn init(
@@ -39,7 +46,20 @@ impl GuestService for GuestServer {
             }));
         }
 
-        // Step 1: Mount all volumes (virtiofs + block devices)
+        // Step 1: Load requested kernel modules (before mounts, which may need them)
+        if !req.kernel_modules.is_empty() {
+            info!("Loading {} kernel modules", req.kernel_modules.len());
+            if let Err(e) = crate::kmod::load_modules(&req.kernel_modules) {
+                error!("Failed to load kernel modules: {}", e);
+                return Ok(Response::new(GuestInitResponse {
+                    result: Some(guest_init_response::Result::Error(GuestInitError {
+                        reason: format!("Failed to load kernel modules: {}", e),
+                    })),
+                }));
+            }
+        }
+
+        // Step 2: Mount all volumes (virtiofs + block devices)
         // Empty mount_point = guest determines path from tag
         info!("Mounting {} volumes", req.volumes.len());
         if let Err(e) = crate::storage::mount_volumes(&req.volumes) {
@@ -51,7 +71,7 @@ impl GuestService for GuestServer {
             }));
         }
 
-        // Step 2: Configure network (if specified)
+        // Step 3: Configure network (if specified)
         if let Some(network) = req.network {
             info!("Configuring network interface: {}", network.interface);
             if let Err(e) = crate::network::configure_network_from_config(
@@ -75,7 +95,9 @@ impl GuestService for GuestServer {
 
         info!("✅ Guest initialized successfully");
         Ok(Response::new(GuestInitResponse {
-            result: Some(guest_init_response::Result::Success(GuestInitSuccess {})),
+            result: Some(guest_init_response::Result::Success(GuestInitSuccess {
+                boot_timing: Some(crate::boot_timing()),
+            })),
         }))
     }
 
@@ -88,14 +110,20 @@ impl GuestService for GuestServer {
 
     async fn shutdown(
         &self,
-        _request: Request<ShutdownRequest>,
+        request: Request<ShutdownRequest>,
     ) -> Result<Response<ShutdownResponse>, Status> {
         info!("Received shutdown request - graceful shutdown starting");
 
         // Step 1: Gracefully shutdown all running executions
         const EXEC_SHUTDOWN_TIMEOUT_MS: u64 = 1000;
-        info!("Stopping running executions...");
-        self.registry.shutdown_all(EXEC_SHUTDOWN_TIMEOUT_MS).await;
+        let grace_ms = request
+            .into_inner()
+            .exec_grace_ms
+            .unwrap_or(EXEC_SHUTDOWN_TIMEOUT_MS);
+        info!(grace_ms, "Stopping running executions...");
+        self.registry
+            .shutdown_all(std::time::Duration::from_millis(grace_ms))
+            .await;
 
         // Step 2: Gracefully shutdown all containers
         const CONTAINER_SHUTDOWN_TIMEOUT_MS: u64 = 2000;
@@ -153,4 +181,75 @@ impl GuestService for GuestServer {
 
         Ok(Response::new(ThawResponse { thawed_count }))
     }
+
+    /// Resize guest-usable memory by offlining/onlining memory blocks.
+    async fn resize_memory(
+        &self,
+        request: Request<ResizeMemoryRequest>,
+    ) -> Result<Response<ResizeMemoryResponse>, Status> {
+        let target_mib = request.into_inner().target_mib;
+        info!(target_mib, "Received memory resize request");
+
+        let actual_mib = tokio::task::spawn_blocking(move || crate::memory::resize(target_mib))
+            .await
+            .map_err(|e| Status::internal(format!("memory resize task failed: {e}")))?
+            .map_err(|e| match e {
+                boxlite_shared::BoxliteError::InvalidArgument(msg) => Status::invalid_argument(msg),
+                boxlite_shared::BoxliteError::Unsupported(msg) => Status::unimplemented(msg),
+                other => Status::internal(other.to_string()),
+            })?;
+
+        Ok(Response::new(ResizeMemoryResponse { actual_mib }))
+    }
+
+    /// Report filesystem usage of a container's rootfs.
+    async fn disk_stats(
+        &self,
+        request: Request<DiskStatsRequest>,
+    ) -> Result<Response<DiskStatsResponse>, Status> {
+        let container_id = request.into_inner().container_id;
+        if !self.containers.lock().await.contains_key(&container_id) {
+            return Err(Status::not_found(format!(
+                "container {container_id} not found"
+            )));
+        }
+
+        let rootfs = self.layout.container(&container_id).rootfs_dir();
+        let usage = crate::storage::usage::fs_usage(&rootfs)
+            .map_err(|e| Status::internal(format!("statvfs {} failed: {e}", rootfs.display())))?;
+        debug!(container_id = %container_id, ?usage, "Disk stats");
+
+        Ok(Response::new(DiskStatsResponse {
+            bytes_total: usage.total,
+            bytes_used: usage.used,
+            bytes_available: usage.available,
+        }))
+    }
+
+    /// Claim the guest for idle shutdown if no execution ran for the window.
+    async fn claim_idle(
+        &self,
+        request: Request<ClaimIdleRequest>,
+    ) -> Result<Response<ClaimIdleResponse>, Status> {
+        let idle = std::time::Duration::from_millis(request.into_inner().idle_timeout_ms);
+        let claimed = self.registry.claim_idle(idle).await;
+        if claimed {
+            info!(idle_ms = idle.as_millis() as u64, "Idle shutdown claimed");
+        }
+        Ok(Response::new(ClaimIdleResponse { claimed }))
+    }
+
+    type StreamLogsStream =
+        Pin<Box<dyn Stream<Item = Result<GuestLogEvent, Status>> + Send + 'static>>;
+
+    /// Stream agent log events from now on. A client too slow to keep up
+    /// skips the lines it missed rather than stalling the agent.
+    async fn stream_logs(
+        &self,
+        _request: Request<StreamLogsRequest>,
+    ) -> Result<Response<Self::StreamLogsStream>, Status> {
+        let stream = BroadcastStream::new(crate::log_stream::subscribe())
+            .filter_map(|event| event.ok().map(Ok));
+        Ok(Response::new(Box::pin(stream) as Self::StreamLogsStream))
+    }
 }
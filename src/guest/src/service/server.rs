@@ -5,6 +5,7 @@ use boxlite_shared::{BoxTransport, BoxliteResult};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tonic::transport::Server;
 use tracing::{info, warn};
@@ -58,7 +59,7 @@ impl GuestServer {
 
     /// Run the tonic server listening on the specified transport.
     ///
-    /// Binds to the specified transport (Unix, TCP, or Vsock) and serves
+    /// Binds to the specified transport (Unix, TCP, TLS, or Vsock) and serves
     /// all three gRPC services on a single port.
     ///
     /// If `notify_uri` is provided, connects to that URI after the server
@@ -79,7 +80,17 @@ impl GuestServer {
         // Wrap self in Arc for sharing across services
         let server = Arc::new(self);
 
-        let server_builder = Server::builder()
+        let mut server_builder = Server::builder();
+        if let BoxTransport::Tls { files, .. } = &transport {
+            let tls = boxlite_shared::tls::server_config(files)?;
+            server_builder = server_builder.tls_config(tls).map_err(|e| {
+                boxlite_shared::errors::BoxliteError::Config(format!(
+                    "Invalid TLS configuration: {}",
+                    e
+                ))
+            })?;
+        }
+        let server_builder = server_builder
             .add_service(boxlite_shared::ContainerServer::from_arc(server.clone()))
             .add_service(boxlite_shared::GuestServer::from_arc(server.clone()))
             .add_service(boxlite_shared::ExecutionServer::from_arc(server.clone()))
@@ -194,6 +205,38 @@ impl GuestServer {
                         ))
                     })?;
             }
+
+            BoxTransport::Tls { host, port, .. } => {
+                use tokio_stream::wrappers::TcpListenerStream;
+
+                let addr = format!("{}:{}", host, port);
+                info!("Binding to TLS address: {}", addr);
+                let listener = tokio::net::TcpListener::bind(&addr).await?;
+                info!("Listening on tcps://{}", addr);
+                eprintln!(
+                    "[guest] T+{}ms: server bound (tcps:{})",
+                    crate::boot_elapsed_ms(),
+                    port
+                );
+
+                let incoming = TcpListenerStream::new(listener);
+
+                tokio::spawn(async move {
+                    if let Err(e) = notify_host_ready(notify_uri).await {
+                        warn!("Failed to notify host: {}", e);
+                    }
+                });
+
+                server_builder
+                    .serve_with_incoming(incoming)
+                    .await
+                    .map_err(|e| {
+                        boxlite_shared::errors::BoxliteError::Internal(format!(
+                            "Server error: {}",
+                            e
+                        ))
+                    })?;
+            }
         }
 
         Ok(())
@@ -202,8 +245,75 @@ impl GuestServer {
 
 /// Notify host that guest is ready by connecting to the notify URI.
 ///
-/// The connection itself is the signal - no data needs to be sent.
+/// The connection itself is the signal - no data needs to be sent. Failed
+/// connects are retried with backoff within the URI's [`NotifyRetry`] bounds,
+/// since the host listener may not be up yet.
+/// The ready time is recorded for the boot timing reported in Guest.Init.
 async fn notify_host_ready(notify_uri: Option<String>) -> BoxliteResult<()> {
+    let result = connect_notify_uri(notify_uri).await;
+    if result.is_ok() {
+        crate::mark_agent_ready();
+    }
+    result
+}
+
+/// Retry bounds for the readiness notification, from the notify URI's
+/// query params (`retries`, `timeout_ms`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct NotifyRetry {
+    /// Connect attempts after the first one.
+    retries: u32,
+    /// Give up once this much time has passed since the first attempt.
+    timeout: Duration,
+}
+
+impl Default for NotifyRetry {
+    fn default() -> Self {
+        Self {
+            retries: 20,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+const NOTIFY_INITIAL_BACKOFF: Duration = Duration::from_millis(20);
+const NOTIFY_MAX_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Split `uri` into the transport URI and its retry params.
+fn parse_notify_uri(uri: &str) -> BoxliteResult<(BoxTransport, NotifyRetry)> {
+    let invalid = |e: String| {
+        boxlite_shared::errors::BoxliteError::Internal(format!(
+            "Invalid notify URI '{}': {}",
+            uri, e
+        ))
+    };
+
+    let (base, query) = uri.split_once('?').unwrap_or((uri, ""));
+    let mut retry = NotifyRetry::default();
+    for param in query.split('&').filter(|p| !p.is_empty()) {
+        let (key, value) = param.split_once('=').unwrap_or((param, ""));
+        match key {
+            "retries" => {
+                retry.retries = value
+                    .parse()
+                    .map_err(|e| invalid(format!("retries={}: {}", value, e)))?;
+            }
+            "timeout_ms" => {
+                retry.timeout = Duration::from_millis(
+                    value
+                        .parse()
+                        .map_err(|e| invalid(format!("timeout_ms={}: {}", value, e)))?,
+                );
+            }
+            _ => return Err(invalid(format!("unknown query param '{}'", key))),
+        }
+    }
+
+    let transport = BoxTransport::from_uri(base).map_err(invalid)?;
+    Ok((transport, retry))
+}
+
+async fn connect_notify_uri(notify_uri: Option<String>) -> BoxliteResult<()> {
     let uri = match notify_uri {
         Some(uri) => uri,
         None => {
@@ -212,19 +322,43 @@ async fn notify_host_ready(notify_uri: Option<String>) -> BoxliteResult<()> {
         }
     };
 
-    let transport = BoxTransport::from_uri(uri.as_str()).map_err(|e| {
-        boxlite_shared::errors::BoxliteError::Internal(format!(
-            "Invalid notify URI '{}': {}",
-            uri, e
-        ))
-    })?;
+    let (transport, retry) = parse_notify_uri(&uri)?;
+    let deadline = tokio::time::Instant::now() + retry.timeout;
+    let mut backoff = NOTIFY_INITIAL_BACKOFF;
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match connect_notify_transport(&transport).await {
+            Ok(()) => {
+                info!(attempt, "Host notified successfully");
+                return Ok(());
+            }
+            Err(e) if attempt > retry.retries || tokio::time::Instant::now() >= deadline => {
+                warn!(attempt, "Giving up notifying host: {}", e);
+                return Err(e);
+            }
+            Err(e) => {
+                warn!(
+                    attempt,
+                    retry_in_ms = backoff.as_millis() as u64,
+                    "Host notification failed: {}",
+                    e
+                );
+                let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                tokio::time::sleep(backoff.min(remaining)).await;
+                backoff = (backoff * 2).min(NOTIFY_MAX_BACKOFF);
+            }
+        }
+    }
+}
 
+async fn connect_notify_transport(transport: &BoxTransport) -> BoxliteResult<()> {
     match transport {
         BoxTransport::Vsock { port } => {
             use tokio_vsock::{VsockAddr, VsockStream, VMADDR_CID_HOST};
 
             info!("Notifying host via vsock:{}", port);
-            let addr = VsockAddr::new(VMADDR_CID_HOST, port);
+            let addr = VsockAddr::new(VMADDR_CID_HOST, *port);
             let _stream = VsockStream::connect(addr).await.map_err(|e| {
                 boxlite_shared::errors::BoxliteError::Internal(format!(
                     "Failed to connect to notify vsock: {}",
@@ -236,12 +370,11 @@ async fn notify_host_ready(notify_uri: Option<String>) -> BoxliteResult<()> {
                 crate::boot_elapsed_ms(),
                 port
             );
-            info!("Host notified successfully");
             // Connection itself signals readiness, drop immediately
         }
         BoxTransport::Unix { socket_path } => {
             info!("Notifying host via unix:{}", socket_path.display());
-            let _stream = tokio::net::UnixStream::connect(&socket_path)
+            let _stream = tokio::net::UnixStream::connect(socket_path)
                 .await
                 .map_err(|e| {
                     boxlite_shared::errors::BoxliteError::Internal(format!(
@@ -253,7 +386,6 @@ async fn notify_host_ready(notify_uri: Option<String>) -> BoxliteResult<()> {
                 "[guest] T+{}ms: host notified (unix)",
                 crate::boot_elapsed_ms()
             );
-            info!("Host notified successfully");
         }
         BoxTransport::Tcp { port } => {
             info!("Notifying host via tcp:127.0.0.1:{}", port);
@@ -270,9 +402,161 @@ async fn notify_host_ready(notify_uri: Option<String>) -> BoxliteResult<()> {
                 crate::boot_elapsed_ms(),
                 port
             );
-            info!("Host notified successfully");
+        }
+        BoxTransport::Tls { host, port, .. } => {
+            // The connection itself is the signal; no handshake is needed.
+            info!("Notifying host via tcp:{}:{}", host, port);
+            let _stream = tokio::net::TcpStream::connect(format!("{}:{}", host, port))
+                .await
+                .map_err(|e| {
+                    boxlite_shared::errors::BoxliteError::Internal(format!(
+                        "Failed to connect to notify tcp: {}",
+                        e
+                    ))
+                })?;
+            eprintln!(
+                "[guest] T+{}ms: host notified (tcps:{})",
+                crate::boot_elapsed_ms(),
+                port
+            );
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use boxlite_shared::TlsFiles;
+
+    #[test]
+    fn test_parse_notify_uri_query_params() {
+        let (transport, retry) = parse_notify_uri("vsock://2696").unwrap();
+        assert_eq!(transport, BoxTransport::vsock(2696));
+        assert_eq!(retry, NotifyRetry::default());
+
+        let (transport, retry) = parse_notify_uri("vsock://2696?retries=5&timeout_ms=300").unwrap();
+        assert_eq!(transport, BoxTransport::vsock(2696));
+        assert_eq!(
+            retry,
+            NotifyRetry {
+                retries: 5,
+                timeout: Duration::from_millis(300),
+            }
+        );
+
+        assert!(parse_notify_uri("vsock://2696?retries=-1").is_err());
+        assert!(parse_notify_uri("vsock://2696?bogus=1").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_notify_waits_for_late_listener() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("ready.sock");
+
+        let listener_path = socket_path.clone();
+        let host = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let listener = tokio::net::UnixListener::bind(&listener_path).unwrap();
+            listener.accept().await.unwrap();
+        });
+
+        let uri = format!(
+            "unix://{}?retries=50&timeout_ms=5000",
+            socket_path.display()
+        );
+        connect_notify_uri(Some(uri)).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), host)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+
+    /// Write a CA plus `guest` and `host` leaf certificates for 127.0.0.1.
+    fn write_tls_files(dir: &std::path::Path) -> (TlsFiles, TlsFiles) {
+        use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+
+        let ca_key = KeyPair::generate().unwrap();
+        let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let ca = ca_params.self_signed(&ca_key).unwrap();
+        std::fs::write(dir.join("ca.pem"), ca.pem()).unwrap();
+
+        let leaf = |name: &str| {
+            let key = KeyPair::generate().unwrap();
+            let cert = CertificateParams::new(vec!["127.0.0.1".to_string()])
+                .unwrap()
+                .signed_by(&key, &ca, &ca_key)
+                .unwrap();
+            std::fs::write(dir.join(format!("{name}.pem")), cert.pem()).unwrap();
+            std::fs::write(dir.join(format!("{name}.key")), key.serialize_pem()).unwrap();
+            TlsFiles {
+                cert: dir.join(format!("{name}.pem")),
+                key: dir.join(format!("{name}.key")),
+                ca: dir.join("ca.pem"),
+            }
+        };
+        (leaf("guest"), leaf("host"))
+    }
+
+    async fn ping_over_tls(port: u16, files: &TlsFiles) -> BoxliteResult<String> {
+        let mut last_err = None;
+        for _ in 0..50 {
+            match boxlite_shared::tls::client_endpoint("127.0.0.1", port, files)?
+                .connect()
+                .await
+            {
+                Ok(channel) => {
+                    let response = boxlite_shared::GuestClient::new(channel)
+                        .ping(boxlite_shared::PingRequest {})
+                        .await?;
+                    return Ok(response.into_inner().version);
+                }
+                Err(e) => last_err = Some(e),
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        Err(last_err.unwrap().into())
+    }
+
+    #[tokio::test]
+    async fn test_tls_server_answers_ping() {
+        let dir = tempfile::tempdir().unwrap();
+        let (guest_files, host_files) = write_tls_files(dir.path());
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let server = GuestServer::new(GuestLayout::with_base(dir.path().join("guest")));
+        let uri = BoxTransport::tls("127.0.0.1", port, guest_files).to_uri();
+        let serving = tokio::spawn(server.run(uri, None));
+
+        let version = ping_over_tls(port, &host_files).await.unwrap();
+        assert_eq!(version, env!("CARGO_PKG_VERSION"));
+
+        // A client whose certificate the guest's CA did not sign is refused.
+        let other = tempfile::tempdir().unwrap();
+        let (_, mut stranger) = write_tls_files(other.path());
+        stranger.ca = host_files.ca.clone();
+        assert!(ping_over_tls(port, &stranger).await.is_err());
+
+        serving.abort();
+    }
+
+    #[tokio::test]
+    async fn test_notify_gives_up_after_retries() {
+        let dir = tempfile::tempdir().unwrap();
+        let uri = format!(
+            "unix://{}?retries=2&timeout_ms=5000",
+            dir.path().join("missing.sock").display()
+        );
+
+        let started = std::time::Instant::now();
+        assert!(connect_notify_uri(Some(uri)).await.is_err());
+        // Two backoffs (20ms + 40ms), far below the timeout.
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+}
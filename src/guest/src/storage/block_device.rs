@@ -22,24 +22,34 @@ impl BlockDeviceMount {
     /// * `filesystem` - Target filesystem type
     /// * `need_format` - If true, format device before mounting
     /// * `need_resize` - If true, resize filesystem after mounting to fill disk
+    /// * `read_only` - If true, mount with `MS_RDONLY`; writes fail with EROFS
     pub fn mount(
         device: &Path,
         mount_point: &Path,
         filesystem: Filesystem,
         need_format: bool,
         need_resize: bool,
+        read_only: bool,
     ) -> BoxliteResult<()> {
         let fs_name = filesystem_to_str(filesystem);
 
         tracing::info!(
-            "Mounting block device: {} → {} (filesystem={:?}, format={}, resize={})",
+            "Mounting block device: {} → {} (filesystem={:?}, format={}, resize={}, {})",
             device.display(),
             mount_point.display(),
             filesystem,
             need_format,
-            need_resize
+            need_resize,
+            if read_only { "ro" } else { "rw" }
         );
 
+        if read_only && (need_format || need_resize) {
+            return Err(BoxliteError::Storage(format!(
+                "Cannot format or resize read-only block device {}",
+                device.display()
+            )));
+        }
+
         // Check device exists
         if !device.exists() {
             return Err(BoxliteError::Storage(format!(
@@ -64,19 +74,11 @@ impl BlockDeviceMount {
             ))
         })?;
 
-        // PERF: Mount with noatime to reduce unnecessary disk writes.
-        // - MS_NOATIME: Don't update file access times (saves ~10-20ms on mount)
-        // - MS_NODIRATIME: Don't update directory access times
-        // These flags significantly reduce I/O overhead, especially for read-heavy
-        // workloads. Access time tracking is rarely needed in container contexts.
-        let mount_flags = MsFlags::MS_NOATIME | MsFlags::MS_NODIRATIME;
-
-        // Mount using nix
         mount(
             Some(device),
             mount_point,
             Some(fs_name),
-            mount_flags,
+            mount_flags(read_only),
             None::<&str>,
         )
         .map_err(|e| {
@@ -93,8 +95,11 @@ impl BlockDeviceMount {
             Self::resize_filesystem(device, filesystem)?;
         }
 
-        // Fix ownership if needed (fallback in case debugfs didn't run on host)
-        super::perms::OwnershipFixer::fix_if_needed(mount_point)?;
+        // Fix ownership if needed (fallback in case debugfs didn't run on host).
+        // A read-only mount can't be chowned; it is presented as-is.
+        if !read_only {
+            super::perms::OwnershipFixer::fix_if_needed(mount_point)?;
+        }
 
         tracing::info!(
             "Mounted block device: {} → {}",
@@ -214,6 +219,34 @@ impl BlockDeviceMount {
         Ok(())
     }
 
+    /// Check whether a device already carries an ext2/3/4 filesystem.
+    ///
+    /// Reads the superblock magic (0xEF53, 56 bytes into the superblock at
+    /// offset 1024). A blank or short device reports `false`.
+    pub fn has_ext4_superblock(device: &Path) -> BoxliteResult<bool> {
+        use std::io::{ErrorKind, Read, Seek, SeekFrom};
+
+        const EXT4_MAGIC_OFFSET: u64 = 1024 + 56;
+        const EXT4_MAGIC: u16 = 0xEF53;
+
+        let mut file = std::fs::File::open(device).map_err(|e| {
+            BoxliteError::Storage(format!("Failed to open {}: {}", device.display(), e))
+        })?;
+        let mut magic = [0u8; 2];
+        let read = file
+            .seek(SeekFrom::Start(EXT4_MAGIC_OFFSET))
+            .and_then(|_| file.read_exact(&mut magic));
+        match read {
+            Ok(()) => Ok(u16::from_le_bytes(magic) == EXT4_MAGIC),
+            Err(e) if e.kind() == ErrorKind::UnexpectedEof => Ok(false),
+            Err(e) => Err(BoxliteError::Storage(format!(
+                "Failed to read superblock from {}: {}",
+                device.display(),
+                e
+            ))),
+        }
+    }
+
     /// Format device with specified filesystem.
     fn format(device: &Path, filesystem: &str) -> BoxliteResult<()> {
         // Debug: log user info and device status
@@ -260,6 +293,20 @@ impl BlockDeviceMount {
     }
 }
 
+/// Mount flags for a block device.
+fn mount_flags(read_only: bool) -> MsFlags {
+    // PERF: Mount with noatime to reduce unnecessary disk writes.
+    // - MS_NOATIME: Don't update file access times (saves ~10-20ms on mount)
+    // - MS_NODIRATIME: Don't update directory access times
+    // These flags significantly reduce I/O overhead, especially for read-heavy
+    // workloads. Access time tracking is rarely needed in container contexts.
+    let mut flags = MsFlags::MS_NOATIME | MsFlags::MS_NODIRATIME;
+    if read_only {
+        flags |= MsFlags::MS_RDONLY;
+    }
+    flags
+}
+
 /// Convert Filesystem enum to string for mkfs command.
 fn filesystem_to_str(fs: Filesystem) -> &'static str {
     match fs {
@@ -285,3 +332,52 @@ fn human_readable_size(bytes: u64) -> String {
         format!("{:.2} {}", size, UNITS[unit_idx])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_has_ext4_superblock() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let blank = dir.path().join("blank.img");
+        std::fs::write(&blank, vec![0u8; 4096]).unwrap();
+        assert!(!BlockDeviceMount::has_ext4_superblock(&blank).unwrap());
+
+        let short = dir.path().join("short.img");
+        std::fs::write(&short, vec![0u8; 512]).unwrap();
+        assert!(!BlockDeviceMount::has_ext4_superblock(&short).unwrap());
+
+        let mut image = vec![0u8; 4096];
+        image[1080..1082].copy_from_slice(&0xEF53u16.to_le_bytes());
+        let ext4 = dir.path().join("ext4.img");
+        std::fs::write(&ext4, image).unwrap();
+        assert!(BlockDeviceMount::has_ext4_superblock(&ext4).unwrap());
+    }
+
+    #[test]
+    fn test_mount_flags_read_only() {
+        assert!(!mount_flags(false).contains(MsFlags::MS_RDONLY));
+        assert!(mount_flags(true).contains(MsFlags::MS_RDONLY));
+        assert!(mount_flags(true).contains(MsFlags::MS_NOATIME));
+    }
+
+    #[test]
+    fn test_read_only_rejects_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("disk.img");
+        std::fs::write(&image, vec![0u8; 4096]).unwrap();
+
+        let err = BlockDeviceMount::mount(
+            &image,
+            &dir.path().join("mnt"),
+            Filesystem::Ext4,
+            true,
+            false,
+            true,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("read-only"), "{err}");
+    }
+}
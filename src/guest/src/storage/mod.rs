@@ -3,6 +3,7 @@
 //! Provides unified abstraction for mounting different volume types:
 //! - Virtiofs: Host-shared directories via virtio-fs
 //! - Block devices: Disk images attached via virtio-blk
+//! - Tmpfs: In-memory scratch space
 
 pub mod block_device;
 #[allow(dead_code)]
@@ -10,6 +11,8 @@ mod copy;
 pub mod fsfreeze;
 pub mod idmap;
 mod perms;
+mod tmpfs;
+pub mod usage;
 mod virtiofs;
 mod volume;
 
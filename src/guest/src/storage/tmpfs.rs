@@ -0,0 +1,57 @@
+//! Tmpfs mount helper.
+
+use std::path::Path;
+
+use boxlite_shared::errors::{BoxliteError, BoxliteResult};
+use nix::mount::{mount, MsFlags};
+
+pub struct TmpfsMount;
+
+impl TmpfsMount {
+    /// Mount a tmpfs limited to `size_mib` at mount point.
+    pub fn mount(mount_point: &Path, size_mib: u64, mode: u32) -> BoxliteResult<()> {
+        let options = mount_options(size_mib, mode);
+        tracing::info!("Mounting tmpfs at {} ({})", mount_point.display(), options);
+
+        std::fs::create_dir_all(mount_point).map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to create mount point {}: {}",
+                mount_point.display(),
+                e
+            ))
+        })?;
+
+        mount(
+            Some("tmpfs"),
+            mount_point,
+            Some("tmpfs"),
+            MsFlags::MS_NOSUID | MsFlags::MS_NODEV,
+            Some(options.as_str()),
+        )
+        .map_err(|e| {
+            BoxliteError::Storage(format!(
+                "Failed to mount tmpfs at {}: {}",
+                mount_point.display(),
+                e
+            ))
+        })?;
+
+        tracing::info!("Mounted tmpfs at {}", mount_point.display());
+        Ok(())
+    }
+}
+
+fn mount_options(size_mib: u64, mode: u32) -> String {
+    format!("size={}m,mode={:o}", size_mib, mode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mount_options() {
+        assert_eq!(mount_options(16, 0o1777), "size=16m,mode=1777");
+        assert_eq!(mount_options(512, 0o700), "size=512m,mode=700");
+    }
+}
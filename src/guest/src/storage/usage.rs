@@ -0,0 +1,51 @@
+//! Filesystem usage via `statvfs`.
+//!
+//! Reports on whatever backs the path: the ext4 image on a virtio-blk disk
+//! for block-device rootfs, the host share for virtiofs (the FUSE server
+//! answers `statfs` from the host filesystem), and the upper filesystem for
+//! an overlay mount.
+
+use std::path::Path;
+
+use nix::sys::statvfs::statvfs;
+
+/// Capacity and usage of one filesystem, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FsUsage {
+    pub total: u64,
+    pub used: u64,
+    /// Free space available to unprivileged writers (excludes root reserve).
+    pub available: u64,
+}
+
+/// Measure the filesystem containing `path`.
+pub fn fs_usage(path: &Path) -> nix::Result<FsUsage> {
+    let st = statvfs(path)?;
+    let frag = st.fragment_size() as u64;
+    let total = st.blocks() as u64 * frag;
+    let free = st.blocks_free() as u64 * frag;
+    Ok(FsUsage {
+        total,
+        used: total.saturating_sub(free),
+        available: st.blocks_available() as u64 * frag,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_of_tempdir_is_consistent() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let usage = fs_usage(dir.path()).unwrap();
+        assert!(usage.total > 0);
+        assert!(usage.used <= usage.total);
+        assert!(usage.available <= usage.total);
+    }
+
+    #[test]
+    fn missing_path_is_an_error() {
+        assert!(fs_usage(Path::new("/nonexistent/boxlite-usage")).is_err());
+    }
+}
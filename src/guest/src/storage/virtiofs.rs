@@ -26,16 +26,11 @@ impl VirtiofsMount {
             ))
         })?;
 
-        let mut flags = MsFlags::empty();
-        if read_only {
-            flags |= MsFlags::MS_RDONLY;
-        }
-
         mount(
             Some(tag),
             mount_point,
             Some("virtiofs"),
-            flags,
+            mount_flags(read_only),
             None::<&str>,
         )
         .map_err(|e| {
@@ -56,3 +51,26 @@ impl VirtiofsMount {
         Ok(())
     }
 }
+
+/// Mount flags for a virtiofs share.
+///
+/// The host also refuses writes to a read-only share, but mounting with
+/// `MS_RDONLY` makes the guest kernel fail them with EROFS up front.
+fn mount_flags(read_only: bool) -> MsFlags {
+    if read_only {
+        MsFlags::MS_RDONLY
+    } else {
+        MsFlags::empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mount_flags() {
+        assert_eq!(mount_flags(true), MsFlags::MS_RDONLY);
+        assert!(mount_flags(false).is_empty());
+    }
+}
@@ -10,6 +10,7 @@ use boxlite_shared::layout::GUEST_BASE;
 use boxlite_shared::{volume, Filesystem, Volume};
 
 use super::block_device::BlockDeviceMount;
+use super::tmpfs::TmpfsMount;
 use super::virtiofs::VirtiofsMount;
 
 /// Resolve mount point from tag when mount_point is empty.
@@ -76,8 +77,13 @@ pub fn mount_volume(vol: &Volume) -> BoxliteResult<()> {
                 filesystem,
                 block.need_format,
                 block.need_resize,
+                block.read_only,
             )
         }
+        Some(volume::Source::Tmpfs(tmpfs)) => {
+            let mount_point = resolve_mount_point(&tmpfs.name, &vol.mount_point, &vol.container_id);
+            TmpfsMount::mount(&mount_point, tmpfs.size_mib, tmpfs.mode)
+        }
         None => {
             tracing::warn!("Volume {} has no source, skipping", vol.mount_point);
             Ok(())
@@ -14,6 +14,57 @@ pub mod container {
     pub const RLIMIT_NOFILE_HARD: u64 = 1024;
 }
 
+/// Linux capability names accepted in `cap_add` / `cap_drop`
+pub mod capabilities {
+    /// Keyword that stands for every capability in [`ALL`]
+    pub const ALL_KEYWORD: &str = "ALL";
+
+    /// Every capability the guest kernel knows, in `CAP_*` form
+    pub const ALL: &[&str] = &[
+        "CAP_CHOWN",
+        "CAP_DAC_OVERRIDE",
+        "CAP_DAC_READ_SEARCH",
+        "CAP_FOWNER",
+        "CAP_FSETID",
+        "CAP_KILL",
+        "CAP_SETGID",
+        "CAP_SETUID",
+        "CAP_SETPCAP",
+        "CAP_LINUX_IMMUTABLE",
+        "CAP_NET_BIND_SERVICE",
+        "CAP_NET_BROADCAST",
+        "CAP_NET_ADMIN",
+        "CAP_NET_RAW",
+        "CAP_IPC_LOCK",
+        "CAP_IPC_OWNER",
+        "CAP_SYS_MODULE",
+        "CAP_SYS_RAWIO",
+        "CAP_SYS_CHROOT",
+        "CAP_SYS_PTRACE",
+        "CAP_SYS_PACCT",
+        "CAP_SYS_ADMIN",
+        "CAP_SYS_BOOT",
+        "CAP_SYS_NICE",
+        "CAP_SYS_RESOURCE",
+        "CAP_SYS_TIME",
+        "CAP_SYS_TTY_CONFIG",
+        "CAP_MKNOD",
+        "CAP_LEASE",
+        "CAP_AUDIT_WRITE",
+        "CAP_AUDIT_CONTROL",
+        "CAP_SETFCAP",
+        "CAP_MAC_OVERRIDE",
+        "CAP_MAC_ADMIN",
+        "CAP_SYSLOG",
+        "CAP_WAKE_ALARM",
+        "CAP_BLOCK_SUSPEND",
+        "CAP_AUDIT_READ",
+        "CAP_PERFMON",
+        "CAP_BPF",
+        "CAP_CHECKPOINT_RESTORE",
+    ];
+}
+
 /// Network constants
 pub mod network {
     /// Default vsock port for guest agent gRPC server
@@ -24,6 +75,14 @@ pub mod network {
     /// Guest connects to this port to signal it's ready to serve
     /// Port 2696 = "BOXM" on phone keypad
     pub const GUEST_READY_PORT: u32 = 2696;
+
+    /// Lowest vsock port applications may register (`BoxOptions::vsock_ports`).
+    /// Ports below it are reserved for BoxLite's own channels (the agent and
+    /// ready ports above, and future ones).
+    pub const USER_VSOCK_PORT_MIN: u32 = 4096;
+
+    /// `VMADDR_PORT_ANY`; never a valid port to register.
+    pub const VSOCK_PORT_ANY: u32 = u32::MAX;
 }
 
 /// Executor environment variable
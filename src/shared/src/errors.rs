@@ -82,6 +82,11 @@ pub enum BoxliteError {
     /// a new exec instead.
     #[error("session reaped: {0}")]
     SessionReaped(String),
+
+    /// An operation ran past its deadline: an exec's timeout, the box boot
+    /// timeout, or an RPC deadline.
+    #[error("timed out: {0}")]
+    Timeout(String),
 }
 
 // Implement From for common error types to enable `?` operator
@@ -111,7 +116,10 @@ impl From<&str> for BoxliteError {
 
 impl From<tonic::Status> for BoxliteError {
     fn from(err: tonic::Status) -> Self {
-        BoxliteError::Rpc(err.to_string())
+        match err.code() {
+            tonic::Code::DeadlineExceeded => BoxliteError::Timeout(err.to_string()),
+            _ => BoxliteError::Rpc(err.to_string()),
+        }
     }
 }
 
@@ -148,6 +156,8 @@ impl From<tonic::transport::Error> for BoxliteError {
 /// - `Network`, `Portal`, `Rpc`, `RpcTransport`, `Engine` are 503
 ///   because they signal an internal dep is unavailable, not that the
 ///   server itself failed (gRPC `UNAVAILABLE`).
+/// - `Timeout` is 504: the request was accepted but did not finish in
+///   time (gRPC `DEADLINE_EXCEEDED`).
 /// - `Storage`, `Database`, `MetadataError`, `Config`, `Internal` are
 ///   500 because they indicate a server-side bug or data-plane
 ///   corruption — not a recoverable condition.
@@ -173,6 +183,7 @@ impl BoxliteError {
                 (503, "UpstreamUnavailableError", "upstream_unavailable")
             }
             BoxliteError::Engine(_) => (503, "EngineError", "engine_unavailable"),
+            BoxliteError::Timeout(_) => (504, "TimeoutError", "timeout"),
             BoxliteError::Storage(_) => (500, "StorageError", "storage_error"),
             BoxliteError::Database(_) => (500, "DatabaseError", "database_error"),
             BoxliteError::MetadataError(_) => (500, "MetadataError", "metadata_error"),
@@ -293,6 +304,12 @@ mod tests {
                 "EngineError",
                 "engine_unavailable",
             ),
+            (
+                BoxliteError::Timeout("exec exceeded 30s".into()),
+                504,
+                "TimeoutError",
+                "timeout",
+            ),
             (
                 BoxliteError::Storage("qcow2 corrupt".into()),
                 500,
@@ -362,6 +379,7 @@ mod tests {
             BoxliteError::Rpc(String::new()),
             BoxliteError::RpcTransport(String::new()),
             BoxliteError::Engine(String::new()),
+            BoxliteError::Timeout(String::new()),
             BoxliteError::Storage(String::new()),
             BoxliteError::Database(String::new()),
             BoxliteError::MetadataError(String::new()),
@@ -389,4 +407,12 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn deadline_exceeded_status_maps_to_timeout() {
+        let err = BoxliteError::from(tonic::Status::deadline_exceeded("exec wait"));
+        assert!(matches!(err, BoxliteError::Timeout(_)), "{err:?}");
+
+        let err = BoxliteError::from(tonic::Status::unavailable("agent gone"));
+        assert!(matches!(err, BoxliteError::Rpc(_)), "{err:?}");
+    }
 }
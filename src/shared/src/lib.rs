@@ -7,6 +7,7 @@ pub mod constants;
 pub mod errors;
 pub mod layout;
 pub mod tar;
+pub mod tls;
 pub mod transport;
 
 // Generated protobuf types
@@ -16,7 +17,7 @@ pub mod generated {
 }
 
 pub use errors::{BoxliteError, BoxliteResult};
-pub use transport::BoxTransport;
+pub use transport::{BoxTransport, TlsFiles};
 
 // Container service
 pub use generated::container_client::ContainerClient;
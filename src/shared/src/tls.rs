@@ -0,0 +1,42 @@
+//! Mutual TLS for the `tcps://` transport.
+//!
+//! Builds tonic's rustls configuration from a [`TlsFiles`] so the guest
+//! server and host client load certificates the same way.
+
+use std::path::Path;
+
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity, ServerTlsConfig};
+
+use crate::errors::{BoxliteError, BoxliteResult};
+use crate::transport::TlsFiles;
+
+/// Server config presenting `files.cert` and requiring client certificates
+/// signed by `files.ca`.
+pub fn server_config(files: &TlsFiles) -> BoxliteResult<ServerTlsConfig> {
+    Ok(ServerTlsConfig::new()
+        .identity(identity(files)?)
+        .client_ca_root(Certificate::from_pem(read_pem(&files.ca)?)))
+}
+
+/// Endpoint for `https://host:port` that verifies the server against
+/// `files.ca` and presents `files.cert` as the client certificate.
+pub fn client_endpoint(host: &str, port: u16, files: &TlsFiles) -> BoxliteResult<Endpoint> {
+    let tls = ClientTlsConfig::new()
+        .domain_name(host)
+        .ca_certificate(Certificate::from_pem(read_pem(&files.ca)?))
+        .identity(identity(files)?);
+
+    Ok(Endpoint::from_shared(format!("https://{}:{}", host, port))?.tls_config(tls)?)
+}
+
+fn identity(files: &TlsFiles) -> BoxliteResult<Identity> {
+    Ok(Identity::from_pem(
+        read_pem(&files.cert)?,
+        read_pem(&files.key)?,
+    ))
+}
+
+fn read_pem(path: &Path) -> BoxliteResult<Vec<u8>> {
+    std::fs::read(path)
+        .map_err(|e| BoxliteError::Config(format!("failed to read {}: {}", path.display(), e)))
+}
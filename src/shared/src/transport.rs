@@ -16,6 +16,27 @@ pub enum BoxTransport {
 
     /// Vsock transport (guest-specific)
     Vsock { port: u32 },
+
+    /// TCP transport secured with mutual TLS
+    Tls {
+        host: String,
+        port: u16,
+        files: TlsFiles,
+    },
+}
+
+/// PEM files for a [`BoxTransport::Tls`] endpoint.
+///
+/// Both sides present `cert`/`key` and verify the peer against `ca`, so the
+/// guest only serves hosts holding a certificate from the same CA.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TlsFiles {
+    /// Certificate chain presented to the peer.
+    pub cert: PathBuf,
+    /// Private key for `cert`.
+    pub key: PathBuf,
+    /// CA certificate the peer's certificate must chain to.
+    pub ca: PathBuf,
 }
 
 impl BoxTransport {
@@ -34,12 +55,29 @@ impl BoxTransport {
         Self::Vsock { port }
     }
 
+    /// Create a TLS transport.
+    pub fn tls(host: impl Into<String>, port: u16, files: TlsFiles) -> Self {
+        Self::Tls {
+            host: host.into(),
+            port,
+            files,
+        }
+    }
+
     /// Get the URI representation of this transport.
     pub fn to_uri(&self) -> String {
         match self {
             BoxTransport::Tcp { port } => format!("tcp://127.0.0.1:{}", port),
             BoxTransport::Unix { socket_path } => format!("unix://{}", socket_path.display()),
             BoxTransport::Vsock { port } => format!("vsock://{}", port),
+            BoxTransport::Tls { host, port, files } => format!(
+                "tcps://{}:{}?cert={}&key={}&ca={}",
+                host,
+                port,
+                files.cert.display(),
+                files.key.display(),
+                files.ca.display()
+            ),
         }
     }
 
@@ -60,13 +98,63 @@ impl BoxTransport {
                 .parse::<u32>()
                 .map_err(|e| format!("invalid vsock port in '{}': {}", uri, e))?;
             Ok(Self::vsock(port))
+        } else if let Some(rest) = uri.strip_prefix("tcps://") {
+            Self::parse_tls(uri, rest)
         } else {
             Err(format!(
-                "invalid transport URI '{}': expected tcp://, unix://, or vsock://",
+                "invalid transport URI '{}': expected tcp://, tcps://, unix://, or vsock://",
                 uri
             ))
         }
     }
+
+    /// Parse `host:port?cert=PATH&key=PATH&ca=PATH`.
+    fn parse_tls(uri: &str, rest: &str) -> Result<Self, String> {
+        let (addr, query) = rest
+            .split_once('?')
+            .ok_or_else(|| format!("invalid TLS URI '{}': missing cert, key and ca", uri))?;
+        let (host, port) = addr
+            .rsplit_once(':')
+            .ok_or_else(|| format!("invalid TLS URI '{}': missing port", uri))?;
+        if host.is_empty() {
+            return Err(format!("invalid TLS URI '{}': missing host", uri));
+        }
+        let port = port
+            .parse::<u16>()
+            .map_err(|e| format!("invalid TLS port in '{}': {}", uri, e))?;
+
+        let (mut cert, mut key, mut ca) = (None, None, None);
+        for pair in query.split('&') {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("invalid TLS URI '{}': bad parameter '{}'", uri, pair))?;
+            let slot = match name {
+                "cert" => &mut cert,
+                "key" => &mut key,
+                "ca" => &mut ca,
+                _ => {
+                    return Err(format!(
+                        "invalid TLS URI '{}': unknown parameter '{}'",
+                        uri, name
+                    ))
+                }
+            };
+            *slot = Some(PathBuf::from(value));
+        }
+        let require = |path: Option<PathBuf>, name: &str| {
+            path.ok_or_else(|| format!("invalid TLS URI '{}': missing {}", uri, name))
+        };
+
+        Ok(Self::tls(
+            host,
+            port,
+            TlsFiles {
+                cert: require(cert, "cert")?,
+                key: require(key, "key")?,
+                ca: require(ca, "ca")?,
+            },
+        ))
+    }
 }
 
 impl std::fmt::Display for BoxTransport {
@@ -88,12 +176,25 @@ mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    fn tls_transport() -> BoxTransport {
+        BoxTransport::tls(
+            "10.0.0.2",
+            8443,
+            TlsFiles {
+                cert: PathBuf::from("/etc/boxlite/guest.pem"),
+                key: PathBuf::from("/etc/boxlite/guest.key"),
+                ca: PathBuf::from("/etc/boxlite/ca.pem"),
+            },
+        )
+    }
+
     #[test]
     fn to_uri_from_uri_roundtrips_every_variant() {
         for t in [
             BoxTransport::tcp(8080),
             BoxTransport::unix(PathBuf::from("/tmp/box/net.sock")),
             BoxTransport::vsock(1024),
+            tls_transport(),
         ] {
             let uri = t.to_uri();
             assert_eq!(
@@ -112,6 +213,10 @@ mod tests {
             "unix:///a/b.sock"
         );
         assert_eq!(BoxTransport::vsock(42).to_uri(), "vsock://42");
+        assert_eq!(
+            tls_transport().to_uri(),
+            "tcps://10.0.0.2:8443?cert=/etc/boxlite/guest.pem&key=/etc/boxlite/guest.key&ca=/etc/boxlite/ca.pem"
+        );
     }
 
     #[test]
@@ -123,6 +228,26 @@ mod tests {
         assert!(BoxTransport::from_uri("vsock://nope").is_err()); // non-numeric vsock
     }
 
+    #[test]
+    fn tls_from_uri_requires_every_file() {
+        assert!(BoxTransport::from_uri("tcps://h:443").is_err()); // no files
+        assert!(BoxTransport::from_uri("tcps://h:443?cert=/c&key=/k").is_err()); // no ca
+        assert!(BoxTransport::from_uri("tcps://:443?cert=/c&key=/k&ca=/a").is_err()); // no host
+        assert!(BoxTransport::from_uri("tcps://h:443?cert=/c&key=/k&ca=/a&x=1").is_err());
+        assert_eq!(
+            BoxTransport::from_uri("tcps://h:443?ca=/a&key=/k&cert=/c").unwrap(),
+            BoxTransport::tls(
+                "h",
+                443,
+                TlsFiles {
+                    cert: PathBuf::from("/c"),
+                    key: PathBuf::from("/k"),
+                    ca: PathBuf::from("/a"),
+                },
+            )
+        );
+    }
+
     #[test]
     fn display_and_fromstr_delegate_to_uri_helpers() {
         let t = BoxTransport::unix(PathBuf::from("/tmp/s.sock"));
@@ -139,6 +264,7 @@ mod tests {
             BoxTransport::tcp(8080),
             BoxTransport::unix(PathBuf::from("/tmp/box/net.sock")),
             BoxTransport::vsock(1024),
+            tls_transport(),
         ] {
             let json = serde_json::to_string(&t).unwrap();
             assert_eq!(serde_json::from_str::<BoxTransport>(&json).unwrap(), t);
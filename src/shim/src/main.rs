@@ -21,7 +21,10 @@ use std::time::{Duration, Instant};
 
 use boxlite::{
     util,
-    vmm::{self, ExitInfo, InstanceSpec, VmmConfig, controller::watchdog},
+    vmm::{
+        self, ExitInfo, InstanceSpec, VmmConfig,
+        controller::{control, watchdog},
+    },
 };
 use boxlite_shared::errors::{BoxliteError, BoxliteResult};
 use crash_capture::CrashCapture;
@@ -31,7 +34,8 @@ use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 use boxlite::net::gvproxy::GvproxyInstance;
 
 // No CLI args — all config (including engine type) is read from stdin pipe.
-// This avoids /proc/<pid>/cmdline exposure of secrets and CA keys.
+// This avoids /proc/<pid>/cmdline exposure of secrets and CA keys. The one
+// exception is the control helper, which only gets a socket path and a PID.
 
 /// Initialize tracing with file logging.
 ///
@@ -64,6 +68,11 @@ fn main() -> BoxliteResult<()> {
     let t0 = Instant::now();
     let timing = |msg: &str| eprintln!("[shim] T+{}ms: {msg}", t0.elapsed().as_millis());
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some(control::CONTROL_HELPER_ARG) {
+        return control::run_helper(&args[1..]);
+    }
+
     let wall = chrono::Utc::now().format("%H:%M:%S%.6f");
     eprintln!("[shim] {wall} T+0ms: main() entered");
 
@@ -132,6 +141,13 @@ fn run_shim(mut config: InstanceSpec, timing: impl Fn(&str)) -> BoxliteResult<()
         "Guest entrypoint configured"
     );
 
+    // Keep only the tail of the console: the engine writes to a pipe and a
+    // relay thread mirrors the last lines to the console file.
+    if let (Some(path), Some(lines)) = (config.console_output.clone(), config.console_ring_lines) {
+        config.console_output = Some(vmm::console::spawn_ring(&path, lines)?);
+        timing("console ring started");
+    }
+
     // =========================================================================
     // Network backend (gvproxy) + Seccomp
     // =========================================================================
@@ -149,6 +165,13 @@ fn run_shim(mut config: InstanceSpec, timing: impl Fn(&str)) -> BoxliteResult<()
         Box::leak(Box::new(gvproxy));
     }
 
+    // Start the control socket helper while fork/exec are still allowed:
+    // the seccomp filters below may forbid them.
+    if let Some(ref socket) = config.control_socket {
+        control::spawn_helper(socket);
+        timing("control helper spawned");
+    }
+
     // Apply VMM seccomp filter with TSYNC (covers all threads including gvproxy)
     #[cfg(target_os = "linux")]
     {
@@ -187,9 +210,16 @@ fn run_shim(mut config: InstanceSpec, timing: impl Fn(&str)) -> BoxliteResult<()
         }
     }
 
-    // Save detach/transport before config is moved into engine.create()
+    // Save detach/idle/transport before config is moved into engine.create()
     let detach = config.detach;
+    let idle_timeout = config.idle_timeout;
     let transport = config.transport.clone();
+    #[cfg(target_os = "linux")]
+    let seccomp_profile = config
+        .security
+        .seccomp_profile
+        .clone()
+        .filter(|_| config.security.jailer_enabled);
 
     // Initialize engine options with defaults
     let options = VmmConfig::default();
@@ -215,7 +245,7 @@ fn run_shim(mut config: InstanceSpec, timing: impl Fn(&str)) -> BoxliteResult<()
 
     // Install SIGTERM handler for graceful shutdown (all boxes, detached or not).
     // When SIGTERM is received: Guest.Shutdown() RPC (flush qcow2) → re-raise SIGTERM.
-    install_graceful_shutdown_handler(transport);
+    install_graceful_shutdown_handler(transport.clone());
 
     // Start parent watchdog if detach=false.
     // The parent holds the write end of a pipe (fd 3 in this process).
@@ -228,6 +258,29 @@ fn run_shim(mut config: InstanceSpec, timing: impl Fn(&str)) -> BoxliteResult<()
         tracing::info!("Running in detached mode (detach=true)");
     }
 
+    // Start idle watchdog if the box has an idle timeout. It polls the guest
+    // and, once the guest claims idle, goes through the same SIGTERM path.
+    if let Some(idle_timeout) = idle_timeout {
+        start_idle_watchdog(transport, idle_timeout);
+        tracing::info!(
+            idle_timeout_secs = idle_timeout.as_secs(),
+            "Idle watchdog started"
+        );
+    }
+
+    // Custom seccomp profile goes last: every thread above (signal handler,
+    // watchdog) is already running, and TSYNC covers them all. Log before
+    // installing — the profile may not allow the writes logging needs.
+    #[cfg(target_os = "linux")]
+    if let Some(profile) = seccomp_profile {
+        tracing::info!(
+            allowed = profile.allowed_syscalls.len(),
+            default_action = ?profile.default_action,
+            "Installing custom seccomp profile"
+        );
+        profile.install()?;
+    }
+
     // Hand over process control to Box instance
     // This may never return (process takeover)
     timing("entering VM (krun_start_enter)");
@@ -290,7 +343,7 @@ fn install_graceful_shutdown_handler(transport: boxlite_shared::BoxTransport) {
                     tokio::time::timeout(Duration::from_secs(GUEST_SHUTDOWN_TIMEOUT_SECS), async {
                         match session.guest().await {
                             Ok(mut guest) => {
-                                let _ = guest.shutdown().await;
+                                let _ = guest.shutdown(None).await;
                             }
                             Err(e) => {
                                 tracing::debug!("Could not connect to guest for shutdown: {e}");
@@ -349,23 +402,78 @@ fn start_parent_watchdog() {
             );
         }
 
-        // SIGTERM triggers the graceful shutdown handler
-        let self_pid = std::process::id();
-        unsafe {
-            libc::kill(self_pid as i32, libc::SIGTERM);
-        }
+        terminate_self();
+    });
+}
 
-        // Safety net: wait for handler to complete, then force kill
-        thread::sleep(Duration::from_secs(
-            GUEST_SHUTDOWN_TIMEOUT_SECS + GRACEFUL_SHUTDOWN_TIMEOUT_SECS,
-        ));
+/// Longest gap between idle checks; shorter timeouts poll at their own period.
+const IDLE_POLL_INTERVAL_SECS: u64 = 5;
 
-        tracing::warn!("Graceful shutdown timed out, forcing exit with SIGKILL");
-        unsafe {
-            libc::kill(self_pid as i32, libc::SIGKILL);
-        }
+/// Start a watchdog thread that shuts the box down once it has been idle.
+///
+/// Polls the guest's ClaimIdle RPC. The guest decides idleness itself (no
+/// execution in flight or running, none completed within `idle_timeout`) and,
+/// when it claims, refuses new executions — so an exec arriving mid-shutdown
+/// fails cleanly instead of racing it. Then shuts down like the parent
+/// watchdog: SIGTERM to self, with a SIGKILL safety net.
+fn start_idle_watchdog(transport: boxlite_shared::BoxTransport, idle_timeout: Duration) {
+    thread::spawn(move || {
+        let rt = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(rt) => rt,
+            Err(e) => {
+                tracing::warn!("Failed to build tokio runtime for idle watchdog: {e}");
+                return;
+            }
+        };
+        let poll = idle_timeout.min(Duration::from_secs(IDLE_POLL_INTERVAL_SECS));
+        let session = boxlite::GuestSession::new(transport);
+
+        rt.block_on(async {
+            loop {
+                tokio::time::sleep(poll).await;
+                // The guest may not be up yet; keep polling until it answers.
+                let claimed = match session.guest().await {
+                    Ok(mut guest) => guest.claim_idle(idle_timeout).await,
+                    Err(e) => Err(e),
+                };
+                match claimed {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(e) => tracing::debug!("Idle check failed: {e}"),
+                }
+            }
+        });
 
-        // Fallback: if SIGKILL somehow didn't work, exit forcefully
-        std::process::exit(137); // 128 + 9 (SIGKILL)
+        tracing::info!(
+            idle_timeout_secs = idle_timeout.as_secs(),
+            "Box idle, initiating shutdown"
+        );
+        terminate_self();
     });
 }
+
+/// Send SIGTERM to self so the graceful shutdown handler runs, then SIGKILL
+/// if it hasn't finished in time. Never returns.
+fn terminate_self() -> ! {
+    // SIGTERM triggers the graceful shutdown handler
+    let self_pid = std::process::id();
+    unsafe {
+        libc::kill(self_pid as i32, libc::SIGTERM);
+    }
+
+    // Safety net: wait for handler to complete, then force kill
+    thread::sleep(Duration::from_secs(
+        GUEST_SHUTDOWN_TIMEOUT_SECS + GRACEFUL_SHUTDOWN_TIMEOUT_SECS,
+    ));
+
+    tracing::warn!("Graceful shutdown timed out, forcing exit with SIGKILL");
+    unsafe {
+        libc::kill(self_pid as i32, libc::SIGKILL);
+    }
+
+    // Fallback: if SIGKILL somehow didn't work, exit forcefully
+    std::process::exit(137); // 128 + 9 (SIGKILL)
+}
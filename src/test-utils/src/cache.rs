@@ -201,6 +201,14 @@ impl SharedResources {
                 let runtime = BoxliteRuntime::new(BoxliteOptions {
                     home_dir: home.clone(),
                     image_registries: test_registries(),
+                    image_store_max_bytes: None,
+                    memory_overcommit: None,
+                    pull_retry: None,
+                    dedup_layers: false,
+                    image_config_cache_capacity: None,
+                    max_concurrent_boots: None,
+                    image_blob_store: None,
+                    name_generator: None,
                 })
                 .unwrap();
 
@@ -267,6 +267,14 @@ where
         let runtime = boxlite::BoxliteRuntime::new(BoxliteOptions {
             home_dir: home.path.clone(),
             image_registries: crate::test_registries(),
+            image_store_max_bytes: None,
+            memory_overcommit: None,
+            pull_retry: None,
+            dedup_layers: false,
+            image_config_cache_capacity: None,
+            max_concurrent_boots: None,
+            image_blob_store: None,
+            name_generator: None,
         })
         .expect("create runtime for config matrix");
 
@@ -336,6 +344,14 @@ macro_rules! config_matrix_tests {
                         ::boxlite::runtime::options::BoxliteOptions {
                             home_dir: home.path.clone(),
                             image_registries: $crate::test_registries(),
+                            image_store_max_bytes: None,
+                            memory_overcommit: None,
+                            pull_retry: None,
+                            dedup_layers: false,
+                            image_config_cache_capacity: None,
+                            max_concurrent_boots: None,
+                            image_blob_store: None,
+                            name_generator: None,
                         }
                     ).expect("create runtime for config matrix test");
 